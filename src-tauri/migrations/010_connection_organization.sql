@@ -0,0 +1,27 @@
+-- Nested folders for saved connections
+CREATE TABLE IF NOT EXISTS connection_groups (
+    id INTEGER PRIMARY KEY AUTOINCREMENT,
+    parent_id INTEGER,
+    name TEXT NOT NULL COLLATE NOCASE,
+    sort_order INTEGER NOT NULL DEFAULT 0,
+    created_at TEXT NOT NULL DEFAULT (datetime('now')),
+    updated_at TEXT NOT NULL DEFAULT (datetime('now')),
+    FOREIGN KEY (parent_id) REFERENCES connection_groups(id) ON DELETE CASCADE
+);
+
+CREATE INDEX IF NOT EXISTS idx_connection_groups_parent
+    ON connection_groups(parent_id, sort_order, id);
+
+ALTER TABLE connections ADD COLUMN group_id INTEGER REFERENCES connection_groups(id) ON DELETE SET NULL;
+ALTER TABLE connections ADD COLUMN favorite INTEGER NOT NULL DEFAULT 0;
+ALTER TABLE connections ADD COLUMN sort_order INTEGER NOT NULL DEFAULT 0;
+
+-- Free-form tags, matched case-insensitively
+CREATE TABLE IF NOT EXISTS connection_tags (
+    connection_uuid TEXT NOT NULL,
+    tag TEXT NOT NULL COLLATE NOCASE,
+    PRIMARY KEY (connection_uuid, tag),
+    FOREIGN KEY (connection_uuid) REFERENCES connections(uuid) ON DELETE CASCADE
+);
+
+CREATE INDEX IF NOT EXISTS idx_connection_tags_tag ON connection_tags(tag);
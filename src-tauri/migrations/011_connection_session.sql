@@ -0,0 +1,3 @@
+-- Per-connection session state applied whenever a driver opens a connection
+ALTER TABLE connections ADD COLUMN on_connect_sql TEXT NOT NULL DEFAULT '';
+ALTER TABLE connections ADD COLUMN session_settings TEXT NOT NULL DEFAULT '{}';
@@ -0,0 +1,5 @@
+-- Per-connection query limits; NULL keeps the application default
+ALTER TABLE connections ADD COLUMN query_timeout_ms INTEGER;
+ALTER TABLE connections ADD COLUMN connect_timeout_ms INTEGER;
+ALTER TABLE connections ADD COLUMN max_result_rows INTEGER;
+ALTER TABLE connections ADD COLUMN max_result_bytes INTEGER;
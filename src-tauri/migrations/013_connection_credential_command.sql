@@ -0,0 +1,4 @@
+-- External command that supplies the password (or D1 API token) at connect time
+ALTER TABLE connections ADD COLUMN credential_command TEXT NOT NULL DEFAULT '';
+-- Seconds to reuse the command's output; NULL keeps the application default
+ALTER TABLE connections ADD COLUMN credential_ttl_secs INTEGER;
@@ -0,0 +1,4 @@
+-- What MCP clients may do with a connection: nothing, read, or write once the
+-- user approves each statement in the desktop app
+ALTER TABLE connections ADD COLUMN mcp_permission TEXT NOT NULL DEFAULT 'read'
+    CHECK (mcp_permission IN ('none', 'read', 'write'));
@@ -0,0 +1,21 @@
+-- Named MCP bearer tokens, each limited to an allowlist of connections
+CREATE TABLE IF NOT EXISTS mcp_tokens (
+    id INTEGER PRIMARY KEY AUTOINCREMENT,
+    name TEXT NOT NULL,
+    -- SHA-256 of the token; the token itself is only shown once
+    token_hash TEXT NOT NULL UNIQUE,
+    token_prefix TEXT NOT NULL,
+    permission TEXT NOT NULL DEFAULT 'read' CHECK (permission IN ('read', 'write')),
+    expires_at TEXT,
+    last_used_at TEXT,
+    created_at TEXT NOT NULL DEFAULT (datetime('now')),
+    updated_at TEXT NOT NULL DEFAULT (datetime('now'))
+);
+
+CREATE TABLE IF NOT EXISTS mcp_token_connections (
+    token_id INTEGER NOT NULL,
+    connection_uuid TEXT NOT NULL,
+    PRIMARY KEY (token_id, connection_uuid),
+    FOREIGN KEY (token_id) REFERENCES mcp_tokens(id) ON DELETE CASCADE,
+    FOREIGN KEY (connection_uuid) REFERENCES connections(uuid) ON DELETE CASCADE
+);
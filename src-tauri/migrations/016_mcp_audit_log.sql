@@ -0,0 +1,28 @@
+-- Every MCP tool call, resource read and prompt. Rows are never updated; old
+-- rows are only removed by the retention setting.
+CREATE TABLE IF NOT EXISTS mcp_audit_log (
+    id INTEGER PRIMARY KEY AUTOINCREMENT,
+    -- No foreign key: entries outlive the token and connection they mention
+    token_id INTEGER,
+    token_name TEXT NOT NULL,
+    kind TEXT NOT NULL CHECK (kind IN ('tool', 'resource', 'prompt')),
+    -- Tool name, resource URI or prompt name
+    name TEXT NOT NULL,
+    connection_uuid TEXT,
+    query TEXT,
+    row_count INTEGER,
+    duration_ms INTEGER NOT NULL,
+    error TEXT,
+    created_at TEXT NOT NULL DEFAULT (datetime('now'))
+);
+
+CREATE INDEX IF NOT EXISTS idx_mcp_audit_log_created ON mcp_audit_log(created_at);
+CREATE INDEX IF NOT EXISTS idx_mcp_audit_log_connection
+    ON mcp_audit_log(connection_uuid, created_at);
+CREATE INDEX IF NOT EXISTS idx_mcp_audit_log_token ON mcp_audit_log(token_id, created_at);
+
+CREATE TRIGGER IF NOT EXISTS mcp_audit_log_append_only
+BEFORE UPDATE ON mcp_audit_log
+BEGIN
+    SELECT RAISE(ABORT, 'The MCP audit log is append-only');
+END;
@@ -0,0 +1,7 @@
+-- Engine-specific settings declared by each driver's descriptor, as a JSON
+-- object. D1 keeps its account and database ids here instead of borrowing the
+-- username and database columns.
+ALTER TABLE connections ADD COLUMN options TEXT NOT NULL DEFAULT '{}';
+UPDATE connections
+SET options = json_object('account_id', username, 'database_id', database)
+WHERE db_type = 'd1';
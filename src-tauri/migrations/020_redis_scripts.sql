@@ -0,0 +1,18 @@
+-- Lua scripts saved per Redis connection. parameters_json lists the typed
+-- KEYS and ARGV a run binds, in order.
+CREATE TABLE IF NOT EXISTS redis_scripts (
+    id INTEGER PRIMARY KEY AUTOINCREMENT,
+    connection_uuid TEXT NOT NULL,
+    name TEXT NOT NULL COLLATE NOCASE,
+    description TEXT NOT NULL DEFAULT '',
+    source TEXT NOT NULL,
+    parameters_json TEXT NOT NULL DEFAULT '[]',
+    read_only INTEGER NOT NULL DEFAULT 0,
+    created_at TEXT NOT NULL DEFAULT (datetime('now')),
+    updated_at TEXT NOT NULL DEFAULT (datetime('now')),
+    FOREIGN KEY (connection_uuid) REFERENCES connections(uuid) ON DELETE CASCADE,
+    UNIQUE (connection_uuid, name)
+);
+
+CREATE INDEX IF NOT EXISTS idx_redis_scripts_connection_updated
+    ON redis_scripts(connection_uuid, updated_at DESC, id DESC);
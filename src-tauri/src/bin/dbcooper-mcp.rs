@@ -0,0 +1,51 @@
+//! Standalone MCP server speaking stdio, for MCP clients that launch their
+//! server as a subprocess and for headless machines where the desktop app
+//! can't run.
+//!
+//! It opens the same local store as the app (honouring `DBCOOPER_LOCAL_STORE`)
+//! and keeps its own connection pools. stdout carries only the MCP protocol;
+//! diagnostics go to stderr.
+
+use std::sync::Arc;
+
+use dbcooper_lib::database::pool_manager::PoolManager;
+use dbcooper_lib::mcp::server::serve_stdio;
+
+const USAGE: &str = "Usage: dbcooper-mcp
+
+Serves DBcooper's MCP tools and resources over stdin/stdout.
+
+Environment:
+  DBCOOPER_LOCAL_STORE  Directory of the local store (defaults to the app's)";
+
+#[tokio::main]
+async fn main() {
+    match std::env::args().nth(1).as_deref() {
+        None => {}
+        Some("--version" | "-V") => {
+            println!("dbcooper-mcp {}", env!("CARGO_PKG_VERSION"));
+            return;
+        }
+        Some("--help" | "-h") => {
+            println!("{USAGE}");
+            return;
+        }
+        Some(other) => {
+            eprintln!("dbcooper-mcp: unexpected argument '{other}'\n\n{USAGE}");
+            std::process::exit(2);
+        }
+    }
+
+    let sqlite_pool = match dbcooper_lib::db::init_pool().await {
+        Ok(pool) => pool,
+        Err(e) => {
+            eprintln!("dbcooper-mcp: failed to open the local store: {e}");
+            std::process::exit(1);
+        }
+    };
+
+    if let Err(e) = serve_stdio(sqlite_pool, Arc::new(PoolManager::new())).await {
+        eprintln!("dbcooper-mcp: {e}");
+        std::process::exit(1);
+    }
+}
@@ -0,0 +1,338 @@
+use crate::db::models::ConnectionGroup;
+use sqlx::{Sqlite, SqlitePool, Transaction};
+use tauri::State;
+
+const MAX_GROUP_NAME_LENGTH: usize = 80;
+
+fn normalize_group_name(name: &str) -> Result<String, String> {
+    let name = name.trim();
+    if name.is_empty() {
+        return Err("Folder name is required".to_string());
+    }
+    if name.chars().count() > MAX_GROUP_NAME_LENGTH {
+        return Err(format!(
+            "Folder name must be {MAX_GROUP_NAME_LENGTH} characters or fewer"
+        ));
+    }
+    Ok(name.to_string())
+}
+
+async fn fetch_group(pool: &SqlitePool, id: i64) -> Result<ConnectionGroup, String> {
+    sqlx::query_as::<_, ConnectionGroup>("SELECT * FROM connection_groups WHERE id = ?")
+        .bind(id)
+        .fetch_optional(pool)
+        .await
+        .map_err(|e| e.to_string())?
+        .ok_or_else(|| format!("Folder {id} not found"))
+}
+
+/// True when `candidate` is `group_id` itself or one of its descendants.
+async fn is_within_group(pool: &SqlitePool, group_id: i64, candidate: i64) -> Result<bool, String> {
+    let count: i64 = sqlx::query_scalar(
+        r#"
+        WITH RECURSIVE descendants(id) AS (
+            SELECT ?
+            UNION
+            SELECT g.id FROM connection_groups g JOIN descendants d ON g.parent_id = d.id
+        )
+        SELECT COUNT(*) FROM descendants WHERE id = ?
+        "#,
+    )
+    .bind(group_id)
+    .bind(candidate)
+    .fetch_one(pool)
+    .await
+    .map_err(|e| e.to_string())?;
+    Ok(count > 0)
+}
+
+/// Folder names from the root down to `group_id`, used by connection export.
+pub(crate) async fn group_path(pool: &SqlitePool, group_id: i64) -> Result<Vec<String>, String> {
+    sqlx::query_scalar(
+        r#"
+        WITH RECURSIVE ancestors(id, parent_id, name, depth) AS (
+            SELECT id, parent_id, name, 0 FROM connection_groups WHERE id = ?
+            UNION ALL
+            SELECT g.id, g.parent_id, g.name, a.depth + 1
+            FROM connection_groups g JOIN ancestors a ON g.id = a.parent_id
+            WHERE a.depth < 64
+        )
+        SELECT name FROM ancestors ORDER BY depth DESC
+        "#,
+    )
+    .bind(group_id)
+    .fetch_all(pool)
+    .await
+    .map_err(|e| e.to_string())
+}
+
+/// Resolve a folder path from an import, creating any folders that are missing.
+/// Names are matched case-insensitively under the same parent.
+pub(crate) async fn ensure_group_path(
+    transaction: &mut Transaction<'_, Sqlite>,
+    path: &[String],
+) -> Result<Option<i64>, String> {
+    let mut parent_id: Option<i64> = None;
+    for segment in path.iter().filter(|segment| !segment.trim().is_empty()) {
+        let name = normalize_group_name(segment)?;
+        let existing: Option<i64> = sqlx::query_scalar(
+            "SELECT id FROM connection_groups WHERE parent_id IS ? AND name = ? ORDER BY id LIMIT 1",
+        )
+        .bind(parent_id)
+        .bind(&name)
+        .fetch_optional(&mut **transaction)
+        .await
+        .map_err(|e| e.to_string())?;
+
+        let id = match existing {
+            Some(id) => id,
+            None => sqlx::query_scalar(
+                "INSERT INTO connection_groups (parent_id, name) VALUES (?, ?) RETURNING id",
+            )
+            .bind(parent_id)
+            .bind(&name)
+            .fetch_one(&mut **transaction)
+            .await
+            .map_err(|e| e.to_string())?,
+        };
+        parent_id = Some(id);
+    }
+    Ok(parent_id)
+}
+
+pub(crate) async fn create_group(
+    pool: &SqlitePool,
+    name: &str,
+    parent_id: Option<i64>,
+) -> Result<ConnectionGroup, String> {
+    let name = normalize_group_name(name)?;
+    if let Some(parent_id) = parent_id {
+        fetch_group(pool, parent_id).await?;
+    }
+
+    sqlx::query_as::<_, ConnectionGroup>(
+        r#"
+        INSERT INTO connection_groups (parent_id, name, sort_order)
+        VALUES (?, ?, (SELECT COALESCE(MAX(sort_order) + 1, 0) FROM connection_groups WHERE parent_id IS ?))
+        RETURNING *
+        "#,
+    )
+    .bind(parent_id)
+    .bind(name)
+    .bind(parent_id)
+    .fetch_one(pool)
+    .await
+    .map_err(|e| e.to_string())
+}
+
+pub(crate) async fn move_group(
+    pool: &SqlitePool,
+    id: i64,
+    parent_id: Option<i64>,
+    sort_order: i64,
+) -> Result<ConnectionGroup, String> {
+    fetch_group(pool, id).await?;
+    if let Some(parent_id) = parent_id {
+        fetch_group(pool, parent_id).await?;
+        if is_within_group(pool, id, parent_id).await? {
+            return Err(
+                "A folder cannot be moved into itself or one of its subfolders".to_string(),
+            );
+        }
+    }
+
+    sqlx::query_as::<_, ConnectionGroup>(
+        r#"
+        UPDATE connection_groups
+        SET parent_id = ?, sort_order = ?, updated_at = datetime('now')
+        WHERE id = ?
+        RETURNING *
+        "#,
+    )
+    .bind(parent_id)
+    .bind(sort_order)
+    .bind(id)
+    .fetch_one(pool)
+    .await
+    .map_err(|e| e.to_string())
+}
+
+/// Delete a folder, moving its subfolders and connections up to its parent so
+/// nothing saved inside it is lost.
+pub(crate) async fn delete_group(pool: &SqlitePool, id: i64) -> Result<bool, String> {
+    let group = fetch_group(pool, id).await?;
+    let mut transaction = pool.begin().await.map_err(|e| e.to_string())?;
+
+    sqlx::query("UPDATE connection_groups SET parent_id = ? WHERE parent_id = ?")
+        .bind(group.parent_id)
+        .bind(id)
+        .execute(&mut *transaction)
+        .await
+        .map_err(|e| e.to_string())?;
+    sqlx::query("UPDATE connections SET group_id = ? WHERE group_id = ?")
+        .bind(group.parent_id)
+        .bind(id)
+        .execute(&mut *transaction)
+        .await
+        .map_err(|e| e.to_string())?;
+    sqlx::query("DELETE FROM connection_groups WHERE id = ?")
+        .bind(id)
+        .execute(&mut *transaction)
+        .await
+        .map_err(|e| e.to_string())?;
+
+    transaction.commit().await.map_err(|e| e.to_string())?;
+    Ok(true)
+}
+
+#[tauri::command]
+pub async fn get_connection_groups(
+    pool: State<'_, SqlitePool>,
+) -> Result<Vec<ConnectionGroup>, String> {
+    sqlx::query_as::<_, ConnectionGroup>(
+        "SELECT * FROM connection_groups ORDER BY parent_id, sort_order, name",
+    )
+    .fetch_all(pool.inner())
+    .await
+    .map_err(|e| e.to_string())
+}
+
+#[tauri::command]
+pub async fn create_connection_group(
+    pool: State<'_, SqlitePool>,
+    name: String,
+    parent_id: Option<i64>,
+) -> Result<ConnectionGroup, String> {
+    create_group(pool.inner(), &name, parent_id).await
+}
+
+#[tauri::command]
+pub async fn rename_connection_group(
+    pool: State<'_, SqlitePool>,
+    id: i64,
+    name: String,
+) -> Result<ConnectionGroup, String> {
+    let name = normalize_group_name(&name)?;
+    sqlx::query_as::<_, ConnectionGroup>(
+        r#"
+        UPDATE connection_groups
+        SET name = ?, updated_at = datetime('now')
+        WHERE id = ?
+        RETURNING *
+        "#,
+    )
+    .bind(name)
+    .bind(id)
+    .fetch_one(pool.inner())
+    .await
+    .map_err(|e| e.to_string())
+}
+
+#[tauri::command]
+pub async fn move_connection_group(
+    pool: State<'_, SqlitePool>,
+    id: i64,
+    parent_id: Option<i64>,
+    sort_order: i64,
+) -> Result<ConnectionGroup, String> {
+    move_group(pool.inner(), id, parent_id, sort_order).await
+}
+
+#[tauri::command]
+pub async fn delete_connection_group(pool: State<'_, SqlitePool>, id: i64) -> Result<bool, String> {
+    delete_group(pool.inner(), id).await
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use sqlx::sqlite::SqlitePoolOptions;
+
+    async fn test_pool() -> SqlitePool {
+        let pool = SqlitePoolOptions::new()
+            .max_connections(1)
+            .connect("sqlite::memory:")
+            .await
+            .unwrap();
+        sqlx::migrate!("./migrations").run(&pool).await.unwrap();
+        pool
+    }
+
+    #[test]
+    fn rejects_blank_and_overlong_folder_names() {
+        assert_eq!(normalize_group_name("  Clients ").unwrap(), "Clients");
+        assert!(normalize_group_name("  ").is_err());
+        assert!(normalize_group_name(&"a".repeat(81)).is_err());
+    }
+
+    #[tokio::test]
+    async fn refuses_to_move_a_folder_into_its_own_subtree() {
+        let pool = test_pool().await;
+        let clients = create_group(&pool, "Clients", None).await.unwrap();
+        let acme = create_group(&pool, "Acme", Some(clients.id)).await.unwrap();
+
+        assert!(move_group(&pool, clients.id, Some(acme.id), 0)
+            .await
+            .is_err());
+        assert!(move_group(&pool, clients.id, Some(clients.id), 0)
+            .await
+            .is_err());
+
+        let moved = move_group(&pool, acme.id, None, 3).await.unwrap();
+        assert_eq!(moved.parent_id, None);
+        assert_eq!(moved.sort_order, 3);
+    }
+
+    #[tokio::test]
+    async fn deleting_a_folder_moves_its_contents_to_the_parent() {
+        let pool = test_pool().await;
+        let clients = create_group(&pool, "Clients", None).await.unwrap();
+        let acme = create_group(&pool, "Acme", Some(clients.id)).await.unwrap();
+        let staging = create_group(&pool, "Staging", Some(acme.id)).await.unwrap();
+        sqlx::query(
+            "INSERT INTO connections (uuid, name, host, port, database, username, password, group_id) VALUES ('c1', 'Acme prod', 'localhost', 5432, 'db', 'user', 'pass', ?)",
+        )
+        .bind(acme.id)
+        .execute(&pool)
+        .await
+        .unwrap();
+
+        delete_group(&pool, acme.id).await.unwrap();
+
+        let group_id: Option<i64> =
+            sqlx::query_scalar("SELECT group_id FROM connections WHERE uuid = 'c1'")
+                .fetch_one(&pool)
+                .await
+                .unwrap();
+        assert_eq!(group_id, Some(clients.id));
+        assert_eq!(
+            fetch_group(&pool, staging.id).await.unwrap().parent_id,
+            Some(clients.id)
+        );
+    }
+
+    #[tokio::test]
+    async fn resolves_import_paths_reusing_existing_folders() {
+        let pool = test_pool().await;
+        let clients = create_group(&pool, "Clients", None).await.unwrap();
+
+        let mut transaction = pool.begin().await.unwrap();
+        let leaf = ensure_group_path(
+            &mut transaction,
+            &["clients".to_string(), "Acme".to_string()],
+        )
+        .await
+        .unwrap()
+        .unwrap();
+        transaction.commit().await.unwrap();
+
+        assert_eq!(
+            fetch_group(&pool, leaf).await.unwrap().parent_id,
+            Some(clients.id)
+        );
+        assert_eq!(
+            group_path(&pool, leaf).await.unwrap(),
+            vec!["Clients".to_string(), "Acme".to_string()]
+        );
+    }
+}
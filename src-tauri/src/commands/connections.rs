@@ -1,14 +1,186 @@
+use super::connection_groups::{ensure_group_path, group_path};
+use crate::database::registry::find_driver;
+use crate::database::{CredentialCommand, QueryLimits};
 use crate::db::models::{Connection, ConnectionFormData};
-use sqlx::SqlitePool;
+use crate::mcp::permissions::McpPermission;
+use serde_json::{Map, Value};
+use sqlx::{Sqlite, SqlitePool, Transaction};
+use std::collections::{BTreeMap, HashMap};
 use tauri::State;
 use uuid::Uuid;
 
-#[tauri::command]
-pub async fn get_connections(pool: State<'_, SqlitePool>) -> Result<Vec<Connection>, String> {
-    sqlx::query_as::<_, Connection>("SELECT * FROM connections ORDER BY id DESC")
-        .fetch_all(pool.inner())
+const MAX_TAG_LENGTH: usize = 40;
+
+/// Trim, drop blanks and de-duplicate tags case-insensitively, keeping the
+/// first spelling the user entered.
+fn normalize_tags(tags: &[String]) -> Result<Vec<String>, String> {
+    let mut normalized: Vec<String> = Vec::new();
+    for tag in tags {
+        let tag = tag.trim();
+        if tag.is_empty() {
+            continue;
+        }
+        if tag.chars().count() > MAX_TAG_LENGTH {
+            return Err(format!("Tags must be {MAX_TAG_LENGTH} characters or fewer"));
+        }
+        if !normalized
+            .iter()
+            .any(|existing| existing.eq_ignore_ascii_case(tag))
+        {
+            normalized.push(tag.to_string());
+        }
+    }
+    Ok(normalized)
+}
+
+async fn replace_tags(
+    transaction: &mut Transaction<'_, Sqlite>,
+    uuid: &str,
+    tags: &[String],
+) -> Result<Vec<String>, String> {
+    let tags = normalize_tags(tags)?;
+    sqlx::query("DELETE FROM connection_tags WHERE connection_uuid = ?")
+        .bind(uuid)
+        .execute(&mut **transaction)
+        .await
+        .map_err(|e| e.to_string())?;
+    for tag in &tags {
+        sqlx::query("INSERT INTO connection_tags (connection_uuid, tag) VALUES (?, ?)")
+            .bind(uuid)
+            .bind(tag)
+            .execute(&mut **transaction)
+            .await
+            .map_err(|e| e.to_string())?;
+    }
+    Ok(tags)
+}
+
+fn session_settings_json(settings: &BTreeMap<String, String>) -> Result<String, String> {
+    serde_json::to_string(settings).map_err(|e| e.to_string())
+}
+
+/// Check engine options against the fields the engine declares and encode
+/// them for the `options` column.
+fn options_json(db_type: &str, options: &Map<String, Value>) -> Result<String, String> {
+    find_driver(db_type)?.validate_options(options)?;
+    serde_json::to_string(options).map_err(|e| e.to_string())
+}
+
+/// Reject limits and credential settings the drivers would refuse before they
+/// are saved.
+fn validate_limits(data: &ConnectionFormData) -> Result<(), String> {
+    QueryLimits::from_settings(
+        data.query_timeout_ms.flatten(),
+        data.connect_timeout_ms.flatten(),
+        data.max_result_rows.flatten(),
+        data.max_result_bytes.flatten(),
+    )?;
+    CredentialCommand::from_settings(
+        data.credential_command.as_deref().unwrap_or_default(),
+        data.credential_ttl_secs.flatten(),
+    )?;
+    Ok(())
+}
+
+/// Fill in `Connection::tags`, which live in their own table.
+async fn attach_tags(pool: &SqlitePool, connections: &mut [Connection]) -> Result<(), String> {
+    let rows: Vec<(String, String)> =
+        sqlx::query_as("SELECT connection_uuid, tag FROM connection_tags ORDER BY tag")
+            .fetch_all(pool)
+            .await
+            .map_err(|e| e.to_string())?;
+    let mut by_connection: HashMap<String, Vec<String>> = HashMap::new();
+    for (uuid, tag) in rows {
+        by_connection.entry(uuid).or_default().push(tag);
+    }
+    for connection in connections {
+        connection.tags = by_connection.remove(&connection.uuid).unwrap_or_default();
+    }
+    Ok(())
+}
+
+async fn fetch_connection(pool: &SqlitePool, id: i64) -> Result<Connection, String> {
+    let mut connection = sqlx::query_as::<_, Connection>("SELECT * FROM connections WHERE id = ?")
+        .bind(id)
+        .fetch_one(pool)
         .await
-        .map_err(|e| e.to_string())
+        .map_err(|e| e.to_string())?;
+    attach_tags(pool, std::slice::from_mut(&mut connection)).await?;
+    Ok(connection)
+}
+
+pub(crate) async fn list_connections(
+    pool: &SqlitePool,
+    tag: Option<&str>,
+) -> Result<Vec<Connection>, String> {
+    let mut connections = sqlx::query_as::<_, Connection>(
+        r#"
+        SELECT * FROM connections c
+        WHERE ?1 IS NULL OR EXISTS (
+            SELECT 1 FROM connection_tags t WHERE t.connection_uuid = c.uuid AND t.tag = ?1
+        )
+        ORDER BY sort_order, id DESC
+        "#,
+    )
+    .bind(tag.map(str::trim).filter(|tag| !tag.is_empty()))
+    .fetch_all(pool)
+    .await
+    .map_err(|e| e.to_string())?;
+    attach_tags(pool, &mut connections).await?;
+    Ok(connections)
+}
+
+/// Rank connections against a fuzzy query over name, host, database and tags.
+pub(crate) fn rank_connections(query: &str, connections: Vec<Connection>) -> Vec<Connection> {
+    let mut scored: Vec<(i64, Connection)> = connections
+        .into_iter()
+        .filter_map(|connection| {
+            let mut fields = vec![
+                connection.name.as_str(),
+                connection.host.as_str(),
+                connection.database.as_str(),
+            ];
+            fields.extend(connection.tags.iter().map(String::as_str));
+            let score = crate::fuzzy::score_terms(query, &fields)?;
+            // Favourites win ties so the connections people use most float up.
+            Some((score + connection.favorite, connection))
+        })
+        .collect();
+    scored.sort_by(|(left, _), (right, _)| right.cmp(left));
+    scored
+        .into_iter()
+        .map(|(_, connection)| connection)
+        .collect()
+}
+
+#[tauri::command]
+pub async fn get_connections(
+    pool: State<'_, SqlitePool>,
+    tag: Option<String>,
+) -> Result<Vec<Connection>, String> {
+    list_connections(pool.inner(), tag.as_deref()).await
+}
+
+#[tauri::command]
+pub async fn search_connections(
+    pool: State<'_, SqlitePool>,
+    query: String,
+) -> Result<Vec<Connection>, String> {
+    let connections = list_connections(pool.inner(), None).await?;
+    if query.trim().is_empty() {
+        return Ok(connections);
+    }
+    Ok(rank_connections(&query, connections))
+}
+
+#[tauri::command]
+pub async fn get_connection_tags(pool: State<'_, SqlitePool>) -> Result<Vec<String>, String> {
+    sqlx::query_scalar(
+        "SELECT MIN(tag) FROM connection_tags GROUP BY tag COLLATE NOCASE ORDER BY tag COLLATE NOCASE",
+    )
+    .fetch_all(pool.inner())
+    .await
+    .map_err(|e| e.to_string())
 }
 
 #[tauri::command]
@@ -16,27 +188,39 @@ pub async fn get_connection_by_uuid(
     pool: State<'_, SqlitePool>,
     uuid: String,
 ) -> Result<Connection, String> {
-    sqlx::query_as::<_, Connection>("SELECT * FROM connections WHERE uuid = ?")
-        .bind(&uuid)
-        .fetch_one(pool.inner())
-        .await
-        .map_err(|e| e.to_string())
+    let mut connection =
+        sqlx::query_as::<_, Connection>("SELECT * FROM connections WHERE uuid = ?")
+            .bind(&uuid)
+            .fetch_one(pool.inner())
+            .await
+            .map_err(|e| e.to_string())?;
+    attach_tags(pool.inner(), std::slice::from_mut(&mut connection)).await?;
+    Ok(connection)
 }
 
-#[tauri::command]
-pub async fn create_connection(
-    pool: State<'_, SqlitePool>,
-    data: ConnectionFormData,
+pub(crate) async fn insert_connection(
+    pool: &SqlitePool,
+    data: &ConnectionFormData,
 ) -> Result<Connection, String> {
     let uuid = Uuid::new_v4().to_string();
     let ssl = if data.ssl { 1 } else { 0 };
     let ssh_enabled = if data.ssh_enabled { 1 } else { 0 };
     let ssh_use_key = if data.ssh_use_key { 1 } else { 0 };
+    let favorite = if data.favorite { 1 } else { 0 };
+    let session_settings =
+        session_settings_json(data.session_settings.as_ref().unwrap_or(&BTreeMap::new()))?;
+    let options = options_json(&data.db_type, data.options.as_ref().unwrap_or(&Map::new()))?;
+    validate_limits(data)?;
+    let mcp_permission = match &data.mcp_permission {
+        Some(permission) => McpPermission::parse(permission)?,
+        None => McpPermission::Read,
+    };
+    let mut transaction = pool.begin().await.map_err(|e| e.to_string())?;
 
-    sqlx::query_as::<_, Connection>(
+    let mut connection = sqlx::query_as::<_, Connection>(
         r#"
-        INSERT INTO connections (uuid, type, name, host, port, database, username, password, ssl, db_type, file_path, ssh_enabled, ssh_host, ssh_port, ssh_user, ssh_password, ssh_key_path, ssh_use_key)
-        VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?)
+        INSERT INTO connections (uuid, type, name, host, port, database, username, password, ssl, db_type, file_path, ssh_enabled, ssh_host, ssh_port, ssh_user, ssh_password, ssh_key_path, ssh_use_key, group_id, favorite, on_connect_sql, session_settings, options, query_timeout_ms, connect_timeout_ms, max_result_rows, max_result_bytes, credential_command, credential_ttl_secs, mcp_permission)
+        VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?)
         RETURNING *
         "#,
     )
@@ -58,30 +242,86 @@ pub async fn create_connection(
     .bind(&data.ssh_password)
     .bind(&data.ssh_key_path)
     .bind(ssh_use_key)
-    .fetch_one(pool.inner())
+    .bind(data.group_id)
+    .bind(favorite)
+    .bind(data.on_connect_sql.as_deref().unwrap_or_default())
+    .bind(&session_settings)
+    .bind(&options)
+    .bind(data.query_timeout_ms.flatten())
+    .bind(data.connect_timeout_ms.flatten())
+    .bind(data.max_result_rows.flatten())
+    .bind(data.max_result_bytes.flatten())
+    .bind(data.credential_command.as_deref().unwrap_or_default().trim())
+    .bind(data.credential_ttl_secs.flatten())
+    .bind(mcp_permission.as_str())
+    .fetch_one(&mut *transaction)
     .await
-    .map_err(|e| e.to_string())
+    .map_err(|e| e.to_string())?;
+
+    connection.tags = replace_tags(&mut transaction, &uuid, &data.tags).await?;
+    transaction.commit().await.map_err(|e| e.to_string())?;
+    Ok(connection)
 }
 
 #[tauri::command]
-pub async fn update_connection(
+pub async fn create_connection(
     pool: State<'_, SqlitePool>,
-    id: i64,
     data: ConnectionFormData,
+) -> Result<Connection, String> {
+    insert_connection(pool.inner(), &data).await
+}
+
+/// Save the connection form. Folder, favourite and tags are left alone: the
+/// form doesn't edit them, and `move_connection`, `set_connection_favorite`
+/// and `set_connection_tags` own those columns. Optional settings the request
+/// leaves out keep their stored values.
+pub(crate) async fn save_connection(
+    pool: &SqlitePool,
+    id: i64,
+    data: &ConnectionFormData,
 ) -> Result<Connection, String> {
     let ssl = if data.ssl { 1 } else { 0 };
     let ssh_enabled = if data.ssh_enabled { 1 } else { 0 };
     let ssh_use_key = if data.ssh_use_key { 1 } else { 0 };
+    let session_settings = data
+        .session_settings
+        .as_ref()
+        .map(session_settings_json)
+        .transpose()?;
+    let options = match &data.options {
+        Some(options) => Some(options_json(&data.db_type, options)?),
+        // Options belong to one engine, so switching engines without sending
+        // new ones starts from none.
+        None if fetch_connection(pool, id).await?.db_type != data.db_type => {
+            Some(options_json(&data.db_type, &Map::new())?)
+        }
+        None => None,
+    };
+    validate_limits(data)?;
+    let mcp_permission = data
+        .mcp_permission
+        .as_deref()
+        .map(McpPermission::parse)
+        .transpose()?;
 
-    sqlx::query_as::<_, Connection>(
+    sqlx::query(
         r#"
         UPDATE connections
         SET type = ?, name = ?, host = ?, port = ?, database = ?, username = ?, password = ?, ssl = ?,
             db_type = ?, file_path = ?,
             ssh_enabled = ?, ssh_host = ?, ssh_port = ?, ssh_user = ?, ssh_password = ?, ssh_key_path = ?, ssh_use_key = ?,
+            on_connect_sql = COALESCE(?, on_connect_sql),
+            session_settings = COALESCE(?, session_settings),
+            options = COALESCE(?, options),
+            query_timeout_ms = CASE WHEN ? THEN ? ELSE query_timeout_ms END,
+            connect_timeout_ms = CASE WHEN ? THEN ? ELSE connect_timeout_ms END,
+            max_result_rows = CASE WHEN ? THEN ? ELSE max_result_rows END,
+            max_result_bytes = CASE WHEN ? THEN ? ELSE max_result_bytes END,
+            credential_command = COALESCE(?, credential_command),
+            credential_ttl_secs = CASE WHEN ? THEN ? ELSE credential_ttl_secs END,
+            mcp_permission = COALESCE(?, mcp_permission),
             updated_at = datetime('now')
         WHERE id = ?
-        RETURNING *
         "#,
     )
     .bind(&data.connection_type)
@@ -101,10 +341,84 @@ pub async fn update_connection(
     .bind(&data.ssh_password)
     .bind(&data.ssh_key_path)
     .bind(ssh_use_key)
+    .bind(&data.on_connect_sql)
+    .bind(&session_settings)
+    .bind(&options)
+    .bind(data.query_timeout_ms.is_some())
+    .bind(data.query_timeout_ms.flatten())
+    .bind(data.connect_timeout_ms.is_some())
+    .bind(data.connect_timeout_ms.flatten())
+    .bind(data.max_result_rows.is_some())
+    .bind(data.max_result_rows.flatten())
+    .bind(data.max_result_bytes.is_some())
+    .bind(data.max_result_bytes.flatten())
+    .bind(data.credential_command.as_deref().map(str::trim))
+    .bind(data.credential_ttl_secs.is_some())
+    .bind(data.credential_ttl_secs.flatten())
+    .bind(mcp_permission.map(McpPermission::as_str))
     .bind(id)
-    .fetch_one(pool.inner())
+    .execute(pool)
     .await
-    .map_err(|e| e.to_string())
+    .map_err(|e| e.to_string())?;
+
+    fetch_connection(pool, id).await
+}
+
+#[tauri::command]
+pub async fn update_connection(
+    pool: State<'_, SqlitePool>,
+    id: i64,
+    data: ConnectionFormData,
+) -> Result<Connection, String> {
+    save_connection(pool.inner(), id, &data).await
+}
+
+#[tauri::command]
+pub async fn set_connection_favorite(
+    pool: State<'_, SqlitePool>,
+    id: i64,
+    favorite: bool,
+) -> Result<Connection, String> {
+    sqlx::query("UPDATE connections SET favorite = ?, updated_at = datetime('now') WHERE id = ?")
+        .bind(if favorite { 1 } else { 0 })
+        .bind(id)
+        .execute(pool.inner())
+        .await
+        .map_err(|e| e.to_string())?;
+    fetch_connection(pool.inner(), id).await
+}
+
+/// Place a connection in a folder (or at the top level) at a given position.
+#[tauri::command]
+pub async fn move_connection(
+    pool: State<'_, SqlitePool>,
+    id: i64,
+    group_id: Option<i64>,
+    sort_order: i64,
+) -> Result<Connection, String> {
+    sqlx::query(
+        "UPDATE connections SET group_id = ?, sort_order = ?, updated_at = datetime('now') WHERE id = ?",
+    )
+    .bind(group_id)
+    .bind(sort_order)
+    .bind(id)
+    .execute(pool.inner())
+    .await
+    .map_err(|e| e.to_string())?;
+    fetch_connection(pool.inner(), id).await
+}
+
+#[tauri::command]
+pub async fn set_connection_tags(
+    pool: State<'_, SqlitePool>,
+    id: i64,
+    tags: Vec<String>,
+) -> Result<Connection, String> {
+    let connection = fetch_connection(pool.inner(), id).await?;
+    let mut transaction = pool.begin().await.map_err(|e| e.to_string())?;
+    replace_tags(&mut transaction, &connection.uuid, &tags).await?;
+    transaction.commit().await.map_err(|e| e.to_string())?;
+    fetch_connection(pool.inner(), id).await
 }
 
 #[tauri::command]
@@ -138,6 +452,33 @@ pub struct ExportedConnection {
     pub ssh_password: String,
     pub ssh_key_path: String,
     pub ssh_use_key: bool,
+    /// Folder names from the root to the connection's folder; empty at the top level.
+    #[serde(default)]
+    pub group_path: Vec<String>,
+    #[serde(default)]
+    pub tags: Vec<String>,
+    #[serde(default)]
+    pub favorite: bool,
+    #[serde(default)]
+    pub on_connect_sql: String,
+    #[serde(default)]
+    pub session_settings: BTreeMap<String, String>,
+    #[serde(default)]
+    pub options: Map<String, Value>,
+    #[serde(default)]
+    pub query_timeout_ms: Option<i64>,
+    #[serde(default)]
+    pub connect_timeout_ms: Option<i64>,
+    #[serde(default)]
+    pub max_result_rows: Option<i64>,
+    #[serde(default)]
+    pub max_result_bytes: Option<i64>,
+    #[serde(default)]
+    pub credential_command: String,
+    #[serde(default)]
+    pub credential_ttl_secs: Option<i64>,
+    #[serde(default)]
+    pub mcp_permission: String,
 }
 
 /// Export file format
@@ -153,11 +494,11 @@ pub async fn export_connection(
     pool: State<'_, SqlitePool>,
     id: i64,
 ) -> Result<ConnectionsExport, String> {
-    let connection = sqlx::query_as::<_, Connection>("SELECT * FROM connections WHERE id = ?")
-        .bind(id)
-        .fetch_one(pool.inner())
-        .await
-        .map_err(|e| e.to_string())?;
+    let connection = fetch_connection(pool.inner(), id).await?;
+    let group_path = match connection.group_id {
+        Some(group_id) => group_path(pool.inner(), group_id).await?,
+        None => Vec::new(),
+    };
 
     let exported = ExportedConnection {
         connection_type: connection.connection_type,
@@ -177,6 +518,19 @@ pub async fn export_connection(
         ssh_password: connection.ssh_password,
         ssh_key_path: connection.ssh_key_path,
         ssh_use_key: connection.ssh_use_key == 1,
+        group_path,
+        tags: connection.tags,
+        favorite: connection.favorite == 1,
+        on_connect_sql: connection.on_connect_sql,
+        session_settings: connection.session_settings.0,
+        options: connection.options.0,
+        query_timeout_ms: connection.query_timeout_ms,
+        connect_timeout_ms: connection.connect_timeout_ms,
+        max_result_rows: connection.max_result_rows,
+        max_result_bytes: connection.max_result_bytes,
+        credential_command: connection.credential_command,
+        credential_ttl_secs: connection.credential_ttl_secs,
+        mcp_permission: connection.mcp_permission,
     };
 
     Ok(ConnectionsExport {
@@ -226,10 +580,23 @@ pub async fn import_connections(
             }
         }
 
+        let favorite = if conn.favorite { 1 } else { 0 };
+        let session_settings = session_settings_json(&conn.session_settings)?;
+        let mut options = conn.options.clone();
+        if options.is_empty() && find_driver(&conn.db_type)?.id == "d1" {
+            // Exports from before engine options keep D1's ids in the username
+            // and database fields.
+            options.insert("account_id".to_string(), conn.username.clone().into());
+            options.insert("database_id".to_string(), conn.database.clone().into());
+        }
+        let options = options_json(&conn.db_type, &options)?;
+        let mut transaction = pool.begin().await.map_err(|e| e.to_string())?;
+        let group_id = ensure_group_path(&mut transaction, &conn.group_path).await?;
+
         let result = sqlx::query(
             r#"
-            INSERT INTO connections (uuid, type, name, host, port, database, username, password, ssl, db_type, file_path, ssh_enabled, ssh_host, ssh_port, ssh_user, ssh_password, ssh_key_path, ssh_use_key)
-            VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?)
+            INSERT INTO connections (uuid, type, name, host, port, database, username, password, ssl, db_type, file_path, ssh_enabled, ssh_host, ssh_port, ssh_user, ssh_password, ssh_key_path, ssh_use_key, group_id, favorite, on_connect_sql, session_settings, options, query_timeout_ms, connect_timeout_ms, max_result_rows, max_result_bytes, credential_command, credential_ttl_secs, mcp_permission)
+            VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?)
             "#,
         )
         .bind(&uuid)
@@ -250,13 +617,271 @@ pub async fn import_connections(
         .bind(&conn.ssh_password)
         .bind(&conn.ssh_key_path)
         .bind(ssh_use_key)
-        .execute(pool.inner())
+        .bind(group_id)
+        .bind(favorite)
+        .bind(&conn.on_connect_sql)
+        .bind(&session_settings)
+        .bind(&options)
+        .bind(conn.query_timeout_ms)
+        .bind(conn.connect_timeout_ms)
+        .bind(conn.max_result_rows)
+        .bind(conn.max_result_bytes)
+        .bind(conn.credential_command.trim())
+        .bind(conn.credential_ttl_secs)
+        .bind(McpPermission::from_stored(&conn.mcp_permission).as_str())
+        .execute(&mut *transaction)
         .await;
 
-        if result.is_ok() {
+        if result.is_ok()
+            && replace_tags(&mut transaction, &uuid, &conn.tags)
+                .await
+                .is_ok()
+        {
+            transaction.commit().await.map_err(|e| e.to_string())?;
             imported_count += 1;
         }
     }
 
     Ok(imported_count)
 }
+
+#[cfg(test)]
+mod tests {
+    use super::{insert_connection, normalize_tags, rank_connections, save_connection};
+    use crate::db::models::{Connection, ConnectionFormData};
+    use serde_json::{json, Value};
+    use sqlx::sqlite::SqlitePoolOptions;
+    use sqlx::SqlitePool;
+
+    async fn test_pool() -> SqlitePool {
+        let pool = SqlitePoolOptions::new()
+            .max_connections(1)
+            .connect("sqlite::memory:")
+            .await
+            .unwrap();
+        sqlx::migrate!("./migrations").run(&pool).await.unwrap();
+        pool
+    }
+
+    /// The fields the connection form sends, plus `extra`.
+    fn form(extra: Value) -> ConnectionFormData {
+        let mut data = json!({
+            "type": "postgres",
+            "name": "Billing",
+            "host": "billing-db.internal",
+            "port": 5432,
+            "database": "billing",
+            "username": "postgres",
+            "password": "secret",
+            "ssl": false,
+            "db_type": "postgres"
+        });
+        data.as_object_mut()
+            .unwrap()
+            .extend(extra.as_object().unwrap().clone());
+        serde_json::from_value(data).unwrap()
+    }
+
+    fn connection(name: &str, host: &str, database: &str, tags: &[&str]) -> Connection {
+        Connection {
+            id: 1,
+            uuid: name.to_string(),
+            connection_type: "postgres".to_string(),
+            name: name.to_string(),
+            host: host.to_string(),
+            port: 5432,
+            database: database.to_string(),
+            username: "postgres".to_string(),
+            password: String::new(),
+            ssl: 0,
+            db_type: "postgres".to_string(),
+            file_path: None,
+            ssh_enabled: 0,
+            ssh_host: String::new(),
+            ssh_port: 22,
+            ssh_user: String::new(),
+            ssh_password: String::new(),
+            ssh_key_path: String::new(),
+            ssh_use_key: 0,
+            group_id: None,
+            favorite: 0,
+            sort_order: 0,
+            on_connect_sql: String::new(),
+            session_settings: Default::default(),
+            options: Default::default(),
+            query_timeout_ms: None,
+            connect_timeout_ms: None,
+            max_result_rows: None,
+            max_result_bytes: None,
+            credential_command: String::new(),
+            credential_ttl_secs: None,
+            mcp_permission: "read".to_string(),
+            tags: tags.iter().map(|tag| tag.to_string()).collect(),
+            created_at: String::new(),
+            updated_at: String::new(),
+        }
+    }
+
+    #[test]
+    fn normalizes_tags_case_insensitively() {
+        let tags = normalize_tags(&[
+            " prod ".to_string(),
+            "Prod".to_string(),
+            "".to_string(),
+            "eu-west".to_string(),
+        ])
+        .unwrap();
+
+        assert_eq!(tags, vec!["prod".to_string(), "eu-west".to_string()]);
+        assert!(normalize_tags(&["x".repeat(41)]).is_err());
+    }
+
+    #[test]
+    fn ranks_connections_across_name_host_database_and_tags() {
+        let ranked = rank_connections(
+            "billing prod",
+            vec![
+                connection("Analytics", "warehouse.internal", "events", &["prod"]),
+                connection("Billing", "billing-db.internal", "billing", &["prod", "eu"]),
+                connection(
+                    "Billing staging",
+                    "staging.internal",
+                    "billing",
+                    &["staging"],
+                ),
+            ],
+        );
+
+        let names: Vec<&str> = ranked.iter().map(|c| c.name.as_str()).collect();
+        assert_eq!(names, vec!["Billing"]);
+    }
+
+    #[tokio::test]
+    async fn saving_the_form_keeps_folder_favourite_and_tags() {
+        let pool = test_pool().await;
+        sqlx::query("INSERT INTO connection_groups (name) VALUES ('Clients')")
+            .execute(&pool)
+            .await
+            .unwrap();
+        let created = insert_connection(
+            &pool,
+            &form(json!({"group_id": 1, "favorite": true, "tags": ["prod"]})),
+        )
+        .await
+        .unwrap();
+
+        let saved = save_connection(
+            &pool,
+            created.id,
+            &form(json!({"name": "Billing (primary)"})),
+        )
+        .await
+        .unwrap();
+        assert_eq!(saved.name, "Billing (primary)");
+        assert_eq!(saved.group_id, Some(1));
+        assert_eq!(saved.favorite, 1);
+        assert_eq!(saved.tags, vec!["prod".to_string()]);
+    }
+
+    #[tokio::test]
+    async fn saving_the_form_keeps_settings_it_leaves_out() {
+        let pool = test_pool().await;
+        let created = insert_connection(
+            &pool,
+            &form(json!({
+                "on_connect_sql": "SET ROLE analyst",
+                "session_settings": {"search_path": "billing"},
+                "query_timeout_ms": 30000,
+                "max_result_rows": 500,
+                "credential_command": "vault read -field=password secret/billing",
+                "credential_ttl_secs": 60,
+                "mcp_permission": "none"
+            })),
+        )
+        .await
+        .unwrap();
+
+        let saved = save_connection(&pool, created.id, &form(json!({})))
+            .await
+            .unwrap();
+        assert_eq!(saved.on_connect_sql, "SET ROLE analyst");
+        assert_eq!(
+            saved
+                .session_settings
+                .0
+                .get("search_path")
+                .map(String::as_str),
+            Some("billing")
+        );
+        assert_eq!(saved.query_timeout_ms, Some(30000));
+        assert_eq!(saved.max_result_rows, Some(500));
+        assert_eq!(
+            saved.credential_command,
+            "vault read -field=password secret/billing"
+        );
+        assert_eq!(saved.credential_ttl_secs, Some(60));
+        assert_eq!(saved.mcp_permission, "none");
+
+        let cleared = save_connection(
+            &pool,
+            created.id,
+            &form(json!({
+                "on_connect_sql": "",
+                "session_settings": {},
+                "query_timeout_ms": null,
+                "max_result_rows": 1000,
+                "credential_command": ""
+            })),
+        )
+        .await
+        .unwrap();
+        assert_eq!(cleared.on_connect_sql, "");
+        assert!(cleared.session_settings.0.is_empty());
+        assert_eq!(cleared.query_timeout_ms, None);
+        assert_eq!(cleared.max_result_rows, Some(1000));
+        assert_eq!(cleared.credential_command, "");
+        assert_eq!(cleared.credential_ttl_secs, Some(60));
+    }
+
+    #[tokio::test]
+    async fn saving_the_form_keeps_engine_options_it_leaves_out() {
+        let pool = test_pool().await;
+        let d1 = |extra: Value| {
+            let mut data = json!({"type": "d1", "db_type": "d1", "port": 443});
+            data.as_object_mut()
+                .unwrap()
+                .extend(extra.as_object().unwrap().clone());
+            form(data)
+        };
+        assert_eq!(
+            insert_connection(&pool, &d1(json!({}))).await.unwrap_err(),
+            "Account ID is required"
+        );
+
+        let created = insert_connection(
+            &pool,
+            &d1(json!({"options": {"account_id": "account", "database_id": "billing"}})),
+        )
+        .await
+        .unwrap();
+        let saved = save_connection(&pool, created.id, &d1(json!({})))
+            .await
+            .unwrap();
+        assert_eq!(saved.options.0, created.options.0);
+
+        let error = save_connection(
+            &pool,
+            created.id,
+            &d1(json!({"options": {"account_id": "account"}})),
+        )
+        .await
+        .unwrap_err();
+        assert_eq!(error, "Database ID is required");
+
+        // Switching engines drops options that belong to the old one.
+        let postgres = save_connection(&pool, created.id, &form(json!({})))
+            .await
+            .unwrap();
+        assert!(postgres.options.0.is_empty());
+    }
+}
@@ -3,24 +3,38 @@
 //! This module provides a single set of Tauri commands that work with PostgreSQL,
 //! SQLite, DuckDB, Redis, and ClickHouse databases by dispatching to the appropriate driver.
 
-use crate::commands::pool::{with_pooled_no_retry, with_pooled_read};
+use crate::commands::pool::{ensure_connection, with_pooled_no_retry, with_pooled_read};
+use crate::commands::redis_scripts::{load_redis_script, load_redis_scripts};
+use crate::database::bulk_keys::{BulkKeyJob, BulkKeyManager, BulkKeyPreview, BulkKeyRequest};
 use crate::database::d1::{list_databases, D1DatabaseList};
 use crate::database::driver_factory::{
     create_driver as build_driver, create_driver_with_ssh as build_driver_with_ssh, DriverConfig,
 };
+use crate::database::key_value::{key_value_browser, LiveSource};
+use crate::database::keyspace_analysis::{AnalysisJob, AnalysisManager, AnalysisOptions};
 use crate::database::pool_manager::PoolManager;
-use crate::database::redis::{RedisDriver, RedisKeyDetails, RedisKeyListResponse};
+use crate::database::redis::{RedisKeyDetails, RedisKeyListResponse};
+use crate::database::redis_diagnostics::{
+    server_diagnostics, ClientInfo, LatencyEvent, LatencySample, NodeInfo, SlowlogEntry,
+};
+use crate::database::redis_modules::{RedisModules, JSON_ROOT};
+use crate::database::redis_scripts::{
+    bind_parameters, script_hash, script_runner, FunctionLibrary, ScriptCacheStatus, ScriptCall,
+};
+use crate::database::registry::{driver_descriptors, find_driver, DriverDescriptor};
 use crate::database::sql_policy::{
     ensure_structured_mutations_supported, escape_sql_identifier, format_sql_value,
     validate_raw_sql_value,
 };
-use crate::database::{DatabaseDriver, DatabaseType};
+use crate::database::subscriptions::{SubscriptionInfo, SubscriptionManager};
+use crate::database::{CredentialCommand, DatabaseDriver, DriverCapabilities, QueryLimits};
 use crate::db::models::{
     QueryResult, SchemaOverview, TableDataResponse, TableInfo, TableStructure, TestConnectionResult,
 };
 use crate::ssh_tunnel::SshTunnel;
 use serde::Serialize;
 use sqlx::SqlitePool;
+use std::collections::BTreeMap;
 use std::sync::Arc;
 use tauri::{AppHandle, Emitter, State};
 
@@ -35,13 +49,8 @@ async fn cached_driver(
         .ok_or_else(|| "Connection not found. Please connect first.".to_string())
 }
 
-/// Downcast a pooled driver to a `RedisDriver` for Redis-specific operations.
-fn downcast_redis(driver: &Arc<Box<dyn DatabaseDriver>>) -> Result<&RedisDriver, String> {
-    driver
-        .as_any()
-        .downcast_ref::<RedisDriver>()
-        .ok_or_else(|| "Connection is not a Redis connection".to_string())
-}
+/// Slow log entries fetched per node when the caller does not say.
+const DEFAULT_SLOWLOG_ENTRIES: usize = 128;
 
 #[derive(Clone, Serialize)]
 pub struct RedisScanProgressPayload {
@@ -69,6 +78,8 @@ async fn create_driver_with_ssh(
     ssh_password: Option<String>,
     ssh_key_path: Option<String>,
     ssh_use_key: Option<bool>,
+    on_connect_sql: Option<String>,
+    session_settings: Option<BTreeMap<String, String>>,
 ) -> Result<(Box<dyn DatabaseDriver>, Option<SshTunnel>), String> {
     let ssh_enabled = ssh_enabled.unwrap_or(false);
     let host = if ssh_enabled {
@@ -95,6 +106,7 @@ async fn create_driver_with_ssh(
         password,
         ssl,
         file_path,
+        options: Default::default(),
         ssh_enabled,
         ssh_host,
         ssh_port,
@@ -102,6 +114,10 @@ async fn create_driver_with_ssh(
         ssh_password,
         ssh_key_path,
         ssh_use_key: ssh_use_key.unwrap_or(false),
+        on_connect_sql,
+        session_settings: session_settings.unwrap_or_default(),
+        limits: QueryLimits::default(),
+        credential_command: None,
     })
     .await
 }
@@ -126,6 +142,7 @@ fn create_driver(
         password,
         ssl,
         file_path,
+        options: Default::default(),
         ssh_enabled: false,
         ssh_host: None,
         ssh_port: None,
@@ -133,12 +150,15 @@ fn create_driver(
         ssh_password: None,
         ssh_key_path: None,
         ssh_use_key: false,
+        on_connect_sql: None,
+        session_settings: BTreeMap::new(),
+        limits: QueryLimits::default(),
+        credential_command: None,
     })
 }
 
 fn table_reference(db_type: &str, schema: &str, table: &str) -> Result<String, String> {
-    let engine = DatabaseType::try_from(db_type)?;
-    if engine.qualifies_tables_with_schema() {
+    if find_driver(db_type)?.schema_qualified {
         Ok(format!(
             "\"{}\".\"{}\"",
             escape_sql_identifier(schema),
@@ -149,6 +169,18 @@ fn table_reference(db_type: &str, schema: &str, table: &str) -> Result<String, S
     }
 }
 
+/// What an engine's driver supports, for connections that aren't saved yet.
+#[tauri::command]
+pub async fn get_database_capabilities(db_type: String) -> Result<DriverCapabilities, String> {
+    Ok(find_driver(&db_type)?.capabilities)
+}
+
+/// Every engine a connection can use, with the settings each one reads.
+#[tauri::command]
+pub async fn list_database_drivers() -> Result<Vec<DriverDescriptor>, String> {
+    Ok(driver_descriptors())
+}
+
 #[tauri::command]
 pub async fn d1_list_databases(
     account_id: String,
@@ -175,7 +207,27 @@ pub async fn unified_test_connection(
     ssh_password: Option<String>,
     ssh_key_path: Option<String>,
     ssh_use_key: Option<bool>,
+    on_connect_sql: Option<String>,
+    session_settings: Option<BTreeMap<String, String>>,
+    credential_command: Option<String>,
 ) -> Result<TestConnectionResult, String> {
+    // Run the provider directly so the form tests the command itself rather
+    // than output cached for a saved connection.
+    let password = match CredentialCommand::from_settings(
+        credential_command.as_deref().unwrap_or_default(),
+        Some(0),
+    )? {
+        Some(command) => match command.run().await {
+            Ok(secret) => Some(secret),
+            Err(e) => {
+                return Ok(TestConnectionResult {
+                    success: false,
+                    message: e,
+                })
+            }
+        },
+        None => password,
+    };
     let (driver, _tunnel) = match create_driver_with_ssh(
         &db_type,
         host,
@@ -192,6 +244,8 @@ pub async fn unified_test_connection(
         ssh_password,
         ssh_key_path,
         ssh_use_key,
+        on_connect_sql,
+        session_settings,
     )
     .await
     {
@@ -203,7 +257,7 @@ pub async fn unified_test_connection(
             })
         }
     };
-    driver.test_connection().await
+    driver.test_connection().await.map_err(String::from)
 }
 
 #[tauri::command(rename_all = "snake_case")]
@@ -240,9 +294,11 @@ pub async fn unified_list_tables(
         ssh_password,
         ssh_key_path,
         ssh_use_key,
+        None,
+        None,
     )
     .await?;
-    driver.list_tables().await
+    driver.list_tables().await.map_err(String::from)
 }
 
 #[tauri::command]
@@ -279,6 +335,7 @@ pub async fn unified_get_table_data(
             sort_direction,
         )
         .await
+        .map_err(String::from)
 }
 
 #[tauri::command]
@@ -297,7 +354,10 @@ pub async fn unified_get_table_structure(
     let driver = create_driver(
         &db_type, host, port, database, username, password, ssl, file_path,
     )?;
-    driver.get_table_structure(&schema, &table).await
+    driver
+        .get_table_structure(&schema, &table)
+        .await
+        .map_err(String::from)
 }
 
 #[tauri::command]
@@ -315,7 +375,7 @@ pub async fn unified_execute_query(
     let driver = create_driver(
         &db_type, host, port, database, username, password, ssl, file_path,
     )?;
-    driver.execute_query(&query).await
+    driver.execute_query(&query).await.map_err(String::from)
 }
 
 // ============================================================================
@@ -381,7 +441,7 @@ pub async fn update_table_row(
         table_ref, set_clause, where_clause
     );
 
-    driver.execute_query(&query).await
+    driver.execute_query(&query).await.map_err(String::from)
 }
 
 /// Update a row in a table with raw SQL support
@@ -475,7 +535,7 @@ pub async fn update_table_row_with_raw_sql(
         table_ref, set_clause, where_clause
     );
 
-    driver.execute_query(&query).await
+    driver.execute_query(&query).await.map_err(String::from)
 }
 
 /// Delete a row from a table
@@ -519,7 +579,7 @@ pub async fn delete_table_row(
 
     let query = format!("DELETE FROM {} WHERE {}", table_ref, where_clause);
 
-    driver.execute_query(&query).await
+    driver.execute_query(&query).await.map_err(String::from)
 }
 
 /// Insert a new row into a table
@@ -597,7 +657,7 @@ pub async fn insert_table_row(
         table_ref, columns_clause, values_clause
     );
 
-    driver.execute_query(&query).await
+    driver.execute_query(&query).await.map_err(String::from)
 }
 
 // ============================================================================
@@ -648,8 +708,474 @@ pub async fn redis_search_keys(
         "redis_search_keys",
         || async {
             let driver = cached_driver(&pool_manager, &uuid).await?;
-            downcast_redis(&driver)?
-                .search_keys(&pattern, limit, cursor, make_callback())
+            key_value_browser(&driver)?
+                .search_keys(&pattern, limit, cursor, &make_callback())
+                .await
+        },
+    )
+    .await
+}
+
+/// Start a live Pub/Sub or stream subscription. Messages arrive as
+/// `redis-live-messages` events until it is stopped or fails.
+#[tauri::command]
+pub async fn redis_subscribe(
+    pool_manager: State<'_, Arc<PoolManager>>,
+    sqlite_pool: State<'_, SqlitePool>,
+    subscriptions: State<'_, SubscriptionManager>,
+    uuid: String,
+    source: LiveSource,
+    buffer_size: Option<usize>,
+) -> Result<SubscriptionInfo, String> {
+    with_pooled_no_retry(
+        &pool_manager,
+        sqlite_pool.inner(),
+        &uuid,
+        "redis_subscribe",
+        || async {
+            let driver = cached_driver(&pool_manager, &uuid).await?;
+            subscriptions.start(&uuid, driver, source, buffer_size)
+        },
+    )
+    .await
+}
+
+/// Stop a live subscription
+#[tauri::command]
+pub fn redis_unsubscribe(
+    subscriptions: State<'_, SubscriptionManager>,
+    subscription_id: String,
+) -> bool {
+    subscriptions.stop(&subscription_id)
+}
+
+/// List the live subscriptions of a connection
+#[tauri::command]
+pub fn redis_list_subscriptions(
+    subscriptions: State<'_, SubscriptionManager>,
+    uuid: String,
+) -> Vec<SubscriptionInfo> {
+    subscriptions.list(&uuid)
+}
+
+/// Start a background analysis of the connection's keyspace. Progress
+/// arrives as `redis-analysis-progress` events and the report as a
+/// `redis-analysis-finished` event.
+#[tauri::command]
+pub async fn redis_analyze_keyspace(
+    pool_manager: State<'_, Arc<PoolManager>>,
+    sqlite_pool: State<'_, SqlitePool>,
+    analyses: State<'_, AnalysisManager>,
+    uuid: String,
+    options: Option<AnalysisOptions>,
+) -> Result<AnalysisJob, String> {
+    with_pooled_no_retry(
+        &pool_manager,
+        sqlite_pool.inner(),
+        &uuid,
+        "redis_analyze_keyspace",
+        || async {
+            let driver = cached_driver(&pool_manager, &uuid).await?;
+            analyses.start(&uuid, driver, options.unwrap_or_default())
+        },
+    )
+    .await
+}
+
+/// Cancel a running keyspace analysis
+#[tauri::command]
+pub fn redis_cancel_analysis(analyses: State<'_, AnalysisManager>, job_id: String) -> bool {
+    analyses.cancel(&job_id)
+}
+
+/// The keyspace analysis running for a connection, if any
+#[tauri::command]
+pub fn redis_get_running_analysis(
+    analyses: State<'_, AnalysisManager>,
+    uuid: String,
+) -> Option<AnalysisJob> {
+    analyses.running(&uuid)
+}
+
+/// Dry-run a bulk key operation: count and sample the keys it would touch.
+/// The returned preview id runs it with `redis_run_bulk_keys`.
+#[tauri::command]
+pub async fn redis_preview_bulk_keys(
+    pool_manager: State<'_, Arc<PoolManager>>,
+    sqlite_pool: State<'_, SqlitePool>,
+    bulk_keys: State<'_, BulkKeyManager>,
+    uuid: String,
+    request: BulkKeyRequest,
+) -> Result<BulkKeyPreview, String> {
+    with_pooled_no_retry(
+        &pool_manager,
+        sqlite_pool.inner(),
+        &uuid,
+        "redis_preview_bulk_keys",
+        || async {
+            let driver = cached_driver(&pool_manager, &uuid).await?;
+            bulk_keys.preview(&uuid, &driver, request).await
+        },
+    )
+    .await
+}
+
+/// Run a previewed bulk key operation in the background. Progress arrives
+/// as `redis-bulk-progress` events and the report as a `redis-bulk-finished`
+/// event.
+#[tauri::command]
+pub async fn redis_run_bulk_keys(
+    pool_manager: State<'_, Arc<PoolManager>>,
+    sqlite_pool: State<'_, SqlitePool>,
+    bulk_keys: State<'_, BulkKeyManager>,
+    uuid: String,
+    preview_id: String,
+) -> Result<BulkKeyJob, String> {
+    with_pooled_no_retry(
+        &pool_manager,
+        sqlite_pool.inner(),
+        &uuid,
+        "redis_run_bulk_keys",
+        || async {
+            let request = bulk_keys.previewed_request(&preview_id)?;
+            let target = match request.operation.target_connection() {
+                Some(target_uuid) => {
+                    ensure_connection(&pool_manager, sqlite_pool.inner(), target_uuid).await?;
+                    Some(cached_driver(&pool_manager, target_uuid).await?)
+                }
+                None => None,
+            };
+            let driver = cached_driver(&pool_manager, &uuid).await?;
+            bulk_keys.start(&uuid, &preview_id, driver, target)
+        },
+    )
+    .await
+}
+
+/// Cancel a running bulk key operation
+#[tauri::command]
+pub fn redis_cancel_bulk_keys(bulk_keys: State<'_, BulkKeyManager>, job_id: String) -> bool {
+    bulk_keys.cancel(&job_id)
+}
+
+/// The bulk key operation running for a connection, if any
+#[tauri::command]
+pub fn redis_get_running_bulk_keys(
+    bulk_keys: State<'_, BulkKeyManager>,
+    uuid: String,
+) -> Option<BulkKeyJob> {
+    bulk_keys.running(&uuid)
+}
+
+/// Slow log entries of every node, newest first
+#[tauri::command]
+pub async fn redis_slowlog(
+    pool_manager: State<'_, Arc<PoolManager>>,
+    sqlite_pool: State<'_, SqlitePool>,
+    uuid: String,
+    count: Option<usize>,
+) -> Result<Vec<SlowlogEntry>, String> {
+    with_pooled_read(
+        &pool_manager,
+        sqlite_pool.inner(),
+        &uuid,
+        "redis_slowlog",
+        || async {
+            let driver = cached_driver(&pool_manager, &uuid).await?;
+            server_diagnostics(&driver)?
+                .slowlog(count.unwrap_or(DEFAULT_SLOWLOG_ENTRIES))
+                .await
+        },
+    )
+    .await
+}
+
+/// The latest and worst spike of each latency event
+#[tauri::command]
+pub async fn redis_latency_latest(
+    pool_manager: State<'_, Arc<PoolManager>>,
+    sqlite_pool: State<'_, SqlitePool>,
+    uuid: String,
+) -> Result<Vec<LatencyEvent>, String> {
+    with_pooled_read(
+        &pool_manager,
+        sqlite_pool.inner(),
+        &uuid,
+        "redis_latency_latest",
+        || async {
+            let driver = cached_driver(&pool_manager, &uuid).await?;
+            server_diagnostics(&driver)?.latency_latest().await
+        },
+    )
+    .await
+}
+
+/// The recorded spikes of one latency event
+#[tauri::command]
+pub async fn redis_latency_history(
+    pool_manager: State<'_, Arc<PoolManager>>,
+    sqlite_pool: State<'_, SqlitePool>,
+    uuid: String,
+    event: String,
+) -> Result<Vec<LatencySample>, String> {
+    with_pooled_read(
+        &pool_manager,
+        sqlite_pool.inner(),
+        &uuid,
+        "redis_latency_history",
+        || async {
+            let driver = cached_driver(&pool_manager, &uuid).await?;
+            server_diagnostics(&driver)?.latency_history(&event).await
+        },
+    )
+    .await
+}
+
+/// The clients connected to every node
+#[tauri::command]
+pub async fn redis_client_list(
+    pool_manager: State<'_, Arc<PoolManager>>,
+    sqlite_pool: State<'_, SqlitePool>,
+    uuid: String,
+) -> Result<Vec<ClientInfo>, String> {
+    with_pooled_read(
+        &pool_manager,
+        sqlite_pool.inner(),
+        &uuid,
+        "redis_client_list",
+        || async {
+            let driver = cached_driver(&pool_manager, &uuid).await?;
+            server_diagnostics(&driver)?.client_list().await
+        },
+    )
+    .await
+}
+
+/// `INFO` of every node as typed sections
+#[tauri::command]
+pub async fn redis_server_info(
+    pool_manager: State<'_, Arc<PoolManager>>,
+    sqlite_pool: State<'_, SqlitePool>,
+    uuid: String,
+    section: Option<String>,
+) -> Result<Vec<NodeInfo>, String> {
+    with_pooled_read(
+        &pool_manager,
+        sqlite_pool.inner(),
+        &uuid,
+        "redis_server_info",
+        || async {
+            let driver = cached_driver(&pool_manager, &uuid).await?;
+            server_diagnostics(&driver)?
+                .server_info(section.as_deref())
+                .await
+        },
+    )
+    .await
+}
+
+/// Kill a client the user confirmed by id and address. Returns `false` if it
+/// had already disconnected.
+#[tauri::command]
+pub async fn redis_kill_client(
+    pool_manager: State<'_, Arc<PoolManager>>,
+    sqlite_pool: State<'_, SqlitePool>,
+    uuid: String,
+    node: Option<String>,
+    client_id: u64,
+    addr: String,
+) -> Result<bool, String> {
+    with_pooled_no_retry(
+        &pool_manager,
+        sqlite_pool.inner(),
+        &uuid,
+        "redis_kill_client",
+        || async {
+            let driver = cached_driver(&pool_manager, &uuid).await?;
+            server_diagnostics(&driver)?
+                .kill_client(node.as_deref(), client_id, &addr)
+                .await
+        },
+    )
+    .await
+}
+
+/// Run a saved Lua script, binding `inputs` to its typed parameters
+#[tauri::command]
+pub async fn redis_run_script(
+    pool_manager: State<'_, Arc<PoolManager>>,
+    sqlite_pool: State<'_, SqlitePool>,
+    uuid: String,
+    script_id: i64,
+    inputs: Option<serde_json::Map<String, serde_json::Value>>,
+) -> Result<QueryResult, String> {
+    let script = load_redis_script(sqlite_pool.inner(), &uuid, script_id)
+        .await?
+        .ok_or_else(|| format!("Script {} not found", script_id))?;
+    let call = bind_parameters(&script.parameters, &inputs.unwrap_or_default())?;
+    with_pooled_no_retry(
+        &pool_manager,
+        sqlite_pool.inner(),
+        &uuid,
+        "redis_run_script",
+        || async {
+            let driver = cached_driver(&pool_manager, &uuid).await?;
+            script_runner(&driver)?
+                .eval_script(&script.source, &call, script.read_only)
+                .await
+        },
+    )
+    .await
+}
+
+/// Cache a saved script on the server with `SCRIPT LOAD`, returning its SHA1
+#[tauri::command]
+pub async fn redis_load_script(
+    pool_manager: State<'_, Arc<PoolManager>>,
+    sqlite_pool: State<'_, SqlitePool>,
+    uuid: String,
+    script_id: i64,
+) -> Result<String, String> {
+    let script = load_redis_script(sqlite_pool.inner(), &uuid, script_id)
+        .await?
+        .ok_or_else(|| format!("Script {} not found", script_id))?;
+    with_pooled_no_retry(
+        &pool_manager,
+        sqlite_pool.inner(),
+        &uuid,
+        "redis_load_script",
+        || async {
+            let driver = cached_driver(&pool_manager, &uuid).await?;
+            script_runner(&driver)?.load_script(&script.source).await
+        },
+    )
+    .await
+}
+
+/// Whether the server has each saved script of the connection cached
+#[tauri::command]
+pub async fn redis_get_script_cache(
+    pool_manager: State<'_, Arc<PoolManager>>,
+    sqlite_pool: State<'_, SqlitePool>,
+    uuid: String,
+) -> Result<Vec<ScriptCacheStatus>, String> {
+    let scripts = load_redis_scripts(sqlite_pool.inner(), &uuid).await?;
+    let hashes: Vec<String> = scripts
+        .iter()
+        .map(|script| script_hash(&script.source))
+        .collect();
+    let cached = with_pooled_read(
+        &pool_manager,
+        sqlite_pool.inner(),
+        &uuid,
+        "redis_get_script_cache",
+        || async {
+            let driver = cached_driver(&pool_manager, &uuid).await?;
+            script_runner(&driver)?.scripts_exist(&hashes).await
+        },
+    )
+    .await?;
+    Ok(scripts
+        .iter()
+        .zip(hashes)
+        .zip(cached)
+        .map(|((script, sha), cached)| ScriptCacheStatus {
+            id: script.id,
+            sha,
+            cached,
+        })
+        .collect())
+}
+
+/// Function libraries loaded on the server, optionally with their source
+#[tauri::command]
+pub async fn redis_list_functions(
+    pool_manager: State<'_, Arc<PoolManager>>,
+    sqlite_pool: State<'_, SqlitePool>,
+    uuid: String,
+    with_code: Option<bool>,
+) -> Result<Vec<FunctionLibrary>, String> {
+    with_pooled_read(
+        &pool_manager,
+        sqlite_pool.inner(),
+        &uuid,
+        "redis_list_functions",
+        || async {
+            let driver = cached_driver(&pool_manager, &uuid).await?;
+            script_runner(&driver)?
+                .list_functions(with_code.unwrap_or(false))
+                .await
+        },
+    )
+    .await
+}
+
+/// Load a function library from its `#!lua name=...` source
+#[tauri::command]
+pub async fn redis_load_function(
+    pool_manager: State<'_, Arc<PoolManager>>,
+    sqlite_pool: State<'_, SqlitePool>,
+    uuid: String,
+    source: String,
+    replace: Option<bool>,
+) -> Result<String, String> {
+    with_pooled_no_retry(
+        &pool_manager,
+        sqlite_pool.inner(),
+        &uuid,
+        "redis_load_function",
+        || async {
+            let driver = cached_driver(&pool_manager, &uuid).await?;
+            script_runner(&driver)?
+                .load_function_library(&source, replace.unwrap_or(false))
+                .await
+        },
+    )
+    .await
+}
+
+/// Delete a function library
+#[tauri::command]
+pub async fn redis_delete_function(
+    pool_manager: State<'_, Arc<PoolManager>>,
+    sqlite_pool: State<'_, SqlitePool>,
+    uuid: String,
+    library: String,
+) -> Result<(), String> {
+    with_pooled_no_retry(
+        &pool_manager,
+        sqlite_pool.inner(),
+        &uuid,
+        "redis_delete_function",
+        || async {
+            let driver = cached_driver(&pool_manager, &uuid).await?;
+            script_runner(&driver)?
+                .delete_function_library(&library)
+                .await
+        },
+    )
+    .await
+}
+
+/// Call a library function with `FCALL`, or `FCALL_RO` when `read_only`
+#[tauri::command]
+pub async fn redis_call_function(
+    pool_manager: State<'_, Arc<PoolManager>>,
+    sqlite_pool: State<'_, SqlitePool>,
+    uuid: String,
+    function: String,
+    call: ScriptCall,
+    read_only: Option<bool>,
+) -> Result<QueryResult, String> {
+    with_pooled_no_retry(
+        &pool_manager,
+        sqlite_pool.inner(),
+        &uuid,
+        "redis_call_function",
+        || async {
+            let driver = cached_driver(&pool_manager, &uuid).await?;
+            script_runner(&driver)?
+                .call_function(&function, &call, read_only.unwrap_or(false))
                 .await
         },
     )
@@ -672,7 +1198,7 @@ pub async fn redis_get_key_details(
         "redis_get_key_details",
         || async {
             let driver = cached_driver(&pool_manager, &uuid).await?;
-            downcast_redis(&driver)?.get_key_details(&key).await
+            key_value_browser(&driver)?.get_key_details(&key).await
         },
     )
     .await
@@ -693,7 +1219,7 @@ pub async fn redis_delete_key(
         "redis_delete_key",
         || async {
             let driver = cached_driver(&pool_manager, &uuid).await?;
-            downcast_redis(&driver)?.delete_key(&key).await
+            key_value_browser(&driver)?.delete_key(&key).await
         },
     )
     .await
@@ -716,7 +1242,7 @@ pub async fn redis_set_key(
         "redis_set_key",
         || async {
             let driver = cached_driver(&pool_manager, &uuid).await?;
-            downcast_redis(&driver)?.set_key(&key, &value, ttl).await
+            key_value_browser(&driver)?.set_key(&key, &value, ttl).await
         },
     )
     .await
@@ -739,7 +1265,7 @@ pub async fn redis_set_list_key(
         "redis_set_list_key",
         || async {
             let driver = cached_driver(&pool_manager, &uuid).await?;
-            downcast_redis(&driver)?
+            key_value_browser(&driver)?
                 .set_list_key(&key, &values, ttl)
                 .await
         },
@@ -764,7 +1290,7 @@ pub async fn redis_set_set_key(
         "redis_set_set_key",
         || async {
             let driver = cached_driver(&pool_manager, &uuid).await?;
-            downcast_redis(&driver)?
+            key_value_browser(&driver)?
                 .set_set_key(&key, &values, ttl)
                 .await
         },
@@ -789,7 +1315,7 @@ pub async fn redis_set_hash_key(
         "redis_set_hash_key",
         || async {
             let driver = cached_driver(&pool_manager, &uuid).await?;
-            downcast_redis(&driver)?
+            key_value_browser(&driver)?
                 .set_hash_key(&key, &fields, ttl)
                 .await
         },
@@ -814,7 +1340,7 @@ pub async fn redis_set_zset_key(
         "redis_set_zset_key",
         || async {
             let driver = cached_driver(&pool_manager, &uuid).await?;
-            downcast_redis(&driver)?
+            key_value_browser(&driver)?
                 .set_zset_key(&key, &members, ttl)
                 .await
         },
@@ -838,7 +1364,98 @@ pub async fn redis_update_ttl(
         "redis_update_ttl",
         || async {
             let driver = cached_driver(&pool_manager, &uuid).await?;
-            downcast_redis(&driver)?.update_ttl(&key, ttl).await
+            key_value_browser(&driver)?.update_ttl(&key, ttl).await
+        },
+    )
+    .await
+}
+
+/// The modules loaded on a Redis server (RedisJSON, RediSearch, ...)
+#[tauri::command]
+pub async fn redis_get_modules(
+    pool_manager: State<'_, Arc<PoolManager>>,
+    sqlite_pool: State<'_, SqlitePool>,
+    uuid: String,
+) -> Result<RedisModules, String> {
+    with_pooled_read(
+        &pool_manager,
+        sqlite_pool.inner(),
+        &uuid,
+        "redis_get_modules",
+        || async {
+            let driver = cached_driver(&pool_manager, &uuid).await?;
+            key_value_browser(&driver)?.modules().await
+        },
+    )
+    .await
+}
+
+/// Read a RedisJSON document at `path` (the whole document by default)
+#[tauri::command]
+pub async fn redis_json_get(
+    pool_manager: State<'_, Arc<PoolManager>>,
+    sqlite_pool: State<'_, SqlitePool>,
+    uuid: String,
+    key: String,
+    path: Option<String>,
+) -> Result<serde_json::Value, String> {
+    let path = path.unwrap_or_else(|| JSON_ROOT.to_string());
+    with_pooled_read(
+        &pool_manager,
+        sqlite_pool.inner(),
+        &uuid,
+        "redis_json_get",
+        || async {
+            let driver = cached_driver(&pool_manager, &uuid).await?;
+            key_value_browser(&driver)?.json_get(&key, &path).await
+        },
+    )
+    .await
+}
+
+/// Write `value` at `path` of a RedisJSON document, creating it at the root
+#[tauri::command]
+pub async fn redis_json_set(
+    pool_manager: State<'_, Arc<PoolManager>>,
+    sqlite_pool: State<'_, SqlitePool>,
+    uuid: String,
+    key: String,
+    path: Option<String>,
+    value: serde_json::Value,
+) -> Result<(), String> {
+    let path = path.unwrap_or_else(|| JSON_ROOT.to_string());
+    with_pooled_no_retry(
+        &pool_manager,
+        sqlite_pool.inner(),
+        &uuid,
+        "redis_json_set",
+        || async {
+            let driver = cached_driver(&pool_manager, &uuid).await?;
+            key_value_browser(&driver)?
+                .json_set(&key, &path, &value)
+                .await
+        },
+    )
+    .await
+}
+
+/// Delete the values at `path` of a RedisJSON document, returning the count
+#[tauri::command]
+pub async fn redis_json_delete(
+    pool_manager: State<'_, Arc<PoolManager>>,
+    sqlite_pool: State<'_, SqlitePool>,
+    uuid: String,
+    key: String,
+    path: String,
+) -> Result<u64, String> {
+    with_pooled_no_retry(
+        &pool_manager,
+        sqlite_pool.inner(),
+        &uuid,
+        "redis_json_delete",
+        || async {
+            let driver = cached_driver(&pool_manager, &uuid).await?;
+            key_value_browser(&driver)?.json_delete(&key, &path).await
         },
     )
     .await
@@ -879,8 +1496,10 @@ pub async fn unified_get_schema_overview(
         ssh_password,
         ssh_key_path,
         ssh_use_key,
+        None,
+        None,
     )
     .await?;
 
-    driver.get_schema_overview().await
+    driver.get_schema_overview().await.map_err(String::from)
 }
@@ -5,7 +5,10 @@ use std::sync::Arc;
 use serde::Serialize;
 use tauri::State;
 
+use crate::mcp::approval::{ApprovalBroker, ApprovalRequest};
+use crate::mcp::audit::{self, AuditEntry, AuditFilter};
 use crate::mcp::control::{self, McpControl};
+use crate::mcp::tokens::{self, CreatedMcpToken, McpToken, McpTokenInput};
 
 #[derive(Serialize)]
 pub struct McpStatus {
@@ -61,3 +64,84 @@ pub async fn mcp_regenerate_token(
     }
     build_status(control.inner()).await
 }
+
+/// Write requests from MCP clients still waiting for the user's decision.
+#[tauri::command]
+pub async fn mcp_list_pending_approvals(
+    approvals: State<'_, Arc<ApprovalBroker>>,
+) -> Result<Vec<ApprovalRequest>, String> {
+    Ok(approvals.pending())
+}
+
+/// Approve or reject a pending write; the blocked MCP tool call then resumes.
+#[tauri::command]
+pub async fn mcp_resolve_approval(
+    approvals: State<'_, Arc<ApprovalBroker>>,
+    id: String,
+    approved: bool,
+) -> Result<(), String> {
+    approvals.resolve(&id, approved)
+}
+
+/// Named MCP tokens, without their secrets.
+#[tauri::command]
+pub async fn mcp_list_tokens(control: State<'_, Arc<McpControl>>) -> Result<Vec<McpToken>, String> {
+    tokens::list(control.sqlite_pool()).await
+}
+
+/// Create a scoped token. The returned secret is not shown again.
+#[tauri::command]
+pub async fn mcp_create_token(
+    control: State<'_, Arc<McpControl>>,
+    token: McpTokenInput,
+) -> Result<CreatedMcpToken, String> {
+    tokens::create(control.sqlite_pool(), &token).await
+}
+
+/// Scope changes apply to the token's next request; no restart is needed.
+#[tauri::command]
+pub async fn mcp_update_token(
+    control: State<'_, Arc<McpControl>>,
+    id: i64,
+    token: McpTokenInput,
+) -> Result<McpToken, String> {
+    tokens::update(control.sqlite_pool(), id, &token).await
+}
+
+#[tauri::command]
+pub async fn mcp_delete_token(control: State<'_, Arc<McpControl>>, id: i64) -> Result<(), String> {
+    tokens::delete(control.sqlite_pool(), id).await
+}
+
+/// MCP audit entries matching `filter`, newest first.
+#[tauri::command]
+pub async fn mcp_query_audit_log(
+    control: State<'_, Arc<McpControl>>,
+    filter: AuditFilter,
+) -> Result<Vec<AuditEntry>, String> {
+    audit::query(control.sqlite_pool(), &filter).await
+}
+
+/// Matching audit entries rendered as `"json"` or `"csv"` for saving.
+#[tauri::command]
+pub async fn mcp_export_audit_log(
+    control: State<'_, Arc<McpControl>>,
+    filter: AuditFilter,
+    format: String,
+) -> Result<String, String> {
+    audit::export(control.sqlite_pool(), &filter, &format).await
+}
+
+#[tauri::command]
+pub async fn mcp_get_audit_retention(control: State<'_, Arc<McpControl>>) -> Result<i64, String> {
+    audit::retention_days(control.sqlite_pool()).await
+}
+
+/// Keep audit entries for `days` days; `0` keeps them forever.
+#[tauri::command]
+pub async fn mcp_set_audit_retention(
+    control: State<'_, Arc<McpControl>>,
+    days: i64,
+) -> Result<(), String> {
+    audit::set_retention_days(control.sqlite_pool(), days).await
+}
@@ -1,10 +1,12 @@
 pub mod ai;
+pub mod connection_groups;
 pub mod connections;
 pub mod database;
 pub mod mcp;
 pub mod pool;
 pub mod postgres;
 pub mod queries;
+pub mod redis_scripts;
 pub mod saved_views;
 pub mod settings;
 #[cfg(desktop)]
@@ -4,12 +4,16 @@
 
 use std::sync::Arc;
 
+use crate::database::bulk_keys::BulkKeyManager;
+use crate::database::keyspace_analysis::AnalysisManager;
 use crate::database::mutation::{
     build_delete, build_insert, build_update, MutationPlan, MutationValue,
 };
 use crate::database::pool_manager::{ConnectionStatus, PoolManager};
+use crate::database::registry::find_driver;
 use crate::database::sql_policy::ensure_structured_mutations_supported;
-use crate::database::DatabaseType;
+use crate::database::subscriptions::SubscriptionManager;
+use crate::database::{DatabaseType, DriverCapabilities, DriverError};
 use crate::db::models::{
     Connection, CreateTableRequest, QueryResult, TableInfo, TestConnectionResult,
 };
@@ -35,10 +39,8 @@ pub async fn pool_connect(
     // connect can't race a concurrent ensure_connection/reconnect.
     let lock = pool_manager.get_connect_lock(&uuid).await;
     let _guard = lock.lock().await;
-    crate::docker::ensure_created_connection_running(sqlite_pool.inner(), &uuid).await?;
-    let config = crate::database::utils::get_connection_config(sqlite_pool.inner(), &uuid).await?;
 
-    match pool_manager.connect(&uuid, config).await {
+    match pool_manager.connect_saved(sqlite_pool.inner(), &uuid).await {
         Ok(_) => Ok(ConnectionStatusResponse {
             status: ConnectionStatus::Connected,
             error: None,
@@ -54,8 +56,14 @@ pub async fn pool_connect(
 #[tauri::command]
 pub async fn pool_disconnect(
     pool_manager: State<'_, Arc<PoolManager>>,
+    subscriptions: State<'_, SubscriptionManager>,
+    analyses: State<'_, AnalysisManager>,
+    bulk_keys: State<'_, BulkKeyManager>,
     uuid: String,
 ) -> Result<(), String> {
+    subscriptions.stop_connection(&uuid);
+    analyses.cancel_connection(&uuid);
+    bulk_keys.cancel_connection(&uuid);
     pool_manager.disconnect(&uuid).await;
     Ok(())
 }
@@ -80,8 +88,28 @@ pub async fn pool_health_check(
     pool_manager.health_check(&uuid).await
 }
 
+/// What the connection's driver supports. Open connections and sessions ask
+/// their driver; others answer from the saved connection's engine, without
+/// connecting.
+#[tauri::command]
+pub async fn pool_get_capabilities(
+    pool_manager: State<'_, Arc<PoolManager>>,
+    sqlite_pool: State<'_, SqlitePool>,
+    uuid: String,
+) -> Result<DriverCapabilities, String> {
+    if let Some(driver) = pool_manager.get_cached(&uuid).await {
+        return Ok(driver.capabilities());
+    }
+    let db_type: String = sqlx::query_scalar("SELECT db_type FROM connections WHERE uuid = ?")
+        .bind(PoolManager::connection_uuid(&uuid))
+        .fetch_one(sqlite_pool.inner())
+        .await
+        .map_err(|error| format!("Failed to get connection: {error}"))?;
+    Ok(find_driver(&db_type)?.capabilities)
+}
+
 /// Ensure connection exists, create if not (serialized per-UUID).
-async fn ensure_connection(
+pub(crate) async fn ensure_connection(
     pool_manager: &PoolManager,
     sqlite_pool: &SqlitePool,
     uuid: &str,
@@ -101,9 +129,7 @@ async fn reconnect(
     // Disconnect stale connection
     pool_manager.disconnect_locked(uuid).await;
 
-    crate::docker::ensure_created_connection_running(sqlite_pool, uuid).await?;
-    let config = crate::database::utils::get_connection_config(sqlite_pool, uuid).await?;
-    pool_manager.connect(uuid, config).await?;
+    pool_manager.connect_saved(sqlite_pool, uuid).await?;
     Ok(())
 }
 
@@ -121,33 +147,40 @@ async fn read_retry_policy(pool_manager: &PoolManager, uuid: &str) -> RetryPolic
     }
 }
 
-async fn execute_with_retry_policy<T, Operation, OperationFuture, Reconnect, ReconnectFuture>(
+/// Run `operation`, reconnecting and retrying once when the policy allows it
+/// and the failure looks like a dead connection. Errors the server reported
+/// against a live connection (syntax, permissions, constraints) are returned
+/// as they are, since a fresh connection would fail the same way.
+async fn execute_with_retry_policy<T, E, Operation, OperationFuture, Reconnect, ReconnectFuture>(
     operation_name: &str,
     retry_policy: RetryPolicy,
     mut operation: Operation,
     reconnect: Reconnect,
 ) -> Result<T, String>
 where
+    E: Into<DriverError>,
     Operation: FnMut() -> OperationFuture,
-    OperationFuture: std::future::Future<Output = Result<T, String>>,
+    OperationFuture: std::future::Future<Output = Result<T, E>>,
     Reconnect: FnOnce() -> ReconnectFuture,
     ReconnectFuture: std::future::Future<Output = Result<(), String>>,
 {
-    match operation().await {
-        Ok(result) => Ok(result),
-        Err(error) if retry_policy == RetryPolicy::Never => Err(error),
-        Err(error) => {
-            println!(
-                "[Pool] {} failed: {}, retrying with fresh connection",
-                operation_name, error
-            );
-            reconnect().await?;
-            operation().await
-        }
+    let error: DriverError = match operation().await {
+        Ok(result) => return Ok(result),
+        Err(error) => error.into(),
+    };
+    if retry_policy == RetryPolicy::Never || !error.reconnect_may_help() {
+        return Err(error.into());
     }
+
+    println!(
+        "[Pool] {} failed: {}, retrying with fresh connection",
+        operation_name, error
+    );
+    reconnect().await?;
+    operation().await.map_err(|error| error.into().into())
 }
 
-pub(crate) async fn with_pooled_read<T, F, Fut>(
+pub(crate) async fn with_pooled_read<T, E, F, Fut>(
     pool_manager: &PoolManager,
     sqlite_pool: &SqlitePool,
     uuid: &str,
@@ -155,8 +188,9 @@ pub(crate) async fn with_pooled_read<T, F, Fut>(
     operation: F,
 ) -> Result<T, String>
 where
+    E: Into<DriverError>,
     F: FnMut() -> Fut,
-    Fut: std::future::Future<Output = Result<T, String>>,
+    Fut: std::future::Future<Output = Result<T, E>>,
 {
     ensure_connection(pool_manager, sqlite_pool, uuid).await?;
     execute_with_retry_policy(
@@ -168,7 +202,7 @@ where
     .await
 }
 
-pub(crate) async fn with_pooled_no_retry<T, F, Fut>(
+pub(crate) async fn with_pooled_no_retry<T, E, F, Fut>(
     pool_manager: &PoolManager,
     sqlite_pool: &SqlitePool,
     uuid: &str,
@@ -176,11 +210,14 @@ pub(crate) async fn with_pooled_no_retry<T, F, Fut>(
     operation: F,
 ) -> Result<T, String>
 where
+    E: Into<DriverError>,
     F: FnOnce() -> Fut,
-    Fut: std::future::Future<Output = Result<T, String>>,
+    Fut: std::future::Future<Output = Result<T, E>>,
 {
     ensure_connection(pool_manager, sqlite_pool, uuid).await?;
-    operation().await
+    operation()
+        .await
+        .map_err(|error| String::from(error.into()))
 }
 
 /// List tables using the pooled connection (auto-connects if needed, auto-retries on error)
@@ -201,6 +238,38 @@ pub async fn pool_list_tables(
     .await
 }
 
+/// List the databases on the connection's server (auto-connects if needed, auto-retries on error)
+#[tauri::command]
+pub async fn pool_list_databases(
+    pool_manager: State<'_, Arc<PoolManager>>,
+    sqlite_pool: State<'_, SqlitePool>,
+    uuid: String,
+) -> Result<Vec<String>, String> {
+    with_pooled_read(
+        &pool_manager,
+        sqlite_pool.inner(),
+        &uuid,
+        "list_databases",
+        || pool_manager.list_databases(&uuid),
+    )
+    .await
+}
+
+/// Open a pooled session on another database of the connection's server,
+/// reusing its credentials and SSH tunnel. Returns the session's pool key,
+/// which the other `pool_*` commands accept in place of the connection UUID.
+#[tauri::command]
+pub async fn pool_open_database(
+    pool_manager: State<'_, Arc<PoolManager>>,
+    sqlite_pool: State<'_, SqlitePool>,
+    uuid: String,
+    database: String,
+) -> Result<String, String> {
+    let key = PoolManager::database_session_key(&uuid, &database);
+    ensure_connection(&pool_manager, sqlite_pool.inner(), &key).await?;
+    Ok(key)
+}
+
 /// Get table data using the pooled connection (auto-connects if needed, auto-retries on error)
 #[tauri::command]
 pub async fn pool_get_table_data(
@@ -267,7 +336,10 @@ pub async fn pool_preview_create_table(
     request: CreateTableRequest,
 ) -> Result<String, String> {
     ensure_connection(&pool_manager, sqlite_pool.inner(), &uuid).await?;
-    pool_manager.preview_create_table(&uuid, &request).await
+    pool_manager
+        .preview_create_table(&uuid, &request)
+        .await
+        .map_err(String::from)
 }
 
 #[tauri::command]
@@ -278,7 +350,10 @@ pub async fn pool_create_table(
     request: CreateTableRequest,
 ) -> Result<TableInfo, String> {
     ensure_connection(&pool_manager, sqlite_pool.inner(), &uuid).await?;
-    pool_manager.create_table(&uuid, &request).await
+    pool_manager
+        .create_table(&uuid, &request)
+        .await
+        .map_err(String::from)
 }
 
 /// Execute query using the pooled connection (auto-connects if needed, auto-retries on error)
@@ -360,7 +435,10 @@ async fn run_mutation(
     mutation: &MutationPlan,
 ) -> Result<QueryResult, String> {
     ensure_connection(pool_manager, sqlite_pool, uuid).await?;
-    pool_manager.execute_mutation(uuid, mutation).await
+    pool_manager
+        .execute_mutation(uuid, mutation)
+        .await
+        .map_err(String::from)
 }
 
 /// Update a row in a table using the pooled connection
@@ -427,6 +505,7 @@ pub async fn pool_insert_table_row(
 #[cfg(test)]
 mod tests {
     use super::{execute_with_retry_policy, RetryPolicy};
+    use crate::database::{DriverError, DriverErrorCategory};
     use std::cell::Cell;
 
     #[tokio::test]
@@ -480,4 +559,32 @@ mod tests {
         assert_eq!(operation_calls.get(), 2);
         assert_eq!(reconnect_calls.get(), 1);
     }
+
+    #[tokio::test]
+    async fn reconnect_once_policy_does_not_retry_server_reported_errors() {
+        let operation_calls = Cell::new(0);
+        let reconnect_calls = Cell::new(0);
+
+        let result: Result<(), String> = execute_with_retry_policy(
+            "safe read",
+            RetryPolicy::ReconnectOnce,
+            || async {
+                operation_calls.set(operation_calls.get() + 1);
+                Err(DriverError::new(
+                    DriverErrorCategory::Syntax,
+                    "syntax error at or near \"FORM\"",
+                )
+                .with_code("42601"))
+            },
+            || async {
+                reconnect_calls.set(reconnect_calls.get() + 1);
+                Ok(())
+            },
+        )
+        .await;
+
+        assert_eq!(result, Err("syntax error at or near \"FORM\"".to_string()));
+        assert_eq!(operation_calls.get(), 1);
+        assert_eq!(reconnect_calls.get(), 0);
+    }
 }
@@ -1,14 +1,14 @@
 use crate::database::filter::{classify_column_type, FilterDialect};
-use crate::database::{query_returns_rows, MAX_QUERY_RESULT_ROWS};
+use crate::database::limits::DEFAULT_MAX_RESULT_ROWS;
+use crate::database::{cell, postgres_values};
+use crate::database::{query_returns_rows, DriverError, QueryLimits};
 use crate::db::models::{
     ColumnInfo, ForeignKeyInfo, IndexInfo, QueryResult, TableDataResponse, TableInfo,
     TableStructure, TestConnectionResult,
 };
 use crate::ssh_tunnel::{SshAuth, SshTunnel};
 use futures_util::{StreamExt, TryStreamExt};
-use serde_json::{json, Value};
 use sqlx::postgres::PgPoolOptions;
-use sqlx::{Column, Row, TypeInfo};
 
 fn build_connection_string(
     host: &str,
@@ -40,7 +40,9 @@ pub async fn test_connection(
     ssh_password: Option<String>,
     ssh_key_path: Option<String>,
     ssh_use_key: Option<bool>,
+    connect_timeout_ms: Option<i64>,
 ) -> Result<TestConnectionResult, String> {
+    let limits = QueryLimits::from_settings(None, connect_timeout_ms, None, None)?;
     let _tunnel: Option<SshTunnel>;
     println!(
         "[test_connection] SSH params: enabled={:?}, host={:?}, port={:?}, user={:?}, use_key={:?}, key_path={:?}",
@@ -60,9 +62,8 @@ pub async fn test_connection(
             Some(ssh_key_path_val.as_str()),
         );
 
-        // Use a 20 second timeout for SSH tunnel creation (can take longer due to network/auth)
         match tokio::time::timeout(
-            std::time::Duration::from_secs(20),
+            limits.connect_timeout,
             SshTunnel::new(
                 &ssh_host_val,
                 ssh_port_val,
@@ -88,7 +89,7 @@ pub async fn test_connection(
             Err(_) => {
                 return Ok(TestConnectionResult {
                     success: false,
-                    message: "SSH tunnel connection timed out after 20 seconds".to_string(),
+                    message: format!("SSH tunnel: {}", limits.connect_timeout_message()),
                 });
             }
         }
@@ -106,12 +107,11 @@ pub async fn test_connection(
         ssl,
     );
 
-    // Use a 10 second timeout for connection (longer for SSH tunnel overhead)
     match tokio::time::timeout(
-        std::time::Duration::from_secs(15),
+        limits.connect_timeout,
         PgPoolOptions::new()
             .max_connections(1)
-            .acquire_timeout(std::time::Duration::from_secs(8))
+            .acquire_timeout(limits.connect_timeout)
             .connect(&conn_str),
     )
     .await
@@ -136,7 +136,7 @@ pub async fn test_connection(
         }),
         Err(_) => Ok(TestConnectionResult {
             success: false,
-            message: "Connection timed out after 10 seconds".to_string(),
+            message: limits.connect_timeout_message(),
         }),
     }
 }
@@ -240,83 +240,18 @@ pub async fn get_table_data(
 
     pool.close().await;
 
-    let data: Vec<Value> = rows
+    let columns = rows
+        .first()
+        .map(postgres_values::result_columns)
+        .unwrap_or_default();
+    let data = rows
         .iter()
-        .map(|row| {
-            let mut obj = serde_json::Map::new();
-            for (i, col) in row.columns().iter().enumerate() {
-                let type_name = col.type_info().name();
-                let value: Value = match type_name {
-                    "INT2" => row
-                        .try_get::<i16, _>(i)
-                        .map(|v| json!(v))
-                        .unwrap_or(Value::Null),
-                    "INT4" => row
-                        .try_get::<i32, _>(i)
-                        .map(|v| json!(v))
-                        .unwrap_or(Value::Null),
-                    "INT8" => row
-                        .try_get::<i64, _>(i)
-                        .map(|v| json!(v))
-                        .unwrap_or(Value::Null),
-                    "FLOAT4" => row
-                        .try_get::<f32, _>(i)
-                        .map(|v| json!(v))
-                        .unwrap_or(Value::Null),
-                    "FLOAT8" | "NUMERIC" => row
-                        .try_get::<f64, _>(i)
-                        .map(|v| json!(v))
-                        .unwrap_or(Value::Null),
-                    "BOOL" => row
-                        .try_get::<bool, _>(i)
-                        .map(|v| json!(v))
-                        .unwrap_or(Value::Null),
-                    "TEXT" | "VARCHAR" | "CHAR" | "BPCHAR" | "NAME" => row
-                        .try_get::<String, _>(i)
-                        .map(|v| json!(v))
-                        .unwrap_or(Value::Null),
-                    "UUID" => row
-                        .try_get::<uuid::Uuid, _>(i)
-                        .map(|v| json!(v.to_string()))
-                        .unwrap_or(Value::Null),
-                    "TIMESTAMP" | "TIMESTAMPTZ" => row
-                        .try_get::<chrono::NaiveDateTime, _>(i)
-                        .map(|v| json!(v.to_string()))
-                        .or_else(|_| {
-                            row.try_get::<chrono::DateTime<chrono::Utc>, _>(i)
-                                .map(|v| json!(v.to_string()))
-                        })
-                        .unwrap_or(Value::Null),
-                    "DATE" => row
-                        .try_get::<chrono::NaiveDate, _>(i)
-                        .map(|v| json!(v.to_string()))
-                        .unwrap_or(Value::Null),
-                    "TIME" | "TIMETZ" => row
-                        .try_get::<chrono::NaiveTime, _>(i)
-                        .map(|v| json!(v.to_string()))
-                        .unwrap_or(Value::Null),
-                    "JSON" | "JSONB" => row
-                        .try_get::<serde_json::Value, _>(i)
-                        .unwrap_or(Value::Null),
-                    "BYTEA" => row
-                        .try_get::<Vec<u8>, _>(i)
-                        .map(|v| json!(format!("\\x{}", hex::encode(&v))))
-                        .unwrap_or(Value::Null),
-                    _ => {
-                        // For any other type, try to get as string
-                        row.try_get::<String, _>(i)
-                            .map(|v| json!(v))
-                            .unwrap_or_else(|_| json!(format!("<{}>", type_name)))
-                    }
-                };
-                obj.insert(col.name().to_string(), value);
-            }
-            Value::Object(obj)
-        })
+        .map(|row| cell::row_object(&columns, &postgres_values::row_values(row)))
         .collect();
 
     Ok(TableDataResponse {
         data,
+        columns,
         total,
         page,
         limit,
@@ -477,24 +412,23 @@ pub async fn execute_query(
         return match sqlx::query(&query).execute(&pool).await {
             Ok(result) => {
                 pool.close().await;
-                let rows_affected = result.rows_affected();
-                Ok(QueryResult {
-                    data: vec![],
-                    row_count: rows_affected as i64,
-                    truncated: false,
-                    rows_affected: Some(rows_affected),
-                    error: None,
-                    time_taken_ms: Some(start_time.elapsed().as_millis()),
-                })
+                Ok(QueryResult::from_affected(
+                    result.rows_affected(),
+                    start_time,
+                ))
             }
             Err(e) => {
                 pool.close().await;
                 Ok(QueryResult {
                     data: vec![],
+                    columns: Vec::new(),
+                    rows: Vec::new(),
+                    edit_target: None,
                     row_count: 0,
                     truncated: false,
                     rows_affected: None,
                     error: Some(e.to_string()),
+                    error_details: Some(DriverError::from(e).locate(&query)),
                     time_taken_ms: Some(start_time.elapsed().as_millis()),
                 })
             }
@@ -503,104 +437,40 @@ pub async fn execute_query(
 
     match sqlx::query(&query)
         .fetch(&pool)
-        .take(MAX_QUERY_RESULT_ROWS + 1)
+        .take(DEFAULT_MAX_RESULT_ROWS + 1)
         .try_collect::<Vec<_>>()
         .await
     {
         Ok(rows) => {
             pool.close().await;
-            let data: Vec<Value> = rows
+            let values = rows
                 .iter()
-                .take(MAX_QUERY_RESULT_ROWS)
-                .map(|row| {
-                    let mut obj = serde_json::Map::new();
-                    for (i, col) in row.columns().iter().enumerate() {
-                        let type_name = col.type_info().name();
-                        let value: Value = match type_name {
-                            "INT2" => row
-                                .try_get::<i16, _>(i)
-                                .map(|v| json!(v))
-                                .unwrap_or(Value::Null),
-                            "INT4" => row
-                                .try_get::<i32, _>(i)
-                                .map(|v| json!(v))
-                                .unwrap_or(Value::Null),
-                            "INT8" => row
-                                .try_get::<i64, _>(i)
-                                .map(|v| json!(v))
-                                .unwrap_or(Value::Null),
-                            "FLOAT4" => row
-                                .try_get::<f32, _>(i)
-                                .map(|v| json!(v))
-                                .unwrap_or(Value::Null),
-                            "FLOAT8" | "NUMERIC" => row
-                                .try_get::<f64, _>(i)
-                                .map(|v| json!(v))
-                                .unwrap_or(Value::Null),
-                            "BOOL" => row
-                                .try_get::<bool, _>(i)
-                                .map(|v| json!(v))
-                                .unwrap_or(Value::Null),
-                            "TEXT" | "VARCHAR" | "CHAR" | "BPCHAR" | "NAME" => row
-                                .try_get::<String, _>(i)
-                                .map(|v| json!(v))
-                                .unwrap_or(Value::Null),
-                            "UUID" => row
-                                .try_get::<uuid::Uuid, _>(i)
-                                .map(|v| json!(v.to_string()))
-                                .unwrap_or(Value::Null),
-                            "TIMESTAMP" | "TIMESTAMPTZ" => row
-                                .try_get::<chrono::NaiveDateTime, _>(i)
-                                .map(|v| json!(v.to_string()))
-                                .or_else(|_| {
-                                    row.try_get::<chrono::DateTime<chrono::Utc>, _>(i)
-                                        .map(|v| json!(v.to_string()))
-                                })
-                                .unwrap_or(Value::Null),
-                            "DATE" => row
-                                .try_get::<chrono::NaiveDate, _>(i)
-                                .map(|v| json!(v.to_string()))
-                                .unwrap_or(Value::Null),
-                            "TIME" | "TIMETZ" => row
-                                .try_get::<chrono::NaiveTime, _>(i)
-                                .map(|v| json!(v.to_string()))
-                                .unwrap_or(Value::Null),
-                            "JSON" | "JSONB" => row
-                                .try_get::<serde_json::Value, _>(i)
-                                .unwrap_or(Value::Null),
-                            "BYTEA" => row
-                                .try_get::<Vec<u8>, _>(i)
-                                .map(|v| json!(format!("\\x{}", hex::encode(&v))))
-                                .unwrap_or(Value::Null),
-                            _ => row
-                                .try_get::<String, _>(i)
-                                .map(|v| json!(v))
-                                .unwrap_or_else(|_| json!(format!("<{}>", type_name))),
-                        };
-                        obj.insert(col.name().to_string(), value);
-                    }
-                    Value::Object(obj)
-                })
+                .take(DEFAULT_MAX_RESULT_ROWS)
+                .map(postgres_values::row_values)
                 .collect();
-
-            let row_count = data.len() as i64;
-            Ok(QueryResult {
-                data,
-                row_count,
-                truncated: rows.len() > MAX_QUERY_RESULT_ROWS,
-                rows_affected: None,
-                error: None,
-                time_taken_ms: Some(start_time.elapsed().as_millis()),
-            })
+            let columns = rows
+                .first()
+                .map(postgres_values::result_columns)
+                .unwrap_or_default();
+            Ok(QueryResult::from_table(
+                columns,
+                values,
+                rows.len() > DEFAULT_MAX_RESULT_ROWS,
+                start_time,
+            ))
         }
         Err(e) => {
             pool.close().await;
             Ok(QueryResult {
                 data: vec![],
+                columns: Vec::new(),
+                rows: Vec::new(),
+                edit_target: None,
                 row_count: 0,
                 truncated: false,
                 rows_affected: None,
                 error: Some(e.to_string()),
+                error_details: Some(DriverError::from(e).locate(&query)),
                 time_taken_ms: Some(start_time.elapsed().as_millis()),
             })
         }
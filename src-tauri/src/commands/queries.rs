@@ -2,20 +2,44 @@ use crate::db::models::{QueryHistory, SavedQuery, SavedQueryFormData};
 use sqlx::SqlitePool;
 use tauri::State;
 
-#[tauri::command]
-pub async fn get_saved_queries(
-    pool: State<'_, SqlitePool>,
-    connection_uuid: String,
+/// Saved queries of a connection, most recently updated first.
+pub(crate) async fn load_saved_queries(
+    pool: &SqlitePool,
+    connection_uuid: &str,
 ) -> Result<Vec<SavedQuery>, String> {
     sqlx::query_as::<_, SavedQuery>(
         "SELECT * FROM saved_queries WHERE connection_uuid = ? ORDER BY updated_at DESC",
     )
-    .bind(&connection_uuid)
-    .fetch_all(pool.inner())
+    .bind(connection_uuid)
+    .fetch_all(pool)
     .await
     .map_err(|e| e.to_string())
 }
 
+/// One saved query of a connection, if it exists.
+pub(crate) async fn load_saved_query(
+    pool: &SqlitePool,
+    connection_uuid: &str,
+    id: i64,
+) -> Result<Option<SavedQuery>, String> {
+    sqlx::query_as::<_, SavedQuery>(
+        "SELECT * FROM saved_queries WHERE connection_uuid = ? AND id = ?",
+    )
+    .bind(connection_uuid)
+    .bind(id)
+    .fetch_optional(pool)
+    .await
+    .map_err(|e| e.to_string())
+}
+
+#[tauri::command]
+pub async fn get_saved_queries(
+    pool: State<'_, SqlitePool>,
+    connection_uuid: String,
+) -> Result<Vec<SavedQuery>, String> {
+    load_saved_queries(pool.inner(), &connection_uuid).await
+}
+
 #[tauri::command]
 pub async fn create_saved_query(
     pool: State<'_, SqlitePool>,
@@ -0,0 +1,179 @@
+use crate::database::redis_scripts::validate_parameters;
+use crate::db::models::{SavedRedisScript, SavedRedisScriptFormData};
+use sqlx::types::Json;
+use sqlx::SqlitePool;
+use tauri::State;
+
+const MAX_SCRIPT_NAME_LENGTH: usize = 80;
+
+fn normalize_script_name(name: &str) -> Result<String, String> {
+    let name = name.trim();
+    if name.is_empty() {
+        return Err("Script name is required".to_string());
+    }
+    if name.chars().count() > MAX_SCRIPT_NAME_LENGTH {
+        return Err(format!(
+            "Script name must be {MAX_SCRIPT_NAME_LENGTH} characters or fewer"
+        ));
+    }
+    Ok(name.to_string())
+}
+
+fn validate_script(data: &SavedRedisScriptFormData) -> Result<String, String> {
+    if data.source.trim().is_empty() {
+        return Err("Script source is required".to_string());
+    }
+    validate_parameters(&data.parameters)?;
+    normalize_script_name(&data.name)
+}
+
+fn map_database_error(error: sqlx::Error) -> String {
+    if error
+        .as_database_error()
+        .is_some_and(|database_error| database_error.is_unique_violation())
+    {
+        "A script with this name already exists for this connection".to_string()
+    } else {
+        error.to_string()
+    }
+}
+
+/// Saved scripts of a connection, most recently updated first.
+pub(crate) async fn load_redis_scripts(
+    pool: &SqlitePool,
+    connection_uuid: &str,
+) -> Result<Vec<SavedRedisScript>, String> {
+    sqlx::query_as::<_, SavedRedisScript>(
+        r#"
+        SELECT * FROM redis_scripts
+        WHERE connection_uuid = ?
+        ORDER BY updated_at DESC, id DESC
+        "#,
+    )
+    .bind(connection_uuid)
+    .fetch_all(pool)
+    .await
+    .map_err(map_database_error)
+}
+
+/// One saved script of a connection, if it exists.
+pub(crate) async fn load_redis_script(
+    pool: &SqlitePool,
+    connection_uuid: &str,
+    id: i64,
+) -> Result<Option<SavedRedisScript>, String> {
+    sqlx::query_as::<_, SavedRedisScript>(
+        "SELECT * FROM redis_scripts WHERE connection_uuid = ? AND id = ?",
+    )
+    .bind(connection_uuid)
+    .bind(id)
+    .fetch_optional(pool)
+    .await
+    .map_err(map_database_error)
+}
+
+#[tauri::command]
+pub async fn get_redis_scripts(
+    pool: State<'_, SqlitePool>,
+    connection_uuid: String,
+) -> Result<Vec<SavedRedisScript>, String> {
+    load_redis_scripts(pool.inner(), &connection_uuid).await
+}
+
+#[tauri::command]
+pub async fn create_redis_script(
+    pool: State<'_, SqlitePool>,
+    connection_uuid: String,
+    data: SavedRedisScriptFormData,
+) -> Result<SavedRedisScript, String> {
+    let name = validate_script(&data)?;
+    sqlx::query_as::<_, SavedRedisScript>(
+        r#"
+        INSERT INTO redis_scripts
+            (connection_uuid, name, description, source, parameters_json, read_only)
+        VALUES (?, ?, ?, ?, ?, ?)
+        RETURNING *
+        "#,
+    )
+    .bind(connection_uuid)
+    .bind(name)
+    .bind(data.description.trim())
+    .bind(&data.source)
+    .bind(Json(&data.parameters))
+    .bind(data.read_only)
+    .fetch_one(pool.inner())
+    .await
+    .map_err(map_database_error)
+}
+
+#[tauri::command]
+pub async fn update_redis_script(
+    pool: State<'_, SqlitePool>,
+    id: i64,
+    data: SavedRedisScriptFormData,
+) -> Result<SavedRedisScript, String> {
+    let name = validate_script(&data)?;
+    sqlx::query_as::<_, SavedRedisScript>(
+        r#"
+        UPDATE redis_scripts
+        SET name = ?, description = ?, source = ?, parameters_json = ?, read_only = ?,
+            updated_at = datetime('now')
+        WHERE id = ?
+        RETURNING *
+        "#,
+    )
+    .bind(name)
+    .bind(data.description.trim())
+    .bind(&data.source)
+    .bind(Json(&data.parameters))
+    .bind(data.read_only)
+    .bind(id)
+    .fetch_one(pool.inner())
+    .await
+    .map_err(map_database_error)
+}
+
+#[tauri::command]
+pub async fn delete_redis_script(pool: State<'_, SqlitePool>, id: i64) -> Result<bool, String> {
+    sqlx::query("DELETE FROM redis_scripts WHERE id = ?")
+        .bind(id)
+        .execute(pool.inner())
+        .await
+        .map(|_| true)
+        .map_err(map_database_error)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{normalize_script_name, validate_script};
+    use crate::db::models::SavedRedisScriptFormData;
+
+    fn script() -> SavedRedisScriptFormData {
+        serde_json::from_value(serde_json::json!({
+            "name": " Rate limiter ",
+            "source": "return redis.call('INCR', KEYS[1])",
+            "parameters": [{ "name": "bucket", "kind": "key" }]
+        }))
+        .unwrap()
+    }
+
+    #[test]
+    fn normalizes_valid_names_and_rejects_blank_or_long_names() {
+        assert_eq!(normalize_script_name("  Locks ").unwrap(), "Locks");
+        assert!(normalize_script_name(" ").is_err());
+        assert!(normalize_script_name(&"a".repeat(81)).is_err());
+    }
+
+    #[test]
+    fn requires_a_source_and_valid_parameters() {
+        assert_eq!(validate_script(&script()).unwrap(), "Rate limiter");
+
+        let mut empty = script();
+        empty.source = "  ".to_string();
+        assert!(validate_script(&empty).is_err());
+
+        let mut duplicated = script();
+        duplicated.parameters.push(duplicated.parameters[0].clone());
+        assert!(validate_script(&duplicated).is_err());
+    }
+}
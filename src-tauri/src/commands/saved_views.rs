@@ -96,6 +96,46 @@ fn serialize_state(state: &SavedViewState) -> Result<String, String> {
     serde_json::to_string(state).map_err(|error| error.to_string())
 }
 
+/// Saved views of every table of a connection, most recently updated first.
+pub(crate) async fn load_saved_views(
+    pool: &SqlitePool,
+    connection_uuid: &str,
+) -> Result<Vec<SavedView>, String> {
+    let rows = sqlx::query_as::<_, SavedViewRow>(
+        r#"
+        SELECT * FROM saved_views
+        WHERE connection_uuid = ?
+        ORDER BY updated_at DESC, id DESC
+        "#,
+    )
+    .bind(connection_uuid)
+    .fetch_all(pool)
+    .await
+    .map_err(map_database_error)?;
+
+    rows.into_iter()
+        .map(SavedViewRow::into_saved_view)
+        .collect()
+}
+
+/// One saved view of a connection, if it exists.
+pub(crate) async fn load_saved_view(
+    pool: &SqlitePool,
+    connection_uuid: &str,
+    id: i64,
+) -> Result<Option<SavedView>, String> {
+    sqlx::query_as::<_, SavedViewRow>(
+        "SELECT * FROM saved_views WHERE connection_uuid = ? AND id = ?",
+    )
+    .bind(connection_uuid)
+    .bind(id)
+    .fetch_optional(pool)
+    .await
+    .map_err(map_database_error)?
+    .map(SavedViewRow::into_saved_view)
+    .transpose()
+}
+
 #[tauri::command]
 pub async fn get_saved_views(
     pool: State<'_, SqlitePool>,
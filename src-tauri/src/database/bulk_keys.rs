@@ -0,0 +1,648 @@
+//! Bulk key operations by pattern.
+//!
+//! A [`BulkKeyRequest`] applies one [`BulkKeyOperation`] to every key matching
+//! a pattern. The [`BulkKeyManager`] only runs a request after a dry run has
+//! counted and sampled the keys it will touch: [`BulkKeyManager::preview`]
+//! returns a preview id, and [`BulkKeyManager::start`] runs exactly the
+//! previewed request in the background, reporting progress as it goes.
+
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+use tokio::task::AbortHandle;
+use uuid::Uuid;
+
+use super::key_value::{key_value_browser, KeyValueBrowser};
+use super::DatabaseDriver;
+
+pub const BULK_PROGRESS_EVENT: &str = "redis-bulk-progress";
+pub const BULK_FINISHED_EVENT: &str = "redis-bulk-finished";
+
+const PROGRESS_INTERVAL: Duration = Duration::from_millis(250);
+/// How long a dry run stays valid before it has to be repeated.
+const PREVIEW_TTL: Duration = Duration::from_secs(10 * 60);
+/// Keys listed by a dry run.
+pub const PREVIEW_SAMPLE_SIZE: usize = 20;
+/// Failed keys listed in a report; the rest are only counted.
+const MAX_REPORTED_ERRORS: usize = 100;
+
+#[derive(Clone, Debug, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(tag = "kind", rename_all = "snake_case")]
+pub enum BulkKeyOperation {
+    Delete,
+    /// Expire each key `ttl` seconds from now.
+    SetTtl {
+        ttl: i64,
+    },
+    /// Remove each key's expiry.
+    ClearTtl,
+    /// Replace the leading `from_prefix` of each key with `to_prefix`. Keys
+    /// that do not start with `from_prefix` are skipped.
+    Rename {
+        from_prefix: String,
+        to_prefix: String,
+        #[serde(default)]
+        replace: bool,
+    },
+    Copy {
+        target: KeyTarget,
+        #[serde(default)]
+        replace: bool,
+    },
+    /// Copy, then delete the keys that were copied.
+    Move {
+        target: KeyTarget,
+        #[serde(default)]
+        replace: bool,
+    },
+}
+
+/// Where keys are copied or moved to.
+#[derive(Clone, Debug, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(tag = "kind", rename_all = "snake_case")]
+pub enum KeyTarget {
+    /// Another logical database of the same server.
+    Database { db: i64 },
+    /// Another saved connection, written with `DUMP`/`RESTORE`.
+    Connection { connection_uuid: String },
+}
+
+impl BulkKeyOperation {
+    pub fn target(&self) -> Option<&KeyTarget> {
+        match self {
+            Self::Copy { target, .. } | Self::Move { target, .. } => Some(target),
+            _ => None,
+        }
+    }
+
+    /// The connection keys are written to, when it is not the source.
+    pub fn target_connection(&self) -> Option<&str> {
+        match self.target() {
+            Some(KeyTarget::Connection { connection_uuid }) => Some(connection_uuid),
+            _ => None,
+        }
+    }
+
+    /// The name `key` is renamed to, or `None` when it is left alone.
+    pub fn renamed(&self, key: &[u8]) -> Option<Vec<u8>> {
+        let Self::Rename {
+            from_prefix,
+            to_prefix,
+            ..
+        } = self
+        else {
+            return None;
+        };
+        let rest = key.strip_prefix(from_prefix.as_bytes())?;
+        Some([to_prefix.as_bytes(), rest].concat())
+    }
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize, PartialEq, Eq)]
+pub struct BulkKeyRequest {
+    pub pattern: String,
+    pub operation: BulkKeyOperation,
+}
+
+impl BulkKeyRequest {
+    pub fn validate(&self) -> Result<(), String> {
+        if self.pattern.is_empty() {
+            return Err("Enter a key pattern".to_string());
+        }
+        match &self.operation {
+            BulkKeyOperation::SetTtl { ttl } if *ttl <= 0 => {
+                Err("TTL must be a positive number of seconds".to_string())
+            }
+            BulkKeyOperation::Rename {
+                from_prefix,
+                to_prefix,
+                ..
+            } => {
+                if from_prefix.is_empty() {
+                    Err("Enter the prefix to rename".to_string())
+                } else if to_prefix.starts_with(from_prefix.as_str()) {
+                    // Renamed keys would match the prefix again and could be
+                    // renamed twice by the same scan.
+                    Err("The new prefix cannot start with the old prefix".to_string())
+                } else {
+                    Ok(())
+                }
+            }
+            BulkKeyOperation::Copy { target, .. } | BulkKeyOperation::Move { target, .. } => {
+                match target {
+                    KeyTarget::Database { db } if *db < 0 => {
+                        Err("Database index cannot be negative".to_string())
+                    }
+                    KeyTarget::Connection { connection_uuid } if connection_uuid.is_empty() => {
+                        Err("Choose a target connection".to_string())
+                    }
+                    _ => Ok(()),
+                }
+            }
+            _ => Ok(()),
+        }
+    }
+}
+
+/// The keys matching a pattern: how many, and the first few.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct KeyMatches {
+    pub count: u64,
+    pub sample: Vec<Vec<u8>>,
+}
+
+/// A key exported with `DUMP`, to be written to another connection.
+#[derive(Clone, Debug, PartialEq)]
+pub struct DumpedKey {
+    pub key: Vec<u8>,
+    /// Milliseconds to live, or `0` without expiry (as `RESTORE` expects).
+    pub ttl_ms: i64,
+    pub payload: Vec<u8>,
+}
+
+/// What happened to one key.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum KeyOutcome {
+    Done,
+    /// Left alone: the key vanished, had nothing to change, or its target
+    /// already existed.
+    Skipped,
+    Failed(String),
+}
+
+#[derive(Clone, Debug, Default, Serialize, PartialEq)]
+pub struct BulkKeyReport {
+    pub processed: u64,
+    pub succeeded: u64,
+    pub skipped: u64,
+    pub failed: u64,
+    /// The first failures, by key.
+    pub errors: Vec<KeyError>,
+    pub elapsed_ms: u128,
+}
+
+#[derive(Clone, Debug, Serialize, PartialEq)]
+pub struct KeyError {
+    pub key: String,
+    pub error: String,
+}
+
+impl BulkKeyReport {
+    pub fn record(&mut self, key: &[u8], outcome: KeyOutcome) {
+        self.processed += 1;
+        match outcome {
+            KeyOutcome::Done => self.succeeded += 1,
+            KeyOutcome::Skipped => self.skipped += 1,
+            KeyOutcome::Failed(error) => {
+                self.failed += 1;
+                if self.errors.len() < MAX_REPORTED_ERRORS {
+                    self.errors.push(KeyError {
+                        key: String::from_utf8_lossy(key).into_owned(),
+                        error,
+                    });
+                }
+            }
+        }
+    }
+}
+
+/// Reports the running totals of a bulk operation after each batch.
+pub type BulkProgressSink<'a> = &'a (dyn Fn(&BulkKeyReport) + Send + Sync);
+
+#[derive(Clone, Debug, Serialize, PartialEq)]
+pub struct BulkKeyPreview {
+    pub preview_id: String,
+    /// Keys matching the pattern when the dry run scanned them.
+    pub matched: u64,
+    pub sample: Vec<PreviewKey>,
+}
+
+#[derive(Clone, Debug, Serialize, PartialEq)]
+pub struct PreviewKey {
+    pub key: String,
+    /// The key's new name, for renames.
+    pub new_key: Option<String>,
+}
+
+#[derive(Clone, Debug, Serialize)]
+pub struct BulkKeyJob {
+    pub id: String,
+    pub connection_uuid: String,
+    pub request: BulkKeyRequest,
+    pub expected_keys: u64,
+    pub started_at: String,
+}
+
+#[derive(Clone, Debug, Serialize)]
+pub struct BulkKeyProgress {
+    pub job_id: String,
+    pub connection_uuid: String,
+    /// The dry run's count, for a percentage.
+    pub expected_keys: u64,
+    pub processed: u64,
+    pub succeeded: u64,
+    pub skipped: u64,
+    pub failed: u64,
+}
+
+/// Sent once when a bulk operation ends. A cancelled operation has no
+/// report; keys processed before it was cancelled stay changed.
+#[derive(Clone, Debug, Serialize)]
+pub struct BulkKeyFinished {
+    pub job_id: String,
+    pub connection_uuid: String,
+    pub report: Option<BulkKeyReport>,
+    pub error: Option<String>,
+    pub cancelled: bool,
+}
+
+#[derive(Clone, Debug)]
+pub enum BulkKeyEvent {
+    Progress(BulkKeyProgress),
+    Finished(Box<BulkKeyFinished>),
+}
+
+type Notify = Arc<dyn Fn(&BulkKeyEvent) + Send + Sync>;
+
+struct Preview {
+    connection_uuid: String,
+    request: BulkKeyRequest,
+    matched: u64,
+    created: Instant,
+}
+
+struct Job {
+    info: BulkKeyJob,
+    task: AbortHandle,
+}
+
+/// Dry runs and background bulk operations, at most one running per
+/// connection.
+pub struct BulkKeyManager {
+    notify: Notify,
+    previews: Mutex<HashMap<String, Preview>>,
+    jobs: Arc<Mutex<HashMap<String, Job>>>,
+}
+
+impl BulkKeyManager {
+    pub fn new(notify: impl Fn(&BulkKeyEvent) + Send + Sync + 'static) -> Self {
+        Self {
+            notify: Arc::new(notify),
+            previews: Mutex::new(HashMap::new()),
+            jobs: Arc::new(Mutex::new(HashMap::new())),
+        }
+    }
+
+    /// Count and sample the keys `request` would touch, without changing
+    /// anything. The returned preview id starts the request.
+    pub async fn preview(
+        &self,
+        connection_uuid: &str,
+        driver: &Arc<Box<dyn DatabaseDriver>>,
+        request: BulkKeyRequest,
+    ) -> Result<BulkKeyPreview, String> {
+        request.validate()?;
+        if request.operation.target_connection() == Some(connection_uuid) {
+            return Err("Choose a target connection other than the source".to_string());
+        }
+        let matches = key_value_browser(driver)?
+            .match_keys(&request.pattern, PREVIEW_SAMPLE_SIZE)
+            .await
+            .map_err(String::from)?;
+
+        let sample = matches
+            .sample
+            .iter()
+            .map(|key| PreviewKey {
+                key: String::from_utf8_lossy(key).into_owned(),
+                new_key: request
+                    .operation
+                    .renamed(key)
+                    .map(|key| String::from_utf8_lossy(&key).into_owned()),
+            })
+            .collect();
+        let preview_id = Uuid::new_v4().to_string();
+        let mut previews = self.previews.lock().map_err(|e| e.to_string())?;
+        previews.retain(|_, preview| preview.created.elapsed() < PREVIEW_TTL);
+        previews.insert(
+            preview_id.clone(),
+            Preview {
+                connection_uuid: connection_uuid.to_string(),
+                request,
+                matched: matches.count,
+                created: Instant::now(),
+            },
+        );
+        Ok(BulkKeyPreview {
+            preview_id,
+            matched: matches.count,
+            sample,
+        })
+    }
+
+    /// The request a dry run previewed.
+    pub fn previewed_request(&self, preview_id: &str) -> Result<BulkKeyRequest, String> {
+        let previews = self.previews.lock().map_err(|e| e.to_string())?;
+        previews
+            .get(preview_id)
+            .filter(|preview| preview.created.elapsed() < PREVIEW_TTL)
+            .map(|preview| preview.request.clone())
+            .ok_or_else(|| "The preview has expired. Run the dry run again.".to_string())
+    }
+
+    /// Run a previewed request. `target` is the driver of the target
+    /// connection when keys are copied or moved to another connection.
+    pub fn start(
+        &self,
+        connection_uuid: &str,
+        preview_id: &str,
+        driver: Arc<Box<dyn DatabaseDriver>>,
+        target: Option<Arc<Box<dyn DatabaseDriver>>>,
+    ) -> Result<BulkKeyJob, String> {
+        key_value_browser(&driver)?;
+        if let Some(target) = &target {
+            key_value_browser(target)?;
+        }
+
+        let mut jobs = self.jobs.lock().map_err(|e| e.to_string())?;
+        if jobs
+            .values()
+            .any(|job| job.info.connection_uuid == connection_uuid)
+        {
+            return Err("A bulk operation is already running for this connection".to_string());
+        }
+        let preview = {
+            let mut previews = self.previews.lock().map_err(|e| e.to_string())?;
+            match previews.get(preview_id) {
+                Some(preview)
+                    if preview.connection_uuid == connection_uuid
+                        && preview.created.elapsed() < PREVIEW_TTL =>
+                {
+                    previews.remove(preview_id)
+                }
+                _ => None,
+            }
+        }
+        .ok_or_else(|| "The preview has expired. Run the dry run again.".to_string())?;
+        if preview.request.operation.target_connection().is_some() != target.is_some() {
+            return Err("The target connection is not available".to_string());
+        }
+
+        let info = BulkKeyJob {
+            id: Uuid::new_v4().to_string(),
+            connection_uuid: connection_uuid.to_string(),
+            request: preview.request,
+            expected_keys: preview.matched,
+            started_at: chrono::Utc::now().to_rfc3339(),
+        };
+        // Registered before the lock is released, so a job that finishes
+        // immediately still finds itself to unregister.
+        let task = tokio::spawn(run(
+            info.clone(),
+            driver,
+            target,
+            self.notify.clone(),
+            self.jobs.clone(),
+        ));
+        jobs.insert(
+            info.id.clone(),
+            Job {
+                info: info.clone(),
+                task: task.abort_handle(),
+            },
+        );
+        Ok(info)
+    }
+
+    /// Cancel a bulk operation. Returns `false` if it had already finished.
+    pub fn cancel(&self, job_id: &str) -> bool {
+        let removed = self
+            .jobs
+            .lock()
+            .ok()
+            .and_then(|mut jobs| jobs.remove(job_id));
+        match removed {
+            Some(job) => {
+                self.end(job);
+                true
+            }
+            None => false,
+        }
+    }
+
+    /// Cancel the bulk operation of a connection and forget its dry runs,
+    /// e.g. when it disconnects.
+    pub fn cancel_connection(&self, connection_uuid: &str) {
+        if let Ok(mut previews) = self.previews.lock() {
+            previews.retain(|_, preview| preview.connection_uuid != connection_uuid);
+        }
+        let removed = self.jobs.lock().ok().and_then(|mut jobs| {
+            let id = jobs
+                .values()
+                .find(|job| job.info.connection_uuid == connection_uuid)
+                .map(|job| job.info.id.clone())?;
+            jobs.remove(&id)
+        });
+        if let Some(job) = removed {
+            self.end(job);
+        }
+    }
+
+    /// The bulk operation running for a connection, if any.
+    pub fn running(&self, connection_uuid: &str) -> Option<BulkKeyJob> {
+        self.jobs.lock().ok().and_then(|jobs| {
+            jobs.values()
+                .find(|job| job.info.connection_uuid == connection_uuid)
+                .map(|job| job.info.clone())
+        })
+    }
+
+    fn end(&self, job: Job) {
+        job.task.abort();
+        (self.notify)(&BulkKeyEvent::Finished(Box::new(BulkKeyFinished {
+            job_id: job.info.id,
+            connection_uuid: job.info.connection_uuid,
+            report: None,
+            error: None,
+            cancelled: true,
+        })));
+    }
+}
+
+async fn run(
+    info: BulkKeyJob,
+    driver: Arc<Box<dyn DatabaseDriver>>,
+    target: Option<Arc<Box<dyn DatabaseDriver>>>,
+    notify: Notify,
+    jobs: Arc<Mutex<HashMap<String, Job>>>,
+) {
+    let last_progress: Mutex<Option<Instant>> = Mutex::new(None);
+    let progress = |report: &BulkKeyReport| {
+        let due = last_progress.lock().is_ok_and(|mut last| {
+            let due = last.is_none_or(|last| last.elapsed() >= PROGRESS_INTERVAL);
+            if due {
+                *last = Some(Instant::now());
+            }
+            due
+        });
+        if due {
+            notify(&BulkKeyEvent::Progress(BulkKeyProgress {
+                job_id: info.id.clone(),
+                connection_uuid: info.connection_uuid.clone(),
+                expected_keys: info.expected_keys,
+                processed: report.processed,
+                succeeded: report.succeeded,
+                skipped: report.skipped,
+                failed: report.failed,
+            }));
+        }
+    };
+
+    let target: Option<&dyn KeyValueBrowser> =
+        target.as_ref().and_then(|target| target.key_value());
+    let result = match key_value_browser(&driver) {
+        Ok(browser) => browser
+            .bulk_update(&info.request, target, &progress)
+            .await
+            .map_err(|e| e.to_string()),
+        Err(error) => Err(error),
+    };
+
+    // A cancelled job was removed (and reported) already.
+    let registered = jobs
+        .lock()
+        .ok()
+        .and_then(|mut jobs| jobs.remove(&info.id))
+        .is_some();
+    if registered {
+        let (report, error) = match result {
+            Ok(report) => (Some(report), None),
+            Err(error) => (None, Some(error)),
+        };
+        notify(&BulkKeyEvent::Finished(Box::new(BulkKeyFinished {
+            job_id: info.id.clone(),
+            connection_uuid: info.connection_uuid.clone(),
+            report,
+            error,
+            cancelled: false,
+        })));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn rename(from_prefix: &str, to_prefix: &str) -> BulkKeyOperation {
+        BulkKeyOperation::Rename {
+            from_prefix: from_prefix.to_string(),
+            to_prefix: to_prefix.to_string(),
+            replace: false,
+        }
+    }
+
+    #[test]
+    fn operations_deserialize_by_kind() {
+        let request: BulkKeyRequest = serde_json::from_value(json!({
+            "pattern": "cache:*",
+            "operation": {
+                "kind": "move",
+                "target": { "kind": "connection", "connection_uuid": "staging" }
+            }
+        }))
+        .unwrap();
+        assert_eq!(
+            request.operation,
+            BulkKeyOperation::Move {
+                target: KeyTarget::Connection {
+                    connection_uuid: "staging".to_string()
+                },
+                replace: false,
+            }
+        );
+        assert_eq!(request.operation.target_connection(), Some("staging"));
+    }
+
+    #[test]
+    fn validates_requests_before_they_run() {
+        let request = |pattern: &str, operation: BulkKeyOperation| BulkKeyRequest {
+            pattern: pattern.to_string(),
+            operation,
+        };
+        assert!(request("cache:*", BulkKeyOperation::Delete)
+            .validate()
+            .is_ok());
+        assert!(request("", BulkKeyOperation::Delete).validate().is_err());
+        assert!(request("cache:*", BulkKeyOperation::SetTtl { ttl: 0 })
+            .validate()
+            .is_err());
+        assert!(request("cache:*", rename("cache:", "old:cache:"))
+            .validate()
+            .is_ok());
+        // Renamed keys would match the old prefix again.
+        assert!(request("cache:*", rename("cache:", "cache:v2:"))
+            .validate()
+            .is_err());
+        assert!(request(
+            "cache:*",
+            BulkKeyOperation::Copy {
+                target: KeyTarget::Database { db: -1 },
+                replace: false,
+            }
+        )
+        .validate()
+        .is_err());
+    }
+
+    #[test]
+    fn renames_rewrite_only_the_leading_prefix() {
+        let operation = rename("cache:", "old:cache:");
+        assert_eq!(
+            operation.renamed(b"cache:user:1"),
+            Some(b"old:cache:user:1".to_vec())
+        );
+        assert_eq!(operation.renamed(b"session:cache:1"), None);
+        assert_eq!(BulkKeyOperation::Delete.renamed(b"cache:user:1"), None);
+    }
+
+    #[test]
+    fn reports_count_outcomes_and_keep_the_first_errors() {
+        let mut report = BulkKeyReport::default();
+        report.record(b"a", KeyOutcome::Done);
+        report.record(b"b", KeyOutcome::Skipped);
+        for index in 0..MAX_REPORTED_ERRORS + 5 {
+            report.record(
+                format!("key:{index}").as_bytes(),
+                KeyOutcome::Failed("CROSSSLOT".to_string()),
+            );
+        }
+        assert_eq!(report.processed, MAX_REPORTED_ERRORS as u64 + 7);
+        assert_eq!((report.succeeded, report.skipped), (1, 1));
+        assert_eq!(report.failed, MAX_REPORTED_ERRORS as u64 + 5);
+        assert_eq!(report.errors.len(), MAX_REPORTED_ERRORS);
+        assert_eq!(report.errors[0].key, "key:0");
+    }
+
+    #[test]
+    fn starting_requires_a_preview_of_the_same_connection() {
+        let manager = BulkKeyManager::new(|_| {});
+        manager.previews.lock().unwrap().insert(
+            "preview-1".to_string(),
+            Preview {
+                connection_uuid: "connection-1".to_string(),
+                request: BulkKeyRequest {
+                    pattern: "cache:*".to_string(),
+                    operation: BulkKeyOperation::Delete,
+                },
+                matched: 3,
+                created: Instant::now(),
+            },
+        );
+        assert!(manager.previewed_request("preview-1").is_ok());
+        assert!(manager.previewed_request("preview-2").is_err());
+        manager.cancel_connection("connection-1");
+        assert!(manager.previewed_request("preview-1").is_err());
+    }
+}
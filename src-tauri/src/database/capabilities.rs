@@ -0,0 +1,54 @@
+//! What each engine's driver can do.
+//!
+//! Drivers report a [`DriverCapabilities`] so the frontend, the MCP server and
+//! the commands can adapt to a connection without matching on engine names.
+//! Each driver module owns a `CAPABILITIES` constant; the driver returns it and
+//! [`DatabaseType::capabilities`](super::DatabaseType::capabilities) looks it
+//! up for connections that aren't open yet.
+
+use serde::Serialize;
+
+/// How a driver keeps `execute_query_read_only` from writing.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ReadOnlyEnforcement {
+    /// The engine rejects writes itself (read-only transactions, connection
+    /// flags, server settings).
+    Engine,
+    /// The driver checks the command against a list of read commands.
+    Allowlist,
+    /// The driver rejects statements containing keywords that write, and rolls
+    /// back whatever runs anyway.
+    Denylist,
+    /// Read-only execution is not available.
+    Unsupported,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+pub struct DriverCapabilities {
+    /// `BEGIN` / `COMMIT` can be used in the SQL editor.
+    pub transactions: bool,
+    /// Tables can be created from a [`CreateTableRequest`](crate::db::models::CreateTableRequest).
+    pub create_table: bool,
+    /// Columns can be added, dropped or renamed without writing SQL.
+    pub alter_table: bool,
+    /// Function definitions can be read back.
+    pub functions: bool,
+    pub read_only: ReadOnlyEnforcement,
+    /// A statement that outlives its time limit is stopped on the server, not
+    /// just abandoned by the client.
+    pub cancellation: bool,
+    /// Rows are read incrementally, so the row limit stops the fetch instead
+    /// of trimming a fully buffered result.
+    pub streaming: bool,
+    /// The server hosts sibling databases the same credentials can open.
+    pub multiple_databases: bool,
+    /// Keys can be scanned, inspected and edited through
+    /// [`KeyValueBrowser`](super::key_value::KeyValueBrowser).
+    pub key_value_browsing: bool,
+    /// Query plans can be shown with
+    /// [`DatabaseType::explain_statement`](super::DatabaseType::explain_statement).
+    pub explain: bool,
+    /// Rows can be inserted, updated and deleted through bound mutations.
+    pub row_editing: bool,
+}
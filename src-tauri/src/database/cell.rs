@@ -0,0 +1,1058 @@
+//! Tagged cell values shared by every driver.
+//!
+//! Values JSON carries exactly stay plain: `null`, booleans, text, finite
+//! floats and integers within ±(2^53 - 1). Everything else is an object
+//! tagged with its type, such as `{"$type": "decimal", "value": "12.50"}`, so
+//! precision, bytes and temporal types survive the trip to the UI and back.
+//! Any object in a result row is a tagged value; JSON documents are wrapped
+//! in a `json` tag, and the `$` keeps one with its own `type` field from
+//! reading as a tag.
+
+use super::filter::{classify_column_type, FilterDialect};
+use super::DatabaseType;
+use crate::db::models::{FilterColumnKind, ResultColumn, ResultEditTarget};
+use base64::engine::general_purpose::STANDARD as BASE64;
+use base64::Engine;
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+
+/// The largest integer a JavaScript number holds exactly.
+pub const MAX_SAFE_JSON_INTEGER: i64 = 9_007_199_254_740_991;
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "$type", rename_all = "snake_case")]
+pub enum TaggedValue {
+    /// An integer beyond ±(2^53 - 1), as its digits.
+    Integer {
+        value: String,
+    },
+    /// `NaN`, `Infinity` or `-Infinity`.
+    Float {
+        value: String,
+    },
+    Decimal {
+        value: String,
+    },
+    Date {
+        value: String,
+    },
+    Time {
+        value: String,
+    },
+    Timestamp {
+        value: String,
+    },
+    /// A timestamp with its UTC offset.
+    Timestamptz {
+        value: String,
+    },
+    Interval {
+        value: String,
+    },
+    Uuid {
+        value: String,
+    },
+    /// Base64 bytes and their count.
+    Binary {
+        value: String,
+        length: usize,
+    },
+    Json {
+        value: Value,
+    },
+    Array {
+        value: Vec<Value>,
+    },
+    /// A range in the engine's literal syntax, e.g. `[1,10)`.
+    Range {
+        value: String,
+    },
+    /// Well-known text, with its SRID when there is one.
+    Geometry {
+        value: String,
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        srid: Option<u32>,
+    },
+}
+
+impl From<TaggedValue> for Value {
+    fn from(value: TaggedValue) -> Self {
+        serde_json::to_value(value).unwrap_or(Value::Null)
+    }
+}
+
+impl TaggedValue {
+    /// The tagged value of a result cell, if it is one.
+    pub fn from_cell(cell: &Value) -> Option<Self> {
+        match cell {
+            Value::Object(_) => serde_json::from_value(cell.clone()).ok(),
+            _ => None,
+        }
+    }
+
+    /// The value as text, as it would be typed into a query.
+    pub fn text(&self) -> String {
+        match self {
+            Self::Integer { value }
+            | Self::Float { value }
+            | Self::Decimal { value }
+            | Self::Date { value }
+            | Self::Time { value }
+            | Self::Timestamp { value }
+            | Self::Timestamptz { value }
+            | Self::Interval { value }
+            | Self::Uuid { value }
+            | Self::Range { value }
+            | Self::Geometry { value, .. } => value.clone(),
+            Self::Binary { value, .. } => BASE64
+                .decode(value)
+                .map(|bytes| format!("\\x{}", hex::encode(bytes)))
+                .unwrap_or_default(),
+            Self::Json { value } => value.to_string(),
+            Self::Array { value } => Value::Array(value.iter().map(plain).collect()).to_string(),
+        }
+    }
+}
+
+pub fn integer(value: i64) -> Value {
+    if (-MAX_SAFE_JSON_INTEGER..=MAX_SAFE_JSON_INTEGER).contains(&value) {
+        Value::from(value)
+    } else {
+        TaggedValue::Integer {
+            value: value.to_string(),
+        }
+        .into()
+    }
+}
+
+pub fn unsigned(value: u64) -> Value {
+    if value <= MAX_SAFE_JSON_INTEGER as u64 {
+        Value::from(value)
+    } else {
+        TaggedValue::Integer {
+            value: value.to_string(),
+        }
+        .into()
+    }
+}
+
+/// An integer given as digits, such as a 128-bit value. Text that is not an
+/// integer stays text.
+pub fn integer_text(digits: &str) -> Value {
+    let digits = digits.trim();
+    if let Ok(value) = digits.parse::<i64>() {
+        integer(value)
+    } else if let Ok(value) = digits.parse::<u64>() {
+        unsigned(value)
+    } else if is_integer_literal(digits) {
+        TaggedValue::Integer {
+            value: digits.to_string(),
+        }
+        .into()
+    } else {
+        Value::String(digits.to_string())
+    }
+}
+
+pub fn float(value: f64) -> Value {
+    if value.is_finite() {
+        Value::from(value)
+    } else {
+        let value = if value.is_nan() {
+            "NaN"
+        } else if value > 0.0 {
+            "Infinity"
+        } else {
+            "-Infinity"
+        };
+        TaggedValue::Float {
+            value: value.to_string(),
+        }
+        .into()
+    }
+}
+
+/// A single-precision float with its own shortest digits, not those of the
+/// widened `f64` (`0.1`, not `0.10000000149011612`).
+pub fn float32(value: f32) -> Value {
+    match value.to_string().parse::<f64>() {
+        Ok(widened) if value.is_finite() => float(widened),
+        _ => float(f64::from(value)),
+    }
+}
+
+pub fn decimal(value: impl Into<String>) -> Value {
+    TaggedValue::Decimal {
+        value: value.into(),
+    }
+    .into()
+}
+
+pub fn date(value: impl Into<String>) -> Value {
+    TaggedValue::Date {
+        value: value.into(),
+    }
+    .into()
+}
+
+pub fn time(value: impl Into<String>) -> Value {
+    TaggedValue::Time {
+        value: value.into(),
+    }
+    .into()
+}
+
+pub fn timestamp(value: impl Into<String>) -> Value {
+    TaggedValue::Timestamp {
+        value: value.into(),
+    }
+    .into()
+}
+
+pub fn timestamptz(value: impl Into<String>) -> Value {
+    TaggedValue::Timestamptz {
+        value: value.into(),
+    }
+    .into()
+}
+
+pub fn interval(value: impl Into<String>) -> Value {
+    TaggedValue::Interval {
+        value: value.into(),
+    }
+    .into()
+}
+
+pub fn uuid(value: impl Into<String>) -> Value {
+    TaggedValue::Uuid {
+        value: value.into(),
+    }
+    .into()
+}
+
+pub fn range(value: impl Into<String>) -> Value {
+    TaggedValue::Range {
+        value: value.into(),
+    }
+    .into()
+}
+
+pub fn binary(bytes: &[u8]) -> Value {
+    TaggedValue::Binary {
+        value: BASE64.encode(bytes),
+        length: bytes.len(),
+    }
+    .into()
+}
+
+pub fn json(value: Value) -> Value {
+    TaggedValue::Json { value }.into()
+}
+
+pub fn array(items: Vec<Value>) -> Value {
+    TaggedValue::Array { value: items }.into()
+}
+
+pub fn geometry(wkt: impl Into<String>, srid: Option<u32>) -> Value {
+    TaggedValue::Geometry {
+        value: wkt.into(),
+        srid,
+    }
+    .into()
+}
+
+/// A geometry from (E)WKB, or the bytes themselves when they are not WKB.
+pub fn geometry_from_wkb(bytes: &[u8]) -> Value {
+    match wkb_to_wkt(bytes) {
+        Some((wkt, srid)) => geometry(wkt, srid),
+        None => binary(bytes),
+    }
+}
+
+/// A cell from an engine that reports no types: unsafe integers, arrays and
+/// objects are tagged, everything else stays as it is.
+pub fn untyped(value: Value) -> Value {
+    match value {
+        Value::Number(number) => match (number.as_i64(), number.as_u64()) {
+            (Some(value), _) => integer(value),
+            (None, Some(value)) => unsigned(value),
+            _ => Value::Number(number),
+        },
+        Value::Array(items) => array(items.into_iter().map(untyped).collect()),
+        Value::Object(_) => json(value),
+        value => value,
+    }
+}
+
+/// A row of [`untyped`] cells.
+pub fn untyped_row(row: Value) -> Value {
+    match row {
+        Value::Object(row) => Value::Object(
+            row.into_iter()
+                .map(|(name, value)| (name, untyped(value)))
+                .collect(),
+        ),
+        row => row,
+    }
+}
+
+/// A result column, classified like a table column of the same type.
+pub fn result_column(
+    name: impl Into<String>,
+    data_type: impl Into<String>,
+    dialect: FilterDialect,
+) -> ResultColumn {
+    let data_type = data_type.into();
+    ResultColumn {
+        name: name.into(),
+        filter_kind: classify_column_type(&data_type, dialect),
+        data_type,
+        nullable: None,
+        source: None,
+    }
+}
+
+/// A row aligned to `columns` as an object keyed by column name. A later
+/// column replaces an earlier one of the same name.
+pub fn row_object(columns: &[ResultColumn], row: &[Value]) -> Value {
+    Value::Object(
+        columns
+            .iter()
+            .zip(row)
+            .map(|(column, value)| (column.name.clone(), value.clone()))
+            .collect(),
+    )
+}
+
+/// The table every column of a result is read from, each table column at
+/// most once. `None` for computed columns, joins and repeated columns.
+pub fn source_table(columns: &[ResultColumn]) -> Option<(&str, &str)> {
+    let first = columns.first()?.source.as_ref()?;
+    let mut seen = std::collections::HashSet::new();
+    for column in columns {
+        let source = column.source.as_ref()?;
+        if source.schema != first.schema
+            || source.table != first.table
+            || !seen.insert(source.column.as_str())
+        {
+            return None;
+        }
+    }
+    Some((&first.schema, &first.table))
+}
+
+/// Where the rows of a result can be edited, given the primary key of its
+/// [`source_table`]: only when every key column is in the result.
+pub fn edit_target(columns: &[ResultColumn], primary_key: Vec<String>) -> Option<ResultEditTarget> {
+    let (schema, table) = source_table(columns)?;
+    let covered = primary_key.iter().all(|key| {
+        columns.iter().any(|column| {
+            column
+                .source
+                .as_ref()
+                .is_some_and(|source| &source.column == key)
+        })
+    });
+    (!primary_key.is_empty() && covered).then(|| ResultEditTarget {
+        schema: schema.to_string(),
+        table: table.to_string(),
+        primary_key,
+    })
+}
+
+/// A cell as plain JSON, tags replaced by their text.
+fn plain(cell: &Value) -> Value {
+    match TaggedValue::from_cell(cell) {
+        Some(TaggedValue::Json { value }) => value,
+        Some(TaggedValue::Array { value }) => Value::Array(value.iter().map(plain).collect()),
+        Some(tagged) => Value::String(tagged.text()),
+        None => cell.clone(),
+    }
+}
+
+/// Rows of [`untyped`] cells aligned to `columns`, for engines that return
+/// rows as objects.
+pub fn untyped_rows(rows: Vec<Value>, columns: &[ResultColumn]) -> Vec<Vec<Value>> {
+    rows.into_iter()
+        .map(|mut row| {
+            columns
+                .iter()
+                .map(|column| {
+                    row.get_mut(&column.name)
+                        .map(Value::take)
+                        .map_or(Value::Null, untyped)
+                })
+                .collect()
+        })
+        .collect()
+}
+
+/// Columns named after the first row, for results that carry no types.
+pub fn untyped_columns(rows: &[Value], dialect: FilterDialect) -> Vec<ResultColumn> {
+    rows.first()
+        .and_then(Value::as_object)
+        .map(|row| {
+            row.keys()
+                .map(|name| result_column(name.as_str(), "", dialect))
+                .collect()
+        })
+        .unwrap_or_default()
+}
+
+/// Columns for rows built as JSON objects, such as Redis replies: every key
+/// in the order first seen, plus a `value` column when a row is not an
+/// object.
+pub fn object_columns(rows: &[Value]) -> Vec<ResultColumn> {
+    let mut names: Vec<&str> = Vec::new();
+    for row in rows {
+        match row {
+            Value::Object(row) => {
+                for name in row.keys() {
+                    if !names.contains(&name.as_str()) {
+                        names.push(name);
+                    }
+                }
+            }
+            _ if !names.contains(&"value") => names.push("value"),
+            _ => {}
+        }
+    }
+    names
+        .into_iter()
+        .map(|name| ResultColumn {
+            name: name.to_string(),
+            data_type: String::new(),
+            filter_kind: FilterColumnKind::Other,
+            nullable: None,
+            source: None,
+        })
+        .collect()
+}
+
+/// `rows` aligned to [`object_columns`]; missing keys read as NULL.
+pub fn object_rows(rows: &[Value], columns: &[ResultColumn]) -> Vec<Vec<Value>> {
+    rows.iter()
+        .map(|row| {
+            columns
+                .iter()
+                .map(|column| match row {
+                    Value::Object(row) => row.get(&column.name).cloned().unwrap_or(Value::Null),
+                    value if column.name == "value" => value.clone(),
+                    _ => Value::Null,
+                })
+                .collect()
+        })
+        .collect()
+}
+
+/// How an edited cell is written back.
+#[derive(Debug, Clone, PartialEq)]
+pub enum CellWrite {
+    /// Bound or formatted like any other value.
+    Value(Value),
+    /// SQL the engine reads exactly, such as a blob or decimal literal.
+    Literal(String),
+}
+
+/// Turn a cell sent back by the UI into something the engine stores without
+/// loss. Untagged values, including objects for JSON columns, pass through.
+/// The UI sends JSON edits in a `json` tag, so a document that happens to
+/// carry a `$type` key is not read as another tag.
+pub fn cell_write(cell: &Value, engine: DatabaseType) -> Result<CellWrite, String> {
+    let Some(tagged) = TaggedValue::from_cell(cell) else {
+        return Ok(CellWrite::Value(cell.clone()));
+    };
+    Ok(match tagged {
+        TaggedValue::Integer { value } => {
+            if let Ok(integer) = value.parse::<i64>() {
+                CellWrite::Value(Value::from(integer))
+            } else if let Ok(integer) = value.parse::<u64>() {
+                CellWrite::Value(Value::from(integer))
+            } else if is_integer_literal(&value) {
+                CellWrite::Literal(value)
+            } else {
+                return Err(format!("Invalid integer value '{value}'"));
+            }
+        }
+        TaggedValue::Decimal { value } if is_numeric_literal(&value) => CellWrite::Literal(value),
+        TaggedValue::Decimal { value }
+            if ["nan", "infinity", "-infinity"].contains(&value.to_ascii_lowercase().as_str()) =>
+        {
+            CellWrite::Value(Value::String(value))
+        }
+        TaggedValue::Decimal { value } => return Err(format!("Invalid decimal value '{value}'")),
+        TaggedValue::Binary { value, .. } => {
+            let bytes = BASE64
+                .decode(&value)
+                .map_err(|error| format!("Invalid binary value: {error}"))?;
+            CellWrite::Literal(binary_literal(&bytes, engine))
+        }
+        TaggedValue::Json { value } => CellWrite::Value(Value::String(value.to_string())),
+        TaggedValue::Array { value } if engine == DatabaseType::Postgres => {
+            CellWrite::Value(Value::String(postgres_array_literal(&value)))
+        }
+        TaggedValue::Geometry { value, srid } => match engine {
+            DatabaseType::Mysql | DatabaseType::Mariadb => CellWrite::Literal(format!(
+                "ST_GeomFromText({}, {})",
+                sql_string(&value),
+                srid.unwrap_or(0)
+            )),
+            DatabaseType::SqlServer => CellWrite::Literal(format!(
+                "geometry::STGeomFromText({}, {})",
+                sql_string(&value),
+                srid.unwrap_or(0)
+            )),
+            DatabaseType::Postgres => CellWrite::Value(Value::String(match srid {
+                Some(srid) => format!("SRID={srid};{value}"),
+                None => value,
+            })),
+            _ => CellWrite::Value(Value::String(value)),
+        },
+        tagged => CellWrite::Value(Value::String(tagged.text())),
+    })
+}
+
+fn binary_literal(bytes: &[u8], engine: DatabaseType) -> String {
+    let hex = hex::encode_upper(bytes);
+    match engine {
+        DatabaseType::Postgres => format!("'\\x{hex}'"),
+        DatabaseType::DuckDb => format!(
+            "'{}'::BLOB",
+            bytes
+                .iter()
+                .map(|byte| format!("\\x{byte:02X}"))
+                .collect::<String>()
+        ),
+        DatabaseType::SqlServer => format!("0x{hex}"),
+        DatabaseType::Clickhouse => format!("unhex('{hex}')"),
+        _ => format!("X'{hex}'"),
+    }
+}
+
+/// A Postgres array literal such as `{1,NULL,"a b"}`.
+fn postgres_array_literal(items: &[Value]) -> String {
+    let elements: Vec<String> = items
+        .iter()
+        .map(|item| match TaggedValue::from_cell(item) {
+            Some(TaggedValue::Array { value }) => postgres_array_literal(&value),
+            Some(tagged) => quote_array_element(&tagged.text()),
+            None => match item {
+                Value::Null => "NULL".to_string(),
+                Value::Array(nested) => postgres_array_literal(nested),
+                Value::Bool(value) => if *value { "t" } else { "f" }.to_string(),
+                Value::Number(number) => number.to_string(),
+                Value::String(text) => quote_array_element(text),
+                Value::Object(_) => quote_array_element(&item.to_string()),
+            },
+        })
+        .collect();
+    format!("{{{}}}", elements.join(","))
+}
+
+fn quote_array_element(text: &str) -> String {
+    format!("\"{}\"", text.replace('\\', "\\\\").replace('"', "\\\""))
+}
+
+fn sql_string(value: &str) -> String {
+    format!("'{}'", value.replace('\'', "''"))
+}
+
+fn is_integer_literal(text: &str) -> bool {
+    let digits = text.strip_prefix(['-', '+']).unwrap_or(text);
+    !digits.is_empty() && digits.chars().all(|character| character.is_ascii_digit())
+}
+
+/// `-12`, `3.50`, `.5`, `1e-3`: digits with an optional sign, point and
+/// exponent, and nothing a literal could smuggle.
+fn is_numeric_literal(text: &str) -> bool {
+    let unsigned = text.strip_prefix(['-', '+']).unwrap_or(text);
+    let (mantissa, exponent) = match unsigned.split_once(['e', 'E']) {
+        Some((mantissa, exponent)) => (mantissa, Some(exponent)),
+        None => (unsigned, None),
+    };
+    let (whole, fraction) = mantissa.split_once('.').unwrap_or((mantissa, ""));
+    let digits = |part: &str| part.chars().all(|character| character.is_ascii_digit());
+    !(whole.is_empty() && fraction.is_empty())
+        && digits(whole)
+        && digits(fraction)
+        && exponent.is_none_or(is_integer_literal)
+}
+
+/// Well-known text and SRID of (E)WKB: PostGIS values, MySQL values after
+/// their SRID prefix, and ISO WKB with Z/M dimensions.
+pub fn wkb_to_wkt(bytes: &[u8]) -> Option<(String, Option<u32>)> {
+    let mut reader = WkbReader { bytes, position: 0 };
+    let geometry = reader.geometry()?;
+    (reader.position == bytes.len()).then(|| (geometry.wkt(), geometry.srid))
+}
+
+struct WkbGeometry {
+    /// `POINT`, `POLYGON Z`, ...
+    header: String,
+    /// `(1 2)`, `((0 0, 1 0, 0 0))` or `EMPTY`.
+    body: String,
+    srid: Option<u32>,
+}
+
+impl WkbGeometry {
+    fn wkt(&self) -> String {
+        if self.body != "EMPTY" && !self.header.contains(' ') {
+            format!("{}{}", self.header, self.body)
+        } else {
+            format!("{} {}", self.header, self.body)
+        }
+    }
+}
+
+struct WkbReader<'a> {
+    bytes: &'a [u8],
+    position: usize,
+}
+
+impl WkbReader<'_> {
+    fn take<const N: usize>(&mut self) -> Option<[u8; N]> {
+        let bytes = self
+            .bytes
+            .get(self.position..self.position + N)?
+            .try_into()
+            .ok()?;
+        self.position += N;
+        Some(bytes)
+    }
+
+    fn u32(&mut self, little_endian: bool) -> Option<u32> {
+        let bytes = self.take::<4>()?;
+        Some(if little_endian {
+            u32::from_le_bytes(bytes)
+        } else {
+            u32::from_be_bytes(bytes)
+        })
+    }
+
+    fn f64(&mut self, little_endian: bool) -> Option<f64> {
+        let bytes = self.take::<8>()?;
+        Some(if little_endian {
+            f64::from_le_bytes(bytes)
+        } else {
+            f64::from_be_bytes(bytes)
+        })
+    }
+
+    fn geometry(&mut self) -> Option<WkbGeometry> {
+        let little_endian = match self.take::<1>()? {
+            [0] => false,
+            [1] => true,
+            _ => return None,
+        };
+        let code = self.u32(little_endian)?;
+        let srid = if code & 0x2000_0000 != 0 {
+            Some(self.u32(little_endian)?)
+        } else {
+            None
+        };
+        // EWKB flags the extra dimensions in the high bits, ISO WKB in the
+        // thousands of the type code.
+        let iso = code & 0x0FFF_FFFF;
+        let (iso_z, iso_m) = match iso / 1000 {
+            0 => (false, false),
+            1 => (true, false),
+            2 => (false, true),
+            3 => (true, true),
+            _ => return None,
+        };
+        let has_z = iso_z || code & 0x8000_0000 != 0;
+        let has_m = iso_m || code & 0x4000_0000 != 0;
+        let dimensions = 2 + usize::from(has_z) + usize::from(has_m);
+        let name = match iso % 1000 {
+            1 => "POINT",
+            2 => "LINESTRING",
+            3 => "POLYGON",
+            4 => "MULTIPOINT",
+            5 => "MULTILINESTRING",
+            6 => "MULTIPOLYGON",
+            7 => "GEOMETRYCOLLECTION",
+            _ => return None,
+        };
+        let header = match (has_z, has_m) {
+            (true, true) => format!("{name} ZM"),
+            (true, false) => format!("{name} Z"),
+            (false, true) => format!("{name} M"),
+            (false, false) => name.to_string(),
+        };
+        let body = match iso % 1000 {
+            1 => {
+                let coordinates = self.coordinates(little_endian, dimensions)?;
+                if coordinates.iter().all(|coordinate| coordinate.is_nan()) {
+                    "EMPTY".to_string()
+                } else {
+                    format!("({})", format_coordinates(&coordinates))
+                }
+            }
+            2 => self.points(little_endian, dimensions)?,
+            3 => {
+                let count = self.u32(little_endian)?;
+                let rings = (0..count)
+                    .map(|_| self.points(little_endian, dimensions))
+                    .collect::<Option<Vec<_>>>()?;
+                parenthesized(rings)
+            }
+            kind => {
+                let count = self.u32(little_endian)?;
+                let members = (0..count)
+                    .map(|_| {
+                        let member = self.geometry()?;
+                        // Multi-geometries list bare bodies; collections
+                        // list whole geometries.
+                        Some(if kind == 7 { member.wkt() } else { member.body })
+                    })
+                    .collect::<Option<Vec<_>>>()?;
+                parenthesized(members)
+            }
+        };
+        Some(WkbGeometry { header, body, srid })
+    }
+
+    fn coordinates(&mut self, little_endian: bool, dimensions: usize) -> Option<Vec<f64>> {
+        (0..dimensions).map(|_| self.f64(little_endian)).collect()
+    }
+
+    fn points(&mut self, little_endian: bool, dimensions: usize) -> Option<String> {
+        let count = self.u32(little_endian)?;
+        let points = (0..count)
+            .map(|_| {
+                self.coordinates(little_endian, dimensions)
+                    .map(|coordinates| format_coordinates(&coordinates))
+            })
+            .collect::<Option<Vec<_>>>()?;
+        Some(parenthesized(points))
+    }
+}
+
+fn format_coordinates(coordinates: &[f64]) -> String {
+    coordinates
+        .iter()
+        .map(f64::to_string)
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
+fn parenthesized(parts: Vec<String>) -> String {
+    if parts.is_empty() {
+        "EMPTY".to_string()
+    } else {
+        format!("({})", parts.join(", "))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn wkb(hex: &str) -> Vec<u8> {
+        hex::decode(hex).unwrap()
+    }
+
+    /// Little-endian WKB of `code` followed by `parts`.
+    fn le_wkb(code: u32, parts: &[&[u8]]) -> Vec<u8> {
+        let mut bytes = vec![1];
+        bytes.extend(code.to_le_bytes());
+        for part in parts {
+            bytes.extend_from_slice(part);
+        }
+        bytes
+    }
+
+    fn le_points(points: &[(f64, f64)]) -> Vec<u8> {
+        let mut bytes = (points.len() as u32).to_le_bytes().to_vec();
+        for (x, y) in points {
+            bytes.extend(x.to_le_bytes());
+            bytes.extend(y.to_le_bytes());
+        }
+        bytes
+    }
+
+    #[test]
+    fn keeps_exact_values_plain_and_tags_the_rest() {
+        assert_eq!(integer(42), json!(42));
+        assert_eq!(
+            integer(9_007_199_254_740_993),
+            json!({ "$type": "integer", "value": "9007199254740993" })
+        );
+        assert_eq!(
+            unsigned(u64::MAX),
+            json!({ "$type": "integer", "value": "18446744073709551615" })
+        );
+        assert_eq!(
+            integer_text("170141183460469231731687303715884105727"),
+            json!({ "$type": "integer", "value": "170141183460469231731687303715884105727" })
+        );
+        assert_eq!(integer_text("-7"), json!(-7));
+        assert_eq!(float(1.5), json!(1.5));
+        assert_eq!(float(f64::NAN), json!({ "$type": "float", "value": "NaN" }));
+        assert_eq!(
+            float(f64::NEG_INFINITY),
+            json!({ "$type": "float", "value": "-Infinity" })
+        );
+        assert_eq!(float32(0.1), json!(0.1));
+        assert_eq!(
+            decimal("12.50"),
+            json!({ "$type": "decimal", "value": "12.50" })
+        );
+        assert_eq!(
+            binary(&[0xCA, 0xFE]),
+            json!({ "$type": "binary", "value": "yv4=", "length": 2 })
+        );
+    }
+
+    #[test]
+    fn tags_arrays_objects_and_unsafe_integers_of_untyped_cells() {
+        assert_eq!(untyped(json!("text")), json!("text"));
+        assert_eq!(untyped(json!(2.5)), json!(2.5));
+        assert_eq!(
+            untyped(json!([1, 9_007_199_254_740_993_i64])),
+            json!({
+                "$type": "array",
+                "value": [1, { "$type": "integer", "value": "9007199254740993" }]
+            })
+        );
+        assert_eq!(
+            untyped(json!({ "a": 1 })),
+            json!({ "$type": "json", "value": { "a": 1 } })
+        );
+    }
+
+    #[test]
+    fn tagged_values_round_trip_through_json() {
+        let cell = geometry("POINT(1 2)", Some(4326));
+        assert_eq!(
+            cell,
+            json!({ "$type": "geometry", "value": "POINT(1 2)", "srid": 4326 })
+        );
+        assert_eq!(
+            TaggedValue::from_cell(&cell),
+            Some(TaggedValue::Geometry {
+                value: "POINT(1 2)".to_string(),
+                srid: Some(4326)
+            })
+        );
+        assert_eq!(TaggedValue::from_cell(&json!({ "a": 1 })), None);
+        assert_eq!(TaggedValue::from_cell(&json!("2024-01-01")), None);
+    }
+
+    #[test]
+    fn reads_wkb_and_ewkb_geometry() {
+        assert_eq!(
+            wkb_to_wkt(&wkb("0101000000000000000000F03F0000000000000040")),
+            Some(("POINT(1 2)".to_string(), None))
+        );
+        assert_eq!(
+            wkb_to_wkt(&wkb("0101000020E6100000000000000000F03F0000000000000040")),
+            Some(("POINT(1 2)".to_string(), Some(4326)))
+        );
+        // Big-endian, with a Z coordinate flagged the ISO way.
+        assert_eq!(
+            wkb_to_wkt(&wkb(
+                "00000003E93FF000000000000040000000000000004008000000000000"
+            )),
+            Some(("POINT Z (1 2 3)".to_string(), None))
+        );
+
+        let ring = le_points(&[(0.0, 0.0), (1.0, 0.0), (1.0, 1.0), (0.0, 0.0)]);
+        let polygon = le_wkb(3, &[&1u32.to_le_bytes(), &ring]);
+        assert_eq!(
+            wkb_to_wkt(&polygon).unwrap().0,
+            "POLYGON((0 0, 1 0, 1 1, 0 0))"
+        );
+
+        let first = le_wkb(1, &[&1.0f64.to_le_bytes(), &2.0f64.to_le_bytes()]);
+        let second = le_wkb(1, &[&3.0f64.to_le_bytes(), &4.0f64.to_le_bytes()]);
+        let multipoint = le_wkb(4, &[&2u32.to_le_bytes(), &first, &second]);
+        assert_eq!(
+            wkb_to_wkt(&multipoint).unwrap().0,
+            "MULTIPOINT((1 2), (3 4))"
+        );
+        let collection = le_wkb(7, &[&1u32.to_le_bytes(), &first]);
+        assert_eq!(
+            wkb_to_wkt(&collection).unwrap().0,
+            "GEOMETRYCOLLECTION(POINT(1 2))"
+        );
+        assert_eq!(
+            wkb_to_wkt(&le_wkb(4, &[&0u32.to_le_bytes()])).unwrap().0,
+            "MULTIPOINT EMPTY"
+        );
+    }
+
+    #[test]
+    fn falls_back_to_binary_for_bytes_that_are_not_wkb() {
+        assert_eq!(wkb_to_wkt(&[1, 2, 3]), None);
+        assert_eq!(wkb_to_wkt(&wkb("0101000000000000000000F03F")), None);
+        assert_eq!(geometry_from_wkb(&[1, 2, 3]), binary(&[1, 2, 3]));
+    }
+
+    #[test]
+    fn writes_tagged_cells_back_without_loss() {
+        let write = |cell: Value, engine| cell_write(&cell, engine).unwrap();
+
+        assert_eq!(
+            write(json!("Ada"), DatabaseType::Mysql),
+            CellWrite::Value(json!("Ada"))
+        );
+        assert_eq!(
+            write(json!({ "name": "Ada" }), DatabaseType::Postgres),
+            CellWrite::Value(json!({ "name": "Ada" }))
+        );
+        assert_eq!(
+            write(integer(9_007_199_254_740_993), DatabaseType::Mysql),
+            CellWrite::Value(json!(9_007_199_254_740_993_i64))
+        );
+        assert_eq!(
+            write(decimal("12.50"), DatabaseType::Mysql),
+            CellWrite::Literal("12.50".to_string())
+        );
+        assert_eq!(
+            write(
+                timestamp("2024-01-02 03:04:05.123456"),
+                DatabaseType::Sqlite
+            ),
+            CellWrite::Value(json!("2024-01-02 03:04:05.123456"))
+        );
+        assert_eq!(
+            write(json(json!({ "a": [1] })), DatabaseType::Postgres),
+            CellWrite::Value(json!(r#"{"a":[1]}"#))
+        );
+        assert_eq!(
+            write(
+                array(vec![json!(1), Value::Null, json!("a \"b\"")]),
+                DatabaseType::Postgres
+            ),
+            CellWrite::Value(json!(r#"{1,NULL,"a \"b\""}"#))
+        );
+        assert_eq!(
+            write(array(vec![json!(1), decimal("2.0")]), DatabaseType::DuckDb),
+            CellWrite::Value(json!(r#"[1,"2.0"]"#))
+        );
+        assert_eq!(
+            write(geometry("POINT(1 2)", Some(4326)), DatabaseType::Mariadb),
+            CellWrite::Literal("ST_GeomFromText('POINT(1 2)', 4326)".to_string())
+        );
+        assert_eq!(
+            write(geometry("POINT(1 2)", Some(4326)), DatabaseType::Postgres),
+            CellWrite::Value(json!("SRID=4326;POINT(1 2)"))
+        );
+    }
+
+    #[test]
+    fn keeps_json_documents_shaped_like_a_tag() {
+        let document = json!({ "type": "date", "value": "2024-01-01" });
+        assert_eq!(TaggedValue::from_cell(&document), None);
+        assert_eq!(
+            cell_write(&document, DatabaseType::Postgres).unwrap(),
+            CellWrite::Value(document.clone())
+        );
+
+        let cell = json(document.clone());
+        assert_eq!(
+            cell,
+            json!({ "$type": "json", "value": { "type": "date", "value": "2024-01-01" } })
+        );
+        assert_eq!(
+            cell_write(&cell, DatabaseType::Mysql).unwrap(),
+            CellWrite::Value(Value::String(document.to_string()))
+        );
+    }
+
+    #[test]
+    fn writes_binary_as_each_engines_literal() {
+        let cell = binary(&[0xCA, 0xFE]);
+        let literal = |engine| match cell_write(&cell, engine).unwrap() {
+            CellWrite::Literal(literal) => literal,
+            other => panic!("expected a literal, got {other:?}"),
+        };
+
+        assert_eq!(literal(DatabaseType::Postgres), r"'\xCAFE'");
+        assert_eq!(literal(DatabaseType::Sqlite), "X'CAFE'");
+        assert_eq!(literal(DatabaseType::Mysql), "X'CAFE'");
+        assert_eq!(literal(DatabaseType::SqlServer), "0xCAFE");
+        assert_eq!(literal(DatabaseType::DuckDb), r"'\xCA\xFE'::BLOB");
+        assert_eq!(literal(DatabaseType::Clickhouse), "unhex('CAFE')");
+    }
+
+    #[test]
+    fn rejects_tagged_numbers_that_are_not_numbers() {
+        assert!(cell_write(&decimal("1; DROP TABLE users"), DatabaseType::Mysql).is_err());
+        assert!(cell_write(&decimal("1e"), DatabaseType::Mysql).is_err());
+        assert!(cell_write(
+            &json!({ "$type": "integer", "value": "12a" }),
+            DatabaseType::Postgres
+        )
+        .is_err());
+        assert!(cell_write(&decimal("-.5e+3"), DatabaseType::Mysql).is_ok());
+        assert_eq!(
+            cell_write(&decimal("NaN"), DatabaseType::Postgres).unwrap(),
+            CellWrite::Value(json!("NaN"))
+        );
+    }
+
+    fn sourced(name: &str, table: &str, column: &str) -> ResultColumn {
+        ResultColumn {
+            source: Some(crate::db::models::ColumnSource {
+                schema: "public".to_string(),
+                table: table.to_string(),
+                column: column.to_string(),
+            }),
+            ..result_column(name, "INT4", FilterDialect::Postgres)
+        }
+    }
+
+    #[test]
+    fn edits_results_that_map_back_to_one_table_with_its_key() {
+        let key = || vec!["id".to_string()];
+        let columns = [
+            sourced("id", "users", "id"),
+            sourced("label", "users", "name"),
+        ];
+        assert_eq!(source_table(&columns), Some(("public", "users")));
+        let target = edit_target(&columns, key()).unwrap();
+        assert_eq!(
+            (target.table.as_str(), target.primary_key),
+            ("users", key())
+        );
+
+        // Missing key column, no key, joins, repeats and computed columns.
+        assert!(edit_target(&columns[1..], key()).is_none());
+        assert!(edit_target(&columns, Vec::new()).is_none());
+        let joined = [sourced("id", "users", "id"), sourced("id", "orders", "id")];
+        assert!(source_table(&joined).is_none());
+        let repeated = [
+            sourced("id", "users", "id"),
+            sourced("again", "users", "id"),
+        ];
+        assert!(source_table(&repeated).is_none());
+        let computed = [
+            sourced("id", "users", "id"),
+            result_column("n", "INT8", FilterDialect::Postgres),
+        ];
+        assert!(source_table(&computed).is_none());
+    }
+
+    #[test]
+    fn aligns_object_rows_to_every_key_in_first_seen_order() {
+        let rows = [
+            json!({"__key": "user:1", "name": "Ada"}),
+            json!({"__key": "user:2", "age": "36", "name": "Alan"}),
+            json!("OK"),
+        ];
+        let columns = object_columns(&rows);
+        let names: Vec<_> = columns.iter().map(|column| column.name.as_str()).collect();
+        assert_eq!(names, ["__key", "name", "age", "value"]);
+        assert_eq!(
+            object_rows(&rows, &columns),
+            [
+                vec![json!("user:1"), json!("Ada"), Value::Null, Value::Null],
+                vec![json!("user:2"), json!("Alan"), json!("36"), Value::Null],
+                vec![Value::Null, Value::Null, Value::Null, json!("OK")],
+            ]
+        );
+    }
+}
@@ -2,22 +2,30 @@ use async_trait::async_trait;
 use serde::{Deserialize, Serialize};
 use serde_json::{json, Value};
 
+use super::capabilities::{DriverCapabilities, ReadOnlyEnforcement};
+use super::cell;
 use super::filter::{
     build_where_clause, classify_column_type, compile_filter, structured_expression, FilterDialect,
     FilterValue,
 };
-use super::{DatabaseDriver, MAX_QUERY_RESULT_ROWS};
+use super::registry::{
+    DriverDescriptor, DriverOptions, SshSupport, DATABASE, HOST, PASSWORD, PORT, SSL, USERNAME,
+};
+use super::{DatabaseDriver, DriverError, QueryLimits, SessionOptions};
 use crate::database::queries::clickhouse::{
     COLUMNS_QUERY, FUNCTION_DEFINITION_QUERY, FUNCTION_SUMMARIES_QUERY, INDEXES_QUERY,
     TABLES_QUERY, TABLE_COLUMNS_QUERY, TABLE_INDEXES_QUERY,
 };
 use crate::db::models::{
     ColumnInfo, ForeignKeyInfo, FunctionDefinition, FunctionSummary, IndexInfo, QueryResult,
-    SchemaOverview, TableDataResponse, TableFilter, TableInfo, TableStructure, TableWithStructure,
-    TestConnectionResult,
+    ResultColumn, SchemaOverview, TableDataResponse, TableFilter, TableInfo, TableStructure,
+    TableWithStructure, TestConnectionResult,
 };
 use std::collections::HashMap;
 
+/// Output format of user queries: a row of names, a row of types, then rows.
+const TYPED_FORMAT: &str = "JSONCompactEachRowWithNamesAndTypes";
+
 /// ClickHouse protocol type
 #[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
 pub enum ClickhouseProtocol {
@@ -42,6 +50,59 @@ pub struct ClickhouseConfig {
     #[allow(dead_code)] // Reserved for future TCP protocol support
     pub protocol: ClickhouseProtocol,
     pub ssl: bool,
+    /// Sent as query settings on every request; HTTP has no session to hold them.
+    pub session: SessionOptions,
+    pub limits: QueryLimits,
+}
+
+pub(crate) const CAPABILITIES: DriverCapabilities = DriverCapabilities {
+    transactions: false,
+    create_table: false,
+    alter_table: false,
+    functions: true,
+    read_only: ReadOnlyEnforcement::Engine,
+    cancellation: true,
+    streaming: true,
+    multiple_databases: true,
+    key_value_browsing: false,
+    explain: true,
+    row_editing: false,
+};
+
+pub(crate) const DESCRIPTOR: DriverDescriptor = DriverDescriptor {
+    id: "clickhouse",
+    label: "ClickHouse",
+    aliases: &[],
+    default_port: Some(8123),
+    fields: &[HOST, PORT, DATABASE, USERNAME, PASSWORD, SSL],
+    ssh: SshSupport::Supported,
+    sessions: true,
+    capabilities: CAPABILITIES,
+    schema_qualified: true,
+    replays_reads: true,
+    explain: |query| Ok(format!("EXPLAIN {query}")),
+    build: build_driver,
+};
+
+fn build_driver(options: DriverOptions) -> Result<Box<dyn DatabaseDriver>, String> {
+    options.session.validate_clickhouse()?;
+    Ok(Box::new(ClickhouseDriver::new(ClickhouseConfig {
+        host: options
+            .text("host")
+            .unwrap_or_else(|| "localhost".to_string()),
+        port: options.integer("port").unwrap_or(8123),
+        database: options
+            .text("database")
+            .unwrap_or_else(|| "default".to_string()),
+        username: options
+            .text("username")
+            .unwrap_or_else(|| "default".to_string()),
+        password: options.text("password").unwrap_or_default(),
+        protocol: ClickhouseProtocol::Http,
+        ssl: options.flag("ssl"),
+        session: options.session,
+        limits: options.limits,
+    })))
 }
 
 pub struct ClickhouseDriver {
@@ -61,10 +122,11 @@ type ClickhouseParam = (String, ClickhouseParamValue);
 
 impl ClickhouseDriver {
     pub fn new(config: ClickhouseConfig) -> Self {
-        Self {
-            config,
-            client: reqwest::Client::new(),
-        }
+        let client = reqwest::Client::builder()
+            .connect_timeout(config.limits.connect_timeout)
+            .build()
+            .unwrap_or_default();
+        Self { config, client }
     }
 
     fn build_url(&self) -> String {
@@ -72,6 +134,34 @@ impl ClickhouseDriver {
         format!("{}://{}:{}", scheme, self.config.host, self.config.port)
     }
 
+    /// Database selection, the query timeout and the connection's session
+    /// settings, sent with every request.
+    fn base_query_params(&self) -> Vec<(String, String)> {
+        let mut params = vec![("database".to_string(), self.config.database.clone())];
+        // ClickHouse takes seconds; an explicit session setting still wins.
+        if let Some(timeout_ms) = self.config.limits.query_timeout_ms() {
+            if !self
+                .config
+                .session
+                .settings
+                .contains_key("max_execution_time")
+            {
+                params.push((
+                    "max_execution_time".to_string(),
+                    (timeout_ms as f64 / 1000.0).to_string(),
+                ));
+            }
+        }
+        params.extend(
+            self.config
+                .session
+                .settings
+                .iter()
+                .map(|(name, value)| (name.clone(), value.clone())),
+        );
+        params
+    }
+
     /// Execute a query and return JSON results using raw HTTP
     async fn execute_query_json(&self, query: &str) -> Result<Vec<Value>, String> {
         self.execute_query_json_with_params(query, &[]).await
@@ -81,26 +171,34 @@ impl ClickhouseDriver {
         &self,
         query: &str,
         params: &[ClickhouseParam],
-    ) -> Result<(Vec<Value>, bool), String> {
+    ) -> Result<(Vec<Vec<Value>>, bool, Vec<ResultColumn>), String> {
         let cleaned_query = query.trim().trim_end_matches(';').trim();
         let upper = cleaned_query.to_uppercase();
+        let limits = &self.config.limits;
         let bounded_query = if upper.starts_with("SELECT") || upper.starts_with("WITH") {
             format!(
                 "SELECT * FROM ({cleaned_query}) LIMIT {}",
-                MAX_QUERY_RESULT_ROWS + 1
+                limits.fetch_limit()
             )
         } else {
             cleaned_query.to_string()
         };
-        let mut rows = self
-            .execute_query_json_with_params(&bounded_query, params)
-            .await?;
-        let truncated = rows.len() > MAX_QUERY_RESULT_ROWS;
-        rows.truncate(MAX_QUERY_RESULT_ROWS);
-        Ok((rows, truncated))
+        // The server enforces `max_execution_time`; dropping the request is the
+        // backstop if the HTTP connection itself stalls.
+        let (rows, columns) = limits
+            .run(
+                true,
+                self.execute_typed_query_with_params(&bounded_query, params),
+            )
+            .await??;
+        let (rows, truncated) = limits.bound_rows(rows);
+        Ok((rows, truncated, columns))
     }
 
-    async fn execute_bounded_query_json(&self, query: &str) -> Result<(Vec<Value>, bool), String> {
+    async fn execute_bounded_query_json(
+        &self,
+        query: &str,
+    ) -> Result<(Vec<Vec<Value>>, bool, Vec<ResultColumn>), String> {
         self.execute_bounded_query_json_with_params(query, &[])
             .await
     }
@@ -132,11 +230,49 @@ impl ClickhouseDriver {
         Ok(rows)
     }
 
+    /// Execute a query with its column types, tagging each value by type.
+    /// Queries that choose their own FORMAT are run as is, untyped.
+    async fn execute_typed_query_with_params(
+        &self,
+        query: &str,
+        params: &[ClickhouseParam],
+    ) -> Result<(Vec<Vec<Value>>, Vec<ResultColumn>), String> {
+        if Self::has_format(query) {
+            let rows = self.execute_query_json_with_params(query, params).await?;
+            let columns = cell::untyped_columns(&rows, FilterDialect::Clickhouse);
+            return Ok((cell::untyped_rows(rows, &columns), columns));
+        }
+
+        let client = &self.client;
+        let request = self.build_request(client, query, params, TYPED_FORMAT)?;
+        let response = client.execute(request).await.map_err(|e| e.to_string())?;
+        if !response.status().is_success() {
+            let error_text = response.text().await.unwrap_or_default();
+            return Err(error_text);
+        }
+        let text = response.text().await.map_err(|e| e.to_string())?;
+        parse_typed_rows(&text)
+    }
+
+    fn has_format(query: &str) -> bool {
+        query.trim().to_uppercase().contains("FORMAT ")
+    }
+
     fn build_query_request(
         &self,
         client: &reqwest::Client,
         query: &str,
         params: &[ClickhouseParam],
+    ) -> Result<reqwest::Request, String> {
+        self.build_request(client, query, params, "JSONEachRow")
+    }
+
+    fn build_request(
+        &self,
+        client: &reqwest::Client,
+        query: &str,
+        params: &[ClickhouseParam],
+        format: &str,
     ) -> Result<reqwest::Request, String> {
         let url = self.build_url();
 
@@ -144,13 +280,20 @@ impl ClickhouseDriver {
         let cleaned_query = query.trim().trim_end_matches(';').trim();
 
         // Only add FORMAT if not already present
-        let full_query = if cleaned_query.to_uppercase().contains("FORMAT ") {
+        let full_query = if Self::has_format(cleaned_query) {
             cleaned_query.to_string()
         } else {
-            format!("{} FORMAT JSONEachRow", cleaned_query)
+            format!("{} FORMAT {}", cleaned_query, format)
         };
 
-        let mut query_params = vec![("database".to_string(), self.config.database.clone())];
+        let mut query_params = self.base_query_params();
+        if format == TYPED_FORMAT {
+            // Decimals as strings keep their exact digits.
+            query_params.push((
+                "output_format_json_quote_decimals".to_string(),
+                "1".to_string(),
+            ));
+        }
         query_params.extend(params.iter().map(|(key, value)| {
             let value = match value {
                 ClickhouseParamValue::EscapedText(value) => Self::escape_param_text(value),
@@ -230,13 +373,17 @@ impl ClickhouseDriver {
         let url = self.build_url();
         let client = &self.client;
 
-        let response = client
+        let request = client
             .post(&url)
             .basic_auth(&self.config.username, Some(&self.config.password))
-            .query(&[("database", &self.config.database)])
+            .query(&self.base_query_params())
             .body(query.to_string())
-            .send()
-            .await
+            .send();
+        let response = self
+            .config
+            .limits
+            .run(true, request)
+            .await?
             .map_err(|e| e.to_string())?;
 
         if !response.status().is_success() {
@@ -305,11 +452,11 @@ impl ClickhouseDriver {
 
 #[async_trait]
 impl DatabaseDriver for ClickhouseDriver {
-    fn as_any(&self) -> &dyn std::any::Any {
-        self
+    fn capabilities(&self) -> DriverCapabilities {
+        CAPABILITIES
     }
 
-    async fn test_connection(&self) -> Result<TestConnectionResult, String> {
+    async fn test_connection(&self) -> Result<TestConnectionResult, DriverError> {
         match self.execute_query_json("SELECT 1").await {
             Ok(_) => Ok(TestConnectionResult {
                 success: true,
@@ -322,7 +469,7 @@ impl DatabaseDriver for ClickhouseDriver {
         }
     }
 
-    async fn list_tables(&self) -> Result<Vec<TableInfo>, String> {
+    async fn list_tables(&self) -> Result<Vec<TableInfo>, DriverError> {
         let params = Self::catalog_params(&self.config.database, None);
         let rows = self
             .execute_query_json_with_params(TABLES_QUERY, &params)
@@ -338,6 +485,16 @@ impl DatabaseDriver for ClickhouseDriver {
             .collect())
     }
 
+    async fn list_databases(&self) -> Result<Vec<String>, DriverError> {
+        let rows = self
+            .execute_query_json("SELECT name FROM system.databases ORDER BY name")
+            .await?;
+        Ok(rows
+            .into_iter()
+            .filter_map(|row| row["name"].as_str().map(str::to_string))
+            .collect())
+    }
+
     async fn get_table_data(
         &self,
         _schema: &str,
@@ -347,7 +504,7 @@ impl DatabaseDriver for ClickhouseDriver {
         filter: Option<TableFilter>,
         sort_column: Option<String>,
         sort_direction: Option<String>,
-    ) -> Result<TableDataResponse, String> {
+    ) -> Result<TableDataResponse, DriverError> {
         let offset = (page - 1) * limit;
         let compiled_filter = if let Some(expression) = structured_expression(filter.as_ref()) {
             let columns = self
@@ -404,12 +561,16 @@ impl DatabaseDriver for ClickhouseDriver {
             "SELECT * FROM `{}`{}{} LIMIT {} OFFSET {}",
             table, where_clause, order_clause, limit, offset
         );
-        let data = self
-            .execute_query_json_with_params(&data_query, &filter_params)
+        let (rows, columns) = self
+            .execute_typed_query_with_params(&data_query, &filter_params)
             .await?;
 
         Ok(TableDataResponse {
-            data,
+            data: rows
+                .iter()
+                .map(|row| cell::row_object(&columns, row))
+                .collect(),
+            columns,
             total,
             page,
             limit,
@@ -420,7 +581,7 @@ impl DatabaseDriver for ClickhouseDriver {
         &self,
         _schema: &str,
         table: &str,
-    ) -> Result<TableStructure, String> {
+    ) -> Result<TableStructure, DriverError> {
         // Get columns
         let params = Self::catalog_params(&self.config.database, Some(table));
         let columns = self
@@ -477,7 +638,7 @@ impl DatabaseDriver for ClickhouseDriver {
         })
     }
 
-    async fn execute_query_read_only(&self, query: &str) -> Result<QueryResult, String> {
+    async fn execute_query_read_only(&self, query: &str) -> Result<QueryResult, DriverError> {
         let start_time = std::time::Instant::now();
         // `readonly=1` is enforced server-side: writes, DDL, and SET are rejected.
         let params = vec![(
@@ -488,12 +649,17 @@ impl DatabaseDriver for ClickhouseDriver {
             .execute_bounded_query_json_with_params(query, &params)
             .await
         {
-            Ok((rows, truncated)) => Ok(QueryResult::from_rows(rows, truncated, start_time)),
-            Err(e) => Ok(QueryResult::from_error(e, start_time)),
+            Ok((rows, truncated, columns)) => Ok(QueryResult::from_table(
+                columns, rows, truncated, start_time,
+            )),
+            Err(e) => Ok(QueryResult::from_error(
+                DriverError::from_clickhouse(e).locate(query),
+                start_time,
+            )),
         }
     }
 
-    async fn get_schema_overview(&self) -> Result<SchemaOverview, String> {
+    async fn get_schema_overview(&self) -> Result<SchemaOverview, DriverError> {
         let params = Self::catalog_params(&self.config.database, None);
         let columns_rows = self
             .execute_query_json_with_params(COLUMNS_QUERY, &params)
@@ -602,7 +768,7 @@ impl DatabaseDriver for ClickhouseDriver {
         _schema: &str,
         name: &str,
         identity_args: &str,
-    ) -> Result<FunctionDefinition, String> {
+    ) -> Result<FunctionDefinition, DriverError> {
         let mut params = Self::catalog_params(&self.config.database, None);
         params.push((
             "param_name".to_string(),
@@ -618,7 +784,7 @@ impl DatabaseDriver for ClickhouseDriver {
         let (summary, create_query) = Self::map_clickhouse_function(&self.config.database, &row);
 
         if !identity_args.is_empty() && summary.identity_args != identity_args {
-            return Err(format!("Function not found: {}({})", name, identity_args));
+            return Err(format!("Function not found: {}({})", name, identity_args).into());
         }
 
         let definition = if create_query.trim().is_empty() {
@@ -641,7 +807,7 @@ impl DatabaseDriver for ClickhouseDriver {
         })
     }
 
-    async fn execute_query(&self, query: &str) -> Result<QueryResult, String> {
+    async fn execute_query(&self, query: &str) -> Result<QueryResult, DriverError> {
         let start_time = std::time::Instant::now();
         // Check if it's a SELECT query
         let trimmed = query.trim().to_uppercase();
@@ -652,50 +818,172 @@ impl DatabaseDriver for ClickhouseDriver {
 
         if is_select {
             match self.execute_bounded_query_json(query).await {
-                Ok((rows, truncated)) => {
-                    let row_count = rows.len() as i64;
-                    Ok(QueryResult {
-                        data: rows,
-                        row_count,
-                        truncated,
-                        rows_affected: None,
-                        error: None,
-                        time_taken_ms: Some(start_time.elapsed().as_millis()),
-                    })
-                }
-                Err(e) => Ok(QueryResult {
-                    data: vec![],
-                    row_count: 0,
-                    truncated: false,
-                    rows_affected: None,
-                    error: Some(e),
-                    time_taken_ms: Some(start_time.elapsed().as_millis()),
-                }),
+                Ok((rows, truncated, columns)) => Ok(QueryResult::from_table(
+                    columns, rows, truncated, start_time,
+                )),
+                Err(e) => Ok(QueryResult::from_error(
+                    DriverError::from_clickhouse(e).locate(query),
+                    start_time,
+                )),
             }
         } else {
             // For non-SELECT queries (INSERT, ALTER, CREATE, etc.)
             match self.execute_command(query).await {
                 Ok(_) => Ok(QueryResult {
-                    data: vec![json!({"result": "Query executed successfully"})],
                     row_count: 0,
-                    truncated: false,
                     rows_affected: Some(0),
-                    error: None,
-                    time_taken_ms: Some(start_time.elapsed().as_millis()),
-                }),
-                Err(e) => Ok(QueryResult {
-                    data: vec![],
-                    row_count: 0,
-                    truncated: false,
-                    rows_affected: None,
-                    error: Some(e),
-                    time_taken_ms: Some(start_time.elapsed().as_millis()),
+                    ..QueryResult::from_rows(
+                        vec![json!({"result": "Query executed successfully"})],
+                        false,
+                        start_time,
+                    )
                 }),
+                Err(e) => Ok(QueryResult::from_error(
+                    DriverError::from_clickhouse(e).locate(query),
+                    start_time,
+                )),
             }
         }
     }
 }
 
+/// Rows of a `JSONCompactEachRowWithNamesAndTypes` response as objects of
+/// tagged values, with their columns.
+fn parse_typed_rows(text: &str) -> Result<(Vec<Vec<Value>>, Vec<ResultColumn>), String> {
+    let mut lines = text.lines().filter(|line| !line.is_empty());
+    let mut header = || -> Result<Vec<String>, String> {
+        match lines.next() {
+            Some(line) => serde_json::from_str(line).map_err(|error| error.to_string()),
+            None => Ok(Vec::new()),
+        }
+    };
+    let names = header()?;
+    let types = header()?;
+    let columns: Vec<ResultColumn> = names
+        .iter()
+        .zip(&types)
+        .map(|(name, data_type)| ResultColumn {
+            nullable: Some(is_nullable(data_type)),
+            ..cell::result_column(name.as_str(), data_type.as_str(), FilterDialect::Clickhouse)
+        })
+        .collect();
+    let rows = lines
+        .filter_map(|line| serde_json::from_str::<Vec<Value>>(line).ok())
+        .map(|values| {
+            values
+                .into_iter()
+                .zip(&types)
+                .map(|(value, data_type)| typed_cell(value, data_type))
+                .collect()
+        })
+        .collect();
+    Ok((rows, columns))
+}
+
+/// Whether a column type admits NULL, including `LowCardinality(Nullable(..))`.
+fn is_nullable(data_type: &str) -> bool {
+    let data_type = data_type.trim();
+    type_argument(data_type, "LowCardinality")
+        .unwrap_or(data_type)
+        .starts_with("Nullable(")
+}
+
+/// The argument of a type such as `Nullable(String)`.
+fn type_argument<'a>(data_type: &'a str, wrapper: &str) -> Option<&'a str> {
+    data_type
+        .strip_prefix(wrapper)?
+        .strip_prefix('(')?
+        .strip_suffix(')')
+        .map(str::trim)
+}
+
+fn typed_cell(value: Value, data_type: &str) -> Value {
+    let data_type = data_type.trim();
+    if value.is_null() {
+        return value;
+    }
+    for wrapper in ["Nullable", "LowCardinality"] {
+        if let Some(inner) = type_argument(data_type, wrapper) {
+            return typed_cell(value, inner);
+        }
+    }
+    if let Some(element) = type_argument(data_type, "Array") {
+        return match value {
+            Value::Array(items) => cell::array(
+                items
+                    .into_iter()
+                    .map(|item| typed_cell(item, element))
+                    .collect(),
+            ),
+            value => cell::untyped(value),
+        };
+    }
+    if let Some(wkt) = geometry_wkt(&value, data_type) {
+        return cell::geometry(wkt, None);
+    }
+    let base = data_type.split('(').next().unwrap_or_default();
+    match (base, value) {
+        (
+            "Int64" | "UInt64" | "Int128" | "UInt128" | "Int256" | "UInt256",
+            Value::String(digits),
+        ) => cell::integer_text(&digits),
+        (base, Value::String(text)) if base.starts_with("Decimal") => cell::decimal(text),
+        (base, Value::Number(number)) if base.starts_with("Decimal") => {
+            cell::decimal(number.to_string())
+        }
+        ("Date" | "Date32", Value::String(text)) => cell::date(text),
+        ("DateTime" | "DateTime64", Value::String(text)) => cell::timestamp(text),
+        ("UUID", Value::String(text)) => cell::uuid(text),
+        ("Map" | "Tuple" | "Nested" | "JSON" | "Object" | "Variant" | "Dynamic", value) => {
+            cell::json(value)
+        }
+        (_, value) => cell::untyped(value),
+    }
+}
+
+/// WKT for ClickHouse's geo types, which arrive as nested coordinate arrays.
+fn geometry_wkt(value: &Value, data_type: &str) -> Option<String> {
+    fn point(value: &Value) -> Option<String> {
+        match value.as_array()?.as_slice() {
+            [x, y] => Some(format!("{} {}", x.as_f64()?, y.as_f64()?)),
+            _ => None,
+        }
+    }
+    fn points(value: &Value) -> Option<String> {
+        let points = value
+            .as_array()?
+            .iter()
+            .map(point)
+            .collect::<Option<Vec<_>>>()?;
+        Some(format!("({})", points.join(", ")))
+    }
+    fn polygon(value: &Value) -> Option<String> {
+        let rings = value
+            .as_array()?
+            .iter()
+            .map(points)
+            .collect::<Option<Vec<_>>>()?;
+        Some(format!("({})", rings.join(", ")))
+    }
+    fn many(value: &Value, part: fn(&Value) -> Option<String>) -> Option<String> {
+        let parts = value
+            .as_array()?
+            .iter()
+            .map(part)
+            .collect::<Option<Vec<_>>>()?;
+        Some(format!("({})", parts.join(", ")))
+    }
+
+    Some(match data_type {
+        "Point" => format!("POINT({})", point(value)?),
+        "Ring" | "LineString" => format!("LINESTRING{}", points(value)?),
+        "MultiLineString" => format!("MULTILINESTRING{}", polygon(value)?),
+        "Polygon" => format!("POLYGON{}", polygon(value)?),
+        "MultiPolygon" => format!("MULTIPOLYGON{}", many(value, polygon)?),
+        _ => return None,
+    })
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -720,6 +1008,8 @@ mod tests {
             password: String::new(),
             protocol: ClickhouseProtocol::Http,
             ssl: false,
+            session: SessionOptions::default(),
+            limits: QueryLimits::default(),
         });
         let params = ClickhouseDriver::catalog_params(database, Some(table));
         let client = reqwest::Client::new();
@@ -741,6 +1031,36 @@ mod tests {
         assert_eq!(query_params["param_table"], "table'\\\\\\tend");
     }
 
+    #[test]
+    fn request_carries_session_settings_and_timeout_as_query_parameters() {
+        let driver = ClickhouseDriver::new(ClickhouseConfig {
+            host: "localhost".to_string(),
+            port: 8123,
+            database: "default".to_string(),
+            username: "default".to_string(),
+            password: String::new(),
+            protocol: ClickhouseProtocol::Http,
+            ssl: false,
+            session: SessionOptions::new(
+                None,
+                [("max_threads".to_string(), "4".to_string())].into(),
+            ),
+            limits: QueryLimits {
+                query_timeout: Some(std::time::Duration::from_millis(2500)),
+                ..QueryLimits::default()
+            },
+        });
+        let client = reqwest::Client::new();
+        let request = driver
+            .build_query_request(&client, "SELECT 1", &[])
+            .unwrap();
+        let query_params: HashMap<_, _> = request.url().query_pairs().into_owned().collect();
+
+        assert_eq!(query_params["database"], "default");
+        assert_eq!(query_params["max_threads"], "4");
+        assert_eq!(query_params["max_execution_time"], "2.5");
+    }
+
     #[test]
     fn filter_request_escapes_only_text_values() {
         let driver = ClickhouseDriver::new(ClickhouseConfig {
@@ -751,6 +1071,8 @@ mod tests {
             password: String::new(),
             protocol: ClickhouseProtocol::Http,
             ssl: false,
+            session: SessionOptions::default(),
+            limits: QueryLimits::default(),
         });
         let values = vec![
             FilterValue::Text("a\\b\n".to_string()),
@@ -782,4 +1104,60 @@ mod tests {
             "340282366920938463463374607431768211455"
         );
     }
+
+    #[test]
+    fn parses_typed_rows_into_tagged_cells() {
+        let text = concat!(
+            "[\"id\",\"price\",\"at\",\"tags\",\"spot\"]\n",
+            "[\"UInt64\",\"Nullable(Decimal(10, 2))\",\"DateTime\",\"Array(Int64)\",\"Point\"]\n",
+            "[\"18446744073709551615\",\"12.50\",\"2024-01-02 03:04:05\",[\"1\"],[1.5,2]]\n",
+            "[\"7\",null,\"2024-01-02 03:04:05\",[],[0,0]]\n",
+        );
+        let (rows, columns) = parse_typed_rows(text).unwrap();
+
+        assert_eq!(columns[1].name, "price");
+        assert_eq!(columns[1].data_type, "Nullable(Decimal(10, 2))");
+        assert_eq!(
+            columns[1].filter_kind,
+            crate::db::models::FilterColumnKind::Decimal
+        );
+        assert_eq!(columns[0].nullable, Some(false));
+        assert_eq!(columns[1].nullable, Some(true));
+        assert_eq!(
+            rows[0],
+            vec![
+                json!({"$type": "integer", "value": "18446744073709551615"}),
+                json!({"$type": "decimal", "value": "12.50"}),
+                json!({"$type": "timestamp", "value": "2024-01-02 03:04:05"}),
+                json!({"$type": "array", "value": [1]}),
+                json!({"$type": "geometry", "value": "POINT(1.5 2)"}),
+            ]
+        );
+        assert_eq!(rows[1][0], json!(7));
+        assert_eq!(rows[1][1], Value::Null);
+    }
+
+    #[test]
+    fn typed_requests_quote_decimals() {
+        let driver = ClickhouseDriver::new(ClickhouseConfig {
+            host: "localhost".to_string(),
+            port: 8123,
+            database: "default".to_string(),
+            username: "default".to_string(),
+            password: String::new(),
+            protocol: ClickhouseProtocol::Http,
+            ssl: false,
+            session: SessionOptions::default(),
+            limits: QueryLimits::default(),
+        });
+        let client = reqwest::Client::new();
+        let request = driver
+            .build_request(&client, "SELECT 1;", &[], TYPED_FORMAT)
+            .unwrap();
+        let body = std::str::from_utf8(request.body().unwrap().as_bytes().unwrap()).unwrap();
+        let query_params: HashMap<_, _> = request.url().query_pairs().into_owned().collect();
+
+        assert_eq!(body, "SELECT 1 FORMAT JSONCompactEachRowWithNamesAndTypes");
+        assert_eq!(query_params["output_format_json_quote_decimals"], "1");
+    }
 }
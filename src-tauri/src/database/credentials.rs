@@ -0,0 +1,299 @@
+//! External credential providers for short-lived passwords.
+//!
+//! A connection can take its password (or D1 API token) from a command such as
+//! `aws rds generate-db-auth-token ...`, `vault read -field=password ...` or
+//! `op read ...`. The pool manager runs the command before connecting, caches
+//! its output for the configured TTL and drops the cached value as soon as the
+//! engine rejects it, so an expired token is replaced on the next attempt.
+
+use std::collections::HashMap;
+use std::process::Stdio;
+use std::time::{Duration, Instant};
+use tokio::process::Command;
+
+pub const DEFAULT_CREDENTIAL_TTL: Duration = Duration::from_secs(5 * 60);
+pub const CREDENTIAL_COMMAND_TIMEOUT: Duration = Duration::from_secs(30);
+
+/// Longest stderr excerpt included in an error message.
+const MAX_STDERR_CHARS: usize = 500;
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct CredentialCommand {
+    /// Run through the platform shell; the trimmed stdout is the secret.
+    pub command: String,
+    /// How long the output is reused. Zero runs the command on every connect.
+    pub ttl: Duration,
+}
+
+impl CredentialCommand {
+    /// Build from the columns stored on a connection. A blank command means
+    /// the saved password is used as-is.
+    pub fn from_settings(command: &str, ttl_secs: Option<i64>) -> Result<Option<Self>, String> {
+        let command = command.trim();
+        if command.is_empty() {
+            return Ok(None);
+        }
+        let ttl = match ttl_secs {
+            None => DEFAULT_CREDENTIAL_TTL,
+            Some(secs) if secs >= 0 => Duration::from_secs(secs as u64),
+            Some(_) => return Err("Credential cache TTL cannot be negative".to_string()),
+        };
+        Ok(Some(Self {
+            command: command.to_string(),
+            ttl,
+        }))
+    }
+
+    /// Run the command once, without caching.
+    pub async fn run(&self) -> Result<String, String> {
+        let mut child = shell_command(&self.command)
+            .stdin(Stdio::null())
+            .stdout(Stdio::piped())
+            .stderr(Stdio::piped())
+            .kill_on_drop(true)
+            .spawn()
+            .map_err(|e| format!("Failed to start credential command: {e}"))?;
+        let stdout = child.stdout.take();
+        let stderr = child.stderr.take();
+
+        let output = tokio::time::timeout(CREDENTIAL_COMMAND_TIMEOUT, async {
+            let read_stdout = read_pipe(stdout);
+            let read_stderr = read_pipe(stderr);
+            let (status, stdout, stderr) = tokio::join!(child.wait(), read_stdout, read_stderr);
+            (status, stdout, stderr)
+        })
+        .await
+        .map_err(|_| {
+            format!(
+                "Credential command timed out after {}s",
+                CREDENTIAL_COMMAND_TIMEOUT.as_secs()
+            )
+        })?;
+
+        let (status, stdout, stderr) = output;
+        let status = status.map_err(|e| format!("Credential command failed: {e}"))?;
+        if !status.success() {
+            let stderr = stderr.trim();
+            let detail: String = stderr.chars().take(MAX_STDERR_CHARS).collect();
+            return Err(match status.code() {
+                Some(code) if detail.is_empty() => {
+                    format!("Credential command exited with status {code}")
+                }
+                Some(code) => format!("Credential command exited with status {code}: {detail}"),
+                None => "Credential command was terminated by a signal".to_string(),
+            });
+        }
+
+        let secret = stdout.trim();
+        if secret.is_empty() {
+            return Err("Credential command produced no output".to_string());
+        }
+        Ok(secret.to_string())
+    }
+}
+
+#[cfg(windows)]
+fn shell_command(command: &str) -> Command {
+    let mut shell = Command::new("cmd");
+    shell.arg("/C").arg(command);
+    shell
+}
+
+#[cfg(not(windows))]
+fn shell_command(command: &str) -> Command {
+    let mut shell = Command::new("sh");
+    shell.arg("-c").arg(command);
+    shell
+}
+
+async fn read_pipe(pipe: Option<impl tokio::io::AsyncRead + Unpin>) -> String {
+    use tokio::io::AsyncReadExt;
+    let mut output = String::new();
+    if let Some(mut pipe) = pipe {
+        let mut bytes = Vec::new();
+        let _ = pipe.read_to_end(&mut bytes).await;
+        output = String::from_utf8_lossy(&bytes).into_owned();
+    }
+    output
+}
+
+struct CachedCredential {
+    command: String,
+    secret: String,
+    expires_at: Instant,
+}
+
+/// Command output per connection UUID. Entries are keyed on the command too,
+/// so editing a connection's command never reuses the old command's output.
+#[derive(Default)]
+pub struct CredentialCache {
+    entries: std::sync::Mutex<HashMap<String, CachedCredential>>,
+}
+
+impl CredentialCache {
+    /// Return the cached secret for `uuid`, running the command when there is
+    /// none or it has expired. The flag reports whether the value was cached.
+    pub async fn resolve(
+        &self,
+        uuid: &str,
+        command: &CredentialCommand,
+    ) -> Result<(String, bool), String> {
+        if let Some(secret) = self.cached(uuid, command) {
+            return Ok((secret, true));
+        }
+
+        let secret = command.run().await?;
+        if !command.ttl.is_zero() {
+            if let Ok(mut entries) = self.entries.lock() {
+                entries.insert(
+                    uuid.to_string(),
+                    CachedCredential {
+                        command: command.command.clone(),
+                        secret: secret.clone(),
+                        expires_at: Instant::now() + command.ttl,
+                    },
+                );
+            }
+        }
+        Ok((secret, false))
+    }
+
+    fn cached(&self, uuid: &str, command: &CredentialCommand) -> Option<String> {
+        let entries = self.entries.lock().ok()?;
+        entries
+            .get(uuid)
+            .filter(|entry| entry.command == command.command && entry.expires_at > Instant::now())
+            .map(|entry| entry.secret.clone())
+    }
+
+    pub fn invalidate(&self, uuid: &str) {
+        if let Ok(mut entries) = self.entries.lock() {
+            entries.remove(uuid);
+        }
+    }
+}
+
+/// Whether a connect error means the engine rejected the credentials, as
+/// opposed to the server being unreachable.
+pub(crate) fn is_authentication_error(message: &str) -> bool {
+    let message = message.to_ascii_lowercase();
+    [
+        "password authentication failed",
+        "pam authentication failed",
+        "access denied for user",
+        "authentication failed",
+        "authentication_failed",
+        "authentication error",
+        "wrongpass",
+        "noauth",
+        "401 unauthorized",
+        "403 forbidden",
+    ]
+    .iter()
+    .any(|pattern| message.contains(pattern))
+}
+
+#[cfg(all(test, unix))]
+mod tests {
+    use super::{is_authentication_error, CredentialCache, CredentialCommand};
+    use std::time::Duration;
+
+    fn command(command: String, ttl_secs: u64) -> CredentialCommand {
+        CredentialCommand {
+            command,
+            ttl: Duration::from_secs(ttl_secs),
+        }
+    }
+
+    /// A provider script that hands out `token-1`, `token-2`, ... per call.
+    fn counting_script(dir: &tempfile::TempDir) -> String {
+        let calls = dir.path().join("calls");
+        format!(
+            "printf x >> '{0}'; echo \"token-$(wc -c < '{0}' | tr -d ' ')\"",
+            calls.display()
+        )
+    }
+
+    #[test]
+    fn blank_commands_keep_the_saved_password() {
+        assert_eq!(CredentialCommand::from_settings("  ", Some(60)), Ok(None));
+        let command = CredentialCommand::from_settings(" op read op://db/password ", None)
+            .unwrap()
+            .unwrap();
+        assert_eq!(command.command, "op read op://db/password");
+        assert_eq!(command.ttl, super::DEFAULT_CREDENTIAL_TTL);
+        assert!(CredentialCommand::from_settings("true", Some(-1)).is_err());
+    }
+
+    #[tokio::test]
+    async fn trims_output_and_reports_failures() {
+        let secret = command("printf '  s3cret\\n\\n'".to_string(), 0)
+            .run()
+            .await
+            .unwrap();
+        assert_eq!(secret, "s3cret");
+
+        let error = command("echo 'token expired' >&2; exit 3".to_string(), 0)
+            .run()
+            .await
+            .unwrap_err();
+        assert_eq!(
+            error,
+            "Credential command exited with status 3: token expired"
+        );
+
+        let error = command("true".to_string(), 0).run().await.unwrap_err();
+        assert_eq!(error, "Credential command produced no output");
+    }
+
+    #[tokio::test]
+    async fn caches_output_until_invalidated() {
+        let dir = tempfile::tempdir().unwrap();
+        let cache = CredentialCache::default();
+        let provider = command(counting_script(&dir), 60);
+
+        assert_eq!(
+            cache.resolve("conn", &provider).await.unwrap(),
+            ("token-1".to_string(), false)
+        );
+        assert_eq!(
+            cache.resolve("conn", &provider).await.unwrap(),
+            ("token-1".to_string(), true)
+        );
+
+        cache.invalidate("conn");
+        assert_eq!(
+            cache.resolve("conn", &provider).await.unwrap(),
+            ("token-2".to_string(), false)
+        );
+
+        let uncached = command(counting_script(&dir), 0);
+        assert_eq!(
+            cache.resolve("other", &uncached).await.unwrap(),
+            ("token-3".to_string(), false)
+        );
+        assert_eq!(
+            cache.resolve("other", &uncached).await.unwrap(),
+            ("token-4".to_string(), false)
+        );
+    }
+
+    #[test]
+    fn recognizes_rejected_credentials() {
+        assert!(is_authentication_error(
+            "error returned from database: password authentication failed for user \"app\""
+        ));
+        assert!(is_authentication_error(
+            "Access denied for user 'app'@'10.0.0.1' (using password: YES)"
+        ));
+        assert!(is_authentication_error(
+            "WRONGPASS invalid username-password pair or user is disabled."
+        ));
+        assert!(is_authentication_error(
+            "Cloudflare D1 API error 10000: Authentication error"
+        ));
+        assert!(!is_authentication_error(
+            "Connection refused (os error 111)"
+        ));
+    }
+}
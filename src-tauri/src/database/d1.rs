@@ -5,17 +5,20 @@ use serde::{Deserialize, Serialize};
 use serde_json::{json, Value};
 use std::time::Instant;
 
+use super::capabilities::{DriverCapabilities, ReadOnlyEnforcement};
+use super::cell;
 use super::create_table::build_sqlite_create_table_sql;
 use super::filter::{
     build_where_clause, classify_column_type, compile_filter, structured_expression, FilterDialect,
     FilterValue,
 };
 use super::queries::sqlite::TABLES_QUERY;
-use super::{DatabaseDriver, MAX_QUERY_RESULT_ROWS};
+use super::registry::{ConfigField, ConfigFieldKind, DriverDescriptor, DriverOptions, SshSupport};
+use super::{DatabaseDriver, DriverError, QueryLimits};
 use crate::db::models::{
-    ColumnInfo, CreateTableRequest, ForeignKeyInfo, IndexInfo, QueryResult, SchemaOverview,
-    TableDataResponse, TableFilter, TableInfo, TableStructure, TableWithStructure,
-    TestConnectionResult,
+    ColumnInfo, ColumnSource, CreateTableRequest, ForeignKeyInfo, IndexInfo, QueryResult,
+    ResultColumn, SchemaOverview, TableDataResponse, TableFilter, TableInfo, TableStructure,
+    TableWithStructure, TestConnectionResult,
 };
 
 const CLOUDFLARE_API_BASE_URL: &str = "https://api.cloudflare.com/client/v4";
@@ -25,6 +28,7 @@ pub struct D1Config {
     pub account_id: String,
     pub database_id: String,
     pub api_token: String,
+    pub limits: QueryLimits,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
@@ -105,6 +109,50 @@ struct D1QueryRequest<'a> {
     params: Vec<Value>,
 }
 
+pub(crate) const CAPABILITIES: DriverCapabilities = DriverCapabilities {
+    transactions: false,
+    create_table: true,
+    alter_table: false,
+    functions: false,
+    read_only: ReadOnlyEnforcement::Unsupported,
+    cancellation: false,
+    streaming: false,
+    multiple_databases: false,
+    key_value_browsing: false,
+    explain: true,
+    row_editing: true,
+};
+
+pub(crate) const DESCRIPTOR: DriverDescriptor = DriverDescriptor {
+    id: "d1",
+    label: "Cloudflare D1",
+    aliases: &["cloudflare-d1"],
+    default_port: Some(443),
+    fields: &[
+        ConfigField::new("account_id", "Account ID", ConfigFieldKind::Text).required(),
+        ConfigField::new("database_id", "Database ID", ConfigFieldKind::Text).required(),
+        ConfigField::new("password", "API token", ConfigFieldKind::Text)
+            .required()
+            .secret(),
+    ],
+    ssh: SshSupport::Unsupported,
+    sessions: false,
+    capabilities: CAPABILITIES,
+    schema_qualified: false,
+    replays_reads: false,
+    explain: |query| Ok(format!("EXPLAIN QUERY PLAN {query}")),
+    build: build_driver,
+};
+
+fn build_driver(options: DriverOptions) -> Result<Box<dyn DatabaseDriver>, String> {
+    Ok(Box::new(D1Driver::new(D1Config {
+        account_id: options.text("account_id").unwrap_or_default(),
+        database_id: options.text("database_id").unwrap_or_default(),
+        api_token: options.text("password").unwrap_or_default(),
+        limits: options.limits,
+    })))
+}
+
 pub struct D1Driver {
     config: D1Config,
     client: Client,
@@ -113,18 +161,17 @@ pub struct D1Driver {
 
 impl D1Driver {
     pub fn new(config: D1Config) -> Self {
-        Self {
-            config,
-            client: Client::new(),
-            api_base_url: api_base_url(),
-        }
+        Self::with_api_base_url(config, api_base_url())
     }
 
-    #[cfg(test)]
     fn with_api_base_url(config: D1Config, api_base_url: String) -> Self {
+        let client = Client::builder()
+            .connect_timeout(config.limits.connect_timeout)
+            .build()
+            .unwrap_or_default();
         Self {
             config,
-            client: Client::new(),
+            client,
             api_base_url,
         }
     }
@@ -246,11 +293,11 @@ impl D1Driver {
 
 #[async_trait]
 impl DatabaseDriver for D1Driver {
-    fn as_any(&self) -> &dyn std::any::Any {
-        self
+    fn capabilities(&self) -> DriverCapabilities {
+        CAPABILITIES
     }
 
-    async fn test_connection(&self) -> Result<TestConnectionResult, String> {
+    async fn test_connection(&self) -> Result<TestConnectionResult, DriverError> {
         match self.query("SELECT 1 AS ok", vec![]).await {
             Ok(_) => Ok(TestConnectionResult {
                 success: true,
@@ -263,7 +310,7 @@ impl DatabaseDriver for D1Driver {
         }
     }
 
-    async fn list_tables(&self) -> Result<Vec<TableInfo>, String> {
+    async fn list_tables(&self) -> Result<Vec<TableInfo>, DriverError> {
         self.query(TABLES_QUERY, vec![])
             .await?
             .results
@@ -283,11 +330,11 @@ impl DatabaseDriver for D1Driver {
             .collect()
     }
 
-    fn preview_create_table(&self, request: &CreateTableRequest) -> Result<String, String> {
-        build_sqlite_create_table_sql(request)
+    fn preview_create_table(&self, request: &CreateTableRequest) -> Result<String, DriverError> {
+        Ok(build_sqlite_create_table_sql(request)?)
     }
 
-    async fn create_table(&self, request: &CreateTableRequest) -> Result<TableInfo, String> {
+    async fn create_table(&self, request: &CreateTableRequest) -> Result<TableInfo, DriverError> {
         let sql = self.preview_create_table(request)?;
         self.query(&sql, vec![]).await?;
         Ok(TableInfo {
@@ -299,14 +346,14 @@ impl DatabaseDriver for D1Driver {
 
     async fn get_table_data(
         &self,
-        _schema: &str,
+        schema: &str,
         table: &str,
         page: i64,
         limit: i64,
         filter: Option<TableFilter>,
         sort_column: Option<String>,
         sort_direction: Option<String>,
-    ) -> Result<TableDataResponse, String> {
+    ) -> Result<TableDataResponse, DriverError> {
         let structure = self.structure(table).await?;
         let compiled_filter = structured_expression(filter.as_ref())
             .map(|expression| compile_filter(expression, &structure.columns, FilterDialect::Sqlite))
@@ -360,7 +407,22 @@ impl DatabaseDriver for D1Driver {
             .results;
 
         Ok(TableDataResponse {
-            data,
+            data: data.into_iter().map(cell::untyped_row).collect(),
+            columns: structure
+                .columns
+                .into_iter()
+                .map(|column| ResultColumn {
+                    nullable: Some(column.nullable),
+                    source: Some(ColumnSource {
+                        schema: schema.to_string(),
+                        table: table.to_string(),
+                        column: column.name.clone(),
+                    }),
+                    name: column.name,
+                    data_type: column.data_type,
+                    filter_kind: column.filter_kind,
+                })
+                .collect(),
             total,
             page,
             limit,
@@ -371,15 +433,23 @@ impl DatabaseDriver for D1Driver {
         &self,
         _schema: &str,
         table: &str,
-    ) -> Result<TableStructure, String> {
-        self.structure(table).await
+    ) -> Result<TableStructure, DriverError> {
+        Ok(self.structure(table).await?)
     }
 
-    async fn execute_query(&self, query: &str) -> Result<QueryResult, String> {
+    async fn execute_query(&self, query: &str) -> Result<QueryResult, DriverError> {
         let start = Instant::now();
-        match self.query(query, vec![]).await {
+        // D1 has no server-side statement timeout; dropping the request is the
+        // only cancellation available.
+        let limits = &self.config.limits;
+        match limits
+            .run(false, self.query(query, vec![]))
+            .await
+            .and_then(|result| {
+                result.map_err(|error| DriverError::from_message(error).locate(query))
+            }) {
             Ok(statement) => {
-                let mut result = query_result_from_statement(statement);
+                let mut result = query_result_from_statement(statement, limits);
                 result.time_taken_ms = Some(start.elapsed().as_millis());
                 Ok(result)
             }
@@ -387,14 +457,14 @@ impl DatabaseDriver for D1Driver {
         }
     }
 
-    async fn execute_query_read_only(&self, _query: &str) -> Result<QueryResult, String> {
+    async fn execute_query_read_only(&self, _query: &str) -> Result<QueryResult, DriverError> {
         Ok(QueryResult::from_error(
             "Cloudflare D1 query execution is not available through MCP yet".to_string(),
             Instant::now(),
         ))
     }
 
-    async fn get_schema_overview(&self) -> Result<SchemaOverview, String> {
+    async fn get_schema_overview(&self) -> Result<SchemaOverview, DriverError> {
         let tables = self.list_tables().await?;
         let mut overview = Vec::with_capacity(tables.len());
         for table in tables {
@@ -538,22 +608,29 @@ fn parse_query_response(response: D1QueryResponse) -> Result<QueryResult, String
             .error
             .unwrap_or_else(|| "Cloudflare D1 query failed".to_string()));
     }
-    Ok(query_result_from_statement(statement))
+    Ok(query_result_from_statement(
+        statement,
+        &QueryLimits::default(),
+    ))
 }
 
-fn query_result_from_statement(statement: D1StatementResult) -> QueryResult {
-    let truncated = statement.results.len() > MAX_QUERY_RESULT_ROWS;
-    let data = statement
-        .results
-        .into_iter()
-        .take(MAX_QUERY_RESULT_ROWS)
-        .collect::<Vec<_>>();
+fn query_result_from_statement(statement: D1StatementResult, limits: &QueryLimits) -> QueryResult {
+    // D1 reports no result types; columns are named after the first row.
+    let columns = cell::untyped_columns(&statement.results, FilterDialect::Sqlite);
+    let (rows, truncated) = limits.bound_rows(cell::untyped_rows(statement.results, &columns));
     QueryResult {
-        row_count: data.len() as i64,
-        data,
+        row_count: rows.len() as i64,
+        data: rows
+            .iter()
+            .map(|row| cell::row_object(&columns, row))
+            .collect(),
+        columns,
+        rows,
+        edit_target: None,
         truncated,
         rows_affected: Some(statement.meta.changes),
         error: None,
+        error_details: None,
         time_taken_ms: None,
     }
 }
@@ -610,7 +687,7 @@ fn escape_sql_string(value: &str) -> String {
 mod tests {
     use super::{
         d1_database_url, list_databases_at_base, parse_query_response, D1Config, D1Driver,
-        D1QueryResponse,
+        D1QueryResponse, QueryLimits,
     };
     use crate::database::DatabaseDriver;
     use axum::extract::State;
@@ -708,6 +785,7 @@ mod tests {
                 account_id: " account-id ".to_string(),
                 database_id: "database-id".to_string(),
                 api_token: " secret-token\n".to_string(),
+                limits: QueryLimits::default(),
             },
             format!("http://{address}/client/v4"),
         );
@@ -741,6 +819,7 @@ mod tests {
                 account_id: "account-id".to_string(),
                 database_id: "database-id".to_string(),
                 api_token: "secret-token".to_string(),
+                limits: QueryLimits::default(),
             },
             format!("http://{address}/client/v4"),
         );
@@ -859,6 +938,7 @@ mod tests {
                 account_id: "account-id".to_string(),
                 database_id: "database-id".to_string(),
                 api_token: "secret-token".to_string(),
+                limits: QueryLimits::default(),
             },
             format!("http://{address}/client/v4"),
         );
@@ -885,6 +965,7 @@ mod tests {
                 account_id: "local-account".to_string(),
                 database_id: "local-database".to_string(),
                 api_token: "local-token".to_string(),
+                limits: QueryLimits::default(),
             },
             api_base_url,
         );
@@ -1,15 +1,8 @@
-use super::clickhouse::ClickhouseDriver;
-use super::d1::D1Driver;
-use super::duckdb::DuckDbDriver;
-use super::mysql::MysqlDriver;
-use super::postgres::PostgresDriver;
-use super::redis::RedisDriver;
-use super::sqlite::SqliteDriver;
-use super::{
-    ClickhouseConfig, ClickhouseProtocol, D1Config, DatabaseDriver, DatabaseType, DuckDbConfig,
-    MysqlConfig, MysqlFlavor, PostgresConfig, RedisConfig, SqliteConfig,
-};
+use super::registry::{find_driver, DriverDescriptor, DriverOptions, SshSupport};
+use super::{CredentialCommand, DatabaseDriver, QueryLimits, SessionOptions};
 use crate::ssh_tunnel::{SshAuth, SshTunnel};
+use serde_json::{Map, Value};
+use std::collections::BTreeMap;
 
 #[derive(Clone, Debug)]
 pub struct DriverConfig {
@@ -21,6 +14,9 @@ pub struct DriverConfig {
     pub password: Option<String>,
     pub ssl: Option<bool>,
     pub file_path: Option<String>,
+    /// Engine-specific settings declared by the engine's
+    /// [`DriverDescriptor::fields`], e.g. the D1 account id.
+    pub options: Map<String, Value>,
     pub ssh_enabled: bool,
     pub ssh_host: Option<String>,
     pub ssh_port: Option<i64>,
@@ -28,36 +24,65 @@ pub struct DriverConfig {
     pub ssh_password: Option<String>,
     pub ssh_key_path: Option<String>,
     pub ssh_use_key: bool,
+    pub on_connect_sql: Option<String>,
+    pub session_settings: BTreeMap<String, String>,
+    pub limits: QueryLimits,
+    /// Supplies `password` (the API token for D1) at connect time; resolved
+    /// by the pool manager before the driver is built.
+    pub credential_command: Option<CredentialCommand>,
 }
 
 impl DriverConfig {
-    fn engine(&self) -> Result<DatabaseType, String> {
-        DatabaseType::try_from(self.db_type.as_str())
+    fn session(&self, descriptor: &DriverDescriptor) -> Result<SessionOptions, String> {
+        let session =
+            SessionOptions::new(self.on_connect_sql.clone(), self.session_settings.clone());
+        if !descriptor.sessions && !session.is_empty() {
+            return Err(format!(
+                "Session settings are not supported for {}",
+                descriptor.id
+            ));
+        }
+        Ok(session)
+    }
+
+    /// The connection's columns and engine options as one map, with `host`
+    /// and `port` pointing where the driver should connect.
+    fn values(&self, host: Option<String>, port: Option<i64>) -> Map<String, Value> {
+        let mut values = Map::new();
+        let mut put = |name: &str, value: Option<Value>| {
+            if let Some(value) = value {
+                values.insert(name.to_string(), value);
+            }
+        };
+        put("host", host.map(Value::from));
+        put("port", port.map(Value::from));
+        put("database", self.database.clone().map(Value::from));
+        put("username", self.username.clone().map(Value::from));
+        put("password", self.password.clone().map(Value::from));
+        put("ssl", self.ssl.map(Value::from));
+        put("file_path", self.file_path.clone().map(Value::from));
+        values.extend(self.options.clone());
+        values
     }
 }
 
 pub fn create_driver(config: &DriverConfig) -> Result<Box<dyn DatabaseDriver>, String> {
-    let engine = config.engine()?;
-    let host = config.host.clone().unwrap_or_else(|| {
-        if engine == DatabaseType::Clickhouse {
-            "localhost".to_string()
-        } else {
-            String::new()
-        }
-    });
-    let port = config.port.unwrap_or_else(|| engine.default_port());
-    build_driver(engine, config, host, port)
+    let descriptor = find_driver(&config.db_type)?;
+    let port = config.port.or(descriptor.default_port);
+    build_driver(&descriptor, config, config.host.clone(), port)
 }
 
 pub async fn create_driver_with_ssh(
     config: &DriverConfig,
 ) -> Result<(Box<dyn DatabaseDriver>, Option<SshTunnel>), String> {
-    let engine = config.engine()?;
-    if engine == DatabaseType::D1 && config.ssh_enabled {
-        return Err("SSH tunnels are not supported for Cloudflare D1".to_string());
+    let descriptor = find_driver(&config.db_type)?;
+    if descriptor.ssh == SshSupport::Unsupported && config.ssh_enabled {
+        return Err(format!(
+            "SSH tunnels are not supported for {}",
+            descriptor.label
+        ));
     }
-    let ssh_enabled =
-        config.ssh_enabled && !matches!(engine, DatabaseType::Sqlite | DatabaseType::DuckDb);
+    let ssh_enabled = config.ssh_enabled && descriptor.ssh == SshSupport::Supported;
     let (host, port, tunnel) = if ssh_enabled {
         let ssh_host = config.ssh_host.as_deref().ok_or("SSH host is required")?;
         let ssh_port = config.ssh_port.unwrap_or(22) as u16;
@@ -68,108 +93,73 @@ pub async fn create_driver_with_ssh(
             config.ssh_key_path.as_deref(),
         );
         let remote_host = config.host.as_deref().ok_or("Remote host is required")?;
-        let remote_port = config.port.unwrap_or_else(|| engine.default_port()) as u16;
+        let remote_port = config
+            .port
+            .or(descriptor.default_port)
+            .ok_or("Remote port is required")? as u16;
         let tunnel = tokio::time::timeout(
-            std::time::Duration::from_secs(20),
+            config.limits.connect_timeout,
             SshTunnel::new(ssh_host, ssh_port, ssh_user, auth, remote_host, remote_port),
         )
         .await
-        .map_err(|_| "SSH tunnel connection timed out after 20 seconds".to_string())?
+        .map_err(|_| format!("SSH tunnel: {}", config.limits.connect_timeout_message()))?
         .map_err(|error| format!("SSH tunnel failed: {error}"))?;
 
         (
-            "127.0.0.1".to_string(),
-            tunnel.local_port as i64,
+            Some("127.0.0.1".to_string()),
+            Some(tunnel.local_port as i64),
             Some(tunnel),
         )
     } else {
         (
-            config.host.clone().unwrap_or_default(),
-            config.port.unwrap_or_else(|| engine.default_port()),
+            config.host.clone(),
+            config.port.or(descriptor.default_port),
             None,
         )
     };
 
-    Ok((build_driver(engine, config, host, port)?, tunnel))
+    Ok((build_driver(&descriptor, config, host, port)?, tunnel))
 }
 
-fn build_driver(
-    engine: DatabaseType,
+/// Build a driver that reaches the server through an already open tunnel
+/// (or directly when there is none), for extra sessions on a connection.
+pub fn create_driver_through(
     config: &DriverConfig,
-    host: String,
-    port: i64,
+    tunnel: Option<&SshTunnel>,
 ) -> Result<Box<dyn DatabaseDriver>, String> {
-    match engine {
-        DatabaseType::Postgres => Ok(Box::new(PostgresDriver::new(PostgresConfig {
-            host,
-            port,
-            database: config.database.clone().unwrap_or_default(),
-            username: config.username.clone().unwrap_or_default(),
-            password: config.password.clone().unwrap_or_default(),
-            ssl: config.ssl.unwrap_or(false),
-        }))),
-        DatabaseType::Mysql | DatabaseType::Mariadb => {
-            Ok(Box::new(MysqlDriver::new(MysqlConfig {
-                flavor: MysqlFlavor::try_from(engine)?,
-                host,
-                port,
-                database: config.database.clone().unwrap_or_default(),
-                username: config.username.clone().unwrap_or_default(),
-                password: config.password.clone().unwrap_or_default(),
-                ssl: config.ssl.unwrap_or(false),
-            })))
-        }
-        DatabaseType::Sqlite => {
-            let file_path = config
-                .file_path
-                .clone()
-                .ok_or("File path is required for SQLite connections")?;
-            Ok(Box::new(SqliteDriver::new(SqliteConfig { file_path })))
-        }
-        DatabaseType::DuckDb => {
-            let file_path = config
-                .file_path
-                .clone()
-                .ok_or("File path is required for DuckDB connections")?;
-            Ok(Box::new(DuckDbDriver::new(DuckDbConfig { file_path })))
-        }
-        DatabaseType::Redis => Ok(Box::new(RedisDriver::new(RedisConfig {
-            host,
-            port,
-            username: config
-                .username
-                .clone()
-                .filter(|username| !username.is_empty()),
-            password: config.password.clone(),
-            db: config.database.clone().and_then(|value| value.parse().ok()),
-            tls: config.ssl.unwrap_or(false),
-        }))),
-        DatabaseType::Clickhouse => Ok(Box::new(ClickhouseDriver::new(ClickhouseConfig {
-            host,
-            port,
-            database: config
-                .database
-                .clone()
-                .unwrap_or_else(|| "default".to_string()),
-            username: config
-                .username
-                .clone()
-                .unwrap_or_else(|| "default".to_string()),
-            password: config.password.clone().unwrap_or_default(),
-            protocol: ClickhouseProtocol::Http,
-            ssl: config.ssl.unwrap_or(false),
-        }))),
-        DatabaseType::D1 => Ok(Box::new(D1Driver::new(D1Config {
-            account_id: config.username.clone().unwrap_or_default(),
-            database_id: config.database.clone().unwrap_or_default(),
-            api_token: config.password.clone().unwrap_or_default(),
-        }))),
+    match tunnel {
+        Some(tunnel) => build_driver(
+            &find_driver(&config.db_type)?,
+            config,
+            Some("127.0.0.1".to_string()),
+            Some(tunnel.local_port as i64),
+        ),
+        None => create_driver(config),
     }
 }
 
+fn build_driver(
+    descriptor: &DriverDescriptor,
+    config: &DriverConfig,
+    host: Option<String>,
+    port: Option<i64>,
+) -> Result<Box<dyn DatabaseDriver>, String> {
+    descriptor.validate_options(&config.options)?;
+    (descriptor.build)(DriverOptions {
+        values: config.values(host, port),
+        session: config.session(descriptor)?,
+        limits: config.limits,
+    })
+}
+
 #[cfg(test)]
 mod tests {
-    use super::{create_driver, create_driver_with_ssh, DriverConfig};
+    use super::{create_driver, create_driver_with_ssh, DriverConfig, QueryLimits};
+    use crate::database::registry::{
+        register_driver, ConfigField, ConfigFieldKind, DriverDescriptor, DriverOptions, SshSupport,
+    };
+    use crate::database::sqlite::{SqliteDriver, CAPABILITIES};
+    use crate::database::{DatabaseDriver, SqliteConfig};
 
     fn config(db_type: &str) -> DriverConfig {
         DriverConfig {
@@ -181,6 +171,7 @@ mod tests {
             password: None,
             ssl: None,
             file_path: Some("database.db".to_string()),
+            options: Default::default(),
             ssh_enabled: false,
             ssh_host: None,
             ssh_port: None,
@@ -188,6 +179,10 @@ mod tests {
             ssh_password: None,
             ssh_key_path: None,
             ssh_use_key: false,
+            on_connect_sql: None,
+            session_settings: Default::default(),
+            limits: QueryLimits::default(),
+            credential_command: None,
         }
     }
 
@@ -215,6 +210,29 @@ mod tests {
         );
     }
 
+    #[test]
+    fn rejects_session_settings_for_engines_without_sessions() {
+        let mut redis = config("redis");
+        redis.on_connect_sql = Some("SELECT 1".to_string());
+        assert_eq!(
+            create_driver(&redis).err().unwrap(),
+            "Session settings are not supported for redis"
+        );
+
+        let mut clickhouse = config("clickhouse");
+        clickhouse
+            .session_settings
+            .insert("readonly".to_string(), "0".to_string());
+        assert!(create_driver(&clickhouse).is_err());
+
+        let mut postgres = config("postgres");
+        postgres
+            .session_settings
+            .insert("search_path".to_string(), "tenant, public".to_string());
+        postgres.on_connect_sql = Some("SET ROLE analyst".to_string());
+        assert!(create_driver(&postgres).is_ok());
+    }
+
     #[tokio::test]
     async fn d1_rejects_ssh_before_opening_a_tunnel() {
         let mut config = config("d1");
@@ -225,4 +243,48 @@ mod tests {
             "SSH tunnels are not supported for Cloudflare D1"
         );
     }
+
+    const SCRATCH_FIELDS: [ConfigField; 1] =
+        [ConfigField::new("directory", "Directory", ConfigFieldKind::Path).required()];
+
+    fn build_scratch(options: DriverOptions) -> Result<Box<dyn DatabaseDriver>, String> {
+        let directory = options.required_text("directory", "Directory is required")?;
+        Ok(Box::new(SqliteDriver::new(SqliteConfig {
+            file_path: format!("{directory}/scratch.db"),
+            session: options.session,
+            limits: options.limits,
+        })))
+    }
+
+    #[test]
+    fn builds_engines_registered_at_runtime() {
+        register_driver(DriverDescriptor {
+            id: "scratch",
+            label: "Scratch",
+            aliases: &[],
+            default_port: None,
+            fields: &SCRATCH_FIELDS,
+            ssh: SshSupport::NotApplicable,
+            sessions: false,
+            capabilities: CAPABILITIES,
+            schema_qualified: true,
+            replays_reads: true,
+            explain: |query| Ok(format!("EXPLAIN {query}")),
+            build: build_scratch,
+        })
+        .unwrap();
+
+        let mut scratch = config("scratch");
+        assert_eq!(
+            create_driver(&scratch).err().unwrap(),
+            "Directory is required"
+        );
+        scratch
+            .options
+            .insert("directory".to_string(), "/tmp".into());
+        assert!(create_driver(&scratch).is_ok());
+
+        scratch.options.insert("directory".to_string(), 7.into());
+        assert!(create_driver(&scratch).is_err());
+    }
 }
@@ -10,24 +10,62 @@ use tokio::process::{Child, ChildStderr, ChildStdin, ChildStdout, Command};
 use tokio::sync::Mutex;
 use tokio::time::{timeout, Duration};
 
+use super::capabilities::{DriverCapabilities, ReadOnlyEnforcement};
+use super::cell::{self, MAX_SAFE_JSON_INTEGER};
 use super::filter::{
     build_where_clause, classify_column_type, compile_filter, structured_expression,
     CompiledFilter, FilterDialect, FilterValue,
 };
-use super::{
-    query_returns_rows_with_keywords, DatabaseDriver, DuckDbConfig, MAX_QUERY_RESULT_ROWS,
-};
+use super::registry::{DriverDescriptor, DriverOptions, SshSupport, FILE_PATH};
+use super::{query_returns_rows_with_keywords, DatabaseDriver, DriverError, DuckDbConfig};
 use crate::db::models::{
-    ColumnInfo, ForeignKeyInfo, IndexInfo, QueryResult, SchemaOverview, TableDataResponse,
-    TableFilter, TableInfo, TableStructure, TableWithStructure, TestConnectionResult,
+    ColumnInfo, ColumnSource, ForeignKeyInfo, IndexInfo, QueryResult, ResultColumn, SchemaOverview,
+    TableDataResponse, TableFilter, TableInfo, TableStructure, TableWithStructure,
+    TestConnectionResult,
 };
 use crate::duckdb_helper;
 
-const MAX_SAFE_JSON_INTEGER: i64 = 9_007_199_254_740_991;
 const MAX_CLI_OUTPUT_BYTES: usize = 64 * 1024 * 1024;
 const MAX_CLI_ERROR_BYTES: usize = 1024 * 1024;
 static FILE_LOCKS: OnceLock<StdMutex<HashMap<PathBuf, Weak<Mutex<()>>>>> = OnceLock::new();
 
+pub(crate) const CAPABILITIES: DriverCapabilities = DriverCapabilities {
+    transactions: true,
+    create_table: false,
+    alter_table: false,
+    functions: false,
+    read_only: ReadOnlyEnforcement::Engine,
+    cancellation: true,
+    streaming: false,
+    multiple_databases: false,
+    key_value_browsing: false,
+    explain: true,
+    row_editing: false,
+};
+
+pub(crate) const DESCRIPTOR: DriverDescriptor = DriverDescriptor {
+    id: "duckdb",
+    label: "DuckDB",
+    aliases: &[],
+    default_port: None,
+    fields: &[FILE_PATH],
+    ssh: SshSupport::NotApplicable,
+    sessions: false,
+    capabilities: CAPABILITIES,
+    schema_qualified: true,
+    replays_reads: true,
+    explain: |query| Ok(format!("EXPLAIN {query}")),
+    build: build_driver,
+};
+
+fn build_driver(options: DriverOptions) -> Result<Box<dyn DatabaseDriver>, String> {
+    Ok(Box::new(DuckDbDriver::new(DuckDbConfig {
+        file_path: options
+            .required_text("file_path", "File path is required for DuckDB connections")?,
+        limits: options.limits,
+    })))
+}
+
 pub struct DuckDbDriver {
     config: DuckDbConfig,
     helper_path: PathBuf,
@@ -81,6 +119,25 @@ impl DuckDbDriver {
         self.run_cli(sql, false).await
     }
 
+    /// The column names and types of a query's result, when DuckDB can
+    /// describe it.
+    async fn describe(&self, query: &str) -> Option<Vec<ResultColumn>> {
+        let query = query.trim().trim_end_matches(';');
+        let rows = self
+            .run_cli(&format!("DESCRIBE {query}"), false)
+            .await
+            .ok()?;
+        rows.iter()
+            .map(|row| {
+                Some(cell::result_column(
+                    row["column_name"].as_str()?,
+                    row["column_type"].as_str()?,
+                    FilterDialect::DuckDb,
+                ))
+            })
+            .collect()
+    }
+
     async fn ensure_helper_available(&self) -> Result<(), String> {
         if self.managed_helper && !duckdb_helper::is_helper_installed() {
             duckdb_helper::ensure_duckdb_helper_silent().await?;
@@ -468,11 +525,11 @@ fn duckdb_command(helper_path: &PathBuf) -> Command {
 
 #[async_trait]
 impl DatabaseDriver for DuckDbDriver {
-    fn as_any(&self) -> &dyn std::any::Any {
-        self
+    fn capabilities(&self) -> DriverCapabilities {
+        CAPABILITIES
     }
 
-    async fn test_connection(&self) -> Result<TestConnectionResult, String> {
+    async fn test_connection(&self) -> Result<TestConnectionResult, DriverError> {
         self.ensure_helper_available().await?;
         let _guard = self.file_lock.lock().await;
         if self.interactive_session.lock().await.is_some() {
@@ -486,7 +543,7 @@ impl DatabaseDriver for DuckDbDriver {
         })
     }
 
-    async fn list_tables(&self) -> Result<Vec<TableInfo>, String> {
+    async fn list_tables(&self) -> Result<Vec<TableInfo>, DriverError> {
         self.query_rows(
             "SELECT table_schema, table_name, \
                     CASE WHEN table_type = 'VIEW' THEN 'view' ELSE 'table' END AS object_type \
@@ -516,7 +573,7 @@ impl DatabaseDriver for DuckDbDriver {
         filter: Option<TableFilter>,
         sort_column: Option<String>,
         sort_direction: Option<String>,
-    ) -> Result<TableDataResponse, String> {
+    ) -> Result<TableDataResponse, DriverError> {
         let structure = self.get_table_structure(schema, table).await?;
         let compiled = structured_expression(filter.as_ref())
             .map(|expression| compile_filter(expression, &structure.columns, FilterDialect::DuckDb))
@@ -535,7 +592,7 @@ impl DatabaseDriver for DuckDbDriver {
                 .iter()
                 .any(|candidate| candidate.name == column)
             {
-                return Err(format!("Unknown sort column: {column}"));
+                return Err(format!("Unknown sort column: {column}").into());
             }
             vec![column]
         } else {
@@ -584,8 +641,27 @@ impl DatabaseDriver for DuckDbDriver {
                 "SELECT * FROM {table_ref}{where_clause}{order_clause} LIMIT {limit} OFFSET {offset}"
             ))
             .await?;
+        let columns: Vec<ResultColumn> = structure
+            .columns
+            .into_iter()
+            .map(|column| ResultColumn {
+                nullable: Some(column.nullable),
+                source: Some(ColumnSource {
+                    schema: schema.to_string(),
+                    table: table.to_string(),
+                    column: column.name.clone(),
+                }),
+                name: column.name,
+                data_type: column.data_type,
+                filter_kind: column.filter_kind,
+            })
+            .collect();
         Ok(TableDataResponse {
-            data,
+            data: typed_rows(data, &columns)
+                .iter()
+                .map(|row| cell::row_object(&columns, row))
+                .collect(),
+            columns,
             total,
             page,
             limit,
@@ -596,19 +672,19 @@ impl DatabaseDriver for DuckDbDriver {
         &self,
         schema: &str,
         table: &str,
-    ) -> Result<TableStructure, String> {
-        self.table_structure_inner(schema, table).await
+    ) -> Result<TableStructure, DriverError> {
+        Ok(self.table_structure_inner(schema, table).await?)
     }
 
-    async fn execute_query(&self, query: &str) -> Result<QueryResult, String> {
+    async fn execute_query(&self, query: &str) -> Result<QueryResult, DriverError> {
         execute_query(self, query, false).await
     }
 
-    async fn execute_query_read_only(&self, query: &str) -> Result<QueryResult, String> {
+    async fn execute_query_read_only(&self, query: &str) -> Result<QueryResult, DriverError> {
         execute_query(self, query, true).await
     }
 
-    async fn get_schema_overview(&self) -> Result<SchemaOverview, String> {
+    async fn get_schema_overview(&self) -> Result<SchemaOverview, DriverError> {
         let objects = self.list_tables().await?;
         let mut tables = Vec::with_capacity(objects.len());
         for object in objects {
@@ -635,23 +711,29 @@ async fn execute_query(
     driver: &DuckDbDriver,
     query: &str,
     read_only: bool,
-) -> Result<QueryResult, String> {
+) -> Result<QueryResult, DriverError> {
     let start = Instant::now();
-    match driver.run_cli(query, read_only).await {
+    // DuckDB has no statement timeout; dropping the call kills the helper
+    // process (`kill_on_drop`), which cancels the query.
+    let limits = &driver.config.limits;
+    match limits
+        .run(false, driver.run_cli(query, read_only))
+        .await
+        .and_then(|result| result.map_err(|error| DriverError::from_message(error).locate(query)))
+    {
         Ok(mut data) => {
             if !duckdb_query_returns_rows(query) {
                 data.clear();
             }
-            let truncated = data.len() > MAX_QUERY_RESULT_ROWS;
-            data.truncate(MAX_QUERY_RESULT_ROWS);
-            Ok(QueryResult {
-                row_count: data.len() as i64,
-                data,
-                truncated,
-                rows_affected: None,
-                error: None,
-                time_taken_ms: Some(start.elapsed().as_millis()),
-            })
+            // Read-only runs spawn a fresh helper per call, so only the
+            // interactive session asks for the result's column types.
+            let columns = match data.first() {
+                Some(_) if !read_only => driver.describe(query).await,
+                _ => None,
+            }
+            .unwrap_or_else(|| cell::untyped_columns(&data, FilterDialect::DuckDb));
+            let (rows, truncated) = limits.bound_rows(typed_rows(data, &columns));
+            Ok(QueryResult::from_table(columns, rows, truncated, start))
         }
         Err(error) => Ok(QueryResult::from_error(error, start)),
     }
@@ -702,6 +784,111 @@ fn normalize_cli_value(value: &mut Value) {
     }
 }
 
+fn typed_rows(rows: Vec<Value>, columns: &[ResultColumn]) -> Vec<Vec<Value>> {
+    rows.into_iter()
+        .map(|mut row| {
+            columns
+                .iter()
+                .map(|column| {
+                    let value = row.get_mut(&column.name).map_or(Value::Null, Value::take);
+                    typed_cell(value, &column.data_type)
+                })
+                .collect()
+        })
+        .collect()
+}
+
+/// Tag a value from the CLI's JSON output by its DuckDB type. Unsafe integers
+/// already arrive as digits (see `normalize_cli_value`).
+fn typed_cell(value: Value, data_type: &str) -> Value {
+    let data_type = data_type.trim().to_ascii_uppercase();
+    if value.is_null() {
+        return value;
+    }
+    if let Some(element) = data_type
+        .strip_suffix(']')
+        .and_then(|rest| rest.rsplit_once('['))
+        .map(|(element, _)| element)
+    {
+        return match value {
+            Value::Array(items) => cell::array(
+                items
+                    .into_iter()
+                    .map(|item| typed_cell(item, element))
+                    .collect(),
+            ),
+            value => cell::untyped(value),
+        };
+    }
+    let base = data_type.split('(').next().unwrap_or_default().trim();
+    match (base, value) {
+        (
+            "TINYINT" | "SMALLINT" | "INTEGER" | "BIGINT" | "HUGEINT" | "UTINYINT" | "USMALLINT"
+            | "UINTEGER" | "UBIGINT" | "UHUGEINT",
+            Value::String(digits),
+        ) => cell::integer_text(&digits),
+        ("DECIMAL", Value::Number(number)) => cell::decimal(decimal_text(&number, &data_type)),
+        ("DECIMAL", Value::String(text)) => cell::decimal(text),
+        ("DATE", Value::String(text)) => cell::date(text),
+        ("TIME", Value::String(text)) => cell::time(text),
+        ("TIMESTAMP WITH TIME ZONE" | "TIMESTAMPTZ", Value::String(text)) => {
+            cell::timestamptz(text)
+        }
+        (
+            "TIMESTAMP" | "TIMESTAMP_S" | "TIMESTAMP_MS" | "TIMESTAMP_NS" | "DATETIME",
+            Value::String(text),
+        ) => cell::timestamp(text),
+        ("INTERVAL", Value::String(text)) => cell::interval(text),
+        ("UUID", Value::String(text)) => cell::uuid(text),
+        ("BLOB", Value::String(text)) => cell::binary(&blob_bytes(&text)),
+        ("JSON", Value::String(text)) => {
+            serde_json::from_str(&text).map_or(Value::String(text), cell::json)
+        }
+        ("STRUCT" | "MAP" | "UNION", value) => cell::json(value),
+        (_, value) => cell::untyped(value),
+    }
+}
+
+/// A DECIMAL printed with its declared scale, e.g. `12.50` for
+/// `DECIMAL(10,2)`.
+fn decimal_text(number: &serde_json::Number, data_type: &str) -> String {
+    let scale = data_type
+        .split_once(',')
+        .and_then(|(_, scale)| scale.trim_end_matches(')').trim().parse::<usize>().ok());
+    match (scale, number.as_f64()) {
+        (Some(scale), Some(value)) if !number.is_i64() || scale > 0 => {
+            format!("{value:.scale$}")
+        }
+        _ => number.to_string(),
+    }
+}
+
+/// Bytes of a BLOB as the CLI prints it: printable ASCII as is, everything
+/// else as `\xHH`.
+fn blob_bytes(text: &str) -> Vec<u8> {
+    let bytes = text.as_bytes();
+    let mut blob = Vec::with_capacity(bytes.len());
+    let mut index = 0;
+    while index < bytes.len() {
+        let escaped = bytes
+            .get(index..index + 4)
+            .filter(|escape| escape.starts_with(b"\\x"))
+            .and_then(|escape| std::str::from_utf8(&escape[2..]).ok())
+            .and_then(|digits| u8::from_str_radix(digits, 16).ok());
+        match escaped {
+            Some(byte) => {
+                blob.push(byte);
+                index += 4;
+            }
+            None => {
+                blob.push(bytes[index]);
+                index += 1;
+            }
+        }
+    }
+    blob
+}
+
 fn render_filter(where_clause: &str, filter: &CompiledFilter) -> Result<String, String> {
     let mut parts = where_clause.split('?');
     let mut rendered = parts.next().unwrap_or_default().to_string();
@@ -785,8 +972,10 @@ fn string_array(value: &Value) -> Vec<String> {
 
 #[cfg(test)]
 mod tests {
-    use super::{normalize_cli_value, render_filter, CompiledFilter, DuckDbDriver, FilterValue};
-    use crate::database::{DatabaseDriver, DuckDbConfig};
+    use super::{
+        normalize_cli_value, render_filter, typed_cell, CompiledFilter, DuckDbDriver, FilterValue,
+    };
+    use crate::database::{DatabaseDriver, DuckDbConfig, QueryLimits};
     use serde_json::json;
     use std::path::PathBuf;
     use tempfile::tempdir;
@@ -860,6 +1049,39 @@ done
         assert_eq!(value, json!({"value": "\\xCA\\xFE"}));
     }
 
+    #[test]
+    fn tags_cli_values_by_described_type() {
+        assert_eq!(
+            typed_cell(json!(12.5), "DECIMAL(10,2)"),
+            json!({"$type": "decimal", "value": "12.50"})
+        );
+        assert_eq!(
+            typed_cell(json!("9007199254740993"), "BIGINT"),
+            json!({"$type": "integer", "value": "9007199254740993"})
+        );
+        assert_eq!(
+            typed_cell(json!("2024-01-02 03:04:05"), "TIMESTAMP"),
+            json!({"$type": "timestamp", "value": "2024-01-02 03:04:05"})
+        );
+        assert_eq!(
+            typed_cell(json!("a\\xCA\\xFE"), "BLOB"),
+            json!({"$type": "binary", "value": "Ycr+", "length": 3})
+        );
+        assert_eq!(
+            typed_cell(json!([["2024-01-01"], null]), "DATE[][]"),
+            json!({"$type": "array", "value": [
+                {"$type": "array", "value": [{"$type": "date", "value": "2024-01-01"}]},
+                null
+            ]})
+        );
+        assert_eq!(
+            typed_cell(json!({"a": 1}), "STRUCT(a INTEGER)"),
+            json!({"$type": "json", "value": {"a": 1}})
+        );
+        assert_eq!(typed_cell(json!("text"), "VARCHAR"), json!("text"));
+        assert_eq!(typed_cell(json!(1), ""), json!(1));
+    }
+
     #[cfg(unix)]
     #[tokio::test]
     async fn restarts_the_cli_after_a_transport_failure() {
@@ -867,6 +1089,7 @@ done
         let driver = DuckDbDriver::with_helper_path(
             DuckDbConfig {
                 file_path: directory.path().join("data.duckdb").display().to_string(),
+                limits: QueryLimits::default(),
             },
             helper_path,
         );
@@ -886,6 +1109,7 @@ done
         let driver = DuckDbDriver::with_helper_path(
             DuckDbConfig {
                 file_path: directory.path().join("data.duckdb").display().to_string(),
+                limits: QueryLimits::default(),
             },
             helper_path,
         );
@@ -911,6 +1135,7 @@ done
         let driver = DuckDbDriver::with_helper_path(
             DuckDbConfig {
                 file_path: directory.path().join("data.duckdb").display().to_string(),
+                limits: QueryLimits::default(),
             },
             helper_path.clone(),
         );
@@ -932,6 +1157,7 @@ done
         let driver = DuckDbDriver::with_helper_path(
             DuckDbConfig {
                 file_path: directory.path().join("data.duckdb").display().to_string(),
+                limits: QueryLimits::default(),
             },
             helper_path.clone(),
         );
@@ -0,0 +1,549 @@
+//! Typed driver errors.
+//!
+//! A [`DriverError`] says what kind of failure happened, carries the engine's
+//! own code (SQLSTATE or vendor number) and, when the engine reports one,
+//! where in the statement it went wrong. Reconnect and credential-refresh
+//! decisions look at the category instead of matching message text, and the
+//! frontend uses the position to highlight the failing token.
+
+use serde::{Deserialize, Serialize};
+use thiserror::Error;
+
+use super::credentials::is_authentication_error;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum DriverErrorCategory {
+    /// The server could not be reached or the connection dropped.
+    Connection,
+    /// The server rejected the credentials.
+    Auth,
+    /// The statement could not be parsed.
+    Syntax,
+    /// The user may not do this, including writes refused in read-only mode.
+    Permission,
+    /// A unique, foreign key, not-null or check constraint was violated.
+    Constraint,
+    Timeout,
+    Cancelled,
+    Other,
+}
+
+impl DriverErrorCategory {
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Self::Connection => "connection",
+            Self::Auth => "auth",
+            Self::Syntax => "syntax",
+            Self::Permission => "permission",
+            Self::Constraint => "constraint",
+            Self::Timeout => "timeout",
+            Self::Cancelled => "cancelled",
+            Self::Other => "other",
+        }
+    }
+}
+
+/// Where in the statement an error points. `offset` counts characters from
+/// the start of the statement; `line` and `column` start at 1 (0 until
+/// [`DriverError::locate`] has seen the statement).
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ErrorPosition {
+    pub offset: usize,
+    pub line: usize,
+    pub column: usize,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, Error)]
+#[error("{message}")]
+pub struct DriverError {
+    pub category: DriverErrorCategory,
+    /// SQLSTATE (PostgreSQL), error number (MySQL, SQL Server, ClickHouse) or
+    /// result code (SQLite), as reported by the engine.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub code: Option<String>,
+    pub message: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub position: Option<ErrorPosition>,
+}
+
+impl DriverError {
+    pub fn new(category: DriverErrorCategory, message: impl Into<String>) -> Self {
+        Self {
+            category,
+            code: None,
+            message: message.into(),
+            position: None,
+        }
+    }
+
+    pub fn with_code(mut self, code: impl Into<String>) -> Self {
+        self.code = Some(code.into());
+        self
+    }
+
+    /// Put `context` in front of the message, keeping everything else.
+    pub fn context(mut self, context: &str) -> Self {
+        self.message = format!("{}: {}", context, self.message);
+        self
+    }
+
+    /// Whether running the operation again on a fresh connection could
+    /// succeed. Errors the server reported with its own code came from a live
+    /// connection, so only dropped connections and unclassified failures
+    /// qualify.
+    pub fn reconnect_may_help(&self) -> bool {
+        match self.category {
+            DriverErrorCategory::Connection => true,
+            DriverErrorCategory::Other => self.code.is_none(),
+            _ => false,
+        }
+    }
+
+    /// Classify an error from its message alone, for engines and transports
+    /// that report nothing more structured.
+    pub fn from_message(message: impl Into<String>) -> Self {
+        let message = message.into();
+        let category = classify_message(&message);
+        Self::new(category, message)
+    }
+
+    /// PostgreSQL errors, by SQLSTATE class.
+    pub fn from_sqlstate(code: &str, message: impl Into<String>) -> Self {
+        let message = message.into();
+        let category = match code {
+            "42501" | "25006" => DriverErrorCategory::Permission,
+            "42601" => DriverErrorCategory::Syntax,
+            "57014" if message.contains("statement timeout") => DriverErrorCategory::Timeout,
+            "57014" => DriverErrorCategory::Cancelled,
+            "53300" | "57P01" | "57P02" | "57P03" => DriverErrorCategory::Connection,
+            _ if code.starts_with("08") => DriverErrorCategory::Connection,
+            _ if code.starts_with("28") => DriverErrorCategory::Auth,
+            _ if code.starts_with("23") => DriverErrorCategory::Constraint,
+            _ => DriverErrorCategory::Other,
+        };
+        Self::new(category, message).with_code(code)
+    }
+
+    /// MySQL and MariaDB errors, by server error number.
+    pub fn from_mysql(number: u16, message: impl Into<String>) -> Self {
+        let category = match number {
+            1045 | 1698 => DriverErrorCategory::Auth,
+            1044 | 1142 | 1143 | 1227 | 1290 | 1370 | 1792 => DriverErrorCategory::Permission,
+            1064 | 1149 => DriverErrorCategory::Syntax,
+            1048 | 1062 | 1216 | 1217 | 1451 | 1452 | 1557 | 1586 | 3819 | 4025 => {
+                DriverErrorCategory::Constraint
+            }
+            1969 | 3024 => DriverErrorCategory::Timeout,
+            1317 => DriverErrorCategory::Cancelled,
+            1040 | 1053 | 1152 | 1153 | 1158..=1161 | 2002 | 2003 | 2006 | 2013 => {
+                DriverErrorCategory::Connection
+            }
+            _ => DriverErrorCategory::Other,
+        };
+        Self::new(category, message).with_code(number.to_string())
+    }
+
+    /// SQLite errors, by (extended) result code.
+    pub fn from_sqlite(code: &str, message: impl Into<String>) -> Self {
+        let message = message.into();
+        let primary = code.parse::<i32>().map(|code| code & 0xff).unwrap_or(0);
+        let category = match primary {
+            19 => DriverErrorCategory::Constraint,
+            8 | 23 => DriverErrorCategory::Permission,
+            9 => DriverErrorCategory::Cancelled,
+            1 if message.contains("syntax error") || message == "incomplete input" => {
+                DriverErrorCategory::Syntax
+            }
+            _ => DriverErrorCategory::Other,
+        };
+        Self::new(category, message).with_code(code)
+    }
+
+    /// ClickHouse errors, which carry their number as `Code: 62. DB::Exception: ...`.
+    pub fn from_clickhouse(message: impl Into<String>) -> Self {
+        let message = message.into();
+        let Some(number) = message
+            .split_once("Code: ")
+            .and_then(|(_, rest)| rest.split(|c: char| !c.is_ascii_digit()).next())
+            .and_then(|digits| digits.parse::<u32>().ok())
+        else {
+            return Self::from_message(message);
+        };
+        let category = match number {
+            62 => DriverErrorCategory::Syntax,
+            192 | 516 => DriverErrorCategory::Auth,
+            164 | 497 => DriverErrorCategory::Permission,
+            159 => DriverErrorCategory::Timeout,
+            394 => DriverErrorCategory::Cancelled,
+            209 | 210 => DriverErrorCategory::Connection,
+            _ => DriverErrorCategory::Other,
+        };
+        Self::new(category, message).with_code(number.to_string())
+    }
+
+    /// SQL Server errors, by message number. The server reports the line of
+    /// the batch the error is on, which [`locate`](Self::locate) narrows down.
+    pub fn from_sqlserver(number: u32, message: impl Into<String>, line: u32) -> Self {
+        let category = match number {
+            18456 | 18470 | 18486 | 18487 | 18488 | 4060 => DriverErrorCategory::Auth,
+            229 | 230 | 262 | 297 | 300 | 916 | 3906 => DriverErrorCategory::Permission,
+            102 | 103 | 105 | 156 | 170 | 319 => DriverErrorCategory::Syntax,
+            515 | 547 | 2601 | 2627 => DriverErrorCategory::Constraint,
+            1222 => DriverErrorCategory::Timeout,
+            233 | 10053 | 10054 | 10060 | 10061 | 40613 => DriverErrorCategory::Connection,
+            _ => DriverErrorCategory::Other,
+        };
+        let error = Self::new(category, message).with_code(number.to_string());
+        match line {
+            0 => error,
+            line => Self {
+                position: Some(ErrorPosition {
+                    offset: 0,
+                    line: line as usize,
+                    column: 0,
+                }),
+                ..error
+            },
+        }
+    }
+
+    /// Point the error at the character `offset` of `query`.
+    pub fn at_offset(mut self, query: &str, offset: usize) -> Self {
+        let offset = offset.min(query.chars().count());
+        let before: Vec<char> = query.chars().take(offset).collect();
+        let line = before.iter().filter(|c| **c == '\n').count() + 1;
+        let column = before.iter().rev().take_while(|c| **c != '\n').count() + 1;
+        self.position = Some(ErrorPosition {
+            offset,
+            line,
+            column,
+        });
+        self
+    }
+
+    /// Fill in the position from what the message says about `query`, if the
+    /// engine didn't report one directly: ClickHouse's `at position N`, and
+    /// the `near "..."` / `near '...' at line N` snippets of PostgreSQL-style
+    /// parsers, SQLite, MySQL and SQL Server.
+    pub fn locate(self, query: &str) -> Self {
+        // SQL Server reports only the line; the snippet finds the column.
+        let reported_line = match self.position {
+            // PostgreSQL reports only the offset; see `From<sqlx::Error>`.
+            Some(position) if position.line == 0 => {
+                return self.at_offset(query, position.offset);
+            }
+            Some(position) if position.column != 0 => return self,
+            Some(position) => Some(position.line),
+            None => None,
+        };
+
+        if let Some(position) = number_after(&self.message, "at position ") {
+            return self.at_offset(query, position.saturating_sub(1));
+        }
+
+        let line_start = reported_line
+            .or_else(|| number_after(&self.message, "at line "))
+            .map(|line| {
+                query
+                    .split_inclusive('\n')
+                    .take(line.saturating_sub(1))
+                    .map(|l| l.chars().count())
+                    .sum::<usize>()
+            });
+        let haystack: String = query.chars().skip(line_start.unwrap_or(0)).collect();
+        let found = near_snippet(&self.message).and_then(|snippet| haystack.find(snippet));
+        match (found, line_start) {
+            (Some(byte_offset), _) => {
+                let offset = line_start.unwrap_or(0) + haystack[..byte_offset].chars().count();
+                self.at_offset(query, offset)
+            }
+            (None, Some(line_start)) if reported_line.is_some() => {
+                self.at_offset(query, line_start)
+            }
+            _ => self,
+        }
+    }
+}
+
+fn classify_message(message: &str) -> DriverErrorCategory {
+    let lower = message.to_ascii_lowercase();
+    let has = |patterns: &[&str]| patterns.iter().any(|pattern| lower.contains(pattern));
+
+    if is_authentication_error(message) {
+        DriverErrorCategory::Auth
+    } else if has(&[
+        "connection refused",
+        "connection reset",
+        "connection closed",
+        "connection not found",
+        "broken pipe",
+        "unexpected eof",
+        "pool timed out",
+        "server has gone away",
+        "lost connection",
+        "could not connect",
+        "failed to connect",
+        "error sending request",
+    ]) {
+        DriverErrorCategory::Connection
+    } else if has(&["timed out", "timeout", "max_execution_time"]) {
+        DriverErrorCategory::Timeout
+    } else if has(&[
+        "canceling statement",
+        "cancelled",
+        "canceled",
+        "interrupted",
+    ]) {
+        DriverErrorCategory::Cancelled
+    } else if has(&["syntax error", "parser error", "parse error"]) {
+        DriverErrorCategory::Syntax
+    } else if has(&[
+        "permission denied",
+        "read-only",
+        "read only",
+        "readonly",
+        "not allowed",
+        "insufficient privilege",
+        "noperm",
+    ]) {
+        DriverErrorCategory::Permission
+    } else if has(&[
+        "constraint",
+        "duplicate key",
+        "duplicate entry",
+        "foreign key",
+    ]) {
+        DriverErrorCategory::Constraint
+    } else {
+        DriverErrorCategory::Other
+    }
+}
+
+fn number_after(message: &str, marker: &str) -> Option<usize> {
+    let (_, rest) = message.split_once(marker)?;
+    rest.split(|c: char| !c.is_ascii_digit())
+        .next()
+        .and_then(|digits| digits.parse().ok())
+}
+
+/// The statement text an error message quotes after `near`.
+fn near_snippet(message: &str) -> Option<&str> {
+    let (_, rest) = message.split_once("near ")?;
+    let quote = rest.chars().next().filter(|c| *c == '"' || *c == '\'')?;
+    let rest = &rest[1..];
+    // MySQL quotes the rest of the statement and doesn't escape quotes in it,
+    // so its snippet runs up to the final `' at line N`.
+    let end = rest.rfind("' at line ").filter(|_| quote == '\'');
+    let snippet = match end {
+        Some(end) => &rest[..end],
+        None => &rest[..rest.find(quote)?],
+    };
+    (!snippet.is_empty()).then_some(snippet)
+}
+
+impl From<sqlx::Error> for DriverError {
+    fn from(error: sqlx::Error) -> Self {
+        match &error {
+            sqlx::Error::Database(database_error) => {
+                let message = database_error.message().to_string();
+                if let Some(pg) =
+                    database_error.try_downcast_ref::<sqlx::postgres::PgDatabaseError>()
+                {
+                    let error = Self::from_sqlstate(pg.code(), message);
+                    // The line and column need the statement; `locate` fills
+                    // them in.
+                    return match pg.position() {
+                        Some(sqlx::postgres::PgErrorPosition::Original(position)) => Self {
+                            position: Some(ErrorPosition {
+                                offset: position.saturating_sub(1),
+                                line: 0,
+                                column: 0,
+                            }),
+                            ..error
+                        },
+                        _ => error,
+                    };
+                }
+                if let Some(mysql) =
+                    database_error.try_downcast_ref::<sqlx::mysql::MySqlDatabaseError>()
+                {
+                    return Self::from_mysql(mysql.number(), message);
+                }
+                match database_error.code() {
+                    Some(code)
+                        if database_error
+                            .try_downcast_ref::<sqlx::sqlite::SqliteError>()
+                            .is_some() =>
+                    {
+                        Self::from_sqlite(&code, message)
+                    }
+                    _ => Self::from_message(message),
+                }
+            }
+            sqlx::Error::Io(_)
+            | sqlx::Error::Tls(_)
+            | sqlx::Error::Protocol(_)
+            | sqlx::Error::PoolTimedOut
+            | sqlx::Error::PoolClosed
+            | sqlx::Error::WorkerCrashed => {
+                Self::new(DriverErrorCategory::Connection, error.to_string())
+            }
+            _ => Self::from_message(error.to_string()),
+        }
+    }
+}
+
+impl From<tiberius::error::Error> for DriverError {
+    fn from(error: tiberius::error::Error) -> Self {
+        match &error {
+            tiberius::error::Error::Server(token) => {
+                Self::from_sqlserver(token.code(), token.message(), token.line())
+            }
+            tiberius::error::Error::Io { .. }
+            | tiberius::error::Error::Tls(_)
+            | tiberius::error::Error::Protocol(_)
+            | tiberius::error::Error::Routing { .. } => {
+                Self::new(DriverErrorCategory::Connection, error.to_string())
+            }
+            _ => Self::from_message(error.to_string()),
+        }
+    }
+}
+
+impl From<String> for DriverError {
+    fn from(message: String) -> Self {
+        Self::from_message(message)
+    }
+}
+
+impl From<&str> for DriverError {
+    fn from(message: &str) -> Self {
+        Self::from_message(message)
+    }
+}
+
+impl From<DriverError> for String {
+    fn from(error: DriverError) -> Self {
+        error.message
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{DriverError, DriverErrorCategory, ErrorPosition};
+
+    #[test]
+    fn maps_engine_codes_to_categories() {
+        assert_eq!(
+            DriverError::from_sqlstate("28P01", "password authentication failed").category,
+            DriverErrorCategory::Auth
+        );
+        assert_eq!(
+            DriverError::from_sqlstate("23505", "duplicate key").category,
+            DriverErrorCategory::Constraint
+        );
+        assert_eq!(
+            DriverError::from_sqlstate("57014", "canceling statement due to statement timeout")
+                .category,
+            DriverErrorCategory::Timeout
+        );
+        assert_eq!(
+            DriverError::from_mysql(1064, "You have an error in your SQL syntax").category,
+            DriverErrorCategory::Syntax
+        );
+        assert_eq!(
+            DriverError::from_mysql(2013, "Lost connection").category,
+            DriverErrorCategory::Connection
+        );
+        assert_eq!(
+            DriverError::from_sqlite("2067", "UNIQUE constraint failed: t.id").category,
+            DriverErrorCategory::Constraint
+        );
+        let clickhouse = DriverError::from_clickhouse(
+            "Code: 516. DB::Exception: default: Authentication failed",
+        );
+        assert_eq!(clickhouse.category, DriverErrorCategory::Auth);
+        assert_eq!(clickhouse.code.as_deref(), Some("516"));
+        assert_eq!(
+            DriverError::from_sqlserver(18456, "Login failed for user 'sa'.", 1).category,
+            DriverErrorCategory::Auth
+        );
+        assert_eq!(
+            DriverError::from_sqlserver(2627, "Violation of PRIMARY KEY constraint", 1).category,
+            DriverErrorCategory::Constraint
+        );
+    }
+
+    #[test]
+    fn classifies_bare_messages() {
+        assert_eq!(
+            DriverError::from("Connection not found. Please connect first.").category,
+            DriverErrorCategory::Connection
+        );
+        assert_eq!(
+            DriverError::from("Query timed out after 30s").category,
+            DriverErrorCategory::Timeout
+        );
+        assert_eq!(
+            DriverError::from("stale connection").category,
+            DriverErrorCategory::Other
+        );
+    }
+
+    #[test]
+    fn only_retries_errors_a_fresh_connection_could_fix() {
+        assert!(DriverError::from("broken pipe").reconnect_may_help());
+        assert!(DriverError::from("something odd").reconnect_may_help());
+        assert!(
+            !DriverError::from_sqlstate("42P01", "relation does not exist").reconnect_may_help()
+        );
+        assert!(!DriverError::from_mysql(1064, "syntax").reconnect_may_help());
+        assert!(!DriverError::from("Query timed out after 30s").reconnect_may_help());
+    }
+
+    #[test]
+    fn locates_the_failing_token() {
+        let query = "SELECT *\nFORM users";
+        let clickhouse = DriverError::from_clickhouse(
+            "Code: 62. DB::Exception: Syntax error: failed at position 10 ('FORM'): FORM users.",
+        )
+        .locate(query);
+        assert_eq!(
+            clickhouse.position,
+            Some(ErrorPosition {
+                offset: 9,
+                line: 2,
+                column: 1
+            })
+        );
+
+        let mysql = DriverError::from_mysql(
+            1064,
+            "You have an error in your SQL syntax; check the manual that corresponds to your MySQL server version for the right syntax to use near 'FORM users' at line 2",
+        )
+        .locate(query);
+        assert_eq!(mysql.position.map(|p| (p.line, p.column)), Some((2, 1)));
+
+        let sqlite = DriverError::from_sqlite("1", "near \"FORM\": syntax error").locate(query);
+        assert_eq!(sqlite.category, DriverErrorCategory::Syntax);
+        assert_eq!(sqlite.position.map(|p| p.offset), Some(9));
+
+        let sqlserver =
+            DriverError::from_sqlserver(102, "Incorrect syntax near 'FORM'.", 2).locate(query);
+        assert_eq!(
+            sqlserver.position,
+            Some(ErrorPosition {
+                offset: 9,
+                line: 2,
+                column: 1
+            })
+        );
+        let sqlserver = DriverError::from_sqlserver(208, "Invalid object name 'users'.", 2)
+            .locate("SELECT 1;\nSELECT * FROM users");
+        assert_eq!(sqlserver.position.map(|p| (p.line, p.column)), Some((2, 1)));
+
+        let unknown = DriverError::from("boom").locate(query);
+        assert_eq!(unknown.position, None);
+    }
+}
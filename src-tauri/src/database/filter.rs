@@ -13,6 +13,7 @@ pub enum FilterDialect {
     DuckDb,
     Clickhouse,
     Mysql,
+    SqlServer,
 }
 
 fn normalize_clickhouse_type(data_type: &str) -> String {
@@ -43,25 +44,26 @@ pub fn classify_column_type(data_type: &str, dialect: FilterDialect) -> FilterCo
 
     match dialect {
         FilterDialect::Postgres => match normalized.as_str() {
-            "smallint" | "integer" | "bigint" | "smallserial" | "serial" | "bigserial" => {
-                FilterColumnKind::Integer
-            }
-            "numeric" | "decimal" | "real" | "double precision" | "money" => {
+            // Catalog names, and the wire names query results report.
+            "smallint" | "integer" | "bigint" | "smallserial" | "serial" | "bigserial" | "int2"
+            | "int4" | "int8" => FilterColumnKind::Integer,
+            "numeric" | "decimal" | "real" | "double precision" | "money" | "float4" | "float8" => {
                 FilterColumnKind::Decimal
             }
-            "boolean" => FilterColumnKind::Boolean,
+            "boolean" | "bool" => FilterColumnKind::Boolean,
             "date"
             | "time"
             | "time without time zone"
             | "time with time zone"
+            | "timetz"
             | "timestamp"
             | "timestamp without time zone"
             | "timestamp with time zone"
+            | "timestamptz"
             | "interval" => FilterColumnKind::Temporal,
             "uuid" => FilterColumnKind::Uuid,
-            "text" | "character" | "character varying" | "citext" | "name" => {
-                FilterColumnKind::Text
-            }
+            "text" | "character" | "character varying" | "citext" | "name" | "varchar" | "char"
+            | "bpchar" => FilterColumnKind::Text,
             _ => FilterColumnKind::Other,
         },
         FilterDialect::Sqlite => {
@@ -71,6 +73,10 @@ pub fn classify_column_type(data_type: &str, dialect: FilterDialect) -> FilterCo
             if normalized.contains("date") || normalized.contains("time") {
                 return FilterColumnKind::Temporal;
             }
+            // Result columns of expressions have no declared type.
+            if normalized == "null" {
+                return FilterColumnKind::Other;
+            }
 
             let declared_type = normalized.to_ascii_uppercase();
             if declared_type.contains("INT") {
@@ -157,6 +163,21 @@ pub fn classify_column_type(data_type: &str, dialect: FilterDialect) -> FilterCo
             }
             _ => FilterColumnKind::Other,
         },
+        FilterDialect::SqlServer => match normalized.split(['(', ' ']).next().unwrap_or_default() {
+            "tinyint" | "smallint" | "int" | "bigint" => FilterColumnKind::Integer,
+            "decimal" | "numeric" | "money" | "smallmoney" | "float" | "real" => {
+                FilterColumnKind::Decimal
+            }
+            "bit" => FilterColumnKind::Boolean,
+            "date" | "time" | "datetime" | "datetime2" | "smalldatetime" | "datetimeoffset" => {
+                FilterColumnKind::Temporal
+            }
+            "uniqueidentifier" => FilterColumnKind::Uuid,
+            "char" | "varchar" | "nchar" | "nvarchar" | "text" | "ntext" | "sysname" => {
+                FilterColumnKind::Text
+            }
+            _ => FilterColumnKind::Other,
+        },
     }
 }
 
@@ -309,6 +330,7 @@ fn quote_identifier(identifier: &str, dialect: FilterDialect) -> String {
         FilterDialect::Clickhouse | FilterDialect::Mysql => {
             format!("`{}`", identifier.replace('`', "``"))
         }
+        FilterDialect::SqlServer => format!("[{}]", identifier.replace(']', "]]")),
     }
 }
 
@@ -347,7 +369,10 @@ fn tagged_number(
 
     let parsed = match (dialect, expected_kind) {
         (
-            FilterDialect::Postgres | FilterDialect::Sqlite | FilterDialect::DuckDb,
+            FilterDialect::Postgres
+            | FilterDialect::Sqlite
+            | FilterDialect::DuckDb
+            | FilterDialect::SqlServer,
             FilterColumnKind::Integer,
         ) => value
             .parse::<i64>()
@@ -380,6 +405,19 @@ fn tagged_number(
                 data_type,
             }
         }
+        (FilterDialect::SqlServer, FilterColumnKind::Decimal) => {
+            // Compared as the column's own type so no digits are lost.
+            let data_type = column.data_type.trim().to_string();
+            if !data_type.chars().all(|character| {
+                character.is_ascii_alphanumeric() || matches!(character, '(' | ')' | ',' | ' ')
+            }) {
+                return Err("Unsupported SQL Server numeric column type".to_string());
+            }
+            FilterValue::ExactNumber {
+                value: value.to_string(),
+                data_type,
+            }
+        }
         _ => unreachable!(),
     };
 
@@ -431,6 +469,12 @@ fn placeholder(index: usize, value: &FilterValue, dialect: FilterDialect) -> Str
             _ => format!("${}", index + 1),
         },
         FilterDialect::Sqlite | FilterDialect::DuckDb | FilterDialect::Mysql => "?".to_string(),
+        FilterDialect::SqlServer => match value {
+            FilterValue::ExactNumber { data_type, .. } => {
+                format!("CAST(@P{} AS {data_type})", index + 1)
+            }
+            _ => format!("@P{}", index + 1),
+        },
         FilterDialect::Clickhouse => {
             let value_type = match value {
                 FilterValue::Text(_) => "String",
@@ -509,6 +553,19 @@ mod tests {
             classify_column_type("TIMESTAMP_NS", FilterDialect::DuckDb),
             FilterColumnKind::Temporal
         );
+
+        assert_eq!(
+            classify_column_type("TIMESTAMPTZ", FilterDialect::Postgres),
+            FilterColumnKind::Temporal
+        );
+        assert_eq!(
+            classify_column_type("INT8", FilterDialect::Postgres),
+            FilterColumnKind::Integer
+        );
+        assert_eq!(
+            classify_column_type("NULL", FilterDialect::Sqlite),
+            FilterColumnKind::Other
+        );
     }
 
     #[test]
@@ -737,6 +794,43 @@ mod tests {
         );
     }
 
+    #[test]
+    fn compiles_sql_server_values_as_numbered_parameters() {
+        let columns = [
+            column("customer]name", "nvarchar(200)", FilterDialect::SqlServer),
+            column("amount", "decimal(38,10)", FilterDialect::SqlServer),
+        ];
+        let compiled = compile_filter(
+            &FilterExpression {
+                conjunction: FilterConjunction::And,
+                conditions: vec![
+                    FilterCondition {
+                        column: "customer]name".to_string(),
+                        operator: FilterOperator::StartsWith,
+                        value: Some(json!("Ada")),
+                    },
+                    FilterCondition {
+                        column: "amount".to_string(),
+                        operator: FilterOperator::GreaterThan,
+                        value: Some(json!({
+                            "kind": "decimal",
+                            "value": "1234567890123456789.5"
+                        })),
+                    },
+                ],
+            },
+            &columns,
+            FilterDialect::SqlServer,
+        )
+        .unwrap();
+
+        assert_eq!(
+            compiled.sql,
+            "[customer]]name] LIKE @P1 AND [amount] > CAST(@P2 AS decimal(38,10))"
+        );
+        assert_eq!(compiled.values[0], FilterValue::Text("Ada%".to_string()));
+    }
+
     #[test]
     fn table_filter_rejects_conflicting_representations_at_the_boundary() {
         let error = TableFilter::from_parts(
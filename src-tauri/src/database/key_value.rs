@@ -0,0 +1,283 @@
+//! Key browsing and editing for key/value engines.
+//!
+//! SQL drivers return `None` from [`DatabaseDriver::key_value`]; engines with
+//! a keyspace instead of tables (Redis) return themselves, so commands and
+//! MCP tools reach key operations without knowing the concrete driver.
+
+use std::collections::HashMap;
+use std::sync::Arc;
+
+use async_trait::async_trait;
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+
+use super::bulk_keys::{
+    BulkKeyReport, BulkKeyRequest, BulkProgressSink, DumpedKey, KeyMatches, KeyOutcome,
+};
+use super::keyspace_analysis::{AnalysisOptions, AnalysisProgressSink, KeyspaceReport};
+use super::redis::{RedisKeyDetails, RedisKeyListResponse};
+use super::redis_modules::RedisModules;
+use super::{DatabaseDriver, DriverError};
+
+/// Reports SCAN progress: iteration, maximum iterations, keys found so far and
+/// the keys found by this iteration.
+pub type ScanProgress<'a> = &'a (dyn Fn(u32, u32, usize, &[String]) + Send + Sync);
+
+/// Receives each message of a live subscription as it arrives.
+pub type LiveSink<'a> = &'a (dyn Fn(LiveMessage) + Send + Sync);
+
+/// What a live subscription listens to.
+#[derive(Clone, Debug, Serialize, Deserialize, PartialEq)]
+#[serde(tag = "kind", rename_all = "snake_case")]
+pub enum LiveSource {
+    /// Pub/Sub channels, by name and by glob pattern.
+    Channels {
+        #[serde(default)]
+        channels: Vec<String>,
+        #[serde(default)]
+        patterns: Vec<String>,
+    },
+    /// Entries appended to a stream. Reading starts after `start_id`, or with
+    /// the next new entry when it is not set.
+    Stream {
+        key: String,
+        #[serde(default)]
+        start_id: Option<String>,
+        #[serde(default)]
+        group: Option<ConsumerGroup>,
+    },
+    /// Every command the server runs (`MONITOR`), for `duration_secs`.
+    /// `MONITOR` slows a busy server down, so it always ends on its own. In
+    /// cluster mode it watches the first seed node.
+    Monitor {
+        #[serde(default = "default_monitor_secs")]
+        duration_secs: u64,
+    },
+}
+
+/// How long a `MONITOR` capture runs, by default and at most.
+pub const DEFAULT_MONITOR_SECS: u64 = 30;
+pub const MAX_MONITOR_SECS: u64 = 300;
+
+fn default_monitor_secs() -> u64 {
+    DEFAULT_MONITOR_SECS
+}
+
+/// Read a stream as `consumer` of `group`, creating the group if needed.
+#[derive(Clone, Debug, Serialize, Deserialize, PartialEq)]
+pub struct ConsumerGroup {
+    pub group: String,
+    pub consumer: String,
+    /// Acknowledge (`XACK`) each entry once it has been delivered.
+    #[serde(default)]
+    pub ack: bool,
+}
+
+impl LiveSource {
+    pub fn validate(&self) -> Result<(), String> {
+        match self {
+            Self::Channels { channels, patterns } if channels.is_empty() && patterns.is_empty() => {
+                Err("Subscribe to at least one channel or pattern".to_string())
+            }
+            Self::Stream { key, .. } if key.is_empty() => {
+                Err("A stream subscription needs a key".to_string())
+            }
+            Self::Stream {
+                group: Some(group), ..
+            } if group.group.is_empty() || group.consumer.is_empty() => {
+                Err("A consumer group needs a group and a consumer name".to_string())
+            }
+            Self::Monitor { duration_secs } if !(1..=MAX_MONITOR_SECS).contains(duration_secs) => {
+                Err(format!(
+                    "A MONITOR capture runs for 1 to {MAX_MONITOR_SECS} seconds"
+                ))
+            }
+            _ => Ok(()),
+        }
+    }
+}
+
+/// One Pub/Sub message or stream entry.
+#[derive(Clone, Debug, Serialize, PartialEq)]
+pub struct LiveMessage {
+    /// The channel or stream key the message came from, or the client that
+    /// ran a monitored command.
+    pub source: String,
+    /// The pattern that matched, for pattern subscriptions.
+    pub pattern: Option<String>,
+    /// The stream entry ID.
+    pub id: Option<String>,
+    /// The message text, an object of the entry's fields, or a monitored
+    /// command (a `MonitorEvent`).
+    pub payload: Value,
+    pub received_at: String,
+}
+
+#[async_trait]
+pub trait KeyValueBrowser: Send + Sync {
+    /// Scan for keys matching a glob `pattern`, starting at `cursor`.
+    async fn search_keys(
+        &self,
+        pattern: &str,
+        limit: i64,
+        cursor: u64,
+        progress: ScanProgress<'_>,
+    ) -> Result<RedisKeyListResponse, DriverError>;
+
+    async fn get_key_details(&self, key: &str) -> Result<RedisKeyDetails, DriverError>;
+
+    async fn delete_key(&self, key: &str) -> Result<bool, DriverError>;
+
+    async fn set_key(&self, key: &str, value: &str, ttl: Option<i64>) -> Result<(), DriverError>;
+
+    async fn set_list_key(
+        &self,
+        key: &str,
+        values: &[String],
+        ttl: Option<i64>,
+    ) -> Result<(), DriverError>;
+
+    async fn set_set_key(
+        &self,
+        key: &str,
+        values: &[String],
+        ttl: Option<i64>,
+    ) -> Result<(), DriverError>;
+
+    async fn set_hash_key(
+        &self,
+        key: &str,
+        fields: &HashMap<String, String>,
+        ttl: Option<i64>,
+    ) -> Result<(), DriverError>;
+
+    async fn set_zset_key(
+        &self,
+        key: &str,
+        members: &[(String, f64)],
+        ttl: Option<i64>,
+    ) -> Result<(), DriverError>;
+
+    /// Set the key's TTL in seconds, or persist it when `ttl` is `None`.
+    async fn update_ttl(&self, key: &str, ttl: Option<i64>) -> Result<(), DriverError>;
+
+    /// Listen to `source` on a dedicated connection, passing each message to
+    /// `sink`. Runs until the future is dropped or the connection fails.
+    async fn watch(&self, source: &LiveSource, sink: LiveSink<'_>) -> Result<(), DriverError>;
+
+    /// Walk the whole keyspace on a dedicated connection and report memory
+    /// by key, type, key pattern and TTL.
+    async fn analyze_keyspace(
+        &self,
+        options: &AnalysisOptions,
+        progress: AnalysisProgressSink<'_>,
+    ) -> Result<KeyspaceReport, DriverError>;
+
+    /// Count the keys matching `pattern`, keeping the first `sample_size`.
+    async fn match_keys(
+        &self,
+        pattern: &str,
+        sample_size: usize,
+    ) -> Result<KeyMatches, DriverError>;
+
+    /// Apply a bulk operation to every key matching its pattern. Keys copied
+    /// or moved to another connection are written with `target`.
+    async fn bulk_update(
+        &self,
+        request: &BulkKeyRequest,
+        target: Option<&dyn KeyValueBrowser>,
+        progress: BulkProgressSink<'_>,
+    ) -> Result<BulkKeyReport, DriverError>;
+
+    /// Write keys dumped from another connection, one outcome per key.
+    async fn restore_keys(
+        &self,
+        keys: &[DumpedKey],
+        replace: bool,
+    ) -> Result<Vec<KeyOutcome>, DriverError>;
+
+    /// The server modules, from `MODULE LIST`. Servers that refuse the
+    /// command report none.
+    async fn modules(&self) -> Result<RedisModules, DriverError>;
+
+    /// The JSON at `path` of a RedisJSON document. JSONPath (`$...`) returns
+    /// an array of every match.
+    async fn json_get(&self, key: &str, path: &str) -> Result<Value, DriverError>;
+
+    /// Write `value` at `path`, creating the document when `path` is the root.
+    async fn json_set(&self, key: &str, path: &str, value: &Value) -> Result<(), DriverError>;
+
+    /// Delete the values at `path`, returning how many were removed.
+    async fn json_delete(&self, key: &str, path: &str) -> Result<u64, DriverError>;
+}
+
+/// The key operations of a pooled driver, or an error for SQL connections.
+pub(crate) fn key_value_browser(
+    driver: &Arc<Box<dyn DatabaseDriver>>,
+) -> Result<&dyn KeyValueBrowser, String> {
+    driver
+        .key_value()
+        .ok_or_else(|| "Connection does not support key browsing".to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn live_sources_deserialize_by_kind() {
+        let source: LiveSource = serde_json::from_value(json!({
+            "kind": "stream",
+            "key": "orders",
+            "group": { "group": "audit", "consumer": "dbcooper", "ack": true }
+        }))
+        .unwrap();
+        assert_eq!(
+            source,
+            LiveSource::Stream {
+                key: "orders".to_string(),
+                start_id: None,
+                group: Some(ConsumerGroup {
+                    group: "audit".to_string(),
+                    consumer: "dbcooper".to_string(),
+                    ack: true,
+                }),
+            }
+        );
+        assert!(source.validate().is_ok());
+
+        let source: LiveSource =
+            serde_json::from_value(json!({ "kind": "channels", "patterns": ["orders.*"] }))
+                .unwrap();
+        assert!(source.validate().is_ok());
+    }
+
+    #[test]
+    fn live_sources_need_something_to_listen_to() {
+        let channels: LiveSource = serde_json::from_value(json!({ "kind": "channels" })).unwrap();
+        assert!(channels.validate().is_err());
+        let group: LiveSource = serde_json::from_value(json!({
+            "kind": "stream",
+            "key": "orders",
+            "group": { "group": "audit", "consumer": "" }
+        }))
+        .unwrap();
+        assert!(group.validate().is_err());
+    }
+
+    #[test]
+    fn monitor_captures_are_time_boxed() {
+        let monitor: LiveSource = serde_json::from_value(json!({ "kind": "monitor" })).unwrap();
+        assert_eq!(
+            monitor,
+            LiveSource::Monitor {
+                duration_secs: DEFAULT_MONITOR_SECS
+            }
+        );
+        assert!(monitor.validate().is_ok());
+        for duration_secs in [0, MAX_MONITOR_SECS + 1] {
+            assert!(LiveSource::Monitor { duration_secs }.validate().is_err());
+        }
+    }
+}
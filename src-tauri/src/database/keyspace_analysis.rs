@@ -0,0 +1,678 @@
+//! Keyspace memory analysis.
+//!
+//! [`KeyValueBrowser::analyze_keyspace`] walks every key and hands its type,
+//! memory, TTL and encoding to a [`KeyspaceAnalyzer`], which keeps the
+//! biggest keys and totals by type, by key pattern and by TTL. Analyses run in
+//! the background under an [`AnalysisManager`], which throttles progress
+//! events and can cancel a running analysis.
+
+use serde::{Deserialize, Serialize};
+use std::cmp::Reverse;
+use std::collections::{BinaryHeap, HashMap};
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+use tokio::task::AbortHandle;
+use uuid::Uuid;
+
+use super::key_value::key_value_browser;
+use super::DatabaseDriver;
+
+pub const ANALYSIS_PROGRESS_EVENT: &str = "redis-analysis-progress";
+pub const ANALYSIS_FINISHED_EVENT: &str = "redis-analysis-finished";
+
+const PROGRESS_INTERVAL: Duration = Duration::from_millis(250);
+/// Patterns past this many are counted under [`OTHER_PATTERN`], so keys
+/// without a recognisable structure cannot grow the report without bound.
+const MAX_PATTERNS: usize = 10_000;
+const OTHER_PATTERN: &str = "(other)";
+/// How many patterns the report lists, biggest first.
+const REPORTED_PATTERNS: usize = 200;
+
+const TTL_BUCKETS: [(&str, i64); 5] = [
+    ("Under 1 minute", 60),
+    ("Under 1 hour", 60 * 60),
+    ("Under 1 day", 24 * 60 * 60),
+    ("Under 1 week", 7 * 24 * 60 * 60),
+    ("1 week or more", i64::MAX),
+];
+
+#[derive(Clone, Debug, Serialize, Deserialize, PartialEq)]
+pub struct AnalysisOptions {
+    /// Only analyse keys matching this glob.
+    #[serde(default)]
+    pub pattern: Option<String>,
+    /// Characters that separate the segments of a key name.
+    #[serde(default = "default_delimiters")]
+    pub delimiters: String,
+    /// Segments kept in a key pattern before the rest collapses to `*`.
+    #[serde(default = "default_max_depth")]
+    pub max_depth: usize,
+    /// How many of the biggest keys to report.
+    #[serde(default = "default_top_keys")]
+    pub top_keys: usize,
+}
+
+fn default_delimiters() -> String {
+    ":".to_string()
+}
+
+fn default_max_depth() -> usize {
+    3
+}
+
+fn default_top_keys() -> usize {
+    50
+}
+
+impl Default for AnalysisOptions {
+    fn default() -> Self {
+        Self {
+            pattern: None,
+            delimiters: default_delimiters(),
+            max_depth: default_max_depth(),
+            top_keys: default_top_keys(),
+        }
+    }
+}
+
+/// What the analysis learned about one key.
+#[derive(Clone, Debug, PartialEq)]
+pub struct KeySample {
+    pub key: String,
+    pub key_type: String,
+    /// `MEMORY USAGE` in bytes; `None` when the server would not say.
+    pub memory: Option<u64>,
+    /// Seconds to live, or `-1` without expiry.
+    pub ttl: i64,
+    pub encoding: Option<String>,
+}
+
+#[derive(Clone, Debug, Serialize, PartialEq)]
+pub struct AnalysisProgress {
+    pub scanned_keys: u64,
+    /// The key count when the analysis started (`DBSIZE`), for a percentage.
+    pub total_keys: u64,
+    pub memory_bytes: u64,
+}
+
+/// Reports analysis progress after each batch of keys.
+pub type AnalysisProgressSink<'a> = &'a (dyn Fn(&AnalysisProgress) + Send + Sync);
+
+#[derive(Clone, Debug, Serialize, PartialEq)]
+pub struct KeyspaceReport {
+    pub scanned_keys: u64,
+    pub total_memory: u64,
+    pub keys_without_expiry: u64,
+    pub memory_without_expiry: u64,
+    pub by_type: Vec<GroupSummary>,
+    pub by_pattern: Vec<PatternSummary>,
+    pub ttl_distribution: Vec<GroupSummary>,
+    pub biggest_keys: Vec<BigKey>,
+    pub elapsed_ms: u128,
+}
+
+#[derive(Clone, Debug, Default, Serialize, PartialEq)]
+pub struct GroupSummary {
+    pub name: String,
+    pub keys: u64,
+    pub memory: u64,
+}
+
+#[derive(Clone, Debug, Default, Serialize, PartialEq)]
+pub struct PatternSummary {
+    pub pattern: String,
+    pub keys: u64,
+    pub memory: u64,
+    pub keys_without_expiry: u64,
+    /// The largest key matching the pattern, as an example.
+    pub biggest_key: String,
+    pub biggest_key_memory: u64,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq, PartialOrd, Ord, Serialize)]
+pub struct BigKey {
+    // Field order is the ranking order.
+    pub memory: u64,
+    pub key: String,
+    pub key_type: String,
+    pub ttl: i64,
+    pub encoding: Option<String>,
+}
+
+/// Aggregates [`KeySample`]s into a [`KeyspaceReport`].
+pub struct KeyspaceAnalyzer {
+    options: AnalysisOptions,
+    started: Instant,
+    scanned_keys: u64,
+    total_memory: u64,
+    keys_without_expiry: u64,
+    memory_without_expiry: u64,
+    by_type: HashMap<String, GroupSummary>,
+    by_pattern: HashMap<String, PatternSummary>,
+    ttl_distribution: Vec<GroupSummary>,
+    biggest_keys: BinaryHeap<Reverse<BigKey>>,
+}
+
+impl KeyspaceAnalyzer {
+    pub fn new(options: &AnalysisOptions) -> Self {
+        Self {
+            options: options.clone(),
+            started: Instant::now(),
+            scanned_keys: 0,
+            total_memory: 0,
+            keys_without_expiry: 0,
+            memory_without_expiry: 0,
+            by_type: HashMap::new(),
+            by_pattern: HashMap::new(),
+            ttl_distribution: std::iter::once("No expiry")
+                .chain(TTL_BUCKETS.iter().map(|(name, _)| *name))
+                .map(|name| GroupSummary {
+                    name: name.to_string(),
+                    ..Default::default()
+                })
+                .collect(),
+            biggest_keys: BinaryHeap::new(),
+        }
+    }
+
+    pub fn add(&mut self, sample: KeySample) {
+        let memory = sample.memory.unwrap_or(0);
+        let persistent = sample.ttl < 0;
+        self.scanned_keys += 1;
+        self.total_memory += memory;
+        if persistent {
+            self.keys_without_expiry += 1;
+            self.memory_without_expiry += memory;
+        }
+
+        let by_type = self
+            .by_type
+            .entry(sample.key_type.clone())
+            .or_insert_with(|| GroupSummary {
+                name: sample.key_type.clone(),
+                ..Default::default()
+            });
+        by_type.keys += 1;
+        by_type.memory += memory;
+
+        let bucket = if persistent {
+            0
+        } else {
+            1 + TTL_BUCKETS
+                .iter()
+                .position(|(_, limit)| sample.ttl < *limit)
+                .unwrap_or(TTL_BUCKETS.len() - 1)
+        };
+        self.ttl_distribution[bucket].keys += 1;
+        self.ttl_distribution[bucket].memory += memory;
+
+        let mut pattern = key_pattern(
+            &sample.key,
+            &self.options.delimiters,
+            self.options.max_depth,
+        );
+        if !self.by_pattern.contains_key(&pattern) && self.by_pattern.len() >= MAX_PATTERNS {
+            pattern = OTHER_PATTERN.to_string();
+        }
+        let by_pattern = self
+            .by_pattern
+            .entry(pattern.clone())
+            .or_insert_with(|| PatternSummary {
+                pattern,
+                ..Default::default()
+            });
+        by_pattern.keys += 1;
+        by_pattern.memory += memory;
+        if persistent {
+            by_pattern.keys_without_expiry += 1;
+        }
+        if by_pattern.biggest_key.is_empty() || memory > by_pattern.biggest_key_memory {
+            by_pattern.biggest_key = sample.key.clone();
+            by_pattern.biggest_key_memory = memory;
+        }
+
+        if self.options.top_keys > 0 {
+            let smallest = self.biggest_keys.peek().map(|Reverse(key)| key.memory);
+            if self.biggest_keys.len() < self.options.top_keys
+                || smallest.is_some_and(|smallest| memory > smallest)
+            {
+                self.biggest_keys.push(Reverse(BigKey {
+                    memory,
+                    key: sample.key,
+                    key_type: sample.key_type,
+                    ttl: sample.ttl,
+                    encoding: sample.encoding,
+                }));
+                if self.biggest_keys.len() > self.options.top_keys {
+                    self.biggest_keys.pop();
+                }
+            }
+        }
+    }
+
+    pub fn scanned_keys(&self) -> u64 {
+        self.scanned_keys
+    }
+
+    pub fn total_memory(&self) -> u64 {
+        self.total_memory
+    }
+
+    pub fn report(self) -> KeyspaceReport {
+        let mut by_type: Vec<GroupSummary> = self.by_type.into_values().collect();
+        by_type.sort_by(|a, b| b.memory.cmp(&a.memory).then(a.name.cmp(&b.name)));
+        let mut by_pattern: Vec<PatternSummary> = self.by_pattern.into_values().collect();
+        by_pattern.sort_by(|a, b| b.memory.cmp(&a.memory).then(a.pattern.cmp(&b.pattern)));
+        by_pattern.truncate(REPORTED_PATTERNS);
+        let mut biggest_keys: Vec<BigKey> = self
+            .biggest_keys
+            .into_iter()
+            .map(|Reverse(key)| key)
+            .collect();
+        biggest_keys.sort_by(|a, b| b.cmp(a));
+
+        KeyspaceReport {
+            scanned_keys: self.scanned_keys,
+            total_memory: self.total_memory,
+            keys_without_expiry: self.keys_without_expiry,
+            memory_without_expiry: self.memory_without_expiry,
+            by_type,
+            by_pattern,
+            ttl_distribution: self.ttl_distribution,
+            biggest_keys,
+            elapsed_ms: self.started.elapsed().as_millis(),
+        }
+    }
+}
+
+/// The pattern a key is grouped under. Segments between delimiters that look
+/// like identifiers (numbers, UUIDs, hashes, tokens) become `*`, and segments
+/// past `max_depth` collapse into a trailing `*`:
+/// `session:8f14e45f-ceea-4b6e-9bd2-1b1f4a2e4c3a:data` becomes `session:*:data`.
+pub fn key_pattern(key: &str, delimiters: &str, max_depth: usize) -> String {
+    let mut pattern = String::new();
+    let mut depth = 0;
+    let mut rest = key;
+    loop {
+        let end = rest
+            .find(|c: char| delimiters.contains(c))
+            .unwrap_or(rest.len());
+        let segment = &rest[..end];
+        if depth == max_depth.max(1) {
+            pattern.push('*');
+            break;
+        }
+        pattern.push_str(if is_identifier(segment) { "*" } else { segment });
+        depth += 1;
+
+        let Some(delimiter) = rest[end..].chars().next() else {
+            break;
+        };
+        pattern.push(delimiter);
+        rest = &rest[end + delimiter.len_utf8()..];
+    }
+    pattern
+}
+
+fn is_identifier(segment: &str) -> bool {
+    let has_digit = segment.chars().any(|c| c.is_ascii_digit());
+    let numeric = !segment.is_empty() && segment.chars().all(|c| c.is_ascii_digit());
+    let hex = segment.len() >= 8
+        && has_digit
+        && segment.chars().all(|c| c.is_ascii_hexdigit() || c == '-');
+    let token = segment.len() >= 20
+        && has_digit
+        && segment
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || matches!(c, '-' | '_' | '=' | '+' | '/'));
+    numeric || hex || token
+}
+
+#[derive(Clone, Debug, Serialize)]
+pub struct AnalysisJob {
+    pub id: String,
+    pub connection_uuid: String,
+    pub options: AnalysisOptions,
+    pub started_at: String,
+}
+
+#[derive(Clone, Debug, Serialize)]
+pub struct AnalysisProgressPayload {
+    pub job_id: String,
+    pub connection_uuid: String,
+    #[serde(flatten)]
+    pub progress: AnalysisProgress,
+}
+
+/// Sent once when an analysis ends, with its report, its error, or neither
+/// when it was cancelled.
+#[derive(Clone, Debug, Serialize)]
+pub struct AnalysisFinished {
+    pub job_id: String,
+    pub connection_uuid: String,
+    pub report: Option<KeyspaceReport>,
+    pub error: Option<String>,
+    pub cancelled: bool,
+}
+
+#[derive(Clone, Debug)]
+pub enum AnalysisEvent {
+    Progress(AnalysisProgressPayload),
+    Finished(Box<AnalysisFinished>),
+}
+
+type Notify = Arc<dyn Fn(&AnalysisEvent) + Send + Sync>;
+
+struct Job {
+    info: AnalysisJob,
+    task: AbortHandle,
+}
+
+/// Background keyspace analyses, at most one per connection.
+pub struct AnalysisManager {
+    notify: Notify,
+    jobs: Arc<Mutex<HashMap<String, Job>>>,
+}
+
+impl AnalysisManager {
+    pub fn new(notify: impl Fn(&AnalysisEvent) + Send + Sync + 'static) -> Self {
+        Self {
+            notify: Arc::new(notify),
+            jobs: Arc::new(Mutex::new(HashMap::new())),
+        }
+    }
+
+    pub fn start(
+        &self,
+        connection_uuid: &str,
+        driver: Arc<Box<dyn DatabaseDriver>>,
+        options: AnalysisOptions,
+    ) -> Result<AnalysisJob, String> {
+        key_value_browser(&driver)?;
+        if options.delimiters.is_empty() {
+            return Err("Choose at least one key delimiter".to_string());
+        }
+
+        let mut jobs = self.jobs.lock().map_err(|e| e.to_string())?;
+        if jobs
+            .values()
+            .any(|job| job.info.connection_uuid == connection_uuid)
+        {
+            return Err("An analysis is already running for this connection".to_string());
+        }
+        let info = AnalysisJob {
+            id: Uuid::new_v4().to_string(),
+            connection_uuid: connection_uuid.to_string(),
+            options,
+            started_at: chrono::Utc::now().to_rfc3339(),
+        };
+        // Registered before the lock is released, so a job that finishes
+        // immediately still finds itself to unregister.
+        let task = tokio::spawn(run(
+            info.clone(),
+            driver,
+            self.notify.clone(),
+            self.jobs.clone(),
+        ));
+        jobs.insert(
+            info.id.clone(),
+            Job {
+                info: info.clone(),
+                task: task.abort_handle(),
+            },
+        );
+        Ok(info)
+    }
+
+    /// Cancel an analysis. Returns `false` if it had already finished.
+    pub fn cancel(&self, job_id: &str) -> bool {
+        let removed = self
+            .jobs
+            .lock()
+            .ok()
+            .and_then(|mut jobs| jobs.remove(job_id));
+        match removed {
+            Some(job) => {
+                self.end(job);
+                true
+            }
+            None => false,
+        }
+    }
+
+    /// Cancel the analysis of a connection, e.g. when it disconnects.
+    pub fn cancel_connection(&self, connection_uuid: &str) {
+        let removed = self.jobs.lock().ok().and_then(|mut jobs| {
+            let id = jobs
+                .values()
+                .find(|job| job.info.connection_uuid == connection_uuid)
+                .map(|job| job.info.id.clone())?;
+            jobs.remove(&id)
+        });
+        if let Some(job) = removed {
+            self.end(job);
+        }
+    }
+
+    /// The running analysis of a connection, if any.
+    pub fn running(&self, connection_uuid: &str) -> Option<AnalysisJob> {
+        self.jobs.lock().ok().and_then(|jobs| {
+            jobs.values()
+                .find(|job| job.info.connection_uuid == connection_uuid)
+                .map(|job| job.info.clone())
+        })
+    }
+
+    fn end(&self, job: Job) {
+        job.task.abort();
+        (self.notify)(&AnalysisEvent::Finished(Box::new(AnalysisFinished {
+            job_id: job.info.id,
+            connection_uuid: job.info.connection_uuid,
+            report: None,
+            error: None,
+            cancelled: true,
+        })));
+    }
+}
+
+async fn run(
+    info: AnalysisJob,
+    driver: Arc<Box<dyn DatabaseDriver>>,
+    notify: Notify,
+    jobs: Arc<Mutex<HashMap<String, Job>>>,
+) {
+    let last_progress: Mutex<Option<Instant>> = Mutex::new(None);
+    let progress = |progress: &AnalysisProgress| {
+        let due = last_progress.lock().is_ok_and(|mut last| {
+            let due = last.is_none_or(|last| last.elapsed() >= PROGRESS_INTERVAL);
+            if due {
+                *last = Some(Instant::now());
+            }
+            due
+        });
+        if due {
+            notify(&AnalysisEvent::Progress(AnalysisProgressPayload {
+                job_id: info.id.clone(),
+                connection_uuid: info.connection_uuid.clone(),
+                progress: progress.clone(),
+            }));
+        }
+    };
+
+    let result = match key_value_browser(&driver) {
+        Ok(browser) => browser
+            .analyze_keyspace(&info.options, &progress)
+            .await
+            .map_err(|e| e.to_string()),
+        Err(error) => Err(error),
+    };
+
+    // A cancelled job was removed (and reported) already.
+    let registered = jobs
+        .lock()
+        .ok()
+        .and_then(|mut jobs| jobs.remove(&info.id))
+        .is_some();
+    if registered {
+        let (report, error) = match result {
+            Ok(report) => (Some(report), None),
+            Err(error) => (None, Some(error)),
+        };
+        notify(&AnalysisEvent::Finished(Box::new(AnalysisFinished {
+            job_id: info.id.clone(),
+            connection_uuid: info.connection_uuid.clone(),
+            report,
+            error,
+            cancelled: false,
+        })));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::database::redis::RedisDriver;
+    use crate::database::{QueryLimits, RedisConfig, RedisTopology};
+
+    fn sample(key: &str, key_type: &str, memory: u64, ttl: i64) -> KeySample {
+        KeySample {
+            key: key.to_string(),
+            key_type: key_type.to_string(),
+            memory: Some(memory),
+            ttl,
+            encoding: None,
+        }
+    }
+
+    #[test]
+    fn groups_keys_by_delimiter_aware_patterns() {
+        assert_eq!(key_pattern("user:42:profile", ":", 3), "user:*:profile");
+        assert_eq!(
+            key_pattern("session:8f14e45f-ceea-4b6e-9bd2-1b1f4a2e4c3a:data", ":", 3),
+            "session:*:data"
+        );
+        assert_eq!(
+            key_pattern("cache:en:home:v2:hero", ":", 3),
+            "cache:en:home:*"
+        );
+        assert_eq!(key_pattern("orders/2024/12", ":/", 3), "orders/*/*");
+        assert_eq!(key_pattern("config", ":", 3), "config");
+        assert_eq!(key_pattern("12345", ":", 3), "*");
+        assert_eq!(key_pattern("queue:", ":", 3), "queue:");
+        // Words that happen to be hex are not identifiers without a digit.
+        assert_eq!(key_pattern("feed:deadbeef", ":", 3), "feed:deadbeef");
+    }
+
+    #[test]
+    fn reports_the_biggest_keys_and_totals() {
+        let mut analyzer = KeyspaceAnalyzer::new(&AnalysisOptions {
+            top_keys: 2,
+            ..Default::default()
+        });
+        analyzer.add(sample("user:1", "hash", 100, -1));
+        analyzer.add(sample("user:2", "hash", 300, 30));
+        analyzer.add(sample("feed:1", "list", 5_000, 7200));
+        analyzer.add(sample("flag", "string", 50, 30 * 24 * 60 * 60));
+
+        let report = analyzer.report();
+        assert_eq!(report.scanned_keys, 4);
+        assert_eq!(report.total_memory, 5_450);
+        assert_eq!(report.keys_without_expiry, 1);
+        assert_eq!(report.memory_without_expiry, 100);
+
+        let biggest: Vec<&str> = report.biggest_keys.iter().map(|k| k.key.as_str()).collect();
+        assert_eq!(biggest, vec!["feed:1", "user:2"]);
+
+        assert_eq!(report.by_type[0].name, "list");
+        assert_eq!(report.by_pattern[1].pattern, "user:*");
+        assert_eq!(report.by_pattern[1].keys, 2);
+        assert_eq!(report.by_pattern[1].memory, 400);
+        assert_eq!(report.by_pattern[1].keys_without_expiry, 1);
+        assert_eq!(report.by_pattern[1].biggest_key, "user:2");
+
+        let ttl: Vec<(&str, u64)> = report
+            .ttl_distribution
+            .iter()
+            .map(|bucket| (bucket.name.as_str(), bucket.keys))
+            .collect();
+        assert_eq!(
+            ttl,
+            vec![
+                ("No expiry", 1),
+                ("Under 1 minute", 1),
+                ("Under 1 hour", 0),
+                ("Under 1 day", 1),
+                ("Under 1 week", 0),
+                ("1 week or more", 1),
+            ]
+        );
+    }
+
+    #[test]
+    fn folds_patterns_past_the_limit_into_other() {
+        let mut analyzer = KeyspaceAnalyzer::new(&AnalysisOptions::default());
+        for index in 0..MAX_PATTERNS + 5 {
+            analyzer.add(sample(
+                &format!("word{}x", "y".repeat(index % 50)),
+                "string",
+                1,
+                -1,
+            ));
+        }
+        for index in 0..MAX_PATTERNS + 5 {
+            analyzer.add(sample(&format!("key-{index}-name"), "string", 1, -1));
+        }
+        assert!(analyzer.by_pattern.len() <= MAX_PATTERNS + 1);
+        assert!(analyzer.by_pattern.contains_key(OTHER_PATTERN));
+    }
+
+    #[tokio::test]
+    async fn a_failed_analysis_reports_why_and_unregisters() {
+        let (sender, mut events) = tokio::sync::mpsc::unbounded_channel();
+        let manager = AnalysisManager::new(move |event: &AnalysisEvent| {
+            let _ = sender.send(event.clone());
+        });
+        let driver: Arc<Box<dyn DatabaseDriver>> =
+            Arc::new(Box::new(RedisDriver::new(RedisConfig {
+                host: "127.0.0.1".to_string(),
+                port: 1,
+                username: None,
+                password: None,
+                db: None,
+                tls: false,
+                topology: RedisTopology::Standalone,
+                limits: QueryLimits::default(),
+            })));
+
+        assert!(manager
+            .start(
+                "connection-1",
+                driver.clone(),
+                AnalysisOptions {
+                    delimiters: String::new(),
+                    ..Default::default()
+                }
+            )
+            .is_err());
+        let job = manager
+            .start("connection-1", driver.clone(), AnalysisOptions::default())
+            .unwrap();
+        assert!(manager
+            .start("connection-1", driver, AnalysisOptions::default())
+            .is_err());
+        assert_eq!(manager.running("connection-1").unwrap().id, job.id);
+
+        let event = tokio::time::timeout(Duration::from_secs(10), events.recv())
+            .await
+            .unwrap()
+            .unwrap();
+        let AnalysisEvent::Finished(finished) = event else {
+            panic!("expected the analysis to finish");
+        };
+        assert_eq!(finished.job_id, job.id);
+        assert!(finished.error.is_some());
+        assert!(!finished.cancelled);
+        assert!(manager.running("connection-1").is_none());
+        assert!(!manager.cancel(&job.id));
+    }
+}
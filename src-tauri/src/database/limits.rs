@@ -0,0 +1,236 @@
+//! Per-connection query timeouts and result size caps.
+//!
+//! Drivers push the query timeout down to the engine where it has a native
+//! knob (`statement_timeout`, `max_execution_time`, ...) and additionally run
+//! every query under [`QueryLimits::run`], a client-side timeout that stops
+//! waiting for the in-flight work. That alone does not stop the statement on
+//! the server, which is what
+//! [`DriverCapabilities::cancellation`](super::DriverCapabilities::cancellation)
+//! reports. Row and byte caps are applied to the decoded rows so every engine
+//! truncates the same way.
+
+use serde::Serialize;
+use serde_json::Value;
+use std::future::Future;
+use std::time::Duration;
+
+use super::{DriverError, DriverErrorCategory};
+
+pub const DEFAULT_MAX_RESULT_ROWS: usize = 10_000;
+pub const DEFAULT_CONNECT_TIMEOUT: Duration = Duration::from_secs(15);
+
+/// Slack given to engines that enforce the timeout themselves, so their own
+/// (more specific) error normally arrives before the client-side backstop.
+const NATIVE_TIMEOUT_GRACE: Duration = Duration::from_secs(2);
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct QueryLimits {
+    /// `None` lets queries run until they finish.
+    pub query_timeout: Option<Duration>,
+    pub connect_timeout: Duration,
+    pub max_rows: usize,
+    /// Approximate cap on the JSON size of the returned rows, counting both
+    /// the array and the object copy of each.
+    pub max_bytes: Option<usize>,
+}
+
+impl Default for QueryLimits {
+    fn default() -> Self {
+        Self {
+            query_timeout: None,
+            connect_timeout: DEFAULT_CONNECT_TIMEOUT,
+            max_rows: DEFAULT_MAX_RESULT_ROWS,
+            max_bytes: None,
+        }
+    }
+}
+
+fn positive(value: Option<i64>, label: &str) -> Result<Option<u64>, String> {
+    match value {
+        None => Ok(None),
+        Some(value) if value > 0 => Ok(Some(value as u64)),
+        Some(_) => Err(format!("{label} must be greater than zero")),
+    }
+}
+
+impl QueryLimits {
+    /// Build limits from the nullable columns stored on a connection; `None`
+    /// keeps the default for that limit.
+    pub fn from_settings(
+        query_timeout_ms: Option<i64>,
+        connect_timeout_ms: Option<i64>,
+        max_result_rows: Option<i64>,
+        max_result_bytes: Option<i64>,
+    ) -> Result<Self, String> {
+        let defaults = Self::default();
+        Ok(Self {
+            query_timeout: positive(query_timeout_ms, "Query timeout")?.map(Duration::from_millis),
+            connect_timeout: positive(connect_timeout_ms, "Connect timeout")?
+                .map_or(defaults.connect_timeout, Duration::from_millis),
+            max_rows: positive(max_result_rows, "Maximum result rows")?
+                .map_or(defaults.max_rows, |rows| rows as usize),
+            max_bytes: positive(max_result_bytes, "Maximum result size")?
+                .map(|bytes| bytes as usize),
+        })
+    }
+
+    pub fn query_timeout_ms(&self) -> Option<u128> {
+        self.query_timeout.map(|timeout| timeout.as_millis())
+    }
+
+    /// Rows to request from the engine: one more than the cap, so truncation
+    /// can be detected without reading the whole result.
+    pub fn fetch_limit(&self) -> usize {
+        self.max_rows.saturating_add(1)
+    }
+
+    pub fn timeout_message(&self) -> String {
+        format!(
+            "Query exceeded the {} timeout",
+            describe_duration(self.query_timeout.unwrap_or_default())
+        )
+    }
+
+    pub fn connect_timeout_message(&self) -> String {
+        format!(
+            "Connection timed out after {}",
+            describe_duration(self.connect_timeout)
+        )
+    }
+
+    /// Run `future` under the query timeout, dropping it when time runs out.
+    /// The engine may keep running a statement the future had already sent.
+    /// `native` marks engines that already enforce the timeout server-side;
+    /// for those this is only a backstop for a wedged connection.
+    pub async fn run<T>(
+        &self,
+        native: bool,
+        future: impl Future<Output = T>,
+    ) -> Result<T, DriverError> {
+        let Some(timeout) = self.query_timeout else {
+            return Ok(future.await);
+        };
+        let deadline = if native {
+            timeout + NATIVE_TIMEOUT_GRACE
+        } else {
+            timeout
+        };
+        tokio::time::timeout(deadline, future)
+            .await
+            .map_err(|_| DriverError::new(DriverErrorCategory::Timeout, self.timeout_message()))
+    }
+
+    /// Apply the row and byte caps to decoded rows (fetched with at most
+    /// [`fetch_limit`](Self::fetch_limit) rows). Returns the kept rows and
+    /// whether anything was dropped.
+    pub fn bound_rows(&self, mut rows: Vec<Vec<Value>>) -> (Vec<Vec<Value>>, bool) {
+        let mut truncated = rows.len() > self.max_rows;
+        rows.truncate(self.max_rows);
+
+        if let Some(max_bytes) = self.max_bytes {
+            let mut total = 0usize;
+            let keep = rows
+                .iter()
+                .position(|row| {
+                    total = total.saturating_add(json_size(row));
+                    total > max_bytes
+                })
+                .unwrap_or(rows.len());
+            if keep < rows.len() {
+                rows.truncate(keep);
+                truncated = true;
+            }
+        }
+        (rows, truncated)
+    }
+}
+
+fn describe_duration(duration: Duration) -> String {
+    let millis = duration.as_millis();
+    if millis.is_multiple_of(1000) {
+        format!("{}s", millis / 1000)
+    } else {
+        format!("{millis}ms")
+    }
+}
+
+/// Serialized JSON length of `value`, counted without allocating the string.
+fn json_size(value: &impl Serialize) -> usize {
+    struct Counter(usize);
+    impl std::io::Write for Counter {
+        fn write(&mut self, buf: &[u8]) -> std::io::Result<usize> {
+            self.0 += buf.len();
+            Ok(buf.len())
+        }
+        fn flush(&mut self) -> std::io::Result<()> {
+            Ok(())
+        }
+    }
+    let mut counter = Counter(0);
+    let _ = serde_json::to_writer(&mut counter, value);
+    counter.0
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{DriverErrorCategory, QueryLimits};
+    use serde_json::json;
+    use std::time::Duration;
+
+    #[test]
+    fn stored_nulls_fall_back_to_defaults() {
+        let limits = QueryLimits::from_settings(None, None, None, None).unwrap();
+        assert_eq!(limits, QueryLimits::default());
+
+        let limits = QueryLimits::from_settings(Some(1500), Some(5000), Some(10), None).unwrap();
+        assert_eq!(limits.query_timeout, Some(Duration::from_millis(1500)));
+        assert_eq!(limits.connect_timeout, Duration::from_secs(5));
+        assert_eq!(limits.max_rows, 10);
+        assert_eq!(limits.fetch_limit(), 11);
+
+        assert!(QueryLimits::from_settings(Some(0), None, None, None).is_err());
+        assert!(QueryLimits::from_settings(None, None, Some(-1), None).is_err());
+    }
+
+    #[test]
+    fn bounds_rows_by_count_and_serialized_size() {
+        let rows: Vec<_> = (0..5).map(|id| vec![json!(id)]).collect();
+        let limits = QueryLimits {
+            max_rows: 3,
+            ..QueryLimits::default()
+        };
+        let (kept, truncated) = limits.bound_rows(rows.clone());
+        assert_eq!(kept.len(), 3);
+        assert!(truncated);
+
+        // Each row is sent as `[N]`, 3 bytes.
+        let limits = QueryLimits {
+            max_bytes: Some(7),
+            ..QueryLimits::default()
+        };
+        let (kept, truncated) = limits.bound_rows(rows.clone());
+        assert_eq!(kept.len(), 2);
+        assert!(truncated);
+
+        let (kept, truncated) = QueryLimits::default().bound_rows(rows);
+        assert_eq!(kept.len(), 5);
+        assert!(!truncated);
+    }
+
+    #[tokio::test]
+    async fn cancels_work_that_outlives_the_query_timeout() {
+        let limits = QueryLimits {
+            query_timeout: Some(Duration::from_millis(20)),
+            ..QueryLimits::default()
+        };
+        let error = limits
+            .run(false, tokio::time::sleep(Duration::from_secs(5)))
+            .await
+            .unwrap_err();
+        assert_eq!(error.message, "Query exceeded the 20ms timeout");
+        assert_eq!(error.category, DriverErrorCategory::Timeout);
+
+        assert_eq!(limits.run(false, async { 7 }).await, Ok(7));
+        assert_eq!(QueryLimits::default().run(true, async { 7 }).await, Ok(7));
+    }
+}
@@ -1,30 +1,54 @@
 use async_trait::async_trait;
 
+pub mod bulk_keys;
+pub mod capabilities;
+pub mod cell;
 pub mod clickhouse;
 pub mod create_table;
+pub mod credentials;
 pub mod d1;
 pub mod driver_factory;
 pub mod duckdb;
+pub mod error;
 pub mod filter;
+pub mod key_value;
+pub mod keyspace_analysis;
+pub mod limits;
 pub mod mutation;
 pub mod mysql;
 mod mysql_read_only;
 pub mod pool_manager;
 pub mod postgres;
+pub mod postgres_values;
 pub mod queries;
 pub mod redis;
+pub mod redis_cli;
+pub mod redis_diagnostics;
+pub mod redis_modules;
 pub mod redis_read_only;
+pub mod redis_scripts;
+pub mod registry;
+pub mod session;
 pub mod sql_policy;
 pub mod sqlite;
+pub mod sqlserver;
+mod sqlserver_read_only;
+pub mod subscriptions;
 pub mod utils;
 
 use crate::db::models::{
     CreateTableRequest, FunctionDefinition, QueryResult, SchemaOverview, TableDataResponse,
     TableFilter, TableInfo, TableStructure, TestConnectionResult,
 };
+pub use capabilities::{DriverCapabilities, ReadOnlyEnforcement};
+pub use credentials::CredentialCommand;
+pub use error::{DriverError, DriverErrorCategory};
+use key_value::KeyValueBrowser;
+pub use limits::QueryLimits;
 use mutation::MutationPlan;
-
-pub const MAX_QUERY_RESULT_ROWS: usize = 10_000;
+use redis_diagnostics::ServerDiagnostics;
+use redis_scripts::ScriptRunner;
+pub use session::SessionOptions;
 
 fn is_identifier_char(ch: char) -> bool {
     ch.is_ascii_alphanumeric() || ch == '_'
@@ -200,23 +224,45 @@ pub fn query_returns_rows(query: &str) -> bool {
     query_returns_rows_with_keywords(query, &[])
 }
 
-/// Common trait for all database drivers
+/// Common trait for all database drivers. Failures are [`DriverError`]s so
+/// callers can tell a dropped connection from a bad statement.
 #[async_trait]
 pub trait DatabaseDriver: Send + Sync {
+    /// What this driver supports. Callers check this instead of matching on
+    /// the engine, and the defaults below only run for drivers that don't.
+    fn capabilities(&self) -> DriverCapabilities;
+
+    /// Key operations for key/value engines; `None` for SQL databases.
+    fn key_value(&self) -> Option<&dyn KeyValueBrowser> {
+        None
+    }
+
+    /// Server introspection (slow log, clients, `INFO`); `None` when the
+    /// engine has none.
+    fn diagnostics(&self) -> Option<&dyn ServerDiagnostics> {
+        None
+    }
+
+    /// Server-side scripts and function libraries; `None` when the engine
+    /// has none.
+    fn scripting(&self) -> Option<&dyn ScriptRunner> {
+        None
+    }
+
     /// Test if the connection is valid
-    async fn test_connection(&self) -> Result<TestConnectionResult, String>;
+    async fn test_connection(&self) -> Result<TestConnectionResult, DriverError>;
 
     /// List all tables in the database
-    async fn list_tables(&self) -> Result<Vec<TableInfo>, String>;
+    async fn list_tables(&self) -> Result<Vec<TableInfo>, DriverError>;
 
     /// Build the exact CREATE TABLE statement without executing it.
-    fn preview_create_table(&self, _request: &CreateTableRequest) -> Result<String, String> {
-        Err("Creating tables is not supported for this database".to_string())
+    fn preview_create_table(&self, _request: &CreateTableRequest) -> Result<String, DriverError> {
+        Err("Creating tables is not supported for this database".into())
     }
 
     /// Create a table exactly once.
-    async fn create_table(&self, _request: &CreateTableRequest) -> Result<TableInfo, String> {
-        Err("Creating tables is not supported for this database".to_string())
+    async fn create_table(&self, _request: &CreateTableRequest) -> Result<TableInfo, DriverError> {
+        Err("Creating tables is not supported for this database".into())
     }
 
     /// Get paginated data from a table
@@ -229,21 +275,21 @@ pub trait DatabaseDriver: Send + Sync {
         filter: Option<TableFilter>,
         sort_column: Option<String>,
         sort_direction: Option<String>,
-    ) -> Result<TableDataResponse, String>;
+    ) -> Result<TableDataResponse, DriverError>;
 
     /// Get the structure of a table (columns, indexes, foreign keys)
     async fn get_table_structure(
         &self,
         schema: &str,
         table: &str,
-    ) -> Result<TableStructure, String>;
+    ) -> Result<TableStructure, DriverError>;
 
     /// Execute a raw SQL query
-    async fn execute_query(&self, query: &str) -> Result<QueryResult, String>;
+    async fn execute_query(&self, query: &str) -> Result<QueryResult, DriverError>;
 
-    async fn execute_mutation(&self, mutation: &MutationPlan) -> Result<QueryResult, String> {
+    async fn execute_mutation(&self, mutation: &MutationPlan) -> Result<QueryResult, DriverError> {
         if !mutation.values.is_empty() {
-            return Err("Bound mutations are not supported for this database".to_string());
+            return Err("Bound mutations are not supported for this database".into());
         }
         self.execute_query(&mutation.sql).await
     }
@@ -256,10 +302,10 @@ pub trait DatabaseDriver: Send + Sync {
     /// mutating pragmas, etc. are rejected by the engine itself. Drivers that
     /// cannot get an engine-level guarantee (e.g. Redis) fall back to a
     /// best-effort, subcommand-aware allowlist.
-    async fn execute_query_read_only(&self, query: &str) -> Result<QueryResult, String>;
+    async fn execute_query_read_only(&self, query: &str) -> Result<QueryResult, DriverError>;
 
     /// Get schema overview with all tables and their structures (columns, foreign keys, indexes)
-    async fn get_schema_overview(&self) -> Result<SchemaOverview, String>;
+    async fn get_schema_overview(&self) -> Result<SchemaOverview, DriverError>;
 
     /// Get a function definition by fully qualified identity signature.
     async fn get_function_definition(
@@ -267,13 +313,15 @@ pub trait DatabaseDriver: Send + Sync {
         _schema: &str,
         _name: &str,
         _identity_args: &str,
-    ) -> Result<FunctionDefinition, String> {
-        Err("Function definitions are not supported for this database".to_string())
+    ) -> Result<FunctionDefinition, DriverError> {
+        Err("Function definitions are not supported for this database".into())
     }
 
-    /// Downcast helper so callers can reach driver-specific methods (e.g. Redis
-    /// key browsing) on a pooled `Box<dyn DatabaseDriver>`.
-    fn as_any(&self) -> &dyn std::any::Any;
+    /// List the other databases on the same server that this connection's
+    /// credentials can open.
+    async fn list_databases(&self) -> Result<Vec<String>, DriverError> {
+        Err("Listing databases is not supported for this database".into())
+    }
 }
 
 /// Configuration for Postgres connections
@@ -285,6 +333,8 @@ pub struct PostgresConfig {
     pub username: String,
     pub password: String,
     pub ssl: bool,
+    pub session: SessionOptions,
+    pub limits: QueryLimits,
 }
 
 #[derive(Clone)]
@@ -296,17 +346,37 @@ pub struct MysqlConfig {
     pub username: String,
     pub password: String,
     pub ssl: bool,
+    pub session: SessionOptions,
+    pub limits: QueryLimits,
+}
+
+#[derive(Clone)]
+pub struct SqlServerConfig {
+    pub host: String,
+    pub port: i64,
+    pub database: String,
+    pub username: String,
+    pub password: String,
+    pub ssl: bool,
+    /// Accept the server's certificate without validating it, as SQL Server's
+    /// own clients do for self-signed development certificates.
+    pub trust_server_certificate: bool,
+    pub session: SessionOptions,
+    pub limits: QueryLimits,
 }
 
 /// Configuration for SQLite connections
 #[derive(Clone)]
 pub struct SqliteConfig {
     pub file_path: String,
+    pub session: SessionOptions,
+    pub limits: QueryLimits,
 }
 
 #[derive(Clone)]
 pub struct DuckDbConfig {
     pub file_path: String,
+    pub limits: QueryLimits,
 }
 
 pub use d1::D1Config;
@@ -320,12 +390,33 @@ pub struct RedisConfig {
     pub password: Option<String>,
     pub db: Option<i64>,
     pub tls: bool,
+    pub topology: RedisTopology,
+    pub limits: QueryLimits,
+}
+
+/// How a Redis connection reaches its data. `host`/`port` is always the first
+/// seed: a cluster node in cluster mode, a sentinel in sentinel mode.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub enum RedisTopology {
+    #[default]
+    Standalone,
+    /// Cluster mode with extra seed nodes besides `host`/`port`.
+    Cluster { nodes: Vec<(String, u16)> },
+    /// Sentinel mode: `master_name` is resolved through the sentinels, which
+    /// may carry their own credentials.
+    Sentinel {
+        master_name: String,
+        nodes: Vec<(String, u16)>,
+        sentinel_password: Option<String>,
+    },
 }
 
 // Re-export ClickHouse config from its module
 pub use clickhouse::{ClickhouseConfig, ClickhouseProtocol};
 
-/// Database type enum for dispatching
+/// The SQL dialects of the built-in engines. Connecting, browsing and
+/// querying go through the [driver registry](registry); this only decides how
+/// structured row changes, cell literals and filters are written.
 #[derive(Clone, Copy, Debug, PartialEq, Eq)]
 pub enum DatabaseType {
     Postgres,
@@ -336,38 +427,68 @@ pub enum DatabaseType {
     Redis,
     Clickhouse,
     D1,
+    SqlServer,
 }
 
 impl DatabaseType {
-    pub fn as_str(self) -> &'static str {
+    const ALL: [Self; 9] = [
+        Self::Postgres,
+        Self::Mysql,
+        Self::Mariadb,
+        Self::Sqlite,
+        Self::DuckDb,
+        Self::Redis,
+        Self::Clickhouse,
+        Self::D1,
+        Self::SqlServer,
+    ];
+
+    /// The built-in engine's entry in the [driver registry](registry).
+    pub fn descriptor(self) -> &'static registry::DriverDescriptor {
         match self {
-            Self::Postgres => "postgres",
-            Self::Mysql => "mysql",
-            Self::Mariadb => "mariadb",
-            Self::Sqlite => "sqlite",
-            Self::DuckDb => "duckdb",
-            Self::Redis => "redis",
-            Self::Clickhouse => "clickhouse",
-            Self::D1 => "d1",
+            Self::Postgres => &postgres::DESCRIPTOR,
+            Self::Mysql => &mysql::MYSQL_DESCRIPTOR,
+            Self::Mariadb => &mysql::MARIADB_DESCRIPTOR,
+            Self::Sqlite => &sqlite::DESCRIPTOR,
+            Self::DuckDb => &duckdb::DESCRIPTOR,
+            Self::Redis => &redis::DESCRIPTOR,
+            Self::Clickhouse => &clickhouse::DESCRIPTOR,
+            Self::D1 => &d1::DESCRIPTOR,
+            Self::SqlServer => &sqlserver::DESCRIPTOR,
         }
     }
 
+    pub fn as_str(self) -> &'static str {
+        self.descriptor().id
+    }
+
     pub fn default_port(self) -> i64 {
-        match self {
-            Self::Postgres | Self::Sqlite | Self::DuckDb => 5432,
-            Self::Mysql | Self::Mariadb => 3306,
-            Self::Redis => 6379,
-            Self::Clickhouse => 8123,
-            Self::D1 => 443,
-        }
+        self.descriptor().default_port.unwrap_or_default()
     }
 
     pub fn qualifies_tables_with_schema(self) -> bool {
-        !matches!(self, Self::Sqlite | Self::D1)
+        self.descriptor().schema_qualified
     }
 
     pub fn replays_failed_reads_after_reconnect(self) -> bool {
-        self != Self::D1
+        self.descriptor().replays_reads
+    }
+
+    /// The capabilities the engine's driver reports, available before a
+    /// connection is opened.
+    pub fn capabilities(self) -> DriverCapabilities {
+        self.descriptor().capabilities
+    }
+
+    /// Engines where one server hosts several databases reachable with the
+    /// same credentials, so a connection can open sessions on its siblings.
+    pub fn switches_databases(self) -> bool {
+        self.capabilities().multiple_databases
+    }
+
+    /// Wrap `query` so the engine describes its plan without running it.
+    pub fn explain_statement(self, query: &str) -> Result<String, String> {
+        self.descriptor().explain_statement(query)
     }
 }
 
@@ -375,17 +496,10 @@ impl TryFrom<&str> for DatabaseType {
     type Error = String;
 
     fn try_from(value: &str) -> Result<Self, Self::Error> {
-        match value.to_ascii_lowercase().as_str() {
-            "postgres" | "postgresql" => Ok(Self::Postgres),
-            "mysql" => Ok(Self::Mysql),
-            "mariadb" => Ok(Self::Mariadb),
-            "sqlite" | "sqlite3" => Ok(Self::Sqlite),
-            "duckdb" => Ok(Self::DuckDb),
-            "redis" => Ok(Self::Redis),
-            "clickhouse" => Ok(Self::Clickhouse),
-            "d1" | "cloudflare-d1" => Ok(Self::D1),
-            _ => Err(format!("Unsupported database type: {value}")),
-        }
+        Self::ALL
+            .into_iter()
+            .find(|engine| engine.descriptor().answers_to(value))
+            .ok_or_else(|| format!("Unsupported database type: {value}"))
     }
 }
 
@@ -409,6 +523,7 @@ impl TryFrom<DatabaseType> for MysqlFlavor {
 
 #[cfg(test)]
 mod database_type_tests {
+    use super::sql_policy::database_label;
     use super::{DatabaseType, MysqlFlavor};
 
     #[test]
@@ -435,6 +550,55 @@ mod database_type_tests {
         assert!(DatabaseType::Postgres.replays_failed_reads_after_reconnect());
     }
 
+    #[test]
+    fn explains_without_running_the_query() {
+        assert_eq!(
+            DatabaseType::Postgres.explain_statement(" SELECT 1; "),
+            Ok("EXPLAIN (FORMAT JSON) SELECT 1".to_string())
+        );
+        assert_eq!(
+            DatabaseType::D1.explain_statement("SELECT * FROM users"),
+            Ok("EXPLAIN QUERY PLAN SELECT * FROM users".to_string())
+        );
+        assert!(DatabaseType::Mysql.explain_statement(" ;").is_err());
+        assert!(DatabaseType::Redis.explain_statement("GET key").is_err());
+    }
+
+    #[test]
+    fn capabilities_agree_with_engine_behaviour_and_the_catalog() {
+        for engine in [
+            DatabaseType::Postgres,
+            DatabaseType::Mysql,
+            DatabaseType::Mariadb,
+            DatabaseType::Sqlite,
+            DatabaseType::DuckDb,
+            DatabaseType::Redis,
+            DatabaseType::Clickhouse,
+            DatabaseType::D1,
+            DatabaseType::SqlServer,
+        ] {
+            let capabilities = engine.capabilities();
+            assert_eq!(
+                capabilities.explain,
+                engine.explain_statement("SELECT 1").is_ok(),
+                "{engine:?}"
+            );
+            assert_eq!(
+                capabilities.key_value_browsing,
+                engine == DatabaseType::Redis
+            );
+        }
+        for engine in DatabaseType::ALL {
+            assert_eq!(
+                database_label(engine.as_str()),
+                Ok(engine.descriptor().label),
+                "{engine:?}"
+            );
+        }
+        assert!(DatabaseType::Mariadb.switches_databases());
+        assert!(!DatabaseType::Sqlite.switches_databases());
+    }
+
     #[test]
     fn mysql_flavor_rejects_unrelated_engines() {
         assert_eq!(
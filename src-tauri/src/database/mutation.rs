@@ -1,3 +1,4 @@
+use super::cell::{cell_write, CellWrite};
 use super::sql_policy::{escape_sql_identifier, format_sql_value, validate_raw_sql_value};
 use super::DatabaseType;
 use serde::Deserialize;
@@ -44,7 +45,7 @@ pub fn build_update(
         .collect::<Result<Vec<_>, String>>()?
         .join(", ");
     let where_clause =
-        build_where_clause(engine, primary_key_columns, primary_key_values, &mut values);
+        build_where_clause(engine, primary_key_columns, primary_key_values, &mut values)?;
 
     Ok(MutationPlan {
         sql: format!(
@@ -67,7 +68,7 @@ pub fn build_delete(
     validate_primary_key(primary_key_columns, primary_key_values)?;
     let mut values = Vec::new();
     let where_clause =
-        build_where_clause(engine, primary_key_columns, primary_key_values, &mut values);
+        build_where_clause(engine, primary_key_columns, primary_key_values, &mut values)?;
     Ok(MutationPlan {
         sql: format!(
             "DELETE FROM {} WHERE {}",
@@ -124,25 +125,23 @@ fn build_where_clause(
     columns: &[String],
     primary_key_values: &[Value],
     values: &mut Vec<Value>,
-) -> String {
-    columns
+) -> Result<String, String> {
+    Ok(columns
         .iter()
         .zip(primary_key_values)
         .map(|(column, value)| {
             let identifier = identifier(column, engine);
-            if parameterized(engine) {
-                if value.is_null() {
-                    format!("{identifier} IS NULL")
-                } else {
-                    values.push(value.clone());
-                    format!("{identifier} = ?")
-                }
+            if parameterized(engine) && value.is_null() {
+                Ok(format!("{identifier} IS NULL"))
             } else {
-                format!("{identifier} = {}", format_sql_value(value))
+                Ok(format!(
+                    "{identifier} = {}",
+                    cell_sql(value, engine, values)?
+                ))
             }
         })
-        .collect::<Vec<_>>()
-        .join(" AND ")
+        .collect::<Result<Vec<_>, String>>()?
+        .join(" AND "))
 }
 
 fn mutation_value(
@@ -158,23 +157,44 @@ fn mutation_value(
         validate_raw_sql_value(raw, engine.as_str())
             .map_err(|error| format!("Invalid raw SQL value: {error}"))?;
         Ok(raw.to_string())
-    } else if parameterized(engine) {
-        values.push(value.value.clone());
-        Ok("?".to_string())
     } else {
-        Ok(format_sql_value(&value.value))
+        cell_sql(&value.value, engine, values)
+    }
+}
+
+/// A cell as SQL: literals the engine needs verbatim are inlined, other
+/// values are bound or formatted.
+fn cell_sql(cell: &Value, engine: DatabaseType, values: &mut Vec<Value>) -> Result<String, String> {
+    match cell_write(cell, engine)? {
+        CellWrite::Literal(sql) => Ok(sql),
+        CellWrite::Value(value) if parameterized(engine) => {
+            values.push(value);
+            Ok(placeholder(engine, values.len()))
+        }
+        CellWrite::Value(value) => Ok(format_sql_value(&value)),
     }
 }
 
 fn parameterized(engine: DatabaseType) -> bool {
-    matches!(engine, DatabaseType::Mysql | DatabaseType::Mariadb)
+    matches!(
+        engine,
+        DatabaseType::Mysql | DatabaseType::Mariadb | DatabaseType::SqlServer
+    )
+}
+
+/// The marker for the `position`th bound value (counting from 1).
+fn placeholder(engine: DatabaseType, position: usize) -> String {
+    match engine {
+        DatabaseType::SqlServer => format!("@P{position}"),
+        _ => "?".to_string(),
+    }
 }
 
 fn identifier(value: &str, engine: DatabaseType) -> String {
-    if parameterized(engine) {
-        format!("`{}`", value.replace('`', "``"))
-    } else {
-        format!("\"{}\"", escape_sql_identifier(value))
+    match engine {
+        DatabaseType::Mysql | DatabaseType::Mariadb => format!("`{}`", value.replace('`', "``")),
+        DatabaseType::SqlServer => format!("[{}]", value.replace(']', "]]")),
+        _ => format!("\"{}\"", escape_sql_identifier(value)),
     }
 }
 
@@ -281,6 +301,91 @@ mod tests {
         assert!(plan.values.is_empty());
     }
 
+    #[test]
+    fn sql_server_mutations_number_their_parameters() {
+        let plan = build_update(
+            DatabaseType::SqlServer,
+            "dbo",
+            "orders",
+            &["id".to_string(), "region".to_string()],
+            &[json!(7), json!("eu")],
+            &[MutationValue {
+                column: "note]s".to_string(),
+                value: json!("Ada"),
+                is_raw_sql: false,
+            }],
+        )
+        .unwrap();
+
+        assert_eq!(
+            plan.sql,
+            "UPDATE [dbo].[orders] SET [note]]s] = @P1 WHERE [id] = @P2 AND [region] = @P3"
+        );
+        assert_eq!(plan.values, vec![json!("Ada"), json!(7), json!("eu")]);
+    }
+
+    #[test]
+    fn tagged_cells_are_written_back_without_loss() {
+        let plan = build_update(
+            DatabaseType::Mysql,
+            "app",
+            "files",
+            &["id".to_string()],
+            &[json!({"$type": "integer", "value": "9007199254740993"})],
+            &[
+                MutationValue {
+                    column: "price".to_string(),
+                    value: json!({"$type": "decimal", "value": "12.50"}),
+                    is_raw_sql: false,
+                },
+                MutationValue {
+                    column: "body".to_string(),
+                    value: json!({"$type": "binary", "value": "yv4=", "length": 2}),
+                    is_raw_sql: false,
+                },
+                MutationValue {
+                    column: "seen_at".to_string(),
+                    value: json!({"$type": "timestamp", "value": "2024-01-02 03:04:05"}),
+                    is_raw_sql: false,
+                },
+            ],
+        )
+        .unwrap();
+        assert_eq!(
+            plan.sql,
+            "UPDATE `app`.`files` SET `price` = 12.50, `body` = X'CAFE', `seen_at` = ? WHERE `id` = ?"
+        );
+        assert_eq!(
+            plan.values,
+            vec![json!("2024-01-02 03:04:05"), json!(9007199254740993_u64)]
+        );
+
+        let insert = build_insert(
+            DatabaseType::Postgres,
+            "public",
+            "files",
+            &[MutationValue {
+                column: "body".to_string(),
+                value: json!({"$type": "binary", "value": "yv4=", "length": 2}),
+                is_raw_sql: false,
+            }],
+        )
+        .unwrap();
+        assert_eq!(
+            insert.sql,
+            "INSERT INTO \"public\".\"files\" (\"body\") VALUES ('\\xCAFE')"
+        );
+
+        assert!(build_delete(
+            DatabaseType::Sqlite,
+            "main",
+            "files",
+            &["id".to_string()],
+            &[json!({"$type": "decimal", "value": "1; DROP TABLE files"})],
+        )
+        .is_err());
+    }
+
     #[test]
     fn mutation_values_deserialize_the_tauri_contract() {
         let value: MutationValue = serde_json::from_value(json!({
@@ -1,12 +1,15 @@
 use async_trait::async_trait;
+use futures_util::stream::BoxStream;
 use futures_util::{StreamExt, TryStreamExt};
 use serde_json::{json, Value};
-use sqlx::mysql::{MySqlConnectOptions, MySqlPoolOptions, MySqlSslMode};
-use sqlx::{Column, Row, TypeInfo};
+use sqlx::mysql::{MySqlConnectOptions, MySqlPoolOptions, MySqlRow, MySqlSslMode};
+use sqlx::{Column, Executor, Row, TypeInfo};
 use std::collections::BTreeMap;
 use std::sync::Arc;
 use tokio::sync::RwLock;
 
+use super::capabilities::{DriverCapabilities, ReadOnlyEnforcement};
+use super::cell;
 use super::create_table::{build_mariadb_create_table_sql, build_mysql_create_table_sql};
 use super::filter::{
     build_where_clause, classify_column_type, compile_filter, structured_expression,
@@ -14,13 +17,72 @@ use super::filter::{
 };
 use super::mutation::MutationPlan;
 use super::mysql_read_only::{query_is_safe, uses_text_protocol};
-use super::{query_returns_rows, DatabaseDriver, MysqlConfig, MysqlFlavor};
+use super::registry::{
+    DriverDescriptor, DriverOptions, SshSupport, DATABASE, HOST, PASSWORD, PORT, SSL, USERNAME,
+};
+use super::session::sql_setting_literal;
+use super::{
+    query_returns_rows, DatabaseDriver, DriverError, DriverErrorCategory, MysqlConfig, MysqlFlavor,
+};
 use crate::db::models::{
-    ColumnInfo, CreateTableRequest, ForeignKeyInfo, IndexInfo, QueryResult, SchemaOverview,
-    TableDataResponse, TableFilter, TableInfo, TableStructure, TableWithStructure,
+    ColumnInfo, CreateTableRequest, ForeignKeyInfo, IndexInfo, QueryResult, ResultColumn,
+    SchemaOverview, TableDataResponse, TableFilter, TableInfo, TableStructure, TableWithStructure,
     TestConnectionResult,
 };
 
+pub(crate) const CAPABILITIES: DriverCapabilities = DriverCapabilities {
+    transactions: true,
+    create_table: true,
+    alter_table: false,
+    functions: false,
+    read_only: ReadOnlyEnforcement::Engine,
+    cancellation: false,
+    streaming: true,
+    multiple_databases: true,
+    key_value_browsing: false,
+    explain: true,
+    row_editing: true,
+};
+
+pub(crate) const MYSQL_DESCRIPTOR: DriverDescriptor = DriverDescriptor {
+    id: "mysql",
+    label: "MySQL",
+    aliases: &[],
+    default_port: Some(3306),
+    fields: &[HOST, PORT, DATABASE, USERNAME, PASSWORD, SSL],
+    ssh: SshSupport::Supported,
+    sessions: true,
+    capabilities: CAPABILITIES,
+    schema_qualified: true,
+    replays_reads: true,
+    explain: |query| Ok(format!("EXPLAIN FORMAT=JSON {query}")),
+    build: |options| build_driver(MysqlFlavor::Mysql, options),
+};
+
+pub(crate) const MARIADB_DESCRIPTOR: DriverDescriptor = DriverDescriptor {
+    id: "mariadb",
+    label: "MariaDB",
+    build: |options| build_driver(MysqlFlavor::Mariadb, options),
+    ..MYSQL_DESCRIPTOR
+};
+
+fn build_driver(
+    flavor: MysqlFlavor,
+    options: DriverOptions,
+) -> Result<Box<dyn DatabaseDriver>, String> {
+    Ok(Box::new(MysqlDriver::new(MysqlConfig {
+        flavor,
+        host: options.text("host").unwrap_or_default(),
+        port: options.integer("port").unwrap_or(3306),
+        database: options.text("database").unwrap_or_default(),
+        username: options.text("username").unwrap_or_default(),
+        password: options.text("password").unwrap_or_default(),
+        ssl: options.flag("ssl"),
+        session: options.session,
+        limits: options.limits,
+    })))
+}
+
 pub struct MysqlDriver {
     config: MysqlConfig,
     pool: Arc<RwLock<Option<sqlx::MySqlPool>>>,
@@ -58,22 +120,61 @@ impl MysqlDriver {
             }))
     }
 
+    /// `SET SESSION` statements for the query timeout and the configured
+    /// variables. Names are validated as identifiers because MySQL cannot bind
+    /// them.
+    fn session_statements(&self) -> Result<Vec<String>, String> {
+        self.config.session.validate_setting_names()?;
+        // MySQL only times out read-only SELECTs (in milliseconds); MariaDB
+        // covers every statement but takes seconds.
+        let mut statements: Vec<String> = self
+            .config
+            .limits
+            .query_timeout_ms()
+            .map(|timeout_ms| match self.config.flavor {
+                MysqlFlavor::Mysql => format!("SET SESSION max_execution_time = {timeout_ms}"),
+                MysqlFlavor::Mariadb => format!(
+                    "SET SESSION max_statement_time = {}",
+                    timeout_ms as f64 / 1000.0
+                ),
+            })
+            .into_iter()
+            .collect();
+        statements.extend(
+            self.config.session.settings.iter().map(|(name, value)| {
+                format!("SET SESSION {name} = {}", sql_setting_literal(value))
+            }),
+        );
+        statements.extend(self.config.session.on_connect_sql.clone());
+        Ok(statements)
+    }
+
     async fn create_pool(&self) -> Result<sqlx::MySqlPool, String> {
         let options = self.connect_options()?;
+        let statements = Arc::new(self.session_statements()?);
         match tokio::time::timeout(
-            std::time::Duration::from_secs(15),
+            self.config.limits.connect_timeout,
             MySqlPoolOptions::new()
                 .max_connections(5)
                 .acquire_timeout(std::time::Duration::from_secs(30))
                 .idle_timeout(std::time::Duration::from_secs(600))
                 .test_before_acquire(false)
+                .after_connect(move |conn, _meta| {
+                    let statements = statements.clone();
+                    Box::pin(async move {
+                        for statement in statements.iter() {
+                            conn.execute(statement.as_str()).await?;
+                        }
+                        Ok(())
+                    })
+                })
                 .connect_with(options),
         )
         .await
         {
             Ok(Ok(pool)) => Ok(pool),
             Ok(Err(error)) => Err(format!("Failed to connect to {}: {error}", self.label())),
-            Err(_) => Err("Connection timed out after 15 seconds".to_string()),
+            Err(_) => Err(self.config.limits.connect_timeout_message()),
         }
     }
 
@@ -90,6 +191,33 @@ impl MysqlDriver {
         Ok(pool)
     }
 
+    /// Collect a row stream under the connection's timeout and result caps.
+    async fn fetch_bounded<'e>(
+        &self,
+        rows: BoxStream<'e, Result<MySqlRow, sqlx::Error>>,
+        query: &str,
+        start: std::time::Instant,
+    ) -> Result<QueryResult, DriverError> {
+        let limits = &self.config.limits;
+        let rows = limits.run(
+            true,
+            rows.take(limits.fetch_limit()).try_collect::<Vec<_>>(),
+        );
+        match rows.await {
+            Ok(Ok(rows)) => {
+                let columns = rows.first().map(Self::result_columns).unwrap_or_default();
+                let (values, truncated) =
+                    limits.bound_rows(rows.iter().map(Self::row_values).collect());
+                Ok(QueryResult::from_table(columns, values, truncated, start))
+            }
+            Ok(Err(error)) => Ok(QueryResult::from_error(
+                DriverError::from(error).locate(query),
+                start,
+            )),
+            Err(timeout) => Ok(QueryResult::from_error(timeout, start)),
+        }
+    }
+
     fn bind_filter<'q>(
         mut query: sqlx::query::Query<'q, sqlx::MySql, sqlx::mysql::MySqlArguments>,
         filter: &'q CompiledFilter,
@@ -137,8 +265,9 @@ impl MysqlDriver {
         Ok(format!("{}.{}", Self::quote(schema), Self::quote(table)))
     }
 
-    fn row_to_json(row: &sqlx::mysql::MySqlRow) -> Value {
-        let mut object = serde_json::Map::new();
+    /// The row's cells in column order.
+    fn row_values(row: &sqlx::mysql::MySqlRow) -> Vec<Value> {
+        let mut values = Vec::with_capacity(row.len());
         for (index, column) in row.columns().iter().enumerate() {
             let type_name = column.type_info().name();
             let value = match type_name {
@@ -151,44 +280,51 @@ impl MysqlDriver {
                 "INT UNSIGNED" | "MEDIUMINT UNSIGNED" => {
                     row.try_get::<u32, _>(index).map(|value| json!(value))
                 }
-                "BIGINT" => row
-                    .try_get::<i64, _>(index)
-                    .map(|value| json!(value.to_string())),
-                "BIGINT UNSIGNED" => row
-                    .try_get::<u64, _>(index)
-                    .map(|value| json!(value.to_string())),
-                "FLOAT" => row.try_get::<f32, _>(index).map(|value| json!(value)),
-                "DOUBLE" => row.try_get::<f64, _>(index).map(|value| json!(value)),
+                "BIGINT" => row.try_get::<i64, _>(index).map(cell::integer),
+                "BIGINT UNSIGNED" => row.try_get::<u64, _>(index).map(cell::unsigned),
+                "FLOAT" => row.try_get::<f32, _>(index).map(cell::float32),
+                "DOUBLE" => row.try_get::<f64, _>(index).map(cell::float),
                 "YEAR" => row.try_get::<u16, _>(index).map(|value| json!(value)),
-                "BIT" => row
-                    .try_get::<u64, _>(index)
-                    .map(|value| json!(value.to_string())),
-                "DECIMAL" => row
-                    .try_get_unchecked::<String, _>(index)
-                    .map(|value| json!(value)),
+                "BIT" => row.try_get::<u64, _>(index).map(cell::unsigned),
+                "DECIMAL" => row.try_get_unchecked::<String, _>(index).map(cell::decimal),
                 "DATE" => row
                     .try_get::<chrono::NaiveDate, _>(index)
-                    .map(|value| json!(value.to_string())),
+                    .map(|value| cell::date(value.to_string())),
                 "DATETIME" | "TIMESTAMP" => row
                     .try_get::<chrono::NaiveDateTime, _>(index)
-                    .map(|value| json!(value.to_string())),
+                    .map(|value| cell::timestamp(value.to_string())),
                 "TIME" => row
                     .try_get::<sqlx::mysql::types::MySqlTime, _>(index)
-                    .map(|value| json!(value.to_string())),
-                "JSON" => row.try_get::<Value, _>(index),
-                "BINARY" | "VARBINARY" | "TINYBLOB" | "BLOB" | "MEDIUMBLOB" | "LONGBLOB"
-                | "GEOMETRY" => row
+                    .map(|value| cell::time(value.to_string())),
+                "JSON" => row.try_get::<Value, _>(index).map(cell::json),
+                "BINARY" | "VARBINARY" | "TINYBLOB" | "BLOB" | "MEDIUMBLOB" | "LONGBLOB" => row
                     .try_get::<Vec<u8>, _>(index)
-                    .map(|value| json!(format!("0x{}", hex::encode(value)))),
+                    .map(|value| cell::binary(&value)),
+                "GEOMETRY" => row
+                    .try_get::<Vec<u8>, _>(index)
+                    .map(|value| geometry_cell(&value)),
                 "ENUM" | "SET" => row
                     .try_get_unchecked::<String, _>(index)
                     .map(|value| json!(value)),
                 _ => row.try_get::<String, _>(index).map(|value| json!(value)),
             }
             .unwrap_or(Value::Null);
-            object.insert(column.name().to_string(), value);
+            values.push(value);
         }
-        Value::Object(object)
+        values
+    }
+
+    fn result_columns(row: &MySqlRow) -> Vec<ResultColumn> {
+        row.columns()
+            .iter()
+            .map(|column| {
+                cell::result_column(
+                    column.name(),
+                    column.type_info().name(),
+                    FilterDialect::Mysql,
+                )
+            })
+            .collect()
     }
 
     async fn primary_key_columns(&self, table: &str) -> Result<Vec<String>, String> {
@@ -204,13 +340,23 @@ impl MysqlDriver {
     }
 }
 
+/// MySQL stores geometry as a little-endian SRID followed by WKB.
+fn geometry_cell(bytes: &[u8]) -> Value {
+    let geometry = bytes.split_first_chunk::<4>().and_then(|(srid, wkb)| {
+        let (wkt, _) = cell::wkb_to_wkt(wkb)?;
+        let srid = u32::from_le_bytes(*srid);
+        Some(cell::geometry(wkt, (srid != 0).then_some(srid)))
+    });
+    geometry.unwrap_or_else(|| cell::binary(bytes))
+}
+
 #[async_trait]
 impl DatabaseDriver for MysqlDriver {
-    fn as_any(&self) -> &dyn std::any::Any {
-        self
+    fn capabilities(&self) -> DriverCapabilities {
+        CAPABILITIES
     }
 
-    async fn test_connection(&self) -> Result<TestConnectionResult, String> {
+    async fn test_connection(&self) -> Result<TestConnectionResult, DriverError> {
         match self.get_pool().await {
             Ok(pool) => match sqlx::query("SELECT 1").fetch_one(&pool).await {
                 Ok(_) => Ok(TestConnectionResult {
@@ -229,7 +375,7 @@ impl DatabaseDriver for MysqlDriver {
         }
     }
 
-    async fn list_tables(&self) -> Result<Vec<TableInfo>, String> {
+    async fn list_tables(&self) -> Result<Vec<TableInfo>, DriverError> {
         let pool = self.get_pool().await?;
         let rows = sqlx::query_as::<_, (String, String)>(
             "SELECT CAST(table_name AS CHAR), CAST(table_type AS CHAR) FROM information_schema.tables WHERE table_schema = ? ORDER BY table_name",
@@ -253,18 +399,28 @@ impl DatabaseDriver for MysqlDriver {
             .collect())
     }
 
-    fn preview_create_table(&self, request: &CreateTableRequest) -> Result<String, String> {
+    async fn list_databases(&self) -> Result<Vec<String>, DriverError> {
+        let pool = self.get_pool().await?;
+        sqlx::query_scalar::<_, String>(
+            "SELECT CAST(schema_name AS CHAR) FROM information_schema.schemata ORDER BY schema_name",
+        )
+        .fetch_all(&pool)
+        .await
+        .map_err(DriverError::from)
+    }
+
+    fn preview_create_table(&self, request: &CreateTableRequest) -> Result<String, DriverError> {
         if request.schema != self.config.database {
-            return Err("Tables can only be created in the selected database".to_string());
+            return Err("Tables can only be created in the selected database".into());
         }
         if self.config.flavor == MysqlFlavor::Mariadb {
-            build_mariadb_create_table_sql(request)
+            Ok(build_mariadb_create_table_sql(request)?)
         } else {
-            build_mysql_create_table_sql(request)
+            Ok(build_mysql_create_table_sql(request)?)
         }
     }
 
-    async fn create_table(&self, request: &CreateTableRequest) -> Result<TableInfo, String> {
+    async fn create_table(&self, request: &CreateTableRequest) -> Result<TableInfo, DriverError> {
         let sql = self.preview_create_table(request)?;
         sqlx::query(&sql)
             .execute(&self.get_pool().await?)
@@ -286,7 +442,7 @@ impl DatabaseDriver for MysqlDriver {
         filter: Option<TableFilter>,
         sort_column: Option<String>,
         sort_direction: Option<String>,
-    ) -> Result<TableDataResponse, String> {
+    ) -> Result<TableDataResponse, DriverError> {
         let pool = self.get_pool().await?;
         let table_ref = self.table_ref(schema, table)?;
         let compiled = if let Some(expression) = structured_expression(filter.as_ref()) {
@@ -350,8 +506,13 @@ impl DatabaseDriver for MysqlDriver {
                 .await
         }
         .map_err(|error| error.to_string())?;
+        let columns = rows.first().map(Self::result_columns).unwrap_or_default();
         Ok(TableDataResponse {
-            data: rows.iter().map(Self::row_to_json).collect(),
+            data: rows
+                .iter()
+                .map(|row| cell::row_object(&columns, &Self::row_values(row)))
+                .collect(),
+            columns,
             total,
             page,
             limit,
@@ -362,7 +523,7 @@ impl DatabaseDriver for MysqlDriver {
         &self,
         schema: &str,
         table: &str,
-    ) -> Result<TableStructure, String> {
+    ) -> Result<TableStructure, DriverError> {
         self.table_ref(schema, table)?;
         let pool = self.get_pool().await?;
         let column_rows = sqlx::query_as::<_, (String, String, String, Option<String>, String)>(
@@ -414,120 +575,73 @@ impl DatabaseDriver for MysqlDriver {
         })
     }
 
-    async fn execute_query(&self, query: &str) -> Result<QueryResult, String> {
+    async fn execute_query(&self, query: &str) -> Result<QueryResult, DriverError> {
         let start = std::time::Instant::now();
         let pool = self.get_pool().await?;
         if query_returns_rows(query) {
-            match sqlx::raw_sql(query)
-                .fetch(&pool)
-                .take(super::MAX_QUERY_RESULT_ROWS + 1)
-                .try_collect::<Vec<_>>()
+            self.fetch_bounded(sqlx::raw_sql(query).fetch(&pool), query, start)
                 .await
-            {
-                Ok(rows) => {
-                    let truncated = rows.len() > super::MAX_QUERY_RESULT_ROWS;
-                    Ok(QueryResult::from_rows(
-                        rows.iter()
-                            .take(super::MAX_QUERY_RESULT_ROWS)
-                            .map(Self::row_to_json)
-                            .collect(),
-                        truncated,
-                        start,
-                    ))
-                }
-                Err(error) => Ok(QueryResult::from_error(error.to_string(), start)),
-            }
         } else {
-            match sqlx::raw_sql(query).execute(&pool).await {
-                Ok(result) => Ok(QueryResult {
-                    data: vec![],
-                    row_count: result.rows_affected() as i64,
-                    truncated: false,
-                    rows_affected: Some(result.rows_affected()),
-                    error: None,
-                    time_taken_ms: Some(start.elapsed().as_millis()),
-                }),
-                Err(error) => Ok(QueryResult::from_error(error.to_string(), start)),
+            match self
+                .config
+                .limits
+                .run(true, sqlx::raw_sql(query).execute(&pool))
+                .await
+            {
+                Err(timeout) => Ok(QueryResult::from_error(timeout, start)),
+                Ok(Ok(result)) => Ok(QueryResult::from_affected(result.rows_affected(), start)),
+                Ok(Err(error)) => Ok(QueryResult::from_error(
+                    DriverError::from(error).locate(query),
+                    start,
+                )),
             }
         }
     }
 
-    async fn execute_mutation(&self, mutation: &MutationPlan) -> Result<QueryResult, String> {
+    async fn execute_mutation(&self, mutation: &MutationPlan) -> Result<QueryResult, DriverError> {
         let start = std::time::Instant::now();
         let pool = self.get_pool().await?;
         match Self::bind_values(sqlx::query(&mutation.sql), &mutation.values)?
             .execute(&pool)
             .await
         {
-            Ok(result) => Ok(QueryResult {
-                data: vec![],
-                row_count: result.rows_affected() as i64,
-                truncated: false,
-                rows_affected: Some(result.rows_affected()),
-                error: None,
-                time_taken_ms: Some(start.elapsed().as_millis()),
-            }),
-            Err(error) => Ok(QueryResult::from_error(error.to_string(), start)),
+            Ok(result) => Ok(QueryResult::from_affected(result.rows_affected(), start)),
+            Err(error) => Ok(QueryResult::from_error(
+                DriverError::from(error).locate(&mutation.sql),
+                start,
+            )),
         }
     }
 
-    async fn execute_query_read_only(&self, query: &str) -> Result<QueryResult, String> {
+    async fn execute_query_read_only(&self, query: &str) -> Result<QueryResult, DriverError> {
         let start = std::time::Instant::now();
         if !query_is_safe(query) {
             return Ok(QueryResult::from_error(
-                "Read-only mode only allows a single read statement".to_string(),
+                DriverError::new(
+                    DriverErrorCategory::Permission,
+                    "Read-only mode only allows a single read statement",
+                ),
                 start,
             ));
         }
         let pool = self.get_pool().await?;
         if uses_text_protocol(query) {
-            return match sqlx::raw_sql(query)
-                .fetch(&pool)
-                .take(super::MAX_QUERY_RESULT_ROWS + 1)
-                .try_collect::<Vec<_>>()
-                .await
-            {
-                Ok(rows) => {
-                    let truncated = rows.len() > super::MAX_QUERY_RESULT_ROWS;
-                    Ok(QueryResult::from_rows(
-                        rows.iter()
-                            .take(super::MAX_QUERY_RESULT_ROWS)
-                            .map(Self::row_to_json)
-                            .collect(),
-                        truncated,
-                        start,
-                    ))
-                }
-                Err(error) => Ok(QueryResult::from_error(error.to_string(), start)),
-            };
+            return self
+                .fetch_bounded(sqlx::raw_sql(query).fetch(&pool), query, start)
+                .await;
         }
         let mut transaction = match pool.begin_with("START TRANSACTION READ ONLY").await {
             Ok(transaction) => transaction,
-            Err(error) => return Ok(QueryResult::from_error(error.to_string(), start)),
+            Err(error) => return Ok(QueryResult::from_error(error, start)),
         };
-        let result = sqlx::query(query)
-            .fetch(&mut *transaction)
-            .take(super::MAX_QUERY_RESULT_ROWS + 1)
-            .try_collect::<Vec<_>>()
+        let result = self
+            .fetch_bounded(sqlx::query(query).fetch(&mut *transaction), query, start)
             .await;
         let _ = transaction.rollback().await;
-        match result {
-            Ok(rows) => {
-                let truncated = rows.len() > super::MAX_QUERY_RESULT_ROWS;
-                Ok(QueryResult::from_rows(
-                    rows.iter()
-                        .take(super::MAX_QUERY_RESULT_ROWS)
-                        .map(Self::row_to_json)
-                        .collect(),
-                    truncated,
-                    start,
-                ))
-            }
-            Err(error) => Ok(QueryResult::from_error(error.to_string(), start)),
-        }
+        result
     }
 
-    async fn get_schema_overview(&self) -> Result<SchemaOverview, String> {
+    async fn get_schema_overview(&self) -> Result<SchemaOverview, DriverError> {
         let mut tables = Vec::new();
         for table in self.list_tables().await? {
             let structure = self.get_table_structure(&table.schema, &table.name).await?;
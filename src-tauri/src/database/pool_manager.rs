@@ -14,11 +14,16 @@ use tokio::sync::{Mutex, RwLock};
 const IDLE_TIMEOUT: Duration = Duration::from_secs(30 * 60);
 /// How often the idle reaper checks for connections to evict.
 const IDLE_CHECK_INTERVAL: Duration = Duration::from_secs(5 * 60);
+/// Separates the connection UUID from the database name in the pool key of a
+/// session on another database of the same server.
+const DATABASE_SESSION_SEPARATOR: char = '/';
 
-use super::driver_factory::create_driver_with_ssh;
+use super::credentials::CredentialCache;
 pub use super::driver_factory::DriverConfig as ConnectionConfig;
+use super::driver_factory::{create_driver_through, create_driver_with_ssh};
 use super::mutation::MutationPlan;
-use super::{DatabaseDriver, DatabaseType};
+use super::registry::find_driver;
+use super::{DatabaseDriver, DriverError, DriverErrorCategory};
 use crate::db::models::{
     CreateTableRequest, FunctionDefinition, QueryResult, TableDataResponse, TableInfo,
     TableStructure, TestConnectionResult,
@@ -43,8 +48,9 @@ struct PoolEntry {
     /// read by the idle reaper without taking a write lock on the whole map.
     last_used: std::sync::Mutex<Instant>,
     last_error: Option<String>,
-    #[allow(dead_code)]
-    ssh_tunnel: Option<SshTunnel>,
+    /// Shared with the connection's database sessions, which reach the
+    /// server through the same tunnel.
+    ssh_tunnel: Option<Arc<SshTunnel>>,
 }
 
 fn should_keep_entry(entry: &PoolEntry) -> bool {
@@ -67,6 +73,8 @@ pub struct PoolManager {
     pools: Arc<RwLock<HashMap<String, PoolEntry>>>,
     /// Mutex per connection UUID to serialize connect/disconnect
     connect_locks: RwLock<HashMap<String, Arc<Mutex<()>>>>,
+    /// Output of connections' credential commands, reused until it expires.
+    credentials: CredentialCache,
 }
 
 impl Default for PoolManager {
@@ -80,6 +88,7 @@ impl PoolManager {
         Self {
             pools: Arc::new(RwLock::new(HashMap::new())),
             connect_locks: RwLock::new(HashMap::new()),
+            credentials: CredentialCache::default(),
         }
     }
 
@@ -96,7 +105,7 @@ impl PoolManager {
                 pools.retain(|uuid, entry| {
                     let keep = should_keep_entry(entry);
                     if !keep {
-                        println!("[Pool] Evicting idle connection {}", uuid);
+                        eprintln!("[Pool] Evicting idle connection {}", uuid);
                     }
                     keep
                 });
@@ -137,17 +146,102 @@ impl PoolManager {
             return Ok(());
         }
 
+        self.connect_saved(sqlite_pool, uuid).await?;
+        Ok(())
+    }
+
+    /// Pool key for a session on `database`, another database on the server
+    /// of connection `uuid`. Every pooled operation accepts it in place of a
+    /// connection UUID.
+    pub fn database_session_key(uuid: &str, database: &str) -> String {
+        format!("{uuid}{DATABASE_SESSION_SEPARATOR}{database}")
+    }
+
+    /// The saved connection a pool key belongs to.
+    pub fn connection_uuid(key: &str) -> &str {
+        key.split_once(DATABASE_SESSION_SEPARATOR)
+            .map_or(key, |(uuid, _)| uuid)
+    }
+
+    /// (Re)connect `key` from its saved connection. Callers hold the key's
+    /// connect lock.
+    pub(crate) async fn connect_saved(
+        &self,
+        sqlite_pool: &sqlx::SqlitePool,
+        key: &str,
+    ) -> Result<Arc<Box<dyn DatabaseDriver>>, String> {
+        match key.split_once(DATABASE_SESSION_SEPARATOR) {
+            Some((uuid, database)) => {
+                self.connect_database(sqlite_pool, key, uuid, database)
+                    .await
+            }
+            None => self.connect_from_store(sqlite_pool, key).await,
+        }
+    }
+
+    async fn connect_from_store(
+        &self,
+        sqlite_pool: &sqlx::SqlitePool,
+        uuid: &str,
+    ) -> Result<Arc<Box<dyn DatabaseDriver>>, String> {
         crate::docker::ensure_created_connection_running(sqlite_pool, uuid).await?;
         let config = crate::database::utils::get_connection_config(sqlite_pool, uuid).await?;
-        self.connect(uuid, config).await?;
-        Ok(())
+        self.connect(uuid, config).await
+    }
+
+    /// Open a session on `database` that reuses the resolved credentials and
+    /// SSH tunnel of connection `uuid`, connecting that first if needed.
+    async fn connect_database(
+        &self,
+        sqlite_pool: &sqlx::SqlitePool,
+        key: &str,
+        uuid: &str,
+        database: &str,
+    ) -> Result<Arc<Box<dyn DatabaseDriver>>, String> {
+        if database.trim().is_empty() {
+            return Err("Database name is required".to_string());
+        }
+        {
+            let lock = self.get_connect_lock(uuid).await;
+            let _guard = lock.lock().await;
+            if self.get_cached(uuid).await.is_none() {
+                self.connect_from_store(sqlite_pool, uuid).await?;
+            }
+        }
+
+        let (mut config, ssh_tunnel) = {
+            let pools = self.pools.read().await;
+            let entry = pools
+                .get(uuid)
+                .ok_or_else(|| "Connection not found. Please connect first.".to_string())?;
+            (entry.config.clone(), entry.ssh_tunnel.clone())
+        };
+        let engine = find_driver(&config.db_type)?;
+        if !engine.capabilities.multiple_databases {
+            return Err(format!(
+                "Switching databases is not supported for {}",
+                engine.id
+            ));
+        }
+        // The password was already resolved for the parent connection; the
+        // command is kept so the session can fetch a fresh one when it expires.
+        config.database = Some(database.to_string());
+
+        let driver = create_driver_through(&config, ssh_tunnel.as_deref())?;
+        self.store_tested(key, Arc::new(driver), config, ssh_tunnel)
+            .await
+            .map_err(String::from)
     }
 
-    /// Explicitly connect (or reconnect) a connection
+    /// Explicitly connect (or reconnect) a connection.
+    ///
+    /// Connections with a credential command get their password from it first.
+    /// If the engine rejects a cached credential, the cache is dropped and the
+    /// connect is retried once with fresh command output.
     pub async fn connect(
         &self,
         uuid: &str,
-        config: ConnectionConfig,
+        mut config: ConnectionConfig,
     ) -> Result<Arc<Box<dyn DatabaseDriver>>, String> {
         // Update status to reconnecting if entry exists
         {
@@ -157,10 +251,53 @@ impl PoolManager {
             }
         }
 
+        let Some(command) = config.credential_command.clone() else {
+            return self
+                .connect_with_config(uuid, config)
+                .await
+                .map_err(String::from);
+        };
+        let (secret, cached) = self.credentials.resolve(uuid, &command).await?;
+        config.password = Some(secret);
+        match self.connect_with_config(uuid, config.clone()).await {
+            Err(error) if error.category == DriverErrorCategory::Auth => {
+                self.credentials.invalidate(uuid);
+                if !cached {
+                    return Err(error.into());
+                }
+                eprintln!(
+                    "[Pool] Cached credential for {} was rejected, refreshing",
+                    uuid
+                );
+                let (secret, _) = self.credentials.resolve(uuid, &command).await?;
+                config.password = Some(secret);
+                self.connect_with_config(uuid, config)
+                    .await
+                    .map_err(String::from)
+            }
+            result => result.map_err(String::from),
+        }
+    }
+
+    async fn connect_with_config(
+        &self,
+        uuid: &str,
+        config: ConnectionConfig,
+    ) -> Result<Arc<Box<dyn DatabaseDriver>>, DriverError> {
         // Create new driver (with optional SSH tunnel)
         let (driver, ssh_tunnel) = create_driver_with_ssh(&config).await?;
-        let driver = Arc::new(driver);
+        self.store_tested(uuid, Arc::new(driver), config, ssh_tunnel.map(Arc::new))
+            .await
+    }
 
+    /// Test a new driver and store it under `key`, replacing any old entry.
+    async fn store_tested(
+        &self,
+        key: &str,
+        driver: Arc<Box<dyn DatabaseDriver>>,
+        config: ConnectionConfig,
+        ssh_tunnel: Option<Arc<SshTunnel>>,
+    ) -> Result<Arc<Box<dyn DatabaseDriver>>, DriverError> {
         // Test the connection
         let test_result = driver.test_connection().await?;
 
@@ -186,13 +323,13 @@ impl PoolManager {
         // Store in pool
         {
             let mut pools = self.pools.write().await;
-            pools.insert(uuid.to_string(), entry);
+            pools.insert(key.to_string(), entry);
         }
 
         if status == ConnectionStatus::Connected {
             Ok(driver)
         } else {
-            Err(test_result.message)
+            Err(DriverError::from_message(test_result.message))
         }
     }
 
@@ -203,9 +340,15 @@ impl PoolManager {
         self.disconnect_locked(uuid).await;
     }
 
-    pub(crate) async fn disconnect_locked(&self, uuid: &str) {
+    /// Remove `key` from the pool. Disconnecting a connection also closes the
+    /// sessions it opened on other databases.
+    pub(crate) async fn disconnect_locked(&self, key: &str) {
         let mut pools = self.pools.write().await;
-        pools.remove(uuid);
+        pools.remove(key);
+        if !key.contains(DATABASE_SESSION_SEPARATOR) {
+            let prefix = format!("{key}{DATABASE_SESSION_SEPARATOR}");
+            pools.retain(|pooled_key, _| !pooled_key.starts_with(&prefix));
+        }
     }
 
     /// Get the current status of a connection
@@ -282,28 +425,140 @@ impl PoolManager {
     pub async fn allows_reconnect_retry(&self, uuid: &str) -> bool {
         self.get_config(uuid)
             .await
-            .and_then(|config| DatabaseType::try_from(config.db_type.as_str()).ok())
-            .is_some_and(DatabaseType::replays_failed_reads_after_reconnect)
+            .and_then(|config| find_driver(&config.db_type).ok())
+            .is_some_and(|engine| engine.replays_reads)
+    }
+
+    /// The pooled driver for `uuid`, or a connection error if there is none.
+    async fn connected_driver(
+        &self,
+        uuid: &str,
+    ) -> Result<Arc<Box<dyn DatabaseDriver>>, DriverError> {
+        self.get_cached(uuid).await.ok_or_else(|| {
+            DriverError::new(
+                DriverErrorCategory::Connection,
+                "Connection not found. Please connect first.",
+            )
+        })
+    }
+
+    /// Run `operation` on the pooled driver for `key`.
+    ///
+    /// Pools open physical connections long after connect, so a short-lived
+    /// password from a credential command can expire in between. When the
+    /// engine rejects it, the cached output is dropped, the driver rebuilt
+    /// with a fresh secret and the operation run once more; a rejected login
+    /// means the statement never reached the server.
+    async fn with_driver<T, F, Fut>(&self, key: &str, operation: F) -> Result<T, DriverError>
+    where
+        F: Fn(Arc<Box<dyn DatabaseDriver>>) -> Fut,
+        Fut: std::future::Future<Output = Result<T, DriverError>>,
+    {
+        self.retry_rejected_login(key, operation, |_| false).await
+    }
+
+    /// [`Self::with_driver`] for statements, whose drivers report a failed
+    /// login inside the result rather than as an error.
+    async fn with_query_driver<F, Fut>(
+        &self,
+        key: &str,
+        operation: F,
+    ) -> Result<QueryResult, DriverError>
+    where
+        F: Fn(Arc<Box<dyn DatabaseDriver>>) -> Fut,
+        Fut: std::future::Future<Output = Result<QueryResult, DriverError>>,
+    {
+        self.retry_rejected_login(key, operation, |result: &QueryResult| {
+            result
+                .error_details
+                .as_ref()
+                .is_some_and(|error| error.category == DriverErrorCategory::Auth)
+        })
+        .await
+    }
+
+    async fn retry_rejected_login<T, F, Fut>(
+        &self,
+        key: &str,
+        operation: F,
+        rejected: fn(&T) -> bool,
+    ) -> Result<T, DriverError>
+    where
+        F: Fn(Arc<Box<dyn DatabaseDriver>>) -> Fut,
+        Fut: std::future::Future<Output = Result<T, DriverError>>,
+    {
+        let driver = self.connected_driver(key).await?;
+        let outcome = operation(driver).await;
+        let login_rejected = match &outcome {
+            Ok(value) => rejected(value),
+            Err(error) => error.category == DriverErrorCategory::Auth,
+        };
+        if !login_rejected || !self.uses_credential_command(key).await {
+            return outcome;
+        }
+        eprintln!(
+            "[Pool] Credential for {} was rejected, refreshing",
+            Self::connection_uuid(key)
+        );
+        let driver = self.refresh_credentials(key).await?;
+        operation(driver).await
+    }
+
+    async fn uses_credential_command(&self, key: &str) -> bool {
+        self.get_config(key)
+            .await
+            .is_some_and(|config| config.credential_command.is_some())
+    }
+
+    /// Rebuild the driver for `key` with fresh credential command output,
+    /// keeping its SSH tunnel.
+    async fn refresh_credentials(
+        &self,
+        key: &str,
+    ) -> Result<Arc<Box<dyn DatabaseDriver>>, DriverError> {
+        let lock = self.get_connect_lock(key).await;
+        let _guard = lock.lock().await;
+        let (mut config, ssh_tunnel) = {
+            let pools = self.pools.read().await;
+            let entry = pools.get(key).ok_or_else(|| {
+                DriverError::new(
+                    DriverErrorCategory::Connection,
+                    "Connection not found. Please connect first.",
+                )
+            })?;
+            (entry.config.clone(), entry.ssh_tunnel.clone())
+        };
+        let Some(command) = config.credential_command.clone() else {
+            return self.connected_driver(key).await;
+        };
+        // Sessions on other databases share the connection's credentials.
+        let uuid = Self::connection_uuid(key);
+        self.credentials.invalidate(uuid);
+        let (secret, _) = self.credentials.resolve(uuid, &command).await?;
+        config.password = Some(secret);
+        let driver = create_driver_through(&config, ssh_tunnel.as_deref())?;
+        self.store_tested(key, Arc::new(driver), config, ssh_tunnel)
+            .await
     }
 
     /// List tables using the pooled connection
-    pub async fn list_tables(&self, uuid: &str) -> Result<Vec<TableInfo>, String> {
-        let driver = self
-            .get_cached(uuid)
+    pub async fn list_tables(&self, uuid: &str) -> Result<Vec<TableInfo>, DriverError> {
+        self.with_driver(uuid, |driver| async move { driver.list_tables().await })
+            .await
+    }
+
+    /// List the databases on the pooled connection's server
+    pub async fn list_databases(&self, uuid: &str) -> Result<Vec<String>, DriverError> {
+        self.with_driver(uuid, |driver| async move { driver.list_databases().await })
             .await
-            .ok_or_else(|| "Connection not found. Please connect first.".to_string())?;
-        driver.list_tables().await
     }
 
     pub async fn preview_create_table(
         &self,
         uuid: &str,
         request: &CreateTableRequest,
-    ) -> Result<String, String> {
-        let driver = self
-            .get_cached(uuid)
-            .await
-            .ok_or_else(|| "Connection not found. Please connect first.".to_string())?;
+    ) -> Result<String, DriverError> {
+        let driver = self.connected_driver(uuid).await?;
         driver.preview_create_table(request)
     }
 
@@ -311,12 +566,12 @@ impl PoolManager {
         &self,
         uuid: &str,
         request: &CreateTableRequest,
-    ) -> Result<TableInfo, String> {
-        let driver = self
-            .get_cached(uuid)
-            .await
-            .ok_or_else(|| "Connection not found. Please connect first.".to_string())?;
-        driver.create_table(request).await
+    ) -> Result<TableInfo, DriverError> {
+        self.with_driver(
+            uuid,
+            |driver| async move { driver.create_table(request).await },
+        )
+        .await
     }
 
     /// Get table data using the pooled connection
@@ -330,22 +585,26 @@ impl PoolManager {
         filter: Option<crate::db::models::TableFilter>,
         sort_column: Option<String>,
         sort_direction: Option<String>,
-    ) -> Result<TableDataResponse, String> {
-        let driver = self
-            .get_cached(uuid)
-            .await
-            .ok_or_else(|| "Connection not found. Please connect first.".to_string())?;
-        driver
-            .get_table_data(
-                schema,
-                table,
-                page,
-                limit,
-                filter,
-                sort_column,
-                sort_direction,
-            )
-            .await
+    ) -> Result<TableDataResponse, DriverError> {
+        self.with_driver(uuid, |driver| {
+            let filter = filter.clone();
+            let sort_column = sort_column.clone();
+            let sort_direction = sort_direction.clone();
+            async move {
+                driver
+                    .get_table_data(
+                        schema,
+                        table,
+                        page,
+                        limit,
+                        filter,
+                        sort_column,
+                        sort_direction,
+                    )
+                    .await
+            }
+        })
+        .await
     }
 
     /// Get table structure using the pooled connection
@@ -354,33 +613,31 @@ impl PoolManager {
         uuid: &str,
         schema: &str,
         table: &str,
-    ) -> Result<TableStructure, String> {
-        let driver = self
-            .get_cached(uuid)
-            .await
-            .ok_or_else(|| "Connection not found. Please connect first.".to_string())?;
-        driver.get_table_structure(schema, table).await
+    ) -> Result<TableStructure, DriverError> {
+        self.with_driver(uuid, |driver| async move {
+            driver.get_table_structure(schema, table).await
+        })
+        .await
     }
 
     /// Execute query using the pooled connection
-    pub async fn execute_query(&self, uuid: &str, query: &str) -> Result<QueryResult, String> {
-        let driver = self
-            .get_cached(uuid)
-            .await
-            .ok_or_else(|| "Connection not found. Please connect first.".to_string())?;
-        driver.execute_query(query).await
+    pub async fn execute_query(&self, uuid: &str, query: &str) -> Result<QueryResult, DriverError> {
+        self.with_query_driver(
+            uuid,
+            |driver| async move { driver.execute_query(query).await },
+        )
+        .await
     }
 
     pub async fn execute_mutation(
         &self,
         uuid: &str,
         mutation: &MutationPlan,
-    ) -> Result<QueryResult, String> {
-        let driver = self
-            .get_cached(uuid)
-            .await
-            .ok_or_else(|| "Connection not found. Please connect first.".to_string())?;
-        driver.execute_mutation(mutation).await
+    ) -> Result<QueryResult, DriverError> {
+        self.with_query_driver(uuid, |driver| async move {
+            driver.execute_mutation(mutation).await
+        })
+        .await
     }
 
     /// Execute a query with read-only enforcement (engine-enforced where possible).
@@ -388,25 +645,23 @@ impl PoolManager {
         &self,
         uuid: &str,
         query: &str,
-    ) -> Result<QueryResult, String> {
-        let driver = self
-            .get_cached(uuid)
-            .await
-            .ok_or_else(|| "Connection not found. Please connect first.".to_string())?;
-        driver.execute_query_read_only(query).await
+    ) -> Result<QueryResult, DriverError> {
+        self.with_query_driver(uuid, |driver| async move {
+            driver.execute_query_read_only(query).await
+        })
+        .await
     }
 
     /// Get schema overview using the pooled connection
     pub async fn get_schema_overview(
         &self,
         uuid: &str,
-    ) -> Result<crate::db::models::SchemaOverview, String> {
-        let driver = self
-            .get_cached(uuid)
-            .await
-            .ok_or_else(|| "Connection not found. Please connect first.".to_string())?;
-
-        driver.get_schema_overview().await
+    ) -> Result<crate::db::models::SchemaOverview, DriverError> {
+        self.with_driver(
+            uuid,
+            |driver| async move { driver.get_schema_overview().await },
+        )
+        .await
     }
 
     /// Get a function definition using the pooled connection
@@ -416,15 +671,13 @@ impl PoolManager {
         schema: &str,
         name: &str,
         identity_args: &str,
-    ) -> Result<FunctionDefinition, String> {
-        let driver = self
-            .get_cached(uuid)
-            .await
-            .ok_or_else(|| "Connection not found. Please connect first.".to_string())?;
-
-        driver
-            .get_function_definition(schema, name, identity_args)
-            .await
+    ) -> Result<FunctionDefinition, DriverError> {
+        self.with_driver(uuid, |driver| async move {
+            driver
+                .get_function_definition(schema, name, identity_args)
+                .await
+        })
+        .await
     }
 }
 
@@ -432,7 +685,7 @@ impl PoolManager {
 mod tests {
     use super::*;
     use crate::database::redis::RedisDriver;
-    use crate::database::RedisConfig;
+    use crate::database::{QueryLimits, RedisConfig, RedisTopology};
 
     fn expired_entry() -> PoolEntry {
         let driver: Arc<Box<dyn DatabaseDriver>> =
@@ -443,6 +696,8 @@ mod tests {
                 password: None,
                 db: None,
                 tls: false,
+                topology: RedisTopology::Standalone,
+                limits: QueryLimits::default(),
             })));
 
         PoolEntry {
@@ -456,6 +711,7 @@ mod tests {
                 password: None,
                 ssl: Some(false),
                 file_path: None,
+                options: Default::default(),
                 ssh_enabled: false,
                 ssh_host: None,
                 ssh_port: None,
@@ -463,6 +719,10 @@ mod tests {
                 ssh_password: None,
                 ssh_key_path: None,
                 ssh_use_key: false,
+                on_connect_sql: None,
+                session_settings: Default::default(),
+                limits: QueryLimits::default(),
+                credential_command: None,
             },
             status: ConnectionStatus::Connected,
             last_used: std::sync::Mutex::new(Instant::now() - IDLE_TIMEOUT),
@@ -503,4 +763,58 @@ mod tests {
         drop(guard);
         disconnect.await.unwrap();
     }
+
+    #[tokio::test]
+    async fn disconnecting_a_connection_closes_its_database_sessions() {
+        let manager = PoolManager::new();
+        let tenant = PoolManager::database_session_key("connection-1", "tenant_a");
+        assert_eq!(tenant, "connection-1/tenant_a");
+        assert_eq!(PoolManager::connection_uuid(&tenant), "connection-1");
+        {
+            let mut pools = manager.pools.write().await;
+            for key in ["connection-1", tenant.as_str(), "connection-10"] {
+                pools.insert(key.to_string(), expired_entry());
+            }
+        }
+
+        manager.disconnect(&tenant).await;
+        assert!(manager.get_cached("connection-1").await.is_some());
+
+        manager
+            .pools
+            .write()
+            .await
+            .insert(tenant.clone(), expired_entry());
+        manager.disconnect("connection-1").await;
+        assert!(manager.get_cached(&tenant).await.is_none());
+        assert!(manager.get_cached("connection-10").await.is_some());
+    }
+
+    #[tokio::test]
+    async fn database_sessions_need_an_engine_with_sibling_databases() {
+        let store = sqlx::sqlite::SqlitePoolOptions::new()
+            .max_connections(1)
+            .connect("sqlite::memory:")
+            .await
+            .unwrap();
+        sqlx::migrate!("./migrations").run(&store).await.unwrap();
+        let dir = tempfile::tempdir().unwrap();
+        sqlx::query(
+            "INSERT INTO connections (uuid, type, name, host, port, database, username, password, db_type, file_path) VALUES ('local', 'sqlite', 'Local', '', 0, '', '', '', 'sqlite', ?)",
+        )
+        .bind(dir.path().join("local.db").to_string_lossy().to_string())
+        .execute(&store)
+        .await
+        .unwrap();
+
+        let manager = PoolManager::new();
+        let key = PoolManager::database_session_key("local", "other");
+        assert_eq!(
+            manager.ensure_connected(&store, &key).await.unwrap_err(),
+            "Switching databases is not supported for sqlite"
+        );
+        // The parent connection is opened on the way and stays usable.
+        assert!(manager.get_cached("local").await.is_some());
+        assert!(manager.get_cached(&key).await.is_none());
+    }
 }
@@ -1,29 +1,95 @@
 use async_trait::async_trait;
 use futures_util::{StreamExt, TryStreamExt};
-use serde_json::{json, Value};
-use sqlx::postgres::PgPoolOptions;
-use sqlx::{Column, Row, TypeInfo};
+use serde_json::Value;
+use sqlx::postgres::{PgPoolOptions, PgRow};
+use sqlx::{Executor, Row};
+use std::collections::HashMap;
 use std::sync::Arc;
 use tokio::sync::RwLock;
 
+use super::capabilities::{DriverCapabilities, ReadOnlyEnforcement};
+use super::cell;
 use super::create_table::build_postgres_create_table_sql;
 use super::filter::{
     build_where_clause, classify_column_type, compile_filter, structured_expression,
     CompiledFilter, FilterDialect, FilterValue,
 };
-use super::{query_returns_rows, DatabaseDriver, PostgresConfig};
+use super::postgres_values;
+use super::registry::{
+    DriverDescriptor, DriverOptions, SshSupport, DATABASE, HOST, PASSWORD, PORT, SSL, USERNAME,
+};
+use super::{
+    query_returns_rows, DatabaseDriver, DriverError, DriverErrorCategory, PostgresConfig,
+    QueryLimits,
+};
 use crate::database::queries::postgres::{
-    FUNCTION_DEFINITION_QUERY, FUNCTION_SUMMARIES_QUERY, SCHEMA_OVERVIEW_QUERY,
+    FUNCTION_DEFINITION_QUERY, FUNCTION_SUMMARIES_QUERY, RESULT_SOURCE_TABLE_QUERY,
+    SCHEMA_OVERVIEW_QUERY,
 };
 use crate::db::models::{
-    ColumnInfo, CreateTableRequest, ForeignKeyInfo, FunctionDefinition, FunctionSummary, IndexInfo,
-    QueryResult, SchemaOverview, TableDataResponse, TableFilter, TableInfo, TableStructure,
-    TableWithStructure, TestConnectionResult,
+    ColumnInfo, ColumnSource, CreateTableRequest, ForeignKeyInfo, FunctionDefinition,
+    FunctionSummary, IndexInfo, QueryResult, SchemaOverview, TableDataResponse, TableFilter,
+    TableInfo, TableStructure, TableWithStructure, TestConnectionResult,
 };
 
+pub(crate) const CAPABILITIES: DriverCapabilities = DriverCapabilities {
+    transactions: true,
+    create_table: true,
+    alter_table: false,
+    functions: true,
+    read_only: ReadOnlyEnforcement::Engine,
+    cancellation: true,
+    streaming: true,
+    multiple_databases: true,
+    key_value_browsing: false,
+    explain: true,
+    row_editing: true,
+};
+
+pub(crate) const DESCRIPTOR: DriverDescriptor = DriverDescriptor {
+    id: "postgres",
+    label: "PostgreSQL",
+    aliases: &["postgresql"],
+    default_port: Some(5432),
+    fields: &[HOST, PORT, DATABASE, USERNAME, PASSWORD, SSL],
+    ssh: SshSupport::Supported,
+    sessions: true,
+    capabilities: CAPABILITIES,
+    schema_qualified: true,
+    replays_reads: true,
+    explain: |query| Ok(format!("EXPLAIN (FORMAT JSON) {query}")),
+    build: build_driver,
+};
+
+fn build_driver(options: DriverOptions) -> Result<Box<dyn DatabaseDriver>, String> {
+    Ok(Box::new(PostgresDriver::new(PostgresConfig {
+        host: options.text("host").unwrap_or_default(),
+        port: options.integer("port").unwrap_or(5432),
+        database: options.text("database").unwrap_or_default(),
+        username: options.text("username").unwrap_or_default(),
+        password: options.text("password").unwrap_or_default(),
+        ssl: options.flag("ssl"),
+        session: options.session,
+        limits: options.limits,
+    })))
+}
+
 pub struct PostgresDriver {
     config: PostgresConfig,
     pool: Arc<RwLock<Option<sqlx::PgPool>>>,
+    /// Catalog details of tables query results were read from, by oid.
+    source_tables: RwLock<HashMap<i64, Arc<SourceTable>>>,
+}
+
+/// What [`PostgresDriver::describe_sources`] needs to know about a table.
+#[derive(Debug)]
+struct SourceTable {
+    schema: String,
+    table: String,
+    /// Number, name and NOT NULL flag of each live column.
+    columns: Vec<(i16, String, bool)>,
+    /// Primary key column names, in key order.
+    primary_key: Vec<String>,
 }
 
 impl PostgresDriver {
@@ -31,6 +97,7 @@ impl PostgresDriver {
         Self {
             config,
             pool: Arc::new(RwLock::new(None)),
+            source_tables: RwLock::new(HashMap::new()),
         }
     }
 
@@ -69,10 +136,11 @@ impl PostgresDriver {
 
     async fn create_pool(&self) -> Result<sqlx::PgPool, String> {
         let conn_str = self.build_connection_string();
+        let session = Arc::new(self.config.session.clone());
+        let statement_timeout = self.config.limits.query_timeout_ms();
 
-        // Use a 15 second timeout for connection (longer for SSH tunnel overhead)
         match tokio::time::timeout(
-            std::time::Duration::from_secs(15),
+            self.config.limits.connect_timeout,
             PgPoolOptions::new()
                 .max_connections(5)
                 // Keep one connection warm so the first query after an idle
@@ -85,13 +153,41 @@ impl PostgresDriver {
                 // connection that has gone stale gets replaced.
                 .max_lifetime(std::time::Duration::from_secs(1800))
                 .test_before_acquire(false)
+                // Runs on every new physical connection, so session state is
+                // restored after recycling and reconnects.
+                .after_connect(move |conn, _meta| {
+                    let session = session.clone();
+                    Box::pin(async move {
+                        // Saved settings run afterwards, so an explicit
+                        // `statement_timeout` setting still wins.
+                        if let Some(timeout_ms) = statement_timeout {
+                            conn.execute(
+                                sqlx::query("SELECT set_config('statement_timeout', $1, false)")
+                                    .bind(timeout_ms.to_string()),
+                            )
+                            .await?;
+                        }
+                        for (name, value) in &session.settings {
+                            conn.execute(
+                                sqlx::query("SELECT set_config($1, $2, false)")
+                                    .bind(name.as_str())
+                                    .bind(value.as_str()),
+                            )
+                            .await?;
+                        }
+                        if let Some(sql) = &session.on_connect_sql {
+                            conn.execute(sql.as_str()).await?;
+                        }
+                        Ok(())
+                    })
+                })
                 .connect(&conn_str),
         )
         .await
         {
             Ok(Ok(pool)) => Ok(pool),
             Ok(Err(e)) => Err(format!("Failed to connect to PostgreSQL: {}", e)),
-            Err(_) => Err("Connection timed out after 15 seconds".to_string()),
+            Err(_) => Err(self.config.limits.connect_timeout_message()),
         }
     }
 
@@ -125,43 +221,143 @@ impl PostgresDriver {
     async fn query_error_result(
         &self,
         error: sqlx::Error,
+        query: &str,
         start_time: std::time::Instant,
-    ) -> Result<QueryResult, String> {
-        let error_str = error.to_string();
-        let should_reset = error_str.contains("Connection reset by peer")
-            || error_str.contains("broken pipe")
-            || error_str.contains("connection closed")
-            || error_str.contains("server closed the connection");
-
-        if should_reset {
-            println!(
+    ) -> Result<QueryResult, DriverError> {
+        let error = DriverError::from(error).locate(query);
+        if error.category == DriverErrorCategory::Connection {
+            eprintln!(
                 "[Postgres] Connection error detected, resetting pool: {}",
-                error_str
+                error
             );
             let _ = self.reset_pool().await;
         }
 
-        Ok(QueryResult {
-            data: vec![],
-            row_count: 0,
-            truncated: false,
-            rows_affected: None,
-            error: Some(error_str),
-            time_taken_ms: Some(start_time.elapsed().as_millis()),
-        })
+        Ok(QueryResult::from_error(error, start_time))
     }
 
     async fn get_pool_with_retry(&self) -> Result<sqlx::PgPool, String> {
         match self.get_pool().await {
             Ok(pool) => Ok(pool),
             Err(e) => {
-                println!("[Postgres] Pool initialization failed: {}, resetting...", e);
+                eprintln!("[Postgres] Pool initialization failed: {}, resetting...", e);
                 self.reset_pool().await?;
                 self.get_pool().await
             }
         }
     }
 
+    /// A bounded result of the fetched rows.
+    fn query_result(
+        rows: &[PgRow],
+        limits: &QueryLimits,
+        start_time: std::time::Instant,
+    ) -> QueryResult {
+        let columns = rows
+            .first()
+            .map(postgres_values::result_columns)
+            .unwrap_or_default();
+        let (values, truncated) =
+            limits.bound_rows(rows.iter().map(postgres_values::row_values).collect());
+        QueryResult::from_table(columns, values, truncated, start_time)
+    }
+
+    /// Fill in the table column and nullability of each result column, and
+    /// the edit target when the result maps back to one table with its
+    /// primary key. Only results read from a single table are looked up, at
+    /// most once per table until a statement that returns no rows (such as
+    /// DDL) runs. A failed lookup leaves the result as is.
+    async fn describe_sources(
+        &self,
+        pool: &sqlx::PgPool,
+        rows: &[PgRow],
+        result: &mut QueryResult,
+    ) {
+        let Some(origins) = rows
+            .first()
+            .map(postgres_values::column_origins)
+            .and_then(|origins| origins.into_iter().collect::<Option<Vec<_>>>())
+        else {
+            return;
+        };
+        let Some(&(oid, _)) = origins.first() else {
+            return;
+        };
+        if origins.iter().any(|(table, _)| *table != oid) {
+            return;
+        }
+
+        let cached = self.source_tables.read().await.get(&oid).cloned();
+        let source = match cached {
+            // A column added since the lookup means the entry is stale.
+            Some(source)
+                if origins.iter().all(|(_, number)| {
+                    source.columns.iter().any(|(known, ..)| known == number)
+                }) =>
+            {
+                source
+            }
+            _ => {
+                let lookup = self.config.limits.run(true, Self::source_table(pool, oid));
+                let Ok(Ok(Some(source))) = lookup.await else {
+                    return;
+                };
+                let source = Arc::new(source);
+                self.source_tables
+                    .write()
+                    .await
+                    .insert(oid, Arc::clone(&source));
+                source
+            }
+        };
+
+        for (column, (_, number)) in result.columns.iter_mut().zip(&origins) {
+            let Some((_, name, not_null)) =
+                source.columns.iter().find(|(known, ..)| known == number)
+            else {
+                continue;
+            };
+            column.nullable = Some(!not_null);
+            column.source = Some(ColumnSource {
+                schema: source.schema.clone(),
+                table: source.table.clone(),
+                column: name.clone(),
+            });
+        }
+        result.edit_target = cell::edit_target(&result.columns, source.primary_key.clone());
+    }
+
+    async fn source_table(
+        pool: &sqlx::PgPool,
+        oid: i64,
+    ) -> Result<Option<SourceTable>, sqlx::Error> {
+        let rows = sqlx::query_as::<_, (String, String, i16, String, bool, Option<i32>)>(
+            RESULT_SOURCE_TABLE_QUERY,
+        )
+        .bind(oid)
+        .fetch_all(pool)
+        .await?;
+        let Some((schema, table, ..)) = rows.first() else {
+            return Ok(None);
+        };
+        let mut source = SourceTable {
+            schema: schema.clone(),
+            table: table.clone(),
+            columns: Vec::with_capacity(rows.len()),
+            primary_key: Vec::new(),
+        };
+        let mut key = Vec::new();
+        for (_, _, number, name, not_null, key_position) in rows {
+            if let Some(position) = key_position {
+                key.push((position, name.clone()));
+            }
+            source.columns.push((number, name, not_null));
+        }
+        key.sort_unstable();
+        source.primary_key = key.into_iter().map(|(_, name)| name).collect();
+        Ok(Some(source))
+    }
+
     async fn get_primary_key_columns(
         pool: &sqlx::PgPool,
         schema: &str,
@@ -189,85 +385,15 @@ impl PostgresDriver {
 
         Ok(rows.into_iter().map(|(column,)| column).collect())
     }
-
-    fn row_to_json(row: &sqlx::postgres::PgRow) -> Value {
-        let mut obj = serde_json::Map::new();
-        for (i, col) in row.columns().iter().enumerate() {
-            let type_name = col.type_info().name();
-            let value: Value = match type_name {
-                "INT2" => row
-                    .try_get::<i16, _>(i)
-                    .map(|v| json!(v))
-                    .unwrap_or(Value::Null),
-                "INT4" => row
-                    .try_get::<i32, _>(i)
-                    .map(|v| json!(v))
-                    .unwrap_or(Value::Null),
-                "INT8" => row
-                    .try_get::<i64, _>(i)
-                    .map(|v| json!(v))
-                    .unwrap_or(Value::Null),
-                "FLOAT4" => row
-                    .try_get::<f32, _>(i)
-                    .map(|v| json!(v))
-                    .unwrap_or(Value::Null),
-                "FLOAT8" | "NUMERIC" => row
-                    .try_get::<f64, _>(i)
-                    .map(|v| json!(v))
-                    .unwrap_or(Value::Null),
-                "BOOL" => row
-                    .try_get::<bool, _>(i)
-                    .map(|v| json!(v))
-                    .unwrap_or(Value::Null),
-                "TEXT" | "VARCHAR" | "CHAR" | "BPCHAR" | "NAME" => row
-                    .try_get::<String, _>(i)
-                    .map(|v| json!(v))
-                    .unwrap_or(Value::Null),
-                "UUID" => row
-                    .try_get::<uuid::Uuid, _>(i)
-                    .map(|v| json!(v.to_string()))
-                    .unwrap_or(Value::Null),
-                "TIMESTAMP" | "TIMESTAMPTZ" => row
-                    .try_get::<chrono::NaiveDateTime, _>(i)
-                    .map(|v| json!(v.to_string()))
-                    .or_else(|_| {
-                        row.try_get::<chrono::DateTime<chrono::Utc>, _>(i)
-                            .map(|v| json!(v.to_string()))
-                    })
-                    .unwrap_or(Value::Null),
-                "DATE" => row
-                    .try_get::<chrono::NaiveDate, _>(i)
-                    .map(|v| json!(v.to_string()))
-                    .unwrap_or(Value::Null),
-                "TIME" | "TIMETZ" => row
-                    .try_get::<chrono::NaiveTime, _>(i)
-                    .map(|v| json!(v.to_string()))
-                    .unwrap_or(Value::Null),
-                "JSON" | "JSONB" => row
-                    .try_get::<serde_json::Value, _>(i)
-                    .unwrap_or(Value::Null),
-                "BYTEA" => row
-                    .try_get::<Vec<u8>, _>(i)
-                    .map(|v| json!(format!("\\x{}", hex::encode(&v))))
-                    .unwrap_or(Value::Null),
-                _ => row
-                    .try_get::<String, _>(i)
-                    .map(|v| json!(v))
-                    .unwrap_or_else(|_| json!(format!("<{}>", type_name))),
-            };
-            obj.insert(col.name().to_string(), value);
-        }
-        Value::Object(obj)
-    }
 }
 
 #[async_trait]
 impl DatabaseDriver for PostgresDriver {
-    fn as_any(&self) -> &dyn std::any::Any {
-        self
+    fn capabilities(&self) -> DriverCapabilities {
+        CAPABILITIES
     }
 
-    async fn test_connection(&self) -> Result<TestConnectionResult, String> {
+    async fn test_connection(&self) -> Result<TestConnectionResult, DriverError> {
         match self.get_pool().await {
             Ok(pool) => {
                 let result = sqlx::query("SELECT 1").fetch_one(&pool).await;
@@ -289,7 +415,7 @@ impl DatabaseDriver for PostgresDriver {
         }
     }
 
-    async fn list_tables(&self) -> Result<Vec<TableInfo>, String> {
+    async fn list_tables(&self) -> Result<Vec<TableInfo>, DriverError> {
         let pool = self.get_pool_with_retry().await?;
 
         let tables = sqlx::query_as::<_, (String, String, String)>(
@@ -315,7 +441,7 @@ impl DatabaseDriver for PostgresDriver {
                 || error_str.contains("broken pipe")
                 || error_str.contains("connection closed")
             {
-                println!("[Postgres] Connection error in list_tables, will reset pool on next access: {}", error_str);
+                eprintln!("[Postgres] Connection error in list_tables, will reset pool on next access: {}", error_str);
             }
             error_str
         })?;
@@ -330,11 +456,28 @@ impl DatabaseDriver for PostgresDriver {
             .collect())
     }
 
-    fn preview_create_table(&self, request: &CreateTableRequest) -> Result<String, String> {
-        build_postgres_create_table_sql(request)
+    async fn list_databases(&self) -> Result<Vec<String>, DriverError> {
+        let pool = self.get_pool_with_retry().await?;
+        sqlx::query_scalar::<_, String>(
+            r#"
+            SELECT datname
+            FROM pg_database
+            WHERE datallowconn
+              AND NOT datistemplate
+              AND has_database_privilege(datname, 'CONNECT')
+            ORDER BY datname
+            "#,
+        )
+        .fetch_all(&pool)
+        .await
+        .map_err(DriverError::from)
+    }
+
+    fn preview_create_table(&self, request: &CreateTableRequest) -> Result<String, DriverError> {
+        Ok(build_postgres_create_table_sql(request)?)
     }
 
-    async fn create_table(&self, request: &CreateTableRequest) -> Result<TableInfo, String> {
+    async fn create_table(&self, request: &CreateTableRequest) -> Result<TableInfo, DriverError> {
         let sql = self.preview_create_table(request)?;
         let pool = self.get_pool_with_retry().await?;
         sqlx::query(&sql)
@@ -358,7 +501,7 @@ impl DatabaseDriver for PostgresDriver {
         filter: Option<TableFilter>,
         sort_column: Option<String>,
         sort_direction: Option<String>,
-    ) -> Result<TableDataResponse, String> {
+    ) -> Result<TableDataResponse, DriverError> {
         let pool = self.get_pool_with_retry().await?;
 
         let offset = (page - 1) * limit;
@@ -421,7 +564,7 @@ impl DatabaseDriver for PostgresDriver {
                     || error_str.contains("broken pipe")
                     || error_str.contains("connection closed")
                 {
-                    println!("[Postgres] Connection error in get_table_data (count), will reset pool on next access: {}", error_str);
+                    eprintln!("[Postgres] Connection error in get_table_data (count), will reset pool on next access: {}", error_str);
                 }
                 error_str
             })?;
@@ -445,15 +588,23 @@ impl DatabaseDriver for PostgresDriver {
                     || error_str.contains("broken pipe")
                     || error_str.contains("connection closed")
                 {
-                    println!("[Postgres] Connection error in get_table_data (data), will reset pool on next access: {}", error_str);
+                    eprintln!("[Postgres] Connection error in get_table_data (data), will reset pool on next access: {}", error_str);
                 }
                 error_str
             })?;
 
-        let data: Vec<Value> = rows.iter().map(Self::row_to_json).collect();
+        let columns = rows
+            .first()
+            .map(postgres_values::result_columns)
+            .unwrap_or_default();
+        let data = rows
+            .iter()
+            .map(|row| cell::row_object(&columns, &postgres_values::row_values(row)))
+            .collect();
 
         Ok(TableDataResponse {
             data,
+            columns,
             total,
             page,
             limit,
@@ -464,7 +615,7 @@ impl DatabaseDriver for PostgresDriver {
         &self,
         schema: &str,
         table: &str,
-    ) -> Result<TableStructure, String> {
+    ) -> Result<TableStructure, DriverError> {
         let pool = self.get_pool_with_retry().await?;
 
         let columns = sqlx::query_as::<_, (String, String, bool, Option<String>, bool)>(
@@ -499,7 +650,7 @@ impl DatabaseDriver for PostgresDriver {
                 || error_str.contains("broken pipe")
                 || error_str.contains("connection closed")
             {
-                println!("[Postgres] Connection error in get_table_structure, will reset pool on next access: {}", error_str);
+                eprintln!("[Postgres] Connection error in get_table_structure, will reset pool on next access: {}", error_str);
             }
             error_str
         })?;
@@ -530,7 +681,7 @@ impl DatabaseDriver for PostgresDriver {
                 || error_str.contains("broken pipe")
                 || error_str.contains("connection closed")
             {
-                println!("[Postgres] Connection error in get_table_structure, will reset pool on next access: {}", error_str);
+                eprintln!("[Postgres] Connection error in get_table_structure, will reset pool on next access: {}", error_str);
             }
             error_str
         })?;
@@ -562,7 +713,7 @@ impl DatabaseDriver for PostgresDriver {
                 || error_str.contains("broken pipe")
                 || error_str.contains("connection closed")
             {
-                println!("[Postgres] Connection error in get_table_structure, will reset pool on next access: {}", error_str);
+                eprintln!("[Postgres] Connection error in get_table_structure, will reset pool on next access: {}", error_str);
             }
             error_str
         })?;
@@ -604,7 +755,7 @@ impl DatabaseDriver for PostgresDriver {
         })
     }
 
-    async fn execute_query(&self, query: &str) -> Result<QueryResult, String> {
+    async fn execute_query(&self, query: &str) -> Result<QueryResult, DriverError> {
         let start_time = std::time::Instant::now();
         let pool = self.get_pool_with_retry().await?;
 
@@ -614,87 +765,82 @@ impl DatabaseDriver for PostgresDriver {
         // each run, so the prepared-statement cache never helps them — and over
         // an SSH tunnel each saved round-trip is a full network RTT. Values come
         // back in text format, which row_to_json decodes the same way.
+        let limits = &self.config.limits;
         if query_returns_rows(query) {
-            match sqlx::raw_sql(query)
-                .fetch(&pool)
-                .take(crate::database::MAX_QUERY_RESULT_ROWS + 1)
-                .try_collect::<Vec<_>>()
-                .await
-            {
-                Ok(rows) => {
-                    let truncated = rows.len() > crate::database::MAX_QUERY_RESULT_ROWS;
-                    let data: Vec<Value> = rows
-                        .iter()
-                        .take(crate::database::MAX_QUERY_RESULT_ROWS)
-                        .map(Self::row_to_json)
-                        .collect();
-                    let row_count = data.len() as i64;
-                    Ok(QueryResult {
-                        data,
-                        row_count,
-                        truncated,
-                        rows_affected: None,
-                        error: None,
-                        time_taken_ms: Some(start_time.elapsed().as_millis()),
-                    })
+            let rows = limits.run(
+                true,
+                sqlx::raw_sql(query)
+                    .fetch(&pool)
+                    .take(limits.fetch_limit())
+                    .try_collect::<Vec<_>>(),
+            );
+            match rows.await {
+                Err(timeout) => Ok(QueryResult::from_error(timeout, start_time)),
+                Ok(Ok(rows)) => {
+                    let mut result = Self::query_result(&rows, limits, start_time);
+                    self.describe_sources(&pool, &rows, &mut result).await;
+                    Ok(result)
                 }
-                Err(e) => self.query_error_result(e, start_time).await,
+                Ok(Err(e)) => self.query_error_result(e, query, start_time).await,
             }
         } else {
-            match sqlx::raw_sql(query).execute(&pool).await {
-                Ok(result) => {
-                    let rows_affected = result.rows_affected();
-                    Ok(QueryResult {
-                        data: vec![],
-                        row_count: rows_affected as i64,
-                        truncated: false,
-                        rows_affected: Some(rows_affected),
-                        error: None,
-                        time_taken_ms: Some(start_time.elapsed().as_millis()),
-                    })
+            match limits.run(true, sqlx::raw_sql(query).execute(&pool)).await {
+                Err(timeout) => Ok(QueryResult::from_error(timeout, start_time)),
+                Ok(Ok(result)) => {
+                    // The statement may have changed a table's columns or key.
+                    self.source_tables.write().await.clear();
+                    Ok(QueryResult::from_affected(
+                        result.rows_affected(),
+                        start_time,
+                    ))
                 }
-                Err(e) => self.query_error_result(e, start_time).await,
+                Ok(Err(e)) => self.query_error_result(e, query, start_time).await,
             }
         }
     }
 
-    async fn execute_query_read_only(&self, query: &str) -> Result<QueryResult, String> {
+    async fn execute_query_read_only(&self, query: &str) -> Result<QueryResult, DriverError> {
         let start_time = std::time::Instant::now();
         let pool = self.get_pool_with_retry().await?;
 
         // Run inside a READ ONLY transaction so the server rejects any write,
         // including writes hidden in CTEs or executed by `EXPLAIN ANALYZE`.
-        let mut tx = pool.begin().await.map_err(|e| e.to_string())?;
+        let mut tx = pool.begin().await?;
         if let Err(e) = sqlx::query("SET TRANSACTION READ ONLY")
             .execute(&mut *tx)
             .await
         {
-            return Ok(QueryResult::from_error(e.to_string(), start_time));
+            return Ok(QueryResult::from_error(e, start_time));
         }
 
-        let result = sqlx::query(query)
-            .fetch(&mut *tx)
-            .take(crate::database::MAX_QUERY_RESULT_ROWS + 1)
-            .try_collect::<Vec<_>>()
+        let limits = &self.config.limits;
+        let result = limits
+            .run(
+                true,
+                sqlx::query(query)
+                    .fetch(&mut *tx)
+                    .take(limits.fetch_limit())
+                    .try_collect::<Vec<_>>(),
+            )
             .await;
         // Nothing to persist in a read-only transaction; always roll back.
         let _ = tx.rollback().await;
 
         match result {
-            Ok(rows) => {
-                let truncated = rows.len() > crate::database::MAX_QUERY_RESULT_ROWS;
-                let data: Vec<Value> = rows
-                    .iter()
-                    .take(crate::database::MAX_QUERY_RESULT_ROWS)
-                    .map(Self::row_to_json)
-                    .collect();
-                Ok(QueryResult::from_rows(data, truncated, start_time))
+            Ok(Ok(rows)) => {
+                let mut result = Self::query_result(&rows, limits, start_time);
+                self.describe_sources(&pool, &rows, &mut result).await;
+                Ok(result)
             }
-            Err(e) => Ok(QueryResult::from_error(e.to_string(), start_time)),
+            Ok(Err(e)) => Ok(QueryResult::from_error(
+                DriverError::from(e).locate(query),
+                start_time,
+            )),
+            Err(timeout) => Ok(QueryResult::from_error(timeout, start_time)),
         }
     }
 
-    async fn get_schema_overview(&self) -> Result<SchemaOverview, String> {
+    async fn get_schema_overview(&self) -> Result<SchemaOverview, DriverError> {
         let pool = self.get_pool_with_retry().await?;
 
         let rows = sqlx::query(SCHEMA_OVERVIEW_QUERY)
@@ -706,7 +852,7 @@ impl DatabaseDriver for PostgresDriver {
                     || error_str.contains("broken pipe")
                     || error_str.contains("connection closed")
                 {
-                    println!(
+                    eprintln!(
                         "[Postgres] Connection error in get_schema_overview, will reset pool on next access: {}",
                         error_str
                     );
@@ -760,7 +906,7 @@ impl DatabaseDriver for PostgresDriver {
                     || error_str.contains("broken pipe")
                     || error_str.contains("connection closed")
                 {
-                    println!(
+                    eprintln!(
                         "[Postgres] Connection error in get_schema_overview functions query, will reset pool on next access: {}",
                         error_str
                     );
@@ -790,7 +936,7 @@ impl DatabaseDriver for PostgresDriver {
         schema: &str,
         name: &str,
         identity_args: &str,
-    ) -> Result<FunctionDefinition, String> {
+    ) -> Result<FunctionDefinition, DriverError> {
         let pool = self.get_pool_with_retry().await?;
 
         let row =
@@ -808,7 +954,7 @@ impl DatabaseDriver for PostgresDriver {
                     || error_str.contains("broken pipe")
                     || error_str.contains("connection closed")
                 {
-                    println!(
+                    eprintln!(
                         "[Postgres] Connection error in get_function_definition, will reset pool on next access: {}",
                         error_str
                     );
@@ -828,10 +974,9 @@ impl DatabaseDriver for PostgresDriver {
                     definition,
                 })
             }
-            None => Err(format!(
-                "Function not found: {}.{}({})",
-                schema, name, identity_args
-            )),
+            None => {
+                Err(format!("Function not found: {}.{}({})", schema, name, identity_args).into())
+            }
         }
     }
 }
@@ -0,0 +1,776 @@
+//! Postgres cells as tagged values. Console queries use the simple protocol
+//! and get text values; prepared statements get binary ones. Both decode to
+//! the same cells, binary values being rendered the way Postgres prints them.
+
+use super::cell;
+use super::filter::FilterDialect;
+use crate::db::models::ResultColumn;
+use chrono::{Duration, NaiveDate};
+use serde_json::Value;
+use sqlx::postgres::{PgRow, PgTypeInfo, PgTypeKind, PgValueFormat};
+use sqlx::{Column, Row, TypeInfo, ValueRef};
+
+/// Microseconds in a day.
+const DAY_MICROS: i64 = 86_400_000_000;
+
+/// How a column's values are laid out, domains resolved to their base type.
+#[derive(Debug, Clone, PartialEq)]
+enum CellType {
+    /// A type by the name sqlx reports, e.g. `INT4` or `geometry`.
+    Scalar(String),
+    Array(Box<CellType>),
+    Range(Box<CellType>),
+}
+
+impl CellType {
+    fn of(info: &PgTypeInfo) -> Self {
+        match info.kind() {
+            PgTypeKind::Domain(base) => Self::of(base),
+            PgTypeKind::Array(element) => Self::Array(Box::new(Self::of(element))),
+            PgTypeKind::Range(element) => Self::Range(Box::new(Self::of(element))),
+            PgTypeKind::Enum(_) => Self::Scalar("TEXT".to_string()),
+            _ => Self::Scalar(info.name().to_string()),
+        }
+    }
+}
+
+/// The row's cells in column order.
+pub(crate) fn row_values(row: &PgRow) -> Vec<Value> {
+    let mut values = Vec::with_capacity(row.len());
+    for (index, column) in row.columns().iter().enumerate() {
+        let value = match row.try_get_raw(index) {
+            Ok(raw) if !raw.is_null() => {
+                let format = raw.format();
+                raw.as_bytes().map_or(Value::Null, |bytes| {
+                    decode(&CellType::of(column.type_info()), format, bytes)
+                })
+            }
+            _ => Value::Null,
+        };
+        values.push(value);
+    }
+    values
+}
+
+pub(crate) fn result_columns(row: &PgRow) -> Vec<ResultColumn> {
+    row.columns()
+        .iter()
+        .map(|column| {
+            cell::result_column(
+                column.name(),
+                column.type_info().name(),
+                FilterDialect::Postgres,
+            )
+        })
+        .collect()
+}
+
+/// The `(table oid, column number)` each result column is read from, or
+/// `None` for computed columns.
+pub(crate) fn column_origins(row: &PgRow) -> Vec<Option<(i64, i16)>> {
+    row.columns()
+        .iter()
+        .map(|column| {
+            Some((
+                i64::from(column.relation_id()?.0),
+                column.relation_attribute_no()?,
+            ))
+        })
+        .collect()
+}
+
+fn decode(cell_type: &CellType, format: PgValueFormat, bytes: &[u8]) -> Value {
+    match (cell_type, format) {
+        (CellType::Scalar(name), PgValueFormat::Text) => match std::str::from_utf8(bytes) {
+            Ok(text) => scalar_from_text(name, text),
+            Err(_) => cell::binary(bytes),
+        },
+        (CellType::Scalar(name), PgValueFormat::Binary) => {
+            scalar_from_binary(name, bytes).unwrap_or_else(|| Value::String(format!("<{name}>")))
+        }
+        (CellType::Array(element), PgValueFormat::Text) => std::str::from_utf8(bytes)
+            .ok()
+            .and_then(parse_text_array)
+            .map(|items| text_array_cell(element, items))
+            .unwrap_or_else(|| Value::String(String::from_utf8_lossy(bytes).into_owned())),
+        (CellType::Array(element), PgValueFormat::Binary) => {
+            binary_array(element, bytes).unwrap_or(Value::Null)
+        }
+        (CellType::Range(_), PgValueFormat::Text) => {
+            cell::range(String::from_utf8_lossy(bytes).into_owned())
+        }
+        (CellType::Range(element), PgValueFormat::Binary) => binary_range(element, bytes)
+            .map(cell::range)
+            .unwrap_or(Value::Null),
+    }
+}
+
+fn scalar_from_text(name: &str, text: &str) -> Value {
+    match name {
+        "INT2" | "INT4" | "INT8" | "OID" => cell::integer_text(text),
+        "FLOAT4" | "FLOAT8" => text
+            .parse::<f64>()
+            .map_or_else(|_| Value::String(text.to_string()), cell::float),
+        "NUMERIC" => cell::decimal(text),
+        "BOOL" => Value::Bool(text == "t"),
+        "DATE" => cell::date(text),
+        "TIME" | "TIMETZ" => cell::time(text),
+        "TIMESTAMP" => cell::timestamp(text),
+        "TIMESTAMPTZ" => cell::timestamptz(text),
+        "INTERVAL" => cell::interval(text),
+        "UUID" => cell::uuid(text),
+        "JSON" | "JSONB" => {
+            serde_json::from_str(text).map_or_else(|_| Value::String(text.to_string()), cell::json)
+        }
+        "BYTEA" => text
+            .strip_prefix("\\x")
+            .and_then(|digits| hex::decode(digits).ok())
+            .map_or_else(
+                || Value::String(text.to_string()),
+                |bytes| cell::binary(&bytes),
+            ),
+        // PostGIS prints hex EWKB.
+        "geometry" | "geography" => hex::decode(text).map_or_else(
+            |_| Value::String(text.to_string()),
+            |bytes| cell::geometry_from_wkb(&bytes),
+        ),
+        name if name.ends_with("multirange") => cell::range(text),
+        _ => Value::String(text.to_string()),
+    }
+}
+
+fn scalar_from_binary(name: &str, bytes: &[u8]) -> Option<Value> {
+    let mut reader = Reader(bytes);
+    let value = match name {
+        "INT2" => cell::integer(reader.i16()?.into()),
+        "INT4" => cell::integer(reader.i32()?.into()),
+        "INT8" => cell::integer(reader.i64()?),
+        "OID" => cell::integer(reader.u32()?.into()),
+        "FLOAT4" => cell::float32(f32::from_bits(reader.u32()?)),
+        "FLOAT8" => cell::float(f64::from_bits(reader.i64()? as u64)),
+        "BOOL" => Value::Bool(*bytes.first()? != 0),
+        "NUMERIC" => cell::decimal(numeric_text(bytes)?),
+        "MONEY" => {
+            let cents = reader.i64()?;
+            let sign = if cents < 0 { "-" } else { "" };
+            let cents = cents.unsigned_abs();
+            cell::decimal(format!("{sign}{}.{:02}", cents / 100, cents % 100))
+        }
+        "DATE" => cell::date(date_text(reader.i32()?)),
+        "TIME" => cell::time(clock_text(reader.i64()?)),
+        "TIMETZ" => {
+            let micros = reader.i64()?;
+            // Stored as seconds west of UTC.
+            let offset = -reader.i32()?;
+            cell::time(format!("{}{}", clock_text(micros), offset_text(offset)))
+        }
+        "TIMESTAMP" => cell::timestamp(timestamp_text(reader.i64()?)),
+        "TIMESTAMPTZ" => {
+            let micros = reader.i64()?;
+            let text = timestamp_text(micros);
+            cell::timestamptz(if text.ends_with("infinity") {
+                text
+            } else {
+                format!("{text}+00")
+            })
+        }
+        "INTERVAL" => {
+            let micros = reader.i64()?;
+            let days = reader.i32()?;
+            let months = reader.i32()?;
+            cell::interval(interval_text(months, days, micros))
+        }
+        "UUID" => cell::uuid(uuid::Uuid::from_slice(bytes).ok()?.to_string()),
+        "JSON" => cell::json(serde_json::from_slice(bytes).ok()?),
+        // JSONB is prefixed with its format version.
+        "JSONB" => cell::json(serde_json::from_slice(bytes.get(1..)?).ok()?),
+        "BYTEA" => cell::binary(bytes),
+        "geometry" | "geography" => cell::geometry_from_wkb(bytes),
+        "INET" | "CIDR" => Value::String(inet_text(name == "CIDR", bytes)?),
+        "MACADDR" | "MACADDR8" => Value::String(
+            bytes
+                .iter()
+                .map(|byte| format!("{byte:02x}"))
+                .collect::<Vec<_>>()
+                .join(":"),
+        ),
+        "TEXT" | "VARCHAR" | "CHAR" | "\"CHAR\"" | "NAME" | "UNKNOWN" | "XML" | "JSONPATH"
+        | "citext" => Value::String(String::from_utf8(bytes.to_vec()).ok()?),
+        _ => return None,
+    };
+    Some(value)
+}
+
+/// Big-endian reads off the front of a binary value.
+struct Reader<'a>(&'a [u8]);
+
+impl Reader<'_> {
+    fn take<const N: usize>(&mut self) -> Option<[u8; N]> {
+        let (head, rest) = self.0.split_at_checked(N)?;
+        self.0 = rest;
+        head.try_into().ok()
+    }
+
+    fn i16(&mut self) -> Option<i16> {
+        self.take().map(i16::from_be_bytes)
+    }
+
+    fn u16(&mut self) -> Option<u16> {
+        self.take().map(u16::from_be_bytes)
+    }
+
+    fn i32(&mut self) -> Option<i32> {
+        self.take().map(i32::from_be_bytes)
+    }
+
+    fn u32(&mut self) -> Option<u32> {
+        self.take().map(u32::from_be_bytes)
+    }
+
+    fn i64(&mut self) -> Option<i64> {
+        self.take().map(i64::from_be_bytes)
+    }
+
+    /// A length-prefixed element; `None` inside for SQL NULL.
+    fn element(&mut self) -> Option<Option<&[u8]>> {
+        let length = self.i32()?;
+        if length < 0 {
+            return Some(None);
+        }
+        let (element, rest) = self.0.split_at_checked(length as usize)?;
+        self.0 = rest;
+        Some(Some(element))
+    }
+}
+
+/// Binary NUMERIC: base-10000 digits with a weight, sign and display scale.
+fn numeric_text(bytes: &[u8]) -> Option<String> {
+    let mut reader = Reader(bytes);
+    let count = reader.i16()?;
+    let weight = reader.i16()? as i32;
+    let sign = reader.u16()?;
+    let scale = reader.u16()? as usize;
+    let digits = (0..count)
+        .map(|_| reader.i16())
+        .collect::<Option<Vec<_>>>()?;
+    match sign {
+        0xC000 => return Some("NaN".to_string()),
+        0xD000 => return Some("Infinity".to_string()),
+        0xF000 => return Some("-Infinity".to_string()),
+        _ => {}
+    }
+    let digit = |index: i32| -> i16 {
+        usize::try_from(index)
+            .ok()
+            .and_then(|index| digits.get(index).copied())
+            .unwrap_or(0)
+    };
+
+    let mut text = String::new();
+    if sign == 0x4000 {
+        text.push('-');
+    }
+    if weight < 0 {
+        text.push('0');
+    } else {
+        text.push_str(&digit(0).to_string());
+        for index in 1..=weight {
+            text.push_str(&format!("{:04}", digit(index)));
+        }
+    }
+    if scale > 0 {
+        let mut fraction = String::new();
+        let mut index = weight + 1;
+        while fraction.len() < scale {
+            fraction.push_str(&format!("{:04}", digit(index)));
+            index += 1;
+        }
+        fraction.truncate(scale);
+        text.push('.');
+        text.push_str(&fraction);
+    }
+    Some(text)
+}
+
+/// Days since 2000-01-01, as `YYYY-MM-DD`.
+fn date_text(days: i32) -> String {
+    match days {
+        i32::MAX => "infinity".to_string(),
+        i32::MIN => "-infinity".to_string(),
+        days => epoch()
+            .checked_add_signed(Duration::days(days.into()))
+            .map_or_else(|| days.to_string(), |date| date.to_string()),
+    }
+}
+
+/// Microseconds since 2000-01-01 00:00:00.
+fn timestamp_text(micros: i64) -> String {
+    match micros {
+        i64::MAX => "infinity".to_string(),
+        i64::MIN => "-infinity".to_string(),
+        micros => {
+            let days = micros.div_euclid(DAY_MICROS);
+            match i32::try_from(days) {
+                Ok(days) => format!(
+                    "{} {}",
+                    date_text(days),
+                    clock_text(micros.rem_euclid(DAY_MICROS))
+                ),
+                Err(_) => micros.to_string(),
+            }
+        }
+    }
+}
+
+fn epoch() -> NaiveDate {
+    NaiveDate::from_ymd_opt(2000, 1, 1).expect("valid date")
+}
+
+/// `HH:MM:SS` with the fraction Postgres prints, trailing zeros dropped.
+/// Hours may pass 24, as in intervals.
+fn clock_text(micros: i64) -> String {
+    let sign = if micros < 0 { "-" } else { "" };
+    let micros = micros.unsigned_abs();
+    let seconds = micros / 1_000_000;
+    let mut text = format!(
+        "{sign}{:02}:{:02}:{:02}",
+        seconds / 3600,
+        seconds / 60 % 60,
+        seconds % 60
+    );
+    let fraction = micros % 1_000_000;
+    if fraction > 0 {
+        text.push_str(format!(".{fraction:06}").trim_end_matches('0'));
+    }
+    text
+}
+
+/// A UTC offset in seconds east, as `+02` or `-03:30`.
+fn offset_text(seconds: i32) -> String {
+    let sign = if seconds < 0 { '-' } else { '+' };
+    let seconds = seconds.unsigned_abs();
+    let mut text = format!("{sign}{:02}", seconds / 3600);
+    if !seconds.is_multiple_of(3600) {
+        text.push_str(&format!(":{:02}", seconds / 60 % 60));
+    }
+    if !seconds.is_multiple_of(60) {
+        text.push_str(&format!(":{:02}", seconds % 60));
+    }
+    text
+}
+
+/// An interval the way the default `postgres` IntervalStyle prints it,
+/// e.g. `1 year 2 mons 3 days 04:05:06.5`.
+fn interval_text(months: i32, days: i32, micros: i64) -> String {
+    let unit =
+        |count: i32, name: &str| format!("{count} {name}{}", if count == 1 { "" } else { "s" });
+    let mut parts = Vec::new();
+    if months / 12 != 0 {
+        parts.push(unit(months / 12, "year"));
+    }
+    if months % 12 != 0 {
+        parts.push(unit(months % 12, "mon"));
+    }
+    if days != 0 {
+        parts.push(unit(days, "day"));
+    }
+    if micros != 0 || parts.is_empty() {
+        parts.push(clock_text(micros));
+    }
+    parts.join(" ")
+}
+
+fn inet_text(cidr: bool, bytes: &[u8]) -> Option<String> {
+    let [family, bits, _, length] = *bytes.get(..4)? else {
+        return None;
+    };
+    let address = bytes.get(4..4 + usize::from(length))?;
+    let (address, full) = match family {
+        2 => (
+            std::net::IpAddr::from(<[u8; 4]>::try_from(address).ok()?),
+            32,
+        ),
+        3 => (
+            std::net::IpAddr::from(<[u8; 16]>::try_from(address).ok()?),
+            128,
+        ),
+        _ => return None,
+    };
+    Some(if cidr || bits != full {
+        format!("{address}/{bits}")
+    } else {
+        address.to_string()
+    })
+}
+
+/// An element of a text array literal, before decoding.
+#[derive(Debug, PartialEq)]
+enum TextElement {
+    Null,
+    Text(String),
+    Nested(Vec<TextElement>),
+}
+
+/// Parse a text array literal such as `{1,NULL,"a \"b\""}` or
+/// `[0:1]={{1,2},{3,4}}`.
+fn parse_text_array(text: &str) -> Option<Vec<TextElement>> {
+    // Arrays not starting at 1 are prefixed with their bounds.
+    let literal = match text.split_once('=') {
+        Some((bounds, literal)) if bounds.starts_with('[') => literal,
+        _ => text,
+    };
+    let mut characters = literal.trim().chars().peekable();
+    let elements = parse_text_elements(&mut characters)?;
+    characters.next().is_none().then_some(elements)
+}
+
+fn parse_text_elements(
+    characters: &mut std::iter::Peekable<std::str::Chars<'_>>,
+) -> Option<Vec<TextElement>> {
+    if characters.next()? != '{' {
+        return None;
+    }
+    let mut elements = Vec::new();
+    if characters.peek() == Some(&'}') {
+        characters.next();
+        return Some(elements);
+    }
+    loop {
+        let element = match characters.peek()? {
+            '{' => TextElement::Nested(parse_text_elements(characters)?),
+            '"' => {
+                characters.next();
+                let mut text = String::new();
+                loop {
+                    match characters.next()? {
+                        '\\' => text.push(characters.next()?),
+                        '"' => break,
+                        character => text.push(character),
+                    }
+                }
+                TextElement::Text(text)
+            }
+            _ => {
+                let mut text = String::new();
+                while let Some(&character) = characters.peek() {
+                    if character == ',' || character == '}' {
+                        break;
+                    }
+                    text.push(character);
+                    characters.next();
+                }
+                let text = text.trim();
+                if text.eq_ignore_ascii_case("NULL") {
+                    TextElement::Null
+                } else {
+                    TextElement::Text(text.to_string())
+                }
+            }
+        };
+        elements.push(element);
+        match characters.next()? {
+            ',' => continue,
+            '}' => return Some(elements),
+            _ => return None,
+        }
+    }
+}
+
+fn text_array_cell(element: &CellType, items: Vec<TextElement>) -> Value {
+    cell::array(
+        items
+            .into_iter()
+            .map(|item| match item {
+                TextElement::Null => Value::Null,
+                TextElement::Text(text) => decode(element, PgValueFormat::Text, text.as_bytes()),
+                TextElement::Nested(items) => text_array_cell(element, items),
+            })
+            .collect(),
+    )
+}
+
+/// Binary arrays: dimensions, then every element in row-major order.
+fn binary_array(element: &CellType, bytes: &[u8]) -> Option<Value> {
+    let mut reader = Reader(bytes);
+    let dimensions = reader.i32()?;
+    let _has_nulls = reader.i32()?;
+    let _element_oid = reader.u32()?;
+    let lengths = (0..dimensions)
+        .map(|_| {
+            let length = reader.i32()?;
+            let _lower_bound = reader.i32()?;
+            usize::try_from(length).ok()
+        })
+        .collect::<Option<Vec<_>>>()?;
+    if lengths.is_empty() {
+        return Some(cell::array(Vec::new()));
+    }
+    let value = binary_array_level(element, &lengths, &mut reader)?;
+    reader.0.is_empty().then_some(value)
+}
+
+fn binary_array_level(element: &CellType, lengths: &[usize], reader: &mut Reader) -> Option<Value> {
+    let (length, inner) = lengths.split_first()?;
+    let items = (0..*length)
+        .map(|_| {
+            if inner.is_empty() {
+                Some(reader.element()?.map_or(Value::Null, |bytes| {
+                    decode(element, PgValueFormat::Binary, bytes)
+                }))
+            } else {
+                binary_array_level(element, inner, reader)
+            }
+        })
+        .collect::<Option<Vec<_>>>()?;
+    Some(cell::array(items))
+}
+
+/// Binary ranges, printed as Postgres does: `[1,10)`, `empty`, `(,5]`.
+fn binary_range(element: &CellType, bytes: &[u8]) -> Option<String> {
+    const EMPTY: u8 = 0x01;
+    const LOWER_INCLUSIVE: u8 = 0x02;
+    const UPPER_INCLUSIVE: u8 = 0x04;
+    const LOWER_INFINITE: u8 = 0x08;
+    const UPPER_INFINITE: u8 = 0x10;
+
+    let (&flags, rest) = bytes.split_first()?;
+    if flags & EMPTY != 0 {
+        return Some("empty".to_string());
+    }
+    let mut reader = Reader(rest);
+    let mut bound = |infinite: bool| -> Option<String> {
+        if infinite {
+            return Some(String::new());
+        }
+        let bytes = reader.element()??;
+        Some(range_bound_text(&decode(
+            element,
+            PgValueFormat::Binary,
+            bytes,
+        )))
+    };
+    let lower = bound(flags & LOWER_INFINITE != 0)?;
+    let upper = bound(flags & UPPER_INFINITE != 0)?;
+    Some(format!(
+        "{}{lower},{upper}{}",
+        if flags & LOWER_INCLUSIVE != 0 {
+            '['
+        } else {
+            '('
+        },
+        if flags & UPPER_INCLUSIVE != 0 {
+            ']'
+        } else {
+            ')'
+        },
+    ))
+}
+
+/// A range bound as text, quoted when it holds spaces or delimiters.
+fn range_bound_text(value: &Value) -> String {
+    let text = match cell::TaggedValue::from_cell(value) {
+        Some(tagged) => tagged.text(),
+        None => match value {
+            Value::String(text) => text.clone(),
+            value => value.to_string(),
+        },
+    };
+    if text
+        .chars()
+        .any(|character| matches!(character, ' ' | ',' | '(' | ')' | '[' | ']' | '"'))
+    {
+        format!("\"{}\"", text.replace('\\', "\\\\").replace('"', "\\\""))
+    } else {
+        text
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn scalar(name: &str) -> CellType {
+        CellType::Scalar(name.to_string())
+    }
+
+    fn numeric(weight: i16, sign: u16, scale: u16, digits: &[i16]) -> Vec<u8> {
+        let mut bytes = Vec::new();
+        bytes.extend((digits.len() as i16).to_be_bytes());
+        bytes.extend(weight.to_be_bytes());
+        bytes.extend(sign.to_be_bytes());
+        bytes.extend(scale.to_be_bytes());
+        for digit in digits {
+            bytes.extend(digit.to_be_bytes());
+        }
+        bytes
+    }
+
+    fn element(bytes: &[u8]) -> Vec<u8> {
+        let mut element = (bytes.len() as i32).to_be_bytes().to_vec();
+        element.extend_from_slice(bytes);
+        element
+    }
+
+    #[test]
+    fn prints_binary_numerics_like_postgres() {
+        assert_eq!(
+            numeric_text(&numeric(0, 0, 2, &[12, 5000])).unwrap(),
+            "12.50"
+        );
+        assert_eq!(numeric_text(&numeric(-1, 0, 4, &[1])).unwrap(), "0.0001");
+        assert_eq!(
+            numeric_text(&numeric(-2, 0, 6, &[1200])).unwrap(),
+            "0.000012"
+        );
+        assert_eq!(
+            numeric_text(&numeric(2, 0x4000, 1, &[1, 2345, 6789, 5000])).unwrap(),
+            "-123456789.5"
+        );
+        assert_eq!(numeric_text(&numeric(1, 0, 0, &[1])).unwrap(), "10000");
+        assert_eq!(numeric_text(&numeric(0, 0, 0, &[])).unwrap(), "0");
+        assert_eq!(numeric_text(&numeric(0, 0xC000, 0, &[])).unwrap(), "NaN");
+    }
+
+    #[test]
+    fn prints_binary_temporal_values_like_postgres() {
+        assert_eq!(date_text(8766), "2024-01-01");
+        assert_eq!(date_text(-1), "1999-12-31");
+        assert_eq!(date_text(i32::MAX), "infinity");
+        assert_eq!(
+            timestamp_text(8766 * DAY_MICROS + 3_723_500_000),
+            "2024-01-01 01:02:03.5"
+        );
+        assert_eq!(timestamp_text(-1), "1999-12-31 23:59:59.999999");
+        assert_eq!(offset_text(7200), "+02");
+        assert_eq!(offset_text(-12_600), "-03:30");
+        assert_eq!(
+            interval_text(14, 3, 14_706_500_000),
+            "1 year 2 mons 3 days 04:05:06.5"
+        );
+        assert_eq!(interval_text(0, 1, 0), "1 day");
+        assert_eq!(interval_text(-24, 0, 0), "-2 years");
+        assert_eq!(interval_text(0, 0, -3_600_000_000), "-01:00:00");
+        assert_eq!(interval_text(0, 0, 0), "00:00:00");
+    }
+
+    #[test]
+    fn decodes_binary_scalars_to_tagged_cells() {
+        let binary = |name: &str, bytes: &[u8]| decode(&scalar(name), PgValueFormat::Binary, bytes);
+
+        assert_eq!(binary("INT8", &42i64.to_be_bytes()), json!(42));
+        assert_eq!(
+            binary("INT8", &i64::MAX.to_be_bytes()),
+            json!({ "$type": "integer", "value": "9223372036854775807" })
+        );
+        assert_eq!(binary("FLOAT4", &0.1f32.to_be_bytes()), json!(0.1));
+        assert_eq!(
+            binary("NUMERIC", &numeric(0, 0, 2, &[12, 5000])),
+            json!({ "$type": "decimal", "value": "12.50" })
+        );
+        assert_eq!(
+            binary("TIMESTAMPTZ", &(8766 * DAY_MICROS).to_be_bytes()),
+            json!({ "$type": "timestamptz", "value": "2024-01-01 00:00:00+00" })
+        );
+        let mut jsonb = vec![1];
+        jsonb.extend_from_slice(br#"{"a":1}"#);
+        assert_eq!(
+            binary("JSONB", &jsonb),
+            json!({ "$type": "json", "value": { "a": 1 } })
+        );
+        assert_eq!(
+            binary("INET", &[2, 24, 1, 4, 192, 168, 0, 0]),
+            json!("192.168.0.0/24")
+        );
+        assert_eq!(binary("BOX", &[0; 32]), json!("<BOX>"));
+    }
+
+    #[test]
+    fn decodes_text_scalars_to_the_same_cells() {
+        let text =
+            |name: &str, text: &str| decode(&scalar(name), PgValueFormat::Text, text.as_bytes());
+
+        assert_eq!(text("INT8", "42"), json!(42));
+        assert_eq!(
+            text("NUMERIC", "12.50"),
+            json!({ "$type": "decimal", "value": "12.50" })
+        );
+        assert_eq!(text("BOOL", "t"), json!(true));
+        assert_eq!(
+            text("FLOAT8", "NaN"),
+            json!({ "$type": "float", "value": "NaN" })
+        );
+        assert_eq!(
+            text("BYTEA", "\\xcafe"),
+            json!({ "$type": "binary", "value": "yv4=", "length": 2 })
+        );
+        assert_eq!(
+            text(
+                "geometry",
+                "0101000020E6100000000000000000F03F0000000000000040"
+            ),
+            json!({ "$type": "geometry", "value": "POINT(1 2)", "srid": 4326 })
+        );
+        assert_eq!(text("TEXT", "plain"), json!("plain"));
+    }
+
+    #[test]
+    fn parses_text_arrays() {
+        assert_eq!(
+            parse_text_array(r#"{1,NULL,"a \"b\"",{x}}"#).unwrap(),
+            vec![
+                TextElement::Text("1".to_string()),
+                TextElement::Null,
+                TextElement::Text("a \"b\"".to_string()),
+                TextElement::Nested(vec![TextElement::Text("x".to_string())]),
+            ]
+        );
+        assert_eq!(parse_text_array("{}").unwrap(), vec![]);
+        assert!(parse_text_array("{1,2").is_none());
+
+        let array = CellType::Array(Box::new(scalar("INT4")));
+        assert_eq!(
+            decode(&array, PgValueFormat::Text, b"[0:1]={{1,2},{3,NULL}}"),
+            json!({ "$type": "array", "value": [
+                { "$type": "array", "value": [1, 2] },
+                { "$type": "array", "value": [3, null] }
+            ] })
+        );
+    }
+
+    #[test]
+    fn decodes_binary_arrays_and_ranges() {
+        let mut bytes = Vec::new();
+        for header in [1i32, 1, 23, 2, 1] {
+            bytes.extend(header.to_be_bytes());
+        }
+        bytes.extend(element(&7i32.to_be_bytes()));
+        bytes.extend((-1i32).to_be_bytes());
+        let array = CellType::Array(Box::new(scalar("INT4")));
+        assert_eq!(
+            decode(&array, PgValueFormat::Binary, &bytes),
+            json!({ "$type": "array", "value": [7, null] })
+        );
+
+        let range = CellType::Range(Box::new(scalar("INT4")));
+        let mut bytes = vec![0x02];
+        bytes.extend(element(&1i32.to_be_bytes()));
+        bytes.extend(element(&10i32.to_be_bytes()));
+        assert_eq!(
+            decode(&range, PgValueFormat::Binary, &bytes),
+            json!({ "$type": "range", "value": "[1,10)" })
+        );
+        assert_eq!(
+            decode(&range, PgValueFormat::Binary, &[0x01]),
+            json!({ "$type": "range", "value": "empty" })
+        );
+
+        let timestamps = CellType::Range(Box::new(scalar("TIMESTAMP")));
+        let mut bytes = vec![0x02 | 0x10];
+        bytes.extend(element(&(8766 * DAY_MICROS).to_be_bytes()));
+        assert_eq!(
+            decode(&timestamps, PgValueFormat::Binary, &bytes),
+            json!({ "$type": "range", "value": "[\"2024-01-01 00:00:00\",)" })
+        );
+    }
+}
@@ -1,3 +1,4 @@
 pub mod clickhouse;
 pub mod postgres;
 pub mod sqlite;
+pub mod sqlserver;
@@ -126,3 +126,23 @@ WHERE p.prokind = 'f'
     AND pg_get_function_identity_arguments(p.oid) = $3
 LIMIT 1;
 "#;
+
+/// Schema and name of the table with the given oid, with the number, name,
+/// NOT NULL flag and primary key position (1-based, `NULL` outside the key)
+/// of each of its live columns.
+pub const RESULT_SOURCE_TABLE_QUERY: &str = r#"
+SELECT
+    n.nspname AS schema,
+    c.relname AS table_name,
+    a.attnum AS column_number,
+    a.attname AS column_name,
+    a.attnotnull AS not_null,
+    array_position(pk.indkey::int2[], a.attnum) AS key_position
+FROM pg_class c
+JOIN pg_namespace n ON n.oid = c.relnamespace
+JOIN pg_attribute a ON a.attrelid = c.oid
+LEFT JOIN pg_index pk ON pk.indrelid = c.oid AND pk.indisprimary
+WHERE c.oid = $1::int8::oid
+    AND a.attnum > 0
+    AND NOT a.attisdropped;
+"#;
@@ -0,0 +1,81 @@
+pub const TABLES_QUERY: &str = r#"
+SELECT TABLE_SCHEMA, TABLE_NAME, TABLE_TYPE
+FROM INFORMATION_SCHEMA.TABLES
+WHERE TABLE_SCHEMA NOT IN ('sys', 'INFORMATION_SCHEMA')
+ORDER BY TABLE_SCHEMA, TABLE_NAME;
+"#;
+
+pub const TABLE_COLUMNS_QUERY: &str = r#"
+SELECT
+    c.COLUMN_NAME,
+    c.DATA_TYPE + CASE
+        WHEN c.CHARACTER_MAXIMUM_LENGTH = -1 THEN N'(max)'
+        WHEN c.CHARACTER_MAXIMUM_LENGTH IS NOT NULL
+            AND c.DATA_TYPE NOT IN ('text', 'ntext', 'image', 'xml')
+            THEN N'(' + CAST(c.CHARACTER_MAXIMUM_LENGTH AS NVARCHAR(10)) + N')'
+        WHEN c.DATA_TYPE IN ('decimal', 'numeric')
+            THEN N'(' + CAST(c.NUMERIC_PRECISION AS NVARCHAR(10)) + N','
+                + CAST(c.NUMERIC_SCALE AS NVARCHAR(10)) + N')'
+        ELSE N''
+    END AS data_type,
+    c.IS_NULLABLE,
+    c.COLUMN_DEFAULT,
+    CAST(CASE WHEN pk.COLUMN_NAME IS NULL THEN 0 ELSE 1 END AS BIT) AS primary_key
+FROM INFORMATION_SCHEMA.COLUMNS c
+LEFT JOIN (
+    SELECT ku.TABLE_SCHEMA, ku.TABLE_NAME, ku.COLUMN_NAME
+    FROM INFORMATION_SCHEMA.TABLE_CONSTRAINTS tc
+    JOIN INFORMATION_SCHEMA.KEY_COLUMN_USAGE ku
+        ON tc.CONSTRAINT_NAME = ku.CONSTRAINT_NAME
+        AND tc.TABLE_SCHEMA = ku.TABLE_SCHEMA
+    WHERE tc.CONSTRAINT_TYPE = 'PRIMARY KEY'
+) pk ON c.TABLE_SCHEMA = pk.TABLE_SCHEMA
+    AND c.TABLE_NAME = pk.TABLE_NAME
+    AND c.COLUMN_NAME = pk.COLUMN_NAME
+WHERE c.TABLE_SCHEMA = @P1 AND c.TABLE_NAME = @P2
+ORDER BY c.ORDINAL_POSITION;
+"#;
+
+pub const PRIMARY_KEY_QUERY: &str = r#"
+SELECT ku.COLUMN_NAME
+FROM INFORMATION_SCHEMA.TABLE_CONSTRAINTS tc
+JOIN INFORMATION_SCHEMA.KEY_COLUMN_USAGE ku
+    ON tc.CONSTRAINT_NAME = ku.CONSTRAINT_NAME
+    AND tc.TABLE_SCHEMA = ku.TABLE_SCHEMA
+WHERE tc.CONSTRAINT_TYPE = 'PRIMARY KEY'
+    AND tc.TABLE_SCHEMA = @P1
+    AND tc.TABLE_NAME = @P2
+ORDER BY ku.ORDINAL_POSITION;
+"#;
+
+/// `@P1` is the quoted `[schema].[table]` name, resolved with `OBJECT_ID`.
+pub const TABLE_INDEXES_QUERY: &str = r#"
+SELECT i.name, c.name, i.is_unique, i.is_primary_key
+FROM sys.indexes i
+JOIN sys.index_columns ic
+    ON ic.object_id = i.object_id AND ic.index_id = i.index_id
+JOIN sys.columns c
+    ON c.object_id = ic.object_id AND c.column_id = ic.column_id
+WHERE i.object_id = OBJECT_ID(@P1)
+    AND i.name IS NOT NULL
+    AND ic.is_included_column = 0
+ORDER BY i.name, ic.key_ordinal;
+"#;
+
+pub const TABLE_FOREIGN_KEYS_QUERY: &str = r#"
+SELECT fk.name, pc.name, OBJECT_NAME(fk.referenced_object_id), rc.name
+FROM sys.foreign_keys fk
+JOIN sys.foreign_key_columns fkc ON fkc.constraint_object_id = fk.object_id
+JOIN sys.columns pc
+    ON pc.object_id = fkc.parent_object_id AND pc.column_id = fkc.parent_column_id
+JOIN sys.columns rc
+    ON rc.object_id = fkc.referenced_object_id AND rc.column_id = fkc.referenced_column_id
+WHERE fk.parent_object_id = OBJECT_ID(@P1)
+ORDER BY fk.name, fkc.constraint_column_id;
+"#;
+
+pub const DATABASES_QUERY: &str = r#"
+SELECT name FROM sys.databases
+WHERE HAS_DBACCESS(name) = 1
+ORDER BY name;
+"#;
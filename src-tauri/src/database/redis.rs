@@ -1,12 +1,46 @@
 use async_trait::async_trait;
+use futures_util::StreamExt;
+use redis::aio::ConnectionLike;
+use redis::cluster_routing::{RoutingInfo, SingleNodeRoutingInfo};
 use redis::AsyncCommands;
 use serde_json::{json, Value};
 use std::sync::Arc;
 use tokio::sync::RwLock;
 
-use super::{DatabaseDriver, RedisConfig};
+use super::bulk_keys::{
+    BulkKeyOperation, BulkKeyReport, BulkKeyRequest, BulkProgressSink, DumpedKey, KeyMatches,
+    KeyOutcome, KeyTarget,
+};
+use super::capabilities::{DriverCapabilities, ReadOnlyEnforcement};
+use super::key_value::{
+    ConsumerGroup, KeyValueBrowser, LiveMessage, LiveSink, LiveSource, ScanProgress,
+};
+use super::keyspace_analysis::{
+    AnalysisOptions, AnalysisProgress, AnalysisProgressSink, KeySample, KeyspaceAnalyzer,
+    KeyspaceReport,
+};
+use super::redis_cli::{parse_script, RedisScript};
+use super::redis_diagnostics::{
+    parse_client_list, parse_info, parse_latency_history, parse_latency_latest, parse_monitor_line,
+    parse_slowlog, ClientInfo, LatencyEvent, LatencySample, NodeInfo, ServerDiagnostics,
+    SlowlogEntry,
+};
+use super::redis_modules::{
+    aggregate_rows, json_length, parse_index_info, search_rows, RedisModules, SearchIndexInfo,
+    SearchReplyShape, JSON_KEY_TYPE, SEARCH_INDEX_TABLE_TYPE, SEARCH_SCHEMA,
+};
+use super::redis_read_only::first_write_command;
+use super::redis_scripts::{
+    parse_function_list, script_hash, FunctionLibrary, ScriptCall, ScriptRunner,
+};
+use super::registry::{
+    ConfigField, ConfigFieldKind, DriverDescriptor, DriverOptions, SshSupport, HOST, PASSWORD,
+    PORT, USERNAME,
+};
+use super::{DatabaseDriver, DriverError, DriverErrorCategory, RedisConfig, RedisTopology};
 use crate::db::models::{
-    QueryResult, SchemaOverview, TableDataResponse, TableInfo, TableStructure, TestConnectionResult,
+    QueryResult, SchemaOverview, TableDataResponse, TableInfo, TableStructure, TableWithStructure,
+    TestConnectionResult,
 };
 
 /// Redis-specific types for key values
@@ -40,9 +74,311 @@ pub struct RedisKeyListResponse {
     pub scan_complete: bool,
 }
 
+pub(crate) const CAPABILITIES: DriverCapabilities = DriverCapabilities {
+    transactions: false,
+    create_table: false,
+    alter_table: false,
+    functions: false,
+    read_only: ReadOnlyEnforcement::Allowlist,
+    cancellation: false,
+    streaming: false,
+    multiple_databases: false,
+    key_value_browsing: true,
+    explain: false,
+    row_editing: false,
+};
+
+pub(crate) const DESCRIPTOR: DriverDescriptor = DriverDescriptor {
+    id: "redis",
+    label: "Redis",
+    aliases: &[],
+    default_port: Some(6379),
+    fields: &[
+        HOST,
+        PORT,
+        ConfigField::new("database", "Database number", ConfigFieldKind::Integer),
+        USERNAME,
+        PASSWORD,
+        ConfigField::new("ssl", "TLS", ConfigFieldKind::Boolean),
+        ConfigField::new("mode", "Mode", ConfigFieldKind::Text),
+        ConfigField::new("nodes", "Additional nodes", ConfigFieldKind::Text),
+        ConfigField::new(
+            "sentinel_master",
+            "Sentinel master name",
+            ConfigFieldKind::Text,
+        ),
+        ConfigField::new(
+            "sentinel_password",
+            "Sentinel password",
+            ConfigFieldKind::Text,
+        )
+        .secret(),
+    ],
+    ssh: SshSupport::Supported,
+    sessions: false,
+    capabilities: CAPABILITIES,
+    schema_qualified: true,
+    replays_reads: true,
+    explain: |_| Err("Redis has no query plans to explain".to_string()),
+    build: build_driver,
+};
+
+fn build_driver(options: DriverOptions) -> Result<Box<dyn DatabaseDriver>, String> {
+    Ok(Box::new(RedisDriver::new(RedisConfig {
+        host: options.text("host").unwrap_or_default(),
+        port: options.integer("port").unwrap_or(6379),
+        username: options.text("username"),
+        password: options.text("password"),
+        db: options.integer("database"),
+        tls: options.flag("ssl"),
+        topology: topology(&options)?,
+        limits: options.limits,
+    })))
+}
+
+/// Read the `mode` option. `nodes` lists extra seeds as `host:port`,
+/// separated by commas or whitespace.
+fn topology(options: &DriverOptions) -> Result<RedisTopology, String> {
+    let mode = options.text("mode").unwrap_or_default();
+    match mode.trim().to_ascii_lowercase().as_str() {
+        "" | "standalone" => Ok(RedisTopology::Standalone),
+        "cluster" if options.integer("database").unwrap_or(0) != 0 => {
+            Err("Redis Cluster only has database 0".to_string())
+        }
+        "cluster" => Ok(RedisTopology::Cluster {
+            nodes: parse_nodes(&options.text("nodes").unwrap_or_default(), 6379)?,
+        }),
+        "sentinel" => Ok(RedisTopology::Sentinel {
+            master_name: options.required_text(
+                "sentinel_master",
+                "Sentinel mode requires the master name to monitor",
+            )?,
+            nodes: parse_nodes(&options.text("nodes").unwrap_or_default(), 26379)?,
+            sentinel_password: options.text("sentinel_password"),
+        }),
+        other => Err(format!(
+            "Unknown Redis mode '{other}': expected standalone, cluster or sentinel"
+        )),
+    }
+}
+
+fn parse_nodes(nodes: &str, default_port: u16) -> Result<Vec<(String, u16)>, String> {
+    nodes
+        .split(|c: char| c == ',' || c.is_whitespace())
+        .filter(|node| !node.is_empty())
+        .map(|node| match node.rsplit_once(':') {
+            Some((host, port)) => port
+                .parse()
+                .map(|port| (host.trim_matches(['[', ']']).to_string(), port))
+                .map_err(|_| format!("Invalid Redis node address: {node}")),
+            None => Ok((node.to_string(), default_port)),
+        })
+        .collect()
+}
+
+/// A cluster node's host and port.
+type NodeAddress = (String, u16);
+
+/// Masters of a cluster as reported by `CLUSTER NODES`, skipping failed
+/// nodes and those without an address.
+fn cluster_masters(nodes: &str) -> Vec<NodeAddress> {
+    nodes
+        .lines()
+        .filter_map(|line| {
+            let mut fields = line.split_whitespace();
+            let address = fields.nth(1)?;
+            let flags: Vec<&str> = fields.next()?.split(',').collect();
+            if !flags.contains(&"master")
+                || flags
+                    .iter()
+                    .any(|flag| matches!(*flag, "fail" | "fail?" | "noaddr" | "handshake"))
+            {
+                return None;
+            }
+            // ip:port@cport[,hostname]
+            let address = address.split(['@', ',']).next()?;
+            let (host, port) = address.rsplit_once(':')?;
+            Some((host.to_string(), port.parse().ok()?))
+        })
+        .collect()
+}
+
+/// Entries read per `XREAD`, and how long each read blocks waiting for new
+/// ones before it is sent again.
+const STREAM_READ_COUNT: usize = 100;
+const STREAM_BLOCK_MS: u64 = 5_000;
+
+/// Keys asked for per `SCAN` when walking the whole keyspace; each batch is
+/// then inspected or changed in one pipeline.
+const KEY_SCAN_COUNT: usize = 500;
+
+/// A key described by [`RedisDriver::describe_keys`]: its `TYPE`, `TTL` and,
+/// when present, `MEMORY USAGE` and `OBJECT ENCODING` replies. Keys deleted
+/// since the scan are skipped.
+fn key_sample(key: &[u8], replies: &[redis::Value]) -> Option<KeySample> {
+    let key_type = match replies.first()? {
+        redis::Value::SimpleString(key_type) if key_type != "none" => key_type.clone(),
+        _ => return None,
+    };
+    let ttl = match replies.get(1)? {
+        redis::Value::Int(-2) => return None,
+        redis::Value::Int(ttl) => *ttl,
+        _ => -1,
+    };
+    let memory = match replies.get(2) {
+        Some(redis::Value::Int(memory)) => u64::try_from(*memory).ok(),
+        _ => None,
+    };
+    let encoding = replies.get(3).and_then(RedisDriver::reply_text);
+    Some(KeySample {
+        key: String::from_utf8_lossy(key).into_owned(),
+        key_type,
+        memory,
+        ttl,
+        encoding,
+    })
+}
+
+/// What a reply to a per-key command says happened to the key. Keys that
+/// vanished since the scan, or whose target already exists (`BUSYKEY`), are
+/// skipped rather than failed.
+fn key_outcome(reply: redis::RedisResult<redis::Value>) -> KeyOutcome {
+    match reply {
+        Ok(redis::Value::ServerError(error)) => key_outcome(Err(error.into())),
+        Ok(redis::Value::Int(0) | redis::Value::Nil) => KeyOutcome::Skipped,
+        Ok(_) => KeyOutcome::Done,
+        Err(error)
+            if error.code() == Some("BUSYKEY")
+                || error
+                    .detail()
+                    .is_some_and(|detail| detail.contains("no such key")) =>
+        {
+            KeyOutcome::Skipped
+        }
+        Err(error) => KeyOutcome::Failed(error.to_string()),
+    }
+}
+
+/// The `(id, fields)` entries of an `XREAD`/`XREADGROUP` reply for a single
+/// stream. A blocked read that timed out replies with nil.
+fn stream_entries(reply: redis::Value) -> Vec<(String, Vec<(String, redis::Value)>)> {
+    let streams = match reply {
+        redis::Value::Array(streams) => streams
+            .into_iter()
+            .filter_map(|stream| match stream {
+                redis::Value::Array(mut pair) if pair.len() == 2 => pair.pop(),
+                _ => None,
+            })
+            .collect(),
+        redis::Value::Map(streams) => streams.into_iter().map(|(_, entries)| entries).collect(),
+        _ => Vec::new(),
+    };
+    streams
+        .into_iter()
+        .flat_map(|entries| match entries {
+            redis::Value::Array(entries) => entries,
+            _ => Vec::new(),
+        })
+        .filter_map(|entry| {
+            let redis::Value::Array(entry) = entry else {
+                return None;
+            };
+            let mut entry = entry.into_iter();
+            let id: String = redis::from_owned_redis_value(entry.next()?).ok()?;
+            // Entries deleted while pending in a group come back with nil fields.
+            let fields = match entry.next()? {
+                redis::Value::Array(fields) => {
+                    let mut fields = fields.into_iter();
+                    std::iter::from_fn(|| {
+                        let field = redis::from_owned_redis_value(fields.next()?).ok()?;
+                        Some((field, fields.next()?))
+                    })
+                    .collect()
+                }
+                redis::Value::Map(fields) => fields
+                    .into_iter()
+                    .filter_map(|(field, value)| {
+                        Some((redis::from_owned_redis_value(field).ok()?, value))
+                    })
+                    .collect(),
+                _ => Vec::new(),
+            };
+            Some((id, fields))
+        })
+        .collect()
+}
+
+/// Split a `search_keys` cursor into the node's own SCAN cursor and the
+/// index of the node being scanned. With one node this is the SCAN cursor.
+fn split_cluster_cursor(cursor: u64, node_count: u64) -> (u64, u64) {
+    (cursor / node_count, cursor % node_count)
+}
+
+/// A connection to a single server (standalone, or the master a sentinel
+/// resolved) or to a whole cluster, which routes each command by key slot.
+#[derive(Clone)]
+enum RedisConnection {
+    Node(redis::aio::MultiplexedConnection),
+    Cluster(redis::cluster_async::ClusterConnection),
+}
+
+impl ConnectionLike for RedisConnection {
+    fn req_packed_command<'a>(
+        &'a mut self,
+        cmd: &'a redis::Cmd,
+    ) -> redis::RedisFuture<'a, redis::Value> {
+        match self {
+            Self::Node(conn) => conn.req_packed_command(cmd),
+            Self::Cluster(conn) => conn.req_packed_command(cmd),
+        }
+    }
+
+    fn req_packed_commands<'a>(
+        &'a mut self,
+        pipe: &'a redis::Pipeline,
+        offset: usize,
+        count: usize,
+    ) -> redis::RedisFuture<'a, Vec<redis::Value>> {
+        match self {
+            Self::Node(conn) => conn.req_packed_commands(pipe, offset, count),
+            Self::Cluster(conn) => conn.req_packed_commands(pipe, offset, count),
+        }
+    }
+
+    fn get_db(&self) -> i64 {
+        match self {
+            Self::Node(conn) => conn.get_db(),
+            Self::Cluster(conn) => conn.get_db(),
+        }
+    }
+}
+
 pub struct RedisDriver {
     config: RedisConfig,
-    connection: Arc<RwLock<Option<redis::aio::MultiplexedConnection>>>,
+    connection: Arc<RwLock<Option<RedisConnection>>>,
+}
+
+/// Classify a Redis error by its kind and server error code (`NOPERM`, ...).
+fn redis_error(error: &redis::RedisError) -> DriverError {
+    let category = if error.is_timeout() {
+        DriverErrorCategory::Timeout
+    } else if error.is_connection_refusal() || error.is_connection_dropped() || error.is_io_error()
+    {
+        DriverErrorCategory::Connection
+    } else if error.kind() == redis::ErrorKind::AuthenticationFailed {
+        DriverErrorCategory::Auth
+    } else {
+        match error.code() {
+            Some("WRONGPASS" | "NOAUTH") => DriverErrorCategory::Auth,
+            Some("NOPERM" | "READONLY") => DriverErrorCategory::Permission,
+            _ => DriverErrorCategory::Other,
+        }
+    };
+    let driver_error = DriverError::new(category, error.to_string());
+    match error.code() {
+        Some(code) => driver_error.with_code(code),
+        None => driver_error,
+    }
 }
 
 impl RedisDriver {
@@ -93,26 +429,97 @@ impl RedisDriver {
         Ok(redis::ConnectionInfo { addr, redis })
     }
 
+    /// The seed addresses: `host`/`port` followed by the extra `nodes`.
+    fn seed_connection_infos(
+        &self,
+        nodes: &[(String, u16)],
+    ) -> Result<Vec<redis::ConnectionInfo>, String> {
+        std::iter::once(self.build_connection_info())
+            .chain(
+                nodes
+                    .iter()
+                    .map(|(host, port)| self.build_connection_info_with_host(host, *port as i64)),
+            )
+            .collect()
+    }
+
     /// Create a new Redis connection
-    async fn create_connection(&self) -> Result<redis::aio::MultiplexedConnection, String> {
-        let client = redis::Client::open(self.build_connection_info()?)
-            .map_err(|e| format!("Failed to create Redis client: {}", e))?;
-
-        // Use a 10 second timeout for connection
-        match tokio::time::timeout(
-            std::time::Duration::from_secs(10),
-            client.get_multiplexed_async_connection(),
-        )
-        .await
-        {
+    async fn create_connection(&self) -> Result<RedisConnection, String> {
+        match tokio::time::timeout(self.config.limits.connect_timeout, self.connect()).await {
             Ok(Ok(conn)) => Ok(conn),
             Ok(Err(e)) => Err(format!("Failed to connect to Redis: {}", e)),
-            Err(_) => Err("Connection timed out after 10 seconds".to_string()),
+            Err(_) => Err(self.config.limits.connect_timeout_message()),
+        }
+    }
+
+    async fn connect(&self) -> Result<RedisConnection, String> {
+        match &self.config.topology {
+            // Credentials and TLS are taken from the first seed and used for
+            // every node the cluster reports.
+            RedisTopology::Cluster { nodes } => {
+                let client =
+                    redis::cluster::ClusterClient::builder(self.seed_connection_infos(nodes)?)
+                        .connection_timeout(self.config.limits.connect_timeout)
+                        .build()
+                        .map_err(|e| format!("Failed to create Redis Cluster client: {}", e))?;
+                client
+                    .get_async_connection()
+                    .await
+                    .map(RedisConnection::Cluster)
+                    .map_err(|e| e.to_string())
+            }
+            RedisTopology::Standalone | RedisTopology::Sentinel { .. } => self
+                .node_client()
+                .await?
+                .get_multiplexed_async_connection()
+                .await
+                .map(RedisConnection::Node)
+                .map_err(|e| e.to_string()),
         }
     }
 
+    /// A client for a single server: the configured one, the first seed of a
+    /// cluster, or the master the sentinels currently report. The master is
+    /// looked up on every call, so reconnecting after a failover reaches the
+    /// promoted replica.
+    async fn node_client(&self) -> Result<redis::Client, String> {
+        let RedisTopology::Sentinel {
+            master_name,
+            nodes,
+            sentinel_password,
+        } = &self.config.topology
+        else {
+            return redis::Client::open(self.build_connection_info()?)
+                .map_err(|e| format!("Failed to create Redis client: {}", e));
+        };
+
+        let sentinels = self
+            .seed_connection_infos(nodes)?
+            .into_iter()
+            .map(|mut sentinel| {
+                sentinel.redis = redis::RedisConnectionInfo {
+                    password: sentinel_password.clone(),
+                    ..Default::default()
+                };
+                sentinel
+            })
+            .collect();
+        let mut sentinel = redis::sentinel::Sentinel::build(sentinels)
+            .map_err(|e| format!("Failed to create Redis Sentinel client: {}", e))?;
+        sentinel
+            .async_master_for(
+                master_name,
+                Some(&redis::sentinel::SentinelNodeConnectionInfo {
+                    tls_mode: self.config.tls.then_some(redis::TlsMode::Secure),
+                    redis_connection_info: Some(self.build_connection_info()?.redis),
+                }),
+            )
+            .await
+            .map_err(|e| e.to_string())
+    }
+
     /// Get or create a cached connection
-    async fn get_connection(&self) -> Result<redis::aio::MultiplexedConnection, String> {
+    async fn get_connection(&self) -> Result<RedisConnection, String> {
         {
             let conn_guard = self.connection.read().await;
             if let Some(ref conn) = *conn_guard {
@@ -140,11 +547,11 @@ impl RedisDriver {
     }
 
     /// Get connection with retry on failure
-    async fn get_connection_with_retry(&self) -> Result<redis::aio::MultiplexedConnection, String> {
+    async fn get_connection_with_retry(&self) -> Result<RedisConnection, String> {
         match self.get_connection().await {
             Ok(conn) => Ok(conn),
             Err(e) => {
-                println!("[Redis] Connection failed: {}, resetting...", e);
+                eprintln!("[Redis] Connection failed: {}, resetting...", e);
                 self.reset_connection().await?;
                 self.get_connection().await
             }
@@ -152,21 +559,23 @@ impl RedisDriver {
     }
 
     /// Check if error is a connection error and handle reset if needed
-    fn handle_connection_error(&self, error: &redis::RedisError, operation: &str) -> String {
-        let error_str = error.to_string();
-        let should_reset = error_str.contains("Connection reset by peer")
-            || error_str.contains("broken pipe")
-            || error_str.contains("connection closed")
-            || error_str.contains("Connection refused");
-
-        if should_reset {
-            println!(
+    fn handle_connection_error(&self, error: &redis::RedisError, operation: &str) -> DriverError {
+        let error = redis_error(error);
+        // A dropped connection, or a master demoted by a failover (READONLY),
+        // is replaced on the next request; sentinel mode then resolves the
+        // current master again.
+        if error.category == DriverErrorCategory::Connection
+            || error.code.as_deref() == Some("READONLY")
+        {
+            eprintln!(
                 "[Redis] Connection error in {}, resetting connection: {}",
-                operation, error_str
+                operation, error
             );
-            // Reset will happen on next connection attempt via get_connection_with_retry
+            if let Ok(mut conn_guard) = self.connection.try_write() {
+                *conn_guard = None;
+            }
         }
-        format!("Failed to {}: {}", operation, error_str)
+        error.context(&format!("Failed to {}", operation))
     }
 
     /// Convert Redis value to JSON
@@ -225,6 +634,7 @@ impl RedisDriver {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::database::QueryLimits;
 
     fn driver(config: RedisConfig) -> RedisDriver {
         RedisDriver::new(config)
@@ -239,6 +649,8 @@ mod tests {
             password: None,
             db: Some(0),
             tls: false,
+            topology: RedisTopology::Standalone,
+            limits: QueryLimits::default(),
         });
 
         let info = driver.build_connection_info().unwrap();
@@ -261,6 +673,8 @@ mod tests {
             password: Some("secret".to_string()),
             db: Some(4),
             tls: false,
+            topology: RedisTopology::Standalone,
+            limits: QueryLimits::default(),
         });
 
         let info = driver.build_connection_info().unwrap();
@@ -279,6 +693,8 @@ mod tests {
             password: Some("secret".to_string()),
             db: Some(7),
             tls: true,
+            topology: RedisTopology::Standalone,
+            limits: QueryLimits::default(),
         });
 
         let info = driver.build_connection_info().unwrap();
@@ -306,21 +722,413 @@ mod tests {
             password: None,
             db: None,
             tls: false,
+            topology: RedisTopology::Standalone,
+            limits: QueryLimits::default(),
         });
 
         let error = driver.build_connection_info().unwrap_err();
 
         assert!(error.contains("Invalid Redis port"));
     }
+
+    fn options(values: Value) -> DriverOptions {
+        DriverOptions {
+            values: serde_json::from_value(values).unwrap(),
+            session: Default::default(),
+            limits: QueryLimits::default(),
+        }
+    }
+
+    #[test]
+    fn reads_the_topology_from_options() {
+        assert_eq!(
+            topology(&options(json!({}))).unwrap(),
+            RedisTopology::Standalone
+        );
+        assert_eq!(
+            topology(&options(json!({
+                "mode": "Cluster",
+                "nodes": "10.0.0.2:7001, 10.0.0.3\n[::1]:7002"
+            })))
+            .unwrap(),
+            RedisTopology::Cluster {
+                nodes: vec![
+                    ("10.0.0.2".to_string(), 7001),
+                    ("10.0.0.3".to_string(), 6379),
+                    ("::1".to_string(), 7002),
+                ]
+            }
+        );
+        assert_eq!(
+            topology(&options(json!({
+                "mode": "sentinel",
+                "nodes": "sentinel-2",
+                "sentinel_master": "mymaster",
+                "sentinel_password": "s3cret"
+            })))
+            .unwrap(),
+            RedisTopology::Sentinel {
+                master_name: "mymaster".to_string(),
+                nodes: vec![("sentinel-2".to_string(), 26379)],
+                sentinel_password: Some("s3cret".to_string()),
+            }
+        );
+        assert!(topology(&options(json!({ "mode": "sentinel" }))).is_err());
+        assert!(topology(&options(json!({ "mode": "replica" }))).is_err());
+        assert!(topology(&options(json!({ "mode": "cluster", "database": 2 }))).is_err());
+        assert!(topology(&options(json!({ "mode": "cluster", "nodes": "a:b" }))).is_err());
+    }
+
+    #[test]
+    fn lists_reachable_cluster_masters() {
+        let nodes = "\
+07c37dfeb235213a872192d90877d0cd55635b91 127.0.0.1:30004@31004,cache-4 slave e7d1eecce10fd6bb5eb35b9f99a514335d9ba9ca 0 1426238317239 4 connected
+67ed2db8d677e59ec4a4cefb06858cf2a1a89fa1 127.0.0.1:30002@31002 master - 0 1426238316232 2 connected 5461-10922
+292f8b365bb7edb5e285caf0b7e6ddc7265d2f4f 127.0.0.1:30003@31003 master - 0 1426238318243 3 connected 10923-16383
+6ec23923021cf3ffec47632106199cb7f496ce01 127.0.0.1:30005@31005 master,fail - 1426238316232 0 5 disconnected
+e7d1eecce10fd6bb5eb35b9f99a514335d9ba9ca 127.0.0.1:30001@31001 myself,master - 0 0 1 connected 0-5460
+";
+        assert_eq!(
+            cluster_masters(nodes),
+            vec![
+                ("127.0.0.1".to_string(), 30002),
+                ("127.0.0.1".to_string(), 30003),
+                ("127.0.0.1".to_string(), 30001),
+            ]
+        );
+    }
+
+    #[test]
+    fn cluster_cursors_round_trip_the_node_index() {
+        assert_eq!(split_cluster_cursor(0, 1), (0, 0));
+        assert_eq!(split_cluster_cursor(1792, 1), (1792, 0));
+        assert_eq!(split_cluster_cursor(1792 * 3 + 2, 3), (1792, 2));
+        // A node whose scan has just finished resumes the next node at 0.
+        assert_eq!(split_cluster_cursor(1, 3), (0, 1));
+    }
+
+    #[test]
+    fn reads_entries_from_stream_replies() {
+        let bulk = |text: &str| redis::Value::BulkString(text.as_bytes().to_vec());
+        let reply = redis::Value::Array(vec![redis::Value::Array(vec![
+            bulk("orders"),
+            redis::Value::Array(vec![
+                redis::Value::Array(vec![
+                    bulk("1700000000000-0"),
+                    redis::Value::Array(vec![
+                        bulk("status"),
+                        bulk("paid"),
+                        bulk("total"),
+                        bulk("12"),
+                    ]),
+                ]),
+                // Deleted while pending in a consumer group.
+                redis::Value::Array(vec![bulk("1700000000000-1"), redis::Value::Nil]),
+            ]),
+        ])]);
+
+        let entries = stream_entries(reply);
+        assert_eq!(entries.len(), 2);
+        assert_eq!(entries[0].0, "1700000000000-0");
+        assert_eq!(
+            RedisDriver::stream_message("orders", &entries[0].0, entries[0].1.clone()).payload,
+            json!({ "status": "paid", "total": "12" })
+        );
+        assert!(entries[1].1.is_empty());
+        // A blocked read that timed out.
+        assert!(stream_entries(redis::Value::Nil).is_empty());
+    }
+
+    #[test]
+    fn describes_keys_from_pipelined_replies() {
+        let status = |text: &str| redis::Value::SimpleString(text.to_string());
+        let sample = key_sample(
+            b"user:1",
+            &[
+                status("hash"),
+                redis::Value::Int(-1),
+                redis::Value::Int(96),
+                redis::Value::BulkString(b"listpack".to_vec()),
+            ],
+        )
+        .unwrap();
+        assert_eq!(sample.key, "user:1");
+        assert_eq!(sample.key_type, "hash");
+        assert_eq!(sample.ttl, -1);
+        assert_eq!(sample.memory, Some(96));
+        assert_eq!(sample.encoding.as_deref(), Some("listpack"));
+
+        // Without MEMORY and OBJECT replies.
+        let sample = key_sample(b"session", &[status("string"), redis::Value::Int(30)]).unwrap();
+        assert_eq!((sample.ttl, sample.memory), (30, None));
+
+        // Deleted between the scan and the pipeline.
+        assert!(key_sample(b"gone", &[status("none"), redis::Value::Int(-2)]).is_none());
+        assert!(key_sample(b"gone", &[status("string"), redis::Value::Int(-2)]).is_none());
+    }
+
+    #[test]
+    fn reads_per_key_outcomes_from_replies() {
+        let server_error = |reply: &str| redis::parse_redis_value(reply.as_bytes()).unwrap();
+        assert_eq!(key_outcome(Ok(redis::Value::Int(1))), KeyOutcome::Done);
+        assert_eq!(key_outcome(Ok(redis::Value::Okay)), KeyOutcome::Done);
+        assert_eq!(key_outcome(Ok(redis::Value::Int(0))), KeyOutcome::Skipped);
+        assert_eq!(
+            key_outcome(Ok(server_error(
+                "-BUSYKEY Target key name already exists.\r\n"
+            ))),
+            KeyOutcome::Skipped
+        );
+        assert!(matches!(
+            key_outcome(Ok(server_error(
+                "-CROSSSLOT Keys in request don't hash to the same slot\r\n"
+            ))),
+            KeyOutcome::Failed(_)
+        ));
+    }
+
+    #[test]
+    fn info_from_a_cluster_has_a_row_per_node() {
+        let reply = redis::Value::Map(vec![
+            (
+                redis::Value::BulkString(b"10.0.0.1:6379".to_vec()),
+                redis::Value::VerbatimString {
+                    format: redis::VerbatimFormat::Text,
+                    text: "# Server\r\nredis_version:7.2.0".to_string(),
+                },
+            ),
+            (
+                redis::Value::BulkString(b"10.0.0.2:6379".to_vec()),
+                redis::Value::BulkString(b"# Server".to_vec()),
+            ),
+        ]);
+        let rows = RedisDriver::info_rows(&reply).unwrap();
+        assert_eq!(rows.len(), 2);
+        assert_eq!(
+            rows[1],
+            json!({ "node": "10.0.0.2:6379", "info": "# Server" })
+        );
+        assert_eq!(
+            RedisDriver::info_rows(&redis::Value::BulkString(b"# Server".to_vec())).unwrap(),
+            vec![json!({ "info": "# Server" })]
+        );
+    }
+}
+
+#[async_trait]
+impl KeyValueBrowser for RedisDriver {
+    async fn search_keys(
+        &self,
+        pattern: &str,
+        limit: i64,
+        cursor: u64,
+        progress: ScanProgress<'_>,
+    ) -> Result<RedisKeyListResponse, DriverError> {
+        Ok(RedisDriver::search_keys(self, pattern, limit, cursor, progress).await?)
+    }
+
+    async fn get_key_details(&self, key: &str) -> Result<RedisKeyDetails, DriverError> {
+        Ok(RedisDriver::get_key_details(self, key).await?)
+    }
+
+    async fn delete_key(&self, key: &str) -> Result<bool, DriverError> {
+        Ok(RedisDriver::delete_key(self, key).await?)
+    }
+
+    async fn set_key(&self, key: &str, value: &str, ttl: Option<i64>) -> Result<(), DriverError> {
+        Ok(RedisDriver::set_key(self, key, value, ttl).await?)
+    }
+
+    async fn set_list_key(
+        &self,
+        key: &str,
+        values: &[String],
+        ttl: Option<i64>,
+    ) -> Result<(), DriverError> {
+        Ok(RedisDriver::set_list_key(self, key, values, ttl).await?)
+    }
+
+    async fn set_set_key(
+        &self,
+        key: &str,
+        values: &[String],
+        ttl: Option<i64>,
+    ) -> Result<(), DriverError> {
+        Ok(RedisDriver::set_set_key(self, key, values, ttl).await?)
+    }
+
+    async fn set_hash_key(
+        &self,
+        key: &str,
+        fields: &std::collections::HashMap<String, String>,
+        ttl: Option<i64>,
+    ) -> Result<(), DriverError> {
+        Ok(RedisDriver::set_hash_key(self, key, fields, ttl).await?)
+    }
+
+    async fn set_zset_key(
+        &self,
+        key: &str,
+        members: &[(String, f64)],
+        ttl: Option<i64>,
+    ) -> Result<(), DriverError> {
+        Ok(RedisDriver::set_zset_key(self, key, members, ttl).await?)
+    }
+
+    async fn update_ttl(&self, key: &str, ttl: Option<i64>) -> Result<(), DriverError> {
+        Ok(RedisDriver::update_ttl(self, key, ttl).await?)
+    }
+
+    async fn watch(&self, source: &LiveSource, sink: LiveSink<'_>) -> Result<(), DriverError> {
+        RedisDriver::watch(self, source, sink).await
+    }
+
+    async fn analyze_keyspace(
+        &self,
+        options: &AnalysisOptions,
+        progress: AnalysisProgressSink<'_>,
+    ) -> Result<KeyspaceReport, DriverError> {
+        RedisDriver::analyze_keyspace(self, options, progress).await
+    }
+
+    async fn match_keys(
+        &self,
+        pattern: &str,
+        sample_size: usize,
+    ) -> Result<KeyMatches, DriverError> {
+        RedisDriver::match_keys(self, pattern, sample_size).await
+    }
+
+    async fn bulk_update(
+        &self,
+        request: &BulkKeyRequest,
+        target: Option<&dyn KeyValueBrowser>,
+        progress: BulkProgressSink<'_>,
+    ) -> Result<BulkKeyReport, DriverError> {
+        RedisDriver::bulk_update(self, request, target, progress).await
+    }
+
+    async fn restore_keys(
+        &self,
+        keys: &[DumpedKey],
+        replace: bool,
+    ) -> Result<Vec<KeyOutcome>, DriverError> {
+        RedisDriver::restore_keys(self, keys, replace).await
+    }
+
+    async fn modules(&self) -> Result<RedisModules, DriverError> {
+        RedisDriver::modules(self).await
+    }
+
+    async fn json_get(&self, key: &str, path: &str) -> Result<Value, DriverError> {
+        RedisDriver::json_get(self, key, path).await
+    }
+
+    async fn json_set(&self, key: &str, path: &str, value: &Value) -> Result<(), DriverError> {
+        RedisDriver::json_set(self, key, path, value).await
+    }
+
+    async fn json_delete(&self, key: &str, path: &str) -> Result<u64, DriverError> {
+        RedisDriver::json_delete(self, key, path).await
+    }
+}
+
+#[async_trait]
+impl ServerDiagnostics for RedisDriver {
+    async fn slowlog(&self, count: usize) -> Result<Vec<SlowlogEntry>, DriverError> {
+        RedisDriver::slowlog(self, count).await
+    }
+
+    async fn latency_latest(&self) -> Result<Vec<LatencyEvent>, DriverError> {
+        RedisDriver::latency_latest(self).await
+    }
+
+    async fn latency_history(&self, event: &str) -> Result<Vec<LatencySample>, DriverError> {
+        RedisDriver::latency_history(self, event).await
+    }
+
+    async fn client_list(&self) -> Result<Vec<ClientInfo>, DriverError> {
+        RedisDriver::client_list(self).await
+    }
+
+    async fn kill_client(
+        &self,
+        node: Option<&str>,
+        id: u64,
+        addr: &str,
+    ) -> Result<bool, DriverError> {
+        RedisDriver::kill_client(self, node, id, addr).await
+    }
+
+    async fn server_info(&self, section: Option<&str>) -> Result<Vec<NodeInfo>, DriverError> {
+        RedisDriver::server_info(self, section).await
+    }
+}
+
+#[async_trait]
+impl ScriptRunner for RedisDriver {
+    async fn eval_script(
+        &self,
+        source: &str,
+        call: &ScriptCall,
+        read_only: bool,
+    ) -> Result<QueryResult, DriverError> {
+        RedisDriver::eval_script(self, source, call, read_only).await
+    }
+
+    async fn load_script(&self, source: &str) -> Result<String, DriverError> {
+        RedisDriver::load_script(self, source).await
+    }
+
+    async fn scripts_exist(&self, hashes: &[String]) -> Result<Vec<bool>, DriverError> {
+        RedisDriver::scripts_exist(self, hashes).await
+    }
+
+    async fn list_functions(&self, with_code: bool) -> Result<Vec<FunctionLibrary>, DriverError> {
+        RedisDriver::list_functions(self, with_code).await
+    }
+
+    async fn load_function_library(
+        &self,
+        source: &str,
+        replace: bool,
+    ) -> Result<String, DriverError> {
+        RedisDriver::load_function_library(self, source, replace).await
+    }
+
+    async fn delete_function_library(&self, library: &str) -> Result<(), DriverError> {
+        RedisDriver::delete_function_library(self, library).await
+    }
+
+    async fn call_function(
+        &self,
+        function: &str,
+        call: &ScriptCall,
+        read_only: bool,
+    ) -> Result<QueryResult, DriverError> {
+        RedisDriver::call_function(self, function, call, read_only).await
+    }
 }
 
 #[async_trait]
 impl DatabaseDriver for RedisDriver {
-    fn as_any(&self) -> &dyn std::any::Any {
-        self
+    fn capabilities(&self) -> DriverCapabilities {
+        CAPABILITIES
     }
 
-    async fn test_connection(&self) -> Result<TestConnectionResult, String> {
+    fn key_value(&self) -> Option<&dyn KeyValueBrowser> {
+        Some(self)
+    }
+
+    fn diagnostics(&self) -> Option<&dyn ServerDiagnostics> {
+        Some(self)
+    }
+
+    fn scripting(&self) -> Option<&dyn ScriptRunner> {
+        Some(self)
+    }
+
+    async fn test_connection(&self) -> Result<TestConnectionResult, DriverError> {
         match self.get_connection_with_retry().await {
             Ok(mut conn) => match redis::cmd("PING").query_async::<String>(&mut conn).await {
                 Ok(_) => Ok(TestConnectionResult {
@@ -339,28 +1147,52 @@ impl DatabaseDriver for RedisDriver {
         }
     }
 
-    async fn list_tables(&self) -> Result<Vec<TableInfo>, String> {
+    async fn list_tables(&self) -> Result<Vec<TableInfo>, DriverError> {
         // Redis doesn't have tables, return key count as "info"
-        Ok(vec![TableInfo {
+        let mut tables = vec![TableInfo {
             schema: "redis".to_string(),
             name: "keys".to_string(),
             table_type: "keyspace".to_string(),
-        }])
+        }];
+        // RediSearch indexes are listed as tables of their own schema.
+        tables.extend(
+            self.search_indexes()
+                .await?
+                .into_iter()
+                .map(|name| TableInfo {
+                    schema: SEARCH_SCHEMA.to_string(),
+                    name,
+                    table_type: SEARCH_INDEX_TABLE_TYPE.to_string(),
+                }),
+        );
+        Ok(tables)
     }
 
     async fn get_table_data(
         &self,
-        _schema: &str,
-        _table: &str,
-        _page: i64,
-        _limit: i64,
-        _filter: Option<crate::db::models::TableFilter>,
-        _sort_column: Option<String>,
-        _sort_direction: Option<String>,
-    ) -> Result<TableDataResponse, String> {
+        schema: &str,
+        table: &str,
+        page: i64,
+        limit: i64,
+        filter: Option<crate::db::models::TableFilter>,
+        sort_column: Option<String>,
+        sort_direction: Option<String>,
+    ) -> Result<TableDataResponse, DriverError> {
+        if schema == SEARCH_SCHEMA {
+            if filter.is_some() {
+                return Err(DriverError::new(
+                    DriverErrorCategory::Syntax,
+                    "Search indexes are filtered with a query: run FT.SEARCH from the console",
+                ));
+            }
+            return self
+                .browse_index(table, page, limit, sort_column, sort_direction)
+                .await;
+        }
         // Not applicable for Redis - use search_keys instead
         Ok(TableDataResponse {
             data: vec![],
+            columns: Vec::new(),
             total: 0,
             page: 1,
             limit: 100,
@@ -369,9 +1201,12 @@ impl DatabaseDriver for RedisDriver {
 
     async fn get_table_structure(
         &self,
-        _schema: &str,
-        _table: &str,
-    ) -> Result<TableStructure, String> {
+        schema: &str,
+        table: &str,
+    ) -> Result<TableStructure, DriverError> {
+        if schema == SEARCH_SCHEMA {
+            return Ok(self.search_index(table).await?.structure());
+        }
         // Redis doesn't have table structure
         Ok(TableStructure {
             columns: vec![],
@@ -380,125 +1215,300 @@ impl DatabaseDriver for RedisDriver {
         })
     }
 
-    async fn execute_query(&self, query: &str) -> Result<QueryResult, String> {
-        // For Redis, this is primarily for INFO and other commands
+    async fn execute_query(&self, query: &str) -> Result<QueryResult, DriverError> {
         let start_time = std::time::Instant::now();
-        let mut conn = self.get_connection_with_retry().await?;
-
-        let trimmed_query = query.trim();
-
-        // Handle INFO command
-        if trimmed_query.to_uppercase().starts_with("INFO") {
-            match redis::cmd("INFO")
-                .arg(trimmed_query.strip_prefix("INFO").unwrap_or(""))
-                .query_async::<String>(&mut conn)
-                .await
-            {
-                Ok(info) => {
-                    return Ok(QueryResult {
-                        data: vec![json!({"info": info})],
-                        row_count: 1,
-                        truncated: false,
-                        rows_affected: None,
-                        error: None,
-                        time_taken_ms: Some(start_time.elapsed().as_millis()),
-                    });
-                }
-                Err(e) => {
-                    let error_msg = self.handle_connection_error(&e, "execute_query (INFO)");
-                    return Ok(QueryResult {
-                        data: vec![],
-                        row_count: 0,
-                        truncated: false,
-                        rows_affected: None,
-                        error: Some(error_msg),
-                        time_taken_ms: Some(start_time.elapsed().as_millis()),
-                    });
-                }
-            }
-        }
-
-        // Try to execute as raw Redis command
-        let parts: Vec<&str> = trimmed_query.split_whitespace().collect();
-        if parts.is_empty() {
-            return Ok(QueryResult {
-                data: vec![],
-                row_count: 0,
-                truncated: false,
-                rows_affected: None,
-                error: Some("Empty query".to_string()),
-                time_taken_ms: Some(start_time.elapsed().as_millis()),
-            });
-        }
-
-        let mut cmd = redis::cmd(parts[0]);
-        for part in &parts[1..] {
-            cmd.arg(*part);
-        }
-
-        match cmd.query_async(&mut conn).await {
-            Ok(value) => {
-                let json_value = Self::redis_value_to_json(&value, "unknown");
-                Ok(QueryResult {
-                    data: vec![json_value],
-                    row_count: 1,
-                    truncated: false,
-                    rows_affected: None,
-                    error: None,
-                    time_taken_ms: Some(start_time.elapsed().as_millis()),
-                })
-            }
-            Err(e) => {
-                let error_msg = self.handle_connection_error(&e, "execute_query");
-                Ok(QueryResult {
-                    data: vec![],
-                    row_count: 0,
-                    truncated: false,
-                    rows_affected: None,
-                    error: Some(error_msg),
-                    time_taken_ms: Some(start_time.elapsed().as_millis()),
-                })
-            }
+        match parse_script(query) {
+            Ok(script) => self.execute_script(&script).await,
+            Err(error) => Ok(QueryResult::from_error(
+                DriverError::new(DriverErrorCategory::Syntax, error),
+                start_time,
+            )),
         }
     }
 
-    async fn execute_query_read_only(&self, query: &str) -> Result<QueryResult, String> {
+    async fn execute_query_read_only(&self, query: &str) -> Result<QueryResult, DriverError> {
+        let start_time = std::time::Instant::now();
+        let script = match parse_script(query) {
+            Ok(script) => script,
+            Err(error) => {
+                return Ok(QueryResult::from_error(
+                    DriverError::new(DriverErrorCategory::Syntax, error),
+                    start_time,
+                ))
+            }
+        };
         // Redis has no per-connection read-only mode, so this is a best-effort,
         // subcommand-aware allowlist rather than an engine-enforced guarantee.
-        if !crate::database::redis_read_only::is_read_only_redis_command(query) {
-            let command = query.split_whitespace().next().unwrap_or("").to_uppercase();
-            return Ok(QueryResult {
-                data: vec![],
-                row_count: 0,
-                truncated: false,
-                rows_affected: None,
-                error: Some(format!(
-                    "Read-only mode: '{}' is not an allowed read command.",
-                    command
-                )),
-                time_taken_ms: Some(0),
-            });
+        if let Some(command) = first_write_command(&script) {
+            return Ok(QueryResult::from_error(
+                DriverError::new(
+                    DriverErrorCategory::Permission,
+                    format!(
+                        "Read-only mode: '{}' is not an allowed read command.",
+                        command.name()
+                    ),
+                ),
+                start_time,
+            ));
         }
-        self.execute_query(query).await
+        self.execute_script(&script).await
     }
 
-    async fn get_schema_overview(&self) -> Result<SchemaOverview, String> {
+    async fn get_schema_overview(&self) -> Result<SchemaOverview, DriverError> {
+        let mut tables = Vec::new();
+        for name in self.search_indexes().await? {
+            let structure = self.search_index(&name).await?.structure();
+            tables.push(TableWithStructure {
+                schema: SEARCH_SCHEMA.to_string(),
+                name,
+                table_type: SEARCH_INDEX_TABLE_TYPE.to_string(),
+                columns: structure.columns,
+                foreign_keys: structure.foreign_keys,
+                indexes: structure.indexes,
+            });
+        }
         Ok(SchemaOverview {
-            tables: vec![],
+            tables,
             functions: vec![],
         })
     }
 }
 
 impl RedisDriver {
-    /// Search for keys matching a pattern using the Redis `SCAN` command (non-blocking).
-    ///
-    /// This performs an incremental scan of the keyspace:
-    /// - The scan starts from `start_cursor` (use `0` to start a new scan).
-    /// - On each iteration, a batch of keys matching `pattern` is fetched.
-    /// - The function stops when:
-    ///   - the Redis cursor wraps to `0` (the full SCAN cycle has completed), or
-    ///   - the requested `limit` of keys has been collected, or
+    async fn execute_script(&self, script: &RedisScript) -> Result<QueryResult, DriverError> {
+        let start_time = std::time::Instant::now();
+        // Dropping the multiplexed request on timeout frees the caller; Redis
+        // itself has no way to abort a command that is already running.
+        match self.config.limits.run(false, self.run_script(script)).await {
+            Ok(result) => Ok(result?),
+            Err(error) => Ok(QueryResult::from_error(error, start_time)),
+        }
+    }
+
+    /// Run a console script. A single command returns its decoded reply (INFO
+    /// text wrapped in an object); several commands are sent as one pipeline
+    /// (one by one on a cluster), or one `MULTI`/`EXEC` transaction, and
+    /// return a row per command.
+    async fn run_script(&self, script: &RedisScript) -> Result<QueryResult, String> {
+        let start_time = std::time::Instant::now();
+        let mut conn = self.get_connection_with_retry().await?;
+
+        if let [command] = script.commands.as_slice() {
+            if !script.transaction {
+                return Ok(
+                    match command
+                        .to_cmd()
+                        .query_async::<redis::Value>(&mut conn)
+                        .await
+                    {
+                        Ok(value) => {
+                            let rows = match command.name().as_str() {
+                                "INFO" => Self::info_rows(&value),
+                                "FT.SEARCH" => search_rows(
+                                    &value,
+                                    SearchReplyShape::from_arguments(&command.args),
+                                )
+                                .map(|(_, rows)| rows),
+                                "FT.AGGREGATE" => aggregate_rows(&value).map(|(_, rows)| rows),
+                                _ => None,
+                            }
+                            .unwrap_or_else(|| vec![Self::redis_value_to_json(&value, "unknown")]);
+                            QueryResult::from_rows(rows, false, start_time)
+                        }
+                        Err(e) => QueryResult::from_error(
+                            self.handle_connection_error(&e, "execute_query"),
+                            start_time,
+                        ),
+                    },
+                );
+            }
+        }
+
+        // Reading the raw replies keeps one failed command from hiding the
+        // results of the others.
+        let replies = if matches!(conn, RedisConnection::Cluster(_)) && !script.transaction {
+            // A cluster pipeline must keep to one hash slot, so each command
+            // is sent on its own and routed by its keys.
+            let mut replies = Vec::with_capacity(script.commands.len());
+            for command in &script.commands {
+                match conn.req_packed_command(&command.to_cmd()).await {
+                    Ok(redis::Value::ServerError(error)) => replies.push(Err(error.into())),
+                    Ok(reply) => replies.push(Ok(reply)),
+                    // An error reply fails only its own command.
+                    Err(e) if e.code().is_some() => replies.push(Err(e)),
+                    Err(e) => {
+                        return Ok(QueryResult::from_error(
+                            self.handle_connection_error(&e, "execute_query"),
+                            start_time,
+                        ))
+                    }
+                }
+            }
+            Ok(replies)
+        } else {
+            let mut pipe = redis::pipe();
+            if script.transaction {
+                pipe.atomic();
+            }
+            for command in &script.commands {
+                pipe.add_command(command.to_cmd());
+            }
+            let count = script.commands.len();
+            if script.transaction {
+                conn.req_packed_commands(&pipe, count + 1, 1)
+                    .await
+                    .and_then(|mut replies| match replies.pop() {
+                        Some(redis::Value::Array(replies)) => Ok(replies),
+                        Some(redis::Value::ServerError(error)) => Err(error.into()),
+                        _ => {
+                            Err((redis::ErrorKind::ExecAbortError, "Transaction discarded").into())
+                        }
+                    })
+            } else {
+                conn.req_packed_commands(&pipe, 0, count).await
+            }
+            .map(|replies| {
+                replies
+                    .into_iter()
+                    .map(|reply| match reply {
+                        redis::Value::ServerError(error) => Err(error.into()),
+                        reply => Ok(reply),
+                    })
+                    .collect()
+            })
+        };
+        let replies = match replies {
+            Ok(replies) => replies,
+            Err(e) => {
+                return Ok(QueryResult::from_error(
+                    self.handle_connection_error(&e, "execute_query"),
+                    start_time,
+                ))
+            }
+        };
+
+        let data = script
+            .commands
+            .iter()
+            .zip(replies)
+            .map(|(command, reply)| match reply {
+                Ok(reply) => json!({
+                    "command": command.display(),
+                    "result": Self::redis_value_to_json(&reply, "unknown"),
+                }),
+                Err(error) => json!({
+                    "command": command.display(),
+                    "error": redis_error(&error).message,
+                }),
+            })
+            .collect();
+        Ok(QueryResult::from_rows(data, false, start_time))
+    }
+
+    /// INFO in cluster mode is answered by every node, as a map from the
+    /// node's address to its report; that becomes one row per node.
+    fn info_rows(value: &redis::Value) -> Option<Vec<Value>> {
+        match value {
+            redis::Value::Map(nodes) => nodes
+                .iter()
+                .map(|(node, info)| {
+                    Some(
+                        json!({ "node": Self::reply_text(node)?, "info": Self::reply_text(info)? }),
+                    )
+                })
+                .collect(),
+            value => Some(vec![json!({ "info": Self::reply_text(value)? })]),
+        }
+    }
+
+    /// The nodes that hold keys: every master of a cluster, or the server
+    /// itself (`None`).
+    async fn key_nodes(
+        &self,
+        conn: &mut RedisConnection,
+        operation: &str,
+    ) -> Result<Vec<Option<NodeAddress>>, DriverError> {
+        let RedisConnection::Cluster(cluster) = conn else {
+            return Ok(vec![None]);
+        };
+        let nodes = redis::cmd("CLUSTER")
+            .arg("NODES")
+            .query_async::<String>(cluster)
+            .await
+            .map_err(|e| self.handle_connection_error(&e, operation))?;
+        let masters = cluster_masters(&nodes);
+        if masters.is_empty() {
+            return Err(DriverError::new(
+                DriverErrorCategory::Connection,
+                "The cluster reported no reachable masters",
+            ));
+        }
+        Ok(masters.into_iter().map(Some).collect())
+    }
+
+    /// Send `cmd` to one node of a cluster rather than routing it by key.
+    async fn query_node<T: redis::FromRedisValue>(
+        conn: &mut RedisConnection,
+        node: &Option<NodeAddress>,
+        cmd: &redis::Cmd,
+    ) -> redis::RedisResult<T> {
+        match (conn, node) {
+            (RedisConnection::Cluster(cluster), Some((host, port))) => cluster
+                .route_command(
+                    cmd,
+                    RoutingInfo::SingleNode(SingleNodeRoutingInfo::ByAddress {
+                        host: host.clone(),
+                        port: *port,
+                    }),
+                )
+                .await
+                .and_then(redis::from_owned_redis_value),
+            (conn, _) => cmd.query_async(conn).await,
+        }
+    }
+
+    /// Send a pipeline to one node, returning the raw reply of each command.
+    async fn pipeline_on_node(
+        conn: &mut RedisConnection,
+        node: &Option<NodeAddress>,
+        pipe: &redis::Pipeline,
+        count: usize,
+    ) -> redis::RedisResult<Vec<redis::Value>> {
+        match (conn, node) {
+            (RedisConnection::Cluster(cluster), Some((host, port))) => {
+                cluster
+                    .route_pipeline(
+                        pipe,
+                        0,
+                        count,
+                        SingleNodeRoutingInfo::ByAddress {
+                            host: host.clone(),
+                            port: *port,
+                        },
+                    )
+                    .await
+            }
+            (conn, _) => conn.req_packed_commands(pipe, 0, count).await,
+        }
+    }
+
+    fn reply_text(value: &redis::Value) -> Option<String> {
+        match value {
+            redis::Value::BulkString(bytes) => String::from_utf8(bytes.clone()).ok(),
+            redis::Value::VerbatimString { text, .. } => Some(text.clone()),
+            redis::Value::SimpleString(text) => Some(text.clone()),
+            _ => None,
+        }
+    }
+
+    /// Search for keys matching a pattern using the Redis `SCAN` command (non-blocking).
+    ///
+    /// This performs an incremental scan of the keyspace:
+    /// - The scan starts from `start_cursor` (use `0` to start a new scan).
+    /// - On each iteration, a batch of keys matching `pattern` is fetched.
+    /// - The function stops when:
+    ///   - the Redis cursor wraps to `0` (the full SCAN cycle has completed), or
+    ///   - the requested `limit` of keys has been collected, or
     ///   - an internal `max_iterations` threshold is reached (to avoid scanning the entire keyspace in one call).
     ///
     /// Only key names are returned for fast initial loading; metadata such as type, TTL, and size
@@ -513,6 +1523,10 @@ impl RedisDriver {
     /// The returned [`RedisKeyListResponse`] includes:
     /// - `cursor`: the next cursor to use for continuing the scan (or `0` if the scan has completed).
     /// - `scan_complete`: `true` if the underlying SCAN cycle has finished (cursor is `0`), `false` otherwise.
+    ///
+    /// In cluster mode every master is scanned in turn. The returned cursor
+    /// then packs the node's own cursor and the node's index (see
+    /// [`split_cluster_cursor`]), so it stays opaque to callers.
     pub async fn search_keys<F>(
         &self,
         pattern: &str,
@@ -525,23 +1539,25 @@ impl RedisDriver {
     {
         let start_time = std::time::Instant::now();
         let mut conn = self.get_connection_with_retry().await?;
+        let nodes = self.key_nodes(&mut conn, "search_keys").await?;
+        let node_count = nodes.len() as u64;
+        let (mut cursor, mut node) = split_cluster_cursor(start_cursor, node_count);
 
         // Use SCAN instead of KEYS for better performance on large keyspaces
         // SCAN is non-blocking and iterates incrementally
         let mut keys: Vec<String> = Vec::new();
-        let mut cursor: u64 = start_cursor;
         let count_per_scan = 100; // Number of keys to scan per iteration
         let max_iterations: u32 = 100; // Max iterations to prevent scanning entire keyspace
         let mut iterations: u32 = 0;
 
         loop {
-            match redis::cmd("SCAN")
-                .arg(cursor)
+            let mut scan = redis::cmd("SCAN");
+            scan.arg(cursor)
                 .arg("MATCH")
                 .arg(pattern)
                 .arg("COUNT")
-                .arg(count_per_scan)
-                .query_async::<(u64, Vec<String>)>(&mut conn)
+                .arg(count_per_scan);
+            match Self::query_node::<(u64, Vec<String>)>(&mut conn, &nodes[node as usize], &scan)
                 .await
             {
                 Ok((new_cursor, batch)) => {
@@ -556,18 +1572,29 @@ impl RedisDriver {
                     keys.extend(batch);
                     cursor = new_cursor;
                     iterations += 1;
+                    if cursor == 0 {
+                        node += 1;
+                    }
 
                     // Stop if we've reached the limit, completed the scan, or hit max iterations
-                    if cursor == 0 || keys.len() >= limit as usize || iterations >= max_iterations {
+                    if node >= node_count
+                        || keys.len() >= limit as usize
+                        || iterations >= max_iterations
+                    {
                         break;
                     }
                 }
                 Err(e) => {
-                    return Err(self.handle_connection_error(&e, "search_keys"));
+                    return Err(self.handle_connection_error(&e, "search_keys").into());
                 }
             }
         }
 
+        let cursor = if node >= node_count {
+            0
+        } else {
+            cursor * node_count + node
+        };
         let scan_complete = cursor == 0;
 
         // Apply limit and create key infos with placeholder values
@@ -600,7 +1627,9 @@ impl RedisDriver {
         let exists: bool = match conn.exists(key).await {
             Ok(exists) => exists,
             Err(e) => {
-                return Err(self.handle_connection_error(&e, "get_key_details (exists)"));
+                return Err(self
+                    .handle_connection_error(&e, "get_key_details (exists)")
+                    .into());
             }
         };
 
@@ -612,7 +1641,9 @@ impl RedisDriver {
         let key_type: String = match conn.key_type(key).await {
             Ok(kt) => kt,
             Err(e) => {
-                return Err(self.handle_connection_error(&e, "get_key_details (key_type)"));
+                return Err(self
+                    .handle_connection_error(&e, "get_key_details (key_type)")
+                    .into());
             }
         };
 
@@ -647,6 +1678,14 @@ impl RedisDriver {
                 // Streams are complex, return a placeholder
                 json!("<stream data - use XREAD command>")
             }
+            JSON_KEY_TYPE => redis::cmd("JSON.GET")
+                .arg(key)
+                .query_async::<Option<String>>(&mut conn)
+                .await
+                .ok()
+                .flatten()
+                .and_then(|document| serde_json::from_str(&document).ok())
+                .unwrap_or(Value::Null),
             _ => json!(null),
         };
 
@@ -669,6 +1708,7 @@ impl RedisDriver {
             "set" => conn.scard(key).await.ok().map(|c: usize| c),
             "zset" => conn.zcard(key).await.ok().map(|c: usize| c),
             "hash" => conn.hlen(key).await.ok().map(|c: usize| c),
+            JSON_KEY_TYPE => json_length(&value),
             _ => None,
         };
 
@@ -715,7 +1755,7 @@ impl RedisDriver {
 
         match result {
             Ok(_) => Ok(()),
-            Err(e) => Err(self.handle_connection_error(&e, "set_key")),
+            Err(e) => Err(self.handle_connection_error(&e, "set_key").into()),
         }
     }
 
@@ -943,4 +1983,1151 @@ impl RedisDriver {
 
         Ok(())
     }
+
+    /// Run a live subscription until the connection fails. Pub/Sub puts its
+    /// connection in subscriber mode, and a blocking `XREAD` would hold up
+    /// every request multiplexed with it, so both open a connection of their
+    /// own rather than using the cached one.
+    pub async fn watch(&self, source: &LiveSource, sink: LiveSink<'_>) -> Result<(), DriverError> {
+        source
+            .validate()
+            .map_err(|e| DriverError::new(DriverErrorCategory::Other, e))?;
+        match source {
+            LiveSource::Channels { channels, patterns } => {
+                self.watch_channels(channels, patterns, sink).await
+            }
+            LiveSource::Monitor { duration_secs } => {
+                self.watch_monitor(std::time::Duration::from_secs(*duration_secs), sink)
+                    .await
+            }
+            LiveSource::Stream {
+                key,
+                start_id,
+                group,
+            } => {
+                let mut conn = self.create_connection().await?;
+                match group {
+                    Some(group) => {
+                        Self::watch_stream_group(&mut conn, key, start_id.as_deref(), group, sink)
+                            .await
+                    }
+                    None => Self::watch_stream(&mut conn, key, start_id.as_deref(), sink).await,
+                }
+            }
+        }
+    }
+
+    /// Open a Pub/Sub or `MONITOR` connection within the connect timeout.
+    async fn connect_dedicated<T>(
+        &self,
+        connect: impl std::future::Future<Output = Result<T, String>>,
+    ) -> Result<T, DriverError> {
+        match tokio::time::timeout(self.config.limits.connect_timeout, connect).await {
+            Ok(Ok(connection)) => Ok(connection),
+            Ok(Err(e)) => Err(DriverError::new(
+                DriverErrorCategory::Connection,
+                format!("Failed to connect to Redis: {}", e),
+            )),
+            Err(_) => Err(DriverError::new(
+                DriverErrorCategory::Timeout,
+                self.config.limits.connect_timeout_message(),
+            )),
+        }
+    }
+
+    /// Report every command the server runs until `duration` is up.
+    async fn watch_monitor(
+        &self,
+        duration: std::time::Duration,
+        sink: LiveSink<'_>,
+    ) -> Result<(), DriverError> {
+        let mut monitor = self
+            .connect_dedicated(async {
+                self.node_client()
+                    .await?
+                    .get_async_monitor()
+                    .await
+                    .map_err(|e| e.to_string())
+            })
+            .await?;
+        monitor
+            .monitor()
+            .await
+            .map_err(|e| redis_error(&e).context("Failed to start MONITOR"))?;
+
+        let deadline = tokio::time::Instant::now() + duration;
+        let mut lines = monitor.on_message::<String>();
+        loop {
+            match tokio::time::timeout_at(deadline, lines.next()).await {
+                Err(_) => return Ok(()),
+                Ok(None) => {
+                    return Err(DriverError::new(
+                        DriverErrorCategory::Connection,
+                        "The MONITOR connection was closed",
+                    ))
+                }
+                Ok(Some(line)) => {
+                    if let Some(event) = parse_monitor_line(&line) {
+                        sink(LiveMessage {
+                            source: event.client.clone(),
+                            pattern: None,
+                            id: None,
+                            payload: json!(event),
+                            received_at: chrono::Utc::now().to_rfc3339(),
+                        });
+                    }
+                }
+            }
+        }
+    }
+
+    /// Pub/Sub messages are broadcast to every node of a cluster, so one
+    /// subscriber connection to a seed node sees all of them.
+    async fn watch_channels(
+        &self,
+        channels: &[String],
+        patterns: &[String],
+        sink: LiveSink<'_>,
+    ) -> Result<(), DriverError> {
+        let mut pubsub = self
+            .connect_dedicated(async {
+                self.node_client()
+                    .await?
+                    .get_async_pubsub()
+                    .await
+                    .map_err(|e| e.to_string())
+            })
+            .await?;
+        for channel in channels {
+            pubsub
+                .subscribe(channel)
+                .await
+                .map_err(|e| redis_error(&e).context("Failed to subscribe"))?;
+        }
+        for pattern in patterns {
+            pubsub
+                .psubscribe(pattern)
+                .await
+                .map_err(|e| redis_error(&e).context("Failed to subscribe"))?;
+        }
+
+        let mut messages = pubsub.on_message();
+        while let Some(message) = messages.next().await {
+            let payload = redis::Value::BulkString(message.get_payload_bytes().to_vec());
+            sink(LiveMessage {
+                source: message.get_channel_name().to_string(),
+                pattern: message
+                    .from_pattern()
+                    .then(|| message.get_pattern::<String>().ok())
+                    .flatten(),
+                id: None,
+                payload: Self::redis_value_to_json(&payload, "string"),
+                received_at: chrono::Utc::now().to_rfc3339(),
+            });
+        }
+        Err(DriverError::new(
+            DriverErrorCategory::Connection,
+            "The Pub/Sub connection was closed",
+        ))
+    }
+
+    /// Tail a stream with `XREAD BLOCK`. `$` is resolved to the current last
+    /// ID up front: re-sending `$` after each timeout would skip entries
+    /// added between two reads.
+    async fn watch_stream(
+        conn: &mut RedisConnection,
+        key: &str,
+        start_id: Option<&str>,
+        sink: LiveSink<'_>,
+    ) -> Result<(), DriverError> {
+        let mut last_id = match start_id {
+            Some(id) if id != "$" => id.to_string(),
+            _ => {
+                let newest: Vec<(String, redis::Value)> = redis::cmd("XREVRANGE")
+                    .arg(key)
+                    .arg("+")
+                    .arg("-")
+                    .arg("COUNT")
+                    .arg(1)
+                    .query_async(conn)
+                    .await
+                    .map_err(|e| redis_error(&e).context("Failed to read the stream"))?;
+                newest
+                    .into_iter()
+                    .next()
+                    .map_or_else(|| "0-0".to_string(), |(id, _)| id)
+            }
+        };
+
+        loop {
+            let reply = redis::cmd("XREAD")
+                .arg("COUNT")
+                .arg(STREAM_READ_COUNT)
+                .arg("BLOCK")
+                .arg(STREAM_BLOCK_MS)
+                .arg("STREAMS")
+                .arg(key)
+                .arg(&last_id)
+                .query_async::<redis::Value>(conn)
+                .await
+                .map_err(|e| redis_error(&e).context("Failed to read the stream"))?;
+            for (id, fields) in stream_entries(reply) {
+                sink(Self::stream_message(key, &id, fields));
+                last_id = id;
+            }
+        }
+    }
+
+    /// Read a stream as a member of a consumer group, creating the group (and
+    /// the stream) when it does not exist yet.
+    async fn watch_stream_group(
+        conn: &mut RedisConnection,
+        key: &str,
+        start_id: Option<&str>,
+        group: &ConsumerGroup,
+        sink: LiveSink<'_>,
+    ) -> Result<(), DriverError> {
+        let created = redis::cmd("XGROUP")
+            .arg("CREATE")
+            .arg(key)
+            .arg(&group.group)
+            .arg(start_id.unwrap_or("$"))
+            .arg("MKSTREAM")
+            .query_async::<()>(conn)
+            .await;
+        match created {
+            Err(e) if e.code() != Some("BUSYGROUP") => {
+                return Err(redis_error(&e).context("Failed to create the consumer group"))
+            }
+            _ => {}
+        }
+
+        loop {
+            let reply = redis::cmd("XREADGROUP")
+                .arg("GROUP")
+                .arg(&group.group)
+                .arg(&group.consumer)
+                .arg("COUNT")
+                .arg(STREAM_READ_COUNT)
+                .arg("BLOCK")
+                .arg(STREAM_BLOCK_MS)
+                .arg("STREAMS")
+                .arg(key)
+                .arg(">")
+                .query_async::<redis::Value>(conn)
+                .await
+                .map_err(|e| redis_error(&e).context("Failed to read the stream"))?;
+            let entries = stream_entries(reply);
+            let ids: Vec<String> = entries.iter().map(|(id, _)| id.clone()).collect();
+            for (id, fields) in entries {
+                sink(Self::stream_message(key, &id, fields));
+            }
+            if group.ack && !ids.is_empty() {
+                redis::cmd("XACK")
+                    .arg(key)
+                    .arg(&group.group)
+                    .arg(&ids)
+                    .query_async::<i64>(conn)
+                    .await
+                    .map_err(|e| redis_error(&e).context("Failed to acknowledge entries"))?;
+            }
+        }
+    }
+
+    fn stream_message(key: &str, id: &str, fields: Vec<(String, redis::Value)>) -> LiveMessage {
+        let payload = serde_json::Map::from_iter(
+            fields
+                .into_iter()
+                .map(|(field, value)| (field, Self::redis_value_to_json(&value, "stream"))),
+        );
+        LiveMessage {
+            source: key.to_string(),
+            pattern: None,
+            id: Some(id.to_string()),
+            payload: Value::Object(payload),
+            received_at: chrono::Utc::now().to_rfc3339(),
+        }
+    }
+
+    /// Walk every key of every node with `SCAN`, pipelining `TYPE`,
+    /// `MEMORY USAGE`, `TTL` and `OBJECT ENCODING` per batch. Uses a
+    /// connection of its own so a long analysis does not hold up the browser.
+    /// Servers that disable `MEMORY` or `OBJECT` still get a report of types
+    /// and TTLs.
+    pub async fn analyze_keyspace(
+        &self,
+        options: &AnalysisOptions,
+        progress: AnalysisProgressSink<'_>,
+    ) -> Result<KeyspaceReport, DriverError> {
+        let mut conn = self.create_connection().await?;
+        let nodes = self.key_nodes(&mut conn, "analyze_keyspace").await?;
+        let mut total_keys = 0;
+        for node in &nodes {
+            total_keys += Self::query_node::<u64>(&mut conn, node, &redis::cmd("DBSIZE"))
+                .await
+                .map_err(|e| redis_error(&e).context("Failed to count keys"))?;
+        }
+
+        let pattern = options.pattern.as_deref().unwrap_or("*");
+        let mut analyzer = KeyspaceAnalyzer::new(options);
+        let mut detailed = true;
+        for node in &nodes {
+            let mut cursor = 0u64;
+            loop {
+                let (next, keys) = Self::scan_batch(&mut conn, node, pattern, cursor).await?;
+
+                if !keys.is_empty() {
+                    let replies = match Self::describe_keys(&mut conn, node, &keys, detailed).await
+                    {
+                        Err(e) if detailed && e.kind() != redis::ErrorKind::IoError => {
+                            detailed = false;
+                            Self::describe_keys(&mut conn, node, &keys, detailed).await
+                        }
+                        replies => replies,
+                    }
+                    .map_err(|e| redis_error(&e).context("Failed to inspect keys"))?;
+                    let per_key = replies.len() / keys.len();
+                    for (key, replies) in keys.iter().zip(replies.chunks(per_key)) {
+                        if let Some(sample) = key_sample(key, replies) {
+                            analyzer.add(sample);
+                        }
+                    }
+                    progress(&AnalysisProgress {
+                        scanned_keys: analyzer.scanned_keys(),
+                        total_keys,
+                        memory_bytes: analyzer.total_memory(),
+                    });
+                }
+
+                cursor = next;
+                if cursor == 0 {
+                    break;
+                }
+            }
+        }
+        Ok(analyzer.report())
+    }
+
+    /// Pipeline `TYPE` and `TTL` for each key, plus `MEMORY USAGE` and
+    /// `OBJECT ENCODING` when `detailed`, in that order.
+    async fn describe_keys(
+        conn: &mut RedisConnection,
+        node: &Option<NodeAddress>,
+        keys: &[Vec<u8>],
+        detailed: bool,
+    ) -> redis::RedisResult<Vec<redis::Value>> {
+        let mut pipe = redis::pipe();
+        for key in keys {
+            pipe.cmd("TYPE").arg(key).cmd("TTL").arg(key);
+            if detailed {
+                pipe.cmd("MEMORY")
+                    .arg("USAGE")
+                    .arg(key)
+                    .cmd("OBJECT")
+                    .arg("ENCODING")
+                    .arg(key);
+            }
+        }
+        let count = keys.len() * if detailed { 4 } else { 2 };
+        Self::pipeline_on_node(conn, node, &pipe, count).await
+    }
+
+    /// One `SCAN` step on one node, with binary-safe key names.
+    async fn scan_batch(
+        conn: &mut RedisConnection,
+        node: &Option<NodeAddress>,
+        pattern: &str,
+        cursor: u64,
+    ) -> Result<(u64, Vec<Vec<u8>>), DriverError> {
+        let mut scan = redis::cmd("SCAN");
+        scan.arg(cursor)
+            .arg("MATCH")
+            .arg(pattern)
+            .arg("COUNT")
+            .arg(KEY_SCAN_COUNT);
+        Self::query_node(conn, node, &scan)
+            .await
+            .map_err(|e| redis_error(&e).context("Failed to scan keys"))
+    }
+
+    /// Count the keys matching `pattern` on every node, keeping the first
+    /// `sample_size`. `SCAN` can return a key twice while the keyspace is
+    /// rehashed, so the count is approximate.
+    pub async fn match_keys(
+        &self,
+        pattern: &str,
+        sample_size: usize,
+    ) -> Result<KeyMatches, DriverError> {
+        let mut conn = self.create_connection().await?;
+        let nodes = self.key_nodes(&mut conn, "match_keys").await?;
+        let mut matches = KeyMatches::default();
+        for node in &nodes {
+            let mut cursor = 0u64;
+            loop {
+                let (next, keys) = Self::scan_batch(&mut conn, node, pattern, cursor).await?;
+                matches.count += keys.len() as u64;
+                let room = sample_size.saturating_sub(matches.sample.len());
+                matches.sample.extend(keys.into_iter().take(room));
+                cursor = next;
+                if cursor == 0 {
+                    break;
+                }
+            }
+        }
+        Ok(matches)
+    }
+
+    /// Apply a bulk operation to every key matching its pattern, one `SCAN`
+    /// batch at a time, on a connection of its own. Each batch is changed in
+    /// one pipeline, deleting with `UNLINK` so large values are freed in the
+    /// background. Keys copied to another connection are read with `DUMP`
+    /// and written by `target`.
+    pub async fn bulk_update(
+        &self,
+        request: &BulkKeyRequest,
+        target: Option<&dyn KeyValueBrowser>,
+        progress: BulkProgressSink<'_>,
+    ) -> Result<BulkKeyReport, DriverError> {
+        request
+            .validate()
+            .map_err(|e| DriverError::new(DriverErrorCategory::Other, e))?;
+        if request.operation.target_connection().is_some() && target.is_none() {
+            return Err(DriverError::new(
+                DriverErrorCategory::Other,
+                "The target connection is not available",
+            ));
+        }
+        let started = std::time::Instant::now();
+        let mut conn = self.create_connection().await?;
+        if let Some(KeyTarget::Database { db }) = request.operation.target() {
+            if matches!(conn, RedisConnection::Cluster(_)) {
+                return Err(DriverError::new(
+                    DriverErrorCategory::Other,
+                    "Redis Cluster only has database 0",
+                ));
+            }
+            if *db == self.config.db.unwrap_or(0) {
+                return Err(DriverError::new(
+                    DriverErrorCategory::Other,
+                    format!("Keys are already in database {}", db),
+                ));
+            }
+        }
+
+        let nodes = self.key_nodes(&mut conn, "bulk_update").await?;
+        let mut report = BulkKeyReport::default();
+        for node in &nodes {
+            let mut cursor = 0u64;
+            loop {
+                let (next, keys) =
+                    Self::scan_batch(&mut conn, node, &request.pattern, cursor).await?;
+                if !keys.is_empty() {
+                    let outcomes =
+                        Self::update_keys(&mut conn, node, &keys, &request.operation, target)
+                            .await?;
+                    for (key, outcome) in keys.iter().zip(outcomes) {
+                        report.record(key, outcome);
+                    }
+                    progress(&report);
+                }
+                cursor = next;
+                if cursor == 0 {
+                    break;
+                }
+            }
+        }
+        report.elapsed_ms = started.elapsed().as_millis();
+        Ok(report)
+    }
+
+    /// Apply `operation` to one batch of keys held by `node`.
+    async fn update_keys(
+        conn: &mut RedisConnection,
+        node: &Option<NodeAddress>,
+        keys: &[Vec<u8>],
+        operation: &BulkKeyOperation,
+        target: Option<&dyn KeyValueBrowser>,
+    ) -> Result<Vec<KeyOutcome>, DriverError> {
+        let per_key =
+            |name: &str, args: &dyn Fn(&mut redis::Cmd, &[u8])| -> Vec<Option<redis::Cmd>> {
+                keys.iter()
+                    .map(|key| {
+                        let mut cmd = redis::cmd(name);
+                        cmd.arg(key);
+                        args(&mut cmd, key);
+                        Some(cmd)
+                    })
+                    .collect()
+            };
+        let commands = match operation {
+            BulkKeyOperation::Delete => per_key("UNLINK", &|_, _| {}),
+            BulkKeyOperation::SetTtl { ttl } => per_key("EXPIRE", &|cmd, _| {
+                cmd.arg(*ttl);
+            }),
+            BulkKeyOperation::ClearTtl => per_key("PERSIST", &|_, _| {}),
+            BulkKeyOperation::Rename { replace, .. } => keys
+                .iter()
+                .map(|key| {
+                    let renamed = operation.renamed(key)?;
+                    let mut cmd = redis::cmd(if *replace { "RENAME" } else { "RENAMENX" });
+                    cmd.arg(key).arg(renamed);
+                    Some(cmd)
+                })
+                .collect(),
+            // `MOVE` cannot replace an existing key, so a replacing move
+            // copies and then deletes.
+            BulkKeyOperation::Move {
+                target: KeyTarget::Database { db },
+                replace: false,
+            } => per_key("MOVE", &|cmd, _| {
+                cmd.arg(*db);
+            }),
+            BulkKeyOperation::Copy {
+                target: KeyTarget::Database { db },
+                replace,
+            }
+            | BulkKeyOperation::Move {
+                target: KeyTarget::Database { db },
+                replace,
+            } => per_key("COPY", &|cmd, key| {
+                cmd.arg(key).arg("DB").arg(*db);
+                if *replace {
+                    cmd.arg("REPLACE");
+                }
+            }),
+            BulkKeyOperation::Copy {
+                target: KeyTarget::Connection { .. },
+                replace,
+            }
+            | BulkKeyOperation::Move {
+                target: KeyTarget::Connection { .. },
+                replace,
+            } => {
+                let target = target.ok_or_else(|| {
+                    DriverError::new(
+                        DriverErrorCategory::Other,
+                        "The target connection is not available",
+                    )
+                })?;
+                return Self::copy_keys(
+                    conn,
+                    node,
+                    keys,
+                    target,
+                    *replace,
+                    matches!(operation, BulkKeyOperation::Move { .. }),
+                )
+                .await;
+            }
+        };
+        let outcomes: Vec<KeyOutcome> = Self::pipeline_per_key(conn, node, &commands)
+            .await?
+            .into_iter()
+            .map(|reply| reply.map_or(KeyOutcome::Skipped, |reply| key_outcome(Ok(reply))))
+            .collect();
+
+        if let BulkKeyOperation::Move {
+            target: KeyTarget::Database { .. },
+            replace: true,
+        } = operation
+        {
+            Self::unlink_copied(conn, node, keys, &outcomes).await?;
+        }
+        Ok(outcomes)
+    }
+
+    /// Copy keys to another connection with `DUMP`, keeping their TTLs, and
+    /// delete the copied ones when moving.
+    async fn copy_keys(
+        conn: &mut RedisConnection,
+        node: &Option<NodeAddress>,
+        keys: &[Vec<u8>],
+        target: &dyn KeyValueBrowser,
+        replace: bool,
+        delete_copied: bool,
+    ) -> Result<Vec<KeyOutcome>, DriverError> {
+        let mut pipe = redis::pipe();
+        for key in keys {
+            pipe.cmd("PTTL").arg(key).cmd("DUMP").arg(key);
+        }
+        let replies = Self::pipeline_on_node(conn, node, &pipe, keys.len() * 2)
+            .await
+            .map_err(|e| redis_error(&e).context("Failed to dump keys"))?;
+
+        let mut outcomes = Vec::with_capacity(keys.len());
+        let mut dumped = Vec::new();
+        for (key, replies) in keys.iter().zip(replies.chunks(2)) {
+            match replies {
+                [redis::Value::Int(ttl), redis::Value::BulkString(payload)] if *ttl != -2 => {
+                    outcomes.push(None);
+                    dumped.push(DumpedKey {
+                        key: key.clone(),
+                        // A TTL about to run out must not become "no expiry".
+                        ttl_ms: if *ttl < 0 { 0 } else { (*ttl).max(1) },
+                        payload: payload.clone(),
+                    });
+                }
+                [_, redis::Value::ServerError(error)] | [redis::Value::ServerError(error), _] => {
+                    outcomes.push(Some(key_outcome(Err(error.clone().into()))))
+                }
+                _ => outcomes.push(Some(KeyOutcome::Skipped)),
+            }
+        }
+        let mut restored = target.restore_keys(&dumped, replace).await?.into_iter();
+        let outcomes: Vec<KeyOutcome> = outcomes
+            .into_iter()
+            .map(|outcome| {
+                outcome.unwrap_or_else(|| restored.next().unwrap_or(KeyOutcome::Skipped))
+            })
+            .collect();
+
+        if delete_copied {
+            Self::unlink_copied(conn, node, keys, &outcomes).await?;
+        }
+        Ok(outcomes)
+    }
+
+    /// Delete the keys of a move that reached their target.
+    async fn unlink_copied(
+        conn: &mut RedisConnection,
+        node: &Option<NodeAddress>,
+        keys: &[Vec<u8>],
+        outcomes: &[KeyOutcome],
+    ) -> Result<(), DriverError> {
+        let commands: Vec<Option<redis::Cmd>> = keys
+            .iter()
+            .zip(outcomes)
+            .map(|(key, outcome)| {
+                (*outcome == KeyOutcome::Done).then(|| {
+                    let mut cmd = redis::cmd("UNLINK");
+                    cmd.arg(key);
+                    cmd
+                })
+            })
+            .collect();
+        Self::pipeline_per_key(conn, node, &commands).await?;
+        Ok(())
+    }
+
+    /// Pipeline the commands that are present, returning each one's reply in
+    /// the same position.
+    async fn pipeline_per_key(
+        conn: &mut RedisConnection,
+        node: &Option<NodeAddress>,
+        commands: &[Option<redis::Cmd>],
+    ) -> Result<Vec<Option<redis::Value>>, DriverError> {
+        let mut pipe = redis::pipe();
+        let mut count = 0;
+        for cmd in commands.iter().flatten() {
+            pipe.add_command(cmd.clone());
+            count += 1;
+        }
+        if count == 0 {
+            return Ok(vec![None; commands.len()]);
+        }
+        let mut replies = Self::pipeline_on_node(conn, node, &pipe, count)
+            .await
+            .map_err(|e| redis_error(&e).context("Failed to update keys"))?
+            .into_iter();
+        Ok(commands
+            .iter()
+            .map(|cmd| cmd.as_ref().and_then(|_| replies.next()))
+            .collect())
+    }
+
+    /// Write keys dumped from another connection with `RESTORE`. A cluster
+    /// routes each key to its own slot, so keys are restored one command at
+    /// a time there rather than in a single pipeline.
+    pub async fn restore_keys(
+        &self,
+        keys: &[DumpedKey],
+        replace: bool,
+    ) -> Result<Vec<KeyOutcome>, DriverError> {
+        if keys.is_empty() {
+            return Ok(Vec::new());
+        }
+        let commands: Vec<redis::Cmd> = keys
+            .iter()
+            .map(|key| {
+                let mut cmd = redis::cmd("RESTORE");
+                cmd.arg(&key.key).arg(key.ttl_ms).arg(&key.payload);
+                if replace {
+                    cmd.arg("REPLACE");
+                }
+                cmd
+            })
+            .collect();
+
+        let mut conn = self.get_connection_with_retry().await?;
+        match &mut conn {
+            RedisConnection::Cluster(cluster) => {
+                let replies = futures_util::future::join_all(commands.iter().map(|cmd| {
+                    let mut cluster = cluster.clone();
+                    async move { cmd.query_async::<redis::Value>(&mut cluster).await }
+                }))
+                .await;
+                Ok(replies.into_iter().map(key_outcome).collect())
+            }
+            conn => {
+                let mut pipe = redis::pipe();
+                for cmd in commands {
+                    pipe.add_command(cmd);
+                }
+                let replies = conn
+                    .req_packed_commands(&pipe, 0, keys.len())
+                    .await
+                    .map_err(|e| self.handle_connection_error(&e, "restore_keys"))?;
+                Ok(replies
+                    .into_iter()
+                    .map(|reply| key_outcome(Ok(reply)))
+                    .collect())
+            }
+        }
+    }
+
+    /// Run `cmd` on every node that holds keys, parsing each reply with
+    /// `parse` and the node's label (`None` for a single server).
+    async fn on_each_node<T, R: redis::FromRedisValue>(
+        &self,
+        cmd: &redis::Cmd,
+        operation: &str,
+        parse: impl Fn(R, Option<&str>) -> Vec<T>,
+    ) -> Result<Vec<T>, DriverError> {
+        let mut conn = self.get_connection_with_retry().await?;
+        let nodes = self.key_nodes(&mut conn, operation).await?;
+        let mut rows = Vec::new();
+        for node in &nodes {
+            let reply = Self::query_node::<R>(&mut conn, node, cmd)
+                .await
+                .map_err(|e| self.handle_connection_error(&e, operation))?;
+            let label = node.as_ref().map(|(host, port)| format!("{host}:{port}"));
+            rows.extend(parse(reply, label.as_deref()));
+        }
+        Ok(rows)
+    }
+
+    /// The newest `count` slow log entries of each node, newest first.
+    pub async fn slowlog(&self, count: usize) -> Result<Vec<SlowlogEntry>, DriverError> {
+        let mut cmd = redis::cmd("SLOWLOG");
+        cmd.arg("GET").arg(count);
+        let mut entries = self
+            .on_each_node(&cmd, "slowlog", |reply: redis::Value, node| {
+                parse_slowlog(&reply, node)
+            })
+            .await?;
+        entries.sort_by(|a, b| b.timestamp.cmp(&a.timestamp).then(b.id.cmp(&a.id)));
+        Ok(entries)
+    }
+
+    pub async fn latency_latest(&self) -> Result<Vec<LatencyEvent>, DriverError> {
+        let mut cmd = redis::cmd("LATENCY");
+        cmd.arg("LATEST");
+        self.on_each_node(&cmd, "latency_latest", |reply: redis::Value, node| {
+            parse_latency_latest(&reply, node)
+        })
+        .await
+    }
+
+    pub async fn latency_history(&self, event: &str) -> Result<Vec<LatencySample>, DriverError> {
+        let mut cmd = redis::cmd("LATENCY");
+        cmd.arg("HISTORY").arg(event);
+        self.on_each_node(&cmd, "latency_history", |reply: redis::Value, node| {
+            parse_latency_history(&reply, node)
+        })
+        .await
+    }
+
+    pub async fn client_list(&self) -> Result<Vec<ClientInfo>, DriverError> {
+        let mut cmd = redis::cmd("CLIENT");
+        cmd.arg("LIST");
+        self.on_each_node(&cmd, "client_list", |reply: String, node| {
+            parse_client_list(&reply, node)
+        })
+        .await
+    }
+
+    /// Kill a client after checking that `id` still belongs to the client
+    /// at `addr` (ids are not reused, but the user confirmed an address) and
+    /// that it is not the connection DBcooper itself is using.
+    pub async fn kill_client(
+        &self,
+        node: Option<&str>,
+        id: u64,
+        addr: &str,
+    ) -> Result<bool, DriverError> {
+        let mut conn = self.get_connection_with_retry().await?;
+        let nodes = self.key_nodes(&mut conn, "kill_client").await?;
+        let node = match node {
+            None if nodes.len() == 1 => &nodes[0],
+            None => {
+                return Err(DriverError::new(
+                    DriverErrorCategory::Other,
+                    "Choose the cluster node the client is connected to",
+                ))
+            }
+            Some(label) => nodes
+                .iter()
+                .find(|node| {
+                    node.as_ref()
+                        .is_some_and(|(host, port)| format!("{host}:{port}") == label)
+                })
+                .ok_or_else(|| {
+                    DriverError::new(
+                        DriverErrorCategory::Other,
+                        format!("{} is not a node of this connection", label),
+                    )
+                })?,
+        };
+
+        let mut list = redis::cmd("CLIENT");
+        list.arg("LIST").arg("ID").arg(id);
+        let clients = Self::query_node::<String>(&mut conn, node, &list)
+            .await
+            .map_err(|e| self.handle_connection_error(&e, "kill_client"))?;
+        let Some(client) = parse_client_list(&clients, None).into_iter().next() else {
+            return Ok(false);
+        };
+        if client.addr != addr {
+            return Err(DriverError::new(
+                DriverErrorCategory::Other,
+                format!(
+                    "Client {} is now connected from {}. Refresh the client list.",
+                    id, client.addr
+                ),
+            ));
+        }
+        let own_id = Self::query_node::<u64>(&mut conn, node, redis::cmd("CLIENT").arg("ID"))
+            .await
+            .map_err(|e| self.handle_connection_error(&e, "kill_client"))?;
+        if own_id == id {
+            return Err(DriverError::new(
+                DriverErrorCategory::Other,
+                "This is DBcooper's own connection",
+            ));
+        }
+
+        let mut kill = redis::cmd("CLIENT");
+        kill.arg("KILL").arg("ID").arg(id);
+        let killed = Self::query_node::<i64>(&mut conn, node, &kill)
+            .await
+            .map_err(|e| self.handle_connection_error(&e, "kill_client"))?;
+        Ok(killed > 0)
+    }
+
+    pub async fn server_info(&self, section: Option<&str>) -> Result<Vec<NodeInfo>, DriverError> {
+        let mut cmd = redis::cmd("INFO");
+        if let Some(section) = section {
+            cmd.arg(section);
+        }
+        self.on_each_node(&cmd, "server_info", |reply: String, node| {
+            vec![NodeInfo {
+                node: node.map(str::to_string),
+                sections: parse_info(&reply),
+            }]
+        })
+        .await
+    }
+
+    /// The server modules. Managed services often disable or rename
+    /// `MODULE`; a server that answers it with an error has none we use.
+    pub async fn modules(&self) -> Result<RedisModules, DriverError> {
+        let mut conn = self.get_connection_with_retry().await?;
+        match redis::cmd("MODULE")
+            .arg("LIST")
+            .query_async::<redis::Value>(&mut conn)
+            .await
+        {
+            Ok(reply) => Ok(RedisModules::from_module_list(&reply)),
+            Err(e) if e.code().is_some() => Ok(RedisModules::default()),
+            Err(e) => Err(self.handle_connection_error(&e, "modules")),
+        }
+    }
+
+    /// The JSON at `path` of the document at `key`.
+    pub async fn json_get(&self, key: &str, path: &str) -> Result<Value, DriverError> {
+        let mut conn = self.get_connection_with_retry().await?;
+        let document: Option<String> = redis::cmd("JSON.GET")
+            .arg(key)
+            .arg(path)
+            .query_async(&mut conn)
+            .await
+            .map_err(|e| self.handle_connection_error(&e, "json_get"))?;
+        let document = document.ok_or_else(|| {
+            DriverError::new(
+                DriverErrorCategory::Other,
+                format!("Key '{}' does not exist", key),
+            )
+        })?;
+        serde_json::from_str(&document).map_err(|e| {
+            DriverError::new(
+                DriverErrorCategory::Other,
+                format!("'{}' holds invalid JSON: {}", key, e),
+            )
+        })
+    }
+
+    /// Write `value` at `path`. RedisJSON answers nil rather than an error
+    /// when the parent of `path` does not exist.
+    pub async fn json_set(&self, key: &str, path: &str, value: &Value) -> Result<(), DriverError> {
+        let mut conn = self.get_connection_with_retry().await?;
+        let reply: Option<String> = redis::cmd("JSON.SET")
+            .arg(key)
+            .arg(path)
+            .arg(value.to_string())
+            .query_async(&mut conn)
+            .await
+            .map_err(|e| self.handle_connection_error(&e, "json_set"))?;
+        match reply {
+            Some(_) => Ok(()),
+            None => Err(DriverError::new(
+                DriverErrorCategory::Other,
+                format!(
+                    "Nothing was written: the parent of '{}' does not exist",
+                    path
+                ),
+            )),
+        }
+    }
+
+    pub async fn json_delete(&self, key: &str, path: &str) -> Result<u64, DriverError> {
+        let mut conn = self.get_connection_with_retry().await?;
+        redis::cmd("JSON.DEL")
+            .arg(key)
+            .arg(path)
+            .query_async(&mut conn)
+            .await
+            .map_err(|e| self.handle_connection_error(&e, "json_delete"))
+    }
+
+    /// The names of the RediSearch indexes, or none without the module.
+    async fn search_indexes(&self) -> Result<Vec<String>, DriverError> {
+        if !self.modules().await?.search {
+            return Ok(vec![]);
+        }
+        let mut conn = self.get_connection_with_retry().await?;
+        let mut indexes: Vec<String> = redis::cmd("FT._LIST")
+            .query_async(&mut conn)
+            .await
+            .map_err(|e| self.handle_connection_error(&e, "search_indexes"))?;
+        indexes.sort();
+        Ok(indexes)
+    }
+
+    async fn search_index(&self, index: &str) -> Result<SearchIndexInfo, DriverError> {
+        let mut conn = self.get_connection_with_retry().await?;
+        let reply: redis::Value = redis::cmd("FT.INFO")
+            .arg(index)
+            .query_async(&mut conn)
+            .await
+            .map_err(|e| self.handle_connection_error(&e, "search_index"))?;
+        parse_index_info(&reply).ok_or_else(|| {
+            DriverError::new(
+                DriverErrorCategory::Other,
+                format!("Unexpected FT.INFO reply for index '{}'", index),
+            )
+        })
+    }
+
+    /// A page of every document in `index`, one column per attribute.
+    async fn browse_index(
+        &self,
+        index: &str,
+        page: i64,
+        limit: i64,
+        sort_column: Option<String>,
+        sort_direction: Option<String>,
+    ) -> Result<TableDataResponse, DriverError> {
+        let info = self.search_index(index).await?;
+        let page = page.max(1);
+        let limit = limit.max(1);
+        let mut cmd = redis::cmd("FT.SEARCH");
+        cmd.arg(index).arg("*").arg(info.return_arguments());
+        // Only attributes can be sorted on; the key column cannot.
+        if let Some(column) = sort_column.filter(|column| {
+            info.attributes
+                .iter()
+                .any(|attribute| &attribute.attribute == column)
+        }) {
+            let descending = sort_direction.is_some_and(|d| d.eq_ignore_ascii_case("desc"));
+            cmd.arg("SORTBY")
+                .arg(column)
+                .arg(if descending { "DESC" } else { "ASC" });
+        }
+        cmd.arg("LIMIT").arg((page - 1) * limit).arg(limit);
+
+        let mut conn = self.get_connection_with_retry().await?;
+        let reply: redis::Value = cmd
+            .query_async(&mut conn)
+            .await
+            .map_err(|e| self.handle_connection_error(&e, "browse_index"))?;
+        let (total, mut data) =
+            search_rows(&reply, SearchReplyShape::default()).ok_or_else(|| {
+                DriverError::new(
+                    DriverErrorCategory::Other,
+                    format!("Unexpected FT.SEARCH reply for index '{}'", index),
+                )
+            })?;
+        info.decode_numbers(&mut data);
+        Ok(TableDataResponse {
+            data,
+            columns: Vec::new(),
+            total,
+            page,
+            limit,
+        })
+    }
+
+    /// `EVALSHA`/`FCALL` style command: the script or function, the number
+    /// of keys, the keys, then the arguments.
+    fn script_command(command: &str, target: &str, call: &ScriptCall) -> redis::Cmd {
+        let mut cmd = redis::cmd(command);
+        cmd.arg(target)
+            .arg(call.keys.len())
+            .arg(&call.keys)
+            .arg(&call.args);
+        cmd
+    }
+
+    /// Run a script or function call within the query limits, its reply as
+    /// a single row like a console command.
+    async fn run_call(
+        &self,
+        run: impl std::future::Future<Output = Result<redis::RedisResult<redis::Value>, DriverError>>,
+        operation: &str,
+    ) -> Result<QueryResult, DriverError> {
+        let start_time = std::time::Instant::now();
+        Ok(
+            match self
+                .config
+                .limits
+                .run(false, run)
+                .await
+                .and_then(|reply| reply)
+            {
+                Ok(Ok(reply)) => QueryResult::from_rows(
+                    vec![Self::redis_value_to_json(&reply, "unknown")],
+                    false,
+                    start_time,
+                ),
+                Ok(Err(e)) => {
+                    QueryResult::from_error(self.handle_connection_error(&e, operation), start_time)
+                }
+                Err(error) => QueryResult::from_error(error, start_time),
+            },
+        )
+    }
+
+    /// Run Lua `source` by its SHA1. A server that has not cached it (or
+    /// flushed its cache) answers `NOSCRIPT`; the script is then loaded and
+    /// the call retried once.
+    pub async fn eval_script(
+        &self,
+        source: &str,
+        call: &ScriptCall,
+        read_only: bool,
+    ) -> Result<QueryResult, DriverError> {
+        let command = if read_only { "EVALSHA_RO" } else { "EVALSHA" };
+        let cmd = Self::script_command(command, &script_hash(source), call);
+        self.run_call(
+            async {
+                let mut conn = self.get_connection_with_retry().await?;
+                Ok(match cmd.query_async(&mut conn).await {
+                    Err(e) if e.kind() == redis::ErrorKind::NoScriptError => {
+                        self.load_script(source).await?;
+                        cmd.query_async(&mut conn).await
+                    }
+                    reply => reply,
+                })
+            },
+            "eval_script",
+        )
+        .await
+    }
+
+    pub async fn load_script(&self, source: &str) -> Result<String, DriverError> {
+        let mut conn = self.get_connection_with_retry().await?;
+        redis::cmd("SCRIPT")
+            .arg("LOAD")
+            .arg(source)
+            .query_async(&mut conn)
+            .await
+            .map_err(|e| self.handle_connection_error(&e, "load_script"))
+    }
+
+    pub async fn scripts_exist(&self, hashes: &[String]) -> Result<Vec<bool>, DriverError> {
+        if hashes.is_empty() {
+            return Ok(vec![]);
+        }
+        let mut conn = self.get_connection_with_retry().await?;
+        redis::cmd("SCRIPT")
+            .arg("EXISTS")
+            .arg(hashes)
+            .query_async(&mut conn)
+            .await
+            .map_err(|e| self.handle_connection_error(&e, "scripts_exist"))
+    }
+
+    pub async fn list_functions(
+        &self,
+        with_code: bool,
+    ) -> Result<Vec<FunctionLibrary>, DriverError> {
+        let mut conn = self.get_connection_with_retry().await?;
+        let mut cmd = redis::cmd("FUNCTION");
+        cmd.arg("LIST");
+        if with_code {
+            cmd.arg("WITHCODE");
+        }
+        let reply: redis::Value = cmd
+            .query_async(&mut conn)
+            .await
+            .map_err(|e| self.handle_connection_error(&e, "list_functions"))?;
+        let mut libraries = parse_function_list(&reply);
+        libraries.sort_by(|a, b| a.name.cmp(&b.name));
+        Ok(libraries)
+    }
+
+    pub async fn load_function_library(
+        &self,
+        source: &str,
+        replace: bool,
+    ) -> Result<String, DriverError> {
+        let mut conn = self.get_connection_with_retry().await?;
+        let mut cmd = redis::cmd("FUNCTION");
+        cmd.arg("LOAD");
+        if replace {
+            cmd.arg("REPLACE");
+        }
+        cmd.arg(source)
+            .query_async(&mut conn)
+            .await
+            .map_err(|e| self.handle_connection_error(&e, "load_function_library"))
+    }
+
+    pub async fn delete_function_library(&self, library: &str) -> Result<(), DriverError> {
+        let mut conn = self.get_connection_with_retry().await?;
+        redis::cmd("FUNCTION")
+            .arg("DELETE")
+            .arg(library)
+            .query_async::<()>(&mut conn)
+            .await
+            .map_err(|e| self.handle_connection_error(&e, "delete_function_library"))
+    }
+
+    pub async fn call_function(
+        &self,
+        function: &str,
+        call: &ScriptCall,
+        read_only: bool,
+    ) -> Result<QueryResult, DriverError> {
+        let command = if read_only { "FCALL_RO" } else { "FCALL" };
+        let cmd = Self::script_command(command, function, call);
+        self.run_call(
+            async {
+                let mut conn = self.get_connection_with_retry().await?;
+                Ok(cmd.query_async(&mut conn).await)
+            },
+            "call_function",
+        )
+        .await
+    }
 }
@@ -0,0 +1,358 @@
+//! redis-cli compatible command parsing.
+//!
+//! Arguments are split the way `redis-cli` splits a line: whitespace separates
+//! arguments, double quotes understand `\n`, `\r`, `\t`, `\b`, `\a`, `\\`,
+//! `\"` and `\xHH` escapes, and single quotes only `\'`. Each line is one
+//! command (a quoted argument may span lines), and lines starting with `#` are
+//! comments, so runbook snippets paste in unchanged.
+
+/// One parsed command. Arguments are bytes because `\xHH` escapes can spell
+/// values that are not UTF-8.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RedisCommand {
+    pub args: Vec<Vec<u8>>,
+}
+
+impl RedisCommand {
+    /// The command name, upper-cased.
+    pub fn name(&self) -> String {
+        self.arg(0)
+    }
+
+    /// The first argument upper-cased, for command families like `CLIENT`.
+    pub fn subcommand(&self) -> String {
+        self.arg(1)
+    }
+
+    fn arg(&self, index: usize) -> String {
+        self.args
+            .get(index)
+            .map(|arg| String::from_utf8_lossy(arg).to_ascii_uppercase())
+            .unwrap_or_default()
+    }
+
+    pub(crate) fn to_cmd(&self) -> redis::Cmd {
+        let mut cmd = redis::Cmd::new();
+        for arg in &self.args {
+            cmd.arg(arg.as_slice());
+        }
+        cmd
+    }
+
+    /// The command as redis-cli would echo it, quoting arguments that need it.
+    pub fn display(&self) -> String {
+        self.args
+            .iter()
+            .map(|arg| display_arg(arg))
+            .collect::<Vec<_>>()
+            .join(" ")
+    }
+}
+
+/// The commands of a console script. `transaction` is set when the script was
+/// wrapped in `MULTI` ... `EXEC`; the markers themselves are not in `commands`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RedisScript {
+    pub commands: Vec<RedisCommand>,
+    pub transaction: bool,
+}
+
+/// Parse a console script into commands.
+pub fn parse_script(script: &str) -> Result<RedisScript, String> {
+    let mut commands = split_commands(script)?;
+    if commands.is_empty() {
+        return Err("Empty query".to_string());
+    }
+
+    for command in &commands {
+        match command.name().as_str() {
+            "WATCH" | "UNWATCH" => {
+                return Err(
+                    "WATCH is not supported: the console shares its connection with other requests"
+                        .to_string(),
+                )
+            }
+            name @ ("SUBSCRIBE" | "SSUBSCRIBE" | "PSUBSCRIBE" | "MONITOR") => {
+                return Err(format!(
+                    "{name} streams replies forever and cannot run in the console"
+                ))
+            }
+            _ => {}
+        }
+    }
+
+    let is_marker =
+        |command: &RedisCommand| matches!(command.name().as_str(), "MULTI" | "EXEC" | "DISCARD");
+    let transaction = commands.first().is_some_and(|c| c.name() == "MULTI");
+    if transaction {
+        if commands.len() < 2 || commands.last().is_some_and(|c| c.name() != "EXEC") {
+            return Err("A script that starts with MULTI must end with EXEC".to_string());
+        }
+        commands.pop();
+        commands.remove(0);
+    }
+    if let Some(marker) = commands.iter().find(|command| is_marker(command)) {
+        return Err(format!(
+            "{} is only allowed as the first (MULTI) or last (EXEC) command of a script",
+            marker.name()
+        ));
+    }
+    if transaction && commands.is_empty() {
+        return Err("The transaction has no commands".to_string());
+    }
+
+    Ok(RedisScript {
+        commands,
+        transaction,
+    })
+}
+
+/// Split one line of redis-cli style arguments, as `MONITOR` prints them.
+pub(crate) fn split_arguments(line: &str) -> Result<Vec<Vec<u8>>, String> {
+    Ok(split_commands(line)?
+        .into_iter()
+        .flat_map(|command| command.args)
+        .collect())
+}
+
+fn split_commands(script: &str) -> Result<Vec<RedisCommand>, String> {
+    let mut commands = Vec::new();
+    let mut args: Vec<Vec<u8>> = Vec::new();
+    let mut chars = script.chars().peekable();
+    let mut line = 1;
+
+    loop {
+        while chars.next_if(|c| matches!(c, ' ' | '\t' | '\r')).is_some() {}
+        match chars.peek() {
+            None => break,
+            Some('\n') => {
+                chars.next();
+                line += 1;
+                if !args.is_empty() {
+                    commands.push(RedisCommand {
+                        args: std::mem::take(&mut args),
+                    });
+                }
+                continue;
+            }
+            Some('#') if args.is_empty() => {
+                while chars.next_if(|c| *c != '\n').is_some() {}
+                continue;
+            }
+            Some(_) => {}
+        }
+
+        let mut current = Vec::new();
+        let mut quote: Option<char> = None;
+        let start_line = line;
+        loop {
+            if quote.is_none() && chars.peek().is_some_and(char::is_ascii_whitespace) {
+                break;
+            }
+            let Some(c) = chars.next() else {
+                if quote.is_some() {
+                    return Err(format!("Unbalanced quotes on line {start_line}"));
+                }
+                break;
+            };
+            match quote {
+                Some('"') => match c {
+                    '\\' => match chars.next() {
+                        Some('x') => {
+                            let high = chars.next_if(char::is_ascii_hexdigit);
+                            let low = high.and_then(|_| chars.next_if(char::is_ascii_hexdigit));
+                            match (high, low) {
+                                (Some(high), Some(low)) => current.push(
+                                    (high.to_digit(16).unwrap() * 16 + low.to_digit(16).unwrap())
+                                        as u8,
+                                ),
+                                // redis-cli keeps a malformed escape as an `x`.
+                                (high, _) => {
+                                    current.push(b'x');
+                                    if let Some(high) = high {
+                                        push_char(&mut current, high);
+                                    }
+                                }
+                            }
+                        }
+                        Some('n') => current.push(b'\n'),
+                        Some('r') => current.push(b'\r'),
+                        Some('t') => current.push(b'\t'),
+                        Some('b') => current.push(0x08),
+                        Some('a') => current.push(0x07),
+                        Some(other) => {
+                            if other == '\n' {
+                                line += 1;
+                            }
+                            push_char(&mut current, other);
+                        }
+                        None => return Err(format!("Unbalanced quotes on line {start_line}")),
+                    },
+                    '"' => {
+                        close_quote(&mut chars, line)?;
+                        break;
+                    }
+                    other => {
+                        if other == '\n' {
+                            line += 1;
+                        }
+                        push_char(&mut current, other);
+                    }
+                },
+                Some(_) => match c {
+                    '\\' if chars.peek() == Some(&'\'') => {
+                        chars.next();
+                        current.push(b'\'');
+                    }
+                    '\'' => {
+                        close_quote(&mut chars, line)?;
+                        break;
+                    }
+                    other => {
+                        if other == '\n' {
+                            line += 1;
+                        }
+                        push_char(&mut current, other);
+                    }
+                },
+                None => match c {
+                    '"' | '\'' => quote = Some(c),
+                    other => push_char(&mut current, other),
+                },
+            }
+        }
+        if !current.is_empty() || quote.is_some() {
+            args.push(current);
+        }
+    }
+    if !args.is_empty() {
+        commands.push(RedisCommand { args });
+    }
+    Ok(commands)
+}
+
+/// A closing quote must end the argument, as in redis-cli.
+fn close_quote(
+    chars: &mut std::iter::Peekable<std::str::Chars<'_>>,
+    line: usize,
+) -> Result<(), String> {
+    match chars.peek() {
+        None | Some(' ' | '\t' | '\r' | '\n') => Ok(()),
+        Some(_) => Err(format!(
+            "Closing quote must be followed by a space on line {line}"
+        )),
+    }
+}
+
+fn push_char(buffer: &mut Vec<u8>, c: char) {
+    let mut encoded = [0; 4];
+    buffer.extend_from_slice(c.encode_utf8(&mut encoded).as_bytes());
+}
+
+fn display_arg(arg: &[u8]) -> String {
+    let plain = !arg.is_empty()
+        && arg
+            .iter()
+            .all(|byte| byte.is_ascii_graphic() && !matches!(byte, b'"' | b'\'' | b'\\'));
+    if plain {
+        return String::from_utf8_lossy(arg).into_owned();
+    }
+    let mut quoted = String::from("\"");
+    for chunk in arg.utf8_chunks() {
+        for c in chunk.valid().chars() {
+            match c {
+                '"' => quoted.push_str("\\\""),
+                '\\' => quoted.push_str("\\\\"),
+                '\n' => quoted.push_str("\\n"),
+                '\r' => quoted.push_str("\\r"),
+                '\t' => quoted.push_str("\\t"),
+                c if c.is_control() => quoted.push_str(&format!("\\x{:02x}", c as u32)),
+                c => quoted.push(c),
+            }
+        }
+        for byte in chunk.invalid() {
+            quoted.push_str(&format!("\\x{byte:02x}"));
+        }
+    }
+    quoted.push('"');
+    quoted
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn args(script: &str) -> Vec<Vec<String>> {
+        split_commands(script)
+            .unwrap()
+            .into_iter()
+            .map(|command| {
+                command
+                    .args
+                    .into_iter()
+                    .map(|arg| String::from_utf8(arg).unwrap())
+                    .collect()
+            })
+            .collect()
+    }
+
+    #[test]
+    fn splits_quoted_arguments_like_redis_cli() {
+        assert_eq!(
+            args(r#"SET greeting "hello world""#),
+            vec![vec!["SET", "greeting", "hello world"]]
+        );
+        assert_eq!(
+            args(r#"SET doc '{"name": "Ada Lovelace"}'"#),
+            vec![vec!["SET", "doc", r#"{"name": "Ada Lovelace"}"#]]
+        );
+        assert_eq!(
+            args(r#"SET k "tab\there \"quoted\"" 'it\'s'"#),
+            vec![vec!["SET", "k", "tab\there \"quoted\"", "it's"]]
+        );
+        assert_eq!(args(r#"SET empty """#), vec![vec!["SET", "empty", ""]]);
+    }
+
+    #[test]
+    fn decodes_hex_escapes_to_raw_bytes() {
+        let commands = split_commands(r#"SET bin "\x00\xff\x41""#).unwrap();
+        assert_eq!(commands[0].args[2], vec![0x00, 0xff, 0x41]);
+        assert_eq!(commands[0].display(), r#"SET bin "\x00\xffA""#);
+    }
+
+    #[test]
+    fn rejects_unbalanced_and_unseparated_quotes() {
+        assert_eq!(
+            split_commands("PING\nSET k \"open").unwrap_err(),
+            "Unbalanced quotes on line 2"
+        );
+        assert!(split_commands(r#"SET k "a"b"#).is_err());
+    }
+
+    #[test]
+    fn reads_one_command_per_line_and_skips_comments() {
+        assert_eq!(
+            args("# warm the cache\nSET a 1\r\n\n  INCR a\nGET a"),
+            vec![vec!["SET", "a", "1"], vec!["INCR", "a"], vec!["GET", "a"]]
+        );
+        assert_eq!(
+            args("SET note \"line one\nline two\""),
+            vec![vec!["SET", "note", "line one\nline two"]]
+        );
+    }
+
+    #[test]
+    fn unwraps_multi_exec_into_a_transaction() {
+        let script = parse_script("MULTI\nINCR a\nINCR b\nEXEC").unwrap();
+        assert!(script.transaction);
+        assert_eq!(script.commands.len(), 2);
+        assert_eq!(script.commands[1].display(), "INCR b");
+
+        assert!(!parse_script("INCR a\nINCR b").unwrap().transaction);
+        assert!(parse_script("MULTI\nINCR a").is_err());
+        assert!(parse_script("INCR a\nEXEC").is_err());
+        assert!(parse_script("MULTI\nEXEC").is_err());
+        assert!(parse_script("WATCH a\nMULTI\nINCR a\nEXEC").is_err());
+        assert_eq!(parse_script("  \n# nothing\n").unwrap_err(), "Empty query");
+    }
+}
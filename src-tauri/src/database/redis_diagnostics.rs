@@ -0,0 +1,449 @@
+//! Redis server introspection.
+//!
+//! The slow log, the latency monitor, connected clients and `INFO` are parsed
+//! from their replies into rows. `MONITOR` streams, so it is a
+//! [`LiveSource`](super::key_value::LiveSource) rather than a method here, and
+//! each command it reports is parsed with [`parse_monitor_line`].
+
+use std::sync::Arc;
+
+use async_trait::async_trait;
+use serde::Serialize;
+
+use super::redis_cli::split_arguments;
+use super::{DatabaseDriver, DriverError};
+
+/// Server introspection for engines that support it.
+#[async_trait]
+pub trait ServerDiagnostics: Send + Sync {
+    /// The newest `count` slow log entries of each node, newest first.
+    async fn slowlog(&self, count: usize) -> Result<Vec<SlowlogEntry>, DriverError>;
+
+    /// The latest and worst spike of each latency event.
+    async fn latency_latest(&self) -> Result<Vec<LatencyEvent>, DriverError>;
+
+    /// The recorded spikes of one latency event, oldest first.
+    async fn latency_history(&self, event: &str) -> Result<Vec<LatencySample>, DriverError>;
+
+    async fn client_list(&self) -> Result<Vec<ClientInfo>, DriverError>;
+
+    /// Kill client `id` on `node`, provided it is still the client at
+    /// `addr` the user confirmed. Returns `false` if it already disconnected.
+    async fn kill_client(
+        &self,
+        node: Option<&str>,
+        id: u64,
+        addr: &str,
+    ) -> Result<bool, DriverError>;
+
+    /// `INFO` of each node, or only `section` of it.
+    async fn server_info(&self, section: Option<&str>) -> Result<Vec<NodeInfo>, DriverError>;
+}
+
+/// The introspection commands of a pooled driver, or an error for engines
+/// without them.
+pub(crate) fn server_diagnostics(
+    driver: &Arc<Box<dyn DatabaseDriver>>,
+) -> Result<&dyn ServerDiagnostics, String> {
+    driver
+        .diagnostics()
+        .ok_or_else(|| "Connection does not support server diagnostics".to_string())
+}
+
+#[derive(Clone, Debug, Serialize, PartialEq)]
+pub struct SlowlogEntry {
+    /// The cluster node that logged the entry.
+    pub node: Option<String>,
+    pub id: i64,
+    /// Unix time, in seconds.
+    pub timestamp: i64,
+    pub duration_us: i64,
+    pub command: Vec<String>,
+    pub client_addr: Option<String>,
+    pub client_name: Option<String>,
+}
+
+#[derive(Clone, Debug, Serialize, PartialEq)]
+pub struct LatencyEvent {
+    pub node: Option<String>,
+    pub event: String,
+    /// Unix time of the latest spike, in seconds.
+    pub timestamp: i64,
+    pub latest_ms: i64,
+    pub max_ms: i64,
+}
+
+#[derive(Clone, Debug, Serialize, PartialEq)]
+pub struct LatencySample {
+    pub node: Option<String>,
+    pub timestamp: i64,
+    pub latency_ms: i64,
+}
+
+/// One line of `CLIENT LIST`.
+#[derive(Clone, Debug, Serialize, PartialEq)]
+pub struct ClientInfo {
+    pub node: Option<String>,
+    pub id: u64,
+    pub addr: String,
+    pub name: Option<String>,
+    pub age_secs: u64,
+    pub idle_secs: u64,
+    pub db: i64,
+    pub flags: String,
+    /// The last command the client ran.
+    pub cmd: String,
+    /// Every field of the line, in server order.
+    pub fields: serde_json::Map<String, serde_json::Value>,
+}
+
+#[derive(Clone, Debug, Serialize, PartialEq)]
+pub struct NodeInfo {
+    pub node: Option<String>,
+    pub sections: Vec<InfoSection>,
+}
+
+#[derive(Clone, Debug, Serialize, PartialEq)]
+pub struct InfoSection {
+    pub name: String,
+    pub fields: Vec<InfoField>,
+}
+
+#[derive(Clone, Debug, Serialize, PartialEq)]
+pub struct InfoField {
+    pub key: String,
+    pub value: InfoValue,
+}
+
+/// An `INFO` value. Values like `keys=12,expires=3` in the keyspace and
+/// command stats sections are split into their own fields.
+#[derive(Clone, Debug, Serialize, PartialEq)]
+#[serde(untagged)]
+pub enum InfoValue {
+    Integer(i64),
+    Float(f64),
+    Text(String),
+    Fields(Vec<InfoField>),
+}
+
+/// A command reported by `MONITOR`.
+#[derive(Clone, Debug, Serialize, PartialEq)]
+pub struct MonitorEvent {
+    pub timestamp: f64,
+    pub db: i64,
+    /// The client address, or `lua` for commands run by a script.
+    pub client: String,
+    pub command: Vec<String>,
+}
+
+fn text(value: &redis::Value) -> Option<String> {
+    match value {
+        redis::Value::BulkString(bytes) => Some(String::from_utf8_lossy(bytes).into_owned()),
+        redis::Value::SimpleString(text) => Some(text.clone()),
+        redis::Value::VerbatimString { text, .. } => Some(text.clone()),
+        _ => None,
+    }
+}
+
+fn int(value: &redis::Value) -> Option<i64> {
+    match value {
+        redis::Value::Int(value) => Some(*value),
+        value => text(value)?.parse().ok(),
+    }
+}
+
+fn items(value: &redis::Value) -> &[redis::Value] {
+    match value {
+        redis::Value::Array(items) | redis::Value::Set(items) => items,
+        _ => &[],
+    }
+}
+
+/// Parse a `SLOWLOG GET` reply. Servers before 4.0 omit the client fields.
+pub fn parse_slowlog(reply: &redis::Value, node: Option<&str>) -> Vec<SlowlogEntry> {
+    items(reply)
+        .iter()
+        .filter_map(|entry| {
+            let fields = items(entry);
+            Some(SlowlogEntry {
+                node: node.map(str::to_string),
+                id: int(fields.first()?)?,
+                timestamp: int(fields.get(1)?)?,
+                duration_us: int(fields.get(2)?)?,
+                command: items(fields.get(3)?).iter().filter_map(text).collect(),
+                client_addr: fields.get(4).and_then(text).filter(|addr| !addr.is_empty()),
+                client_name: fields.get(5).and_then(text).filter(|name| !name.is_empty()),
+            })
+        })
+        .collect()
+}
+
+/// Parse a `LATENCY LATEST` reply.
+pub fn parse_latency_latest(reply: &redis::Value, node: Option<&str>) -> Vec<LatencyEvent> {
+    items(reply)
+        .iter()
+        .filter_map(|event| {
+            let fields = items(event);
+            Some(LatencyEvent {
+                node: node.map(str::to_string),
+                event: text(fields.first()?)?,
+                timestamp: int(fields.get(1)?)?,
+                latest_ms: int(fields.get(2)?)?,
+                max_ms: int(fields.get(3)?)?,
+            })
+        })
+        .collect()
+}
+
+/// Parse a `LATENCY HISTORY` reply.
+pub fn parse_latency_history(reply: &redis::Value, node: Option<&str>) -> Vec<LatencySample> {
+    items(reply)
+        .iter()
+        .filter_map(|sample| {
+            let fields = items(sample);
+            Some(LatencySample {
+                node: node.map(str::to_string),
+                timestamp: int(fields.first()?)?,
+                latency_ms: int(fields.get(1)?)?,
+            })
+        })
+        .collect()
+}
+
+/// Parse `CLIENT LIST` output, one client per line of `field=value` pairs.
+pub fn parse_client_list(list: &str, node: Option<&str>) -> Vec<ClientInfo> {
+    list.lines()
+        .filter_map(|line| {
+            let fields: serde_json::Map<String, serde_json::Value> = line
+                .split_whitespace()
+                .filter_map(|pair| pair.split_once('='))
+                .map(|(field, value)| (field.to_string(), value.into()))
+                .collect();
+            let field = |name: &str| fields.get(name).and_then(|value| value.as_str());
+            let number = |name: &str| field(name).and_then(|value| value.parse().ok());
+            Some(ClientInfo {
+                node: node.map(str::to_string),
+                id: number("id")?,
+                addr: field("addr")?.to_string(),
+                name: field("name")
+                    .filter(|name| !name.is_empty())
+                    .map(str::to_string),
+                age_secs: number("age").unwrap_or(0),
+                idle_secs: number("idle").unwrap_or(0),
+                db: field("db").and_then(|db| db.parse().ok()).unwrap_or(0),
+                flags: field("flags").unwrap_or_default().to_string(),
+                cmd: field("cmd").unwrap_or_default().to_string(),
+                fields,
+            })
+        })
+        .collect()
+}
+
+/// Parse `INFO` output into its `# Section`s.
+pub fn parse_info(info: &str) -> Vec<InfoSection> {
+    let mut sections: Vec<InfoSection> = Vec::new();
+    for line in info.lines().map(str::trim) {
+        if let Some(name) = line.strip_prefix('#') {
+            sections.push(InfoSection {
+                name: name.trim().to_string(),
+                fields: Vec::new(),
+            });
+            continue;
+        }
+        let Some((key, value)) = line.split_once(':') else {
+            continue;
+        };
+        if sections.is_empty() {
+            sections.push(InfoSection {
+                name: String::new(),
+                fields: Vec::new(),
+            });
+        }
+        if let Some(section) = sections.last_mut() {
+            section.fields.push(InfoField {
+                key: key.to_string(),
+                value: info_value(value),
+            });
+        }
+    }
+    sections
+}
+
+fn info_value(value: &str) -> InfoValue {
+    let pairs: Option<Vec<(&str, &str)>> =
+        value.split(',').map(|pair| pair.split_once('=')).collect();
+    match pairs {
+        Some(pairs) => InfoValue::Fields(
+            pairs
+                .into_iter()
+                .map(|(key, value)| InfoField {
+                    key: key.to_string(),
+                    value: info_scalar(value),
+                })
+                .collect(),
+        ),
+        _ => info_scalar(value),
+    }
+}
+
+fn info_scalar(value: &str) -> InfoValue {
+    if let Ok(integer) = value.parse() {
+        return InfoValue::Integer(integer);
+    }
+    // `f64` also parses words like `inf` and `nan`; INFO only uses digits.
+    let numeric = value
+        .trim_start_matches('-')
+        .starts_with(|c: char| c.is_ascii_digit());
+    match value.parse::<f64>() {
+        Ok(float) if numeric && float.is_finite() => InfoValue::Float(float),
+        _ => InfoValue::Text(value.to_string()),
+    }
+}
+
+/// Parse one `MONITOR` line, e.g.
+/// `1339518083.107412 [0 127.0.0.1:60866] "set" "user:1" "Ada"`.
+pub fn parse_monitor_line(line: &str) -> Option<MonitorEvent> {
+    let (timestamp, rest) = line.split_once(' ')?;
+    let (origin, command) = rest.strip_prefix('[')?.split_once("] ")?;
+    let (db, client) = origin.split_once(' ')?;
+    Some(MonitorEvent {
+        timestamp: timestamp.parse().ok()?,
+        db: db.parse().ok()?,
+        client: client.to_string(),
+        command: split_arguments(command)
+            .ok()?
+            .iter()
+            .map(|arg| String::from_utf8_lossy(arg).into_owned())
+            .collect(),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn bulk(text: &str) -> redis::Value {
+        redis::Value::BulkString(text.as_bytes().to_vec())
+    }
+
+    #[test]
+    fn parses_slowlog_entries() {
+        let reply = redis::Value::Array(vec![
+            redis::Value::Array(vec![
+                redis::Value::Int(14),
+                redis::Value::Int(1_700_000_000),
+                redis::Value::Int(25_000),
+                redis::Value::Array(vec![bulk("KEYS"), bulk("*")]),
+                bulk("10.0.0.5:51234"),
+                bulk(""),
+            ]),
+            // Redis 3.x entries end after the arguments.
+            redis::Value::Array(vec![
+                redis::Value::Int(13),
+                redis::Value::Int(1_699_999_000),
+                redis::Value::Int(12_000),
+                redis::Value::Array(vec![bulk("FLUSHALL")]),
+            ]),
+        ]);
+        let entries = parse_slowlog(&reply, Some("10.0.0.1:6379"));
+        assert_eq!(entries.len(), 2);
+        assert_eq!(entries[0].command, vec!["KEYS", "*"]);
+        assert_eq!(entries[0].client_addr.as_deref(), Some("10.0.0.5:51234"));
+        assert_eq!(entries[0].client_name, None);
+        assert_eq!(entries[1].duration_us, 12_000);
+        assert_eq!(entries[1].node.as_deref(), Some("10.0.0.1:6379"));
+    }
+
+    #[test]
+    fn parses_latency_replies() {
+        let latest = redis::Value::Array(vec![redis::Value::Array(vec![
+            bulk("command"),
+            redis::Value::Int(1_700_000_000),
+            redis::Value::Int(250),
+            redis::Value::Int(1_000),
+        ])]);
+        assert_eq!(
+            parse_latency_latest(&latest, None),
+            vec![LatencyEvent {
+                node: None,
+                event: "command".to_string(),
+                timestamp: 1_700_000_000,
+                latest_ms: 250,
+                max_ms: 1_000,
+            }]
+        );
+
+        let history = redis::Value::Array(vec![redis::Value::Array(vec![
+            redis::Value::Int(1_700_000_000),
+            redis::Value::Int(250),
+        ])]);
+        assert_eq!(parse_latency_history(&history, None)[0].latency_ms, 250);
+    }
+
+    #[test]
+    fn parses_client_list_lines() {
+        let list = "id=3 addr=127.0.0.1:51234 laddr=127.0.0.1:6379 fd=8 name=worker age=120 idle=4 flags=N db=2 cmd=client|list user=default\n\
+                    id=4 addr=127.0.0.1:51240 fd=9 name= age=5 idle=5 flags=P db=0 cmd=subscribe\n";
+        let clients = parse_client_list(list, None);
+        assert_eq!(clients.len(), 2);
+        assert_eq!(clients[0].id, 3);
+        assert_eq!(clients[0].name.as_deref(), Some("worker"));
+        assert_eq!((clients[0].age_secs, clients[0].idle_secs), (120, 4));
+        assert_eq!(clients[0].db, 2);
+        assert_eq!(clients[0].cmd, "client|list");
+        assert_eq!(clients[0].fields["user"], "default");
+        assert_eq!(clients[1].name, None);
+        assert_eq!(clients[1].flags, "P");
+    }
+
+    #[test]
+    fn parses_info_sections_into_typed_values() {
+        let info = "# Server\r\nredis_version:7.2.4\r\nuptime_in_seconds:3600\r\n\r\n\
+                    # Memory\r\nused_memory:1048576\r\nused_memory_human:1.00M\r\nmem_fragmentation_ratio:1.25\r\n\r\n\
+                    # Keyspace\r\ndb0:keys=12,expires=3,avg_ttl=0\r\n";
+        let sections = parse_info(info);
+        let names: Vec<&str> = sections.iter().map(|s| s.name.as_str()).collect();
+        assert_eq!(names, vec!["Server", "Memory", "Keyspace"]);
+        assert_eq!(
+            sections[0].fields[0].value,
+            InfoValue::Text("7.2.4".to_string())
+        );
+        assert_eq!(sections[0].fields[1].value, InfoValue::Integer(3600));
+        assert_eq!(
+            sections[1].fields[1].value,
+            InfoValue::Text("1.00M".to_string())
+        );
+        assert_eq!(sections[1].fields[2].value, InfoValue::Float(1.25));
+        let InfoValue::Fields(db0) = &sections[2].fields[0].value else {
+            panic!("keyspace values are split into fields");
+        };
+        assert_eq!(db0[0].key, "keys");
+        assert_eq!(db0[0].value, InfoValue::Integer(12));
+        assert_eq!(
+            serde_json::to_value(&sections[2].fields[0]).unwrap(),
+            serde_json::json!({
+                "key": "db0",
+                "value": [
+                    { "key": "keys", "value": 12 },
+                    { "key": "expires", "value": 3 },
+                    { "key": "avg_ttl", "value": 0 }
+                ]
+            })
+        );
+    }
+
+    #[test]
+    fn parses_monitor_lines() {
+        let event = parse_monitor_line(
+            r#"1339518083.107412 [0 127.0.0.1:60866] "set" "greeting" "say \"hi\"\n""#,
+        )
+        .unwrap();
+        assert_eq!(event.db, 0);
+        assert_eq!(event.client, "127.0.0.1:60866");
+        assert_eq!(event.command, vec!["set", "greeting", "say \"hi\"\n"]);
+
+        let event = parse_monitor_line(r#"1339518083.107412 [3 lua] "get" "k""#).unwrap();
+        assert_eq!((event.db, event.client.as_str()), (3, "lua"));
+        assert!(parse_monitor_line("OK").is_none());
+    }
+}
@@ -0,0 +1,554 @@
+//! RedisJSON and RediSearch support.
+//!
+//! Modules are detected with `MODULE LIST`. RediSearch indexes appear in the
+//! schema sidebar under [`SEARCH_SCHEMA`], and `FT.SEARCH`/`FT.AGGREGATE`
+//! replies — flat RESP2 arrays of keys and field/value pairs — are parsed
+//! here into one row per document.
+
+use serde::Serialize;
+use serde_json::{json, Map, Value};
+
+use crate::db::models::{ColumnInfo, FilterColumnKind, IndexInfo, TableStructure};
+
+/// The type `TYPE` reports for RedisJSON documents.
+pub const JSON_KEY_TYPE: &str = "ReJSON-RL";
+
+/// The sidebar schema that holds RediSearch indexes.
+pub const SEARCH_SCHEMA: &str = "search";
+
+/// The table type of a RediSearch index in the sidebar.
+pub const SEARCH_INDEX_TABLE_TYPE: &str = "search_index";
+
+/// The column holding each document's key in search rows.
+pub const KEY_COLUMN: &str = "__key";
+
+/// The column holding each document's score when `WITHSCORES` is given.
+pub const SCORE_COLUMN: &str = "__score";
+
+/// The root path used when no JSON path is given.
+pub const JSON_ROOT: &str = "$";
+
+#[derive(Clone, Debug, Serialize, PartialEq)]
+pub struct ModuleInfo {
+    pub name: String,
+    pub version: Option<i64>,
+}
+
+/// The modules loaded on the server, and which of them the browser supports.
+#[derive(Clone, Debug, Default, Serialize, PartialEq)]
+pub struct RedisModules {
+    pub json: bool,
+    pub search: bool,
+    pub modules: Vec<ModuleInfo>,
+}
+
+impl RedisModules {
+    /// Read a `MODULE LIST` reply. RediSearch registers as `search` (`ft` or
+    /// `searchlight` on some managed services), RedisJSON as `ReJSON`.
+    pub fn from_module_list(reply: &redis::Value) -> Self {
+        let modules: Vec<ModuleInfo> = items(reply)
+            .iter()
+            .filter_map(|module| {
+                let fields = pairs(module);
+                Some(ModuleInfo {
+                    name: text(field(&fields, "name")?)?,
+                    version: field(&fields, "ver").and_then(int),
+                })
+            })
+            .collect();
+        let loaded = |names: &[&str]| {
+            modules.iter().any(|module| {
+                names
+                    .iter()
+                    .any(|name| module.name.eq_ignore_ascii_case(name))
+            })
+        };
+        Self {
+            json: loaded(&["ReJSON", "json"]),
+            search: loaded(&["search", "ft", "searchlight"]),
+            modules,
+        }
+    }
+}
+
+/// An attribute of a RediSearch index, as listed by `FT.INFO`.
+#[derive(Clone, Debug, Serialize, PartialEq)]
+pub struct SearchAttribute {
+    /// The hash field or JSON path that is indexed.
+    pub identifier: String,
+    /// The name queries use for it.
+    pub attribute: String,
+    /// `TEXT`, `TAG`, `NUMERIC`, `GEO`, `GEOSHAPE` or `VECTOR`.
+    pub field_type: String,
+    pub sortable: bool,
+}
+
+#[derive(Clone, Debug, Serialize, PartialEq)]
+pub struct SearchIndexInfo {
+    pub name: String,
+    /// `HASH` or `JSON`.
+    pub key_type: String,
+    pub prefixes: Vec<String>,
+    pub attributes: Vec<SearchAttribute>,
+    pub num_docs: Option<i64>,
+}
+
+/// Parse an `FT.INFO` reply. Attributes mix `name value` pairs with bare
+/// flags such as `SORTABLE`, so they are read token by token.
+pub fn parse_index_info(reply: &redis::Value) -> Option<SearchIndexInfo> {
+    let fields = pairs(reply);
+    let definition = pairs(field(&fields, "index_definition")?);
+    let attributes = field(&fields, "attributes")
+        .map(items)
+        .unwrap_or_default()
+        .iter()
+        .filter_map(|attribute| {
+            let tokens: Vec<String> = items(attribute).iter().filter_map(text).collect();
+            let value = |name: &str| {
+                let position = tokens.iter().position(|token| token == name)?;
+                tokens.get(position + 1).cloned()
+            };
+            let identifier = value("identifier")?;
+            Some(SearchAttribute {
+                attribute: value("attribute").unwrap_or_else(|| identifier.clone()),
+                identifier,
+                field_type: value("type")?,
+                sortable: tokens.iter().any(|token| token == "SORTABLE"),
+            })
+        })
+        .collect();
+    Some(SearchIndexInfo {
+        name: text(field(&fields, "index_name")?)?,
+        key_type: field(&definition, "key_type")
+            .and_then(text)
+            .unwrap_or_else(|| "HASH".to_string()),
+        prefixes: field(&definition, "prefixes")
+            .map(|prefixes| items(prefixes).iter().filter_map(text).collect())
+            .unwrap_or_default(),
+        attributes,
+        num_docs: field(&fields, "num_docs").and_then(int),
+    })
+}
+
+impl SearchIndexInfo {
+    /// The index as a table: the document key, then a column per attribute.
+    pub fn structure(&self) -> TableStructure {
+        let key = ColumnInfo {
+            name: KEY_COLUMN.to_string(),
+            data_type: "key".to_string(),
+            filter_kind: FilterColumnKind::Text,
+            nullable: false,
+            default: None,
+            primary_key: true,
+        };
+        let attributes = self.attributes.iter().map(|attribute| ColumnInfo {
+            name: attribute.attribute.clone(),
+            data_type: attribute.field_type.clone(),
+            filter_kind: match attribute.field_type.as_str() {
+                "NUMERIC" => FilterColumnKind::Decimal,
+                "TEXT" | "TAG" => FilterColumnKind::Text,
+                _ => FilterColumnKind::Other,
+            },
+            nullable: true,
+            default: None,
+            primary_key: false,
+        });
+        TableStructure {
+            columns: std::iter::once(key).chain(attributes).collect(),
+            indexes: vec![IndexInfo {
+                name: self.name.clone(),
+                columns: self
+                    .attributes
+                    .iter()
+                    .map(|attribute| attribute.attribute.clone())
+                    .collect(),
+                unique: false,
+                primary: false,
+            }],
+            foreign_keys: vec![],
+        }
+    }
+
+    /// `FT.SEARCH` arguments returning every attribute but vectors, named
+    /// as the index names them: `RETURN n identifier AS attribute ...`.
+    pub fn return_arguments(&self) -> Vec<String> {
+        let returned: Vec<&SearchAttribute> = self
+            .attributes
+            .iter()
+            .filter(|attribute| attribute.field_type != "VECTOR")
+            .collect();
+        if returned.is_empty() {
+            return vec![];
+        }
+        let mut arguments = vec!["RETURN".to_string(), (returned.len() * 3).to_string()];
+        for attribute in returned {
+            arguments.extend([
+                attribute.identifier.clone(),
+                "AS".to_string(),
+                attribute.attribute.clone(),
+            ]);
+        }
+        arguments
+    }
+
+    /// Search replies carry every value as text; turn `NUMERIC` attributes
+    /// back into numbers.
+    pub fn decode_numbers(&self, rows: &mut [Value]) {
+        let numeric: Vec<&str> = self
+            .attributes
+            .iter()
+            .filter(|attribute| attribute.field_type == "NUMERIC")
+            .map(|attribute| attribute.attribute.as_str())
+            .collect();
+        for row in rows.iter_mut().filter_map(Value::as_object_mut) {
+            for name in &numeric {
+                if let Some(value) = row.get_mut(*name) {
+                    if let Some(number) = value
+                        .as_str()
+                        .and_then(|text| serde_json::from_str::<serde_json::Number>(text).ok())
+                    {
+                        *value = Value::Number(number);
+                    }
+                }
+            }
+        }
+    }
+}
+
+/// The optional items `FT.SEARCH` places between a document's key and its
+/// fields, as requested by the command's arguments.
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub struct SearchReplyShape {
+    pub with_scores: bool,
+    pub with_payloads: bool,
+    pub with_sort_keys: bool,
+}
+
+impl SearchReplyShape {
+    pub fn from_arguments(args: &[Vec<u8>]) -> Self {
+        let has = |flag: &str| {
+            args.iter()
+                .any(|arg| String::from_utf8_lossy(arg).eq_ignore_ascii_case(flag))
+        };
+        Self {
+            with_scores: has("WITHSCORES"),
+            with_payloads: has("WITHPAYLOADS"),
+            with_sort_keys: has("WITHSORTKEYS"),
+        }
+    }
+}
+
+/// Map an `FT.SEARCH` reply — the total, then each key followed by its
+/// optional items and its fields — to the total and a row per document.
+/// Returns `None` for replies of another shape.
+pub fn search_rows(reply: &redis::Value, shape: SearchReplyShape) -> Option<(i64, Vec<Value>)> {
+    let (total, documents) = items(reply).split_first()?;
+    let total = int(total)?;
+    let mut rows = Vec::new();
+    let mut documents = documents.iter().peekable();
+    while let Some(key) = documents.next() {
+        let mut row = Map::new();
+        row.insert(KEY_COLUMN.to_string(), json!(text(key)?));
+        if shape.with_scores {
+            let score = documents.next()?;
+            row.insert(
+                SCORE_COLUMN.to_string(),
+                text(score)
+                    .and_then(|score| score.parse::<f64>().ok())
+                    .map_or(Value::Null, |score| json!(score)),
+            );
+        }
+        if shape.with_payloads {
+            documents.next()?;
+        }
+        if shape.with_sort_keys {
+            documents.next()?;
+        }
+        // `NOCONTENT` and `RETURN 0` leave only the keys.
+        if let Some(fields) = documents.next_if(|value| matches!(value, redis::Value::Array(_))) {
+            insert_fields(&mut row, fields);
+        }
+        rows.push(Value::Object(row));
+    }
+    Some((total, rows))
+}
+
+/// Map an `FT.AGGREGATE` reply — the total, then a field/value array per
+/// group — to the total and a row per group. Cursor replies (`WITHCURSOR`)
+/// are left as they are so that the cursor id stays visible.
+pub fn aggregate_rows(reply: &redis::Value) -> Option<(i64, Vec<Value>)> {
+    let (total, groups) = items(reply).split_first()?;
+    let total = int(total)?;
+    let rows = groups
+        .iter()
+        .map(|group| {
+            let mut row = Map::new();
+            insert_fields(&mut row, group);
+            Value::Object(row)
+        })
+        .collect();
+    Some((total, rows))
+}
+
+/// Add alternating field names and values to `row`. The `$` field of a JSON
+/// index holds the serialized document, which is decoded.
+fn insert_fields(row: &mut Map<String, Value>, fields: &redis::Value) {
+    for pair in items(fields).chunks(2) {
+        let [name, value] = pair else { continue };
+        let Some(name) = text(name) else { continue };
+        let value = match (name.starts_with('$'), value) {
+            (true, value) => text(value)
+                .and_then(|document| serde_json::from_str(&document).ok())
+                .unwrap_or_else(|| value_json(value)),
+            (false, value) => value_json(value),
+        };
+        row.insert(name, value);
+    }
+}
+
+/// The length the key browser shows for a JSON document.
+pub fn json_length(document: &Value) -> Option<usize> {
+    match document {
+        Value::Object(fields) => Some(fields.len()),
+        Value::Array(items) => Some(items.len()),
+        Value::String(text) => Some(text.len()),
+        _ => None,
+    }
+}
+
+fn value_json(value: &redis::Value) -> Value {
+    match value {
+        redis::Value::Int(value) => json!(value),
+        redis::Value::Double(value) => json!(value),
+        redis::Value::Array(values) | redis::Value::Set(values) => {
+            Value::Array(values.iter().map(value_json).collect())
+        }
+        redis::Value::BulkString(bytes) => match std::str::from_utf8(bytes) {
+            Ok(text) => json!(text),
+            Err(_) => json!(format!("<binary data: {} bytes>", bytes.len())),
+        },
+        value => text(value).map_or(Value::Null, Value::String),
+    }
+}
+
+fn text(value: &redis::Value) -> Option<String> {
+    match value {
+        redis::Value::BulkString(bytes) => Some(String::from_utf8_lossy(bytes).into_owned()),
+        redis::Value::SimpleString(text) => Some(text.clone()),
+        redis::Value::VerbatimString { text, .. } => Some(text.clone()),
+        _ => None,
+    }
+}
+
+fn int(value: &redis::Value) -> Option<i64> {
+    match value {
+        redis::Value::Int(value) => Some(*value),
+        value => text(value)?.parse().ok(),
+    }
+}
+
+fn items(value: &redis::Value) -> &[redis::Value] {
+    match value {
+        redis::Value::Array(items) | redis::Value::Set(items) => items,
+        _ => &[],
+    }
+}
+
+/// Name/value pairs of a flat array or of a RESP3 map.
+fn pairs(value: &redis::Value) -> Vec<(String, &redis::Value)> {
+    match value {
+        redis::Value::Map(entries) => entries
+            .iter()
+            .filter_map(|(name, value)| Some((text(name)?, value)))
+            .collect(),
+        value => items(value)
+            .chunks(2)
+            .filter_map(|pair| match pair {
+                [name, value] => Some((text(name)?, value)),
+                _ => None,
+            })
+            .collect(),
+    }
+}
+
+fn field<'a>(fields: &[(String, &'a redis::Value)], name: &str) -> Option<&'a redis::Value> {
+    fields
+        .iter()
+        .find(|(field, _)| field == name)
+        .map(|(_, value)| *value)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn bulk(text: &str) -> redis::Value {
+        redis::Value::BulkString(text.as_bytes().to_vec())
+    }
+
+    fn array(values: Vec<redis::Value>) -> redis::Value {
+        redis::Value::Array(values)
+    }
+
+    #[test]
+    fn detects_modules_from_module_list() {
+        let reply = array(vec![
+            array(vec![
+                bulk("name"),
+                bulk("search"),
+                bulk("ver"),
+                redis::Value::Int(21005),
+            ]),
+            array(vec![
+                bulk("name"),
+                bulk("ReJSON"),
+                bulk("ver"),
+                redis::Value::Int(20609),
+            ]),
+        ]);
+        let modules = RedisModules::from_module_list(&reply);
+        assert!(modules.json && modules.search);
+        assert_eq!(
+            modules.modules[1],
+            ModuleInfo {
+                name: "ReJSON".to_string(),
+                version: Some(20609)
+            }
+        );
+        assert_eq!(
+            RedisModules::from_module_list(&array(vec![])),
+            RedisModules::default()
+        );
+    }
+
+    #[test]
+    fn parses_index_info() {
+        let reply = array(vec![
+            bulk("index_name"),
+            bulk("products"),
+            bulk("index_definition"),
+            array(vec![
+                bulk("key_type"),
+                bulk("JSON"),
+                bulk("prefixes"),
+                array(vec![bulk("product:")]),
+            ]),
+            bulk("attributes"),
+            array(vec![
+                array(vec![
+                    bulk("identifier"),
+                    bulk("$.name"),
+                    bulk("attribute"),
+                    bulk("name"),
+                    bulk("type"),
+                    bulk("TEXT"),
+                    bulk("WEIGHT"),
+                    bulk("1"),
+                ]),
+                array(vec![
+                    bulk("identifier"),
+                    bulk("$.price"),
+                    bulk("attribute"),
+                    bulk("price"),
+                    bulk("type"),
+                    bulk("NUMERIC"),
+                    bulk("SORTABLE"),
+                ]),
+            ]),
+            bulk("num_docs"),
+            bulk("3"),
+        ]);
+        let info = parse_index_info(&reply).unwrap();
+        assert_eq!(info.name, "products");
+        assert_eq!(info.key_type, "JSON");
+        assert_eq!(info.prefixes, vec!["product:"]);
+        assert_eq!(info.num_docs, Some(3));
+        assert_eq!(
+            info.attributes[1],
+            SearchAttribute {
+                identifier: "$.price".to_string(),
+                attribute: "price".to_string(),
+                field_type: "NUMERIC".to_string(),
+                sortable: true,
+            }
+        );
+        assert!(!info.attributes[0].sortable);
+
+        let structure = info.structure();
+        let columns: Vec<&str> = structure.columns.iter().map(|c| c.name.as_str()).collect();
+        assert_eq!(columns, vec!["__key", "name", "price"]);
+        assert_eq!(
+            info.return_arguments(),
+            vec!["RETURN", "6", "$.name", "AS", "name", "$.price", "AS", "price"]
+        );
+        let mut rows = vec![json!({ "__key": "product:1", "name": "Lamp", "price": "19.5" })];
+        info.decode_numbers(&mut rows);
+        assert_eq!(rows[0]["price"], json!(19.5));
+    }
+
+    #[test]
+    fn maps_search_replies_to_rows() {
+        let reply = array(vec![
+            redis::Value::Int(2),
+            bulk("product:1"),
+            array(vec![bulk("$"), bulk(r#"{"name":"Lamp","price":20}"#)]),
+            bulk("product:2"),
+            array(vec![bulk("name"), bulk("Desk")]),
+        ]);
+        let (total, rows) = search_rows(&reply, SearchReplyShape::default()).unwrap();
+        assert_eq!(total, 2);
+        assert_eq!(
+            rows,
+            vec![
+                json!({ "__key": "product:1", "$": { "name": "Lamp", "price": 20 } }),
+                json!({ "__key": "product:2", "name": "Desk" }),
+            ]
+        );
+
+        let command: Vec<Vec<u8>> = ["FT.SEARCH", "products", "*", "withscores", "NOCONTENT"]
+            .iter()
+            .map(|arg| arg.as_bytes().to_vec())
+            .collect();
+        let shape = SearchReplyShape::from_arguments(&command);
+        assert!(shape.with_scores && !shape.with_payloads);
+        let reply = array(vec![redis::Value::Int(1), bulk("product:1"), bulk("0.5")]);
+        assert_eq!(
+            search_rows(&reply, shape).unwrap().1,
+            vec![json!({ "__key": "product:1", "__score": 0.5 })]
+        );
+        assert_eq!(search_rows(&bulk("OK"), shape), None);
+    }
+
+    #[test]
+    fn maps_aggregate_replies_to_rows() {
+        let reply = array(vec![
+            redis::Value::Int(2),
+            array(vec![
+                bulk("category"),
+                bulk("lighting"),
+                bulk("count"),
+                bulk("4"),
+            ]),
+            array(vec![
+                bulk("category"),
+                bulk("desks"),
+                bulk("count"),
+                bulk("1"),
+            ]),
+        ]);
+        let (total, rows) = aggregate_rows(&reply).unwrap();
+        assert_eq!(total, 2);
+        assert_eq!(rows[0], json!({ "category": "lighting", "count": "4" }));
+
+        // A `WITHCURSOR` reply nests the page next to the cursor id.
+        let cursor = array(vec![reply, redis::Value::Int(42)]);
+        assert_eq!(aggregate_rows(&cursor), None);
+    }
+
+    #[test]
+    fn measures_json_documents() {
+        assert_eq!(json_length(&json!({ "a": 1, "b": 2 })), Some(2));
+        assert_eq!(json_length(&json!([1, 2, 3])), Some(3));
+        assert_eq!(json_length(&json!(4)), None);
+    }
+}
@@ -5,18 +5,30 @@
 //! Anything not explicitly listed is denied (fail closed): an unlisted safe
 //! read is wrongly rejected, but no write ever slips through.
 
-/// Returns `true` if `query` is a recognised read-only Redis command.
-///
+use super::redis_cli::{parse_script, RedisCommand, RedisScript};
+
+/// Returns `true` if every command of the console script `query` is a
+/// recognised read-only Redis command. Scripts that do not parse are denied.
+pub fn is_read_only_redis_command(query: &str) -> bool {
+    parse_script(query).is_ok_and(|script| first_write_command(&script).is_none())
+}
+
+/// The first command of `script` that the allowlist does not cover.
+pub fn first_write_command(script: &RedisScript) -> Option<&RedisCommand> {
+    script
+        .commands
+        .iter()
+        .find(|command| !is_read_only_command(command))
+}
+
 /// Single-word read commands are allowed by name. Multi-subcommand families
-/// (`CLIENT`, `MEMORY`, `OBJECT`, `XINFO`) are validated by subcommand so that
+/// (`CLIENT`, `MEMORY`, `OBJECT`, `MODULE`, `SCRIPT`, `FUNCTION`, `FT.CURSOR`,
+/// `XINFO`) are validated by subcommand so that
 /// administrative variants like `CLIENT KILL` or `MEMORY PURGE` are rejected
 /// even though the family name itself reads.
-pub fn is_read_only_redis_command(query: &str) -> bool {
-    let mut parts = query.split_whitespace();
-    let command = parts.next().unwrap_or("").to_uppercase();
-    let subcommand = parts.next().unwrap_or("").to_uppercase();
-
-    match command.as_str() {
+pub fn is_read_only_command(command: &RedisCommand) -> bool {
+    let subcommand = command.subcommand();
+    match command.name().as_str() {
         // Multi-subcommand families: only safe subcommands are read-only.
         "CLIENT" => matches!(
             subcommand.as_str(),
@@ -30,11 +42,15 @@ pub fn is_read_only_redis_command(query: &str) -> bool {
             subcommand.as_str(),
             "ENCODING" | "REFCOUNT" | "IDLETIME" | "FREQ" | "HELP"
         ),
+        "MODULE" => subcommand == "LIST",
+        "SCRIPT" => subcommand == "EXISTS",
+        "FUNCTION" => matches!(subcommand.as_str(), "LIST" | "STATS"),
+        "FT.CURSOR" => subcommand == "READ",
         // All XINFO subcommands are read-only.
         "XINFO" => true,
         // Plain read-only commands.
-        _ => matches!(
-            command.as_str(),
+        name => matches!(
+            name,
             // String / bitmap commands
             "GET" | "MGET" | "STRLEN" | "GETRANGE" | "SUBSTR" | "LCS"
             | "BITCOUNT" | "BITPOS" | "GETBIT" | "BITFIELD_RO"
@@ -64,6 +80,15 @@ pub fn is_read_only_redis_command(query: &str) -> bool {
             // Geo commands
             | "GEOSEARCH" | "GEOPOS" | "GEODIST" | "GEOHASH"
             | "GEORADIUS_RO" | "GEORADIUSBYMEMBER_RO"
+            // RedisJSON
+            | "JSON.GET" | "JSON.MGET" | "JSON.TYPE" | "JSON.RESP" | "JSON.STRLEN"
+            | "JSON.ARRLEN" | "JSON.ARRINDEX" | "JSON.OBJLEN" | "JSON.OBJKEYS"
+            // RediSearch
+            | "FT.SEARCH" | "FT.AGGREGATE" | "FT.INFO" | "FT._LIST" | "FT.EXPLAIN"
+            | "FT.EXPLAINCLI" | "FT.SPELLCHECK" | "FT.TAGVALS" | "FT.SYNDUMP"
+            | "FT.DICTDUMP"
+            // Scripts the server itself refuses to let write
+            | "EVAL_RO" | "EVALSHA_RO" | "FCALL_RO"
             // Misc
             | "WAIT"
         ),
@@ -117,5 +142,51 @@ mod tests {
         assert!(is_read_only_redis_command("MEMORY USAGE mykey"));
         assert!(is_read_only_redis_command("OBJECT ENCODING mykey"));
         assert!(is_read_only_redis_command("XINFO STREAM mystream"));
+        assert!(is_read_only_redis_command("MODULE LIST"));
+        assert!(!is_read_only_redis_command("MODULE LOAD /tmp/evil.so"));
+        assert!(is_read_only_redis_command("FT.CURSOR READ idx 42"));
+        assert!(!is_read_only_redis_command("FT.CURSOR DEL idx 42"));
+    }
+
+    #[test]
+    fn allows_read_only_scripts_but_not_scripts_that_may_write() {
+        assert!(is_read_only_redis_command("EVAL_RO \"return 1\" 0"));
+        assert!(is_read_only_redis_command(
+            "EVALSHA_RO e0e1f9fabfc9d4800c877a703b823ac0578ff8db 1 lock:a"
+        ));
+        assert!(is_read_only_redis_command("FCALL_RO lock_owner 1 lock:a"));
+        assert!(is_read_only_redis_command("SCRIPT EXISTS abc"));
+        assert!(is_read_only_redis_command("FUNCTION LIST WITHCODE"));
+        assert!(!is_read_only_redis_command("EVAL \"return 1\" 0"));
+        assert!(!is_read_only_redis_command("FCALL acquire 1 lock:a"));
+        assert!(!is_read_only_redis_command("SCRIPT FLUSH"));
+        assert!(!is_read_only_redis_command("FUNCTION DELETE locks"));
+    }
+
+    #[test]
+    fn allows_module_reads_but_not_module_writes() {
+        assert!(is_read_only_redis_command("JSON.GET product:1 $.name"));
+        assert!(is_read_only_redis_command(
+            "FT.SEARCH products \"@name:lamp\""
+        ));
+        assert!(is_read_only_redis_command(
+            "FT.AGGREGATE products * GROUPBY 1 @category"
+        ));
+        assert!(is_read_only_redis_command("FT._LIST"));
+        assert!(!is_read_only_redis_command("JSON.SET product:1 $.price 10"));
+        assert!(!is_read_only_redis_command("JSON.DEL product:1 $.name"));
+        assert!(!is_read_only_redis_command("FT.DROPINDEX products"));
+        assert!(!is_read_only_redis_command(
+            "FT.CREATE idx SCHEMA name TEXT"
+        ));
+    }
+
+    #[test]
+    fn checks_every_command_of_a_script() {
+        assert!(is_read_only_redis_command("GET a\nHGETALL \"my hash\""));
+        assert!(is_read_only_redis_command("MULTI\nGET a\nTTL a\nEXEC"));
+        assert!(!is_read_only_redis_command("GET a\nDEL a"));
+        assert!(!is_read_only_redis_command("MULTI\nGET a\nSET a 1\nEXEC"));
+        assert!(!is_read_only_redis_command("GET \"unterminated"));
     }
 }
@@ -0,0 +1,413 @@
+//! Lua scripts and Redis 7 function libraries.
+//!
+//! Scripts are saved per connection in the app database with typed
+//! parameters; [`bind_parameters`] turns the values a user or agent supplies
+//! into the `KEYS` and `ARGV` of the call. Scripts run by SHA1 through
+//! `EVALSHA`, so the server's script cache is reused between runs.
+
+use std::sync::Arc;
+
+use async_trait::async_trait;
+use serde::{Deserialize, Serialize};
+use serde_json::{Map, Value};
+
+use super::{DatabaseDriver, DriverError};
+use crate::db::models::QueryResult;
+
+/// Where a parameter goes in the call.
+#[derive(Clone, Copy, Debug, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum ScriptParameterKind {
+    Key,
+    Arg,
+}
+
+/// The values a parameter accepts. Redis receives every value as a string.
+#[derive(Clone, Copy, Debug, Default, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum ScriptValueType {
+    #[default]
+    String,
+    Integer,
+    Number,
+    /// Sent as `1` or `0`.
+    Boolean,
+    /// Any JSON value, sent serialized for `cjson.decode`.
+    Json,
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize, PartialEq)]
+pub struct ScriptParameter {
+    pub name: String,
+    pub kind: ScriptParameterKind,
+    #[serde(default, rename = "type")]
+    pub value_type: ScriptValueType,
+    /// Used when a run gives no value.
+    #[serde(default)]
+    pub default: Option<Value>,
+}
+
+/// The `KEYS` and `ARGV` of a script or function call.
+#[derive(Clone, Debug, Default, Serialize, Deserialize, PartialEq)]
+pub struct ScriptCall {
+    #[serde(default)]
+    pub keys: Vec<String>,
+    #[serde(default)]
+    pub args: Vec<String>,
+}
+
+/// A saved script's SHA1 and whether the server has it cached.
+#[derive(Clone, Debug, Serialize, PartialEq)]
+pub struct ScriptCacheStatus {
+    pub id: i64,
+    pub sha: String,
+    pub cached: bool,
+}
+
+#[derive(Clone, Debug, Serialize, PartialEq)]
+pub struct LibraryFunction {
+    pub name: String,
+    pub description: Option<String>,
+    /// `no-writes`, `allow-oom`, ...
+    pub flags: Vec<String>,
+}
+
+/// A library from `FUNCTION LIST`.
+#[derive(Clone, Debug, Serialize, PartialEq)]
+pub struct FunctionLibrary {
+    pub name: String,
+    pub engine: String,
+    pub functions: Vec<LibraryFunction>,
+    /// The source, when listed `WITHCODE`.
+    pub code: Option<String>,
+}
+
+/// Scripting for engines that run server-side code.
+#[async_trait]
+pub trait ScriptRunner: Send + Sync {
+    /// Run Lua `source` by its SHA1, loading it first when the server has
+    /// not cached it. `read_only` runs it with `EVALSHA_RO`, which the
+    /// server refuses for scripts that write.
+    async fn eval_script(
+        &self,
+        source: &str,
+        call: &ScriptCall,
+        read_only: bool,
+    ) -> Result<QueryResult, DriverError>;
+
+    /// Cache `source` on the server (every master of a cluster), returning
+    /// its SHA1.
+    async fn load_script(&self, source: &str) -> Result<String, DriverError>;
+
+    /// Whether each SHA1 is in the server's script cache.
+    async fn scripts_exist(&self, hashes: &[String]) -> Result<Vec<bool>, DriverError>;
+
+    async fn list_functions(&self, with_code: bool) -> Result<Vec<FunctionLibrary>, DriverError>;
+
+    /// Load a library whose source starts with `#!lua name=<library>`,
+    /// returning the library name. `replace` overwrites an existing one.
+    async fn load_function_library(
+        &self,
+        source: &str,
+        replace: bool,
+    ) -> Result<String, DriverError>;
+
+    async fn delete_function_library(&self, library: &str) -> Result<(), DriverError>;
+
+    /// `FCALL` a function, or `FCALL_RO` when `read_only`.
+    async fn call_function(
+        &self,
+        function: &str,
+        call: &ScriptCall,
+        read_only: bool,
+    ) -> Result<QueryResult, DriverError>;
+}
+
+/// The scripting of a pooled driver, or an error for engines without it.
+pub(crate) fn script_runner(
+    driver: &Arc<Box<dyn DatabaseDriver>>,
+) -> Result<&dyn ScriptRunner, String> {
+    driver
+        .scripting()
+        .ok_or_else(|| "Connection does not support scripts".to_string())
+}
+
+/// The SHA1 Redis caches `source` under.
+pub fn script_hash(source: &str) -> String {
+    redis::Script::new(source).get_hash().to_string()
+}
+
+/// Check a script's parameter list: names are required and unique, and
+/// defaults must be valid values of their type.
+pub fn validate_parameters(parameters: &[ScriptParameter]) -> Result<(), String> {
+    for (index, parameter) in parameters.iter().enumerate() {
+        if parameter.name.trim().is_empty() {
+            return Err("Parameter names are required".to_string());
+        }
+        if parameters[..index]
+            .iter()
+            .any(|other| other.name == parameter.name)
+        {
+            return Err(format!("Parameter '{}' is declared twice", parameter.name));
+        }
+        if let Some(default) = parameter.default.as_ref().filter(|value| !value.is_null()) {
+            encode_value(parameter, default)?;
+        }
+    }
+    Ok(())
+}
+
+/// Build the call from named `inputs`, in the order the parameters are
+/// declared. Missing values fall back to the parameter's default.
+pub fn bind_parameters(
+    parameters: &[ScriptParameter],
+    inputs: &Map<String, Value>,
+) -> Result<ScriptCall, String> {
+    if let Some(unknown) = inputs
+        .keys()
+        .find(|name| !parameters.iter().any(|parameter| &parameter.name == *name))
+    {
+        return Err(format!("Unknown parameter '{}'", unknown));
+    }
+    let mut call = ScriptCall::default();
+    for parameter in parameters {
+        let value = inputs
+            .get(&parameter.name)
+            .filter(|value| !value.is_null())
+            .or(parameter.default.as_ref().filter(|value| !value.is_null()))
+            .ok_or_else(|| format!("Missing value for '{}'", parameter.name))?;
+        let encoded = encode_value(parameter, value)?;
+        match parameter.kind {
+            ScriptParameterKind::Key if encoded.is_empty() => {
+                return Err(format!("Key '{}' must not be empty", parameter.name))
+            }
+            ScriptParameterKind::Key => call.keys.push(encoded),
+            ScriptParameterKind::Arg => call.args.push(encoded),
+        }
+    }
+    Ok(call)
+}
+
+fn encode_value(parameter: &ScriptParameter, value: &Value) -> Result<String, String> {
+    let invalid = |expected: &str| format!("'{}' must be {}", parameter.name, expected);
+    match (parameter.value_type, value) {
+        (ScriptValueType::String, Value::String(text)) => Ok(text.clone()),
+        (ScriptValueType::String, _) => Err(invalid("a string")),
+        (ScriptValueType::Integer, Value::Number(number)) => number
+            .as_i64()
+            .map(|number| number.to_string())
+            .ok_or_else(|| invalid("an integer")),
+        (ScriptValueType::Integer, Value::String(text)) => text
+            .trim()
+            .parse::<i64>()
+            .map(|number| number.to_string())
+            .map_err(|_| invalid("an integer")),
+        (ScriptValueType::Number, Value::Number(number)) => Ok(number.to_string()),
+        (ScriptValueType::Number, Value::String(text)) => text
+            .trim()
+            .parse::<f64>()
+            .ok()
+            .filter(|number| number.is_finite())
+            .map(|number| number.to_string())
+            .ok_or_else(|| invalid("a number")),
+        (ScriptValueType::Boolean, Value::Bool(flag)) => Ok(if *flag { "1" } else { "0" }.into()),
+        (ScriptValueType::Boolean, Value::String(text)) => match text.trim() {
+            "true" | "1" => Ok("1".to_string()),
+            "false" | "0" => Ok("0".to_string()),
+            _ => Err(invalid("true or false")),
+        },
+        (ScriptValueType::Json, value) => Ok(value.to_string()),
+        (ScriptValueType::Integer, _) => Err(invalid("an integer")),
+        (ScriptValueType::Number, _) => Err(invalid("a number")),
+        (ScriptValueType::Boolean, _) => Err(invalid("true or false")),
+    }
+}
+
+/// Parse a `FUNCTION LIST` reply.
+pub fn parse_function_list(reply: &redis::Value) -> Vec<FunctionLibrary> {
+    items(reply)
+        .iter()
+        .filter_map(|library| {
+            let fields = pairs(library);
+            Some(FunctionLibrary {
+                name: text(field(&fields, "library_name")?)?,
+                engine: field(&fields, "engine").and_then(text).unwrap_or_default(),
+                functions: field(&fields, "functions")
+                    .map(items)
+                    .unwrap_or_default()
+                    .iter()
+                    .filter_map(|function| {
+                        let fields = pairs(function);
+                        Some(LibraryFunction {
+                            name: text(field(&fields, "name")?)?,
+                            description: field(&fields, "description").and_then(text),
+                            flags: field(&fields, "flags")
+                                .map(|flags| items(flags).iter().filter_map(text).collect())
+                                .unwrap_or_default(),
+                        })
+                    })
+                    .collect(),
+                code: field(&fields, "library_code").and_then(text),
+            })
+        })
+        .collect()
+}
+
+fn text(value: &redis::Value) -> Option<String> {
+    match value {
+        redis::Value::BulkString(bytes) => Some(String::from_utf8_lossy(bytes).into_owned()),
+        redis::Value::SimpleString(text) => Some(text.clone()),
+        redis::Value::VerbatimString { text, .. } => Some(text.clone()),
+        _ => None,
+    }
+}
+
+fn items(value: &redis::Value) -> &[redis::Value] {
+    match value {
+        redis::Value::Array(items) | redis::Value::Set(items) => items,
+        _ => &[],
+    }
+}
+
+/// Name/value pairs of a flat array or of a RESP3 map.
+fn pairs(value: &redis::Value) -> Vec<(String, &redis::Value)> {
+    match value {
+        redis::Value::Map(entries) => entries
+            .iter()
+            .filter_map(|(name, value)| Some((text(name)?, value)))
+            .collect(),
+        value => items(value)
+            .chunks(2)
+            .filter_map(|pair| match pair {
+                [name, value] => Some((text(name)?, value)),
+                _ => None,
+            })
+            .collect(),
+    }
+}
+
+fn field<'a>(fields: &[(String, &'a redis::Value)], name: &str) -> Option<&'a redis::Value> {
+    fields
+        .iter()
+        .find(|(field, _)| field == name)
+        .map(|(_, value)| *value)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn parameters() -> Vec<ScriptParameter> {
+        serde_json::from_value(json!([
+            { "name": "bucket", "kind": "key" },
+            { "name": "limit", "kind": "arg", "type": "integer" },
+            { "name": "window", "kind": "arg", "type": "number", "default": 60 },
+            { "name": "strict", "kind": "arg", "type": "boolean", "default": false },
+            { "name": "meta", "kind": "arg", "type": "json", "default": null }
+        ]))
+        .unwrap()
+    }
+
+    fn inputs(values: Value) -> Map<String, Value> {
+        values.as_object().unwrap().clone()
+    }
+
+    #[test]
+    fn binds_keys_and_args_in_declaration_order() {
+        let call = bind_parameters(
+            &parameters(),
+            &inputs(json!({
+                "meta": { "source": "api" },
+                "limit": "100",
+                "bucket": "rate:user:1",
+                "strict": true
+            })),
+        )
+        .unwrap();
+        assert_eq!(call.keys, vec!["rate:user:1"]);
+        assert_eq!(call.args, vec!["100", "60", "1", r#"{"source":"api"}"#]);
+    }
+
+    #[test]
+    fn rejects_missing_unknown_and_mistyped_values() {
+        let error = |values: Value| bind_parameters(&parameters(), &inputs(values)).unwrap_err();
+        assert_eq!(
+            error(json!({ "bucket": "b", "limit": 1 })),
+            "Missing value for 'meta'"
+        );
+        assert_eq!(
+            error(json!({ "bucket": "b", "limit": 1, "meta": 1, "burst": 2 })),
+            "Unknown parameter 'burst'"
+        );
+        assert_eq!(
+            error(json!({ "bucket": "b", "limit": 1.5, "meta": 1 })),
+            "'limit' must be an integer"
+        );
+        assert_eq!(
+            error(json!({ "bucket": "", "limit": 1, "meta": 1 })),
+            "Key 'bucket' must not be empty"
+        );
+        assert_eq!(
+            error(json!({ "bucket": 7, "limit": 1, "meta": 1 })),
+            "'bucket' must be a string"
+        );
+    }
+
+    #[test]
+    fn validates_parameter_declarations() {
+        assert!(validate_parameters(&parameters()).is_ok());
+        let mut duplicated = parameters();
+        duplicated.push(duplicated[0].clone());
+        assert!(validate_parameters(&duplicated).is_err());
+        let mut bad_default = parameters();
+        bad_default[1].default = Some(json!("many"));
+        assert_eq!(
+            validate_parameters(&bad_default).unwrap_err(),
+            "'limit' must be an integer"
+        );
+    }
+
+    #[test]
+    fn hashes_scripts_like_redis() {
+        assert_eq!(
+            script_hash("return 1"),
+            "e0e1f9fabfc9d4800c877a703b823ac0578ff8db"
+        );
+    }
+
+    #[test]
+    fn parses_function_lists() {
+        let bulk = |text: &str| redis::Value::BulkString(text.as_bytes().to_vec());
+        let reply = redis::Value::Array(vec![redis::Value::Array(vec![
+            bulk("library_name"),
+            bulk("locks"),
+            bulk("engine"),
+            bulk("LUA"),
+            bulk("functions"),
+            redis::Value::Array(vec![redis::Value::Array(vec![
+                bulk("name"),
+                bulk("lock_owner"),
+                bulk("description"),
+                redis::Value::Nil,
+                bulk("flags"),
+                redis::Value::Array(vec![bulk("no-writes")]),
+            ])]),
+            bulk("library_code"),
+            bulk("#!lua name=locks\n..."),
+        ])]);
+        assert_eq!(
+            parse_function_list(&reply),
+            vec![FunctionLibrary {
+                name: "locks".to_string(),
+                engine: "LUA".to_string(),
+                functions: vec![LibraryFunction {
+                    name: "lock_owner".to_string(),
+                    description: None,
+                    flags: vec!["no-writes".to_string()],
+                }],
+                code: Some("#!lua name=locks\n...".to_string()),
+            }]
+        );
+    }
+}
@@ -0,0 +1,433 @@
+//! The engines DBcooper can connect to.
+//!
+//! Each engine registers a [`DriverDescriptor`]: its id and aliases, default
+//! port, the settings it reads, what it supports and a constructor taking
+//! those settings as a generic map. The driver factory looks engines up here
+//! instead of matching on [`DatabaseType`](super::DatabaseType), so an engine
+//! added with [`register_driver`] connects, browses and runs queries like a
+//! built-in one. Structured row edits, cell literals and filters are still
+//! written per SQL dialect and only work for the built-in engines.
+
+use std::sync::{OnceLock, RwLock};
+
+use serde::Serialize;
+use serde_json::{Map, Value};
+
+use super::{DatabaseDriver, DriverCapabilities, QueryLimits, SessionOptions};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ConfigFieldKind {
+    Text,
+    Integer,
+    Boolean,
+    /// A path to a local file.
+    Path,
+}
+
+/// A setting an engine reads from its [`DriverOptions`].
+#[derive(Debug, Clone, Copy, Serialize)]
+pub struct ConfigField {
+    pub name: &'static str,
+    pub label: &'static str,
+    pub kind: ConfigFieldKind,
+    pub required: bool,
+    /// Passwords and tokens: masked in forms, never shown back in full.
+    pub secret: bool,
+}
+
+impl ConfigField {
+    pub const fn new(name: &'static str, label: &'static str, kind: ConfigFieldKind) -> Self {
+        Self {
+            name,
+            label,
+            kind,
+            required: false,
+            secret: false,
+        }
+    }
+
+    pub const fn required(mut self) -> Self {
+        self.required = true;
+        self
+    }
+
+    pub const fn secret(mut self) -> Self {
+        self.secret = true;
+        self
+    }
+}
+
+/// The connection fields shared by network engines, stored in their own
+/// columns rather than in a connection's engine options.
+pub const HOST: ConfigField = ConfigField::new("host", "Host", ConfigFieldKind::Text);
+pub const PORT: ConfigField = ConfigField::new("port", "Port", ConfigFieldKind::Integer);
+pub const DATABASE: ConfigField = ConfigField::new("database", "Database", ConfigFieldKind::Text);
+pub const USERNAME: ConfigField = ConfigField::new("username", "Username", ConfigFieldKind::Text);
+pub const PASSWORD: ConfigField =
+    ConfigField::new("password", "Password", ConfigFieldKind::Text).secret();
+pub const SSL: ConfigField = ConfigField::new("ssl", "SSL", ConfigFieldKind::Boolean);
+pub const FILE_PATH: ConfigField =
+    ConfigField::new("file_path", "File", ConfigFieldKind::Path).required();
+
+const COLUMN_FIELDS: [&str; 7] = [
+    "host",
+    "port",
+    "database",
+    "username",
+    "password",
+    "ssl",
+    "file_path",
+];
+
+/// Whether connections to an engine can go through an SSH tunnel.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum SshSupport {
+    Supported,
+    /// Local files; tunnel settings are ignored.
+    NotApplicable,
+    /// Reached over an API that can't be tunnelled; enabling SSH is an error.
+    Unsupported,
+}
+
+/// The settings a driver is built from: the connection's columns and engine
+/// options in one map, plus session and limits. Host and port already point
+/// at the SSH tunnel when there is one.
+pub struct DriverOptions {
+    pub values: Map<String, Value>,
+    pub session: SessionOptions,
+    pub limits: QueryLimits,
+}
+
+impl DriverOptions {
+    /// A non-empty text setting.
+    pub fn text(&self, name: &str) -> Option<String> {
+        match self.values.get(name) {
+            Some(Value::String(value)) if !value.is_empty() => Some(value.clone()),
+            Some(Value::Number(value)) => Some(value.to_string()),
+            _ => None,
+        }
+    }
+
+    pub fn required_text(&self, name: &str, message: &str) -> Result<String, String> {
+        self.text(name).ok_or_else(|| message.to_string())
+    }
+
+    pub fn integer(&self, name: &str) -> Option<i64> {
+        match self.values.get(name) {
+            Some(Value::Number(value)) => value.as_i64(),
+            Some(Value::String(value)) => value.trim().parse().ok(),
+            _ => None,
+        }
+    }
+
+    pub fn flag(&self, name: &str) -> bool {
+        matches!(self.values.get(name), Some(Value::Bool(true)))
+    }
+}
+
+pub type DriverConstructor = fn(DriverOptions) -> Result<Box<dyn DatabaseDriver>, String>;
+
+/// Wraps a trimmed, non-empty query so the engine describes its plan, or
+/// explains why it can't.
+pub type ExplainStatement = fn(&str) -> Result<String, String>;
+
+#[derive(Clone, Serialize)]
+pub struct DriverDescriptor {
+    /// The `db_type` stored on connections.
+    pub id: &'static str,
+    pub label: &'static str,
+    /// Other names accepted for `id`, e.g. `postgresql`.
+    pub aliases: &'static [&'static str],
+    pub default_port: Option<i64>,
+    pub fields: &'static [ConfigField],
+    pub ssh: SshSupport,
+    /// Accepts on-connect SQL and session settings.
+    pub sessions: bool,
+    pub capabilities: DriverCapabilities,
+    /// Tables are addressed as `schema.table`; engines with a single
+    /// namespace (SQLite, D1) use the bare table name.
+    pub schema_qualified: bool,
+    /// A read that failed on a dropped connection may run again after a
+    /// reconnect. Off where a failed request may still have run.
+    pub replays_reads: bool,
+    #[serde(skip)]
+    pub explain: ExplainStatement,
+    #[serde(skip)]
+    pub build: DriverConstructor,
+}
+
+impl DriverDescriptor {
+    pub(crate) fn answers_to(&self, name: &str) -> bool {
+        self.id.eq_ignore_ascii_case(name)
+            || self
+                .aliases
+                .iter()
+                .any(|alias| alias.eq_ignore_ascii_case(name))
+    }
+
+    /// Wrap `query` so the engine describes its plan without running it.
+    pub fn explain_statement(&self, query: &str) -> Result<String, String> {
+        let query = query.trim().trim_end_matches(';').trim_end();
+        if query.is_empty() {
+            return Err("There is no query to explain".to_string());
+        }
+        (self.explain)(query)
+    }
+
+    /// Check a connection's engine options against the fields this engine
+    /// declares. Settings with their own columns don't belong in the options,
+    /// and required ones must be set.
+    pub fn validate_options(&self, options: &Map<String, Value>) -> Result<(), String> {
+        for (name, value) in options {
+            let field = self
+                .fields
+                .iter()
+                .find(|field| field.name == name && !COLUMN_FIELDS.contains(&field.name))
+                .ok_or_else(|| format!("{} has no option named '{}'", self.label, name))?;
+            let matches_kind = match field.kind {
+                ConfigFieldKind::Text | ConfigFieldKind::Path => value.is_string(),
+                ConfigFieldKind::Integer => value.is_i64(),
+                ConfigFieldKind::Boolean => value.is_boolean(),
+            };
+            if !matches_kind && !value.is_null() {
+                return Err(format!(
+                    "{} option '{}' must be {}",
+                    self.label,
+                    name,
+                    match field.kind {
+                        ConfigFieldKind::Text | ConfigFieldKind::Path => "text",
+                        ConfigFieldKind::Integer => "an integer",
+                        ConfigFieldKind::Boolean => "true or false",
+                    }
+                ));
+            }
+        }
+        for field in self.fields {
+            if !field.required || COLUMN_FIELDS.contains(&field.name) {
+                continue;
+            }
+            let missing = match options.get(field.name) {
+                None | Some(Value::Null) => true,
+                Some(Value::String(value)) => value.trim().is_empty(),
+                Some(_) => false,
+            };
+            if missing {
+                return Err(format!("{} is required", field.label));
+            }
+        }
+        Ok(())
+    }
+}
+
+pub struct DriverRegistry {
+    drivers: Vec<DriverDescriptor>,
+}
+
+impl DriverRegistry {
+    pub fn empty() -> Self {
+        Self {
+            drivers: Vec::new(),
+        }
+    }
+
+    /// The engines DBcooper ships with.
+    pub fn builtin() -> Self {
+        let mut registry = Self::empty();
+        for descriptor in [
+            super::postgres::DESCRIPTOR,
+            super::mysql::MYSQL_DESCRIPTOR,
+            super::mysql::MARIADB_DESCRIPTOR,
+            super::sqlite::DESCRIPTOR,
+            super::duckdb::DESCRIPTOR,
+            super::redis::DESCRIPTOR,
+            super::clickhouse::DESCRIPTOR,
+            super::d1::DESCRIPTOR,
+            super::sqlserver::DESCRIPTOR,
+        ] {
+            registry
+                .register(descriptor)
+                .expect("built-in engines have distinct names");
+        }
+        registry
+    }
+
+    /// Add an engine. Its id and aliases must not name an engine already here.
+    pub fn register(&mut self, descriptor: DriverDescriptor) -> Result<(), String> {
+        let names = std::iter::once(descriptor.id).chain(descriptor.aliases.iter().copied());
+        for name in names {
+            if let Some(existing) = self.find(name) {
+                return Err(format!(
+                    "'{}' is already registered by {}",
+                    name, existing.label
+                ));
+            }
+        }
+        self.drivers.push(descriptor);
+        Ok(())
+    }
+
+    /// The engine with this id or alias, ignoring case.
+    pub fn find(&self, name: &str) -> Option<&DriverDescriptor> {
+        self.drivers
+            .iter()
+            .find(|descriptor| descriptor.answers_to(name))
+    }
+
+    pub fn descriptors(&self) -> &[DriverDescriptor] {
+        &self.drivers
+    }
+}
+
+fn registry() -> &'static RwLock<DriverRegistry> {
+    static REGISTRY: OnceLock<RwLock<DriverRegistry>> = OnceLock::new();
+    REGISTRY.get_or_init(|| RwLock::new(DriverRegistry::builtin()))
+}
+
+/// Make an engine available to every connection in this process.
+pub fn register_driver(descriptor: DriverDescriptor) -> Result<(), String> {
+    registry()
+        .write()
+        .map_err(|_| "Driver registry is unavailable".to_string())?
+        .register(descriptor)
+}
+
+/// Look up an engine by id or alias.
+pub fn find_driver(name: &str) -> Result<DriverDescriptor, String> {
+    registry()
+        .read()
+        .map_err(|_| "Driver registry is unavailable".to_string())?
+        .find(name)
+        .cloned()
+        .ok_or_else(|| format!("Unsupported database type: {name}"))
+}
+
+/// Every registered engine, built-in ones first.
+pub fn driver_descriptors() -> Vec<DriverDescriptor> {
+    registry()
+        .read()
+        .map(|registry| registry.descriptors().to_vec())
+        .unwrap_or_default()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{
+        ConfigField, ConfigFieldKind, DriverDescriptor, DriverOptions, DriverRegistry, SshSupport,
+    };
+    use crate::database::{DatabaseDriver, QueryLimits, SessionOptions};
+    use serde_json::{json, Map};
+
+    fn build_nothing(_: DriverOptions) -> Result<Box<dyn DatabaseDriver>, String> {
+        Err("not a real engine".to_string())
+    }
+
+    const FIELDS: [ConfigField; 3] = [
+        super::HOST,
+        ConfigField::new("region", "Region", ConfigFieldKind::Text).required(),
+        ConfigField::new("replicas", "Replicas", ConfigFieldKind::Integer),
+    ];
+
+    fn descriptor(id: &'static str, aliases: &'static [&'static str]) -> DriverDescriptor {
+        DriverDescriptor {
+            id,
+            label: "Test engine",
+            aliases,
+            default_port: Some(1234),
+            fields: &FIELDS,
+            ssh: SshSupport::Supported,
+            sessions: false,
+            capabilities: crate::database::sqlite::CAPABILITIES,
+            schema_qualified: true,
+            replays_reads: true,
+            explain: |query| Ok(format!("EXPLAIN {query}")),
+            build: build_nothing,
+        }
+    }
+
+    #[test]
+    fn finds_builtin_engines_by_id_and_alias() {
+        let registry = DriverRegistry::builtin();
+        assert_eq!(registry.find("PostgreSQL").unwrap().id, "postgres");
+        assert_eq!(registry.find("cloudflare-d1").unwrap().id, "d1");
+        assert_eq!(registry.find("mariadb").unwrap().default_port, Some(3306));
+        assert!(registry.find("oracle").is_none());
+    }
+
+    #[test]
+    fn registers_new_engines_without_name_clashes() {
+        let mut registry = DriverRegistry::builtin();
+        registry
+            .register(descriptor("tidewater", &["tide"]))
+            .unwrap();
+        assert_eq!(registry.find("TIDE").unwrap().id, "tidewater");
+
+        assert!(registry
+            .register(descriptor("other", &["sqlite3"]))
+            .is_err());
+        assert!(registry.register(descriptor("tidewater", &[])).is_err());
+    }
+
+    #[test]
+    fn validates_engine_options_against_declared_fields() {
+        let engine = descriptor("tidewater", &[]);
+        let options = |value: serde_json::Value| -> Map<String, serde_json::Value> {
+            serde_json::from_value(value).unwrap()
+        };
+        assert!(engine
+            .validate_options(&options(json!({"region": "eu", "replicas": 2})))
+            .is_ok());
+        assert!(engine
+            .validate_options(&options(json!({"replicas": "two"})))
+            .is_err());
+        assert!(engine
+            .validate_options(&options(json!({"zone": "a"})))
+            .is_err());
+        // Host has its own column.
+        assert!(engine
+            .validate_options(&options(json!({"region": "eu", "host": "db"})))
+            .is_err());
+    }
+
+    #[test]
+    fn rejects_engine_options_missing_required_fields() {
+        let engine = descriptor("tidewater", &[]);
+        for options in [json!({}), json!({"region": null}), json!({"region": " "})] {
+            assert_eq!(
+                engine
+                    .validate_options(&serde_json::from_value(options).unwrap())
+                    .unwrap_err(),
+                "Region is required"
+            );
+        }
+
+        let registry = DriverRegistry::builtin();
+        let d1 = registry.find("d1").unwrap();
+        let account_only = serde_json::from_value(json!({"account_id": "account"})).unwrap();
+        assert_eq!(
+            d1.validate_options(&account_only).unwrap_err(),
+            "Database ID is required"
+        );
+    }
+
+    #[test]
+    fn reads_settings_from_the_generic_map() {
+        let options = DriverOptions {
+            values: serde_json::from_value(json!({
+                "host": "db.internal",
+                "port": "5433",
+                "database": "",
+                "ssl": true
+            }))
+            .unwrap(),
+            session: SessionOptions::default(),
+            limits: QueryLimits::default(),
+        };
+        assert_eq!(options.text("host").as_deref(), Some("db.internal"));
+        assert_eq!(options.integer("port"), Some(5433));
+        assert_eq!(options.text("database"), None);
+        assert!(options.flag("ssl"));
+        assert!(options.required_text("file_path", "File required").is_err());
+    }
+}
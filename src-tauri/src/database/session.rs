@@ -0,0 +1,160 @@
+//! Per-connection session state ("on connect" SQL and engine settings).
+//!
+//! Drivers apply this to every new physical connection from their pool's
+//! `after_connect` hook, so it survives pool recycling, `reset_pool` and
+//! `PoolManager` reconnects without callers having to replay anything.
+//! ClickHouse's HTTP interface is stateless, so its settings travel with every
+//! request as query settings instead.
+
+use std::collections::BTreeMap;
+
+/// ClickHouse URL parameters the driver controls itself; letting a saved
+/// setting override them would change which database is queried or weaken
+/// read-only enforcement.
+const CLICKHOUSE_RESERVED_SETTINGS: &[&str] = &[
+    "database",
+    "query",
+    "user",
+    "password",
+    "readonly",
+    "session_id",
+    "default_format",
+];
+
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct SessionOptions {
+    /// SQL run verbatim (may contain several statements) after the settings.
+    pub on_connect_sql: Option<String>,
+    /// Engine settings: Postgres GUCs, MySQL session variables, SQLite pragmas
+    /// or ClickHouse query settings.
+    pub settings: BTreeMap<String, String>,
+}
+
+impl SessionOptions {
+    pub fn new(on_connect_sql: Option<String>, settings: BTreeMap<String, String>) -> Self {
+        Self {
+            on_connect_sql: on_connect_sql.filter(|sql| !sql.trim().is_empty()),
+            settings: settings
+                .into_iter()
+                .map(|(name, value)| (name.trim().to_string(), value))
+                .filter(|(name, _)| !name.is_empty())
+                .collect(),
+        }
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.on_connect_sql.is_none() && self.settings.is_empty()
+    }
+
+    /// Reject setting names that cannot be spliced into `SET`/`PRAGMA`
+    /// statements, which take the name as an identifier rather than a bind.
+    pub fn validate_setting_names(&self) -> Result<(), String> {
+        for name in self.settings.keys() {
+            let mut chars = name.chars();
+            let valid_start = chars
+                .next()
+                .is_some_and(|ch| ch.is_ascii_alphabetic() || ch == '_');
+            if !valid_start || !chars.all(|ch| ch.is_ascii_alphanumeric() || ch == '_' || ch == '.')
+            {
+                return Err(format!("Invalid session setting name: {name}"));
+            }
+        }
+        Ok(())
+    }
+
+    pub fn validate_clickhouse(&self) -> Result<(), String> {
+        if self.on_connect_sql.is_some() {
+            return Err(
+                "ClickHouse connections are stateless over HTTP; use session settings instead of on-connect SQL"
+                    .to_string(),
+            );
+        }
+        self.validate_setting_names()?;
+        if let Some(name) = self.settings.keys().find(|name| {
+            CLICKHOUSE_RESERVED_SETTINGS.contains(&name.as_str()) || name.starts_with("param_")
+        }) {
+            return Err(format!(
+                "The ClickHouse setting '{name}' cannot be overridden"
+            ));
+        }
+        Ok(())
+    }
+}
+
+/// Quote a pragma or session-variable value. Numbers and ON/OFF-style keywords
+/// pass through so typed variables accept them; everything else is quoted.
+pub(crate) fn sql_setting_literal(value: &str) -> String {
+    let trimmed = value.trim();
+    let is_number = trimmed.parse::<f64>().is_ok_and(f64::is_finite);
+    let is_keyword = matches!(
+        trimmed.to_ascii_uppercase().as_str(),
+        "ON" | "OFF" | "TRUE" | "FALSE" | "DEFAULT"
+    );
+    if is_number || is_keyword {
+        trimmed.to_string()
+    } else {
+        format!("'{}'", value.replace('\'', "''"))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{sql_setting_literal, SessionOptions};
+    use std::collections::BTreeMap;
+
+    fn settings(pairs: &[(&str, &str)]) -> BTreeMap<String, String> {
+        pairs
+            .iter()
+            .map(|(name, value)| (name.to_string(), value.to_string()))
+            .collect()
+    }
+
+    #[test]
+    fn drops_blank_sql_and_setting_names() {
+        let session = SessionOptions::new(
+            Some("   ".to_string()),
+            settings(&[(" search_path ", "tenant, public"), ("  ", "ignored")]),
+        );
+
+        assert_eq!(session.on_connect_sql, None);
+        assert_eq!(
+            session.settings,
+            settings(&[("search_path", "tenant, public")])
+        );
+        assert!(SessionOptions::default().is_empty());
+    }
+
+    #[test]
+    fn rejects_setting_names_that_are_not_identifiers() {
+        let session = SessionOptions::new(None, settings(&[("sql_mode; DROP", "x")]));
+        assert!(session.validate_setting_names().is_err());
+
+        let session = SessionOptions::new(None, settings(&[("myapp.tenant_id", "42")]));
+        assert!(session.validate_setting_names().is_ok());
+    }
+
+    #[test]
+    fn protects_clickhouse_parameters_the_driver_owns() {
+        for name in ["readonly", "database", "param_f0"] {
+            let session = SessionOptions::new(None, settings(&[(name, "0")]));
+            assert!(session.validate_clickhouse().is_err(), "{name}");
+        }
+        let session = SessionOptions::new(Some("SET x = 1".to_string()), BTreeMap::new());
+        assert!(session.validate_clickhouse().is_err());
+
+        let session = SessionOptions::new(None, settings(&[("max_threads", "4")]));
+        assert!(session.validate_clickhouse().is_ok());
+    }
+
+    #[test]
+    fn quotes_setting_values_unless_numeric_or_boolean() {
+        assert_eq!(sql_setting_literal("1000"), "1000");
+        assert_eq!(sql_setting_literal("ON"), "ON");
+        assert_eq!(sql_setting_literal("+00:00"), "'+00:00'");
+        assert_eq!(
+            sql_setting_literal("STRICT_TRANS_TABLES,NO_ZERO_DATE"),
+            "'STRICT_TRANS_TABLES,NO_ZERO_DATE'"
+        );
+        assert_eq!(sql_setting_literal("it's"), "'it''s'");
+    }
+}
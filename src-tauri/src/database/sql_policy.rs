@@ -4,12 +4,13 @@ use std::sync::OnceLock;
 use serde::Deserialize;
 use serde_json::Value;
 
+use super::registry::find_driver;
+
 #[derive(Deserialize)]
 #[serde(rename_all = "camelCase")]
 struct DialectPolicy {
     label: String,
     file_database: bool,
-    structured_row_mutations: bool,
     create_table_types: Vec<String>,
     expressions_by_type: HashMap<String, Vec<String>>,
     modifier_policy: Option<String>,
@@ -43,14 +44,12 @@ fn catalog() -> &'static DatabaseCatalog {
     })
 }
 
+/// The catalog entry of an engine, looked up by its registered id so every
+/// alias resolves.
 fn dialect_policy(db_type: &str) -> Result<&'static DialectPolicy, String> {
-    let db_type = match db_type {
-        "postgresql" => "postgres",
-        "sqlite3" => "sqlite",
-        other => other,
-    };
+    let engine = find_driver(db_type)?;
     catalog()
-        .get(db_type)
+        .get(engine.id)
         .ok_or_else(|| format!("Unsupported database type: {db_type}"))
 }
 
@@ -62,14 +61,16 @@ pub fn is_file_database(db_type: &str) -> Result<bool, String> {
     Ok(dialect_policy(db_type)?.file_database)
 }
 
+/// Whether the engine's driver supports row edits without SQL; see
+/// [`DriverCapabilities::row_editing`](super::DriverCapabilities::row_editing).
 pub fn ensure_structured_mutations_supported(db_type: &str) -> Result<(), String> {
-    let policy = dialect_policy(db_type)?;
-    if policy.structured_row_mutations {
+    let engine = find_driver(db_type)?;
+    if engine.capabilities.row_editing {
         Ok(())
     } else {
         Err(format!(
             "Structured row editing is not supported for {}; use the SQL editor",
-            policy.label
+            engine.label
         ))
     }
 }
@@ -183,7 +184,7 @@ pub fn format_sql_value(value: &Value) -> String {
 #[cfg(test)]
 mod tests {
     use super::{
-        ensure_structured_mutations_supported, supports_create_table_modifier,
+        database_label, ensure_structured_mutations_supported, supports_create_table_modifier,
         supports_create_table_type, validate_default_expression, validate_raw_sql_value,
         CreateTableModifier,
     };
@@ -224,10 +225,18 @@ mod tests {
     }
 
     #[test]
-    fn structured_mutation_capability_comes_from_the_database_catalog() {
+    fn resolves_every_engine_alias() {
+        assert_eq!(database_label("mssql"), Ok("SQL Server"));
+        assert_eq!(database_label("sqlite3"), database_label("sqlite"));
+        assert!(database_label("oracle").is_err());
+    }
+
+    #[test]
+    fn structured_mutation_capability_comes_from_the_driver() {
         assert!(ensure_structured_mutations_supported("postgres").is_ok());
         assert!(ensure_structured_mutations_supported("postgresql").is_ok());
         assert!(ensure_structured_mutations_supported("sqlite").is_ok());
+        assert!(ensure_structured_mutations_supported("mssql").is_ok());
         assert!(ensure_structured_mutations_supported("duckdb").is_err());
         assert!(ensure_structured_mutations_supported("clickhouse").is_err());
         assert!(ensure_structured_mutations_supported("redis").is_err());
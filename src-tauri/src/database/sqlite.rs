@@ -2,26 +2,68 @@ use async_trait::async_trait;
 use futures_util::{StreamExt, TryStreamExt};
 use serde_json::{json, Value};
 use sqlx::sqlite::SqlitePoolOptions;
-use sqlx::{Column, Row, TypeInfo};
+use sqlx::{Column, Executor, Row, TypeInfo};
 
+use super::capabilities::{DriverCapabilities, ReadOnlyEnforcement};
+use super::cell;
 use super::create_table::build_sqlite_create_table_sql;
 use super::filter::{
     build_where_clause, classify_column_type, compile_filter, structured_expression,
     CompiledFilter, FilterDialect, FilterValue,
 };
-use super::{query_returns_rows, DatabaseDriver, SqliteConfig};
+use super::registry::{DriverDescriptor, DriverOptions, SshSupport, FILE_PATH};
+use super::session::sql_setting_literal;
+use super::{query_returns_rows, DatabaseDriver, DriverError, DriverErrorCategory, SqliteConfig};
 use crate::database::queries::sqlite::{
     COLUMNS_QUERY, FOREIGN_KEYS_QUERY, INDEXES_QUERY, TABLES_QUERY,
 };
 use crate::db::models::{
-    ColumnInfo, CreateTableRequest, ForeignKeyInfo, IndexInfo, QueryResult, SchemaOverview,
-    TableDataResponse, TableFilter, TableInfo, TableStructure, TableWithStructure,
+    ColumnInfo, CreateTableRequest, ForeignKeyInfo, IndexInfo, QueryResult, ResultColumn,
+    SchemaOverview, TableDataResponse, TableFilter, TableInfo, TableStructure, TableWithStructure,
     TestConnectionResult,
 };
 use std::collections::HashMap;
 use std::sync::Arc;
 use tokio::sync::RwLock;
 
+pub(crate) const CAPABILITIES: DriverCapabilities = DriverCapabilities {
+    transactions: true,
+    create_table: true,
+    alter_table: false,
+    functions: false,
+    read_only: ReadOnlyEnforcement::Engine,
+    cancellation: false,
+    streaming: true,
+    multiple_databases: false,
+    key_value_browsing: false,
+    explain: true,
+    row_editing: true,
+};
+
+pub(crate) const DESCRIPTOR: DriverDescriptor = DriverDescriptor {
+    id: "sqlite",
+    label: "SQLite",
+    aliases: &["sqlite3"],
+    default_port: None,
+    fields: &[FILE_PATH],
+    ssh: SshSupport::NotApplicable,
+    sessions: true,
+    capabilities: CAPABILITIES,
+    schema_qualified: false,
+    replays_reads: true,
+    explain: |query| Ok(format!("EXPLAIN QUERY PLAN {query}")),
+    build: build_driver,
+};
+
+fn build_driver(options: DriverOptions) -> Result<Box<dyn DatabaseDriver>, String> {
+    Ok(Box::new(SqliteDriver::new(SqliteConfig {
+        file_path: options
+            .required_text("file_path", "File path is required for SQLite connections")?,
+        session: options.session,
+        limits: options.limits,
+    })))
+}
+
 pub struct SqliteDriver {
     config: SqliteConfig,
     /// Cached pool, created lazily and reused across queries (previously a fresh
@@ -58,8 +100,28 @@ impl SqliteDriver {
     }
 
     async fn create_pool(&self) -> Result<sqlx::SqlitePool, String> {
+        self.config.session.validate_setting_names()?;
+        let mut statements: Vec<String> = self
+            .config
+            .session
+            .settings
+            .iter()
+            .map(|(name, value)| format!("PRAGMA {name} = {}", sql_setting_literal(value)))
+            .collect();
+        statements.extend(self.config.session.on_connect_sql.clone());
+        let statements = Arc::new(statements);
+
         SqlitePoolOptions::new()
             .max_connections(1)
+            .after_connect(move |conn, _meta| {
+                let statements = statements.clone();
+                Box::pin(async move {
+                    for statement in statements.iter() {
+                        conn.execute(statement.as_str()).await?;
+                    }
+                    Ok(())
+                })
+            })
             .connect(&self.connection_string())
             .await
             .map_err(|e| e.to_string())
@@ -98,18 +160,19 @@ impl SqliteDriver {
         Ok(rows.into_iter().map(|(column,)| column).collect())
     }
 
-    fn row_to_json(row: &sqlx::sqlite::SqliteRow) -> Value {
-        let mut obj = serde_json::Map::new();
+    /// The row's cells in column order.
+    fn row_values(row: &sqlx::sqlite::SqliteRow) -> Vec<Value> {
+        let mut values = Vec::with_capacity(row.len());
         for (i, col) in row.columns().iter().enumerate() {
             let type_name = col.type_info().name().to_uppercase();
             let value: Value = match type_name.as_str() {
                 "INTEGER" => row
                     .try_get::<i64, _>(i)
-                    .map(|v| json!(v))
+                    .map(cell::integer)
                     .unwrap_or(Value::Null),
                 "REAL" => row
                     .try_get::<f64, _>(i)
-                    .map(|v| json!(v))
+                    .map(cell::float)
                     .unwrap_or(Value::Null),
                 "TEXT" => row
                     .try_get::<String, _>(i)
@@ -117,15 +180,16 @@ impl SqliteDriver {
                     .unwrap_or(Value::Null),
                 "BLOB" => row
                     .try_get::<Vec<u8>, _>(i)
-                    .map(|v| json!(format!("[{} bytes]", v.len())))
+                    .map(|v| cell::binary(&v))
                     .unwrap_or(Value::Null),
                 // NULL type can mean either an actual NULL value or an expression result like COUNT(*)
                 // Try to extract as various types before giving up
                 "NULL" => row
                     .try_get::<i64, _>(i)
-                    .map(|v| json!(v))
-                    .or_else(|_| row.try_get::<f64, _>(i).map(|v| json!(v)))
+                    .map(cell::integer)
+                    .or_else(|_| row.try_get::<f64, _>(i).map(cell::float))
                     .or_else(|_| row.try_get::<String, _>(i).map(|v| json!(v)))
+                    .or_else(|_| row.try_get::<Vec<u8>, _>(i).map(|v| cell::binary(&v)))
                     .unwrap_or(Value::Null),
 
                 "BOOLEAN" | "BOOL" => row
@@ -134,12 +198,18 @@ impl SqliteDriver {
                     .or_else(|_| row.try_get::<i64, _>(i).map(|v| json!(v != 0)))
                     .unwrap_or(Value::Null),
                 // Handle datetime types - SQLite stores these as TEXT, REAL, or INTEGER
-                "DATETIME" | "DATE" | "TIME" | "TIMESTAMP" => row
-                    .try_get::<String, _>(i)
-                    .map(|v| json!(v))
-                    .or_else(|_| row.try_get::<f64, _>(i).map(|v| json!(v.to_string())))
-                    .or_else(|_| row.try_get::<i64, _>(i).map(|v| json!(v.to_string())))
-                    .unwrap_or(Value::Null),
+                "DATETIME" | "DATE" | "TIME" | "TIMESTAMP" => {
+                    let tag: fn(String) -> Value = match type_name.as_str() {
+                        "DATE" => cell::date,
+                        "TIME" => cell::time,
+                        _ => cell::timestamp,
+                    };
+                    row.try_get::<String, _>(i)
+                        .or_else(|_| row.try_get::<f64, _>(i).map(|v| v.to_string()))
+                        .or_else(|_| row.try_get::<i64, _>(i).map(|v| v.to_string()))
+                        .map(tag)
+                        .unwrap_or(Value::Null)
+                }
                 _ => {
                     // For unknown types (like COUNT(*) which returns NULL type),
                     // try extracting as different types in order of likelihood
@@ -151,26 +221,36 @@ impl SqliteDriver {
                         int_result
                     );
                     int_result
-                        .map(|v| json!(v))
-                        .or_else(|_| row.try_get::<f64, _>(i).map(|v| json!(v)))
+                        .map(cell::integer)
+                        .or_else(|_| row.try_get::<f64, _>(i).map(cell::float))
                         .or_else(|_| row.try_get::<String, _>(i).map(|v| json!(v)))
                         .or_else(|_| row.try_get::<bool, _>(i).map(|v| json!(v)))
+                        .or_else(|_| row.try_get::<Vec<u8>, _>(i).map(|v| cell::binary(&v)))
                         .unwrap_or(Value::Null)
                 }
             };
-            obj.insert(col.name().to_string(), value);
+            values.push(value);
         }
-        Value::Object(obj)
+        values
+    }
+
+    fn result_columns(row: &sqlx::sqlite::SqliteRow) -> Vec<ResultColumn> {
+        row.columns()
+            .iter()
+            .map(|col| {
+                cell::result_column(col.name(), col.type_info().name(), FilterDialect::Sqlite)
+            })
+            .collect()
     }
 }
 
 #[async_trait]
 impl DatabaseDriver for SqliteDriver {
-    fn as_any(&self) -> &dyn std::any::Any {
-        self
+    fn capabilities(&self) -> DriverCapabilities {
+        CAPABILITIES
     }
 
-    async fn test_connection(&self) -> Result<TestConnectionResult, String> {
+    async fn test_connection(&self) -> Result<TestConnectionResult, DriverError> {
         match self.get_pool().await {
             Ok(pool) => {
                 let result = sqlx::query("SELECT 1").fetch_one(&pool).await;
@@ -192,7 +272,7 @@ impl DatabaseDriver for SqliteDriver {
         }
     }
 
-    async fn list_tables(&self) -> Result<Vec<TableInfo>, String> {
+    async fn list_tables(&self) -> Result<Vec<TableInfo>, DriverError> {
         let pool = self.get_pool().await?;
 
         // SQLite doesn't have schemas, so we use "main" as the default schema
@@ -221,11 +301,11 @@ impl DatabaseDriver for SqliteDriver {
             .collect())
     }
 
-    fn preview_create_table(&self, request: &CreateTableRequest) -> Result<String, String> {
-        build_sqlite_create_table_sql(request)
+    fn preview_create_table(&self, request: &CreateTableRequest) -> Result<String, DriverError> {
+        Ok(build_sqlite_create_table_sql(request)?)
     }
 
-    async fn create_table(&self, request: &CreateTableRequest) -> Result<TableInfo, String> {
+    async fn create_table(&self, request: &CreateTableRequest) -> Result<TableInfo, DriverError> {
         let sql = self.preview_create_table(request)?;
         let pool = self.get_pool().await?;
         sqlx::query(&sql)
@@ -249,7 +329,7 @@ impl DatabaseDriver for SqliteDriver {
         filter: Option<TableFilter>,
         sort_column: Option<String>,
         sort_direction: Option<String>,
-    ) -> Result<TableDataResponse, String> {
+    ) -> Result<TableDataResponse, DriverError> {
         let pool = self.get_pool().await?;
 
         let offset = (page - 1) * limit;
@@ -318,10 +398,15 @@ impl DatabaseDriver for SqliteDriver {
         }
         .map_err(|e| e.to_string())?;
 
-        let data: Vec<Value> = rows.iter().map(Self::row_to_json).collect();
+        let columns = rows.first().map(Self::result_columns).unwrap_or_default();
+        let data = rows
+            .iter()
+            .map(|row| cell::row_object(&columns, &Self::row_values(row)))
+            .collect();
 
         Ok(TableDataResponse {
             data,
+            columns,
             total,
             page,
             limit,
@@ -332,7 +417,7 @@ impl DatabaseDriver for SqliteDriver {
         &self,
         _schema: &str, // SQLite doesn't use schemas
         table: &str,
-    ) -> Result<TableStructure, String> {
+    ) -> Result<TableStructure, DriverError> {
         let pool = self.get_pool().await?;
 
         // Get columns using PRAGMA
@@ -429,73 +514,59 @@ impl DatabaseDriver for SqliteDriver {
         })
     }
 
-    async fn execute_query(&self, query: &str) -> Result<QueryResult, String> {
+    async fn execute_query(&self, query: &str) -> Result<QueryResult, DriverError> {
         let start_time = std::time::Instant::now();
         let pool = self.get_pool().await?;
 
+        // SQLite has no statement timeout. When the limit runs out the result
+        // is abandoned, but the statement keeps its connection until it ends.
+        let limits = &self.config.limits;
         if query_returns_rows(query) {
-            match sqlx::query(query)
-                .fetch(&pool)
-                .take(crate::database::MAX_QUERY_RESULT_ROWS + 1)
-                .try_collect::<Vec<_>>()
-                .await
-            {
-                Ok(rows) => {
-                    let truncated = rows.len() > crate::database::MAX_QUERY_RESULT_ROWS;
-                    let data: Vec<Value> = rows
-                        .iter()
-                        .take(crate::database::MAX_QUERY_RESULT_ROWS)
-                        .map(Self::row_to_json)
-                        .collect();
-                    let row_count = data.len() as i64;
-                    Ok(QueryResult {
-                        data,
-                        row_count,
-                        truncated,
-                        rows_affected: None,
-                        error: None,
-                        time_taken_ms: Some(start_time.elapsed().as_millis()),
-                    })
+            let rows = limits.run(
+                false,
+                sqlx::query(query)
+                    .fetch(&pool)
+                    .take(limits.fetch_limit())
+                    .try_collect::<Vec<_>>(),
+            );
+            match rows.await {
+                Err(timeout) => Ok(QueryResult::from_error(timeout, start_time)),
+                Ok(Ok(rows)) => {
+                    let columns = rows.first().map(Self::result_columns).unwrap_or_default();
+                    let (values, truncated) =
+                        limits.bound_rows(rows.iter().map(Self::row_values).collect());
+                    Ok(QueryResult::from_table(
+                        columns, values, truncated, start_time,
+                    ))
                 }
-                Err(e) => Ok(QueryResult {
-                    data: vec![],
-                    row_count: 0,
-                    truncated: false,
-                    rows_affected: None,
-                    error: Some(e.to_string()),
-                    time_taken_ms: Some(start_time.elapsed().as_millis()),
-                }),
+                Ok(Err(e)) => Ok(QueryResult::from_error(
+                    DriverError::from(e).locate(query),
+                    start_time,
+                )),
             }
         } else {
-            match sqlx::query(query).execute(&pool).await {
-                Ok(result) => {
-                    let rows_affected = result.rows_affected();
-                    Ok(QueryResult {
-                        data: vec![],
-                        row_count: rows_affected as i64,
-                        truncated: false,
-                        rows_affected: Some(rows_affected),
-                        error: None,
-                        time_taken_ms: Some(start_time.elapsed().as_millis()),
-                    })
-                }
-                Err(e) => Ok(QueryResult {
-                    data: vec![],
-                    row_count: 0,
-                    truncated: false,
-                    rows_affected: None,
-                    error: Some(e.to_string()),
-                    time_taken_ms: Some(start_time.elapsed().as_millis()),
-                }),
+            match limits.run(false, sqlx::query(query).execute(&pool)).await {
+                Err(timeout) => Ok(QueryResult::from_error(timeout, start_time)),
+                Ok(Ok(result)) => Ok(QueryResult::from_affected(
+                    result.rows_affected(),
+                    start_time,
+                )),
+                Ok(Err(e)) => Ok(QueryResult::from_error(
+                    DriverError::from(e).locate(query),
+                    start_time,
+                )),
             }
         }
     }
 
-    async fn execute_query_read_only(&self, query: &str) -> Result<QueryResult, String> {
+    async fn execute_query_read_only(&self, query: &str) -> Result<QueryResult, DriverError> {
         let start_time = std::time::Instant::now();
         if !crate::database::sqlite_read_only_query_is_safe(query) {
             return Ok(QueryResult::from_error(
-                "Read-only mode does not allow ATTACH or DETACH".to_string(),
+                DriverError::new(
+                    DriverErrorCategory::Permission,
+                    "Read-only mode does not allow ATTACH or DETACH",
+                ),
                 start_time,
             ));
         }
@@ -509,31 +580,39 @@ impl DatabaseDriver for SqliteDriver {
         // mutating pragmas or otherwise.
         if let Err(e) = sqlx::query("PRAGMA query_only = ON").execute(&pool).await {
             pool.close().await;
-            return Ok(QueryResult::from_error(e.to_string(), start_time));
+            return Ok(QueryResult::from_error(e, start_time));
         }
 
-        let result = sqlx::query(query)
-            .fetch(&pool)
-            .take(crate::database::MAX_QUERY_RESULT_ROWS + 1)
-            .try_collect::<Vec<_>>()
+        let limits = &self.config.limits;
+        let result = limits
+            .run(
+                false,
+                sqlx::query(query)
+                    .fetch(&pool)
+                    .take(limits.fetch_limit())
+                    .try_collect::<Vec<_>>(),
+            )
             .await;
         pool.close().await;
 
         match result {
-            Ok(rows) => {
-                let truncated = rows.len() > crate::database::MAX_QUERY_RESULT_ROWS;
-                let data: Vec<Value> = rows
-                    .iter()
-                    .take(crate::database::MAX_QUERY_RESULT_ROWS)
-                    .map(Self::row_to_json)
-                    .collect();
-                Ok(QueryResult::from_rows(data, truncated, start_time))
+            Ok(Ok(rows)) => {
+                let columns = rows.first().map(Self::result_columns).unwrap_or_default();
+                let (values, truncated) =
+                    limits.bound_rows(rows.iter().map(Self::row_values).collect());
+                Ok(QueryResult::from_table(
+                    columns, values, truncated, start_time,
+                ))
             }
-            Err(e) => Ok(QueryResult::from_error(e.to_string(), start_time)),
+            Ok(Err(e)) => Ok(QueryResult::from_error(
+                DriverError::from(e).locate(query),
+                start_time,
+            )),
+            Err(timeout) => Ok(QueryResult::from_error(timeout, start_time)),
         }
     }
 
-    async fn get_schema_overview(&self) -> Result<SchemaOverview, String> {
+    async fn get_schema_overview(&self) -> Result<SchemaOverview, DriverError> {
         let pool = self.get_pool().await?;
 
         let tables_rows = sqlx::query(TABLES_QUERY)
@@ -0,0 +1,815 @@
+use async_trait::async_trait;
+use futures_util::{StreamExt, TryStreamExt};
+use serde_json::{json, Value};
+use std::collections::BTreeMap;
+use std::sync::Mutex;
+use tiberius::{
+    AuthMethod, Client, ColumnData, ColumnType, Config, EncryptionLevel, FromSql, Query, Row,
+};
+use tokio::net::TcpStream;
+use tokio_util::compat::{Compat, TokioAsyncWriteCompatExt};
+
+use super::capabilities::{DriverCapabilities, ReadOnlyEnforcement};
+use super::cell;
+use super::filter::{
+    build_where_clause, classify_column_type, compile_filter, structured_expression,
+    CompiledFilter, FilterDialect, FilterValue,
+};
+use super::mutation::MutationPlan;
+use super::queries::sqlserver::{
+    DATABASES_QUERY, PRIMARY_KEY_QUERY, TABLES_QUERY, TABLE_COLUMNS_QUERY,
+    TABLE_FOREIGN_KEYS_QUERY, TABLE_INDEXES_QUERY,
+};
+use super::registry::{
+    ConfigField, ConfigFieldKind, DriverDescriptor, DriverOptions, SshSupport, DATABASE, HOST,
+    PASSWORD, PORT, SSL, USERNAME,
+};
+use super::session::sql_setting_literal;
+use super::sqlserver_read_only::{query_is_safe, reports_row_counts, returns_rows};
+use super::{DatabaseDriver, DriverError, DriverErrorCategory, SqlServerConfig};
+use crate::db::models::{
+    ColumnInfo, ForeignKeyInfo, IndexInfo, QueryResult, ResultColumn, SchemaOverview,
+    TableDataResponse, TableFilter, TableInfo, TableStructure, TableWithStructure,
+    TestConnectionResult,
+};
+
+pub(crate) const CAPABILITIES: DriverCapabilities = DriverCapabilities {
+    transactions: true,
+    create_table: false,
+    alter_table: false,
+    functions: false,
+    read_only: ReadOnlyEnforcement::Denylist,
+    cancellation: false,
+    streaming: true,
+    multiple_databases: true,
+    key_value_browsing: false,
+    explain: false,
+    row_editing: true,
+};
+
+pub(crate) const DESCRIPTOR: DriverDescriptor = DriverDescriptor {
+    id: "sqlserver",
+    label: "SQL Server",
+    aliases: &["mssql"],
+    default_port: Some(1433),
+    fields: &[
+        HOST,
+        PORT,
+        DATABASE,
+        USERNAME,
+        PASSWORD,
+        SSL,
+        ConfigField::new(
+            "trust_server_certificate",
+            "Trust server certificate",
+            ConfigFieldKind::Boolean,
+        ),
+    ],
+    ssh: SshSupport::Supported,
+    sessions: true,
+    capabilities: CAPABILITIES,
+    schema_qualified: true,
+    replays_reads: true,
+    explain: |_| {
+        Err(
+            "SQL Server plans need SET SHOWPLAN_XML in its own batch; run it from the SQL editor"
+                .to_string(),
+        )
+    },
+    build: build_driver,
+};
+
+fn build_driver(options: DriverOptions) -> Result<Box<dyn DatabaseDriver>, String> {
+    Ok(Box::new(SqlServerDriver::new(SqlServerConfig {
+        host: options.text("host").unwrap_or_default(),
+        port: options.integer("port").unwrap_or(1433),
+        database: options.text("database").unwrap_or_default(),
+        username: options.text("username").unwrap_or_default(),
+        password: options.text("password").unwrap_or_default(),
+        ssl: options.flag("ssl"),
+        trust_server_certificate: options.flag("trust_server_certificate"),
+        session: options.session,
+        limits: options.limits,
+    })))
+}
+
+type TdsClient = Client<Compat<TcpStream>>;
+
+/// Idle connections kept for catalog and structured statements. TDS
+/// connections are cheap to keep but slow to open (TLS plus login), so a
+/// handful are reused.
+const MAX_IDLE_CONNECTIONS: usize = 5;
+
+pub struct SqlServerDriver {
+    config: SqlServerConfig,
+    idle: Mutex<Vec<TdsClient>>,
+}
+
+impl SqlServerDriver {
+    pub fn new(config: SqlServerConfig) -> Self {
+        Self {
+            config,
+            idle: Mutex::new(Vec::new()),
+        }
+    }
+
+    fn tds_config(&self, read_only: bool) -> Result<Config, String> {
+        let port =
+            u16::try_from(self.config.port).map_err(|_| "Port must be between 1 and 65535")?;
+        let mut config = Config::new();
+        config.host(&self.config.host);
+        config.port(port);
+        if !self.config.database.is_empty() {
+            config.database(&self.config.database);
+        }
+        config.authentication(AuthMethod::sql_server(
+            &self.config.username,
+            &self.config.password,
+        ));
+        // Without SSL only the login packet is encrypted, so the certificate
+        // (self-signed on most installs) is not checked.
+        if self.config.ssl {
+            config.encryption(EncryptionLevel::Required);
+            if self.config.trust_server_certificate {
+                config.trust_cert();
+            }
+        } else {
+            config.encryption(EncryptionLevel::Off);
+            config.trust_cert();
+        }
+        config.readonly(read_only);
+        Ok(config)
+    }
+
+    /// `SET` statements for the configured session options. Names are
+    /// validated as identifiers because T-SQL cannot bind them.
+    fn session_statements(&self) -> Result<Vec<String>, String> {
+        self.config.session.validate_setting_names()?;
+        let mut statements: Vec<String> = self
+            .config
+            .session
+            .settings
+            .iter()
+            .map(|(name, value)| format!("SET {name} {}", sql_setting_literal(value)))
+            .collect();
+        statements.extend(self.config.session.on_connect_sql.clone());
+        Ok(statements)
+    }
+
+    async fn open(config: Config) -> Result<TdsClient, tiberius::error::Error> {
+        let tcp = TcpStream::connect(config.get_addr()).await?;
+        tcp.set_nodelay(true)?;
+        Client::connect(config, tcp.compat_write()).await
+    }
+
+    async fn connect(&self, read_only: bool) -> Result<TdsClient, DriverError> {
+        let mut config = self.tds_config(read_only)?;
+        let statements = self.session_statements()?;
+        let connect = async {
+            // Availability groups answer read-only intent with the address of
+            // a readable secondary.
+            let mut client = match Self::open(config.clone()).await {
+                Err(tiberius::error::Error::Routing { host, port }) => {
+                    config.host(host);
+                    config.port(port);
+                    Self::open(config).await?
+                }
+                result => result?,
+            };
+            for statement in &statements {
+                client.simple_query(statement).await?.into_results().await?;
+            }
+            Ok::<_, tiberius::error::Error>(client)
+        };
+        match tokio::time::timeout(self.config.limits.connect_timeout, connect).await {
+            Ok(Ok(client)) => Ok(client),
+            Ok(Err(error)) => {
+                let error = DriverError::from(error);
+                Err(DriverError {
+                    message: format!("Failed to connect to SQL Server: {}", error.message),
+                    ..error
+                })
+            }
+            Err(_) => Err(DriverError::new(
+                DriverErrorCategory::Timeout,
+                self.config.limits.connect_timeout_message(),
+            )),
+        }
+    }
+
+    async fn client(&self) -> Result<TdsClient, DriverError> {
+        let idle = self.idle.lock().ok().and_then(|mut idle| idle.pop());
+        match idle {
+            Some(client) => Ok(client),
+            None => self.connect(false).await,
+        }
+    }
+
+    /// Hand a connection back for reuse unless the work on it failed in a way
+    /// that leaves its state unknown. Only catalog and structured statements
+    /// come back here: user SQL can switch databases, change `SET` options,
+    /// create temp tables or stop reading mid-result, and tiberius cannot send
+    /// the `sp_reset_connection` flag, so those connections are closed instead.
+    fn release<T>(&self, client: TdsClient, outcome: &Result<T, DriverError>) {
+        if let Err(error) = outcome {
+            if matches!(
+                error.category,
+                DriverErrorCategory::Connection | DriverErrorCategory::Timeout
+            ) {
+                return;
+            }
+        }
+        if let Ok(mut idle) = self.idle.lock() {
+            if idle.len() < MAX_IDLE_CONNECTIONS {
+                idle.push(client);
+            }
+        }
+    }
+
+    async fn fetch_all(
+        client: &mut TdsClient,
+        sql: &str,
+        params: &[&dyn tiberius::ToSql],
+    ) -> Result<Vec<Row>, DriverError> {
+        Ok(client.query(sql, params).await?.into_first_result().await?)
+    }
+
+    /// Run a catalog query on a pooled connection.
+    async fn catalog(
+        &self,
+        sql: &str,
+        params: &[&dyn tiberius::ToSql],
+    ) -> Result<Vec<Row>, DriverError> {
+        let mut client = self.client().await?;
+        let rows = Self::fetch_all(&mut client, sql, params).await;
+        self.release(client, &rows);
+        rows
+    }
+
+    /// Collect the rows of a batch under the connection's timeout and result
+    /// caps. SQL Server has no per-session statement timeout, so the limit is
+    /// enforced here.
+    async fn fetch_bounded(
+        &self,
+        client: &mut TdsClient,
+        query: &str,
+    ) -> Result<(Vec<Vec<Value>>, bool, Vec<ResultColumn>), DriverError> {
+        let limits = &self.config.limits;
+        let rows = limits
+            .run(false, async {
+                client
+                    .simple_query(query)
+                    .await?
+                    .into_row_stream()
+                    .take(limits.fetch_limit())
+                    .try_collect::<Vec<_>>()
+                    .await
+            })
+            .await?
+            .map_err(|error| DriverError::from(error).locate(query))?;
+        let columns = rows.first().map(Self::result_columns).unwrap_or_default();
+        let (values, truncated) = limits.bound_rows(rows.iter().map(Self::row_values).collect());
+        Ok((values, truncated, columns))
+    }
+
+    fn bind_filter<'a>(query: &mut Query<'a>, filter: &CompiledFilter) {
+        for value in &filter.values {
+            match value {
+                FilterValue::Text(value) => query.bind(value.clone()),
+                FilterValue::Integer(value) => query.bind(*value),
+                FilterValue::Float(value) => query.bind(*value),
+                FilterValue::Boolean(value) => query.bind(*value),
+                FilterValue::ExactNumber { value, .. } => query.bind(value.clone()),
+            }
+        }
+    }
+
+    fn bind_values<'a>(query: &mut Query<'a>, values: &[Value]) -> Result<(), String> {
+        for value in values {
+            match value {
+                Value::Null => query.bind(Option::<&str>::None),
+                Value::Bool(value) => query.bind(*value),
+                Value::Number(value) => match value.as_i64() {
+                    Some(value) => query.bind(value),
+                    // Out of i64 range: let the server convert the digits.
+                    None if value.is_u64() => query.bind(value.to_string()),
+                    None => query.bind(value.as_f64().ok_or("Invalid numeric value")?),
+                },
+                Value::String(value) => query.bind(value.clone()),
+                Value::Array(_) | Value::Object(_) => query.bind(value.to_string()),
+            }
+        }
+        Ok(())
+    }
+
+    fn quote(identifier: &str) -> String {
+        format!("[{}]", identifier.replace(']', "]]"))
+    }
+
+    fn table_ref(schema: &str, table: &str) -> String {
+        format!("{}.{}", Self::quote(schema), Self::quote(table))
+    }
+
+    fn text(row: &Row, index: usize) -> String {
+        row.try_get::<&str, _>(index)
+            .ok()
+            .flatten()
+            .unwrap_or_default()
+            .to_string()
+    }
+
+    fn flag(row: &Row, index: usize) -> bool {
+        row.try_get::<bool, _>(index)
+            .ok()
+            .flatten()
+            .unwrap_or_default()
+    }
+
+    /// The row's cells in column order.
+    fn row_values(row: &Row) -> Vec<Value> {
+        row.cells()
+            .map(|(_, data)| Self::cell_to_json(data))
+            .collect()
+    }
+
+    fn cell_to_json(data: &ColumnData<'static>) -> Value {
+        fn temporal<'a, T: FromSql<'a>>(
+            data: &'a ColumnData<'static>,
+            tag: impl FnOnce(T) -> Value,
+        ) -> Value {
+            T::from_sql(data).ok().flatten().map_or(Value::Null, tag)
+        }
+
+        match data {
+            ColumnData::U8(value) => json!(value),
+            ColumnData::I16(value) => json!(value),
+            ColumnData::I32(value) => json!(value),
+            ColumnData::I64(value) => value.map_or(Value::Null, cell::integer),
+            ColumnData::F32(value) => value.map_or(Value::Null, cell::float32),
+            ColumnData::F64(value) => value.map_or(Value::Null, cell::float),
+            ColumnData::Bit(value) => json!(value),
+            ColumnData::String(value) => json!(value),
+            ColumnData::Guid(value) => value.map_or(Value::Null, |value| {
+                cell::uuid(value.to_string().to_uppercase())
+            }),
+            ColumnData::Binary(value) => value
+                .as_ref()
+                .map_or(Value::Null, |value| cell::binary(value)),
+            ColumnData::Numeric(value) => {
+                value.map_or(Value::Null, |value| cell::decimal(value.to_string()))
+            }
+            ColumnData::Xml(value) => value
+                .as_ref()
+                .map_or(Value::Null, |value| json!(value.to_string())),
+            ColumnData::DateTime(_) | ColumnData::SmallDateTime(_) | ColumnData::DateTime2(_) => {
+                temporal(data, |value: chrono::NaiveDateTime| {
+                    cell::timestamp(value.to_string())
+                })
+            }
+            ColumnData::Date(_) => temporal(data, |value: chrono::NaiveDate| {
+                cell::date(value.to_string())
+            }),
+            ColumnData::Time(_) => temporal(data, |value: chrono::NaiveTime| {
+                cell::time(value.to_string())
+            }),
+            ColumnData::DateTimeOffset(_) => {
+                temporal(data, |value: chrono::DateTime<chrono::FixedOffset>| {
+                    cell::timestamptz(value.format("%Y-%m-%d %H:%M:%S%.f%:z").to_string())
+                })
+            }
+        }
+    }
+
+    fn result_columns(row: &Row) -> Vec<ResultColumn> {
+        row.columns()
+            .iter()
+            .map(|column| {
+                cell::result_column(
+                    column.name(),
+                    column_type_name(column.column_type()),
+                    FilterDialect::SqlServer,
+                )
+            })
+            .collect()
+    }
+
+    async fn primary_key_columns(
+        &self,
+        schema: &str,
+        table: &str,
+    ) -> Result<Vec<String>, DriverError> {
+        Ok(self
+            .catalog(PRIMARY_KEY_QUERY, &[&schema, &table])
+            .await?
+            .iter()
+            .map(|row| Self::text(row, 0))
+            .collect())
+    }
+
+    async fn run_query(
+        &self,
+        client: &mut TdsClient,
+        query: &str,
+    ) -> Result<QueryResult, DriverError> {
+        let start = std::time::Instant::now();
+        let limits = &self.config.limits;
+        if returns_rows(query) {
+            let (values, truncated, columns) = self.fetch_bounded(client, query).await?;
+            return Ok(QueryResult::from_table(columns, values, truncated, start));
+        }
+        let rows_affected = if reports_row_counts(query) {
+            limits
+                .run(false, client.execute(query, &[]))
+                .await?
+                .map_err(|error| DriverError::from(error).locate(query))?
+                .total()
+        } else {
+            limits
+                .run(false, async {
+                    client.simple_query(query).await?.into_results().await
+                })
+                .await?
+                .map_err(|error| DriverError::from(error).locate(query))?;
+            0
+        };
+        Ok(QueryResult::from_affected(rows_affected, start))
+    }
+}
+
+/// The SQL Server name of a result column's wire type. Variable-width types
+/// are reported without their width.
+fn column_type_name(column_type: ColumnType) -> &'static str {
+    match column_type {
+        ColumnType::Null => "",
+        ColumnType::Bit | ColumnType::Bitn => "bit",
+        ColumnType::Int1 => "tinyint",
+        ColumnType::Int2 => "smallint",
+        ColumnType::Int4 | ColumnType::Intn => "int",
+        ColumnType::Int8 => "bigint",
+        ColumnType::Float4 => "real",
+        ColumnType::Float8 | ColumnType::Floatn => "float",
+        ColumnType::Money => "money",
+        ColumnType::Money4 => "smallmoney",
+        ColumnType::Datetime4 => "smalldatetime",
+        ColumnType::Datetime | ColumnType::Datetimen => "datetime",
+        ColumnType::Datetime2 => "datetime2",
+        ColumnType::DatetimeOffsetn => "datetimeoffset",
+        ColumnType::Daten => "date",
+        ColumnType::Timen => "time",
+        ColumnType::Guid => "uniqueidentifier",
+        ColumnType::Decimaln => "decimal",
+        ColumnType::Numericn => "numeric",
+        ColumnType::BigVarBin => "varbinary",
+        ColumnType::BigBinary => "binary",
+        ColumnType::Image => "image",
+        ColumnType::BigVarChar => "varchar",
+        ColumnType::BigChar => "char",
+        ColumnType::NVarchar => "nvarchar",
+        ColumnType::NChar => "nchar",
+        ColumnType::Text => "text",
+        ColumnType::NText => "ntext",
+        ColumnType::Xml => "xml",
+        ColumnType::Udt => "udt",
+        ColumnType::SSVariant => "sql_variant",
+    }
+}
+
+#[async_trait]
+impl DatabaseDriver for SqlServerDriver {
+    fn capabilities(&self) -> DriverCapabilities {
+        CAPABILITIES
+    }
+
+    async fn test_connection(&self) -> Result<TestConnectionResult, DriverError> {
+        let result = match self.client().await {
+            Ok(mut client) => {
+                let outcome = Self::fetch_all(&mut client, "SELECT 1", &[]).await;
+                self.release(client, &outcome);
+                outcome.map(|_| ())
+            }
+            Err(error) => Err(error),
+        };
+        Ok(match result {
+            Ok(()) => TestConnectionResult {
+                success: true,
+                message: "Connection successful!".to_string(),
+            },
+            Err(error) => TestConnectionResult {
+                success: false,
+                message: format!("Connection failed: {error}"),
+            },
+        })
+    }
+
+    async fn list_tables(&self) -> Result<Vec<TableInfo>, DriverError> {
+        Ok(self
+            .catalog(TABLES_QUERY, &[])
+            .await?
+            .iter()
+            .map(|row| TableInfo {
+                schema: Self::text(row, 0),
+                name: Self::text(row, 1),
+                table_type: if Self::text(row, 2) == "VIEW" {
+                    "view"
+                } else {
+                    "table"
+                }
+                .to_string(),
+            })
+            .collect())
+    }
+
+    async fn list_databases(&self) -> Result<Vec<String>, DriverError> {
+        Ok(self
+            .catalog(DATABASES_QUERY, &[])
+            .await?
+            .iter()
+            .map(|row| Self::text(row, 0))
+            .collect())
+    }
+
+    async fn get_table_data(
+        &self,
+        schema: &str,
+        table: &str,
+        page: i64,
+        limit: i64,
+        filter: Option<TableFilter>,
+        sort_column: Option<String>,
+        sort_direction: Option<String>,
+    ) -> Result<TableDataResponse, DriverError> {
+        let table_ref = Self::table_ref(schema, table);
+        let compiled = if let Some(expression) = structured_expression(filter.as_ref()) {
+            Some(compile_filter(
+                expression,
+                &self.get_table_structure(schema, table).await?.columns,
+                FilterDialect::SqlServer,
+            )?)
+        } else {
+            None
+        };
+        let where_clause = build_where_clause(filter.as_ref(), compiled.as_ref());
+        let order_columns = if let Some(column) = sort_column {
+            let direction = if sort_direction
+                .as_deref()
+                .is_some_and(|value| value.eq_ignore_ascii_case("desc"))
+            {
+                "DESC"
+            } else {
+                "ASC"
+            };
+            vec![format!("{} {direction}", Self::quote(&column))]
+        } else {
+            self.primary_key_columns(schema, table)
+                .await?
+                .into_iter()
+                .map(|column| format!("{} ASC", Self::quote(&column)))
+                .collect()
+        };
+        // OFFSET/FETCH needs an ORDER BY; without a key any order will do.
+        let order_clause = if order_columns.is_empty() {
+            " ORDER BY (SELECT NULL)".to_string()
+        } else {
+            format!(" ORDER BY {}", order_columns.join(", "))
+        };
+        let bound = compiled
+            .as_ref()
+            .map_or(0, |compiled| compiled.values.len());
+        let count_sql = format!("SELECT COUNT_BIG(*) FROM {table_ref}{where_clause}");
+        let data_sql = format!(
+            "SELECT * FROM {table_ref}{where_clause}{order_clause} OFFSET @P{} ROWS FETCH NEXT @P{} ROWS ONLY",
+            bound + 1,
+            bound + 2
+        );
+        let offset = (page - 1).max(0) * limit;
+
+        let mut client = self.client().await?;
+        let result = async {
+            let mut count = Query::new(count_sql.as_str());
+            if let Some(compiled) = compiled.as_ref() {
+                Self::bind_filter(&mut count, compiled);
+            }
+            let total = count
+                .query(&mut client)
+                .await?
+                .into_row()
+                .await?
+                .and_then(|row| row.get::<i64, _>(0))
+                .unwrap_or_default();
+            let mut data = Query::new(data_sql.as_str());
+            if let Some(compiled) = compiled.as_ref() {
+                Self::bind_filter(&mut data, compiled);
+            }
+            data.bind(offset);
+            data.bind(limit);
+            let rows = data.query(&mut client).await?.into_first_result().await?;
+            Ok::<_, tiberius::error::Error>((total, rows))
+        }
+        .await
+        .map_err(DriverError::from);
+        self.release(client, &result);
+        let (total, rows) = result?;
+        let columns = rows.first().map(Self::result_columns).unwrap_or_default();
+        Ok(TableDataResponse {
+            data: rows
+                .iter()
+                .map(|row| cell::row_object(&columns, &Self::row_values(row)))
+                .collect(),
+            columns,
+            total,
+            page,
+            limit,
+        })
+    }
+
+    async fn get_table_structure(
+        &self,
+        schema: &str,
+        table: &str,
+    ) -> Result<TableStructure, DriverError> {
+        let columns = self
+            .catalog(TABLE_COLUMNS_QUERY, &[&schema, &table])
+            .await?
+            .iter()
+            .map(|row| {
+                let data_type = Self::text(row, 1);
+                ColumnInfo {
+                    name: Self::text(row, 0),
+                    filter_kind: classify_column_type(
+                        data_type.split('(').next().unwrap_or(&data_type),
+                        FilterDialect::SqlServer,
+                    ),
+                    data_type,
+                    nullable: Self::text(row, 2) == "YES",
+                    default: row.try_get::<&str, _>(3).ok().flatten().map(str::to_string),
+                    primary_key: Self::flag(row, 4),
+                }
+            })
+            .collect();
+
+        let object_name = Self::table_ref(schema, table);
+        let mut index_map: BTreeMap<String, (Vec<String>, bool, bool)> = BTreeMap::new();
+        for row in self
+            .catalog(TABLE_INDEXES_QUERY, &[&object_name.as_str()])
+            .await?
+        {
+            let entry = index_map
+                .entry(Self::text(&row, 0))
+                .or_insert_with(|| (Vec::new(), Self::flag(&row, 2), Self::flag(&row, 3)));
+            entry.0.push(Self::text(&row, 1));
+        }
+        let indexes = index_map
+            .into_iter()
+            .map(|(name, (columns, unique, primary))| IndexInfo {
+                name,
+                columns,
+                unique,
+                primary,
+            })
+            .collect();
+
+        let foreign_keys = self
+            .catalog(TABLE_FOREIGN_KEYS_QUERY, &[&object_name.as_str()])
+            .await?
+            .iter()
+            .map(|row| ForeignKeyInfo {
+                name: Self::text(row, 0),
+                column: Self::text(row, 1),
+                references_table: Self::text(row, 2),
+                references_column: Self::text(row, 3),
+            })
+            .collect();
+        Ok(TableStructure {
+            columns,
+            indexes,
+            foreign_keys,
+        })
+    }
+
+    async fn execute_query(&self, query: &str) -> Result<QueryResult, DriverError> {
+        let start = std::time::Instant::now();
+        let mut client = self.client().await?;
+        let result = self.run_query(&mut client, query).await;
+        drop(client);
+        Ok(result.unwrap_or_else(|error| QueryResult::from_error(error, start)))
+    }
+
+    async fn execute_mutation(&self, mutation: &MutationPlan) -> Result<QueryResult, DriverError> {
+        let start = std::time::Instant::now();
+        let mut query = Query::new(mutation.sql.as_str());
+        Self::bind_values(&mut query, &mutation.values)?;
+        let mut client = self.client().await?;
+        let result = query
+            .execute(&mut client)
+            .await
+            .map(|result| result.total())
+            .map_err(|error| DriverError::from(error).locate(&mutation.sql));
+        self.release(client, &result);
+        Ok(match result {
+            Ok(rows_affected) => QueryResult::from_affected(rows_affected, start),
+            Err(error) => QueryResult::from_error(error, start),
+        })
+    }
+
+    /// Reads run on a fresh `ApplicationIntent=ReadOnly` connection, which
+    /// routes to a readable secondary when the server is in an availability
+    /// group, inside a transaction that is always rolled back. SQL Server has
+    /// no read-only transactions, so the statement is also checked before it
+    /// is sent.
+    async fn execute_query_read_only(&self, query: &str) -> Result<QueryResult, DriverError> {
+        let start = std::time::Instant::now();
+        if !query_is_safe(query) {
+            return Ok(QueryResult::from_error(
+                DriverError::new(
+                    DriverErrorCategory::Permission,
+                    "Read-only mode only allows a single read statement",
+                ),
+                start,
+            ));
+        }
+        let mut client = self.connect(true).await?;
+        let result = async {
+            client
+                .simple_query("BEGIN TRANSACTION")
+                .await?
+                .into_results()
+                .await?;
+            let rows = self.fetch_bounded(&mut client, query).await;
+            client
+                .simple_query("IF @@TRANCOUNT > 0 ROLLBACK")
+                .await?
+                .into_results()
+                .await?;
+            rows
+        }
+        .await;
+        drop(client);
+        Ok(match result {
+            Ok((values, truncated, columns)) => {
+                QueryResult::from_table(columns, values, truncated, start)
+            }
+            Err(error) => QueryResult::from_error(error, start),
+        })
+    }
+
+    async fn get_schema_overview(&self) -> Result<SchemaOverview, DriverError> {
+        let mut tables = Vec::new();
+        for table in self.list_tables().await? {
+            let structure = self.get_table_structure(&table.schema, &table.name).await?;
+            tables.push(TableWithStructure {
+                schema: table.schema,
+                name: table.name,
+                table_type: table.table_type,
+                columns: structure.columns,
+                foreign_keys: structure.foreign_keys,
+                indexes: structure.indexes,
+            });
+        }
+        Ok(SchemaOverview {
+            tables,
+            functions: vec![],
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::SqlServerDriver;
+    use serde_json::json;
+    use tiberius::numeric::Numeric;
+    use tiberius::ColumnData;
+
+    #[test]
+    fn converts_tds_values_to_json() {
+        assert_eq!(
+            SqlServerDriver::cell_to_json(&ColumnData::I64(Some(9_007_199_254_740_993))),
+            json!({ "$type": "integer", "value": "9007199254740993" })
+        );
+        assert_eq!(
+            SqlServerDriver::cell_to_json(&ColumnData::Numeric(Some(Numeric::new_with_scale(
+                12345, 2
+            )))),
+            json!({ "$type": "decimal", "value": "123.45" })
+        );
+        assert_eq!(
+            SqlServerDriver::cell_to_json(&ColumnData::Binary(Some(vec![0xde, 0xad].into()))),
+            json!({ "$type": "binary", "value": "3q0=", "length": 2 })
+        );
+        assert_eq!(
+            SqlServerDriver::cell_to_json(&ColumnData::Bit(None)),
+            serde_json::Value::Null
+        );
+        assert_eq!(
+            SqlServerDriver::cell_to_json(&ColumnData::I64(Some(42))),
+            json!(42)
+        );
+    }
+
+    #[test]
+    fn quotes_identifiers_with_brackets() {
+        assert_eq!(
+            SqlServerDriver::table_ref("dbo", "odd]name"),
+            "[dbo].[odd]]name]"
+        );
+    }
+}
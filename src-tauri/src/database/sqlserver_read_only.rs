@@ -0,0 +1,100 @@
+use super::{
+    contains_keyword_outside_literals, query_returns_rows_with_keywords, starts_with_keyword,
+    strip_leading_sql_comments,
+};
+
+/// T-SQL needs no semicolon between statements, so a read is only let through
+/// when no keyword that writes, runs code or controls the transaction appears
+/// anywhere outside literals.
+pub(crate) fn query_is_safe(sql: &str) -> bool {
+    let sql = strip_leading_sql_comments(sql).trim();
+    if !["SELECT", "WITH"]
+        .iter()
+        .any(|keyword| starts_with_keyword(sql, keyword))
+    {
+        return false;
+    }
+
+    ![
+        "INSERT",
+        "UPDATE",
+        "DELETE",
+        "MERGE",
+        "INTO",
+        "CREATE",
+        "ALTER",
+        "DROP",
+        "TRUNCATE",
+        "GRANT",
+        "REVOKE",
+        "DENY",
+        "EXEC",
+        "EXECUTE",
+        "BEGIN",
+        "COMMIT",
+        "ROLLBACK",
+        "SAVE",
+        "SET",
+        "USE",
+        "DBCC",
+        "BACKUP",
+        "RESTORE",
+        "BULK",
+        "OPENROWSET",
+        "OPENDATASOURCE",
+        "OPENQUERY",
+        "WAITFOR",
+        "KILL",
+        "SHUTDOWN",
+        "RECONFIGURE",
+    ]
+    .iter()
+    .any(|keyword| contains_keyword_outside_literals(sql, keyword))
+}
+
+/// Stored procedures and DML with an `OUTPUT` clause return rows too.
+pub(crate) fn returns_rows(sql: &str) -> bool {
+    query_returns_rows_with_keywords(sql, &["EXEC", "EXECUTE"])
+        || (reports_row_counts(sql)
+            && contains_keyword_outside_literals(strip_leading_sql_comments(sql), "OUTPUT"))
+}
+
+/// DML is sent through `sp_executesql`, which reports how many rows changed.
+/// Everything else runs as a plain batch so `SET`, `USE` and transaction
+/// statements keep their effect on the session.
+pub(crate) fn reports_row_counts(sql: &str) -> bool {
+    let sql = strip_leading_sql_comments(sql);
+    ["INSERT", "UPDATE", "DELETE", "MERGE"]
+        .iter()
+        .any(|keyword| starts_with_keyword(sql, keyword))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{query_is_safe, reports_row_counts, returns_rows};
+
+    #[test]
+    fn accepts_reads_and_rejects_writes_without_statement_separators() {
+        assert!(query_is_safe("SELECT TOP 10 * FROM dbo.users"));
+        assert!(query_is_safe(
+            "WITH ids AS (SELECT 1 AS id) SELECT * FROM ids"
+        ));
+        assert!(query_is_safe("SELECT 'DROP TABLE users' AS text"));
+        assert!(!query_is_safe("SELECT 1 DELETE FROM users"));
+        assert!(!query_is_safe("SELECT * INTO archive FROM users"));
+        assert!(!query_is_safe("SELECT 1; COMMIT"));
+        assert!(!query_is_safe("EXEC sp_who"));
+        assert!(!query_is_safe("SET IMPLICIT_TRANSACTIONS OFF"));
+    }
+
+    #[test]
+    fn recognises_procedures_and_output_clauses_as_returning_rows() {
+        assert!(returns_rows("EXEC sp_databases"));
+        assert!(returns_rows(
+            "INSERT INTO users (name) OUTPUT inserted.id VALUES ('Ada')"
+        ));
+        assert!(!returns_rows("INSERT INTO users (name) VALUES ('output')"));
+        assert!(reports_row_counts("-- tidy\nDELETE FROM users"));
+        assert!(!reports_row_counts("BEGIN TRANSACTION"));
+    }
+}
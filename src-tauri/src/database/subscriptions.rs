@@ -0,0 +1,334 @@
+//! Live Pub/Sub and stream subscriptions.
+//!
+//! Each subscription runs [`KeyValueBrowser::watch`] on a task of its own, so
+//! a connection can have several at once. Messages collect in a buffer that
+//! is flushed to the app as one event every [`FLUSH_INTERVAL`]; a busy channel
+//! therefore costs one event per tick, and when the buffer is full the oldest
+//! messages are dropped and counted instead of growing without bound.
+
+use serde::Serialize;
+use std::collections::{HashMap, VecDeque};
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+use tokio::task::AbortHandle;
+use uuid::Uuid;
+
+use super::key_value::{key_value_browser, LiveMessage, LiveSource};
+use super::DatabaseDriver;
+
+pub const LIVE_MESSAGES_EVENT: &str = "redis-live-messages";
+pub const LIVE_STOPPED_EVENT: &str = "redis-live-stopped";
+
+const FLUSH_INTERVAL: Duration = Duration::from_millis(100);
+pub const DEFAULT_BUFFER_SIZE: usize = 1_000;
+pub const MAX_BUFFER_SIZE: usize = 10_000;
+
+#[derive(Clone, Debug, Serialize)]
+pub struct SubscriptionInfo {
+    pub id: String,
+    pub connection_uuid: String,
+    pub source: LiveSource,
+    pub buffer_size: usize,
+    pub started_at: String,
+}
+
+/// The messages received since the last flush. `dropped` counts messages
+/// that were pushed out of a full buffer before they could be sent.
+#[derive(Clone, Debug, Serialize)]
+pub struct LiveBatch {
+    pub subscription_id: String,
+    pub connection_uuid: String,
+    pub messages: Vec<LiveMessage>,
+    pub dropped: u64,
+}
+
+/// Sent once when a subscription ends; `error` is set when it failed rather
+/// than being stopped.
+#[derive(Clone, Debug, Serialize)]
+pub struct LiveStopped {
+    pub subscription_id: String,
+    pub connection_uuid: String,
+    pub error: Option<String>,
+}
+
+#[derive(Clone, Debug)]
+pub enum LiveEvent {
+    Messages(LiveBatch),
+    Stopped(LiveStopped),
+}
+
+type Notify = Arc<dyn Fn(&LiveEvent) + Send + Sync>;
+
+struct Subscription {
+    info: SubscriptionInfo,
+    task: AbortHandle,
+}
+
+#[derive(Default)]
+struct Buffer {
+    messages: VecDeque<LiveMessage>,
+    dropped: u64,
+}
+
+impl Buffer {
+    fn push(&mut self, message: LiveMessage, capacity: usize) {
+        if self.messages.len() == capacity {
+            self.messages.pop_front();
+            self.dropped += 1;
+        }
+        self.messages.push_back(message);
+    }
+
+    fn take(&mut self) -> Option<(Vec<LiveMessage>, u64)> {
+        if self.messages.is_empty() && self.dropped == 0 {
+            return None;
+        }
+        let messages = self.messages.drain(..).collect();
+        Some((messages, std::mem::take(&mut self.dropped)))
+    }
+}
+
+/// The running subscriptions of every connection.
+pub struct SubscriptionManager {
+    notify: Notify,
+    subscriptions: Arc<Mutex<HashMap<String, Subscription>>>,
+}
+
+impl SubscriptionManager {
+    pub fn new(notify: impl Fn(&LiveEvent) + Send + Sync + 'static) -> Self {
+        Self {
+            notify: Arc::new(notify),
+            subscriptions: Arc::new(Mutex::new(HashMap::new())),
+        }
+    }
+
+    /// Start listening to `source` on the pooled driver of `connection_uuid`.
+    pub fn start(
+        &self,
+        connection_uuid: &str,
+        driver: Arc<Box<dyn DatabaseDriver>>,
+        source: LiveSource,
+        buffer_size: Option<usize>,
+    ) -> Result<SubscriptionInfo, String> {
+        key_value_browser(&driver)?;
+        source.validate()?;
+        let buffer_size = buffer_size.unwrap_or(DEFAULT_BUFFER_SIZE);
+        if !(1..=MAX_BUFFER_SIZE).contains(&buffer_size) {
+            return Err(format!(
+                "The message buffer must hold between 1 and {MAX_BUFFER_SIZE} messages"
+            ));
+        }
+
+        let info = SubscriptionInfo {
+            id: Uuid::new_v4().to_string(),
+            connection_uuid: connection_uuid.to_string(),
+            source,
+            buffer_size,
+            started_at: chrono::Utc::now().to_rfc3339(),
+        };
+        // Holding the lock until the subscription is registered keeps a task
+        // that fails immediately from unregistering itself first.
+        let mut subscriptions = self.subscriptions.lock().map_err(|e| e.to_string())?;
+        let task = tokio::spawn(run(
+            info.clone(),
+            driver,
+            self.notify.clone(),
+            self.subscriptions.clone(),
+        ));
+        subscriptions.insert(
+            info.id.clone(),
+            Subscription {
+                info: info.clone(),
+                task: task.abort_handle(),
+            },
+        );
+        Ok(info)
+    }
+
+    /// Stop a subscription. Returns `false` if it had already ended.
+    pub fn stop(&self, id: &str) -> bool {
+        let removed = self
+            .subscriptions
+            .lock()
+            .ok()
+            .and_then(|mut subscriptions| subscriptions.remove(id));
+        match removed {
+            Some(subscription) => {
+                self.end(subscription);
+                true
+            }
+            None => false,
+        }
+    }
+
+    /// Stop every subscription of a connection, e.g. when it disconnects.
+    pub fn stop_connection(&self, connection_uuid: &str) {
+        let removed: Vec<Subscription> = match self.subscriptions.lock() {
+            Ok(mut subscriptions) => {
+                let ids: Vec<String> = subscriptions
+                    .values()
+                    .filter(|subscription| subscription.info.connection_uuid == connection_uuid)
+                    .map(|subscription| subscription.info.id.clone())
+                    .collect();
+                ids.iter()
+                    .filter_map(|id| subscriptions.remove(id))
+                    .collect()
+            }
+            Err(_) => Vec::new(),
+        };
+        for subscription in removed {
+            self.end(subscription);
+        }
+    }
+
+    pub fn list(&self, connection_uuid: &str) -> Vec<SubscriptionInfo> {
+        let mut list: Vec<SubscriptionInfo> = self
+            .subscriptions
+            .lock()
+            .map(|subscriptions| {
+                subscriptions
+                    .values()
+                    .filter(|subscription| subscription.info.connection_uuid == connection_uuid)
+                    .map(|subscription| subscription.info.clone())
+                    .collect()
+            })
+            .unwrap_or_default();
+        list.sort_by(|a, b| a.started_at.cmp(&b.started_at));
+        list
+    }
+
+    fn end(&self, subscription: Subscription) {
+        subscription.task.abort();
+        (self.notify)(&LiveEvent::Stopped(LiveStopped {
+            subscription_id: subscription.info.id,
+            connection_uuid: subscription.info.connection_uuid,
+            error: None,
+        }));
+    }
+}
+
+async fn run(
+    info: SubscriptionInfo,
+    driver: Arc<Box<dyn DatabaseDriver>>,
+    notify: Notify,
+    subscriptions: Arc<Mutex<HashMap<String, Subscription>>>,
+) {
+    let buffer = Mutex::new(Buffer::default());
+    let flush = || {
+        let taken = buffer.lock().ok().and_then(|mut buffer| buffer.take());
+        if let Some((messages, dropped)) = taken {
+            notify(&LiveEvent::Messages(LiveBatch {
+                subscription_id: info.id.clone(),
+                connection_uuid: info.connection_uuid.clone(),
+                messages,
+                dropped,
+            }));
+        }
+    };
+    let sink = |message: LiveMessage| {
+        if let Ok(mut buffer) = buffer.lock() {
+            buffer.push(message, info.buffer_size);
+        }
+    };
+
+    let result = match key_value_browser(&driver) {
+        Ok(browser) => {
+            let watch = browser.watch(&info.source, &sink);
+            tokio::pin!(watch);
+            let mut ticker = tokio::time::interval(FLUSH_INTERVAL);
+            loop {
+                tokio::select! {
+                    result = &mut watch => break result.map_err(|e| e.to_string()),
+                    _ = ticker.tick() => flush(),
+                }
+            }
+        }
+        Err(error) => Err(error),
+    };
+    flush();
+
+    // A subscription that was stopped was removed (and reported) already.
+    let registered = subscriptions
+        .lock()
+        .ok()
+        .and_then(|mut subscriptions| subscriptions.remove(&info.id))
+        .is_some();
+    if registered {
+        notify(&LiveEvent::Stopped(LiveStopped {
+            subscription_id: info.id.clone(),
+            connection_uuid: info.connection_uuid.clone(),
+            error: result.err(),
+        }));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::database::redis::RedisDriver;
+    use crate::database::{QueryLimits, RedisConfig, RedisTopology};
+
+    fn message(source: &str) -> LiveMessage {
+        LiveMessage {
+            source: source.to_string(),
+            pattern: None,
+            id: None,
+            payload: serde_json::json!("hello"),
+            received_at: String::new(),
+        }
+    }
+
+    #[test]
+    fn a_full_buffer_drops_the_oldest_messages() {
+        let mut buffer = Buffer::default();
+        for index in 0..5 {
+            buffer.push(message(&index.to_string()), 3);
+        }
+        let (messages, dropped) = buffer.take().unwrap();
+        assert_eq!(dropped, 2);
+        let sources: Vec<&str> = messages.iter().map(|m| m.source.as_str()).collect();
+        assert_eq!(sources, vec!["2", "3", "4"]);
+        assert!(buffer.take().is_none());
+    }
+
+    #[tokio::test]
+    async fn a_failed_subscription_reports_why_and_unregisters() {
+        let (sender, mut events) = tokio::sync::mpsc::unbounded_channel();
+        let manager = SubscriptionManager::new(move |event: &LiveEvent| {
+            let _ = sender.send(event.clone());
+        });
+        let driver: Arc<Box<dyn DatabaseDriver>> =
+            Arc::new(Box::new(RedisDriver::new(RedisConfig {
+                host: "127.0.0.1".to_string(),
+                port: 1,
+                username: None,
+                password: None,
+                db: None,
+                tls: false,
+                topology: RedisTopology::Standalone,
+                limits: QueryLimits::default(),
+            })));
+        let source = LiveSource::Channels {
+            channels: vec!["orders".to_string()],
+            patterns: Vec::new(),
+        };
+
+        assert!(manager
+            .start("connection-1", driver.clone(), source.clone(), Some(0))
+            .is_err());
+        let info = manager.start("connection-1", driver, source, None).unwrap();
+        assert_eq!(info.buffer_size, DEFAULT_BUFFER_SIZE);
+
+        let event = tokio::time::timeout(Duration::from_secs(10), events.recv())
+            .await
+            .unwrap()
+            .unwrap();
+        let LiveEvent::Stopped(stopped) = event else {
+            panic!("expected the subscription to stop");
+        };
+        assert_eq!(stopped.subscription_id, info.id);
+        assert!(stopped.error.is_some());
+        assert!(manager.list("connection-1").is_empty());
+        assert!(!manager.stop(&info.id));
+    }
+}
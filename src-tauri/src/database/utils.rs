@@ -1,4 +1,5 @@
 use super::pool_manager::ConnectionConfig;
+use super::{CredentialCommand, QueryLimits};
 use sqlx::SqlitePool;
 
 /// Build a ConnectionConfig from a saved connection record in SQLite.
@@ -13,6 +14,14 @@ pub async fn get_connection_config(
             .fetch_one(sqlite_pool)
             .await
             .map_err(|e| format!("Failed to get connection: {}", e))?;
+    let limits = QueryLimits::from_settings(
+        conn.query_timeout_ms,
+        conn.connect_timeout_ms,
+        conn.max_result_rows,
+        conn.max_result_bytes,
+    )?;
+    let credential_command =
+        CredentialCommand::from_settings(&conn.credential_command, conn.credential_ttl_secs)?;
 
     Ok(ConnectionConfig {
         db_type: conn.db_type,
@@ -23,6 +32,7 @@ pub async fn get_connection_config(
         password: Some(conn.password),
         ssl: Some(conn.ssl == 1),
         file_path: conn.file_path,
+        options: conn.options.0,
         ssh_enabled: conn.ssh_enabled == 1,
         ssh_host: if conn.ssh_host.is_empty() {
             None
@@ -46,5 +56,9 @@ pub async fn get_connection_config(
             Some(conn.ssh_key_path)
         },
         ssh_use_key: conn.ssh_use_key == 1,
+        on_connect_sql: Some(conn.on_connect_sql).filter(|sql| !sql.trim().is_empty()),
+        session_settings: conn.session_settings.0,
+        limits,
+        credential_command,
     })
 }
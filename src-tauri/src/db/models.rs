@@ -1,6 +1,9 @@
+use crate::database::redis_scripts::ScriptParameter;
+use crate::database::DriverError;
 use serde::{Deserialize, Serialize};
+use sqlx::types::Json;
 use sqlx::FromRow;
-use std::collections::HashMap;
+use std::collections::{BTreeMap, HashMap};
 
 #[derive(Debug, Clone, Serialize, Deserialize, FromRow)]
 pub struct Connection {
@@ -25,6 +28,49 @@ pub struct Connection {
     pub ssh_password: String,
     pub ssh_key_path: String,
     pub ssh_use_key: i64,
+    #[sqlx(default)]
+    #[serde(default)]
+    pub group_id: Option<i64>,
+    #[sqlx(default)]
+    #[serde(default)]
+    pub favorite: i64,
+    #[sqlx(default)]
+    #[serde(default)]
+    pub sort_order: i64,
+    #[sqlx(default)]
+    #[serde(default)]
+    pub on_connect_sql: String,
+    #[sqlx(default)]
+    #[serde(default)]
+    pub session_settings: Json<BTreeMap<String, String>>,
+    #[sqlx(default)]
+    #[serde(default)]
+    pub options: Json<serde_json::Map<String, serde_json::Value>>,
+    #[sqlx(default)]
+    #[serde(default)]
+    pub query_timeout_ms: Option<i64>,
+    #[sqlx(default)]
+    #[serde(default)]
+    pub connect_timeout_ms: Option<i64>,
+    #[sqlx(default)]
+    #[serde(default)]
+    pub max_result_rows: Option<i64>,
+    #[sqlx(default)]
+    #[serde(default)]
+    pub max_result_bytes: Option<i64>,
+    #[sqlx(default)]
+    #[serde(default)]
+    pub credential_command: String,
+    #[sqlx(default)]
+    #[serde(default)]
+    pub credential_ttl_secs: Option<i64>,
+    #[sqlx(default)]
+    #[serde(default)]
+    pub mcp_permission: String,
+    /// Loaded from `connection_tags`; not a column on `connections`.
+    #[sqlx(skip)]
+    #[serde(default)]
+    pub tags: Vec<String>,
     pub created_at: String,
     pub updated_at: String,
 }
@@ -58,6 +104,59 @@ pub struct ConnectionFormData {
     pub ssh_key_path: String,
     #[serde(default)]
     pub ssh_use_key: bool,
+    #[serde(default)]
+    pub group_id: Option<i64>,
+    #[serde(default)]
+    pub favorite: bool,
+    #[serde(default)]
+    pub tags: Vec<String>,
+    /// SQL run on every new physical connection (e.g. `SET ROLE analyst`).
+    /// Left out, an update keeps the stored SQL.
+    #[serde(default)]
+    pub on_connect_sql: Option<String>,
+    /// Engine settings such as `search_path`, `sql_mode` or ClickHouse settings.
+    /// Left out, an update keeps the stored settings.
+    #[serde(default)]
+    pub session_settings: Option<BTreeMap<String, String>>,
+    /// Engine-specific settings, checked against the fields the engine's
+    /// driver declares (e.g. `account_id` for D1). Left out, an update keeps
+    /// the stored options.
+    #[serde(default)]
+    pub options: Option<serde_json::Map<String, serde_json::Value>>,
+    /// Query limits; `null` keeps the default (no query timeout, 15s connect
+    /// timeout, 10,000 rows, no byte cap) and leaving one out keeps the
+    /// stored limit on update.
+    #[serde(default, deserialize_with = "present")]
+    pub query_timeout_ms: Option<Option<i64>>,
+    #[serde(default, deserialize_with = "present")]
+    pub connect_timeout_ms: Option<Option<i64>>,
+    #[serde(default, deserialize_with = "present")]
+    pub max_result_rows: Option<Option<i64>>,
+    #[serde(default, deserialize_with = "present")]
+    pub max_result_bytes: Option<Option<i64>>,
+    /// Shell command whose output replaces the password at connect time,
+    /// e.g. `vault read -field=password secret/db`. Left out, an update keeps
+    /// the stored command.
+    #[serde(default)]
+    pub credential_command: Option<String>,
+    /// Seconds the command's output is reused; `null` keeps the 5 minute
+    /// default and leaving it out keeps the stored TTL on update.
+    #[serde(default, deserialize_with = "present")]
+    pub credential_ttl_secs: Option<Option<i64>>,
+    /// `none`, `read` or `write` (writes need approval in the app). New
+    /// connections default to `read`; left out, an update keeps the stored
+    /// permission.
+    #[serde(default)]
+    pub mcp_permission: Option<String>,
+}
+
+/// Tell a field sent as `null` (`Some(None)`) from one left out (`None`).
+fn present<'de, D, T>(deserializer: D) -> Result<Option<Option<T>>, D::Error>
+where
+    D: serde::Deserializer<'de>,
+    T: Deserialize<'de>,
+{
+    Option::<T>::deserialize(deserializer).map(Some)
 }
 
 fn default_db_type() -> String {
@@ -68,6 +167,16 @@ fn default_ssh_port() -> i64 {
     22
 }
 
+#[derive(Debug, Clone, Serialize, Deserialize, FromRow)]
+pub struct ConnectionGroup {
+    pub id: i64,
+    pub parent_id: Option<i64>,
+    pub name: String,
+    pub sort_order: i64,
+    pub created_at: String,
+    pub updated_at: String,
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize, FromRow)]
 pub struct SavedQuery {
     pub id: i64,
@@ -187,6 +296,34 @@ mod tests {
 
         assert_eq!(column.filter_kind, FilterColumnKind::Other);
     }
+
+    #[test]
+    fn query_results_keep_duplicate_column_names_in_rows() {
+        let column = |name: &str| ResultColumn {
+            name: name.to_string(),
+            data_type: "INT8".to_string(),
+            filter_kind: FilterColumnKind::Integer,
+            nullable: None,
+            source: None,
+        };
+        let mut result = QueryResult::from_table(
+            vec![column("id"), column("id")],
+            vec![vec![json!(1), json!(2)], vec![json!(3), json!(4)]],
+            false,
+            std::time::Instant::now(),
+        );
+
+        assert_eq!(result.rows[0], vec![json!(1), json!(2)]);
+        assert_eq!(result.data[0], json!({"id": 2}));
+        assert_eq!(result.row_count, 2);
+
+        assert!(result.truncate_rows(1));
+        assert_eq!(result.rows.len(), 1);
+        assert_eq!(result.data.len(), 1);
+        assert_eq!(result.row_count, 1);
+        assert!(result.truncated);
+        assert!(!result.truncate_rows(1));
+    }
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -215,6 +352,9 @@ pub struct TableStructure {
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct TableDataResponse {
     pub data: Vec<serde_json::Value>,
+    /// Column metadata, when the engine reports it.
+    #[serde(default)]
+    pub columns: Vec<ResultColumn>,
     pub total: i64,
     pub page: i64,
     pub limit: i64,
@@ -337,45 +477,184 @@ pub struct SavedViewUpdateData {
     pub state: SavedViewState,
 }
 
+/// A Lua script saved for a Redis connection.
+#[derive(Debug, Clone, Serialize, Deserialize, FromRow)]
+pub struct SavedRedisScript {
+    pub id: i64,
+    pub connection_uuid: String,
+    pub name: String,
+    pub description: String,
+    pub source: String,
+    /// The typed `KEYS` and `ARGV` a run binds, in order.
+    #[sqlx(rename = "parameters_json")]
+    pub parameters: Json<Vec<ScriptParameter>>,
+    /// Run with `EVALSHA_RO`, so the server refuses writes.
+    pub read_only: bool,
+    pub created_at: String,
+    pub updated_at: String,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SavedRedisScriptFormData {
+    pub name: String,
+    #[serde(default)]
+    pub description: String,
+    pub source: String,
+    #[serde(default)]
+    pub parameters: Vec<ScriptParameter>,
+    #[serde(default)]
+    pub read_only: bool,
+}
+
+/// A column of a query result, in result order.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ResultColumn {
+    pub name: String,
+    /// The type as the engine reports it, e.g. `INT8` or `Nullable(String)`.
+    #[serde(rename = "type")]
+    pub data_type: String,
+    pub filter_kind: FilterColumnKind,
+    /// Whether the source column allows NULL, when the engine reports it.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub nullable: Option<bool>,
+    /// The table column the values are read from, when the engine reports it.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub source: Option<ColumnSource>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ColumnSource {
+    pub schema: String,
+    pub table: String,
+    pub column: String,
+}
+
+/// The table a query result can be edited through: every column is read from
+/// it and its whole primary key is in the result.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ResultEditTarget {
+    pub schema: String,
+    pub table: String,
+    /// Primary key columns, by their table names.
+    pub primary_key: Vec<String>,
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct QueryResult {
+    /// Rows keyed by column name, for Rust callers. Not serialized: `columns`
+    /// and `rows` carry the rows on the wire. When two columns share a name
+    /// the later one wins; `rows` keeps both.
+    #[serde(skip)]
     pub data: Vec<serde_json::Value>,
+    /// Column metadata, in result order.
+    #[serde(default)]
+    pub columns: Vec<ResultColumn>,
+    /// The rows as arrays aligned to `columns`. Cells are plain JSON or
+    /// tagged values; see `database::cell`.
+    #[serde(default)]
+    pub rows: Vec<Vec<serde_json::Value>>,
+    /// Only the Postgres driver reports where result columns come from, so
+    /// results of other engines are never editable in place.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub edit_target: Option<ResultEditTarget>,
     pub row_count: i64,
     pub truncated: bool,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub rows_affected: Option<u64>,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub error: Option<String>,
+    /// Category, engine code and position of `error`.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub error_details: Option<DriverError>,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub time_taken_ms: Option<u128>,
 }
 
 impl QueryResult {
-    /// Successful row result with bounded-result metadata and elapsed time.
+    /// Successful row result from rows built as JSON objects, with
+    /// bounded-result metadata and elapsed time. `columns` and `rows` are
+    /// derived from them.
     pub fn from_rows(
         data: Vec<serde_json::Value>,
         truncated: bool,
         start: std::time::Instant,
     ) -> Self {
-        let row_count = data.len() as i64;
+        let columns = crate::database::cell::object_columns(&data);
+        let rows = crate::database::cell::object_rows(&data, &columns);
+        Self::with_rows(data, columns, rows, truncated, start)
+    }
+
+    /// Successful row result from rows aligned to `columns`; `data` is
+    /// derived from them.
+    pub fn from_table(
+        columns: Vec<ResultColumn>,
+        rows: Vec<Vec<serde_json::Value>>,
+        truncated: bool,
+        start: std::time::Instant,
+    ) -> Self {
+        let data = rows
+            .iter()
+            .map(|row| crate::database::cell::row_object(&columns, row))
+            .collect();
+        Self::with_rows(data, columns, rows, truncated, start)
+    }
+
+    fn with_rows(
+        data: Vec<serde_json::Value>,
+        columns: Vec<ResultColumn>,
+        rows: Vec<Vec<serde_json::Value>>,
+        truncated: bool,
+        start: std::time::Instant,
+    ) -> Self {
         Self {
+            row_count: rows.len() as i64,
             data,
-            row_count,
+            columns,
+            rows,
+            edit_target: None,
             truncated,
             rows_affected: None,
             error: None,
+            error_details: None,
             time_taken_ms: Some(start.elapsed().as_millis()),
         }
     }
 
+    /// Successful statement result that returned no rows.
+    pub fn from_affected(rows_affected: u64, start: std::time::Instant) -> Self {
+        Self {
+            row_count: rows_affected as i64,
+            rows_affected: Some(rows_affected),
+            ..Self::from_rows(Vec::new(), false, start)
+        }
+    }
+
+    /// Keep at most `max_rows` rows, in both `data` and `rows`. Returns
+    /// whether anything was dropped.
+    pub fn truncate_rows(&mut self, max_rows: usize) -> bool {
+        let dropped = self.data.len() > max_rows || self.rows.len() > max_rows;
+        self.data.truncate(max_rows);
+        self.rows.truncate(max_rows);
+        if dropped {
+            self.row_count = self.rows.len() as i64;
+            self.truncated = true;
+        }
+        dropped
+    }
+
     /// Error result (no rows), stamped with elapsed time.
-    pub fn from_error(message: String, start: std::time::Instant) -> Self {
+    pub fn from_error(error: impl Into<DriverError>, start: std::time::Instant) -> Self {
+        let error = error.into();
         Self {
             data: vec![],
+            columns: Vec::new(),
+            rows: Vec::new(),
+            edit_target: None,
             row_count: 0,
             truncated: false,
             rows_affected: None,
-            error: Some(message),
+            error: Some(error.message.clone()),
+            error_details: Some(error),
             time_taken_ms: Some(start.elapsed().as_millis()),
         }
     }
@@ -376,6 +376,23 @@ fn readiness_args(
             "--query".into(),
             "SELECT 1".into(),
         ],
+        DockerDatabaseEngine::SqlServer => vec![
+            "exec".into(),
+            container_id.into(),
+            "/opt/mssql-tools18/bin/sqlcmd".into(),
+            // The container's certificate is self-signed.
+            "-C".into(),
+            "-S".into(),
+            "localhost".into(),
+            "-U".into(),
+            username.into(),
+            "-P".into(),
+            password.into(),
+            "-d".into(),
+            database.into(),
+            "-Q".into(),
+            "SELECT 1".into(),
+        ],
     }
 }
 
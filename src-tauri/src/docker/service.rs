@@ -150,6 +150,11 @@ async fn credentials(
             )?;
             Ok((database, username, password))
         }
+        DockerDatabaseEngine::SqlServer => {
+            let password =
+                first_env_value(container_id, env, &["MSSQL_SA_PASSWORD", "SA_PASSWORD"]).await?;
+            Ok((String::new(), String::new(), password))
+        }
     }
 }
 
@@ -295,6 +300,19 @@ pub async fn docker_link_connection(
         ssh_password: String::new(),
         ssh_key_path: String::new(),
         ssh_use_key: false,
+        group_id: None,
+        favorite: false,
+        tags: Vec::new(),
+        on_connect_sql: None,
+        session_settings: None,
+        options: None,
+        query_timeout_ms: None,
+        connect_timeout_ms: None,
+        max_result_rows: None,
+        max_result_bytes: None,
+        credential_command: None,
+        credential_ttl_secs: None,
+        mcp_permission: None,
     };
     let link = DockerLink {
         connection_uuid: uuid.clone(),
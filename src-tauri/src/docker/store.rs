@@ -187,10 +187,14 @@ mod tests {
     }
 
     #[tokio::test]
-    async fn stores_mysql_family_engine_values() {
+    async fn stores_mysql_family_and_sql_server_engine_values() {
         let pool = test_pool().await;
 
-        for engine in [DockerDatabaseEngine::Mysql, DockerDatabaseEngine::Mariadb] {
+        for engine in [
+            DockerDatabaseEngine::Mysql,
+            DockerDatabaseEngine::Mariadb,
+            DockerDatabaseEngine::SqlServer,
+        ] {
             let plan = ManagedDatabasePlan::new(engine);
             let data = plan.connection_data("Local database", 3306);
             let link = plan.link("desktop-linux".to_string(), "container".to_string());
@@ -205,7 +209,7 @@ mod tests {
                 .fetch_all(&pool)
                 .await
                 .unwrap();
-        assert_eq!(engines, vec!["mariadb", "mysql"]);
+        assert_eq!(engines, vec!["mariadb", "mysql", "sqlserver"]);
     }
 
     #[tokio::test]
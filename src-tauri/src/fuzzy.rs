@@ -0,0 +1,93 @@
+//! Lightweight fuzzy matching used by the connection list and schema search.
+
+/// Score `needle` against `haystack`, case-insensitively. Returns `None` when
+/// the needle's characters do not appear in order. Higher scores are better:
+/// contiguous substrings outrank scattered subsequences, and matches that
+/// start at a word boundary outrank matches in the middle of a word.
+pub fn fuzzy_score(needle: &str, haystack: &str) -> Option<i64> {
+    let needle = needle.trim().to_lowercase();
+    if needle.is_empty() {
+        return Some(0);
+    }
+    let haystack = haystack.to_lowercase();
+    let chars: Vec<char> = haystack.chars().collect();
+
+    if let Some(byte_offset) = haystack.find(&needle) {
+        let position = haystack[..byte_offset].chars().count();
+        let mut score = 1000 - position as i64;
+        if position == 0 {
+            score += 500;
+        } else if is_boundary(chars[position - 1]) {
+            score += 250;
+        }
+        if needle.chars().count() == chars.len() {
+            score += 1000;
+        }
+        return Some(score);
+    }
+
+    let mut score = 0i64;
+    let mut next = 0usize;
+    let mut previous: Option<usize> = None;
+    for wanted in needle.chars() {
+        let found = chars[next..].iter().position(|c| *c == wanted)? + next;
+        if previous.is_some_and(|previous| previous + 1 == found) {
+            score += 15;
+        } else if found == 0 || is_boundary(chars[found - 1]) {
+            score += 10;
+        } else {
+            score += 1;
+        }
+        score -= (found - next) as i64;
+        previous = Some(found);
+        next = found + 1;
+    }
+    Some(score)
+}
+
+/// Score a whitespace-separated query against several fields. Every term must
+/// match at least one field; the result sums each term's best field score.
+pub fn score_terms(query: &str, fields: &[&str]) -> Option<i64> {
+    let mut total = 0i64;
+    let mut any_term = false;
+    for term in query.split_whitespace() {
+        any_term = true;
+        total += fields
+            .iter()
+            .filter_map(|field| fuzzy_score(term, field))
+            .max()?;
+    }
+    any_term.then_some(total)
+}
+
+fn is_boundary(c: char) -> bool {
+    !c.is_alphanumeric()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{fuzzy_score, score_terms};
+
+    #[test]
+    fn ranks_exact_prefix_substring_and_subsequence_matches() {
+        let exact = fuzzy_score("orders", "orders").unwrap();
+        let prefix = fuzzy_score("ord", "orders").unwrap();
+        let word_start = fuzzy_score("ord", "customer_orders").unwrap();
+        let subsequence = fuzzy_score("odr", "orders").unwrap();
+
+        assert!(exact > prefix);
+        assert!(prefix > word_start);
+        assert!(word_start > subsequence);
+        assert_eq!(fuzzy_score("xyz", "orders"), None);
+        assert_eq!(fuzzy_score("ORD", "orders"), Some(prefix));
+    }
+
+    #[test]
+    fn requires_every_term_to_match_some_field() {
+        let fields = ["Billing prod", "db.internal", "billing", "eu-west"];
+
+        assert!(score_terms("billing eu", &fields).is_some());
+        assert!(score_terms("billing staging", &fields).is_none());
+        assert!(score_terms("   ", &fields).is_none());
+    }
+}
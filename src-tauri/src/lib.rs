@@ -4,27 +4,47 @@ pub mod database;
 pub mod db;
 pub mod docker;
 pub mod duckdb_helper;
+pub mod fuzzy;
 pub mod mcp;
 mod ssh_tunnel;
 
 use commands::ai::{detect_ai_harnesses, generate_sql, get_ai_status};
+use commands::connection_groups::{
+    create_connection_group, delete_connection_group, get_connection_groups, move_connection_group,
+    rename_connection_group,
+};
 use commands::connections::{
     create_connection, delete_connection, export_connection, get_connection_by_uuid,
-    get_connections, import_connections, update_connection,
+    get_connection_tags, get_connections, import_connections, move_connection, search_connections,
+    set_connection_favorite, set_connection_tags, update_connection,
 };
 use commands::database::{
-    d1_list_databases, delete_table_row, insert_table_row, redis_delete_key, redis_get_key_details,
-    redis_search_keys, redis_set_hash_key, redis_set_key, redis_set_list_key, redis_set_set_key,
-    redis_set_zset_key, redis_update_ttl, unified_execute_query, unified_get_schema_overview,
-    unified_get_table_data, unified_get_table_structure, unified_list_tables,
-    unified_test_connection, update_table_row, update_table_row_with_raw_sql,
+    d1_list_databases, delete_table_row, get_database_capabilities, insert_table_row,
+    list_database_drivers, redis_analyze_keyspace, redis_call_function, redis_cancel_analysis,
+    redis_cancel_bulk_keys, redis_client_list, redis_delete_function, redis_delete_key,
+    redis_get_key_details, redis_get_modules, redis_get_running_analysis,
+    redis_get_running_bulk_keys, redis_get_script_cache, redis_json_delete, redis_json_get,
+    redis_json_set, redis_kill_client, redis_latency_history, redis_latency_latest,
+    redis_list_functions, redis_list_subscriptions, redis_load_function, redis_load_script,
+    redis_preview_bulk_keys, redis_run_bulk_keys, redis_run_script, redis_search_keys,
+    redis_server_info, redis_set_hash_key, redis_set_key, redis_set_list_key, redis_set_set_key,
+    redis_set_zset_key, redis_slowlog, redis_subscribe, redis_unsubscribe, redis_update_ttl,
+    unified_execute_query, unified_get_schema_overview, unified_get_table_data,
+    unified_get_table_structure, unified_list_tables, unified_test_connection, update_table_row,
+    update_table_row_with_raw_sql,
+};
+use commands::mcp::{
+    mcp_create_token, mcp_delete_token, mcp_export_audit_log, mcp_get_audit_retention,
+    mcp_get_status, mcp_list_pending_approvals, mcp_list_tokens, mcp_query_audit_log,
+    mcp_regenerate_token, mcp_resolve_approval, mcp_set_audit_retention, mcp_set_enabled,
+    mcp_update_token,
 };
-use commands::mcp::{mcp_get_status, mcp_regenerate_token, mcp_set_enabled};
 use commands::pool::{
     pool_connect, pool_create_table, pool_delete_table_row, pool_disconnect, pool_execute_query,
-    pool_get_function_definition, pool_get_schema_overview, pool_get_status, pool_get_table_data,
-    pool_get_table_structure, pool_health_check, pool_insert_table_row, pool_list_tables,
-    pool_preview_create_table, pool_update_table_row,
+    pool_get_capabilities, pool_get_function_definition, pool_get_schema_overview, pool_get_status,
+    pool_get_table_data, pool_get_table_structure, pool_health_check, pool_insert_table_row,
+    pool_list_databases, pool_list_tables, pool_open_database, pool_preview_create_table,
+    pool_update_table_row,
 };
 use commands::postgres::{
     execute_query, get_table_data, get_table_structure, list_tables, test_connection,
@@ -33,13 +53,23 @@ use commands::queries::{
     clear_query_history, create_saved_query, delete_saved_query, get_query_history,
     get_saved_queries, record_query_history, update_saved_query,
 };
+use commands::redis_scripts::{
+    create_redis_script, delete_redis_script, get_redis_scripts, update_redis_script,
+};
 use commands::saved_views::{
     create_saved_view, delete_saved_view, get_saved_views, update_saved_view,
 };
 use commands::settings::{get_all_settings, get_setting, set_setting, set_settings};
 #[cfg(desktop)]
 use commands::updates::check_for_update;
+use database::bulk_keys::{BulkKeyEvent, BulkKeyManager, BULK_FINISHED_EVENT, BULK_PROGRESS_EVENT};
+use database::keyspace_analysis::{
+    AnalysisEvent, AnalysisManager, ANALYSIS_FINISHED_EVENT, ANALYSIS_PROGRESS_EVENT,
+};
 use database::pool_manager::PoolManager;
+use database::subscriptions::{
+    LiveEvent, SubscriptionManager, LIVE_MESSAGES_EVENT, LIVE_STOPPED_EVENT,
+};
 use docker::{
     docker_connection_states, docker_control_connection, docker_create_database,
     docker_get_connection_string, docker_link_connection, docker_list_containers,
@@ -222,8 +252,73 @@ pub fn run() {
             pool_manager.spawn_idle_reaper();
             app.manage(pool_manager.clone());
 
+            // MCP write tools block until the user answers an approval prompt.
+            let app_handle = app.handle().clone();
+            let approvals = Arc::new(mcp::approval::ApprovalBroker::new(move |event| {
+                let emitted = match event {
+                    mcp::approval::ApprovalEvent::Requested(request) => {
+                        app_handle.emit(mcp::approval::APPROVAL_REQUESTED_EVENT, request.clone())
+                    }
+                    mcp::approval::ApprovalEvent::Resolved(resolution) => {
+                        app_handle.emit(mcp::approval::APPROVAL_RESOLVED_EVENT, resolution.clone())
+                    }
+                };
+                if let Err(error) = emitted {
+                    eprintln!("Failed to emit MCP approval event: {error}");
+                }
+            }));
+            app.manage(approvals.clone());
+
+            // Live Pub/Sub and stream messages are pushed to the app in batches.
+            let app_handle = app.handle().clone();
+            app.manage(SubscriptionManager::new(move |event| {
+                let emitted = match event {
+                    LiveEvent::Messages(batch) => app_handle.emit(LIVE_MESSAGES_EVENT, batch.clone()),
+                    LiveEvent::Stopped(stopped) => {
+                        app_handle.emit(LIVE_STOPPED_EVENT, stopped.clone())
+                    }
+                };
+                if let Err(error) = emitted {
+                    eprintln!("Failed to emit Redis live event: {error}");
+                }
+            }));
+
+            let app_handle = app.handle().clone();
+            app.manage(AnalysisManager::new(move |event| {
+                let emitted = match event {
+                    AnalysisEvent::Progress(progress) => {
+                        app_handle.emit(ANALYSIS_PROGRESS_EVENT, progress.clone())
+                    }
+                    AnalysisEvent::Finished(finished) => {
+                        app_handle.emit(ANALYSIS_FINISHED_EVENT, finished.clone())
+                    }
+                };
+                if let Err(error) = emitted {
+                    eprintln!("Failed to emit Redis analysis event: {error}");
+                }
+            }));
+
+            let app_handle = app.handle().clone();
+            app.manage(BulkKeyManager::new(move |event| {
+                let emitted = match event {
+                    BulkKeyEvent::Progress(progress) => {
+                        app_handle.emit(BULK_PROGRESS_EVENT, progress.clone())
+                    }
+                    BulkKeyEvent::Finished(finished) => {
+                        app_handle.emit(BULK_FINISHED_EVENT, finished.clone())
+                    }
+                };
+                if let Err(error) = emitted {
+                    eprintln!("Failed to emit Redis bulk key event: {error}");
+                }
+            }));
+
             // The embedded MCP server is opt-in and token-authenticated.
-            let mcp_control = Arc::new(mcp::control::McpControl::new(pool, pool_manager));
+            let mcp_control = Arc::new(mcp::control::McpControl::new(
+                pool,
+                pool_manager,
+                approvals,
+            ));
             app.manage(mcp_control.clone());
             tauri::async_runtime::spawn(async move {
                 if mcp::control::is_enabled(mcp_control.sqlite_pool()).await {
@@ -247,6 +342,16 @@ pub fn run() {
             delete_connection,
             export_connection,
             import_connections,
+            search_connections,
+            get_connection_tags,
+            set_connection_tags,
+            set_connection_favorite,
+            move_connection,
+            get_connection_groups,
+            create_connection_group,
+            rename_connection_group,
+            move_connection_group,
+            delete_connection_group,
             test_connection,
             list_tables,
             get_table_data,
@@ -254,6 +359,8 @@ pub fn run() {
             execute_query,
             unified_test_connection,
             d1_list_databases,
+            get_database_capabilities,
+            list_database_drivers,
             unified_list_tables,
             unified_get_table_data,
             unified_get_table_structure,
@@ -268,6 +375,33 @@ pub fn run() {
             redis_set_hash_key,
             redis_set_zset_key,
             redis_update_ttl,
+            redis_get_modules,
+            redis_json_get,
+            redis_json_set,
+            redis_json_delete,
+            redis_subscribe,
+            redis_unsubscribe,
+            redis_list_subscriptions,
+            redis_analyze_keyspace,
+            redis_cancel_analysis,
+            redis_get_running_analysis,
+            redis_preview_bulk_keys,
+            redis_run_bulk_keys,
+            redis_cancel_bulk_keys,
+            redis_get_running_bulk_keys,
+            redis_slowlog,
+            redis_latency_latest,
+            redis_latency_history,
+            redis_client_list,
+            redis_kill_client,
+            redis_server_info,
+            redis_run_script,
+            redis_load_script,
+            redis_get_script_cache,
+            redis_list_functions,
+            redis_load_function,
+            redis_delete_function,
+            redis_call_function,
             update_table_row,
             update_table_row_with_raw_sql,
             delete_table_row,
@@ -283,6 +417,10 @@ pub fn run() {
             create_saved_view,
             update_saved_view,
             delete_saved_view,
+            get_redis_scripts,
+            create_redis_script,
+            update_redis_script,
+            delete_redis_script,
             get_setting,
             set_setting,
             set_settings,
@@ -295,8 +433,11 @@ pub fn run() {
             pool_connect,
             pool_disconnect,
             pool_get_status,
+            pool_get_capabilities,
             pool_health_check,
             pool_list_tables,
+            pool_list_databases,
+            pool_open_database,
             pool_get_table_data,
             pool_get_table_structure,
             pool_preview_create_table,
@@ -310,6 +451,16 @@ pub fn run() {
             mcp_get_status,
             mcp_set_enabled,
             mcp_regenerate_token,
+            mcp_list_pending_approvals,
+            mcp_resolve_approval,
+            mcp_list_tokens,
+            mcp_create_token,
+            mcp_update_token,
+            mcp_delete_token,
+            mcp_query_audit_log,
+            mcp_export_audit_log,
+            mcp_get_audit_retention,
+            mcp_set_audit_retention,
             docker_list_containers,
             docker_prepare_connection,
             docker_create_database,
@@ -0,0 +1,288 @@
+//! Desktop approval for MCP write tools.
+//!
+//! A write tool registers a pending request, the desktop app is told about it
+//! through the `mcp-approval-requested` event, and the tool call blocks until
+//! the user approves or rejects it in the app or the request times out.
+
+use serde::Serialize;
+use serde_json::Value;
+use std::collections::HashMap;
+use std::time::Duration;
+use tokio::sync::oneshot;
+use uuid::Uuid;
+
+use crate::database::mutation::MutationPlan;
+
+pub const APPROVAL_REQUESTED_EVENT: &str = "mcp-approval-requested";
+pub const APPROVAL_RESOLVED_EVENT: &str = "mcp-approval-resolved";
+
+/// How long a write tool waits for the user before giving up.
+const APPROVAL_TIMEOUT: Duration = Duration::from_secs(5 * 60);
+
+/// A statement an MCP client wants to run, as shown to the user.
+#[derive(Clone, Debug, Serialize)]
+pub struct ApprovalRequest {
+    pub id: String,
+    pub connection_uuid: String,
+    pub connection_name: String,
+    pub tool: String,
+    pub sql: String,
+    /// Values bound to the statement's placeholders, in order.
+    pub values: Vec<Value>,
+    pub requested_at: String,
+}
+
+impl ApprovalRequest {
+    pub fn new(
+        connection_uuid: &str,
+        connection_name: &str,
+        tool: &str,
+        mutation: &MutationPlan,
+    ) -> Self {
+        Self {
+            id: Uuid::new_v4().to_string(),
+            connection_uuid: connection_uuid.to_string(),
+            connection_name: connection_name.to_string(),
+            tool: tool.to_string(),
+            sql: mutation.sql.clone(),
+            values: mutation.values.clone(),
+            requested_at: chrono::Utc::now().to_rfc3339(),
+        }
+    }
+}
+
+#[derive(Clone, Debug, Serialize)]
+pub struct ApprovalResolution {
+    pub id: String,
+    pub approved: bool,
+}
+
+#[derive(Clone, Debug)]
+pub enum ApprovalEvent {
+    Requested(ApprovalRequest),
+    /// Sent when the user answers and when a request expires or its MCP
+    /// call goes away, so the app can dismiss the prompt.
+    Resolved(ApprovalResolution),
+}
+
+type Notify = Box<dyn Fn(&ApprovalEvent) + Send + Sync>;
+
+struct Pending {
+    request: ApprovalRequest,
+    respond: oneshot::Sender<bool>,
+}
+
+/// Pending write approvals shared by the MCP server and the desktop app.
+pub struct ApprovalBroker {
+    /// `None` when nobody can approve (no desktop app): writes are refused.
+    notify: Option<Notify>,
+    pending: std::sync::Mutex<HashMap<String, Pending>>,
+    timeout: Duration,
+}
+
+impl ApprovalBroker {
+    pub fn new(notify: impl Fn(&ApprovalEvent) + Send + Sync + 'static) -> Self {
+        Self {
+            notify: Some(Box::new(notify)),
+            pending: std::sync::Mutex::new(HashMap::new()),
+            timeout: APPROVAL_TIMEOUT,
+        }
+    }
+
+    /// A broker with nobody to ask; every write request is refused.
+    pub fn headless() -> Self {
+        Self {
+            notify: None,
+            pending: std::sync::Mutex::new(HashMap::new()),
+            timeout: APPROVAL_TIMEOUT,
+        }
+    }
+
+    #[cfg(test)]
+    fn with_timeout(mut self, timeout: Duration) -> Self {
+        self.timeout = timeout;
+        self
+    }
+
+    /// Ask the user to approve `request`, waiting for their answer.
+    pub async fn request_approval(&self, request: ApprovalRequest) -> Result<(), String> {
+        let Some(notify) = &self.notify else {
+            return Err(
+                "Writes from MCP clients must be approved in the DBcooper desktop app".to_string(),
+            );
+        };
+
+        let (respond, answer) = oneshot::channel();
+        let id = request.id.clone();
+        if let Ok(mut pending) = self.pending.lock() {
+            pending.insert(
+                id.clone(),
+                Pending {
+                    request: request.clone(),
+                    respond,
+                },
+            );
+        }
+        // Withdraws the prompt if this call times out or is dropped.
+        let _withdraw = Withdraw { broker: self, id };
+        notify(&ApprovalEvent::Requested(request));
+
+        match tokio::time::timeout(self.timeout, answer).await {
+            Ok(Ok(true)) => Ok(()),
+            Ok(Ok(false)) => Err("The user rejected this statement; it was not run".to_string()),
+            Ok(Err(_)) => Err("The approval request was withdrawn".to_string()),
+            Err(_) => Err(format!(
+                "No approval within {}s; the statement was not run",
+                self.timeout.as_secs()
+            )),
+        }
+    }
+
+    /// Answer a pending request from the desktop app.
+    pub fn resolve(&self, id: &str, approved: bool) -> Result<(), String> {
+        let pending = self
+            .pending
+            .lock()
+            .ok()
+            .and_then(|mut pending| pending.remove(id))
+            .ok_or_else(|| "This approval request is no longer pending".to_string())?;
+        let _ = pending.respond.send(approved);
+        self.notify_resolved(id, approved);
+        Ok(())
+    }
+
+    /// Requests still waiting for an answer, oldest first.
+    pub fn pending(&self) -> Vec<ApprovalRequest> {
+        let mut requests: Vec<ApprovalRequest> = self
+            .pending
+            .lock()
+            .map(|pending| pending.values().map(|p| p.request.clone()).collect())
+            .unwrap_or_default();
+        requests.sort_by(|a, b| a.requested_at.cmp(&b.requested_at));
+        requests
+    }
+
+    fn notify_resolved(&self, id: &str, approved: bool) {
+        if let Some(notify) = &self.notify {
+            notify(&ApprovalEvent::Resolved(ApprovalResolution {
+                id: id.to_string(),
+                approved,
+            }));
+        }
+    }
+}
+
+struct Withdraw<'a> {
+    broker: &'a ApprovalBroker,
+    id: String,
+}
+
+impl Drop for Withdraw<'_> {
+    fn drop(&mut self) {
+        let withdrawn = self
+            .broker
+            .pending
+            .lock()
+            .ok()
+            .and_then(|mut pending| pending.remove(&self.id))
+            .is_some();
+        if withdrawn {
+            self.broker.notify_resolved(&self.id, false);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{ApprovalBroker, ApprovalEvent, ApprovalRequest};
+    use std::sync::{Arc, Mutex};
+    use std::time::Duration;
+
+    fn request(id: &str) -> ApprovalRequest {
+        ApprovalRequest {
+            id: id.to_string(),
+            connection_uuid: "dev".to_string(),
+            connection_name: "Dev".to_string(),
+            tool: "delete_row".to_string(),
+            sql: "DELETE FROM \"users\" WHERE \"id\" = ?".to_string(),
+            values: vec![serde_json::json!(7)],
+            requested_at: String::new(),
+        }
+    }
+
+    fn recording_broker() -> (Arc<ApprovalBroker>, Arc<Mutex<Vec<String>>>) {
+        let events = Arc::new(Mutex::new(Vec::new()));
+        let recorded = events.clone();
+        let broker = ApprovalBroker::new(move |event| {
+            let entry = match event {
+                ApprovalEvent::Requested(request) => format!("requested {}", request.id),
+                ApprovalEvent::Resolved(resolution) => {
+                    format!("resolved {} {}", resolution.id, resolution.approved)
+                }
+            };
+            recorded.lock().unwrap().push(entry);
+        });
+        (Arc::new(broker), events)
+    }
+
+    #[tokio::test]
+    async fn blocks_until_the_user_answers() {
+        let (broker, events) = recording_broker();
+        let call = {
+            let broker = broker.clone();
+            tokio::spawn(async move { broker.request_approval(request("a")).await })
+        };
+        while broker.pending().is_empty() {
+            tokio::task::yield_now().await;
+        }
+        assert!(!call.is_finished());
+
+        broker.resolve("a", true).unwrap();
+        assert_eq!(call.await.unwrap(), Ok(()));
+        assert!(broker.resolve("a", true).is_err());
+
+        let call = {
+            let broker = broker.clone();
+            tokio::spawn(async move { broker.request_approval(request("b")).await })
+        };
+        while broker.pending().is_empty() {
+            tokio::task::yield_now().await;
+        }
+        broker.resolve("b", false).unwrap();
+        assert!(call.await.unwrap().unwrap_err().contains("rejected"));
+
+        assert_eq!(
+            *events.lock().unwrap(),
+            [
+                "requested a",
+                "resolved a true",
+                "requested b",
+                "resolved b false"
+            ]
+        );
+    }
+
+    #[tokio::test]
+    async fn withdraws_requests_that_time_out() {
+        let (broker, events) = recording_broker();
+        let broker = Arc::try_unwrap(broker)
+            .ok()
+            .unwrap()
+            .with_timeout(Duration::from_millis(20));
+
+        let error = broker.request_approval(request("slow")).await.unwrap_err();
+        assert!(error.starts_with("No approval within"));
+        assert!(broker.pending().is_empty());
+        assert_eq!(
+            *events.lock().unwrap(),
+            ["requested slow", "resolved slow false"]
+        );
+    }
+
+    #[tokio::test]
+    async fn refuses_writes_without_a_desktop_app() {
+        let broker = ApprovalBroker::headless();
+        assert!(broker.request_approval(request("x")).await.is_err());
+        assert!(broker.pending().is_empty());
+    }
+}
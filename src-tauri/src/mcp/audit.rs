@@ -0,0 +1,392 @@
+//! Audit log of MCP tool calls and resource reads.
+//!
+//! Every `tools/call` and `resources/read` appends one row to `mcp_audit_log`
+//! with the token used, the connection, the SQL that ran, how many rows came
+//! back, how long it took and any error. Rows are never updated; entries older
+//! than the retention setting are removed as new ones are written.
+
+use std::time::Instant;
+
+use serde::{Deserialize, Serialize};
+use sqlx::{FromRow, SqlitePool};
+
+use super::tokens::sqlite_timestamp;
+use super::McpServer;
+
+/// Setting key: days to keep audit entries (`0` keeps them forever).
+pub const SETTING_RETENTION_DAYS: &str = "mcp_audit_retention_days";
+pub const DEFAULT_RETENTION_DAYS: i64 = 90;
+
+/// Rows returned by a query when the filter sets no limit.
+const DEFAULT_QUERY_LIMIT: i64 = 200;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum AuditKind {
+    Tool,
+    Resource,
+    Prompt,
+}
+
+impl AuditKind {
+    fn as_str(self) -> &'static str {
+        match self {
+            Self::Tool => "tool",
+            Self::Resource => "resource",
+            Self::Prompt => "prompt",
+        }
+    }
+}
+
+#[derive(Default)]
+struct CallDetails {
+    query: Option<String>,
+    row_count: Option<i64>,
+}
+
+/// What a single tool call or resource read did, filled in by the handler
+/// while it runs and written out when it finishes.
+#[derive(Default)]
+pub struct CallAudit {
+    details: std::sync::Mutex<CallDetails>,
+}
+
+impl CallAudit {
+    /// The SQL the call ran; the last statement recorded wins.
+    pub fn record_query(&self, sql: &str) {
+        if let Ok(mut details) = self.details.lock() {
+            details.query = Some(sql.to_string());
+        }
+    }
+
+    /// Rows returned, or affected by a write.
+    pub fn record_rows(&self, count: i64) {
+        if let Ok(mut details) = self.details.lock() {
+            details.row_count = Some(count);
+        }
+    }
+
+    fn take(&self) -> CallDetails {
+        self.details
+            .lock()
+            .map(|mut details| std::mem::take(&mut *details))
+            .unwrap_or_default()
+    }
+}
+
+#[derive(Debug, Clone, Serialize, FromRow)]
+pub struct AuditEntry {
+    pub id: i64,
+    pub token_id: Option<i64>,
+    pub token_name: String,
+    pub kind: String,
+    /// Tool name or resource URI.
+    pub name: String,
+    pub connection_uuid: Option<String>,
+    pub query: Option<String>,
+    pub row_count: Option<i64>,
+    pub duration_ms: i64,
+    pub error: Option<String>,
+    pub created_at: String,
+}
+
+/// Which entries to return; unset fields match everything.
+#[derive(Debug, Clone, Default, Deserialize)]
+#[serde(default)]
+pub struct AuditFilter {
+    pub token_id: Option<i64>,
+    pub connection_uuid: Option<String>,
+    /// Exact tool name or resource URI.
+    pub name: Option<String>,
+    /// Case-insensitive substring of the recorded SQL.
+    pub query_contains: Option<String>,
+    pub errors_only: bool,
+    /// RFC 3339 bounds on when the call was made.
+    pub since: Option<String>,
+    pub until: Option<String>,
+    pub limit: Option<i64>,
+    pub offset: Option<i64>,
+}
+
+/// Append the audit entry for a call that started at `started`, then drop
+/// entries past the retention period. Failures are logged, never returned:
+/// auditing must not change what the client sees.
+pub(crate) async fn record(
+    server: &McpServer,
+    kind: AuditKind,
+    name: &str,
+    connection_uuid: Option<&str>,
+    started: Instant,
+    error: Option<String>,
+) {
+    let details = server.audit.take();
+    let inserted = sqlx::query(
+        r#"
+        INSERT INTO mcp_audit_log
+            (token_id, token_name, kind, name, connection_uuid, query, row_count, duration_ms, error)
+        VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?)
+        "#,
+    )
+    .bind(server.scope.token_id)
+    .bind(&server.scope.token_name)
+    .bind(kind.as_str())
+    .bind(name)
+    .bind(connection_uuid)
+    .bind(&details.query)
+    .bind(details.row_count)
+    .bind(started.elapsed().as_millis() as i64)
+    .bind(&error)
+    .execute(&server.sqlite_pool)
+    .await;
+    if let Err(e) = inserted {
+        eprintln!("Failed to write MCP audit entry: {e}");
+        return;
+    }
+    if let Err(e) = prune(&server.sqlite_pool).await {
+        eprintln!("Failed to prune MCP audit log: {e}");
+    }
+}
+
+pub async fn retention_days(pool: &SqlitePool) -> Result<i64, String> {
+    Ok(crate::db::settings::get(pool, SETTING_RETENTION_DAYS)
+        .await?
+        .and_then(|value| value.parse().ok())
+        .unwrap_or(DEFAULT_RETENTION_DAYS))
+}
+
+/// Change the retention period and apply it straight away.
+pub async fn set_retention_days(pool: &SqlitePool, days: i64) -> Result<(), String> {
+    if days < 0 {
+        return Err("Audit retention cannot be negative".to_string());
+    }
+    crate::db::settings::set(pool, SETTING_RETENTION_DAYS, &days.to_string()).await?;
+    prune(pool).await.map(|_| ())
+}
+
+/// Delete entries older than the retention period. Returns how many went.
+pub async fn prune(pool: &SqlitePool) -> Result<u64, String> {
+    let days = retention_days(pool).await?;
+    if days == 0 {
+        return Ok(0);
+    }
+    sqlx::query("DELETE FROM mcp_audit_log WHERE created_at < datetime('now', ?)")
+        .bind(format!("-{days} days"))
+        .execute(pool)
+        .await
+        .map(|result| result.rows_affected())
+        .map_err(|e| e.to_string())
+}
+
+/// Matching entries, newest first.
+pub async fn query(pool: &SqlitePool, filter: &AuditFilter) -> Result<Vec<AuditEntry>, String> {
+    let limit = filter.limit.unwrap_or(DEFAULT_QUERY_LIMIT);
+    fetch(pool, filter, limit).await
+}
+
+/// All matching entries (the filter's limit still applies when set) as
+/// `"json"` or `"csv"`, oldest first so the export reads as a timeline.
+pub async fn export(
+    pool: &SqlitePool,
+    filter: &AuditFilter,
+    format: &str,
+) -> Result<String, String> {
+    let mut entries = fetch(pool, filter, filter.limit.unwrap_or(-1)).await?;
+    entries.reverse();
+    match format {
+        "json" => serde_json::to_string_pretty(&entries).map_err(|e| e.to_string()),
+        "csv" => Ok(to_csv(&entries)),
+        _ => Err(format!(
+            "Unknown audit export format: {format}. Expected json or csv"
+        )),
+    }
+}
+
+async fn fetch(
+    pool: &SqlitePool,
+    filter: &AuditFilter,
+    limit: i64,
+) -> Result<Vec<AuditEntry>, String> {
+    let since = filter.since.as_deref().map(sqlite_timestamp).transpose()?;
+    let until = filter.until.as_deref().map(sqlite_timestamp).transpose()?;
+    sqlx::query_as(
+        r#"
+        SELECT * FROM mcp_audit_log
+        WHERE (?1 IS NULL OR token_id = ?1)
+          AND (?2 IS NULL OR connection_uuid = ?2)
+          AND (?3 IS NULL OR name = ?3)
+          AND (?4 IS NULL OR instr(lower(query), lower(?4)) > 0)
+          AND (?5 = 0 OR error IS NOT NULL)
+          AND (?6 IS NULL OR created_at >= ?6)
+          AND (?7 IS NULL OR created_at <= ?7)
+        ORDER BY created_at DESC, id DESC
+        LIMIT ?8 OFFSET ?9
+        "#,
+    )
+    .bind(filter.token_id)
+    .bind(&filter.connection_uuid)
+    .bind(&filter.name)
+    .bind(&filter.query_contains)
+    .bind(filter.errors_only)
+    .bind(since)
+    .bind(until)
+    .bind(limit)
+    .bind(filter.offset.unwrap_or(0))
+    .fetch_all(pool)
+    .await
+    .map_err(|e| e.to_string())
+}
+
+fn to_csv(entries: &[AuditEntry]) -> String {
+    let mut csv = String::from(
+        "id,created_at,token_id,token_name,kind,name,connection_uuid,query,row_count,duration_ms,error\n",
+    );
+    for entry in entries {
+        let fields = [
+            entry.id.to_string(),
+            entry.created_at.clone(),
+            entry.token_id.map(|id| id.to_string()).unwrap_or_default(),
+            entry.token_name.clone(),
+            entry.kind.clone(),
+            entry.name.clone(),
+            entry.connection_uuid.clone().unwrap_or_default(),
+            entry.query.clone().unwrap_or_default(),
+            entry.row_count.map(|n| n.to_string()).unwrap_or_default(),
+            entry.duration_ms.to_string(),
+            entry.error.clone().unwrap_or_default(),
+        ];
+        let line: Vec<String> = fields.iter().map(|field| csv_field(field)).collect();
+        csv.push_str(&line.join(","));
+        csv.push('\n');
+    }
+    csv
+}
+
+fn csv_field(value: &str) -> String {
+    if value.contains([',', '"', '\n', '\r']) {
+        format!("\"{}\"", value.replace('"', "\"\""))
+    } else {
+        value.to_string()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{export, query, record, set_retention_days, AuditFilter, AuditKind};
+    use crate::database::pool_manager::PoolManager;
+    use crate::mcp::approval::ApprovalBroker;
+    use crate::mcp::McpServer;
+    use sqlx::sqlite::SqlitePoolOptions;
+    use std::sync::Arc;
+    use std::time::Instant;
+
+    async fn server() -> McpServer {
+        let pool = SqlitePoolOptions::new()
+            .max_connections(1)
+            .connect("sqlite::memory:")
+            .await
+            .unwrap();
+        sqlx::migrate!("./migrations").run(&pool).await.unwrap();
+        McpServer::new(
+            pool,
+            Arc::new(PoolManager::new()),
+            Arc::new(ApprovalBroker::headless()),
+        )
+    }
+
+    #[tokio::test]
+    async fn records_filters_and_exports_calls() {
+        let server = server().await;
+        server
+            .audit
+            .record_query("SELECT name, \"total\" FROM orders");
+        server.audit.record_rows(3);
+        record(
+            &server,
+            AuditKind::Tool,
+            "execute_query",
+            Some("dev"),
+            Instant::now(),
+            None,
+        )
+        .await;
+        record(
+            &server,
+            AuditKind::Resource,
+            "dbcooper://connection/prod/schema",
+            Some("prod"),
+            Instant::now(),
+            Some("Connection prod is not available to MCP clients".to_string()),
+        )
+        .await;
+
+        let all = query(&server.sqlite_pool, &AuditFilter::default())
+            .await
+            .unwrap();
+        assert_eq!(all.len(), 2);
+        assert_eq!(
+            all[1].query.as_deref(),
+            Some("SELECT name, \"total\" FROM orders")
+        );
+        assert_eq!(all[1].row_count, Some(3));
+        assert_eq!(all[0].query, None, "details belong to a single call");
+
+        let filter = AuditFilter {
+            query_contains: Some("from ORDERS".to_string()),
+            ..AuditFilter::default()
+        };
+        let matched = query(&server.sqlite_pool, &filter).await.unwrap();
+        assert_eq!(matched.len(), 1);
+        assert_eq!(matched[0].name, "execute_query");
+
+        let errors = AuditFilter {
+            errors_only: true,
+            ..AuditFilter::default()
+        };
+        assert_eq!(
+            query(&server.sqlite_pool, &errors).await.unwrap()[0].kind,
+            "resource"
+        );
+
+        let csv = export(&server.sqlite_pool, &AuditFilter::default(), "csv")
+            .await
+            .unwrap();
+        let lines: Vec<&str> = csv.lines().collect();
+        assert_eq!(lines.len(), 3);
+        assert!(lines[1].contains(",\"SELECT name, \"\"total\"\" FROM orders\",3,"));
+        assert!(export(&server.sqlite_pool, &AuditFilter::default(), "xml")
+            .await
+            .is_err());
+    }
+
+    #[tokio::test]
+    async fn entries_are_append_only_and_expire() {
+        let server = server().await;
+        record(
+            &server,
+            AuditKind::Tool,
+            "list_connections",
+            None,
+            Instant::now(),
+            None,
+        )
+        .await;
+        let pool = &server.sqlite_pool;
+        assert!(sqlx::query("UPDATE mcp_audit_log SET error = 'hidden'")
+            .execute(pool)
+            .await
+            .is_err());
+
+        sqlx::query(
+            "INSERT INTO mcp_audit_log (token_name, kind, name, duration_ms, created_at) VALUES ('old', 'tool', 'list_tables', 1, datetime('now', '-40 days'))",
+        )
+        .execute(pool)
+        .await
+        .unwrap();
+        set_retention_days(pool, 0).await.unwrap();
+        assert_eq!(query(pool, &AuditFilter::default()).await.unwrap().len(), 2);
+        set_retention_days(pool, 30).await.unwrap();
+        let remaining = query(pool, &AuditFilter::default()).await.unwrap();
+        assert_eq!(remaining.len(), 1);
+        assert_eq!(remaining[0].name, "list_connections");
+        assert!(set_retention_days(pool, -1).await.is_err());
+    }
+}
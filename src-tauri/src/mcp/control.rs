@@ -1,7 +1,9 @@
 //! Lifecycle control and settings for the embedded MCP server.
 //!
 //! The server is opt-in: it only starts when the `mcp_server_enabled` setting
-//! is `true`, and every request must carry a bearer token (`mcp_auth_token`).
+//! is `true`, and every request must carry a bearer token: either the app's
+//! own token (`mcp_auth_token`), which reaches every connection, or one of the
+//! named, scoped tokens in [`super::tokens`].
 
 use std::sync::Arc;
 
@@ -9,26 +11,33 @@ use sqlx::SqlitePool;
 use tokio::sync::Mutex;
 use uuid::Uuid;
 
+use super::approval::ApprovalBroker;
 use super::server::{start_mcp_server, McpServerHandle};
 use crate::database::pool_manager::PoolManager;
 
 /// Setting key: whether the MCP server should run (`"true"`/`"false"`).
 pub const SETTING_ENABLED: &str = "mcp_server_enabled";
-/// Setting key: the bearer token required by every MCP request.
+/// Setting key: the app's own bearer token, unrestricted by connection.
 pub const SETTING_TOKEN: &str = "mcp_auth_token";
 
 /// Owns the running MCP server handle and the resources needed to (re)start it.
 pub struct McpControl {
     sqlite_pool: SqlitePool,
     pool_manager: Arc<PoolManager>,
+    approvals: Arc<ApprovalBroker>,
     handle: Mutex<Option<McpServerHandle>>,
 }
 
 impl McpControl {
-    pub fn new(sqlite_pool: SqlitePool, pool_manager: Arc<PoolManager>) -> Self {
+    pub fn new(
+        sqlite_pool: SqlitePool,
+        pool_manager: Arc<PoolManager>,
+        approvals: Arc<ApprovalBroker>,
+    ) -> Self {
         Self {
             sqlite_pool,
             pool_manager,
+            approvals,
             handle: Mutex::new(None),
         }
     }
@@ -55,7 +64,8 @@ impl McpControl {
     }
 
     /// Start the server if it isn't already running. Returns the bound port.
-    /// The server is always read-only; writes are rejected by the database engine.
+    /// What clients may do is decided per connection; writes also need the
+    /// user's approval through `approvals`.
     pub async fn start(&self) -> Result<u16, String> {
         let mut guard = self.handle.lock().await;
         if let Some(handle) = guard.as_ref().filter(|handle| handle.is_running()) {
@@ -66,9 +76,14 @@ impl McpControl {
         }
 
         let token = get_or_create_token(&self.sqlite_pool).await?;
-        let handle = start_mcp_server(self.sqlite_pool.clone(), self.pool_manager.clone(), token)
-            .await
-            .map_err(|e| e.to_string())?;
+        let handle = start_mcp_server(
+            self.sqlite_pool.clone(),
+            self.pool_manager.clone(),
+            self.approvals.clone(),
+            token,
+        )
+        .await
+        .map_err(|e| e.to_string())?;
 
         let port = handle.port;
         *guard = Some(handle);
@@ -119,7 +134,7 @@ pub async fn regenerate_token(pool: &SqlitePool) -> Result<String, String> {
     Ok(token)
 }
 
-fn generate_token() -> String {
+pub(super) fn generate_token() -> String {
     // 256 bits of randomness from two v4 UUIDs.
     format!("{}{}", Uuid::new_v4().simple(), Uuid::new_v4().simple())
 }
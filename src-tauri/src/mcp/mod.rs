@@ -1,28 +1,88 @@
+pub mod approval;
+pub mod audit;
 pub mod control;
+pub mod permissions;
+pub mod prompts;
 pub mod resources;
+pub mod saved;
+pub mod schema_search;
 pub mod server;
+pub mod tokens;
 pub mod tools;
 
 use std::sync::Arc;
 
 use crate::database::pool_manager::PoolManager;
+use crate::db::models::Connection;
+use approval::ApprovalBroker;
+use audit::CallAudit;
+use permissions::{McpPermission, McpScope};
 use rmcp::model::*;
+use rmcp::service::{RequestContext, RoleServer};
 use rmcp::{ErrorData as McpError, ServerHandler};
 use sqlx::SqlitePool;
 
+#[derive(Clone)]
 pub struct McpServer {
     pub sqlite_pool: SqlitePool,
     pub pool_manager: Arc<PoolManager>,
+    pub approvals: Arc<ApprovalBroker>,
+    /// What the client may reach. HTTP requests replace it with the scope of
+    /// the token they authenticated with; see [`Self::scoped`].
+    pub scope: McpScope,
+    /// Details of the request being handled, for its audit log entry.
+    pub audit: Arc<CallAudit>,
 }
 
 impl McpServer {
-    pub fn new(sqlite_pool: SqlitePool, pool_manager: Arc<PoolManager>) -> Self {
+    pub fn new(
+        sqlite_pool: SqlitePool,
+        pool_manager: Arc<PoolManager>,
+        approvals: Arc<ApprovalBroker>,
+    ) -> Self {
         Self {
             sqlite_pool,
             pool_manager,
+            approvals,
+            scope: McpScope::unrestricted(),
+            audit: Arc::default(),
         }
     }
 
+    /// This server as seen by the client making the request in `context`,
+    /// with a fresh audit record for that request.
+    fn scoped(&self, context: &RequestContext<RoleServer>) -> Self {
+        let scope = context
+            .extensions
+            .get::<axum::http::request::Parts>()
+            .and_then(|parts| parts.extensions.get::<McpScope>())
+            .cloned()
+            .unwrap_or_else(|| self.scope.clone());
+        Self {
+            scope,
+            audit: Arc::default(),
+            ..self.clone()
+        }
+    }
+
+    /// Check the connection behind `uuid` allows this client `required` access.
+    pub async fn authorize(
+        &self,
+        uuid: &str,
+        required: McpPermission,
+    ) -> Result<Connection, McpError> {
+        permissions::authorize(&self.sqlite_pool, &self.scope, uuid, required)
+            .await
+            .map_err(|e| McpError::invalid_request(e, None))
+    }
+
+    /// Saved connections this client may see, newest first.
+    pub async fn visible_connections(&self) -> Result<Vec<Connection>, McpError> {
+        permissions::visible_connections(&self.sqlite_pool, &self.scope)
+            .await
+            .map_err(|e| McpError::internal_error(e, None))
+    }
+
     /// Ensure a connection exists in the pool, connecting if needed.
     /// Delegates to the pool manager's per-UUID-serialized connect path.
     pub async fn ensure_connected(&self, uuid: &str) -> Result<(), McpError> {
@@ -39,6 +99,7 @@ impl ServerHandler for McpServer {
             ServerCapabilities::builder()
                 .enable_tools()
                 .enable_resources()
+                .enable_prompts()
                 .build(),
         )
         .with_server_info(Implementation::new(
@@ -63,25 +124,28 @@ impl ServerHandler for McpServer {
     fn call_tool(
         &self,
         request: CallToolRequestParams,
-        _context: rmcp::service::RequestContext<rmcp::service::RoleServer>,
+        context: rmcp::service::RequestContext<rmcp::service::RoleServer>,
     ) -> impl std::future::Future<Output = Result<CallToolResult, McpError>> + Send + '_ {
-        tools::dispatch_tool(self, request)
+        let server = self.scoped(&context);
+        async move { tools::dispatch_tool(&server, request).await }
     }
 
     fn list_resources(
         &self,
         _request: Option<PaginatedRequestParams>,
-        _context: rmcp::service::RequestContext<rmcp::service::RoleServer>,
+        context: rmcp::service::RequestContext<rmcp::service::RoleServer>,
     ) -> impl std::future::Future<Output = Result<ListResourcesResult, McpError>> + Send + '_ {
-        resources::list_resources(self)
+        let server = self.scoped(&context);
+        async move { resources::list_resources(&server).await }
     }
 
     fn read_resource(
         &self,
         request: ReadResourceRequestParams,
-        _context: rmcp::service::RequestContext<rmcp::service::RoleServer>,
+        context: rmcp::service::RequestContext<rmcp::service::RoleServer>,
     ) -> impl std::future::Future<Output = Result<ReadResourceResult, McpError>> + Send + '_ {
-        resources::read_resource(self, request)
+        let server = self.scoped(&context);
+        async move { resources::read_resource(&server, request).await }
     }
 
     fn list_resource_templates(
@@ -95,6 +159,41 @@ impl ServerHandler for McpServer {
                 .with_description("Full schema overview for a connected database")
                 .with_mime_type("application/json")
                 .no_annotation(),
+            RawResourceTemplate::new(
+                "dbcooper://connection/{uuid}/saved-query/{id}",
+                "Saved Query",
+            )
+            .with_description("A saved query; run it with the run_saved_query tool")
+            .with_mime_type("application/json")
+            .no_annotation(),
+            RawResourceTemplate::new(
+                "dbcooper://connection/{uuid}/saved-view/{id}",
+                "Saved View",
+            )
+            .with_description(
+                "A saved table view with its filter, sort and hidden columns; run it with the run_saved_view tool",
+            )
+            .with_mime_type("application/json")
+            .no_annotation(),
         ])))
     }
+
+    fn list_prompts(
+        &self,
+        _request: Option<PaginatedRequestParams>,
+        _context: rmcp::service::RequestContext<rmcp::service::RoleServer>,
+    ) -> impl std::future::Future<Output = Result<ListPromptsResult, McpError>> + Send + '_ {
+        std::future::ready(Ok(ListPromptsResult::with_all_items(
+            prompts::prompt_definitions(),
+        )))
+    }
+
+    fn get_prompt(
+        &self,
+        request: GetPromptRequestParams,
+        context: rmcp::service::RequestContext<rmcp::service::RoleServer>,
+    ) -> impl std::future::Future<Output = Result<GetPromptResult, McpError>> + Send + '_ {
+        let server = self.scoped(&context);
+        async move { prompts::get_prompt(&server, request).await }
+    }
 }
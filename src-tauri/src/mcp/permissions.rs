@@ -0,0 +1,202 @@
+//! Per-connection MCP permission levels.
+//!
+//! Each saved connection decides what MCP clients may do with it: nothing
+//! (the connection is hidden), read, or write. Writes additionally need the
+//! user's approval for every statement; see [`super::approval`]. The token a
+//! client authenticates with narrows this further to an [`McpScope`].
+
+use std::collections::HashSet;
+
+use crate::database::pool_manager::PoolManager;
+use crate::db::models::Connection;
+use sqlx::SqlitePool;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord)]
+pub enum McpPermission {
+    None,
+    Read,
+    Write,
+}
+
+impl McpPermission {
+    pub fn parse(value: &str) -> Result<Self, String> {
+        match value.trim().to_ascii_lowercase().as_str() {
+            "none" => Ok(Self::None),
+            "read" => Ok(Self::Read),
+            "write" => Ok(Self::Write),
+            _ => Err(format!(
+                "Unknown MCP permission: {value}. Expected none, read or write"
+            )),
+        }
+    }
+
+    /// Permission for a stored or imported value; anything unrecognised
+    /// (including rows written before the column existed) reads as `read`.
+    pub fn from_stored(value: &str) -> Self {
+        Self::parse(value).unwrap_or(Self::Read)
+    }
+
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Self::None => "none",
+            Self::Read => "read",
+            Self::Write => "write",
+        }
+    }
+}
+
+/// How the app's own token is named in the audit log.
+pub const APP_TOKEN_NAME: &str = "DBcooper app token";
+
+/// Who an MCP client authenticated as and what its token grants.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct McpScope {
+    /// The named token used, or `None` for the app's own token.
+    pub token_id: Option<i64>,
+    pub token_name: String,
+    /// Connections the client may use; `None` means every connection.
+    pub connections: Option<HashSet<String>>,
+    /// Highest level granted. Each connection's own setting still applies.
+    pub permission: McpPermission,
+}
+
+impl McpScope {
+    /// The scope of the desktop app's own token: every connection, up to
+    /// whatever each connection allows.
+    pub fn unrestricted() -> Self {
+        Self {
+            token_id: None,
+            token_name: APP_TOKEN_NAME.to_string(),
+            connections: None,
+            permission: McpPermission::Write,
+        }
+    }
+
+    pub fn includes(&self, uuid: &str) -> bool {
+        self.connections
+            .as_ref()
+            .is_none_or(|connections| connections.contains(uuid))
+    }
+
+    /// What a client in this scope may do with `connection`.
+    pub fn permission_for(&self, connection: &Connection) -> McpPermission {
+        if !self.includes(&connection.uuid) {
+            return McpPermission::None;
+        }
+        McpPermission::from_stored(&connection.mcp_permission).min(self.permission)
+    }
+}
+
+/// Saved connections visible within `scope`, newest first.
+pub(crate) async fn visible_connections(
+    pool: &SqlitePool,
+    scope: &McpScope,
+) -> Result<Vec<Connection>, String> {
+    let connections: Vec<Connection> = sqlx::query_as("SELECT * FROM connections ORDER BY id DESC")
+        .fetch_all(pool)
+        .await
+        .map_err(|e| format!("Database error: {}", e))?;
+    Ok(connections
+        .into_iter()
+        .filter(|connection| scope.permission_for(connection) > McpPermission::None)
+        .collect())
+}
+
+/// Load the saved connection behind `key` (a connection UUID or database
+/// session key) and check `scope` may use it at the `required` level.
+pub(crate) async fn authorize(
+    pool: &SqlitePool,
+    scope: &McpScope,
+    key: &str,
+    required: McpPermission,
+) -> Result<Connection, String> {
+    let uuid = PoolManager::connection_uuid(key);
+    let connection: Option<Connection> = sqlx::query_as("SELECT * FROM connections WHERE uuid = ?")
+        .bind(uuid)
+        .fetch_optional(pool)
+        .await
+        .map_err(|e| format!("Database error: {}", e))?;
+    let Some(connection) = connection else {
+        return Err(format!("Connection {uuid} is not available to MCP clients"));
+    };
+
+    if scope.permission_for(&connection) == McpPermission::None {
+        return Err(format!("Connection {uuid} is not available to MCP clients"));
+    }
+    if scope.permission < required {
+        return Err(format!(
+            "This MCP token may only read from '{}'",
+            connection.name
+        ));
+    }
+    if McpPermission::from_stored(&connection.mcp_permission) < required {
+        return Err(format!(
+            "MCP clients may only read from '{}'. Allow writes with approval in the connection settings to use this tool.",
+            connection.name
+        ));
+    }
+    Ok(connection)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{McpPermission, McpScope};
+    use crate::db::models::Connection;
+
+    #[test]
+    fn parses_levels_and_treats_unknown_stored_values_as_read() {
+        assert_eq!(McpPermission::parse(" Write "), Ok(McpPermission::Write));
+        assert!(McpPermission::parse("admin").is_err());
+        assert_eq!(McpPermission::from_stored(""), McpPermission::Read);
+        assert_eq!(McpPermission::from_stored("none"), McpPermission::None);
+        assert!(McpPermission::Write > McpPermission::Read);
+        assert!(McpPermission::Read > McpPermission::None);
+    }
+
+    #[test]
+    fn scopes_narrow_connection_permissions() {
+        let connection: Connection = serde_json::from_value(serde_json::json!({
+            "id": 1,
+            "uuid": "dev",
+            "name": "Dev",
+            "type": "database",
+            "host": "",
+            "port": 0,
+            "database": "",
+            "username": "",
+            "password": "",
+            "ssl": 0,
+            "db_type": "sqlite",
+            "ssh_enabled": 0,
+            "ssh_host": "",
+            "ssh_port": 22,
+            "ssh_user": "",
+            "ssh_password": "",
+            "ssh_key_path": "",
+            "ssh_use_key": 0,
+            "mcp_permission": "write",
+            "created_at": "",
+            "updated_at": ""
+        }))
+        .unwrap();
+
+        assert_eq!(
+            McpScope::unrestricted().permission_for(&connection),
+            McpPermission::Write
+        );
+        let read_only = McpScope {
+            token_id: Some(1),
+            token_name: "Reporting".to_string(),
+            connections: Some(["dev".to_string()].into()),
+            permission: McpPermission::Read,
+        };
+        assert_eq!(read_only.permission_for(&connection), McpPermission::Read);
+        let elsewhere = McpScope {
+            token_id: Some(2),
+            token_name: "Ops".to_string(),
+            connections: Some(["prod".to_string()].into()),
+            permission: McpPermission::Write,
+        };
+        assert_eq!(elsewhere.permission_for(&connection), McpPermission::None);
+    }
+}
@@ -0,0 +1,135 @@
+use super::audit::{self, AuditKind};
+use super::permissions::McpPermission;
+use super::tools::{fetch_table_page, TablePage};
+use super::McpServer;
+use rmcp::model::*;
+use rmcp::ErrorData as McpError;
+
+/// Sample rows included in the `analyze_table` prompt.
+const ANALYZE_SAMPLE_ROWS: u64 = 20;
+
+pub fn prompt_definitions() -> Vec<Prompt> {
+    vec![Prompt::new(
+        "analyze_table",
+        Some("Analyze a table from its structure and a sample of its rows"),
+        Some(vec![
+            PromptArgument::new("connection_uuid")
+                .with_description("UUID of the saved connection")
+                .with_required(true),
+            PromptArgument::new("schema")
+                .with_description("Schema name (e.g. 'public' for PostgreSQL, 'main' for SQLite)")
+                .with_required(true),
+            PromptArgument::new("table")
+                .with_description("Table name")
+                .with_required(true),
+            PromptArgument::new("question")
+                .with_description("What to find out about the table (optional)")
+                .with_required(false),
+        ]),
+    )
+    .with_title("Analyze table")]
+}
+
+/// Build a prompt by name and record it in the audit log.
+pub async fn get_prompt(
+    server: &McpServer,
+    request: GetPromptRequestParams,
+) -> Result<GetPromptResult, McpError> {
+    let started = std::time::Instant::now();
+    let argument = |key: &str| {
+        request
+            .arguments
+            .as_ref()
+            .and_then(|args| args.get(key))
+            .and_then(serde_json::Value::as_str)
+    };
+    let connection_uuid = argument("connection_uuid");
+
+    let result = match request.name.as_str() {
+        "analyze_table" => {
+            let required = |key: &str| {
+                argument(key).ok_or_else(|| {
+                    McpError::invalid_params(format!("Missing required argument: {}", key), None)
+                })
+            };
+            match (
+                required("connection_uuid"),
+                required("schema"),
+                required("table"),
+            ) {
+                (Ok(uuid), Ok(schema), Ok(table)) => {
+                    analyze_table(server, uuid, schema, table, argument("question")).await
+                }
+                (Err(e), _, _) | (_, Err(e), _) | (_, _, Err(e)) => Err(e),
+            }
+        }
+        other => Err(McpError::invalid_params(
+            format!("Unknown prompt: {}", other),
+            None,
+        )),
+    };
+
+    let error = result.as_ref().err().map(|e| e.message.to_string());
+    audit::record(
+        server,
+        AuditKind::Prompt,
+        &request.name,
+        connection_uuid,
+        started,
+        error,
+    )
+    .await;
+    result
+}
+
+async fn analyze_table(
+    server: &McpServer,
+    uuid: &str,
+    schema: &str,
+    table: &str,
+    question: Option<&str>,
+) -> Result<GetPromptResult, McpError> {
+    let connection = server.authorize(uuid, McpPermission::Read).await?;
+    server.ensure_connected(uuid).await?;
+
+    let structure = server
+        .pool_manager
+        .get_table_structure(uuid, schema, table)
+        .await
+        .map_err(|e| McpError::internal_error(format!("Failed to describe table: {}", e), None))?;
+    let sample = TablePage {
+        page: 1,
+        limit: ANALYZE_SAMPLE_ROWS,
+        filter: None,
+        sort_column: None,
+        sort_direction: None,
+    };
+    let rows = fetch_table_page(server, uuid, schema, table, sample)
+        .await
+        .map_err(|timeout| McpError::internal_error(timeout.message, None))?
+        .map_err(|e| McpError::internal_error(format!("Failed to sample table: {}", e), None))?;
+    server.audit.record_rows(rows.data.len() as i64);
+
+    let structure = serde_json::to_string_pretty(&structure).unwrap_or_else(|_| "{}".to_string());
+    let samples = serde_json::to_string_pretty(&rows.data).unwrap_or_else(|_| "[]".to_string());
+    let ask = question.map(str::trim).filter(|q| !q.is_empty()).unwrap_or(
+        "Describe what this table holds, how its columns relate to each other and to other tables, and anything in the data that looks inconsistent or worth a closer look.",
+    );
+
+    let text = format!(
+        "Analyze the table `{schema}.{table}` in the {db_type} database \"{name}\" (connection {uuid}).\n\n\
+         Structure (columns, indexes and foreign keys):\n```json\n{structure}\n```\n\n\
+         First {sampled} of {total} rows:\n```json\n{samples}\n```\n\n\
+         {ask}\n\n\
+         Use the DBcooper tools (read-only) if you need more data; prefer a saved query from list_saved_queries when one fits.",
+        db_type = connection.db_type,
+        name = connection.name,
+        sampled = rows.data.len(),
+        total = rows.total,
+    );
+
+    Ok(
+        GetPromptResult::new(vec![PromptMessage::new_text(PromptMessageRole::User, text)])
+            .with_description(format!("Analyze {}.{}", schema, table)),
+    )
+}
@@ -1,10 +1,17 @@
+use super::audit::{self, AuditKind};
+use super::permissions::McpPermission;
+use super::saved::{self, SavedItem};
 use super::McpServer;
+use crate::commands::queries::{load_saved_queries, load_saved_query};
+use crate::commands::saved_views::{load_saved_view, load_saved_views};
 use rmcp::model::*;
 use rmcp::ErrorData as McpError;
 use serde_json::json;
 
-/// List available resources — returns the static `dbcooper://connections` resource
-/// plus a resource for each currently-connected database's schema.
+/// List available resources — returns the static `dbcooper://connections` resource,
+/// a resource for each currently-connected database's schema, and the saved
+/// queries and views of every visible connection. Connections hidden from MCP
+/// clients are left out.
 pub async fn list_resources(server: &McpServer) -> Result<ListResourcesResult, McpError> {
     let mut resources = vec![
         RawResource::new("dbcooper://connections", "Database Connections")
@@ -13,11 +20,7 @@ pub async fn list_resources(server: &McpServer) -> Result<ListResourcesResult, M
             .no_annotation(),
     ];
 
-    let connections: Vec<crate::db::models::Connection> =
-        sqlx::query_as("SELECT * FROM connections ORDER BY id DESC")
-            .fetch_all(&server.sqlite_pool)
-            .await
-            .map_err(|e| McpError::internal_error(format!("Database error: {}", e), None))?;
+    let connections = server.visible_connections().await?;
 
     for conn in &connections {
         if server.pool_manager.get_cached(&conn.uuid).await.is_some() {
@@ -34,18 +37,66 @@ pub async fn list_resources(server: &McpServer) -> Result<ListResourcesResult, M
                 .no_annotation(),
             );
         }
+
+        let queries = load_saved_queries(&server.sqlite_pool, &conn.uuid)
+            .await
+            .map_err(|e| McpError::internal_error(e, None))?;
+        resources.extend(queries.into_iter().map(|query| {
+            RawResource::new(
+                saved::saved_query_uri(&conn.uuid, query.id),
+                format!("{}: {}", conn.name, query.name),
+            )
+            .with_description(format!("Saved query on {}", conn.name))
+            .with_mime_type("application/json")
+            .no_annotation()
+        }));
+
+        let views = load_saved_views(&server.sqlite_pool, &conn.uuid)
+            .await
+            .map_err(|e| McpError::internal_error(e, None))?;
+        resources.extend(views.into_iter().map(|view| {
+            RawResource::new(
+                saved::saved_view_uri(&conn.uuid, view.id),
+                format!("{}: {}", conn.name, view.name),
+            )
+            .with_description(format!(
+                "Saved view of {} on {}",
+                view.table_name, conn.name
+            ))
+            .with_mime_type("application/json")
+            .no_annotation()
+        }));
     }
 
     Ok(ListResourcesResult::with_all_items(resources))
 }
 
-/// Read a specific resource by URI.
+/// Read a specific resource by URI and record it in the audit log.
 pub async fn read_resource(
     server: &McpServer,
     request: ReadResourceRequestParams,
 ) -> Result<ReadResourceResult, McpError> {
+    let started = std::time::Instant::now();
     let uri = request.uri.as_str();
+    let connection_uuid = uri
+        .strip_prefix("dbcooper://connection/")
+        .and_then(|rest| rest.split('/').next());
+
+    let result = read(server, uri).await;
+    let error = result.as_ref().err().map(|e| e.message.to_string());
+    audit::record(
+        server,
+        AuditKind::Resource,
+        uri,
+        connection_uuid,
+        started,
+        error,
+    )
+    .await;
+    result
+}
 
+async fn read(server: &McpServer, uri: &str) -> Result<ReadResourceResult, McpError> {
     if uri == "dbcooper://connections" {
         return read_connections(server).await;
     }
@@ -57,6 +108,10 @@ pub async fn read_resource(
         return read_schema(server, uuid).await;
     }
 
+    if let Some((uuid, item)) = saved::parse_saved_uri(uri) {
+        return read_saved(server, uuid, item, uri).await;
+    }
+
     Err(McpError::resource_not_found(
         format!("Unknown resource: {}", uri),
         None,
@@ -64,11 +119,7 @@ pub async fn read_resource(
 }
 
 async fn read_connections(server: &McpServer) -> Result<ReadResourceResult, McpError> {
-    let connections: Vec<crate::db::models::Connection> =
-        sqlx::query_as("SELECT * FROM connections ORDER BY id DESC")
-            .fetch_all(&server.sqlite_pool)
-            .await
-            .map_err(|e| McpError::internal_error(format!("Database error: {}", e), None))?;
+    let connections = server.visible_connections().await?;
 
     let safe: Vec<serde_json::Value> = connections
         .into_iter()
@@ -82,6 +133,7 @@ async fn read_connections(server: &McpServer) -> Result<ReadResourceResult, McpE
                 "database": c.database,
                 "ssl": c.ssl == 1,
                 "ssh_enabled": c.ssh_enabled == 1,
+                "mcp_permission": server.scope.permission_for(&c).as_str(),
             })
         })
         .collect();
@@ -95,6 +147,7 @@ async fn read_connections(server: &McpServer) -> Result<ReadResourceResult, McpE
 }
 
 async fn read_schema(server: &McpServer, uuid: &str) -> Result<ReadResourceResult, McpError> {
+    server.authorize(uuid, McpPermission::Read).await?;
     server.ensure_connected(uuid).await?;
 
     let overview = server
@@ -110,3 +163,30 @@ async fn read_schema(server: &McpServer, uuid: &str) -> Result<ReadResourceResul
         format!("dbcooper://connection/{}/schema", uuid),
     )]))
 }
+
+async fn read_saved(
+    server: &McpServer,
+    uuid: &str,
+    item: SavedItem,
+    uri: &str,
+) -> Result<ReadResourceResult, McpError> {
+    server.authorize(uuid, McpPermission::Read).await?;
+
+    let value = match item {
+        SavedItem::Query(id) => load_saved_query(&server.sqlite_pool, uuid, id)
+            .await
+            .map_err(|e| McpError::internal_error(e, None))?
+            .map(|query| serde_json::to_value(query).unwrap_or_default()),
+        SavedItem::View(id) => load_saved_view(&server.sqlite_pool, uuid, id)
+            .await
+            .map_err(|e| McpError::internal_error(e, None))?
+            .map(|view| serde_json::to_value(view).unwrap_or_default()),
+    }
+    .ok_or_else(|| McpError::resource_not_found(format!("Unknown resource: {}", uri), None))?;
+
+    let text = serde_json::to_string_pretty(&value).unwrap_or_else(|_| "{}".to_string());
+
+    Ok(ReadResourceResult::new(vec![ResourceContents::text(
+        text, uri,
+    )]))
+}
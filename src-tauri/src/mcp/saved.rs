@@ -0,0 +1,133 @@
+//! Saved queries and saved views as MCP resources, so agents reuse the
+//! curated queries and table views people already keep in DBcooper.
+
+use serde_json::{Map, Value};
+
+use crate::db::models::{SavedView, SavedViewSortDirection, SavedViewState};
+
+/// Which kind of saved item a `dbcooper://connection/{uuid}/...` URI names.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SavedItem {
+    Query(i64),
+    View(i64),
+}
+
+pub fn saved_query_uri(connection_uuid: &str, id: i64) -> String {
+    format!(
+        "dbcooper://connection/{}/saved-query/{}",
+        connection_uuid, id
+    )
+}
+
+pub fn saved_view_uri(connection_uuid: &str, id: i64) -> String {
+    format!(
+        "dbcooper://connection/{}/saved-view/{}",
+        connection_uuid, id
+    )
+}
+
+/// Split a saved query or view URI into its connection UUID and item.
+pub fn parse_saved_uri(uri: &str) -> Option<(&str, SavedItem)> {
+    let rest = uri.strip_prefix("dbcooper://connection/")?;
+    let (uuid, rest) = rest.split_once('/')?;
+    let (kind, id) = rest.split_once('/')?;
+    let id = id.parse().ok()?;
+    match kind {
+        "saved-query" => Some((uuid, SavedItem::Query(id))),
+        "saved-view" => Some((uuid, SavedItem::View(id))),
+        _ => None,
+    }
+}
+
+/// The `(schema, table)` a saved view belongs to. Views store the table the
+/// way the app's tabs name it, `schema.table`.
+pub fn view_table(view: &SavedView) -> (&str, &str) {
+    view.table_name
+        .split_once('.')
+        .unwrap_or(("", view.table_name.as_str()))
+}
+
+/// The sort column and direction of a view, in the form `get_table_data`
+/// takes them.
+pub fn view_sort(state: &SavedViewState) -> (Option<String>, Option<String>) {
+    match &state.sort {
+        Some(sort) => {
+            let direction = match sort.direction {
+                SavedViewSortDirection::Asc => "asc",
+                SavedViewSortDirection::Desc => "desc",
+            };
+            (Some(sort.column.clone()), Some(direction.to_string()))
+        }
+        None => (None, None),
+    }
+}
+
+/// Drop the view's hidden columns from `rows` and put the rest in the view's
+/// column order. Columns the view doesn't mention keep their place after the
+/// ordered ones.
+pub fn apply_view_columns(rows: Vec<Value>, state: &SavedViewState) -> Vec<Value> {
+    if state.hidden_columns.is_empty() && state.column_order.is_empty() {
+        return rows;
+    }
+
+    rows.into_iter()
+        .map(|row| match row {
+            Value::Object(mut fields) => {
+                for hidden in &state.hidden_columns {
+                    fields.remove(hidden);
+                }
+                let mut ordered = Map::with_capacity(fields.len());
+                for column in &state.column_order {
+                    if let Some(value) = fields.remove(column) {
+                        ordered.insert(column.clone(), value);
+                    }
+                }
+                ordered.extend(fields);
+                Value::Object(ordered)
+            }
+            other => other,
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{apply_view_columns, parse_saved_uri, saved_query_uri, saved_view_uri, SavedItem};
+    use crate::db::models::SavedViewState;
+    use serde_json::json;
+
+    #[test]
+    fn round_trips_saved_item_uris() {
+        assert_eq!(
+            parse_saved_uri(&saved_query_uri("abc", 7)),
+            Some(("abc", SavedItem::Query(7)))
+        );
+        assert_eq!(
+            parse_saved_uri(&saved_view_uri("abc", 3)),
+            Some(("abc", SavedItem::View(3)))
+        );
+        assert_eq!(parse_saved_uri("dbcooper://connection/abc/schema"), None);
+        assert_eq!(
+            parse_saved_uri("dbcooper://connection/abc/saved-query/x"),
+            None
+        );
+    }
+
+    #[test]
+    fn hides_and_orders_view_columns() {
+        let state: SavedViewState = serde_json::from_value(json!({
+            "version": 1,
+            "filter": null,
+            "sort": null,
+            "column_order": ["name", "id"],
+            "hidden_columns": ["secret"],
+            "column_widths": {}
+        }))
+        .unwrap();
+        let rows = vec![json!({"id": 1, "secret": "x", "email": "a@b", "name": "a"})];
+
+        let shaped = apply_view_columns(rows, &state);
+        let columns: Vec<&String> = shaped[0].as_object().unwrap().keys().collect();
+        assert_eq!(columns, ["name", "id", "email"]);
+    }
+}
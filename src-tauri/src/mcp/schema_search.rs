@@ -0,0 +1,157 @@
+//! Fuzzy search over a schema overview, so MCP clients can find a table or
+//! column without pulling the whole overview into their context.
+
+use serde::Serialize;
+
+use crate::db::models::SchemaOverview;
+use crate::fuzzy::score_terms;
+
+#[derive(Debug, Clone, Serialize, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum SchemaMatchKind {
+    Table,
+    Column,
+    Function,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct SchemaMatch {
+    pub kind: SchemaMatchKind,
+    pub schema: String,
+    /// Table or function name.
+    pub name: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub column: Option<String>,
+    /// Column type, table type (`BASE TABLE`, `VIEW`, ...) or return type.
+    #[serde(rename = "type")]
+    pub data_type: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub identity_args: Option<String>,
+    pub score: i64,
+}
+
+/// Tables, columns and functions matching `query`, best first. Each
+/// whitespace-separated term must match, so `orders email` finds the `email`
+/// column of `orders`.
+pub fn search_schema(overview: &SchemaOverview, query: &str, limit: usize) -> Vec<SchemaMatch> {
+    let mut matches = Vec::new();
+
+    for table in &overview.tables {
+        let qualified = format!("{}.{}", table.schema, table.name);
+        if let Some(score) = score_terms(query, &[&table.name, &qualified]) {
+            matches.push(SchemaMatch {
+                kind: SchemaMatchKind::Table,
+                schema: table.schema.clone(),
+                name: table.name.clone(),
+                column: None,
+                data_type: table.table_type.clone(),
+                identity_args: None,
+                score,
+            });
+        }
+        for column in &table.columns {
+            let qualified = format!("{}.{}", table.name, column.name);
+            if let Some(score) = score_terms(query, &[&column.name, &qualified]) {
+                matches.push(SchemaMatch {
+                    kind: SchemaMatchKind::Column,
+                    schema: table.schema.clone(),
+                    name: table.name.clone(),
+                    column: Some(column.name.clone()),
+                    data_type: column.data_type.clone(),
+                    identity_args: None,
+                    score,
+                });
+            }
+        }
+    }
+
+    for function in &overview.functions {
+        let qualified = format!("{}.{}", function.schema, function.name);
+        if let Some(score) = score_terms(query, &[&function.name, &qualified]) {
+            matches.push(SchemaMatch {
+                kind: SchemaMatchKind::Function,
+                schema: function.schema.clone(),
+                name: function.name.clone(),
+                column: None,
+                data_type: function.return_type.clone(),
+                identity_args: Some(function.identity_args.clone()),
+                score,
+            });
+        }
+    }
+
+    matches.sort_by(|a, b| {
+        b.score
+            .cmp(&a.score)
+            .then_with(|| a.name.cmp(&b.name))
+            .then_with(|| a.column.cmp(&b.column))
+    });
+    matches.truncate(limit);
+    matches
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{search_schema, SchemaMatchKind};
+    use crate::db::models::SchemaOverview;
+
+    fn overview() -> SchemaOverview {
+        serde_json::from_value(serde_json::json!({
+            "tables": [
+                {
+                    "schema": "public",
+                    "name": "orders",
+                    "type": "BASE TABLE",
+                    "columns": [
+                        {"name": "id", "type": "integer", "nullable": false, "default": null, "primary_key": true},
+                        {"name": "customer_email", "type": "text", "nullable": false, "default": null, "primary_key": false}
+                    ],
+                    "foreign_keys": [],
+                    "indexes": []
+                },
+                {
+                    "schema": "public",
+                    "name": "customers",
+                    "type": "BASE TABLE",
+                    "columns": [
+                        {"name": "email", "type": "text", "nullable": false, "default": null, "primary_key": false}
+                    ],
+                    "foreign_keys": [],
+                    "indexes": []
+                }
+            ],
+            "functions": [
+                {
+                    "schema": "public",
+                    "name": "order_total",
+                    "identity_args": "order_id integer",
+                    "arguments": "order_id integer",
+                    "return_type": "numeric",
+                    "language": "sql"
+                }
+            ]
+        }))
+        .unwrap()
+    }
+
+    #[test]
+    fn finds_columns_by_table_and_name() {
+        let matches = search_schema(&overview(), "orders email", 10);
+        assert_eq!(matches[0].kind, SchemaMatchKind::Column);
+        assert_eq!(matches[0].name, "orders");
+        assert_eq!(matches[0].column.as_deref(), Some("customer_email"));
+        assert!(matches.iter().all(|m| m.name == "orders"));
+    }
+
+    #[test]
+    fn ranks_exact_names_first_and_respects_the_limit() {
+        let matches = search_schema(&overview(), "email", 10);
+        assert_eq!(matches[0].name, "customers");
+        assert_eq!(matches[0].column.as_deref(), Some("email"));
+
+        let functions = search_schema(&overview(), "order_total", 1);
+        assert_eq!(functions.len(), 1);
+        assert_eq!(functions[0].kind, SchemaMatchKind::Function);
+        assert!(search_schema(&overview(), "invoices", 10).is_empty());
+    }
+}
@@ -10,8 +10,11 @@ use sqlx::SqlitePool;
 use tokio::net::TcpListener;
 use tokio_util::sync::CancellationToken;
 
-use super::McpServer;
+use super::approval::ApprovalBroker;
+use super::permissions::McpScope;
+use super::{tokens, McpServer};
 use crate::database::pool_manager::PoolManager;
+use rmcp::ServiceExt;
 
 const DEFAULT_PORT: u16 = 9420;
 const MAX_PORT_ATTEMPTS: u16 = 10;
@@ -33,48 +36,72 @@ impl McpServerHandle {
     }
 }
 
-/// Reject any request that doesn't present `Authorization: Bearer <token>`.
+#[derive(Clone)]
+struct Authenticator {
+    sqlite_pool: SqlitePool,
+    /// The desktop app's own token, which reaches every connection.
+    app_token: Arc<String>,
+}
+
+/// Reject any request that doesn't present `Authorization: Bearer <token>`
+/// with a known, unexpired token, and attach the token's [`McpScope`] to the
+/// request for the tools and resources to honour.
 async fn require_bearer_token(
-    State(expected): State<Arc<String>>,
-    req: axum::extract::Request,
+    State(auth): State<Authenticator>,
+    mut req: axum::extract::Request,
     next: Next,
 ) -> Result<Response, StatusCode> {
     let provided = req
         .headers()
         .get(AUTHORIZATION)
         .and_then(|v| v.to_str().ok())
-        .and_then(|v| v.strip_prefix("Bearer "));
-
-    match provided {
-        Some(token) if token == expected.as_str() => Ok(next.run(req).await),
-        _ => Err(StatusCode::UNAUTHORIZED),
-    }
+        .and_then(|v| v.strip_prefix("Bearer "))
+        .map(str::to_string)
+        .ok_or(StatusCode::UNAUTHORIZED)?;
+
+    let scope = if provided == auth.app_token.as_str() {
+        McpScope::unrestricted()
+    } else {
+        tokens::resolve(&auth.sqlite_pool, &provided)
+            .await
+            .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?
+            .ok_or(StatusCode::UNAUTHORIZED)?
+    };
+    req.extensions_mut().insert(scope);
+    Ok(next.run(req).await)
 }
 
 pub async fn start_mcp_server(
     sqlite_pool: SqlitePool,
     pool_manager: Arc<PoolManager>,
+    approvals: Arc<ApprovalBroker>,
     auth_token: String,
 ) -> Result<McpServerHandle, Box<dyn std::error::Error + Send + Sync>> {
     let ct = CancellationToken::new();
+    let auth = Authenticator {
+        sqlite_pool: sqlite_pool.clone(),
+        app_token: Arc::new(auth_token),
+    };
 
     let (listener, port) = bind_with_retry(DEFAULT_PORT, MAX_PORT_ATTEMPTS).await?;
 
     let config = StreamableHttpServerConfig::default().with_cancellation_token(ct.child_token());
 
     let service = StreamableHttpService::new(
-        move || Ok(McpServer::new(sqlite_pool.clone(), pool_manager.clone())),
+        move || {
+            Ok(McpServer::new(
+                sqlite_pool.clone(),
+                pool_manager.clone(),
+                approvals.clone(),
+            ))
+        },
         Arc::new(LocalSessionManager::default()),
         config,
     );
 
-    let router =
-        axum::Router::new()
-            .nest_service("/mcp", service)
-            .layer(middleware::from_fn_with_state(
-                Arc::new(auth_token),
-                require_bearer_token,
-            ));
+    let router = axum::Router::new()
+        .nest_service("/mcp", service)
+        .layer(middleware::from_fn_with_state(auth, require_bearer_token));
 
     let shutdown_ct = ct.clone();
     let task = tokio::spawn(async move {
@@ -90,6 +117,33 @@ pub async fn start_mcp_server(
     })
 }
 
+/// Audit-log name of the client served by [`serve_stdio`].
+pub const STDIO_CLIENT_NAME: &str = "stdio client";
+
+/// Serve the MCP tools and resources over stdin/stdout until the client
+/// disconnects. The client launched this process, so it reaches every
+/// connection MCP clients may use. With no desktop app to approve them,
+/// write tools are refused.
+pub async fn serve_stdio(
+    sqlite_pool: SqlitePool,
+    pool_manager: Arc<PoolManager>,
+) -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
+    let server = McpServer {
+        scope: McpScope {
+            token_name: STDIO_CLIENT_NAME.to_string(),
+            ..McpScope::unrestricted()
+        },
+        ..McpServer::new(
+            sqlite_pool,
+            pool_manager,
+            Arc::new(ApprovalBroker::headless()),
+        )
+    };
+    let service = server.serve(rmcp::transport::stdio()).await?;
+    service.waiting().await?;
+    Ok(())
+}
+
 async fn bind_with_retry(
     start_port: u16,
     max_attempts: u16,
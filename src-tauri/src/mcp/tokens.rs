@@ -0,0 +1,346 @@
+//! Named MCP tokens stored in the local store.
+//!
+//! Besides the desktop app's own token, which reaches every connection, the
+//! user can hand out named tokens that only reach an allowlist of connections,
+//! may be limited to reads and may expire. Only a SHA-256 hash of each token is
+//! stored; the token itself is returned once, when it is created.
+
+use std::collections::HashSet;
+
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use sqlx::{FromRow, SqlitePool};
+
+use super::control::generate_token;
+use super::permissions::{McpPermission, McpScope};
+
+/// Characters of a token kept in clear so the user can tell tokens apart.
+const TOKEN_PREFIX_CHARS: usize = 8;
+
+/// `last_used_at` is refreshed at most this often, not on every request.
+const LAST_USED_RESOLUTION_SECS: i64 = 60;
+
+#[derive(Debug, Clone, Serialize, FromRow)]
+pub struct McpToken {
+    pub id: i64,
+    pub name: String,
+    pub token_prefix: String,
+    pub permission: String,
+    /// Loaded from `mcp_token_connections`; not a column on `mcp_tokens`.
+    #[sqlx(skip)]
+    pub connection_uuids: Vec<String>,
+    pub expires_at: Option<String>,
+    pub last_used_at: Option<String>,
+    pub created_at: String,
+    pub updated_at: String,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct McpTokenInput {
+    pub name: String,
+    #[serde(default = "default_permission")]
+    pub permission: String,
+    /// Connections the token may use. An empty list reaches none.
+    #[serde(default)]
+    pub connection_uuids: Vec<String>,
+    /// RFC 3339 timestamp after which the token is rejected.
+    #[serde(default)]
+    pub expires_at: Option<String>,
+}
+
+fn default_permission() -> String {
+    "read".to_string()
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct CreatedMcpToken {
+    #[serde(flatten)]
+    pub token: McpToken,
+    /// The bearer token; not retrievable later.
+    pub secret: String,
+}
+
+struct ValidatedInput {
+    name: String,
+    permission: McpPermission,
+    connection_uuids: Vec<String>,
+    expires_at: Option<String>,
+}
+
+async fn validate(pool: &SqlitePool, input: &McpTokenInput) -> Result<ValidatedInput, String> {
+    let name = input.name.trim();
+    if name.is_empty() {
+        return Err("MCP token name cannot be empty".to_string());
+    }
+
+    let permission = McpPermission::parse(&input.permission)?;
+    if permission == McpPermission::None {
+        return Err("MCP tokens grant read or write access".to_string());
+    }
+
+    let mut seen = HashSet::new();
+    let mut connection_uuids = Vec::new();
+    for uuid in &input.connection_uuids {
+        let uuid = uuid.trim();
+        if uuid.is_empty() || !seen.insert(uuid.to_string()) {
+            continue;
+        }
+        let exists: Option<(i64,)> = sqlx::query_as("SELECT id FROM connections WHERE uuid = ?")
+            .bind(uuid)
+            .fetch_optional(pool)
+            .await
+            .map_err(|e| e.to_string())?;
+        if exists.is_none() {
+            return Err(format!("Connection not found: {uuid}"));
+        }
+        connection_uuids.push(uuid.to_string());
+    }
+
+    let expires_at = input
+        .expires_at
+        .as_deref()
+        .map(str::trim)
+        .filter(|value| !value.is_empty())
+        .map(sqlite_timestamp)
+        .transpose()
+        .map_err(|e| format!("Invalid token expiry: {e}"))?;
+
+    Ok(ValidatedInput {
+        name: name.to_string(),
+        permission,
+        connection_uuids,
+        expires_at,
+    })
+}
+
+/// Convert an RFC 3339 timestamp to SQLite's `datetime('now')` format (UTC),
+/// so it compares correctly with the timestamps SQLite writes.
+pub(crate) fn sqlite_timestamp(value: &str) -> Result<String, String> {
+    chrono::DateTime::parse_from_rfc3339(value)
+        .map(|time| {
+            time.with_timezone(&chrono::Utc)
+                .format("%Y-%m-%d %H:%M:%S")
+                .to_string()
+        })
+        .map_err(|e| format!("'{value}' is not an RFC 3339 timestamp: {e}"))
+}
+
+fn hash_token(token: &str) -> String {
+    hex::encode(Sha256::digest(token.as_bytes()))
+}
+
+async fn load_connection_uuids(pool: &SqlitePool, token_id: i64) -> Result<Vec<String>, String> {
+    let rows: Vec<(String,)> = sqlx::query_as(
+        "SELECT connection_uuid FROM mcp_token_connections WHERE token_id = ? ORDER BY connection_uuid",
+    )
+    .bind(token_id)
+    .fetch_all(pool)
+    .await
+    .map_err(|e| e.to_string())?;
+    Ok(rows.into_iter().map(|(uuid,)| uuid).collect())
+}
+
+async fn get(pool: &SqlitePool, id: i64) -> Result<McpToken, String> {
+    let mut token: McpToken = sqlx::query_as("SELECT * FROM mcp_tokens WHERE id = ?")
+        .bind(id)
+        .fetch_optional(pool)
+        .await
+        .map_err(|e| e.to_string())?
+        .ok_or_else(|| format!("MCP token not found: {id}"))?;
+    token.connection_uuids = load_connection_uuids(pool, id).await?;
+    Ok(token)
+}
+
+pub async fn list(pool: &SqlitePool) -> Result<Vec<McpToken>, String> {
+    let mut tokens: Vec<McpToken> =
+        sqlx::query_as("SELECT * FROM mcp_tokens ORDER BY name COLLATE NOCASE, id")
+            .fetch_all(pool)
+            .await
+            .map_err(|e| e.to_string())?;
+    for token in &mut tokens {
+        token.connection_uuids = load_connection_uuids(pool, token.id).await?;
+    }
+    Ok(tokens)
+}
+
+pub async fn create(pool: &SqlitePool, input: &McpTokenInput) -> Result<CreatedMcpToken, String> {
+    let input = validate(pool, input).await?;
+    let secret = generate_token();
+
+    let mut transaction = pool.begin().await.map_err(|e| e.to_string())?;
+    let id = sqlx::query(
+        "INSERT INTO mcp_tokens (name, token_hash, token_prefix, permission, expires_at) VALUES (?, ?, ?, ?, ?)",
+    )
+    .bind(&input.name)
+    .bind(hash_token(&secret))
+    .bind(&secret[..TOKEN_PREFIX_CHARS])
+    .bind(input.permission.as_str())
+    .bind(&input.expires_at)
+    .execute(&mut *transaction)
+    .await
+    .map_err(|e| e.to_string())?
+    .last_insert_rowid();
+    replace_connections(&mut transaction, id, &input.connection_uuids).await?;
+    transaction.commit().await.map_err(|e| e.to_string())?;
+
+    Ok(CreatedMcpToken {
+        token: get(pool, id).await?,
+        secret,
+    })
+}
+
+/// Change a token's name, scope or expiry. The token itself stays the same.
+pub async fn update(pool: &SqlitePool, id: i64, input: &McpTokenInput) -> Result<McpToken, String> {
+    let input = validate(pool, input).await?;
+
+    let mut transaction = pool.begin().await.map_err(|e| e.to_string())?;
+    let updated = sqlx::query(
+        "UPDATE mcp_tokens SET name = ?, permission = ?, expires_at = ?, updated_at = datetime('now') WHERE id = ?",
+    )
+    .bind(&input.name)
+    .bind(input.permission.as_str())
+    .bind(&input.expires_at)
+    .bind(id)
+    .execute(&mut *transaction)
+    .await
+    .map_err(|e| e.to_string())?;
+    if updated.rows_affected() == 0 {
+        return Err(format!("MCP token not found: {id}"));
+    }
+    replace_connections(&mut transaction, id, &input.connection_uuids).await?;
+    transaction.commit().await.map_err(|e| e.to_string())?;
+
+    get(pool, id).await
+}
+
+async fn replace_connections(
+    transaction: &mut sqlx::Transaction<'_, sqlx::Sqlite>,
+    token_id: i64,
+    connection_uuids: &[String],
+) -> Result<(), String> {
+    sqlx::query("DELETE FROM mcp_token_connections WHERE token_id = ?")
+        .bind(token_id)
+        .execute(&mut **transaction)
+        .await
+        .map_err(|e| e.to_string())?;
+    for uuid in connection_uuids {
+        sqlx::query("INSERT INTO mcp_token_connections (token_id, connection_uuid) VALUES (?, ?)")
+            .bind(token_id)
+            .bind(uuid)
+            .execute(&mut **transaction)
+            .await
+            .map_err(|e| e.to_string())?;
+    }
+    Ok(())
+}
+
+pub async fn delete(pool: &SqlitePool, id: i64) -> Result<(), String> {
+    sqlx::query("DELETE FROM mcp_tokens WHERE id = ?")
+        .bind(id)
+        .execute(pool)
+        .await
+        .map(|_| ())
+        .map_err(|e| e.to_string())
+}
+
+/// The scope of a named token, recording that it was used. `None` when the
+/// token is unknown or has expired.
+pub async fn resolve(pool: &SqlitePool, secret: &str) -> Result<Option<McpScope>, String> {
+    let token: Option<(i64, String, String)> = sqlx::query_as(
+        "SELECT id, name, permission FROM mcp_tokens WHERE token_hash = ? AND (expires_at IS NULL OR expires_at > datetime('now'))",
+    )
+    .bind(hash_token(secret))
+    .fetch_optional(pool)
+    .await
+    .map_err(|e| e.to_string())?;
+    let Some((id, name, permission)) = token else {
+        return Ok(None);
+    };
+
+    sqlx::query(
+        "UPDATE mcp_tokens SET last_used_at = datetime('now') WHERE id = ? AND (last_used_at IS NULL OR last_used_at <= datetime('now', ?))",
+    )
+    .bind(id)
+    .bind(format!("-{LAST_USED_RESOLUTION_SECS} seconds"))
+    .execute(pool)
+    .await
+    .map_err(|e| e.to_string())?;
+
+    Ok(Some(McpScope {
+        token_id: Some(id),
+        token_name: name,
+        connections: Some(load_connection_uuids(pool, id).await?.into_iter().collect()),
+        permission: McpPermission::parse(&permission).unwrap_or(McpPermission::Read),
+    }))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{create, delete, list, resolve, update, McpTokenInput};
+    use crate::mcp::permissions::McpPermission;
+    use sqlx::sqlite::SqlitePoolOptions;
+    use sqlx::SqlitePool;
+
+    async fn store() -> SqlitePool {
+        let pool = SqlitePoolOptions::new()
+            .max_connections(1)
+            .connect("sqlite::memory:")
+            .await
+            .unwrap();
+        sqlx::migrate!("./migrations").run(&pool).await.unwrap();
+        for uuid in ["dev", "prod"] {
+            sqlx::query("INSERT INTO connections (uuid, name, host, port, database, username, password) VALUES (?, ?, '', 0, '', '', '')")
+                .bind(uuid)
+                .bind(uuid)
+                .execute(&pool)
+                .await
+                .unwrap();
+        }
+        pool
+    }
+
+    fn input(connection_uuids: &[&str], expires_at: Option<&str>) -> McpTokenInput {
+        McpTokenInput {
+            name: " Reporting agent ".to_string(),
+            permission: "read".to_string(),
+            connection_uuids: connection_uuids
+                .iter()
+                .map(|uuid| uuid.to_string())
+                .collect(),
+            expires_at: expires_at.map(str::to_string),
+        }
+    }
+
+    #[tokio::test]
+    async fn resolves_tokens_to_their_scope_until_they_expire() {
+        let pool = store().await;
+        let created = create(&pool, &input(&["dev", "dev"], None)).await.unwrap();
+        assert_eq!(created.token.name, "Reporting agent");
+        assert_eq!(created.token.connection_uuids, ["dev"]);
+        assert!(created.secret.starts_with(&created.token.token_prefix));
+
+        let scope = resolve(&pool, &created.secret).await.unwrap().unwrap();
+        assert!(scope.includes("dev") && !scope.includes("prod"));
+        assert_eq!(scope.permission, McpPermission::Read);
+        assert!(list(&pool).await.unwrap()[0].last_used_at.is_some());
+        assert_eq!(resolve(&pool, "not-a-token").await.unwrap(), None);
+
+        let expired = input(&["dev", "prod"], Some("2000-01-01T00:00:00Z"));
+        update(&pool, created.token.id, &expired).await.unwrap();
+        assert_eq!(resolve(&pool, &created.secret).await.unwrap(), None);
+
+        delete(&pool, created.token.id).await.unwrap();
+        assert!(list(&pool).await.unwrap().is_empty());
+    }
+
+    #[tokio::test]
+    async fn rejects_unknown_connections_and_levels() {
+        let pool = store().await;
+        assert!(create(&pool, &input(&["staging"], None)).await.is_err());
+        assert!(create(&pool, &input(&[], Some("tomorrow"))).await.is_err());
+
+        let mut none = input(&["dev"], None);
+        none.permission = "none".to_string();
+        assert!(create(&pool, &none).await.is_err());
+    }
+}
@@ -1,10 +1,37 @@
+use super::approval::ApprovalRequest;
+use super::audit::{self, AuditKind};
+use super::permissions::McpPermission;
+use super::saved;
+use super::schema_search::search_schema;
 use super::McpServer;
+use crate::commands::queries::{load_saved_queries, load_saved_query};
+use crate::commands::redis_scripts::{load_redis_script, load_redis_scripts};
+use crate::commands::saved_views::{load_saved_view, load_saved_views};
+use crate::database::key_value::key_value_browser;
+use crate::database::mutation::{
+    build_delete, build_insert, build_update, MutationPlan, MutationValue,
+};
+use crate::database::redis_scripts::{bind_parameters, script_hash, script_runner, ScriptCall};
+use crate::database::registry::find_driver;
+use crate::database::sql_policy::ensure_structured_mutations_supported;
+use crate::database::{DatabaseType, DriverError, QueryLimits};
+use crate::db::models::{
+    FilterExpression, QueryResult, SavedViewStatePayload, TableDataResponse, TableFilter,
+};
 use rmcp::model::*;
 use rmcp::ErrorData as McpError;
-use serde_json::{json, Value};
+use serde_json::{json, Map, Value};
 
 const MAX_ROWS: usize = 1000;
-const QUERY_TIMEOUT: std::time::Duration = std::time::Duration::from_secs(30);
+const DEFAULT_SAMPLE_ROWS: u64 = 20;
+const MAX_SAMPLE_ROWS: u64 = 100;
+const DEFAULT_VIEW_ROWS: u64 = 100;
+const DEFAULT_SEARCH_RESULTS: u64 = 25;
+const MAX_SEARCH_RESULTS: u64 = 200;
+const DEFAULT_REDIS_SCAN_KEYS: u64 = 100;
+/// Used when the connection has no query timeout of its own, so an MCP client
+/// never waits on an unbounded query.
+const DEFAULT_QUERY_TIMEOUT: std::time::Duration = std::time::Duration::from_secs(30);
 
 /// Build an object JSON schema from its properties and required keys.
 fn object_schema(properties: Value, required: Value) -> Value {
@@ -27,6 +54,38 @@ fn read_only_annotations() -> ToolAnnotations {
         .idempotent(true)
 }
 
+/// Annotations for a tool that changes data; each call needs the user's approval.
+fn write_annotations() -> ToolAnnotations {
+    ToolAnnotations::new()
+        .read_only(false)
+        .destructive(true)
+        .idempotent(false)
+}
+
+/// Schema for the row tools: a table plus the given extra properties.
+fn row_schema(extra: Value, extra_required: &[&str]) -> Value {
+    let mut properties = json!({
+        "connection_uuid": {
+            "type": "string",
+            "description": "UUID of a connection that allows writes"
+        },
+        "schema": {
+            "type": "string",
+            "description": "Schema name (e.g. 'public' for PostgreSQL)"
+        },
+        "table": {
+            "type": "string",
+            "description": "Table name"
+        }
+    });
+    if let (Some(properties), Value::Object(extra)) = (properties.as_object_mut(), extra) {
+        properties.extend(extra);
+    }
+    let mut required = vec!["connection_uuid", "schema", "table"];
+    required.extend_from_slice(extra_required);
+    object_schema(properties, json!(required))
+}
+
 /// Return the list of all tool definitions.
 pub fn tool_definitions() -> Vec<Tool> {
     vec![
@@ -117,6 +176,329 @@ pub fn tool_definitions() -> Vec<Tool> {
                 .destructive(false)
                 .idempotent(false),
         ),
+        Tool::new(
+            "sample_table",
+            "Fetch a few rows from a table, optionally filtered and sorted, without writing SQL",
+            object(object_schema(
+                json!({
+                    "connection_uuid": {
+                        "type": "string",
+                        "description": "UUID of the connected database"
+                    },
+                    "schema": {
+                        "type": "string",
+                        "description": "Schema name (e.g. 'public' for PostgreSQL)"
+                    },
+                    "table": {
+                        "type": "string",
+                        "description": "Table name"
+                    },
+                    "limit": {
+                        "type": "integer",
+                        "description": format!("Rows to return (default {DEFAULT_SAMPLE_ROWS}, at most {MAX_SAMPLE_ROWS})")
+                    },
+                    "filter": {
+                        "type": "object",
+                        "description": "Structured filter: {\"conjunction\": \"and\"|\"or\", \"conditions\": [{\"column\", \"operator\", \"value\"}]}. Operators: equals, not_equals, contains, starts_with, ends_with, greater_than, greater_than_or_equal, less_than, less_than_or_equal, in, is_null, is_not_null"
+                    },
+                    "sort_column": {
+                        "type": "string",
+                        "description": "Column to sort by"
+                    },
+                    "sort_direction": {
+                        "type": "string",
+                        "enum": ["asc", "desc"]
+                    }
+                }),
+                json!(["connection_uuid", "schema", "table"]),
+            )),
+        )
+        .with_annotations(read_only_annotations()),
+        Tool::new(
+            "list_saved_queries",
+            "List the saved queries and saved table views of a connection. Prefer these reviewed queries over writing new SQL when one answers the question.",
+            object(connection_uuid_schema("UUID of the saved connection")),
+        )
+        .with_annotations(read_only_annotations()),
+        Tool::new(
+            "run_saved_query",
+            "Run a saved query read-only",
+            object(object_schema(
+                json!({
+                    "connection_uuid": {
+                        "type": "string",
+                        "description": "UUID of the saved connection"
+                    },
+                    "saved_query_id": {
+                        "type": "integer",
+                        "description": "ID from list_saved_queries"
+                    }
+                }),
+                json!(["connection_uuid", "saved_query_id"]),
+            )),
+        )
+        .with_annotations(read_only_annotations()),
+        Tool::new(
+            "run_saved_view",
+            "Read a page of the table behind a saved view, with the view's filter, sort and hidden columns applied",
+            object(object_schema(
+                json!({
+                    "connection_uuid": {
+                        "type": "string",
+                        "description": "UUID of the saved connection"
+                    },
+                    "saved_view_id": {
+                        "type": "integer",
+                        "description": "ID from list_saved_queries"
+                    },
+                    "page": {
+                        "type": "integer",
+                        "description": "Page to read, starting at 1 (default 1)"
+                    },
+                    "limit": {
+                        "type": "integer",
+                        "description": format!("Rows per page (default {DEFAULT_VIEW_ROWS}, at most {MAX_ROWS})")
+                    }
+                }),
+                json!(["connection_uuid", "saved_view_id"]),
+            )),
+        )
+        .with_annotations(read_only_annotations()),
+        Tool::new(
+            "explain_query",
+            "Show the database's execution plan for a query without running it",
+            object(object_schema(
+                json!({
+                    "connection_uuid": {
+                        "type": "string",
+                        "description": "UUID of the connected database"
+                    },
+                    "query": {
+                        "type": "string",
+                        "description": "SQL query to explain"
+                    }
+                }),
+                json!(["connection_uuid", "query"]),
+            )),
+        )
+        .with_annotations(read_only_annotations()),
+        Tool::new(
+            "search_schema",
+            "Fuzzy-search table, column and function names. Every word must match, so 'orders email' finds the email column of orders. Cheaper than get_schema_overview when looking for something specific.",
+            object(object_schema(
+                json!({
+                    "connection_uuid": {
+                        "type": "string",
+                        "description": "UUID of the connected database"
+                    },
+                    "query": {
+                        "type": "string",
+                        "description": "Words to look for in table, column and function names"
+                    },
+                    "limit": {
+                        "type": "integer",
+                        "description": format!("Matches to return (default {DEFAULT_SEARCH_RESULTS}, at most {MAX_SEARCH_RESULTS})")
+                    }
+                }),
+                json!(["connection_uuid", "query"]),
+            )),
+        )
+        .with_annotations(read_only_annotations()),
+        Tool::new(
+            "get_function_definition",
+            "Get the source of a database function. Use the schema, name and identity_args reported by get_schema_overview or search_schema.",
+            object(object_schema(
+                json!({
+                    "connection_uuid": {
+                        "type": "string",
+                        "description": "UUID of the connected database"
+                    },
+                    "schema": {
+                        "type": "string",
+                        "description": "Schema of the function"
+                    },
+                    "name": {
+                        "type": "string",
+                        "description": "Function name"
+                    },
+                    "identity_args": {
+                        "type": "string",
+                        "description": "Argument types identifying an overload (empty for none)"
+                    }
+                }),
+                json!(["connection_uuid", "schema", "name"]),
+            )),
+        )
+        .with_annotations(read_only_annotations()),
+        Tool::new(
+            "redis_scan_keys",
+            "List Redis keys matching a glob pattern with their types and TTLs. Continue a scan by passing the returned cursor.",
+            object(object_schema(
+                json!({
+                    "connection_uuid": {
+                        "type": "string",
+                        "description": "UUID of a connected Redis database"
+                    },
+                    "pattern": {
+                        "type": "string",
+                        "description": "Glob pattern such as 'session:*' (default '*')"
+                    },
+                    "limit": {
+                        "type": "integer",
+                        "description": format!("Keys to return (default {DEFAULT_REDIS_SCAN_KEYS}, at most {MAX_ROWS})")
+                    },
+                    "cursor": {
+                        "type": "integer",
+                        "description": "Cursor returned by a previous call (default 0)"
+                    }
+                }),
+                json!(["connection_uuid"]),
+            )),
+        )
+        .with_annotations(
+            ToolAnnotations::new()
+                .read_only(true)
+                .destructive(false)
+                .idempotent(false),
+        ),
+        Tool::new(
+            "redis_get_key",
+            "Get a Redis key's type, TTL, encoding and value",
+            object(object_schema(
+                json!({
+                    "connection_uuid": {
+                        "type": "string",
+                        "description": "UUID of a connected Redis database"
+                    },
+                    "key": {
+                        "type": "string",
+                        "description": "Key name"
+                    }
+                }),
+                json!(["connection_uuid", "key"]),
+            )),
+        )
+        .with_annotations(read_only_annotations()),
+        Tool::new(
+            "redis_list_scripts",
+            "List the Lua scripts saved for a Redis connection with their KEYS/ARGV parameters. Prefer these reviewed scripts over EVAL_RO with new code.",
+            object(connection_uuid_schema("UUID of the saved Redis connection")),
+        )
+        .with_annotations(read_only_annotations()),
+        Tool::new(
+            "redis_run_script",
+            "Run a saved Lua script with EVALSHA_RO. Scripts that write are rejected by the server.",
+            object(object_schema(
+                json!({
+                    "connection_uuid": {
+                        "type": "string",
+                        "description": "UUID of a connected Redis database"
+                    },
+                    "script_id": {
+                        "type": "integer",
+                        "description": "ID from redis_list_scripts"
+                    },
+                    "inputs": {
+                        "type": "object",
+                        "description": "Values for the script's parameters, by name"
+                    }
+                }),
+                json!(["connection_uuid", "script_id"]),
+            )),
+        )
+        .with_annotations(read_only_annotations()),
+        Tool::new(
+            "redis_call_function",
+            "Call a Redis function with FCALL_RO. Only functions declared with the no-writes flag can run.",
+            object(object_schema(
+                json!({
+                    "connection_uuid": {
+                        "type": "string",
+                        "description": "UUID of a connected Redis database"
+                    },
+                    "function": {
+                        "type": "string",
+                        "description": "Function name"
+                    },
+                    "keys": {
+                        "type": "array",
+                        "items": { "type": "string" },
+                        "description": "Key names passed as KEYS"
+                    },
+                    "args": {
+                        "type": "array",
+                        "items": { "type": "string" },
+                        "description": "Arguments passed as ARGV"
+                    }
+                }),
+                json!(["connection_uuid", "function"]),
+            )),
+        )
+        .with_annotations(read_only_annotations()),
+        Tool::new(
+            "execute_statement",
+            "Execute a SQL statement that may modify data. Only for connections that allow writes; the user must approve the statement in DBcooper before it runs.",
+            object(object_schema(
+                json!({
+                    "connection_uuid": {
+                        "type": "string",
+                        "description": "UUID of a connection that allows writes"
+                    },
+                    "sql": {
+                        "type": "string",
+                        "description": "SQL statement to execute"
+                    }
+                }),
+                json!(["connection_uuid", "sql"]),
+            )),
+        )
+        .with_annotations(write_annotations()),
+        Tool::new(
+            "insert_row",
+            "Insert one row into a table. The user must approve the generated statement in DBcooper before it runs.",
+            object(row_schema(
+                json!({
+                    "values": {
+                        "type": "object",
+                        "description": "Column names mapped to the values to insert"
+                    }
+                }),
+                &["values"],
+            )),
+        )
+        .with_annotations(write_annotations()),
+        Tool::new(
+            "update_row",
+            "Update one row, identified by its primary key. The user must approve the generated statement in DBcooper before it runs.",
+            object(row_schema(
+                json!({
+                    "primary_key": {
+                        "type": "object",
+                        "description": "Primary key column names mapped to the row's current values"
+                    },
+                    "values": {
+                        "type": "object",
+                        "description": "Column names mapped to their new values"
+                    }
+                }),
+                &["primary_key", "values"],
+            )),
+        )
+        .with_annotations(write_annotations()),
+        Tool::new(
+            "delete_row",
+            "Delete one row, identified by its primary key. The user must approve the generated statement in DBcooper before it runs.",
+            object(row_schema(
+                json!({
+                    "primary_key": {
+                        "type": "object",
+                        "description": "Primary key column names mapped to the row's values"
+                    }
+                }),
+                &["primary_key"],
+            )),
+        )
+        .with_annotations(write_annotations()),
     ]
 }
 
@@ -132,10 +514,124 @@ fn get_str_param<'a>(
         })
 }
 
-/// Dispatch a tool call to the appropriate handler.
+fn get_optional_str_param<'a>(
+    args: &'a Option<serde_json::Map<String, Value>>,
+    key: &str,
+) -> Result<Option<&'a str>, McpError> {
+    match args.as_ref().and_then(|m| m.get(key)) {
+        None | Some(Value::Null) => Ok(None),
+        Some(Value::String(value)) => Ok(Some(value)),
+        Some(_) => Err(McpError::invalid_params(
+            format!("Parameter {} must be a string", key),
+            None,
+        )),
+    }
+}
+
+fn get_optional_u64_param(
+    args: &Option<serde_json::Map<String, Value>>,
+    key: &str,
+) -> Result<Option<u64>, McpError> {
+    match args.as_ref().and_then(|m| m.get(key)) {
+        None | Some(Value::Null) => Ok(None),
+        Some(value) => value.as_u64().map(Some).ok_or_else(|| {
+            McpError::invalid_params(
+                format!("Parameter {} must be a non-negative integer", key),
+                None,
+            )
+        }),
+    }
+}
+
+fn get_optional_strings_param(
+    args: &Option<serde_json::Map<String, Value>>,
+    key: &str,
+) -> Result<Vec<String>, McpError> {
+    match args.as_ref().and_then(|m| m.get(key)) {
+        None | Some(Value::Null) => Ok(Vec::new()),
+        Some(value) => serde_json::from_value(value.clone()).map_err(|_| {
+            McpError::invalid_params(
+                format!("Parameter {} must be an array of strings", key),
+                None,
+            )
+        }),
+    }
+}
+
+fn get_id_param(args: &Option<serde_json::Map<String, Value>>, key: &str) -> Result<i64, McpError> {
+    args.as_ref()
+        .and_then(|m| m.get(key))
+        .and_then(Value::as_i64)
+        .ok_or_else(|| {
+            McpError::invalid_params(format!("Missing required integer parameter: {}", key), None)
+        })
+}
+
+fn get_object_param<'a>(
+    args: &'a Option<serde_json::Map<String, Value>>,
+    key: &str,
+) -> Result<&'a Map<String, Value>, McpError> {
+    args.as_ref()
+        .and_then(|m| m.get(key))
+        .and_then(|v| v.as_object())
+        .ok_or_else(|| {
+            McpError::invalid_params(format!("Missing required object parameter: {}", key), None)
+        })
+}
+
+fn tool_error(message: impl Into<String>) -> CallToolResult {
+    CallToolResult::error(vec![Content::text(message.into())])
+}
+
+/// Dispatch a tool call to the appropriate handler and record it in the
+/// audit log.
 pub async fn dispatch_tool(
     server: &McpServer,
     request: CallToolRequestParams,
+) -> Result<CallToolResult, McpError> {
+    let started = std::time::Instant::now();
+    let argument = |key: &str| {
+        request
+            .arguments
+            .as_ref()
+            .and_then(|args| args.get(key))
+            .and_then(Value::as_str)
+            .map(str::to_string)
+    };
+    let connection_uuid = argument("connection_uuid");
+    if let Some(sql) = argument("query").or_else(|| argument("sql")) {
+        server.audit.record_query(&sql);
+    }
+    let name = request.name.to_string();
+
+    let result = run_tool(server, request).await;
+    let error = match &result {
+        Ok(result) if result.is_error == Some(true) => Some(
+            result
+                .content
+                .iter()
+                .find_map(|content| content.as_text())
+                .map(|text| text.text.clone())
+                .unwrap_or_default(),
+        ),
+        Ok(_) => None,
+        Err(e) => Some(e.message.to_string()),
+    };
+    audit::record(
+        server,
+        AuditKind::Tool,
+        &name,
+        connection_uuid.as_deref(),
+        started,
+        error,
+    )
+    .await;
+    result
+}
+
+async fn run_tool(
+    server: &McpServer,
+    request: CallToolRequestParams,
 ) -> Result<CallToolResult, McpError> {
     match request.name.as_ref() {
         "list_connections" => list_connections(server).await,
@@ -166,16 +662,140 @@ pub async fn dispatch_tool(
             let query = get_str_param(&request.arguments, "query")?;
             execute_query(server, uuid, query).await
         }
+        "sample_table" => {
+            let uuid = get_str_param(&request.arguments, "connection_uuid")?;
+            let schema = get_str_param(&request.arguments, "schema")?;
+            let table = get_str_param(&request.arguments, "table")?;
+            let sample = TablePage {
+                page: 1,
+                limit: get_optional_u64_param(&request.arguments, "limit")?
+                    .unwrap_or(DEFAULT_SAMPLE_ROWS)
+                    .clamp(1, MAX_SAMPLE_ROWS),
+                filter: match request.arguments.as_ref().and_then(|m| m.get("filter")) {
+                    None | Some(Value::Null) => None,
+                    Some(filter) => Some(TableFilter::Structured(
+                        serde_json::from_value::<FilterExpression>(filter.clone()).map_err(
+                            |e| McpError::invalid_params(format!("Invalid filter: {}", e), None),
+                        )?,
+                    )),
+                },
+                sort_column: get_optional_str_param(&request.arguments, "sort_column")?
+                    .map(str::to_string),
+                sort_direction: get_optional_str_param(&request.arguments, "sort_direction")?
+                    .map(str::to_string),
+            };
+            sample_table(server, uuid, schema, table, sample).await
+        }
+        "list_saved_queries" => {
+            let uuid = get_str_param(&request.arguments, "connection_uuid")?;
+            list_saved_queries(server, uuid).await
+        }
+        "run_saved_query" => {
+            let uuid = get_str_param(&request.arguments, "connection_uuid")?;
+            let id = get_id_param(&request.arguments, "saved_query_id")?;
+            run_saved_query(server, uuid, id).await
+        }
+        "run_saved_view" => {
+            let uuid = get_str_param(&request.arguments, "connection_uuid")?;
+            let id = get_id_param(&request.arguments, "saved_view_id")?;
+            let page = get_optional_u64_param(&request.arguments, "page")?
+                .unwrap_or(1)
+                .max(1);
+            let limit = get_optional_u64_param(&request.arguments, "limit")?
+                .unwrap_or(DEFAULT_VIEW_ROWS)
+                .clamp(1, MAX_ROWS as u64);
+            run_saved_view(server, uuid, id, page as i64, limit).await
+        }
+        "explain_query" => {
+            let uuid = get_str_param(&request.arguments, "connection_uuid")?;
+            let query = get_str_param(&request.arguments, "query")?;
+            explain_query(server, uuid, query).await
+        }
+        "search_schema" => {
+            let uuid = get_str_param(&request.arguments, "connection_uuid")?;
+            let query = get_str_param(&request.arguments, "query")?;
+            let limit = get_optional_u64_param(&request.arguments, "limit")?
+                .unwrap_or(DEFAULT_SEARCH_RESULTS)
+                .clamp(1, MAX_SEARCH_RESULTS);
+            search_schema_tool(server, uuid, query, limit as usize).await
+        }
+        "get_function_definition" => {
+            let uuid = get_str_param(&request.arguments, "connection_uuid")?;
+            let schema = get_str_param(&request.arguments, "schema")?;
+            let name = get_str_param(&request.arguments, "name")?;
+            let identity_args =
+                get_optional_str_param(&request.arguments, "identity_args")?.unwrap_or("");
+            get_function_definition(server, uuid, schema, name, identity_args).await
+        }
+        "redis_scan_keys" => {
+            let uuid = get_str_param(&request.arguments, "connection_uuid")?;
+            let pattern = get_optional_str_param(&request.arguments, "pattern")?.unwrap_or("*");
+            let limit = get_optional_u64_param(&request.arguments, "limit")?
+                .unwrap_or(DEFAULT_REDIS_SCAN_KEYS)
+                .clamp(1, MAX_ROWS as u64);
+            let cursor = get_optional_u64_param(&request.arguments, "cursor")?.unwrap_or(0);
+            redis_scan_keys(server, uuid, pattern, limit as i64, cursor).await
+        }
+        "redis_get_key" => {
+            let uuid = get_str_param(&request.arguments, "connection_uuid")?;
+            let key = get_str_param(&request.arguments, "key")?;
+            redis_get_key(server, uuid, key).await
+        }
+        "redis_list_scripts" => {
+            let uuid = get_str_param(&request.arguments, "connection_uuid")?;
+            redis_list_scripts(server, uuid).await
+        }
+        "redis_run_script" => {
+            let uuid = get_str_param(&request.arguments, "connection_uuid")?;
+            let id = get_id_param(&request.arguments, "script_id")?;
+            let inputs = match request.arguments.as_ref().and_then(|m| m.get("inputs")) {
+                None | Some(Value::Null) => Map::new(),
+                Some(Value::Object(inputs)) => inputs.clone(),
+                Some(_) => {
+                    return Err(McpError::invalid_params(
+                        "Parameter inputs must be an object",
+                        None,
+                    ))
+                }
+            };
+            redis_run_script(server, uuid, id, &inputs).await
+        }
+        "redis_call_function" => {
+            let uuid = get_str_param(&request.arguments, "connection_uuid")?;
+            let function = get_str_param(&request.arguments, "function")?;
+            let call = ScriptCall {
+                keys: get_optional_strings_param(&request.arguments, "keys")?,
+                args: get_optional_strings_param(&request.arguments, "args")?,
+            };
+            redis_call_function(server, uuid, function, &call).await
+        }
+        "execute_statement" => {
+            let uuid = get_str_param(&request.arguments, "connection_uuid")?;
+            let sql = get_str_param(&request.arguments, "sql")?;
+            execute_statement(server, uuid, sql).await
+        }
+        "insert_row" | "update_row" | "delete_row" => {
+            let uuid = get_str_param(&request.arguments, "connection_uuid")?;
+            let schema = get_str_param(&request.arguments, "schema")?;
+            let table = get_str_param(&request.arguments, "table")?;
+            let row = RowChange {
+                primary_key: match request.name.as_ref() {
+                    "insert_row" => None,
+                    _ => Some(get_object_param(&request.arguments, "primary_key")?),
+                },
+                values: match request.name.as_ref() {
+                    "delete_row" => None,
+                    _ => Some(get_object_param(&request.arguments, "values")?),
+                },
+            };
+            change_row(server, &request.name, uuid, schema, table, row).await
+        }
         _ => Err(McpError::method_not_found::<CallToolRequestMethod>()),
     }
 }
 
 async fn list_connections(server: &McpServer) -> Result<CallToolResult, McpError> {
-    let connections: Vec<crate::db::models::Connection> =
-        sqlx::query_as("SELECT * FROM connections ORDER BY id DESC")
-            .fetch_all(&server.sqlite_pool)
-            .await
-            .map_err(|e| McpError::internal_error(format!("Database error: {}", e), None))?;
+    let connections = server.visible_connections().await?;
 
     let safe: Vec<Value> = connections
         .into_iter()
@@ -189,6 +809,10 @@ async fn list_connections(server: &McpServer) -> Result<CallToolResult, McpError
                 "database": c.database,
                 "ssl": c.ssl == 1,
                 "ssh_enabled": c.ssh_enabled == 1,
+                "mcp_permission": server.scope.permission_for(&c).as_str(),
+                "capabilities": find_driver(&c.db_type)
+                    .ok()
+                    .map(|driver| driver.capabilities),
             })
         })
         .collect();
@@ -198,6 +822,7 @@ async fn list_connections(server: &McpServer) -> Result<CallToolResult, McpError
 }
 
 async fn connect(server: &McpServer, uuid: &str) -> Result<CallToolResult, McpError> {
+    server.authorize(uuid, McpPermission::Read).await?;
     match server.ensure_connected(uuid).await {
         Ok(()) => Ok(CallToolResult::success(vec![Content::text(format!(
             "Connected to {} successfully.",
@@ -211,6 +836,7 @@ async fn connect(server: &McpServer, uuid: &str) -> Result<CallToolResult, McpEr
 }
 
 async fn disconnect(server: &McpServer, uuid: &str) -> Result<CallToolResult, McpError> {
+    server.authorize(uuid, McpPermission::Read).await?;
     server.pool_manager.disconnect(uuid).await;
     Ok(CallToolResult::success(vec![Content::text(format!(
         "Disconnected from {}.",
@@ -219,6 +845,7 @@ async fn disconnect(server: &McpServer, uuid: &str) -> Result<CallToolResult, Mc
 }
 
 async fn list_tables(server: &McpServer, uuid: &str) -> Result<CallToolResult, McpError> {
+    server.authorize(uuid, McpPermission::Read).await?;
     server.ensure_connected(uuid).await?;
 
     match server.pool_manager.list_tables(uuid).await {
@@ -239,6 +866,7 @@ async fn describe_table(
     schema: &str,
     table: &str,
 ) -> Result<CallToolResult, McpError> {
+    server.authorize(uuid, McpPermission::Read).await?;
     server.ensure_connected(uuid).await?;
 
     match server
@@ -259,6 +887,7 @@ async fn describe_table(
 }
 
 async fn get_schema_overview(server: &McpServer, uuid: &str) -> Result<CallToolResult, McpError> {
+    server.authorize(uuid, McpPermission::Read).await?;
     server.ensure_connected(uuid).await?;
 
     match server.pool_manager.get_schema_overview(uuid).await {
@@ -278,30 +907,469 @@ async fn execute_query(
     uuid: &str,
     query: &str,
 ) -> Result<CallToolResult, McpError> {
+    server.authorize(uuid, McpPermission::Read).await?;
     server.ensure_connected(uuid).await?;
 
-    // The MCP server is always read-only; enforcement lives in the driver/engine,
-    // not in a string matcher.
-    let result = tokio::time::timeout(
-        QUERY_TIMEOUT,
-        server.pool_manager.execute_query_read_only(uuid, query),
-    )
-    .await;
+    // Read-only enforcement lives in the driver/engine, not in a string matcher.
+    let (limits, enforced_by_driver) = query_limits(server, uuid).await;
+    let result = limits
+        .run(
+            enforced_by_driver,
+            server.pool_manager.execute_query_read_only(uuid, query),
+        )
+        .await;
+    Ok(query_result_content(server, result))
+}
+
+/// Which page of a table to read, and how to filter and sort it.
+pub(super) struct TablePage {
+    pub page: i64,
+    pub limit: u64,
+    pub filter: Option<TableFilter>,
+    pub sort_column: Option<String>,
+    pub sort_direction: Option<String>,
+}
+
+/// Read a page of a table under the connection's query limits.
+pub(super) async fn fetch_table_page(
+    server: &McpServer,
+    uuid: &str,
+    schema: &str,
+    table: &str,
+    page: TablePage,
+) -> Result<Result<TableDataResponse, DriverError>, DriverError> {
+    let (limits, enforced_by_driver) = query_limits(server, uuid).await;
+    limits
+        .run(
+            enforced_by_driver,
+            server.pool_manager.get_table_data(
+                uuid,
+                schema,
+                table,
+                page.page,
+                page.limit as i64,
+                page.filter,
+                page.sort_column,
+                page.sort_direction,
+            ),
+        )
+        .await
+}
+
+fn table_page_content(server: &McpServer, page: TableDataResponse) -> CallToolResult {
+    server.audit.record_rows(page.data.len() as i64);
+    let json = serde_json::to_string_pretty(&json!({
+        "data": page.data,
+        "total": page.total,
+        "page": page.page,
+    }))
+    .unwrap_or_else(|_| "{}".to_string());
+    CallToolResult::success(vec![Content::text(json)])
+}
+
+async fn sample_table(
+    server: &McpServer,
+    uuid: &str,
+    schema: &str,
+    table: &str,
+    sample: TablePage,
+) -> Result<CallToolResult, McpError> {
+    server.authorize(uuid, McpPermission::Read).await?;
+    server.ensure_connected(uuid).await?;
+
+    match fetch_table_page(server, uuid, schema, table, sample).await {
+        Ok(Ok(page)) => Ok(table_page_content(server, page)),
+        Ok(Err(e)) => Ok(tool_error(format!("Failed to sample table: {}", e))),
+        Err(timeout) => Ok(tool_error(timeout)),
+    }
+}
+
+async fn list_saved_queries(server: &McpServer, uuid: &str) -> Result<CallToolResult, McpError> {
+    server.authorize(uuid, McpPermission::Read).await?;
+
+    let queries = load_saved_queries(&server.sqlite_pool, uuid)
+        .await
+        .map_err(|e| McpError::internal_error(e, None))?;
+    let views = load_saved_views(&server.sqlite_pool, uuid)
+        .await
+        .map_err(|e| McpError::internal_error(e, None))?;
+    server
+        .audit
+        .record_rows((queries.len() + views.len()) as i64);
+
+    let json = serde_json::to_string_pretty(&json!({
+        "queries": queries
+            .iter()
+            .map(|query| json!({
+                "id": query.id,
+                "name": query.name,
+                "query": query.query,
+                "uri": saved::saved_query_uri(uuid, query.id),
+            }))
+            .collect::<Vec<_>>(),
+        "views": views
+            .iter()
+            .map(|view| json!({
+                "id": view.id,
+                "name": view.name,
+                "table": view.table_name,
+                "uri": saved::saved_view_uri(uuid, view.id),
+            }))
+            .collect::<Vec<_>>(),
+    }))
+    .unwrap_or_else(|_| "{}".to_string());
+    Ok(CallToolResult::success(vec![Content::text(json)]))
+}
+
+async fn run_saved_query(
+    server: &McpServer,
+    uuid: &str,
+    id: i64,
+) -> Result<CallToolResult, McpError> {
+    server.authorize(uuid, McpPermission::Read).await?;
+    let Some(saved) = load_saved_query(&server.sqlite_pool, uuid, id)
+        .await
+        .map_err(|e| McpError::internal_error(e, None))?
+    else {
+        return Ok(tool_error(format!("Saved query {} not found", id)));
+    };
+    server.audit.record_query(&saved.query);
+
+    execute_query(server, uuid, &saved.query).await
+}
+
+async fn run_saved_view(
+    server: &McpServer,
+    uuid: &str,
+    id: i64,
+    page: i64,
+    limit: u64,
+) -> Result<CallToolResult, McpError> {
+    server.authorize(uuid, McpPermission::Read).await?;
+    let Some(view) = load_saved_view(&server.sqlite_pool, uuid, id)
+        .await
+        .map_err(|e| McpError::internal_error(e, None))?
+    else {
+        return Ok(tool_error(format!("Saved view {} not found", id)));
+    };
+    let SavedViewStatePayload::Current { state } = &view.state else {
+        return Ok(tool_error(format!(
+            "Saved view '{}' was saved by a newer version of DBcooper",
+            view.name
+        )));
+    };
+    server.ensure_connected(uuid).await?;
+
+    let (schema, table) = saved::view_table(&view);
+    let (sort_column, sort_direction) = saved::view_sort(state);
+    let request = TablePage {
+        page,
+        limit,
+        filter: state.filter.clone(),
+        sort_column,
+        sort_direction,
+    };
+    match fetch_table_page(server, uuid, schema, table, request).await {
+        Ok(Ok(mut page)) => {
+            page.data = saved::apply_view_columns(page.data, state);
+            Ok(table_page_content(server, page))
+        }
+        Ok(Err(e)) => Ok(tool_error(format!("Failed to run saved view: {}", e))),
+        Err(timeout) => Ok(tool_error(timeout)),
+    }
+}
+
+async fn explain_query(
+    server: &McpServer,
+    uuid: &str,
+    query: &str,
+) -> Result<CallToolResult, McpError> {
+    let connection = server.authorize(uuid, McpPermission::Read).await?;
+    let statement =
+        match find_driver(&connection.db_type).and_then(|engine| engine.explain_statement(query)) {
+            Ok(statement) => statement,
+            Err(e) => return Ok(tool_error(e)),
+        };
+    server.ensure_connected(uuid).await?;
+    server.audit.record_query(&statement);
+
+    let (limits, enforced_by_driver) = query_limits(server, uuid).await;
+    let result = limits
+        .run(
+            enforced_by_driver,
+            server
+                .pool_manager
+                .execute_query_read_only(uuid, &statement),
+        )
+        .await;
+    Ok(query_result_content(server, result))
+}
+
+async fn search_schema_tool(
+    server: &McpServer,
+    uuid: &str,
+    query: &str,
+    limit: usize,
+) -> Result<CallToolResult, McpError> {
+    server.authorize(uuid, McpPermission::Read).await?;
+    server.ensure_connected(uuid).await?;
+
+    match server.pool_manager.get_schema_overview(uuid).await {
+        Ok(overview) => {
+            let matches = search_schema(&overview, query, limit);
+            server.audit.record_rows(matches.len() as i64);
+            let json = serde_json::to_string_pretty(&matches).unwrap_or_else(|_| "[]".to_string());
+            Ok(CallToolResult::success(vec![Content::text(json)]))
+        }
+        Err(e) => Ok(tool_error(format!("Failed to search schema: {}", e))),
+    }
+}
+
+async fn get_function_definition(
+    server: &McpServer,
+    uuid: &str,
+    schema: &str,
+    name: &str,
+    identity_args: &str,
+) -> Result<CallToolResult, McpError> {
+    server.authorize(uuid, McpPermission::Read).await?;
+    server.ensure_connected(uuid).await?;
 
+    match server
+        .pool_manager
+        .get_function_definition(uuid, schema, name, identity_args)
+        .await
+    {
+        Ok(definition) => {
+            let json =
+                serde_json::to_string_pretty(&definition).unwrap_or_else(|_| "{}".to_string());
+            Ok(CallToolResult::success(vec![Content::text(json)]))
+        }
+        Err(e) => Ok(tool_error(format!(
+            "Failed to get function definition: {}",
+            e
+        ))),
+    }
+}
+
+async fn redis_scan_keys(
+    server: &McpServer,
+    uuid: &str,
+    pattern: &str,
+    limit: i64,
+    cursor: u64,
+) -> Result<CallToolResult, McpError> {
+    server.authorize(uuid, McpPermission::Read).await?;
+    server.ensure_connected(uuid).await?;
+    let driver = server
+        .pool_manager
+        .get_cached(uuid)
+        .await
+        .ok_or_else(|| McpError::internal_error("Connection not found", None))?;
+    let redis = match key_value_browser(&driver) {
+        Ok(redis) => redis,
+        Err(e) => return Ok(tool_error(e)),
+    };
+
+    let (limits, enforced_by_driver) = query_limits(server, uuid).await;
+    let result = limits
+        .run(
+            enforced_by_driver,
+            redis.search_keys(pattern, limit, cursor, &|_, _, _, _| {}),
+        )
+        .await;
+    match result {
+        Ok(Ok(keys)) => {
+            server.audit.record_rows(keys.keys.len() as i64);
+            let json = serde_json::to_string_pretty(&keys).unwrap_or_else(|_| "{}".to_string());
+            Ok(CallToolResult::success(vec![Content::text(json)]))
+        }
+        Ok(Err(e)) => Ok(tool_error(format!("Failed to scan keys: {}", e))),
+        Err(timeout) => Ok(tool_error(timeout)),
+    }
+}
+
+async fn redis_get_key(
+    server: &McpServer,
+    uuid: &str,
+    key: &str,
+) -> Result<CallToolResult, McpError> {
+    server.authorize(uuid, McpPermission::Read).await?;
+    server.ensure_connected(uuid).await?;
+    let driver = server
+        .pool_manager
+        .get_cached(uuid)
+        .await
+        .ok_or_else(|| McpError::internal_error("Connection not found", None))?;
+    let redis = match key_value_browser(&driver) {
+        Ok(redis) => redis,
+        Err(e) => return Ok(tool_error(e)),
+    };
+
+    match redis.get_key_details(key).await {
+        Ok(mut details) => {
+            // Lists, sets, hashes and sorted sets can be huge; keep the first
+            // elements like query results.
+            let truncated = truncate_collection(&mut details.value, MAX_ROWS);
+            let mut output =
+                serde_json::to_string_pretty(&details).unwrap_or_else(|_| "{}".to_string());
+            if truncated {
+                output.push_str(&format!("\n\n(Value truncated to {} elements)", MAX_ROWS));
+            }
+            Ok(CallToolResult::success(vec![Content::text(output)]))
+        }
+        Err(e) => Ok(tool_error(format!("Failed to get key: {}", e))),
+    }
+}
+
+async fn redis_list_scripts(server: &McpServer, uuid: &str) -> Result<CallToolResult, McpError> {
+    server.authorize(uuid, McpPermission::Read).await?;
+
+    let scripts = load_redis_scripts(&server.sqlite_pool, uuid)
+        .await
+        .map_err(|e| McpError::internal_error(e, None))?;
+    server.audit.record_rows(scripts.len() as i64);
+
+    let json = serde_json::to_string_pretty(&json!({
+        "scripts": scripts
+            .iter()
+            .map(|script| json!({
+                "id": script.id,
+                "name": script.name,
+                "description": script.description,
+                "source": script.source,
+                "sha": script_hash(&script.source),
+                "parameters": script.parameters.0,
+                "read_only": script.read_only,
+            }))
+            .collect::<Vec<_>>(),
+    }))
+    .unwrap_or_else(|_| "{}".to_string());
+    Ok(CallToolResult::success(vec![Content::text(json)]))
+}
+
+async fn redis_run_script(
+    server: &McpServer,
+    uuid: &str,
+    id: i64,
+    inputs: &Map<String, Value>,
+) -> Result<CallToolResult, McpError> {
+    server.authorize(uuid, McpPermission::Read).await?;
+    let Some(script) = load_redis_script(&server.sqlite_pool, uuid, id)
+        .await
+        .map_err(|e| McpError::internal_error(e, None))?
+    else {
+        return Ok(tool_error(format!("Script {} not found", id)));
+    };
+    server.audit.record_query(&script.source);
+    let call = match bind_parameters(&script.parameters, inputs) {
+        Ok(call) => call,
+        Err(e) => return Ok(tool_error(e)),
+    };
+    server.ensure_connected(uuid).await?;
+    let driver = server
+        .pool_manager
+        .get_cached(uuid)
+        .await
+        .ok_or_else(|| McpError::internal_error("Connection not found", None))?;
+    let runner = match script_runner(&driver) {
+        Ok(runner) => runner,
+        Err(e) => return Ok(tool_error(e)),
+    };
+
+    // Always EVALSHA_RO: the server refuses any write, whatever the script's flag says.
+    let (limits, enforced_by_driver) = query_limits(server, uuid).await;
+    let result = limits
+        .run(
+            enforced_by_driver,
+            runner.eval_script(&script.source, &call, true),
+        )
+        .await;
+    Ok(query_result_content(server, result))
+}
+
+async fn redis_call_function(
+    server: &McpServer,
+    uuid: &str,
+    function: &str,
+    call: &ScriptCall,
+) -> Result<CallToolResult, McpError> {
+    server.authorize(uuid, McpPermission::Read).await?;
+    server.ensure_connected(uuid).await?;
+    server.audit.record_query(&format!("FCALL_RO {}", function));
+    let driver = server
+        .pool_manager
+        .get_cached(uuid)
+        .await
+        .ok_or_else(|| McpError::internal_error("Connection not found", None))?;
+    let runner = match script_runner(&driver) {
+        Ok(runner) => runner,
+        Err(e) => return Ok(tool_error(e)),
+    };
+
+    let (limits, enforced_by_driver) = query_limits(server, uuid).await;
+    let result = limits
+        .run(
+            enforced_by_driver,
+            runner.call_function(function, call, true),
+        )
+        .await;
+    Ok(query_result_content(server, result))
+}
+
+/// Keep the first `max` elements of an array or object value.
+fn truncate_collection(value: &mut Value, max: usize) -> bool {
+    match value {
+        Value::Array(items) if items.len() > max => {
+            items.truncate(max);
+            true
+        }
+        Value::Object(entries) if entries.len() > max => {
+            let kept: Map<String, Value> = std::mem::take(entries).into_iter().take(max).collect();
+            *entries = kept;
+            true
+        }
+        _ => false,
+    }
+}
+
+/// The connection's query limits, with the MCP default timeout filled in.
+/// The flag reports whether the driver already enforces the timeout.
+async fn query_limits(server: &McpServer, uuid: &str) -> (QueryLimits, bool) {
+    let mut limits = server
+        .pool_manager
+        .get_config(uuid)
+        .await
+        .map(|config| config.limits)
+        .unwrap_or_default();
+    let enforced_by_driver = limits.query_timeout.is_some();
+    limits.query_timeout.get_or_insert(DEFAULT_QUERY_TIMEOUT);
+    (limits, enforced_by_driver)
+}
+
+fn query_result_content(
+    server: &McpServer,
+    result: Result<Result<QueryResult, DriverError>, DriverError>,
+) -> CallToolResult {
     match result {
         Ok(Ok(mut result)) => {
             // Engine-level rejections (e.g. a write in read-only mode) come back
             // as an error on the result; surface them as a tool error.
             if let Some(err) = result.error.take() {
-                return Ok(CallToolResult::error(vec![Content::text(err)]));
+                return match result.error_details.take() {
+                    Some(details) => tool_error(driver_error_text(&details)),
+                    None => tool_error(err),
+                };
             }
 
-            let truncated = result.truncated || result.data.len() > MAX_ROWS;
-            if result.data.len() > MAX_ROWS {
-                result.data.truncate(MAX_ROWS);
-            }
-            result.row_count = result.data.len() as i64;
-            result.truncated = truncated;
+            result.truncate_rows(MAX_ROWS);
+            result.row_count = result.rows.len() as i64;
+            let truncated = result.truncated;
+            server.audit.record_rows(
+                result
+                    .rows_affected
+                    .map_or(result.row_count, |affected| affected as i64),
+            );
 
             let mut output =
                 serde_json::to_string_pretty(&result).unwrap_or_else(|_| "{}".to_string());
@@ -310,14 +1378,133 @@ async fn execute_query(
                 output.push_str(&format!("\n\n(Results truncated to {} rows)", MAX_ROWS));
             }
 
-            Ok(CallToolResult::success(vec![Content::text(output)]))
+            CallToolResult::success(vec![Content::text(output)])
         }
-        Ok(Err(e)) => Ok(CallToolResult::error(vec![Content::text(format!(
-            "Query failed: {}",
-            e
-        ))])),
-        Err(_) => Ok(CallToolResult::error(vec![Content::text(
-            "Query timed out after 30 seconds",
-        )])),
+        Ok(Err(e)) => tool_error(format!("Query failed: {}", driver_error_text(&e))),
+        Err(timeout) => tool_error(timeout),
+    }
+}
+
+/// An engine error with its category, code and position spelled out, so an
+/// agent can tell a typo from a permissions problem and find the bad token.
+fn driver_error_text(error: &DriverError) -> String {
+    let mut details = vec![format!("category: {}", error.category.as_str())];
+    if let Some(code) = &error.code {
+        details.push(format!("code: {}", code));
+    }
+    if let Some(position) = error.position.filter(|position| position.line > 0) {
+        details.push(format!(
+            "line {}, column {}",
+            position.line, position.column
+        ));
+    }
+    format!("{} ({})", error.message, details.join(", "))
+}
+
+async fn execute_statement(
+    server: &McpServer,
+    uuid: &str,
+    sql: &str,
+) -> Result<CallToolResult, McpError> {
+    let connection = server.authorize(uuid, McpPermission::Write).await?;
+    let mutation = MutationPlan {
+        sql: sql.to_string(),
+        values: Vec::new(),
+    };
+    run_approved_write(
+        server,
+        uuid,
+        &connection.name,
+        "execute_statement",
+        mutation,
+    )
+    .await
+}
+
+/// Arguments of the row tools; which parts are present depends on the tool.
+struct RowChange<'a> {
+    primary_key: Option<&'a Map<String, Value>>,
+    values: Option<&'a Map<String, Value>>,
+}
+
+async fn change_row(
+    server: &McpServer,
+    tool: &str,
+    uuid: &str,
+    schema: &str,
+    table: &str,
+    row: RowChange<'_>,
+) -> Result<CallToolResult, McpError> {
+    let connection = server.authorize(uuid, McpPermission::Write).await?;
+    let engine = match ensure_structured_mutations_supported(&connection.db_type)
+        .and_then(|_| DatabaseType::try_from(connection.db_type.as_str()))
+    {
+        Ok(engine) => engine,
+        Err(e) => return Ok(tool_error(e)),
+    };
+
+    let (key_columns, key_values): (Vec<String>, Vec<Value>) = row
+        .primary_key
+        .map(|key| key.iter().map(|(c, v)| (c.clone(), v.clone())).unzip())
+        .unwrap_or_default();
+    let values: Vec<MutationValue> = row
+        .values
+        .map(|values| {
+            values
+                .iter()
+                .map(|(column, value)| MutationValue {
+                    column: column.clone(),
+                    value: value.clone(),
+                    is_raw_sql: false,
+                })
+                .collect()
+        })
+        .unwrap_or_default();
+
+    let mutation = match (row.primary_key, row.values) {
+        (None, _) => build_insert(engine, schema, table, &values),
+        (Some(_), Some(_)) => {
+            build_update(engine, schema, table, &key_columns, &key_values, &values)
+        }
+        (Some(_), None) => build_delete(engine, schema, table, &key_columns, &key_values),
+    };
+    match mutation {
+        Ok(mutation) => run_approved_write(server, uuid, &connection.name, tool, mutation).await,
+        Err(e) => Ok(tool_error(e)),
+    }
+}
+
+/// Ask the user to approve `mutation`, then run it.
+async fn run_approved_write(
+    server: &McpServer,
+    uuid: &str,
+    connection_name: &str,
+    tool: &str,
+    mutation: MutationPlan,
+) -> Result<CallToolResult, McpError> {
+    server.ensure_connected(uuid).await?;
+    server.audit.record_query(&mutation.sql);
+
+    let request = ApprovalRequest::new(uuid, connection_name, tool, &mutation);
+    if let Err(reason) = server.approvals.request_approval(request).await {
+        return Ok(tool_error(reason));
     }
+
+    let (limits, enforced_by_driver) = query_limits(server, uuid).await;
+    let result = if mutation.values.is_empty() {
+        limits
+            .run(
+                enforced_by_driver,
+                server.pool_manager.execute_query(uuid, &mutation.sql),
+            )
+            .await
+    } else {
+        limits
+            .run(
+                enforced_by_driver,
+                server.pool_manager.execute_mutation(uuid, &mutation),
+            )
+            .await
+    };
+    Ok(query_result_content(server, result))
 }
@@ -139,7 +139,7 @@ async fn authenticate(
                 key_path.to_string()
             };
 
-            println!("[SSH] Attempting key auth with: {}", expanded_path);
+            eprintln!("[SSH] Attempting key auth with: {}", expanded_path);
             match load_secret_key(&expanded_path, auth.key_passphrase) {
                 Ok(key) => {
                     let hash_alg = session
@@ -151,28 +151,28 @@ async fn authenticate(
                     let key = PrivateKeyWithHashAlg::new(Arc::new(key), hash_alg);
                     match session.authenticate_publickey(ssh_user, key).await {
                         Ok(result) if result.success() => {
-                            println!("[SSH] Key authentication successful");
+                            eprintln!("[SSH] Key authentication successful");
                             return Ok(());
                         }
-                        Ok(_) => println!("[SSH] Key authentication rejected by server"),
-                        Err(e) => println!("[SSH] Key authentication error: {}", e),
+                        Ok(_) => eprintln!("[SSH] Key authentication rejected by server"),
+                        Err(e) => eprintln!("[SSH] Key authentication error: {}", e),
                     }
                 }
-                Err(e) => println!("[SSH] Failed to load private key: {}", e),
+                Err(e) => eprintln!("[SSH] Failed to load private key: {}", e),
             }
         }
     }
 
     if let Some(password) = auth.password {
         if !password.is_empty() {
-            println!("[SSH] Attempting password authentication");
+            eprintln!("[SSH] Attempting password authentication");
             match session
                 .authenticate_password(ssh_user, password)
                 .await
                 .map_err(|e| format!("SSH password authentication failed: {}", e))?
             {
                 result if result.success() => {
-                    println!("[SSH] Password authentication successful");
+                    eprintln!("[SSH] Password authentication successful");
                     return Ok(());
                 }
                 _ => {}
@@ -206,7 +206,7 @@ async fn forward_connection(
     {
         Ok(channel) => channel,
         Err(e) => {
-            println!("[SSH] Failed to open channel: {}", e);
+            eprintln!("[SSH] Failed to open channel: {}", e);
             return;
         }
     };
@@ -220,11 +220,11 @@ async fn forward_connection(
     )
     .await
     {
-        Ok((to_remote, to_local)) => println!(
+        Ok((to_remote, to_local)) => eprintln!(
             "[SSH] Tunnel closed. Bytes: {} up, {} down",
             to_remote, to_local
         ),
-        Err(e) => println!("[SSH] Copy error: {}", e),
+        Err(e) => eprintln!("[SSH] Copy error: {}", e),
     }
 }
 
@@ -237,7 +237,7 @@ impl SshTunnel {
         remote_host: &str,
         remote_port: u16,
     ) -> Result<Self, String> {
-        println!(
+        eprintln!(
             "[SSH] Creating tunnel to {}:{} -> {}:{}",
             ssh_host, ssh_port, remote_host, remote_port
         );
@@ -252,7 +252,7 @@ impl SshTunnel {
             ..Default::default()
         });
 
-        println!(
+        eprintln!(
             "[SSH] Connecting to SSH server at {}:{}",
             ssh_host, ssh_port
         );
@@ -272,9 +272,9 @@ impl SshTunnel {
             .await
             .map_err(|e| format!("Failed to connect to SSH server: {}", e))?;
 
-        println!("[SSH] Connected, authenticating...");
+        eprintln!("[SSH] Connected, authenticating...");
         authenticate(&mut session, ssh_user, auth).await?;
-        println!("[SSH] Authentication successful");
+        eprintln!("[SSH] Authentication successful");
 
         let listener = TcpListener::bind("127.0.0.1:0")
             .await
@@ -285,7 +285,7 @@ impl SshTunnel {
             .map_err(|e| format!("Failed to get local address: {}", e))?
             .port();
 
-        println!("[SSH] Tunnel listening on 127.0.0.1:{}", local_port);
+        eprintln!("[SSH] Tunnel listening on 127.0.0.1:{}", local_port);
 
         let (shutdown_tx, mut shutdown_rx) = oneshot::channel::<()>();
         let remote_host = remote_host.to_string();
@@ -294,11 +294,11 @@ impl SshTunnel {
         let session = Arc::new(session);
 
         tokio::spawn(async move {
-            println!("[SSH] Forwarding task started");
+            eprintln!("[SSH] Forwarding task started");
             loop {
                 tokio::select! {
                     _ = &mut shutdown_rx => {
-                        println!("[SSH] Shutdown requested");
+                        eprintln!("[SSH] Shutdown requested");
                         break;
                     }
                     accept_result = listener.accept() => {
@@ -313,7 +313,7 @@ impl SshTunnel {
                                 ));
                             }
                             Err(e) => {
-                                println!("[SSH] Accept error: {}", e);
+                                eprintln!("[SSH] Accept error: {}", e);
                             }
                         }
                     }
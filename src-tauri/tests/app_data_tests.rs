@@ -5,7 +5,7 @@
 //!
 //! Run with: cargo test --test app_data_tests -- --test-threads=1
 
-use dbcooper_lib::db::models::{Connection, SavedQuery, Setting};
+use dbcooper_lib::db::models::{Connection, SavedQuery, SavedRedisScript, Setting};
 use sqlx::sqlite::SqlitePoolOptions;
 use tempfile::NamedTempFile;
 
@@ -93,6 +93,27 @@ async fn create_test_pool() -> (sqlx::SqlitePool, NamedTempFile) {
     .await
     .unwrap();
 
+    sqlx::query(
+        r#"
+        CREATE TABLE IF NOT EXISTS redis_scripts (
+            id INTEGER PRIMARY KEY AUTOINCREMENT,
+            connection_uuid TEXT NOT NULL,
+            name TEXT NOT NULL COLLATE NOCASE,
+            description TEXT NOT NULL DEFAULT '',
+            source TEXT NOT NULL,
+            parameters_json TEXT NOT NULL DEFAULT '[]',
+            read_only INTEGER NOT NULL DEFAULT 0,
+            created_at TEXT NOT NULL DEFAULT (datetime('now')),
+            updated_at TEXT NOT NULL DEFAULT (datetime('now')),
+            FOREIGN KEY (connection_uuid) REFERENCES connections(uuid) ON DELETE CASCADE,
+            UNIQUE (connection_uuid, name)
+        )
+        "#,
+    )
+    .execute(&pool)
+    .await
+    .unwrap();
+
     sqlx::query(
         r#"
         CREATE TABLE IF NOT EXISTS settings (
@@ -531,6 +552,102 @@ async fn test_saved_views_are_deleted_with_their_connection() {
     assert_eq!(count, 0);
 }
 
+// ============================================================================
+// Redis Script Tests
+// ============================================================================
+
+#[tokio::test]
+async fn test_redis_script_round_trip_preserves_parameters() {
+    let (pool, _temp_file) = create_test_pool().await;
+    let connection_uuid = uuid::Uuid::new_v4().to_string();
+    insert_test_connection(&pool, &connection_uuid).await;
+    let parameters = serde_json::json!([
+        { "name": "bucket", "kind": "key" },
+        { "name": "limit", "kind": "arg", "type": "integer", "default": 10 }
+    ])
+    .to_string();
+
+    let saved: SavedRedisScript = sqlx::query_as(
+        r#"
+        INSERT INTO redis_scripts (connection_uuid, name, source, parameters_json, read_only)
+        VALUES (?, ?, ?, ?, 1)
+        RETURNING *
+        "#,
+    )
+    .bind(&connection_uuid)
+    .bind("Rate limiter")
+    .bind("return redis.call('GET', KEYS[1])")
+    .bind(&parameters)
+    .fetch_one(&pool)
+    .await
+    .unwrap();
+
+    assert_eq!(saved.name, "Rate limiter");
+    assert_eq!(saved.description, "");
+    assert!(saved.read_only);
+    assert_eq!(saved.parameters.len(), 2);
+    assert_eq!(saved.parameters[1].name, "limit");
+    assert_eq!(saved.parameters[1].default, Some(serde_json::json!(10)));
+}
+
+#[tokio::test]
+async fn test_redis_script_names_are_unique_per_connection() {
+    let (pool, _temp_file) = create_test_pool().await;
+    let connection_uuid = uuid::Uuid::new_v4().to_string();
+    let other_uuid = uuid::Uuid::new_v4().to_string();
+    insert_test_connection(&pool, &connection_uuid).await;
+    insert_test_connection(&pool, &other_uuid).await;
+    let insert =
+        "INSERT INTO redis_scripts (connection_uuid, name, source) VALUES (?, ?, 'return 1')";
+
+    sqlx::query(insert)
+        .bind(&connection_uuid)
+        .bind("Lock")
+        .execute(&pool)
+        .await
+        .unwrap();
+
+    let duplicate = sqlx::query(insert)
+        .bind(&connection_uuid)
+        .bind("lock")
+        .execute(&pool)
+        .await;
+    assert!(duplicate.is_err());
+
+    let other_connection = sqlx::query(insert)
+        .bind(&other_uuid)
+        .bind("Lock")
+        .execute(&pool)
+        .await;
+    assert!(other_connection.is_ok());
+}
+
+#[tokio::test]
+async fn test_redis_scripts_are_deleted_with_their_connection() {
+    let (pool, _temp_file) = create_test_pool().await;
+    let connection_uuid = uuid::Uuid::new_v4().to_string();
+    insert_test_connection(&pool, &connection_uuid).await;
+    sqlx::query(
+        "INSERT INTO redis_scripts (connection_uuid, name, source) VALUES (?, 'Lock', 'return 1')",
+    )
+    .bind(&connection_uuid)
+    .execute(&pool)
+    .await
+    .unwrap();
+
+    sqlx::query("DELETE FROM connections WHERE uuid = ?")
+        .bind(&connection_uuid)
+        .execute(&pool)
+        .await
+        .unwrap();
+
+    let count: i64 = sqlx::query_scalar("SELECT COUNT(*) FROM redis_scripts")
+        .fetch_one(&pool)
+        .await
+        .unwrap();
+    assert_eq!(count, 0);
+}
+
 // ============================================================================
 // Settings CRUD Tests
 // ============================================================================
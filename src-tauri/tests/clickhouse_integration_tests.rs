@@ -6,7 +6,7 @@
 //! Run with: cargo test --test clickhouse_integration_tests -- --test-threads=1
 
 use dbcooper_lib::database::clickhouse::{ClickhouseConfig, ClickhouseDriver, ClickhouseProtocol};
-use dbcooper_lib::database::DatabaseDriver;
+use dbcooper_lib::database::{DatabaseDriver, QueryLimits, SessionOptions};
 use dbcooper_lib::db::models::{
     FilterCondition, FilterConjunction, FilterExpression, FilterOperator, TableFilter,
 };
@@ -22,6 +22,8 @@ fn create_test_driver() -> ClickhouseDriver {
         password: "clickhouse".to_string(),
         protocol: ClickhouseProtocol::Http,
         ssl: false,
+        session: SessionOptions::default(),
+        limits: QueryLimits::default(),
     };
     ClickhouseDriver::new(config)
 }
@@ -82,6 +84,8 @@ async fn test_connection_failure() {
         password: "clickhouse".to_string(),
         protocol: ClickhouseProtocol::Http,
         ssl: false,
+        session: SessionOptions::default(),
+        limits: QueryLimits::default(),
     };
     let driver = ClickhouseDriver::new(config);
 
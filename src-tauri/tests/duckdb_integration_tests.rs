@@ -1,5 +1,5 @@
 use dbcooper_lib::database::duckdb::DuckDbDriver;
-use dbcooper_lib::database::{DatabaseDriver, DuckDbConfig};
+use dbcooper_lib::database::{DatabaseDriver, DuckDbConfig, QueryLimits};
 use dbcooper_lib::db::models::{
     FilterCondition, FilterConjunction, FilterExpression, FilterOperator, TableFilter,
 };
@@ -18,6 +18,7 @@ fn create_driver(temp_dir: &TempDir) -> DuckDbDriver {
                 .join("analytics.duckdb")
                 .to_string_lossy()
                 .to_string(),
+            limits: QueryLimits::default(),
         },
         helper,
     )
@@ -101,7 +102,10 @@ async fn reads_pages_with_structured_filters_and_primary_key_ordering() {
     assert_eq!(page.total, 2);
     assert_eq!(page.data[0]["id"], 2);
     assert_eq!(page.data[1]["id"], 3);
-    assert_eq!(page.data[0]["value"], "20.50");
+    assert_eq!(
+        page.data[0]["value"],
+        json!({"type": "decimal", "value": "20.50"})
+    );
 }
 
 #[tokio::test]
@@ -159,10 +163,23 @@ async fn exposes_structure_and_complex_values_without_precision_loss() {
     assert_eq!(structure.foreign_keys[0].references_table, "parent");
 
     let result = driver.execute_query("SELECT * FROM child").await.unwrap();
-    assert_eq!(result.data[0]["id"], "9007199254740993");
-    assert_eq!(result.data[0]["amount"], "123.4500");
-    assert_eq!(result.data[0]["tags"], json!(["a", "b"]));
-    assert_eq!(result.data[0]["payload"], "\\xCA\\xFE");
+    assert_eq!(
+        result.data[0]["id"],
+        json!({"type": "integer", "value": "9007199254740993"})
+    );
+    assert_eq!(
+        result.data[0]["amount"],
+        json!({"type": "decimal", "value": "123.4500"})
+    );
+    assert_eq!(
+        result.data[0]["tags"],
+        json!({"type": "array", "value": ["a", "b"]})
+    );
+    assert_eq!(
+        result.data[0]["payload"],
+        json!({"type": "binary", "value": "yv4=", "length": 2})
+    );
+    assert_eq!(result.columns[3].data_type, "DECIMAL(38,4)");
 }
 
 #[tokio::test]
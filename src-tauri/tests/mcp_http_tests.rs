@@ -1,7 +1,10 @@
 use std::sync::Arc;
 
 use dbcooper_lib::database::pool_manager::PoolManager;
+use dbcooper_lib::mcp::approval::ApprovalBroker;
+use dbcooper_lib::mcp::audit::{self, AuditFilter};
 use dbcooper_lib::mcp::server::start_mcp_server;
+use dbcooper_lib::mcp::tokens::{self, McpTokenInput};
 use reqwest::header::{HeaderMap, ACCEPT, AUTHORIZATION, CONTENT_TYPE};
 use serde_json::{json, Value};
 use sqlx::sqlite::SqlitePoolOptions;
@@ -39,6 +42,7 @@ async fn sqlite_pool() -> sqlx::SqlitePool {
             ssh_password TEXT NOT NULL DEFAULT '',
             ssh_key_path TEXT NOT NULL DEFAULT '',
             ssh_use_key INTEGER NOT NULL DEFAULT 0,
+            mcp_permission TEXT NOT NULL DEFAULT 'read',
             created_at TEXT NOT NULL DEFAULT CURRENT_TIMESTAMP,
             updated_at TEXT NOT NULL DEFAULT CURRENT_TIMESTAMP
         )
@@ -69,6 +73,97 @@ async fn sqlite_pool() -> sqlx::SqlitePool {
     .await
     .expect("create docker connections table");
 
+    sqlx::query(
+        r#"
+        CREATE TABLE mcp_tokens (
+            id INTEGER PRIMARY KEY AUTOINCREMENT,
+            name TEXT NOT NULL,
+            token_hash TEXT NOT NULL UNIQUE,
+            token_prefix TEXT NOT NULL,
+            permission TEXT NOT NULL DEFAULT 'read',
+            expires_at TEXT,
+            last_used_at TEXT,
+            created_at TEXT NOT NULL DEFAULT (datetime('now')),
+            updated_at TEXT NOT NULL DEFAULT (datetime('now'))
+        )
+        "#,
+    )
+    .execute(&pool)
+    .await
+    .expect("create MCP tokens table");
+
+    sqlx::query(
+        r#"
+        CREATE TABLE mcp_token_connections (
+            token_id INTEGER NOT NULL,
+            connection_uuid TEXT NOT NULL,
+            PRIMARY KEY (token_id, connection_uuid)
+        )
+        "#,
+    )
+    .execute(&pool)
+    .await
+    .expect("create MCP token connections table");
+
+    sqlx::query(
+        r#"
+        CREATE TABLE saved_queries (
+            id INTEGER PRIMARY KEY AUTOINCREMENT,
+            connection_uuid TEXT NOT NULL,
+            name TEXT NOT NULL,
+            query TEXT NOT NULL,
+            created_at TEXT NOT NULL DEFAULT (datetime('now')),
+            updated_at TEXT NOT NULL DEFAULT (datetime('now'))
+        )
+        "#,
+    )
+    .execute(&pool)
+    .await
+    .expect("create saved queries table");
+
+    sqlx::query(
+        r#"
+        CREATE TABLE saved_views (
+            id INTEGER PRIMARY KEY AUTOINCREMENT,
+            connection_uuid TEXT NOT NULL,
+            table_name TEXT NOT NULL,
+            name TEXT NOT NULL,
+            state_json TEXT NOT NULL,
+            created_at TEXT NOT NULL DEFAULT (datetime('now')),
+            updated_at TEXT NOT NULL DEFAULT (datetime('now'))
+        )
+        "#,
+    )
+    .execute(&pool)
+    .await
+    .expect("create saved views table");
+
+    sqlx::query("CREATE TABLE settings (key TEXT PRIMARY KEY, value TEXT NOT NULL)")
+        .execute(&pool)
+        .await
+        .expect("create settings table");
+
+    sqlx::query(
+        r#"
+        CREATE TABLE mcp_audit_log (
+            id INTEGER PRIMARY KEY AUTOINCREMENT,
+            token_id INTEGER,
+            token_name TEXT NOT NULL,
+            kind TEXT NOT NULL,
+            name TEXT NOT NULL,
+            connection_uuid TEXT,
+            query TEXT,
+            row_count INTEGER,
+            duration_ms INTEGER NOT NULL,
+            error TEXT,
+            created_at TEXT NOT NULL DEFAULT (datetime('now'))
+        )
+        "#,
+    )
+    .execute(&pool)
+    .await
+    .expect("create MCP audit log table");
+
     pool
 }
 
@@ -77,12 +172,22 @@ async fn post_mcp(
     url: &str,
     session_id: Option<&str>,
     body: Value,
+) -> reqwest::Response {
+    post_mcp_as(client, url, TOKEN, session_id, body).await
+}
+
+async fn post_mcp_as(
+    client: &reqwest::Client,
+    url: &str,
+    token: &str,
+    session_id: Option<&str>,
+    body: Value,
 ) -> reqwest::Response {
     let mut request = client
         .post(url)
         .header(ACCEPT, ACCEPT_MCP)
         .header(CONTENT_TYPE, "application/json")
-        .header(AUTHORIZATION, format!("Bearer {TOKEN}"))
+        .header(AUTHORIZATION, format!("Bearer {token}"))
         .json(&body);
 
     if let Some(session_id) = session_id {
@@ -130,6 +235,7 @@ async fn rejects_external_http_clients_without_bearer_token() {
     let handle = start_mcp_server(
         sqlite_pool().await,
         Arc::new(PoolManager::new()),
+        Arc::new(ApprovalBroker::headless()),
         TOKEN.into(),
     )
     .await
@@ -156,6 +262,7 @@ async fn stopping_server_waits_until_the_bound_port_is_released() {
     let handle = start_mcp_server(
         sqlite_pool().await,
         Arc::new(PoolManager::new()),
+        Arc::new(ApprovalBroker::headless()),
         TOKEN.into(),
     )
     .await
@@ -176,6 +283,7 @@ async fn external_http_client_can_initialize_discover_and_call_tools() {
     let handle = start_mcp_server(
         sqlite_pool().await,
         Arc::new(PoolManager::new()),
+        Arc::new(ApprovalBroker::headless()),
         TOKEN.into(),
     )
     .await
@@ -297,9 +405,14 @@ async fn external_http_client_gets_consistent_bounded_read_only_results() {
     .await
     .expect("save target connection");
 
-    let handle = start_mcp_server(metadata_pool, Arc::new(PoolManager::new()), TOKEN.into())
-        .await
-        .expect("start MCP server");
+    let handle = start_mcp_server(
+        metadata_pool,
+        Arc::new(PoolManager::new()),
+        Arc::new(ApprovalBroker::headless()),
+        TOKEN.into(),
+    )
+    .await
+    .expect("start MCP server");
     let url = format!("http://127.0.0.1:{}/mcp", handle.port);
     let client = reqwest::Client::new();
 
@@ -368,7 +481,7 @@ async fn external_http_client_gets_consistent_bounded_read_only_results() {
         .split_once("\n\n(Results truncated")
         .map_or(text, |(json, _)| json);
     let result: Value = serde_json::from_str(json_text).expect("parse query result");
-    assert_eq!(result["data"].as_array().unwrap().len(), 1000);
+    assert_eq!(result["rows"].as_array().unwrap().len(), 1000);
     assert_eq!(result["row_count"], 1000);
     assert_eq!(result["truncated"], true);
 
@@ -423,3 +536,684 @@ async fn external_http_client_gets_consistent_bounded_read_only_results() {
 
     handle.stop().await;
 }
+
+fn initialize_request() -> Value {
+    json!({
+        "jsonrpc": "2.0",
+        "id": 1,
+        "method": "initialize",
+        "params": {
+            "protocolVersion": "2025-03-26",
+            "capabilities": {},
+            "clientInfo": {"name": "dbcooper-http-test", "version": "0.0.0"}
+        }
+    })
+}
+
+async fn initialize_session(client: &reqwest::Client, url: &str) -> String {
+    initialize_session_as(client, url, TOKEN).await
+}
+
+async fn initialize_session_as(client: &reqwest::Client, url: &str, token: &str) -> String {
+    let (headers, _) =
+        json_rpc_response(post_mcp_as(client, url, token, None, initialize_request()).await).await;
+    let session_id = headers
+        .get("mcp-session-id")
+        .and_then(|value| value.to_str().ok())
+        .expect("initialize response should include MCP session id")
+        .to_string();
+    let initialized = post_mcp_as(
+        client,
+        url,
+        token,
+        Some(&session_id),
+        json!({"jsonrpc":"2.0","method":"notifications/initialized"}),
+    )
+    .await;
+    assert_eq!(initialized.status(), reqwest::StatusCode::ACCEPTED);
+    session_id
+}
+
+async fn call_tool(
+    client: &reqwest::Client,
+    url: &str,
+    session_id: &str,
+    id: u64,
+    name: &str,
+    arguments: Value,
+) -> Value {
+    call_tool_as(client, url, TOKEN, session_id, id, name, arguments).await
+}
+
+async fn call_tool_as(
+    client: &reqwest::Client,
+    url: &str,
+    token: &str,
+    session_id: &str,
+    id: u64,
+    name: &str,
+    arguments: Value,
+) -> Value {
+    let (_, response) = json_rpc_response(
+        post_mcp_as(
+            client,
+            url,
+            token,
+            Some(session_id),
+            json!({
+                "jsonrpc":"2.0",
+                "id":id,
+                "method":"tools/call",
+                "params":{"name":name,"arguments":arguments}
+            }),
+        )
+        .await,
+    )
+    .await;
+    response
+}
+
+#[tokio::test]
+async fn write_tools_follow_connection_permissions_and_wait_for_approval() {
+    let _test_guard = MCP_TEST_LOCK.lock().await;
+    let temp_dir = tempfile::tempdir().expect("create temporary database directory");
+    let database_path = temp_dir.path().join("seed.db");
+    let database_url = format!("sqlite://{}?mode=rwc", database_path.to_string_lossy());
+    let database_pool = SqlitePoolOptions::new()
+        .max_connections(1)
+        .connect(&database_url)
+        .await
+        .expect("create MCP target database");
+    sqlx::query("CREATE TABLE users (id INTEGER PRIMARY KEY, name TEXT NOT NULL)")
+        .execute(&database_pool)
+        .await
+        .expect("create target table");
+    database_pool.close().await;
+
+    let metadata_pool = sqlite_pool().await;
+    for (uuid, permission) in [("dev", "write"), ("prod", "read"), ("secret", "none")] {
+        sqlx::query(
+            "INSERT INTO connections (uuid, name, db_type, file_path, mcp_permission) VALUES (?, ?, 'sqlite', ?, ?)",
+        )
+        .bind(uuid)
+        .bind(uuid)
+        .bind(database_path.to_string_lossy().as_ref())
+        .bind(permission)
+        .execute(&metadata_pool)
+        .await
+        .expect("save target connection");
+    }
+
+    // Stand-in for the desktop app: approve inserts, reject everything else.
+    let approvals = Arc::new(ApprovalBroker::new(|_| {}));
+    let approver = {
+        let approvals = approvals.clone();
+        tokio::spawn(async move {
+            loop {
+                for request in approvals.pending() {
+                    let _ = approvals.resolve(&request.id, request.tool == "insert_row");
+                }
+                tokio::time::sleep(std::time::Duration::from_millis(10)).await;
+            }
+        })
+    };
+
+    let handle = start_mcp_server(
+        metadata_pool,
+        Arc::new(PoolManager::new()),
+        approvals,
+        TOKEN.into(),
+    )
+    .await
+    .expect("start MCP server");
+    let url = format!("http://127.0.0.1:{}/mcp", handle.port);
+    let client = reqwest::Client::new();
+    let session_id = initialize_session(&client, &url).await;
+
+    let listed = call_tool(&client, &url, &session_id, 2, "list_connections", json!({})).await;
+    let listed: Value =
+        serde_json::from_str(listed["result"]["content"][0]["text"].as_str().unwrap()).unwrap();
+    let uuids: Vec<&str> = listed
+        .as_array()
+        .unwrap()
+        .iter()
+        .filter_map(|connection| connection["uuid"].as_str())
+        .collect();
+    assert!(uuids.contains(&"dev") && uuids.contains(&"prod"));
+    assert!(!uuids.contains(&"secret"));
+
+    let row = json!({
+        "connection_uuid": "dev",
+        "schema": "main",
+        "table": "users",
+        "values": {"id": 1, "name": "Ada"}
+    });
+    let inserted = call_tool(&client, &url, &session_id, 3, "insert_row", row.clone()).await;
+    assert_eq!(
+        inserted["result"]["isError"], false,
+        "unexpected insert_row response: {inserted}"
+    );
+
+    let rejected = call_tool(
+        &client,
+        &url,
+        &session_id,
+        4,
+        "delete_row",
+        json!({
+            "connection_uuid": "dev",
+            "schema": "main",
+            "table": "users",
+            "primary_key": {"id": 1}
+        }),
+    )
+    .await;
+    assert_eq!(rejected["result"]["isError"], true);
+
+    let mut read_only_row = row;
+    read_only_row["connection_uuid"] = json!("prod");
+    let denied = call_tool(&client, &url, &session_id, 5, "insert_row", read_only_row).await;
+    assert!(denied["error"]["message"]
+        .as_str()
+        .is_some_and(|message| message.contains("may only read")));
+
+    let hidden = call_tool(
+        &client,
+        &url,
+        &session_id,
+        6,
+        "list_tables",
+        json!({"connection_uuid": "secret"}),
+    )
+    .await;
+    assert!(hidden["error"].is_object());
+
+    let rows = call_tool(
+        &client,
+        &url,
+        &session_id,
+        7,
+        "execute_query",
+        json!({"connection_uuid": "prod", "query": "SELECT id, name FROM users"}),
+    )
+    .await;
+    let rows: Value =
+        serde_json::from_str(rows["result"]["content"][0]["text"].as_str().unwrap()).unwrap();
+    assert_eq!(rows["rows"], json!([[1, "Ada"]]));
+
+    approver.abort();
+    handle.stop().await;
+}
+
+#[tokio::test]
+async fn scoped_tokens_only_reach_their_connections_until_they_expire() {
+    let _test_guard = MCP_TEST_LOCK.lock().await;
+    let temp_dir = tempfile::tempdir().expect("create temporary database directory");
+    let database_path = temp_dir.path().join("scoped.db");
+    let database_url = format!("sqlite://{}?mode=rwc", database_path.to_string_lossy());
+    let database_pool = SqlitePoolOptions::new()
+        .max_connections(1)
+        .connect(&database_url)
+        .await
+        .expect("create MCP target database");
+    sqlx::query("CREATE TABLE users (id INTEGER PRIMARY KEY, name TEXT NOT NULL)")
+        .execute(&database_pool)
+        .await
+        .expect("create target table");
+    database_pool.close().await;
+
+    let metadata_pool = sqlite_pool().await;
+    for uuid in ["dev", "prod"] {
+        sqlx::query(
+            "INSERT INTO connections (uuid, name, db_type, file_path, mcp_permission) VALUES (?, ?, 'sqlite', ?, 'write')",
+        )
+        .bind(uuid)
+        .bind(uuid)
+        .bind(database_path.to_string_lossy().as_ref())
+        .execute(&metadata_pool)
+        .await
+        .expect("save target connection");
+    }
+    let reader = tokens::create(
+        &metadata_pool,
+        &McpTokenInput {
+            name: "Dev reader".to_string(),
+            permission: "read".to_string(),
+            connection_uuids: vec!["dev".to_string()],
+            expires_at: None,
+        },
+    )
+    .await
+    .expect("create scoped token");
+
+    let handle = start_mcp_server(
+        metadata_pool.clone(),
+        Arc::new(PoolManager::new()),
+        Arc::new(ApprovalBroker::new(|_| {})),
+        TOKEN.into(),
+    )
+    .await
+    .expect("start MCP server");
+    let url = format!("http://127.0.0.1:{}/mcp", handle.port);
+    let client = reqwest::Client::new();
+    let session_id = initialize_session_as(&client, &url, &reader.secret).await;
+
+    let listed = call_tool_as(
+        &client,
+        &url,
+        &reader.secret,
+        &session_id,
+        2,
+        "list_connections",
+        json!({}),
+    )
+    .await;
+    let listed: Value =
+        serde_json::from_str(listed["result"]["content"][0]["text"].as_str().unwrap()).unwrap();
+    assert_eq!(listed.as_array().unwrap().len(), 1);
+    assert_eq!(listed[0]["uuid"], "dev");
+    assert_eq!(listed[0]["mcp_permission"], "read");
+
+    let outside = call_tool_as(
+        &client,
+        &url,
+        &reader.secret,
+        &session_id,
+        3,
+        "list_tables",
+        json!({"connection_uuid": "prod"}),
+    )
+    .await;
+    assert!(outside["error"].is_object());
+
+    let write = call_tool_as(
+        &client,
+        &url,
+        &reader.secret,
+        &session_id,
+        4,
+        "execute_statement",
+        json!({"connection_uuid": "dev", "sql": "DELETE FROM users"}),
+    )
+    .await;
+    assert!(write["error"]["message"]
+        .as_str()
+        .is_some_and(|message| message.contains("token may only read")));
+
+    let tables = call_tool_as(
+        &client,
+        &url,
+        &reader.secret,
+        &session_id,
+        5,
+        "list_tables",
+        json!({"connection_uuid": "dev"}),
+    )
+    .await;
+    assert_eq!(
+        tables["result"]["isError"], false,
+        "unexpected list_tables response: {tables}"
+    );
+
+    tokens::update(
+        &metadata_pool,
+        reader.token.id,
+        &McpTokenInput {
+            name: "Dev reader".to_string(),
+            permission: "read".to_string(),
+            connection_uuids: vec!["dev".to_string()],
+            expires_at: Some("2000-01-01T00:00:00Z".to_string()),
+        },
+    )
+    .await
+    .expect("expire scoped token");
+    let expired = post_mcp_as(&client, &url, &reader.secret, None, initialize_request()).await;
+    assert_eq!(expired.status(), reqwest::StatusCode::UNAUTHORIZED);
+    let unknown = post_mcp_as(&client, &url, "not-a-token", None, initialize_request()).await;
+    assert_eq!(unknown.status(), reqwest::StatusCode::UNAUTHORIZED);
+
+    let calls = audit::query(
+        &metadata_pool,
+        &AuditFilter {
+            token_id: Some(reader.token.id),
+            ..AuditFilter::default()
+        },
+    )
+    .await
+    .expect("query audit log");
+    let names: Vec<&str> = calls.iter().map(|entry| entry.name.as_str()).collect();
+    assert_eq!(
+        names,
+        [
+            "list_tables",
+            "execute_statement",
+            "list_tables",
+            "list_connections"
+        ]
+    );
+    assert!(calls.iter().all(|entry| entry.token_name == "Dev reader"));
+    assert_eq!(calls[1].query.as_deref(), Some("DELETE FROM users"));
+    assert!(calls[1]
+        .error
+        .as_deref()
+        .is_some_and(|error| error.contains("token may only read")));
+    assert_eq!(calls[2].connection_uuid.as_deref(), Some("prod"));
+    assert!(calls[0].error.is_none());
+
+    handle.stop().await;
+}
+
+#[tokio::test]
+async fn browsing_tools_sample_explain_and_search_without_raw_sql() {
+    let _test_guard = MCP_TEST_LOCK.lock().await;
+    let temp_dir = tempfile::tempdir().expect("create temporary database directory");
+    let database_path = temp_dir.path().join("shop.db");
+    let database_url = format!("sqlite://{}?mode=rwc", database_path.to_string_lossy());
+    let database_pool = SqlitePoolOptions::new()
+        .max_connections(1)
+        .connect(&database_url)
+        .await
+        .expect("create MCP target database");
+    sqlx::query(
+        "CREATE TABLE orders (id INTEGER PRIMARY KEY, customer_email TEXT NOT NULL, total INTEGER NOT NULL)",
+    )
+    .execute(&database_pool)
+    .await
+    .expect("create target table");
+    sqlx::query(
+        "INSERT INTO orders (customer_email, total) VALUES ('a@example.com', 10), ('b@example.com', 30), ('c@example.com', 20)",
+    )
+    .execute(&database_pool)
+    .await
+    .expect("insert target rows");
+    database_pool.close().await;
+
+    let metadata_pool = sqlite_pool().await;
+    sqlx::query(
+        "INSERT INTO connections (uuid, name, db_type, file_path) VALUES ('shop', 'Shop', 'sqlite', ?)",
+    )
+    .bind(database_path.to_string_lossy().as_ref())
+    .execute(&metadata_pool)
+    .await
+    .expect("save target connection");
+
+    let handle = start_mcp_server(
+        metadata_pool,
+        Arc::new(PoolManager::new()),
+        Arc::new(ApprovalBroker::headless()),
+        TOKEN.into(),
+    )
+    .await
+    .expect("start MCP server");
+    let url = format!("http://127.0.0.1:{}/mcp", handle.port);
+    let client = reqwest::Client::new();
+    let session_id = initialize_session(&client, &url).await;
+
+    let sample = call_tool(
+        &client,
+        &url,
+        &session_id,
+        2,
+        "sample_table",
+        json!({
+            "connection_uuid": "shop",
+            "schema": "main",
+            "table": "orders",
+            "limit": 1,
+            "filter": {
+                "conjunction": "and",
+                "conditions": [{"column": "total", "operator": "greater_than", "value": "15"}]
+            },
+            "sort_column": "total",
+            "sort_direction": "desc"
+        }),
+    )
+    .await;
+    assert_eq!(sample["result"]["isError"], false, "{sample}");
+    let sample: Value =
+        serde_json::from_str(sample["result"]["content"][0]["text"].as_str().unwrap()).unwrap();
+    assert_eq!(sample["total"], 2);
+    assert_eq!(sample["data"].as_array().unwrap().len(), 1);
+    assert_eq!(sample["data"][0]["customer_email"], "b@example.com");
+
+    let explain = call_tool(
+        &client,
+        &url,
+        &session_id,
+        3,
+        "explain_query",
+        json!({"connection_uuid": "shop", "query": "SELECT * FROM orders WHERE id = 1;"}),
+    )
+    .await;
+    assert_eq!(explain["result"]["isError"], false, "{explain}");
+    let plan = explain["result"]["content"][0]["text"].as_str().unwrap();
+    assert!(plan.contains("orders"), "unexpected plan: {plan}");
+
+    let search = call_tool(
+        &client,
+        &url,
+        &session_id,
+        4,
+        "search_schema",
+        json!({"connection_uuid": "shop", "query": "orders email"}),
+    )
+    .await;
+    assert_eq!(search["result"]["isError"], false, "{search}");
+    let search: Value =
+        serde_json::from_str(search["result"]["content"][0]["text"].as_str().unwrap()).unwrap();
+    assert_eq!(search[0]["kind"], "column");
+    assert_eq!(search[0]["column"], "customer_email");
+
+    let not_redis = call_tool(
+        &client,
+        &url,
+        &session_id,
+        5,
+        "redis_scan_keys",
+        json!({"connection_uuid": "shop"}),
+    )
+    .await;
+    assert_eq!(not_redis["result"]["isError"], true);
+
+    handle.stop().await;
+}
+
+#[tokio::test]
+async fn saved_queries_and_views_are_resources_tools_and_prompts() {
+    let _test_guard = MCP_TEST_LOCK.lock().await;
+    let temp_dir = tempfile::tempdir().expect("create temporary database directory");
+    let database_path = temp_dir.path().join("crm.db");
+    let database_url = format!("sqlite://{}?mode=rwc", database_path.to_string_lossy());
+    let database_pool = SqlitePoolOptions::new()
+        .max_connections(1)
+        .connect(&database_url)
+        .await
+        .expect("create MCP target database");
+    sqlx::query(
+        "CREATE TABLE customers (id INTEGER PRIMARY KEY, name TEXT NOT NULL, tier TEXT NOT NULL, api_key TEXT)",
+    )
+    .execute(&database_pool)
+    .await
+    .expect("create target table");
+    sqlx::query(
+        "INSERT INTO customers (name, tier, api_key) VALUES ('Ada', 'gold', 'k1'), ('Bob', 'free', 'k2'), ('Cy', 'gold', 'k3')",
+    )
+    .execute(&database_pool)
+    .await
+    .expect("insert target rows");
+    database_pool.close().await;
+
+    let metadata_pool = sqlite_pool().await;
+    sqlx::query(
+        "INSERT INTO connections (uuid, name, db_type, file_path) VALUES ('crm', 'CRM', 'sqlite', ?)",
+    )
+    .bind(database_path.to_string_lossy().as_ref())
+    .execute(&metadata_pool)
+    .await
+    .expect("save target connection");
+    sqlx::query(
+        "INSERT INTO saved_queries (id, connection_uuid, name, query) VALUES (1, 'crm', 'Gold customers', 'SELECT name FROM customers WHERE tier = ''gold'' ORDER BY name')",
+    )
+    .execute(&metadata_pool)
+    .await
+    .expect("save query");
+    sqlx::query("INSERT INTO saved_views (id, connection_uuid, table_name, name, state_json) VALUES (1, 'crm', 'main.customers', 'Gold, newest first', ?)")
+        .bind(
+            json!({
+                "version": 1,
+                "filter": {
+                    "kind": "structured",
+                    "value": {
+                        "conjunction": "and",
+                        "conditions": [{"column": "tier", "operator": "equals", "value": "gold"}]
+                    }
+                },
+                "sort": {"column": "id", "direction": "desc"},
+                "column_order": ["name"],
+                "hidden_columns": ["api_key"],
+                "column_widths": {}
+            })
+            .to_string(),
+        )
+        .execute(&metadata_pool)
+        .await
+        .expect("save view");
+
+    let handle = start_mcp_server(
+        metadata_pool.clone(),
+        Arc::new(PoolManager::new()),
+        Arc::new(ApprovalBroker::headless()),
+        TOKEN.into(),
+    )
+    .await
+    .expect("start MCP server");
+    let url = format!("http://127.0.0.1:{}/mcp", handle.port);
+    let client = reqwest::Client::new();
+    let session_id = initialize_session(&client, &url).await;
+
+    let (_, resources) = json_rpc_response(
+        post_mcp(
+            &client,
+            &url,
+            Some(&session_id),
+            json!({"jsonrpc":"2.0","id":2,"method":"resources/list","params":{}}),
+        )
+        .await,
+    )
+    .await;
+    let uris: Vec<&str> = resources["result"]["resources"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .filter_map(|resource| resource["uri"].as_str())
+        .collect();
+    assert!(uris.contains(&"dbcooper://connection/crm/saved-query/1"));
+    assert!(uris.contains(&"dbcooper://connection/crm/saved-view/1"));
+
+    let (_, saved_query) = json_rpc_response(
+        post_mcp(
+            &client,
+            &url,
+            Some(&session_id),
+            json!({
+                "jsonrpc":"2.0",
+                "id":3,
+                "method":"resources/read",
+                "params":{"uri":"dbcooper://connection/crm/saved-query/1"}
+            }),
+        )
+        .await,
+    )
+    .await;
+    let saved_query: Value = serde_json::from_str(
+        saved_query["result"]["contents"][0]["text"]
+            .as_str()
+            .unwrap(),
+    )
+    .unwrap();
+    assert_eq!(saved_query["name"], "Gold customers");
+
+    let ran = call_tool(
+        &client,
+        &url,
+        &session_id,
+        4,
+        "run_saved_query",
+        json!({"connection_uuid": "crm", "saved_query_id": 1}),
+    )
+    .await;
+    assert_eq!(ran["result"]["isError"], false, "{ran}");
+    let ran: Value =
+        serde_json::from_str(ran["result"]["content"][0]["text"].as_str().unwrap()).unwrap();
+    assert_eq!(ran["rows"], json!([["Ada"], ["Cy"]]));
+
+    let view = call_tool(
+        &client,
+        &url,
+        &session_id,
+        5,
+        "run_saved_view",
+        json!({"connection_uuid": "crm", "saved_view_id": 1}),
+    )
+    .await;
+    assert_eq!(view["result"]["isError"], false, "{view}");
+    let view: Value =
+        serde_json::from_str(view["result"]["content"][0]["text"].as_str().unwrap()).unwrap();
+    assert_eq!(view["total"], 2);
+    assert_eq!(
+        view["data"],
+        json!([
+            {"name": "Cy", "id": 3, "tier": "gold"},
+            {"name": "Ada", "id": 1, "tier": "gold"}
+        ])
+    );
+
+    let (_, prompts) = json_rpc_response(
+        post_mcp(
+            &client,
+            &url,
+            Some(&session_id),
+            json!({"jsonrpc":"2.0","id":6,"method":"prompts/list","params":{}}),
+        )
+        .await,
+    )
+    .await;
+    assert_eq!(prompts["result"]["prompts"][0]["name"], "analyze_table");
+
+    let (_, prompt) = json_rpc_response(
+        post_mcp(
+            &client,
+            &url,
+            Some(&session_id),
+            json!({
+                "jsonrpc":"2.0",
+                "id":7,
+                "method":"prompts/get",
+                "params":{
+                    "name":"analyze_table",
+                    "arguments":{"connection_uuid":"crm","schema":"main","table":"customers"}
+                }
+            }),
+        )
+        .await,
+    )
+    .await;
+    let text = prompt["result"]["messages"][0]["content"]["text"]
+        .as_str()
+        .unwrap_or_else(|| panic!("unexpected prompt response: {prompt}"));
+    assert!(text.contains("main.customers"));
+    assert!(text.contains("\"tier\""));
+    assert!(text.contains("Ada"));
+
+    let audited: Vec<(String, String)> =
+        sqlx::query_as("SELECT kind, name FROM mcp_audit_log ORDER BY id")
+            .fetch_all(&metadata_pool)
+            .await
+            .unwrap();
+    assert_eq!(
+        audited.last(),
+        Some(&("prompt".to_string(), "analyze_table".to_string()))
+    );
+
+    handle.stop().await;
+}
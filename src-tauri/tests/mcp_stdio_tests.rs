@@ -0,0 +1,178 @@
+//! Drives the standalone `dbcooper-mcp` binary over stdio, the way an MCP
+//! client that launches its server as a subprocess would.
+
+use std::process::Stdio;
+
+use serde_json::{json, Value};
+use sqlx::sqlite::SqlitePoolOptions;
+use tokio::io::{AsyncBufReadExt, AsyncWriteExt, BufReader, Lines};
+use tokio::process::{Child, ChildStdin, ChildStdout};
+
+struct StdioClient {
+    child: Child,
+    stdin: ChildStdin,
+    stdout: Lines<BufReader<ChildStdout>>,
+}
+
+impl StdioClient {
+    fn spawn(local_store: &std::path::Path) -> Self {
+        let mut child = tokio::process::Command::new(env!("CARGO_BIN_EXE_dbcooper-mcp"))
+            .env("DBCOOPER_LOCAL_STORE", local_store)
+            .stdin(Stdio::piped())
+            .stdout(Stdio::piped())
+            .stderr(Stdio::inherit())
+            .kill_on_drop(true)
+            .spawn()
+            .expect("spawn dbcooper-mcp");
+        let stdin = child.stdin.take().unwrap();
+        let stdout = BufReader::new(child.stdout.take().unwrap()).lines();
+        Self {
+            child,
+            stdin,
+            stdout,
+        }
+    }
+
+    async fn send(&mut self, message: Value) {
+        let mut line = message.to_string();
+        line.push('\n');
+        self.stdin
+            .write_all(line.as_bytes())
+            .await
+            .expect("write to dbcooper-mcp");
+    }
+
+    /// Send a request and wait for the response with the same id.
+    async fn request(&mut self, id: u64, method: &str, params: Value) -> Value {
+        self.send(json!({"jsonrpc": "2.0", "id": id, "method": method, "params": params}))
+            .await;
+        loop {
+            let line =
+                tokio::time::timeout(std::time::Duration::from_secs(30), self.stdout.next_line())
+                    .await
+                    .expect("dbcooper-mcp should answer within 30s")
+                    .expect("read from dbcooper-mcp")
+                    .expect("dbcooper-mcp closed stdout");
+            let message: Value = serde_json::from_str(&line)
+                .unwrap_or_else(|e| panic!("stdout should only carry JSON-RPC, got {line:?}: {e}"));
+            if message["id"] == id {
+                return message;
+            }
+        }
+    }
+
+    async fn call_tool(&mut self, id: u64, name: &str, arguments: Value) -> Value {
+        self.request(
+            id,
+            "tools/call",
+            json!({"name": name, "arguments": arguments}),
+        )
+        .await
+    }
+}
+
+#[tokio::test]
+async fn standalone_binary_serves_tools_from_the_local_store() {
+    let store_dir = tempfile::tempdir().expect("create local store directory");
+    let target_path = store_dir.path().join("target.db");
+    let target_pool = SqlitePoolOptions::new()
+        .max_connections(1)
+        .connect(&format!("sqlite://{}?mode=rwc", target_path.display()))
+        .await
+        .expect("create target database");
+    sqlx::query("CREATE TABLE items (id INTEGER PRIMARY KEY, name TEXT)")
+        .execute(&target_pool)
+        .await
+        .unwrap();
+    sqlx::query("INSERT INTO items (name) VALUES ('a'), ('b')")
+        .execute(&target_pool)
+        .await
+        .unwrap();
+    target_pool.close().await;
+
+    let mut client = StdioClient::spawn(store_dir.path());
+    let initialize = client
+        .request(
+            1,
+            "initialize",
+            json!({
+                "protocolVersion": "2025-03-26",
+                "capabilities": {},
+                "clientInfo": {"name": "dbcooper-stdio-test", "version": "0.0.0"}
+            }),
+        )
+        .await;
+    assert_eq!(initialize["result"]["serverInfo"]["name"], "dbcooper-mcp");
+    client
+        .send(json!({"jsonrpc": "2.0", "method": "notifications/initialized"}))
+        .await;
+
+    // The binary has created and migrated the store by now; save a
+    // connection the way the desktop app would.
+    let store = SqlitePoolOptions::new()
+        .max_connections(1)
+        .connect(&format!(
+            "sqlite://{}",
+            store_dir.path().join("db.sqlite3").display()
+        ))
+        .await
+        .expect("open local store");
+    sqlx::query(
+        "INSERT INTO connections (uuid, name, host, port, database, username, password, db_type, file_path, mcp_permission) VALUES ('items', 'Items', '', 0, '', '', '', 'sqlite', ?, 'write')",
+    )
+    .bind(target_path.to_string_lossy().as_ref())
+    .execute(&store)
+    .await
+    .expect("save connection");
+
+    let listed = client.call_tool(2, "list_connections", json!({})).await;
+    let listed: Value =
+        serde_json::from_str(listed["result"]["content"][0]["text"].as_str().unwrap()).unwrap();
+    assert_eq!(listed[0]["uuid"], "items");
+
+    let rows = client
+        .call_tool(
+            3,
+            "execute_query",
+            json!({"connection_uuid": "items", "query": "SELECT name FROM items ORDER BY id"}),
+        )
+        .await;
+    assert_eq!(
+        rows["result"]["isError"], false,
+        "unexpected response: {rows}"
+    );
+    let rows: Value =
+        serde_json::from_str(rows["result"]["content"][0]["text"].as_str().unwrap()).unwrap();
+    assert_eq!(rows["rows"], json!([["a"], ["b"]]));
+
+    // Nobody can approve writes without the desktop app.
+    let write = client
+        .call_tool(
+            4,
+            "execute_statement",
+            json!({"connection_uuid": "items", "sql": "DELETE FROM items"}),
+        )
+        .await;
+    assert_eq!(write["result"]["isError"], true);
+
+    let audited: Vec<(String, String)> =
+        sqlx::query_as("SELECT token_name, name FROM mcp_audit_log ORDER BY id")
+            .fetch_all(&store)
+            .await
+            .unwrap();
+    assert_eq!(
+        audited,
+        [
+            ("stdio client".to_string(), "list_connections".to_string()),
+            ("stdio client".to_string(), "execute_query".to_string()),
+            ("stdio client".to_string(), "execute_statement".to_string()),
+        ]
+    );
+
+    drop(client.stdin);
+    let status = tokio::time::timeout(std::time::Duration::from_secs(10), client.child.wait())
+        .await
+        .expect("dbcooper-mcp should exit when stdin closes")
+        .unwrap();
+    assert!(status.success());
+}
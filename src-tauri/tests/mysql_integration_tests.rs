@@ -1,7 +1,10 @@
 use dbcooper_lib::database::mutation::MutationPlan;
 use dbcooper_lib::database::mysql::MysqlDriver;
-use dbcooper_lib::database::{DatabaseDriver, DatabaseType, MysqlConfig, MysqlFlavor};
+use dbcooper_lib::database::{
+    DatabaseDriver, DatabaseType, MysqlConfig, MysqlFlavor, QueryLimits, SessionOptions,
+};
 use dbcooper_lib::db::models::{CreateTableColumn, CreateTableRequest, MysqlColumnModifiers};
+use serde_json::json;
 
 fn driver(engine: DatabaseType, port: i64) -> MysqlDriver {
     MysqlDriver::new(MysqlConfig {
@@ -12,6 +15,8 @@ fn driver(engine: DatabaseType, port: i64) -> MysqlDriver {
         username: "dbcooper".to_string(),
         password: "dbcooper".to_string(),
         ssl: false,
+        session: SessionOptions::default(),
+        limits: QueryLimits::default(),
     })
 }
 
@@ -90,7 +95,10 @@ async fn exercise(engine: DatabaseType, port: i64) {
         .await
         .unwrap();
     assert_eq!(data.total, 1);
-    assert_eq!(data.data[0]["amount"], "123456789012345678.123456789012");
+    assert_eq!(
+        data.data[0]["amount"],
+        json!({"type": "decimal", "value": "123456789012345678.123456789012"})
+    );
 
     let blocked = driver
         .execute_query_read_only(&format!("UPDATE `{table}` SET `label` = 'changed'"))
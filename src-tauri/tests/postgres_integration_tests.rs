@@ -5,8 +5,11 @@
 //!
 //! Run with: cargo test --test postgres_integration_tests -- --test-threads=1
 
+use dbcooper_lib::database::pool_manager::{ConnectionConfig, PoolManager};
 use dbcooper_lib::database::postgres::PostgresDriver;
-use dbcooper_lib::database::{DatabaseDriver, PostgresConfig};
+use dbcooper_lib::database::{
+    CredentialCommand, DatabaseDriver, PostgresConfig, QueryLimits, SessionOptions,
+};
 use dbcooper_lib::db::models::{
     ColumnDefault, CreateTableColumn, CreateTableRequest, FilterCondition, FilterConjunction,
     FilterExpression, FilterOperator, TableFilter,
@@ -22,6 +25,8 @@ fn create_test_driver() -> PostgresDriver {
         username: "postgres".to_string(),
         password: "postgres".to_string(),
         ssl: false,
+        session: SessionOptions::default(),
+        limits: QueryLimits::default(),
     };
     PostgresDriver::new(config)
 }
@@ -74,6 +79,8 @@ async fn test_connection_failure() {
         username: "postgres".to_string(),
         password: "postgres".to_string(),
         ssl: false,
+        session: SessionOptions::default(),
+        limits: QueryLimits::default(),
     };
     let driver = PostgresDriver::new(config);
 
@@ -91,6 +98,108 @@ async fn test_connection_failure() {
     );
 }
 
+#[tokio::test]
+async fn test_session_settings_apply_to_pooled_connections() {
+    let driver = PostgresDriver::new(PostgresConfig {
+        host: "localhost".to_string(),
+        port: 5432,
+        database: "testdb".to_string(),
+        username: "postgres".to_string(),
+        password: "postgres".to_string(),
+        ssl: false,
+        session: SessionOptions::new(
+            Some("SET statement_timeout = '5s'".to_string()),
+            [
+                ("search_path".to_string(), "pg_catalog, public".to_string()),
+                ("TimeZone".to_string(), "UTC".to_string()),
+            ]
+            .into(),
+        ),
+        limits: QueryLimits::default(),
+    });
+
+    let result = driver
+        .execute_query(
+            "SELECT current_setting('search_path') AS search_path, current_setting('statement_timeout') AS timeout, current_setting('TimeZone') AS tz",
+        )
+        .await
+        .unwrap();
+
+    assert_eq!(result.error, None);
+    assert_eq!(result.data[0]["search_path"], "pg_catalog, public");
+    assert_eq!(result.data[0]["timeout"], "5s");
+    assert_eq!(result.data[0]["tz"], "UTC");
+}
+
+#[tokio::test]
+async fn test_pool_refreshes_an_expired_credential_command_password() {
+    let admin = create_test_driver();
+    let role = format!("rotating_{}", uuid::Uuid::new_v4().simple());
+    admin
+        .execute_query(&format!("CREATE ROLE {role} LOGIN PASSWORD 'first'"))
+        .await
+        .unwrap();
+    let dir = tempfile::tempdir().unwrap();
+    let secret = dir.path().join("secret");
+    std::fs::write(&secret, "first").unwrap();
+
+    let manager = PoolManager::new();
+    let config = ConnectionConfig {
+        db_type: "postgres".to_string(),
+        host: Some("localhost".to_string()),
+        port: Some(5432),
+        database: Some("testdb".to_string()),
+        username: Some(role.clone()),
+        password: None,
+        ssl: Some(false),
+        file_path: None,
+        options: Default::default(),
+        ssh_enabled: false,
+        ssh_host: None,
+        ssh_port: None,
+        ssh_user: None,
+        ssh_password: None,
+        ssh_key_path: None,
+        ssh_use_key: false,
+        on_connect_sql: None,
+        session_settings: Default::default(),
+        limits: QueryLimits::default(),
+        credential_command: CredentialCommand::from_settings(
+            &format!("cat '{}'", secret.display()),
+            None,
+        )
+        .unwrap(),
+    };
+    manager.connect("rotating", config).await.unwrap();
+
+    // Rotate the password and drop the pooled sessions, so the next query
+    // logs in again with the cached (now expired) output.
+    std::fs::write(&secret, "second").unwrap();
+    admin
+        .execute_query(&format!("ALTER ROLE {role} PASSWORD 'second'"))
+        .await
+        .unwrap();
+    admin
+        .execute_query(&format!(
+            "SELECT pg_terminate_backend(pid) FROM pg_stat_activity WHERE usename = '{role}'"
+        ))
+        .await
+        .unwrap();
+
+    let result = manager
+        .execute_query("rotating", "SELECT current_user AS who")
+        .await
+        .unwrap();
+    assert_eq!(result.error, None);
+    assert_eq!(result.rows[0][0], json!(role));
+
+    manager.disconnect("rotating").await;
+    admin
+        .execute_query(&format!("DROP ROLE {role}"))
+        .await
+        .unwrap();
+}
+
 // ============================================================================
 // List Tables Tests
 // ============================================================================
@@ -125,6 +234,15 @@ async fn test_list_tables() {
     drop_table(&driver, &table_name).await;
 }
 
+#[tokio::test]
+async fn test_list_databases_skips_templates() {
+    let driver = create_test_driver();
+
+    let databases = driver.list_databases().await.unwrap();
+    assert!(databases.contains(&"testdb".to_string()));
+    assert!(!databases.iter().any(|name| name.starts_with("template")));
+}
+
 #[tokio::test]
 async fn test_list_tables_excludes_system() {
     let driver = create_test_driver();
@@ -204,7 +322,7 @@ async fn test_create_table_uses_requested_schema_and_reports_database_errors() {
         .any(|index| index.unique && index.columns == vec!["slug"]));
 
     let duplicate_error = driver.create_table(&request).await.unwrap_err();
-    assert!(duplicate_error.contains("already exists"));
+    assert!(duplicate_error.message.contains("already exists"));
 
     let mut missing_schema_request = request.clone();
     missing_schema_request.schema = "missing_create_schema".to_string();
@@ -213,7 +331,7 @@ async fn test_create_table_uses_requested_schema_and_reports_database_errors() {
         .create_table(&missing_schema_request)
         .await
         .unwrap_err();
-    assert!(missing_schema_error.contains("does not exist"));
+    assert!(missing_schema_error.message.contains("does not exist"));
 
     drop_schema(&driver, &schema).await;
 }
@@ -1108,10 +1226,105 @@ async fn test_various_data_types() {
     assert!(row.get("json_col").is_some());
     assert!(row.get("uuid_col").is_some());
 
+    // Typed values survive the round trip, in text and binary format alike
+    assert_eq!(row["int8_col"], json!(3));
+    assert_eq!(
+        row["numeric_col"],
+        json!({"type": "decimal", "value": "100.50"})
+    );
+    assert_eq!(
+        row["date_col"],
+        json!({"type": "date", "value": "2024-01-01"})
+    );
+    assert_eq!(
+        row["timestamp_col"],
+        json!({"type": "timestamp", "value": "2024-01-01 12:00:00"})
+    );
+    assert_eq!(
+        row["json_col"],
+        json!({"type": "json", "value": {"key": "value"}})
+    );
+    let binary = driver
+        .execute_query_read_only(&format!("SELECT * FROM \"{}\"", table_name))
+        .await
+        .unwrap();
+    assert_eq!(&binary.data[0], row);
+    assert_eq!(result.columns[5].name, "numeric_col");
+    assert_eq!(result.columns[5].data_type, "NUMERIC");
+
     // Cleanup
     drop_table(&driver, &table_name).await;
 }
 
+#[tokio::test]
+async fn test_query_result_columns_map_back_to_their_table() {
+    let driver = create_test_driver();
+    let table_name = test_table_name("sources");
+
+    driver
+        .execute_query(&format!(
+            "CREATE TABLE \"{}\" (id SERIAL PRIMARY KEY, name TEXT NOT NULL, note TEXT)",
+            table_name
+        ))
+        .await
+        .unwrap();
+    driver
+        .execute_query(&format!(
+            "INSERT INTO \"{}\" (name, note) VALUES ('a', NULL)",
+            table_name
+        ))
+        .await
+        .unwrap();
+
+    let result = driver
+        .execute_query(&format!(
+            "SELECT id, name AS label, note FROM \"{}\"",
+            table_name
+        ))
+        .await
+        .unwrap();
+    let source = result.columns[1].source.as_ref().unwrap();
+    assert_eq!(source.schema, "public");
+    assert_eq!(source.table, table_name);
+    assert_eq!(source.column, "name");
+    assert_eq!(result.columns[1].nullable, Some(false));
+    assert_eq!(result.columns[2].nullable, Some(true));
+    let target = result.edit_target.as_ref().unwrap();
+    assert_eq!(target.table, table_name);
+    assert_eq!(target.primary_key, vec!["id"]);
+
+    // A self-join repeats the column names: both survive in `rows`, and the
+    // result no longer maps back to one row of one table.
+    let joined = driver
+        .execute_query_read_only(&format!(
+            "SELECT a.id, b.id, a.id + 1 AS next FROM \"{0}\" a JOIN \"{0}\" b ON a.id = b.id",
+            table_name
+        ))
+        .await
+        .unwrap();
+    assert_eq!(joined.columns[0].name, "id");
+    assert_eq!(joined.columns[1].name, "id");
+    assert_eq!(joined.rows[0].len(), 3);
+    assert!(joined.columns[2].source.is_none());
+    assert!(joined.edit_target.is_none());
+
+    // Table details are cached, but DDL through the driver drops them.
+    driver
+        .execute_query(&format!(
+            "ALTER TABLE \"{0}\" DROP CONSTRAINT \"{0}_pkey\", ADD PRIMARY KEY (id, name)",
+            table_name
+        ))
+        .await
+        .unwrap();
+    let rekeyed = driver
+        .execute_query(&format!("SELECT id, name, note FROM \"{}\"", table_name))
+        .await
+        .unwrap();
+    assert_eq!(rekeyed.edit_target.unwrap().primary_key, vec!["id", "name"]);
+
+    drop_table(&driver, &table_name).await;
+}
+
 // ============================================================================
 // Update/Delete Isolation Tests
 // ============================================================================
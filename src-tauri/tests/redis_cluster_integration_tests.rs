@@ -0,0 +1,192 @@
+//! Integration tests for Redis Cluster and Sentinel modes
+//!
+//! Requires the cluster (ports 7000-7005) and the sentinel (port 26379, watching
+//! the `redis` service as `mymaster`) from docker-compose:
+//! docker-compose up -d redis redis-cluster redis-sentinel
+//!
+//! Run with: cargo test --test redis_cluster_integration_tests -- --test-threads=1
+
+use dbcooper_lib::database::redis::RedisDriver;
+use dbcooper_lib::database::{DatabaseDriver, QueryLimits, RedisConfig, RedisTopology};
+
+fn cluster_driver() -> RedisDriver {
+    RedisDriver::new(RedisConfig {
+        host: "127.0.0.1".to_string(),
+        port: 7000,
+        username: None,
+        password: None,
+        db: None,
+        tls: false,
+        topology: RedisTopology::Cluster {
+            nodes: vec![("127.0.0.1".to_string(), 7001)],
+        },
+        limits: QueryLimits::default(),
+    })
+}
+
+fn sentinel_driver(master_name: &str) -> RedisDriver {
+    RedisDriver::new(RedisConfig {
+        host: "127.0.0.1".to_string(),
+        port: 26379,
+        username: None,
+        password: None,
+        db: Some(15),
+        tls: false,
+        topology: RedisTopology::Sentinel {
+            master_name: master_name.to_string(),
+            nodes: Vec::new(),
+            sentinel_password: None,
+        },
+        limits: QueryLimits::default(),
+    })
+}
+
+fn test_prefix(name: &str) -> String {
+    format!("test:{}:{}", name, uuid::Uuid::new_v4())
+}
+
+// ============================================================================
+// Cluster
+// ============================================================================
+
+#[tokio::test]
+async fn test_cluster_connection() {
+    let result = cluster_driver().test_connection().await.unwrap();
+    assert!(
+        result.success,
+        "Make sure the cluster is running (docker-compose up -d redis-cluster). Message: {}",
+        result.message
+    );
+}
+
+#[tokio::test]
+async fn test_cluster_routes_keys_to_their_slots() {
+    let driver = cluster_driver();
+    let prefix = test_prefix("slots");
+    let keys: Vec<String> = (0..20).map(|i| format!("{prefix}:{i}")).collect();
+
+    for key in &keys {
+        driver.set_key(key, key, Some(60)).await.unwrap();
+    }
+    for key in &keys {
+        let details = driver.get_key_details(key).await.unwrap();
+        assert_eq!(details.value, serde_json::json!(key));
+    }
+
+    for key in &keys {
+        driver.delete_key(key).await.unwrap();
+    }
+}
+
+#[tokio::test]
+async fn test_cluster_search_keys_scans_every_master() {
+    let driver = cluster_driver();
+    let prefix = test_prefix("scan");
+    let keys: Vec<String> = (0..60).map(|i| format!("{prefix}:{i}")).collect();
+    for key in &keys {
+        driver.set_key(key, "1", Some(60)).await.unwrap();
+    }
+
+    // Page through with a small limit so the cursor crosses nodes.
+    let mut found = Vec::new();
+    let mut cursor = 0;
+    loop {
+        let page = driver
+            .search_keys(&format!("{prefix}:*"), 7, cursor, |_, _, _, _| {})
+            .await
+            .unwrap();
+        found.extend(page.keys.into_iter().map(|key| key.key));
+        if page.scan_complete {
+            break;
+        }
+        cursor = page.cursor;
+    }
+    found.sort();
+    found.dedup();
+    let mut expected = keys.clone();
+    expected.sort();
+    assert_eq!(found, expected);
+
+    for key in &keys {
+        driver.delete_key(key).await.unwrap();
+    }
+}
+
+#[tokio::test]
+async fn test_cluster_info_has_a_row_per_node() {
+    let result = cluster_driver().execute_query("INFO server").await.unwrap();
+    assert!(result.error.is_none(), "{:?}", result.error);
+    assert!(
+        result.row_count >= 3,
+        "expected every node, got {:?}",
+        result.data
+    );
+    for row in &result.data {
+        assert!(row["node"].is_string());
+        assert!(row["info"].as_str().unwrap().contains("redis_version"));
+    }
+}
+
+#[tokio::test]
+async fn test_cluster_script_spans_slots() {
+    let driver = cluster_driver();
+    let prefix = test_prefix("script");
+    // The hash tags pin the keys to slots 15495 and 3300.
+    let (first, second) = (format!("{{a}}:{prefix}"), format!("{{b}}:{prefix}"));
+
+    let result = driver
+        .execute_query(&format!(
+            "SET {first} 1\nSET {second} 2\nINCR {second}\nLPUSH {first} x\nGET {second}"
+        ))
+        .await
+        .unwrap();
+    assert!(result.error.is_none(), "{:?}", result.error);
+    assert_eq!(result.row_count, 5);
+    assert_eq!(result.data[2]["result"], 3);
+    // A failed command is reported on its row and the rest still run.
+    assert!(result.data[3]["error"]
+        .as_str()
+        .unwrap()
+        .contains("WRONGTYPE"));
+    assert_eq!(result.data[4]["result"], "3");
+
+    driver.delete_key(&first).await.unwrap();
+    driver.delete_key(&second).await.unwrap();
+}
+
+// ============================================================================
+// Sentinel
+// ============================================================================
+
+#[tokio::test]
+async fn test_sentinel_resolves_the_master() {
+    let driver = sentinel_driver("mymaster");
+    let result = driver.test_connection().await.unwrap();
+    assert!(
+        result.success,
+        "Make sure the sentinel is running (docker-compose up -d redis redis-sentinel). Message: {}",
+        result.message
+    );
+
+    let key = test_prefix("sentinel");
+    driver
+        .set_key(&key, "through sentinel", Some(60))
+        .await
+        .unwrap();
+    let result = driver.execute_query(&format!("GET {key}")).await.unwrap();
+    assert_eq!(result.data, vec![serde_json::json!("through sentinel")]);
+
+    let role = driver.execute_query("ROLE").await.unwrap();
+    assert_eq!(role.data[0][0], serde_json::json!("master"));
+
+    driver.delete_key(&key).await.unwrap();
+}
+
+#[tokio::test]
+async fn test_sentinel_unknown_master_fails_to_connect() {
+    let result = sentinel_driver("no-such-master")
+        .test_connection()
+        .await
+        .unwrap();
+    assert!(!result.success);
+}
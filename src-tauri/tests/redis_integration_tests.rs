@@ -7,18 +7,28 @@
 
 use std::collections::HashMap;
 
+use dbcooper_lib::database::bulk_keys::{BulkKeyOperation, BulkKeyRequest, KeyTarget};
+use dbcooper_lib::database::key_value::{ConsumerGroup, LiveMessage, LiveSource};
+use dbcooper_lib::database::keyspace_analysis::AnalysisOptions;
 use dbcooper_lib::database::redis::RedisDriver;
-use dbcooper_lib::database::{DatabaseDriver, RedisConfig};
+use dbcooper_lib::database::redis_scripts::{script_hash, ScriptCall};
+use dbcooper_lib::database::{DatabaseDriver, QueryLimits, RedisConfig, RedisTopology};
 
 /// Helper function to create a test Redis driver
 fn create_test_driver() -> RedisDriver {
+    create_test_driver_for_db(15) // Use database 15 for tests to avoid conflicts
+}
+
+fn create_test_driver_for_db(db: i64) -> RedisDriver {
     let config = RedisConfig {
         host: "localhost".to_string(),
         port: 6379,
         username: None,
         password: None,
-        db: Some(15), // Use database 15 for tests to avoid conflicts
+        db: Some(db),
         tls: false,
+        topology: RedisTopology::Standalone,
+        limits: QueryLimits::default(),
     };
     RedisDriver::new(config)
 }
@@ -66,6 +76,8 @@ async fn test_connection_failure() {
         password: None,
         db: None,
         tls: false,
+        topology: RedisTopology::Standalone,
+        limits: QueryLimits::default(),
     };
     let driver = RedisDriver::new(config);
 
@@ -178,6 +190,69 @@ async fn test_execute_query_invalid_command() {
     );
 }
 
+#[tokio::test]
+async fn test_execute_query_keeps_quoted_arguments_together() {
+    let driver = create_test_driver();
+    let key = test_key("quoted");
+
+    let result = driver
+        .execute_query(&format!(r#"SET "{key}" "hello world""#))
+        .await
+        .unwrap();
+    assert!(result.error.is_none(), "{:?}", result.error);
+
+    let result = driver.execute_query(&format!("GET '{key}'")).await.unwrap();
+    assert_eq!(result.data, vec![serde_json::json!("hello world")]);
+
+    cleanup_keys!(driver, &key);
+}
+
+#[tokio::test]
+async fn test_execute_query_pipelines_scripts_with_per_command_results() {
+    let driver = create_test_driver();
+    let key = test_key("pipeline");
+
+    let result = driver
+        .execute_query(&format!(
+            "# runbook snippet\nSET {key} 1\nINCR {key}\nHGET {key} field\nGET {key}"
+        ))
+        .await
+        .unwrap();
+    assert!(result.error.is_none(), "{:?}", result.error);
+    assert_eq!(result.row_count, 4);
+    assert_eq!(result.data[1]["result"], 2);
+    assert!(result.data[2]["error"]
+        .as_str()
+        .unwrap()
+        .contains("WRONGTYPE"));
+    assert_eq!(result.data[3]["result"], "2");
+    assert_eq!(result.data[3]["command"], format!("GET {key}"));
+
+    cleanup_keys!(driver, &key);
+}
+
+#[tokio::test]
+async fn test_execute_query_runs_multi_exec_as_a_transaction() {
+    let driver = create_test_driver();
+    let key = test_key("transaction");
+
+    let result = driver
+        .execute_query(&format!("MULTI\nINCRBY {key} 5\nINCRBY {key} 5\nEXEC"))
+        .await
+        .unwrap();
+    assert!(result.error.is_none(), "{:?}", result.error);
+    assert_eq!(result.row_count, 2);
+    assert_eq!(result.data[1]["result"], 10);
+
+    let blocked = driver
+        .execute_query_read_only(&format!("GET {key}\nDEL {key}"))
+        .await
+        .unwrap();
+    assert!(blocked.error.unwrap().contains("DEL"));
+
+    cleanup_keys!(driver, &key);
+}
+
 // ============================================================================
 // String Key Tests
 // ============================================================================
@@ -626,3 +701,681 @@ async fn test_get_key_details_includes_metadata() {
 
     cleanup_keys!(driver, &key);
 }
+
+// ============================================================================
+// Live Subscription Tests
+// ============================================================================
+
+/// Run `driver.watch(source)` in the background, collecting what it delivers.
+fn spawn_watch(
+    driver: std::sync::Arc<RedisDriver>,
+    source: LiveSource,
+) -> (
+    std::sync::Arc<std::sync::Mutex<Vec<LiveMessage>>>,
+    tokio::task::JoinHandle<()>,
+) {
+    let received = std::sync::Arc::new(std::sync::Mutex::new(Vec::new()));
+    let sink_received = received.clone();
+    let task = tokio::spawn(async move {
+        let sink = move |message: LiveMessage| sink_received.lock().unwrap().push(message);
+        let _ = driver.watch(&source, &sink).await;
+    });
+    (received, task)
+}
+
+/// Wait until `received` holds `count` messages, running `produce` between checks.
+async fn wait_for_messages<F, Fut>(
+    received: &std::sync::Mutex<Vec<LiveMessage>>,
+    count: usize,
+    mut produce: F,
+) -> Vec<LiveMessage>
+where
+    F: FnMut() -> Fut,
+    Fut: std::future::Future<Output = ()>,
+{
+    for _ in 0..50 {
+        produce().await;
+        if received.lock().unwrap().len() >= count {
+            break;
+        }
+        tokio::time::sleep(std::time::Duration::from_millis(100)).await;
+    }
+    received.lock().unwrap().clone()
+}
+
+#[tokio::test]
+async fn test_watch_channels_receives_published_messages() {
+    let driver = std::sync::Arc::new(create_test_driver());
+    let channel = test_key("channel");
+    let (received, task) = spawn_watch(
+        driver.clone(),
+        LiveSource::Channels {
+            channels: vec![channel.clone()],
+            patterns: vec![format!("{channel}.*")],
+        },
+    );
+
+    // Publish until the subscriber is registered and has seen both deliveries.
+    let messages = wait_for_messages(&received, 2, || {
+        let driver = driver.clone();
+        let channel = channel.clone();
+        async move {
+            let published = driver
+                .execute_query(&format!("PUBLISH {channel} \"hello world\""))
+                .await
+                .unwrap();
+            if published.data[0] != serde_json::json!(0) {
+                driver
+                    .execute_query(&format!("PUBLISH {channel}.eu hallo"))
+                    .await
+                    .unwrap();
+            }
+        }
+    })
+    .await;
+    task.abort();
+
+    let direct = messages.iter().find(|m| m.source == channel).unwrap();
+    assert_eq!(direct.payload, serde_json::json!("hello world"));
+    assert_eq!(direct.pattern, None);
+    let matched = messages
+        .iter()
+        .find(|m| m.source == format!("{channel}.eu"))
+        .unwrap();
+    assert_eq!(matched.pattern, Some(format!("{channel}.*")));
+}
+
+#[tokio::test]
+async fn test_watch_stream_tails_new_entries() {
+    let driver = std::sync::Arc::new(create_test_driver());
+    let key = test_key("stream");
+    driver
+        .execute_query(&format!("XADD {key} * status old"))
+        .await
+        .unwrap();
+
+    let (received, task) = spawn_watch(
+        driver.clone(),
+        LiveSource::Stream {
+            key: key.clone(),
+            start_id: None,
+            group: None,
+        },
+    );
+    let messages = wait_for_messages(&received, 1, || {
+        let driver = driver.clone();
+        let key = key.clone();
+        async move {
+            driver
+                .execute_query(&format!("XADD {key} * status new"))
+                .await
+                .unwrap();
+        }
+    })
+    .await;
+    task.abort();
+
+    // Only entries added after the subscription started are delivered.
+    assert!(!messages.is_empty());
+    assert!(messages
+        .iter()
+        .all(|m| m.payload == serde_json::json!({ "status": "new" })));
+    assert!(messages[0].id.is_some());
+
+    cleanup_keys!(driver, &key);
+}
+
+#[tokio::test]
+async fn test_watch_stream_group_acknowledges_entries() {
+    let driver = std::sync::Arc::new(create_test_driver());
+    let key = test_key("group");
+    let (received, task) = spawn_watch(
+        driver.clone(),
+        LiveSource::Stream {
+            key: key.clone(),
+            start_id: None,
+            group: Some(ConsumerGroup {
+                group: "dbcooper".to_string(),
+                consumer: "tests".to_string(),
+                ack: true,
+            }),
+        },
+    );
+
+    let messages = wait_for_messages(&received, 1, || {
+        let driver = driver.clone();
+        let key = key.clone();
+        async move {
+            // The group exists once the consumer has created it.
+            let groups = driver
+                .execute_query(&format!("XINFO GROUPS {key}"))
+                .await
+                .unwrap();
+            if groups.error.is_none() && !groups.data.is_empty() {
+                driver
+                    .execute_query(&format!("XADD {key} * status paid"))
+                    .await
+                    .unwrap();
+            }
+        }
+    })
+    .await;
+    assert!(!messages.is_empty(), "the consumer group delivered nothing");
+
+    // Entries are acknowledged right after they are delivered.
+    let mut pending = serde_json::Value::Null;
+    for _ in 0..50 {
+        let result = driver
+            .execute_query(&format!("XPENDING {key} dbcooper"))
+            .await
+            .unwrap();
+        pending = result.data[0][0].clone();
+        if pending == serde_json::json!(0) {
+            break;
+        }
+        tokio::time::sleep(std::time::Duration::from_millis(100)).await;
+    }
+    task.abort();
+    assert_eq!(pending, serde_json::json!(0));
+
+    cleanup_keys!(driver, &key);
+}
+
+// ============================================================================
+// Keyspace Analysis Tests
+// ============================================================================
+
+#[tokio::test]
+async fn test_analyze_keyspace_groups_keys_and_ranks_the_biggest() {
+    let driver = create_test_driver();
+    let prefix = format!("analysistest:{}", uuid::Uuid::new_v4());
+    let users: Vec<String> = (1..=3).map(|id| format!("{prefix}:user:{id}")).collect();
+    let feed = format!("{prefix}:feed");
+    for user in &users {
+        let fields = HashMap::from([("name".to_string(), "Ada".to_string())]);
+        driver.set_hash_key(user, &fields, Some(600)).await.unwrap();
+    }
+    let items: Vec<String> = (0..500).map(|item| format!("item-{item:04}")).collect();
+    driver.set_list_key(&feed, &items, None).await.unwrap();
+
+    let progress = std::sync::Mutex::new(Vec::new());
+    let report = driver
+        .analyze_keyspace(
+            &AnalysisOptions {
+                pattern: Some(format!("{prefix}:*")),
+                top_keys: 2,
+                ..Default::default()
+            },
+            &|update| progress.lock().unwrap().push(update.clone()),
+        )
+        .await
+        .unwrap();
+
+    assert_eq!(report.scanned_keys, 4);
+    assert_eq!(report.keys_without_expiry, 1);
+    assert_eq!(report.biggest_keys.len(), 2);
+    assert_eq!(report.biggest_keys[0].key, feed);
+    assert_eq!(report.biggest_keys[0].key_type, "list");
+    assert!(report.biggest_keys[0].encoding.is_some());
+    let users_pattern = report
+        .by_pattern
+        .iter()
+        .find(|pattern| pattern.pattern == "analysistest:*:user:*")
+        .expect("users are grouped under one pattern");
+    assert_eq!(users_pattern.keys, 3);
+    assert_eq!(users_pattern.keys_without_expiry, 0);
+    let under_an_hour = report
+        .ttl_distribution
+        .iter()
+        .find(|bucket| bucket.name == "Under 1 hour")
+        .unwrap();
+    assert_eq!(under_an_hour.keys, 3);
+    assert_eq!(progress.lock().unwrap().last().unwrap().scanned_keys, 4);
+
+    cleanup_keys!(driver, &users[0], &users[1], &users[2], &feed);
+}
+
+// ============================================================================
+// Bulk Key Tests
+// ============================================================================
+
+async fn seed_bulk_keys(driver: &RedisDriver, prefix: &str, count: usize) -> Vec<String> {
+    let keys: Vec<String> = (0..count).map(|id| format!("{prefix}:{id}")).collect();
+    for key in &keys {
+        driver.set_key(key, "cached", None).await.unwrap();
+    }
+    keys
+}
+
+fn bulk_request(prefix: &str, operation: BulkKeyOperation) -> BulkKeyRequest {
+    BulkKeyRequest {
+        pattern: format!("{prefix}:*"),
+        operation,
+    }
+}
+
+#[tokio::test]
+async fn test_bulk_match_keys_counts_and_samples() {
+    let driver = create_test_driver();
+    let prefix = format!("bulktest:{}", uuid::Uuid::new_v4());
+    let keys = seed_bulk_keys(&driver, &prefix, 30).await;
+
+    let matches = driver.match_keys(&format!("{prefix}:*"), 5).await.unwrap();
+    assert_eq!(matches.count, 30);
+    assert_eq!(matches.sample.len(), 5);
+
+    for key in &keys {
+        cleanup_keys!(driver, key);
+    }
+}
+
+#[tokio::test]
+async fn test_bulk_delete_set_ttl_and_clear_ttl() {
+    let driver = create_test_driver();
+    let prefix = format!("bulktest:{}", uuid::Uuid::new_v4());
+    let keys = seed_bulk_keys(&driver, &prefix, 1_200).await;
+
+    let report = driver
+        .bulk_update(
+            &bulk_request(&prefix, BulkKeyOperation::SetTtl { ttl: 600 }),
+            None,
+            &|_| {},
+        )
+        .await
+        .unwrap();
+    assert_eq!((report.succeeded, report.failed), (1_200, 0));
+    assert!(driver.get_key_details(&keys[0]).await.unwrap().ttl > 0);
+
+    let report = driver
+        .bulk_update(
+            &bulk_request(&prefix, BulkKeyOperation::ClearTtl),
+            None,
+            &|_| {},
+        )
+        .await
+        .unwrap();
+    assert_eq!(report.succeeded, 1_200);
+    assert_eq!(driver.get_key_details(&keys[0]).await.unwrap().ttl, -1);
+
+    let batches = std::sync::Mutex::new(0);
+    let report = driver
+        .bulk_update(
+            &bulk_request(&prefix, BulkKeyOperation::Delete),
+            None,
+            &|_| {
+                *batches.lock().unwrap() += 1;
+            },
+        )
+        .await
+        .unwrap();
+    assert_eq!(report.succeeded, 1_200);
+    assert!(*batches.lock().unwrap() > 1, "keys are deleted in batches");
+    assert_eq!(
+        driver
+            .match_keys(&format!("{prefix}:*"), 0)
+            .await
+            .unwrap()
+            .count,
+        0
+    );
+}
+
+#[tokio::test]
+async fn test_bulk_rename_rewrites_the_prefix_and_keeps_existing_targets() {
+    let driver = create_test_driver();
+    let prefix = format!("bulktest:{}", uuid::Uuid::new_v4());
+    let keys = seed_bulk_keys(&driver, &format!("{prefix}:old"), 3).await;
+    let existing = format!("{prefix}:new:0");
+    driver.set_key(&existing, "kept", None).await.unwrap();
+
+    let report = driver
+        .bulk_update(
+            &bulk_request(
+                &format!("{prefix}:old"),
+                BulkKeyOperation::Rename {
+                    from_prefix: format!("{prefix}:old:"),
+                    to_prefix: format!("{prefix}:new:"),
+                    replace: false,
+                },
+            ),
+            None,
+            &|_| {},
+        )
+        .await
+        .unwrap();
+    assert_eq!((report.succeeded, report.skipped), (2, 1));
+    assert_eq!(
+        driver.get_key_details(&existing).await.unwrap().value,
+        serde_json::json!("kept")
+    );
+    assert!(driver
+        .get_key_details(&format!("{prefix}:new:2"))
+        .await
+        .is_ok());
+
+    cleanup_keys!(
+        driver,
+        &keys[0],
+        &existing,
+        &format!("{prefix}:new:1"),
+        &format!("{prefix}:new:2")
+    );
+}
+
+#[tokio::test]
+async fn test_bulk_move_to_another_database_and_copy_to_another_connection() {
+    let driver = create_test_driver();
+    let other = create_test_driver_for_db(14);
+    let prefix = format!("bulktest:{}", uuid::Uuid::new_v4());
+    let keys = seed_bulk_keys(&driver, &prefix, 3).await;
+    driver.update_ttl(&keys[0], Some(600)).await.unwrap();
+
+    // Copy to another connection keeps the source keys and their TTLs.
+    let report = driver
+        .bulk_update(
+            &bulk_request(
+                &prefix,
+                BulkKeyOperation::Copy {
+                    target: KeyTarget::Connection {
+                        connection_uuid: "other".to_string(),
+                    },
+                    replace: false,
+                },
+            ),
+            other.key_value(),
+            &|_| {},
+        )
+        .await
+        .unwrap();
+    assert_eq!(report.succeeded, 3, "{:?}", report.errors);
+    assert!(other.get_key_details(&keys[0]).await.unwrap().ttl > 0);
+    assert_eq!(
+        driver
+            .match_keys(&format!("{prefix}:*"), 0)
+            .await
+            .unwrap()
+            .count,
+        3
+    );
+
+    // Moving to the same database skips the keys that already exist there.
+    let report = driver
+        .bulk_update(
+            &bulk_request(
+                &prefix,
+                BulkKeyOperation::Move {
+                    target: KeyTarget::Database { db: 14 },
+                    replace: false,
+                },
+            ),
+            None,
+            &|_| {},
+        )
+        .await
+        .unwrap();
+    assert_eq!(report.skipped, 3);
+
+    let report = driver
+        .bulk_update(
+            &bulk_request(
+                &prefix,
+                BulkKeyOperation::Move {
+                    target: KeyTarget::Database { db: 14 },
+                    replace: true,
+                },
+            ),
+            None,
+            &|_| {},
+        )
+        .await
+        .unwrap();
+    assert_eq!(report.succeeded, 3);
+    assert_eq!(
+        driver
+            .match_keys(&format!("{prefix}:*"), 0)
+            .await
+            .unwrap()
+            .count,
+        0
+    );
+    assert_eq!(
+        other
+            .match_keys(&format!("{prefix}:*"), 0)
+            .await
+            .unwrap()
+            .count,
+        3
+    );
+
+    for key in &keys {
+        cleanup_keys!(other, key);
+    }
+}
+
+// ============================================================================
+// Server Diagnostics Tests
+// ============================================================================
+
+#[tokio::test]
+async fn test_slowlog_records_slow_commands() {
+    let driver = create_test_driver();
+    driver
+        .execute_query("CONFIG SET slowlog-log-slower-than 0")
+        .await
+        .unwrap();
+    let key = test_key("slowlog");
+    driver.set_key(&key, "value", None).await.unwrap();
+    let entries = driver.slowlog(128).await;
+    driver
+        .execute_query("CONFIG SET slowlog-log-slower-than 10000")
+        .await
+        .unwrap();
+
+    let entries = entries.unwrap();
+    assert!(entries
+        .iter()
+        .any(|entry| entry.command.iter().any(|arg| arg == &key)));
+    assert!(entries
+        .windows(2)
+        .all(|pair| pair[0].timestamp >= pair[1].timestamp));
+
+    cleanup_keys!(driver, &key);
+}
+
+#[tokio::test]
+async fn test_server_info_and_latency_parse() {
+    let driver = create_test_driver();
+    let info = driver.server_info(None).await.unwrap();
+    assert_eq!(info.len(), 1);
+    let server = info[0]
+        .sections
+        .iter()
+        .find(|section| section.name == "Server")
+        .expect("INFO has a Server section");
+    assert!(server
+        .fields
+        .iter()
+        .any(|field| field.key == "redis_version"));
+
+    let memory = driver.server_info(Some("memory")).await.unwrap();
+    assert_eq!(memory[0].sections.len(), 1);
+
+    // Nothing has to have spiked; the replies only have to parse.
+    driver.latency_latest().await.unwrap();
+    driver.latency_history("command").await.unwrap();
+}
+
+#[tokio::test]
+async fn test_client_list_and_kill_client() {
+    let driver = create_test_driver();
+    let victim = create_test_driver();
+    victim
+        .execute_query("CLIENT SETNAME dbcooper-kill-test")
+        .await
+        .unwrap();
+
+    let clients = driver.client_list().await.unwrap();
+    let client = clients
+        .iter()
+        .find(|client| client.name.as_deref() == Some("dbcooper-kill-test"))
+        .expect("the named client is listed")
+        .clone();
+
+    assert!(driver
+        .kill_client(None, client.id, "10.255.255.1:1")
+        .await
+        .is_err());
+    assert!(driver
+        .kill_client(None, client.id, &client.addr)
+        .await
+        .unwrap());
+    assert!(!driver
+        .kill_client(None, client.id, &client.addr)
+        .await
+        .unwrap());
+
+    // The driver refuses to kill the connection it is using.
+    let own = driver.execute_query("CLIENT ID").await.unwrap();
+    let own_id = own.data[0][0].as_u64().unwrap_or_default();
+    let own_addr = driver
+        .client_list()
+        .await
+        .unwrap()
+        .into_iter()
+        .find(|client| client.id == own_id)
+        .map(|client| client.addr);
+    if let Some(own_addr) = own_addr {
+        assert!(driver.kill_client(None, own_id, &own_addr).await.is_err());
+    }
+}
+
+#[tokio::test]
+async fn test_watch_monitor_captures_commands_until_its_time_is_up() {
+    let driver = std::sync::Arc::new(create_test_driver());
+    let key = test_key("monitor");
+    let (received, task) = spawn_watch(driver.clone(), LiveSource::Monitor { duration_secs: 2 });
+
+    let messages = wait_for_messages(&received, 1, || async {
+        driver.set_key(&key, "watched", None).await.unwrap();
+    })
+    .await;
+    assert!(messages
+        .iter()
+        .any(|message| message.payload["command"][1] == serde_json::json!(key)));
+
+    // The capture ends on its own.
+    tokio::time::timeout(std::time::Duration::from_secs(10), task)
+        .await
+        .expect("MONITOR stops after its duration")
+        .unwrap();
+
+    cleanup_keys!(driver, &key);
+}
+
+// ============================================================================
+// Script and Function Tests
+// ============================================================================
+
+#[tokio::test]
+async fn test_eval_script_loads_uncached_scripts_and_binds_keys_and_args() {
+    let driver = create_test_driver();
+    let key = test_key("script");
+    // A unique comment keeps the script out of any cache left by other runs.
+    let source = format!(
+        "-- {}\nreturn redis.call('INCRBY', KEYS[1], ARGV[1])",
+        uuid::Uuid::new_v4()
+    );
+    let sha = script_hash(&source);
+    assert_eq!(
+        driver
+            .scripts_exist(std::slice::from_ref(&sha))
+            .await
+            .unwrap(),
+        vec![false]
+    );
+
+    let call = ScriptCall {
+        keys: vec![key.clone()],
+        args: vec!["5".to_string()],
+    };
+    let result = driver.eval_script(&source, &call, false).await.unwrap();
+    assert!(result.error.is_none(), "{:?}", result.error);
+    assert_eq!(result.data, vec![serde_json::json!(5)]);
+    assert_eq!(
+        driver
+            .scripts_exist(std::slice::from_ref(&sha))
+            .await
+            .unwrap(),
+        vec![true]
+    );
+    assert_eq!(driver.load_script(&source).await.unwrap(), sha);
+
+    // EVALSHA_RO refuses a script that writes.
+    let read_only = driver.eval_script(&source, &call, true).await.unwrap();
+    assert!(read_only.error.is_some());
+
+    cleanup_keys!(driver, &key);
+}
+
+#[tokio::test]
+async fn test_function_libraries_load_list_call_and_delete() {
+    let driver = create_test_driver();
+    let key = test_key("function");
+    let library = format!("lib_{}", uuid::Uuid::new_v4().simple());
+    let source = format!(
+        "#!lua name={library}\n\
+         redis.register_function('{library}_incr', function(keys, args) return redis.call('INCR', keys[1]) end)\n\
+         redis.register_function{{function_name='{library}_get', callback=function(keys) return redis.call('GET', keys[1]) end, flags={{'no-writes'}}}}"
+    );
+
+    assert_eq!(
+        driver.load_function_library(&source, false).await.unwrap(),
+        library
+    );
+    assert!(driver.load_function_library(&source, false).await.is_err());
+    driver.load_function_library(&source, true).await.unwrap();
+
+    let libraries = driver.list_functions(true).await.unwrap();
+    let loaded = libraries
+        .iter()
+        .find(|candidate| candidate.name == library)
+        .expect("the library is listed");
+    assert_eq!(loaded.engine, "LUA");
+    assert_eq!(loaded.code.as_deref(), Some(source.as_str()));
+    let get = loaded
+        .functions
+        .iter()
+        .find(|function| function.name == format!("{library}_get"))
+        .unwrap();
+    assert_eq!(get.flags, vec!["no-writes".to_string()]);
+
+    let call = ScriptCall {
+        keys: vec![key.clone()],
+        args: vec![],
+    };
+    let incremented = driver
+        .call_function(&format!("{library}_incr"), &call, false)
+        .await
+        .unwrap();
+    assert_eq!(incremented.data, vec![serde_json::json!(1)]);
+    let read = driver
+        .call_function(&format!("{library}_get"), &call, true)
+        .await
+        .unwrap();
+    assert_eq!(read.data, vec![serde_json::json!("1")]);
+    let refused = driver
+        .call_function(&format!("{library}_incr"), &call, true)
+        .await
+        .unwrap();
+    assert!(refused.error.is_some());
+
+    driver.delete_function_library(&library).await.unwrap();
+    assert!(!driver
+        .list_functions(false)
+        .await
+        .unwrap()
+        .iter()
+        .any(|candidate| candidate.name == library));
+
+    cleanup_keys!(driver, &key);
+}
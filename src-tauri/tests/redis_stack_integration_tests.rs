@@ -0,0 +1,198 @@
+//! Integration tests for RedisJSON and RediSearch
+//!
+//! Requires the redis-stack service (port 6380) from docker-compose:
+//! docker-compose up -d redis-stack
+//!
+//! RediSearch only indexes database 0, so these tests use it with unique key
+//! prefixes and index names, and clean up after themselves.
+//!
+//! Run with: cargo test --test redis_stack_integration_tests -- --test-threads=1
+
+use dbcooper_lib::database::redis::RedisDriver;
+use dbcooper_lib::database::{DatabaseDriver, QueryLimits, RedisConfig, RedisTopology};
+use serde_json::json;
+
+fn stack_driver() -> RedisDriver {
+    RedisDriver::new(RedisConfig {
+        host: "localhost".to_string(),
+        port: 6380,
+        username: None,
+        password: None,
+        db: None,
+        tls: false,
+        topology: RedisTopology::Standalone,
+        limits: QueryLimits::default(),
+    })
+}
+
+fn test_prefix(name: &str) -> String {
+    format!("test:{}:{}", name, uuid::Uuid::new_v4().simple())
+}
+
+/// Store three products under `prefix` and index them as `index`.
+async fn seed_products(driver: &RedisDriver, prefix: &str, index: &str) {
+    let products = [
+        json!({ "name": "Desk lamp", "category": "lighting", "price": 20 }),
+        json!({ "name": "Floor lamp", "category": "lighting", "price": 80 }),
+        json!({ "name": "Standing desk", "category": "desks", "price": 450 }),
+    ];
+    for (id, product) in products.iter().enumerate() {
+        driver
+            .json_set(&format!("{prefix}{id}"), "$", product)
+            .await
+            .unwrap();
+    }
+    let result = driver
+        .execute_query(&format!(
+            "FT.CREATE {index} ON JSON PREFIX 1 {prefix} SCHEMA \
+             $.name AS name TEXT $.category AS category TAG $.price AS price NUMERIC SORTABLE"
+        ))
+        .await
+        .unwrap();
+    assert!(result.error.is_none(), "{:?}", result.error);
+    // Indexing existing documents happens in the background.
+    for _ in 0..50 {
+        let page = driver
+            .get_table_data("search", index, 1, 10, None, None, None)
+            .await
+            .unwrap();
+        if page.total == 3 {
+            return;
+        }
+        tokio::time::sleep(std::time::Duration::from_millis(100)).await;
+    }
+    panic!("index {index} never covered the seeded documents");
+}
+
+async fn drop_products(driver: &RedisDriver, prefix: &str, index: &str) {
+    driver
+        .execute_query(&format!("FT.DROPINDEX {index}"))
+        .await
+        .unwrap();
+    for id in 0..3 {
+        driver.delete_key(&format!("{prefix}{id}")).await.unwrap();
+    }
+}
+
+#[tokio::test]
+async fn test_detects_modules() {
+    let modules = stack_driver().modules().await.unwrap();
+    assert!(
+        modules.json && modules.search,
+        "Make sure redis-stack is running (docker-compose up -d redis-stack): {:?}",
+        modules
+    );
+}
+
+#[tokio::test]
+async fn test_reads_and_edits_json_documents_by_path() {
+    let driver = stack_driver();
+    let key = test_prefix("json");
+    let document = json!({ "name": "Desk lamp", "tags": ["desk"], "stock": { "warehouse": 4 } });
+    driver.json_set(&key, "$", &document).await.unwrap();
+
+    let details = driver.get_key_details(&key).await.unwrap();
+    assert_eq!(details.key_type, "ReJSON-RL");
+    assert_eq!(details.value, document);
+    assert_eq!(details.length, Some(3));
+
+    assert_eq!(
+        driver.json_get(&key, "$.stock.warehouse").await.unwrap(),
+        json!([4])
+    );
+    driver
+        .json_set(&key, "$.stock.warehouse", &json!(7))
+        .await
+        .unwrap();
+    driver
+        .json_set(&key, "$.price", &json!(19.5))
+        .await
+        .unwrap();
+    assert!(driver
+        .json_set(&key, "$.missing.price", &json!(1))
+        .await
+        .is_err());
+    assert_eq!(driver.json_delete(&key, "$.tags").await.unwrap(), 1);
+    assert_eq!(
+        driver.json_get(&key, "$").await.unwrap(),
+        json!([{ "name": "Desk lamp", "stock": { "warehouse": 7 }, "price": 19.5 }])
+    );
+
+    driver.delete_key(&key).await.unwrap();
+    assert!(driver.json_get(&key, "$").await.is_err());
+}
+
+#[tokio::test]
+async fn test_lists_and_browses_search_indexes() {
+    let driver = stack_driver();
+    let prefix = format!("{}:", test_prefix("search"));
+    let index = format!("idx_{}", uuid::Uuid::new_v4().simple());
+    seed_products(&driver, &prefix, &index).await;
+
+    let tables = driver.list_tables().await.unwrap();
+    assert!(tables
+        .iter()
+        .any(|table| table.schema == "search" && table.name == index));
+
+    let structure = driver.get_table_structure("search", &index).await.unwrap();
+    let columns: Vec<&str> = structure
+        .columns
+        .iter()
+        .map(|column| column.name.as_str())
+        .collect();
+    assert_eq!(columns, vec!["__key", "name", "category", "price"]);
+
+    let page = driver
+        .get_table_data(
+            "search",
+            &index,
+            1,
+            2,
+            None,
+            Some("price".to_string()),
+            Some("desc".to_string()),
+        )
+        .await
+        .unwrap();
+    assert_eq!(page.total, 3);
+    assert_eq!(page.data.len(), 2);
+    assert_eq!(page.data[0]["name"], json!("Standing desk"));
+    assert_eq!(page.data[0]["price"], json!(450));
+    assert_eq!(page.data[1]["__key"], json!(format!("{prefix}1")));
+
+    drop_products(&driver, &prefix, &index).await;
+}
+
+#[tokio::test]
+async fn test_maps_search_and_aggregate_replies_to_rows() {
+    let driver = stack_driver();
+    let prefix = format!("{}:", test_prefix("query"));
+    let index = format!("idx_{}", uuid::Uuid::new_v4().simple());
+    seed_products(&driver, &prefix, &index).await;
+
+    let search = driver
+        .execute_query(&format!(
+            "FT.SEARCH {index} \"@category:{{lighting}}\" SORTBY price WITHSCORES"
+        ))
+        .await
+        .unwrap();
+    assert!(search.error.is_none(), "{:?}", search.error);
+    assert_eq!(search.row_count, 2);
+    assert_eq!(search.data[0]["__key"], json!(format!("{prefix}0")));
+    assert!(search.data[0]["__score"].is_number());
+    assert_eq!(search.data[0]["$"]["name"], json!("Desk lamp"));
+
+    let aggregate = driver
+        .execute_query_read_only(&format!(
+            "FT.AGGREGATE {index} * GROUPBY 1 @category REDUCE COUNT 0 AS count SORTBY 2 @count DESC"
+        ))
+        .await
+        .unwrap();
+    assert!(aggregate.error.is_none(), "{:?}", aggregate.error);
+    assert_eq!(
+        aggregate.data[0],
+        json!({ "category": "lighting", "count": "2" })
+    );
+
+    drop_products(&driver, &prefix, &index).await;
+}
@@ -8,7 +8,9 @@ use tempfile::{tempdir, TempDir};
 
 // Re-export the modules we need to test
 use dbcooper_lib::database::sqlite::SqliteDriver;
-use dbcooper_lib::database::{DatabaseDriver, SqliteConfig};
+use dbcooper_lib::database::{
+    DatabaseDriver, DriverErrorCategory, QueryLimits, SessionOptions, SqliteConfig,
+};
 use dbcooper_lib::db::models::{
     ColumnDefault, CreateTableColumn, CreateTableRequest, FilterCondition, FilterConjunction,
     FilterExpression, FilterOperator, TableFilter,
@@ -20,6 +22,8 @@ fn create_test_driver(temp_dir: &TempDir) -> (SqliteDriver, PathBuf) {
     let db_path = temp_dir.path().join("test.db");
     let config = SqliteConfig {
         file_path: db_path.to_string_lossy().to_string(),
+        session: SessionOptions::default(),
+        limits: QueryLimits::default(),
     };
     (SqliteDriver::new(config), db_path)
 }
@@ -106,6 +110,69 @@ async fn test_connection_creates_database() {
     assert!(db_path.exists(), "Database should exist after connection");
 }
 
+#[tokio::test]
+async fn test_session_settings_apply_to_new_connections() {
+    let temp_dir = tempdir().unwrap();
+    let driver = SqliteDriver::new(SqliteConfig {
+        file_path: temp_dir
+            .path()
+            .join("session.db")
+            .to_string_lossy()
+            .to_string(),
+        session: SessionOptions::new(
+            Some("CREATE TEMP VIEW session_marker AS SELECT 'ready' AS status".to_string()),
+            [("cache_size".to_string(), "-4000".to_string())].into(),
+        ),
+        limits: QueryLimits::default(),
+    });
+
+    let pragma = driver.execute_query("PRAGMA cache_size").await.unwrap();
+    assert_eq!(pragma.data[0]["cache_size"], json!(-4000));
+
+    let marker = driver
+        .execute_query("SELECT status FROM session_marker")
+        .await
+        .unwrap();
+    assert_eq!(marker.error, None);
+    assert_eq!(marker.data[0]["status"], json!("ready"));
+}
+
+#[tokio::test]
+async fn test_query_limits_truncate_rows_and_cancel_slow_queries() {
+    let temp_dir = tempdir().unwrap();
+    let driver = SqliteDriver::new(SqliteConfig {
+        file_path: temp_dir
+            .path()
+            .join("limits.db")
+            .to_string_lossy()
+            .to_string(),
+        session: SessionOptions::default(),
+        limits: QueryLimits {
+            query_timeout: Some(std::time::Duration::from_millis(50)),
+            max_rows: 2,
+            ..QueryLimits::default()
+        },
+    });
+
+    let rows = driver
+        .execute_query("SELECT * FROM (VALUES (1), (2), (3), (4))")
+        .await
+        .unwrap();
+    assert_eq!(rows.row_count, 2);
+    assert!(rows.truncated);
+
+    let slow = driver
+        .execute_query(
+            "WITH RECURSIVE n(x) AS (SELECT 1 UNION ALL SELECT x + 1 FROM n WHERE x < 20000000) SELECT count(*) FROM n",
+        )
+        .await
+        .unwrap();
+    assert_eq!(
+        slow.error.as_deref(),
+        Some("Query exceeded the 50ms timeout")
+    );
+}
+
 // ============================================================================
 // List Tables Tests
 // ============================================================================
@@ -184,7 +251,7 @@ async fn test_create_table_builds_sqlite_table_and_reports_duplicates() {
         .any(|index| index.unique && index.columns == vec!["slug"]));
 
     let duplicate_error = driver.create_table(&request).await.unwrap_err();
-    assert!(duplicate_error.contains("already exists"));
+    assert!(duplicate_error.message.contains("already exists"));
 }
 
 #[tokio::test]
@@ -667,6 +734,8 @@ async fn test_read_only_query_rejects_attach_database() {
     let attached_path = temp_dir.path().join("attached.db");
     let attached_driver = SqliteDriver::new(SqliteConfig {
         file_path: attached_path.to_string_lossy().to_string(),
+        session: SessionOptions::default(),
+        limits: QueryLimits::default(),
     });
     attached_driver
         .execute_query("CREATE TABLE secrets (value TEXT)")
@@ -802,6 +871,34 @@ async fn test_execute_query_syntax_error() {
     assert!(query_result.data.is_empty());
 }
 
+#[tokio::test]
+async fn test_execute_query_error_details() {
+    let temp_dir = tempdir().expect("Failed to create temp directory");
+    let driver = create_driver_with_table(&temp_dir).await;
+
+    let query_result = driver
+        .execute_query("SELECT id\nFROM users\nWHERE name = 'x' LIMTI 1")
+        .await
+        .unwrap();
+    let details = query_result.error_details.expect("typed error details");
+    assert_eq!(details.category, DriverErrorCategory::Syntax);
+    assert_eq!(details.code.as_deref(), Some("1"));
+    let position = details.position.expect("error position");
+    assert_eq!((position.line, position.column), (3, 18));
+
+    driver
+        .execute_query("INSERT INTO users (name, email) VALUES ('a', 'a@example.com')")
+        .await
+        .unwrap();
+    let duplicate = driver
+        .execute_query("INSERT INTO users (name, email) VALUES ('b', 'a@example.com')")
+        .await
+        .unwrap();
+    let details = duplicate.error_details.expect("typed error details");
+    assert_eq!(details.category, DriverErrorCategory::Constraint);
+    assert!(!details.reconnect_may_help());
+}
+
 // ============================================================================
 // Get Schema Overview Tests
 // ============================================================================
@@ -912,12 +1009,25 @@ async fn test_row_to_json_all_types() {
     assert_eq!(row.get("int_col").unwrap().as_i64().unwrap(), 42);
     assert!((row.get("real_col").unwrap().as_f64().unwrap() - 3.14).abs() < 0.001);
     assert_eq!(row.get("text_col").unwrap().as_str().unwrap(), "hello");
-    assert!(row
-        .get("blob_col")
-        .unwrap()
-        .as_str()
-        .unwrap()
-        .contains("bytes"));
+    assert_eq!(
+        row.get("blob_col").unwrap(),
+        &json!({"type": "binary", "value": "SEVMTE8=", "length": 5})
+    );
+    let columns: Vec<_> = result
+        .columns
+        .iter()
+        .map(|column| (column.name.as_str(), column.data_type.as_str()))
+        .collect();
+    assert_eq!(
+        columns,
+        vec![
+            ("int_col", "INTEGER"),
+            ("real_col", "REAL"),
+            ("text_col", "TEXT"),
+            ("blob_col", "BLOB"),
+            ("null_col", "TEXT"),
+        ]
+    );
     // NULL columns may be returned as empty string or as null depending on SQLite version
     assert!(
         row.get("null_col").unwrap().is_null()
@@ -930,6 +1040,72 @@ async fn test_row_to_json_all_types() {
     );
 }
 
+#[tokio::test]
+async fn test_byte_cap_counts_both_copies_of_each_row() {
+    let temp_dir = tempdir().unwrap();
+    let driver = SqliteDriver::new(SqliteConfig {
+        file_path: temp_dir
+            .path()
+            .join("bytes.db")
+            .to_string_lossy()
+            .to_string(),
+        session: SessionOptions::default(),
+        limits: QueryLimits {
+            max_bytes: Some(25),
+            ..QueryLimits::default()
+        },
+    });
+
+    // Each row goes out as `[N]` and `{"id":N}`: 11 bytes, so two fit.
+    let result = driver
+        .execute_query("SELECT 1 AS id UNION ALL SELECT 2 UNION ALL SELECT 3")
+        .await
+        .unwrap();
+    assert_eq!(result.rows.len(), 2);
+    assert_eq!(result.data.len(), 2);
+    assert!(result.truncated);
+}
+
+#[tokio::test]
+async fn test_results_are_not_editable_outside_postgres() {
+    let temp_dir = tempdir().expect("Failed to create temp directory");
+    let driver = create_driver_with_table(&temp_dir).await;
+    driver
+        .execute_query("INSERT INTO users (name) VALUES ('Ada')")
+        .await
+        .unwrap();
+
+    // Only the Postgres driver maps result columns back to their table.
+    let result = driver
+        .execute_query("SELECT id, name FROM users")
+        .await
+        .unwrap();
+    assert_eq!(result.rows.len(), 1);
+    assert!(result.edit_target.is_none());
+}
+
+#[tokio::test]
+async fn test_duplicate_column_names_are_kept_in_rows() {
+    let temp_dir = tempdir().expect("Failed to create temp directory");
+    let (driver, _) = create_test_driver(&temp_dir);
+
+    for result in [
+        driver
+            .execute_query("SELECT 1 AS a, 'x' AS a")
+            .await
+            .unwrap(),
+        driver
+            .execute_query_read_only("SELECT 1 AS a, 'x' AS a")
+            .await
+            .unwrap(),
+    ] {
+        assert_eq!(result.columns.len(), 2);
+        assert_eq!(result.rows, vec![vec![json!(1), json!("x")]]);
+        assert_eq!(result.data[0], json!({"a": "x"}));
+        assert!(result.edit_target.is_none());
+    }
+}
+
 // ============================================================================
 // Update/Delete Isolation Tests
 // ============================================================================
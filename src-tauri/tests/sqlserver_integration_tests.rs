@@ -0,0 +1,180 @@
+use dbcooper_lib::database::mutation::{build_delete, build_insert, build_update, MutationValue};
+use dbcooper_lib::database::sqlserver::SqlServerDriver;
+use dbcooper_lib::database::{
+    DatabaseDriver, DatabaseType, QueryLimits, SessionOptions, SqlServerConfig,
+};
+use dbcooper_lib::db::models::{
+    FilterCondition, FilterConjunction, FilterExpression, FilterOperator, TableFilter,
+};
+use serde_json::json;
+
+fn driver() -> SqlServerDriver {
+    SqlServerDriver::new(SqlServerConfig {
+        host: "127.0.0.1".to_string(),
+        port: 1433,
+        database: "master".to_string(),
+        username: "sa".to_string(),
+        password: "DbCooper-2024!".to_string(),
+        ssl: false,
+        trust_server_certificate: false,
+        session: SessionOptions::default(),
+        limits: QueryLimits::default(),
+    })
+}
+
+fn value(column: &str, value: serde_json::Value) -> MutationValue {
+    MutationValue {
+        column: column.to_string(),
+        value,
+        is_raw_sql: false,
+    }
+}
+
+#[tokio::test]
+async fn sql_server_supports_the_full_adapter_path() {
+    let driver = driver();
+    let connection = driver.test_connection().await.unwrap();
+    assert!(connection.success, "{}", connection.message);
+
+    let table = format!("sqlserver_adapter_{}", uuid::Uuid::new_v4().simple());
+    let created = driver
+        .execute_query(&format!(
+            "CREATE TABLE dbo.{table} (id INT IDENTITY PRIMARY KEY, label NVARCHAR(100), amount DECIMAL(30, 12), seen BIT)"
+        ))
+        .await
+        .unwrap();
+    assert!(created.error.is_none(), "{:?}", created.error);
+    assert!(driver
+        .list_tables()
+        .await
+        .unwrap()
+        .iter()
+        .any(|item| item.schema == "dbo" && item.name == table));
+    assert!(driver
+        .list_databases()
+        .await
+        .unwrap()
+        .contains(&"master".to_string()));
+
+    for (label, amount) in [
+        ("alpha", "10.5"),
+        ("beta", "123456789012345678.123456789012"),
+    ] {
+        let insert = build_insert(
+            DatabaseType::SqlServer,
+            "dbo",
+            &table,
+            &[
+                value("label", json!(label)),
+                value("amount", json!(amount)),
+                value("seen", json!(false)),
+            ],
+        )
+        .unwrap();
+        let inserted = driver.execute_mutation(&insert).await.unwrap();
+        assert_eq!(inserted.rows_affected, Some(1), "{:?}", inserted.error);
+    }
+
+    let structure = driver.get_table_structure("dbo", &table).await.unwrap();
+    assert!(structure
+        .columns
+        .iter()
+        .any(|column| column.name == "id" && column.primary_key));
+    assert!(structure
+        .columns
+        .iter()
+        .any(|column| column.name == "amount" && column.data_type == "decimal(30,12)"));
+    assert!(structure.indexes.iter().any(|index| index.primary));
+
+    let filter = TableFilter::Structured(FilterExpression {
+        conjunction: FilterConjunction::And,
+        conditions: vec![FilterCondition {
+            column: "amount".to_string(),
+            operator: FilterOperator::GreaterThan,
+            value: Some(json!("100")),
+        }],
+    });
+    let page = driver
+        .get_table_data("dbo", &table, 1, 25, Some(filter), None, None)
+        .await
+        .unwrap();
+    assert_eq!(page.total, 1);
+    assert_eq!(
+        page.data[0]["amount"],
+        json!({"type": "decimal", "value": "123456789012345678.123456789012"})
+    );
+    let second_page = driver
+        .get_table_data("dbo", &table, 2, 1, None, None, None)
+        .await
+        .unwrap();
+    assert_eq!(second_page.total, 2);
+    assert_eq!(second_page.data[0]["label"], "beta");
+
+    let update = build_update(
+        DatabaseType::SqlServer,
+        "dbo",
+        &table,
+        &["id".to_string()],
+        &[json!(1)],
+        &[value("seen", json!(true))],
+    )
+    .unwrap();
+    assert_eq!(
+        driver
+            .execute_mutation(&update)
+            .await
+            .unwrap()
+            .rows_affected,
+        Some(1)
+    );
+
+    let blocked = driver
+        .execute_query_read_only(&format!("SELECT 1 DELETE FROM dbo.{table}"))
+        .await
+        .unwrap();
+    assert!(blocked.error.is_some());
+    let read = driver
+        .execute_query_read_only(&format!("SELECT label, seen FROM dbo.{table} WHERE id = 1"))
+        .await
+        .unwrap();
+    assert!(read.error.is_none(), "{:?}", read.error);
+    assert_eq!(read.data[0]["label"], "alpha");
+    assert_eq!(read.data[0]["seen"], true);
+
+    let failed = driver
+        .execute_query("SELECT *\nFORM nowhere")
+        .await
+        .unwrap();
+    let details = failed.error_details.unwrap();
+    assert_eq!(details.code.as_deref(), Some("102"));
+
+    let delete = build_delete(
+        DatabaseType::SqlServer,
+        "dbo",
+        &table,
+        &["id".to_string()],
+        &[json!(2)],
+    )
+    .unwrap();
+    assert_eq!(
+        driver
+            .execute_mutation(&delete)
+            .await
+            .unwrap()
+            .rows_affected,
+        Some(1)
+    );
+    assert!(driver
+        .get_schema_overview()
+        .await
+        .unwrap()
+        .tables
+        .iter()
+        .any(|item| item.name == table));
+
+    let dropped = driver
+        .execute_query(&format!("DROP TABLE dbo.{table}"))
+        .await
+        .unwrap();
+    assert!(dropped.error.is_none(), "{:?}", dropped.error);
+}
@@ -47,6 +47,9 @@ async fn test_unified_test_connection_sqlite_success() {
         None,
         None,
         None,
+        None,
+        None,
+        None,
     )
     .await;
 
@@ -74,6 +77,9 @@ async fn test_unified_test_connection_postgres_success() {
         None,
         None,
         None,
+        None,
+        None,
+        None,
     )
     .await;
 
@@ -104,6 +110,9 @@ async fn test_unified_test_connection_invalid_db_type() {
         None,
         None,
         None,
+        None,
+        None,
+        None,
     )
     .await;
 
@@ -117,6 +126,41 @@ async fn test_unified_test_connection_invalid_db_type() {
     );
 }
 
+#[cfg(unix)]
+#[tokio::test]
+async fn test_unified_test_connection_reports_credential_command_failure() {
+    let file_path = temp_sqlite_path();
+
+    let result = unified_test_connection(
+        "sqlite".to_string(),
+        None,
+        None,
+        None,
+        None,
+        None,
+        None,
+        Some(file_path),
+        None,
+        None,
+        None,
+        None,
+        None,
+        None,
+        None,
+        None,
+        None,
+        Some("echo 'vault is sealed' >&2; exit 2".to_string()),
+    )
+    .await;
+
+    let test_result = result.unwrap();
+    assert!(!test_result.success);
+    assert_eq!(
+        test_result.message,
+        "Credential command exited with status 2: vault is sealed"
+    );
+}
+
 // ============================================================================
 // unified_list_tables Tests
 // ============================================================================
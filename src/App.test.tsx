@@ -16,6 +16,9 @@ mock.module("@/contexts/SettingsContext", () => ({
 mock.module("@/contexts/ThemeContext", () => ({
 	ThemeProvider: ({ children }: { children: ReactNode }) => children,
 }));
+mock.module("@/components/McpApprovalPrompt", () => ({
+	McpApprovalPrompt: () => null,
+}));
 mock.module("@/pages/Connections", () => ({
 	Connections: () => <h1>Connections</h1>,
 }));
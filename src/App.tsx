@@ -1,9 +1,11 @@
 import { lazy, Suspense } from "react";
 import { BrowserRouter, Routes, Route } from "react-router-dom";
+import { McpApprovalPrompt } from "@/components/McpApprovalPrompt";
 import { Toaster } from "@/components/ui/sonner";
 import { Spinner } from "@/components/ui/spinner";
 import { ThemeProvider } from "@/contexts/ThemeContext";
 import { SettingsProvider } from "@/contexts/SettingsContext";
+import { api } from "@/lib/tauri";
 import { Connections } from "@/pages/Connections";
 
 const ConnectionDetails = lazy(() =>
@@ -40,6 +42,7 @@ export function App() {
 							<Route path="*" element={<NotFound />} />
 						</Routes>
 					</Suspense>
+					<McpApprovalPrompt client={api.mcp} />
 					<Toaster />
 				</SettingsProvider>
 			</ThemeProvider>
@@ -29,7 +29,14 @@ import { SqliteIcon } from "@/components/icons/sqlite";
 import { DuckdbIcon } from "@/components/icons/duckdb";
 import { CloudflareIcon } from "@/components/icons/cloudflare";
 import { D1ConnectionFields } from "@/components/connections/D1ConnectionFields";
-import { mergeD1ConnectionFields } from "@/lib/connectionFormState";
+import { RedisTopologyFields } from "@/components/connections/RedisTopologyFields";
+import {
+	connectionOptions,
+	d1FormFields,
+	mergeD1ConnectionFields,
+	mergeRedisTopologyFields,
+	redisTopologyFields,
+} from "@/lib/connectionFormState";
 import { toast } from "sonner";
 import { Spinner } from "@/components/ui/spinner";
 import { Switch } from "@/components/ui/switch";
@@ -173,6 +180,8 @@ export function ConnectionForm({
 				ssh_password: initialData.ssh_password || "",
 				ssh_key_path: initialData.ssh_key_path || "",
 				ssh_use_key: initialData.ssh_use_key === 1,
+				options: initialData.options,
+				...(initialData.type === "d1" ? d1FormFields(initialData) : {}),
 			});
 		} else {
 			setFormData(defaultFormData);
@@ -195,6 +204,7 @@ export function ConnectionForm({
 						: formData.host,
 			ssl: type === "d1" ? true : formData.type === "d1" ? false : formData.ssl,
 			ssh_enabled: type === "d1" ? false : formData.ssh_enabled,
+			options: undefined,
 		});
 	};
 
@@ -231,6 +241,7 @@ export function ConnectionForm({
 							ssh_password: formData.ssh_password || "",
 							ssh_key_path: formData.ssh_key_path || "",
 							ssh_use_key: formData.ssh_use_key ? 1 : 0,
+							options: connectionOptions(formData),
 							created_at: "",
 							updated_at: "",
 						})
@@ -277,7 +288,7 @@ export function ConnectionForm({
 			return;
 		}
 		try {
-			await onSubmit(formData);
+			await onSubmit({ ...formData, options: connectionOptions(formData) });
 			if (!isEditMode) {
 				setFormData(defaultFormData);
 			}
@@ -485,24 +496,37 @@ export function ConnectionForm({
 									</Field>
 								</div>
 
-								{/* Redis uses database index, not database name */}
+								{formData.type === "redis" && (
+									<RedisTopologyFields
+										{...redisTopologyFields(formData)}
+										onChange={(values) =>
+											setFormData((current) =>
+												mergeRedisTopologyFields(current, values),
+											)
+										}
+									/>
+								)}
+
+								{/* Redis uses database index, not database name; a cluster only has 0 */}
 								{formData.type === "redis" ? (
-									<Field>
-										<FieldLabel htmlFor="connection-database">
-											Database Index (0-15)
-										</FieldLabel>
-										<Input
-											id="connection-database"
-											type="number"
-											min="0"
-											max="15"
-											value={formData.database}
-											onChange={(e) =>
-												setFormData({ ...formData, database: e.target.value })
-											}
-											placeholder="0"
-										/>
-									</Field>
+									redisTopologyFields(formData).mode !== "cluster" && (
+										<Field>
+											<FieldLabel htmlFor="connection-database">
+												Database Index (0-15)
+											</FieldLabel>
+											<Input
+												id="connection-database"
+												type="number"
+												min="0"
+												max="15"
+												value={formData.database}
+												onChange={(e) =>
+													setFormData({ ...formData, database: e.target.value })
+												}
+												placeholder="0"
+											/>
+										</Field>
+									)
 								) : (
 									<Field>
 										<FieldLabel htmlFor="connection-database">
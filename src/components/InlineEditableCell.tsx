@@ -8,6 +8,12 @@ import { toast } from "sonner";
 import { Button } from "@/components/ui/button";
 import { Input } from "@/components/ui/input";
 import { Spinner } from "@/components/ui/spinner";
+import {
+	cellText,
+	editedTaggedCell,
+	isTaggedCell,
+	jsonCell,
+} from "@/lib/cellValues";
 import type { TableColumn } from "@/types/tabTypes";
 
 interface InlineEditableCellProps {
@@ -18,12 +24,6 @@ interface InlineEditableCellProps {
 	onSave: (value: unknown) => Promise<void>;
 }
 
-function stringifyCellValue(value: unknown): string {
-	if (value === null || value === undefined) return "NULL";
-	if (typeof value === "object") return JSON.stringify(value);
-	return String(value);
-}
-
 function isNumericType(columnType: string): boolean {
 	return (
 		columnType.includes("int") ||
@@ -37,7 +37,11 @@ function isNumericType(columnType: string): boolean {
 	);
 }
 
-function parseCellValue(text: string, column: TableColumn): unknown {
+function parseCellValue(
+	text: string,
+	column: TableColumn,
+	original: unknown,
+): unknown {
 	const trimmed = text.trim();
 	const columnType = column.type.toLowerCase();
 
@@ -45,6 +49,10 @@ function parseCellValue(text: string, column: TableColumn): unknown {
 		return null;
 	}
 
+	if (isTaggedCell(original)) {
+		return editedTaggedCell(text, original);
+	}
+
 	if (columnType === "boolean" || columnType === "bool") {
 		if (["true", "1", "yes"].includes(trimmed.toLowerCase())) return true;
 		if (["false", "0", "no"].includes(trimmed.toLowerCase())) return false;
@@ -63,7 +71,7 @@ function parseCellValue(text: string, column: TableColumn): unknown {
 	if (columnType.includes("json")) {
 		if (trimmed === "") return null;
 		try {
-			return JSON.parse(trimmed);
+			return jsonCell(JSON.parse(trimmed));
 		} catch {
 			throw new Error("JSON values must be valid JSON");
 		}
@@ -80,14 +88,14 @@ export function InlineEditableCell({
 	onSave,
 }: InlineEditableCellProps) {
 	const [editing, setEditing] = useState(false);
-	const [draftValue, setDraftValue] = useState(stringifyCellValue(value));
+	const [draftValue, setDraftValue] = useState(cellText(value));
 	const [saving, setSaving] = useState(false);
 	const inputRef = useRef<HTMLInputElement>(null);
 	const editorRef = useRef<HTMLDivElement>(null);
 
 	useEffect(() => {
 		if (!editing) {
-			setDraftValue(stringifyCellValue(value));
+			setDraftValue(cellText(value));
 		}
 	}, [value, editing]);
 
@@ -103,7 +111,7 @@ export function InlineEditableCell({
 
 		let parsedValue: unknown;
 		try {
-			parsedValue = parseCellValue(draftValue, column);
+			parsedValue = parseCellValue(draftValue, column, value);
 		} catch (error) {
 			toast.error("Invalid cell value", {
 				description: error instanceof Error ? error.message : String(error),
@@ -126,7 +134,7 @@ export function InlineEditableCell({
 	};
 
 	const reset = () => {
-		setDraftValue(stringifyCellValue(value));
+		setDraftValue(cellText(value));
 		setEditing(false);
 	};
 
@@ -152,7 +160,7 @@ export function InlineEditableCell({
 						}
 						if (event.key === "Escape") {
 							event.preventDefault();
-							setDraftValue(stringifyCellValue(value));
+							setDraftValue(cellText(value));
 							setEditing(false);
 						}
 					}}
@@ -0,0 +1,127 @@
+import { afterEach, expect, mock, test } from "bun:test";
+import { GlobalRegistrator } from "@happy-dom/global-registrator";
+import type { ComponentProps, ReactNode } from "react";
+
+if (!globalThis.document) GlobalRegistrator.register();
+
+mock.module("@/components/ui/spinner", () => ({
+	Spinner: () => <span data-testid="spinner" />,
+}));
+mock.module("@/components/ui/alert-dialog", () => ({
+	AlertDialog: ({
+		children,
+		open,
+	}: {
+		children: ReactNode;
+		open?: boolean;
+	}) => (open ? <div>{children}</div> : null),
+	AlertDialogAction: ({ children, ...props }: ComponentProps<"button">) => (
+		<button {...props}>{children}</button>
+	),
+	AlertDialogCancel: ({ children, ...props }: ComponentProps<"button">) => (
+		<button {...props}>{children}</button>
+	),
+	AlertDialogContent: ({ children }: { children: ReactNode }) => (
+		<div>{children}</div>
+	),
+	AlertDialogDescription: ({ children }: { children: ReactNode }) => (
+		<p>{children}</p>
+	),
+	AlertDialogFooter: ({ children }: { children: ReactNode }) => (
+		<div>{children}</div>
+	),
+	AlertDialogHeader: ({ children }: { children: ReactNode }) => (
+		<div>{children}</div>
+	),
+	AlertDialogTitle: ({ children }: { children: ReactNode }) => (
+		<h2>{children}</h2>
+	),
+}));
+const { act, cleanup, render, screen, waitFor } = await import(
+	"@testing-library/react"
+);
+const userEvent = (await import("@testing-library/user-event")).default;
+const { McpApprovalPrompt } = await import("./McpApprovalPrompt");
+
+interface McpApprovalRequest {
+	id: string;
+	connection_uuid: string;
+	connection_name: string;
+	tool: string;
+	sql: string;
+	values: unknown[];
+	requested_at: string;
+}
+
+afterEach(cleanup);
+
+const deleteRequest: McpApprovalRequest = {
+	id: "approval-1",
+	connection_uuid: "connection-1",
+	connection_name: "Billing",
+	tool: "delete_row",
+	sql: 'DELETE FROM "public"."invoices" WHERE "id" = $1',
+	values: [42],
+	requested_at: "2026-10-18T09:00:00Z",
+};
+
+function client(pending: McpApprovalRequest[] = []) {
+	const handlers: {
+		requested?: (request: McpApprovalRequest) => void;
+		resolved?: (resolution: { id: string; approved: boolean }) => void;
+	} = {};
+	return {
+		handlers,
+		listPendingApprovals: async () => pending,
+		resolveApproval: mock(async (_id: string, _approved: boolean) => {}),
+		onApprovalRequested: async (
+			handler: (request: McpApprovalRequest) => void,
+		) => {
+			handlers.requested = handler;
+			return () => {};
+		},
+		onApprovalResolved: async (
+			handler: (resolution: { id: string; approved: boolean }) => void,
+		) => {
+			handlers.resolved = handler;
+			return () => {};
+		},
+	};
+}
+
+test("shows a requested write and approves it", async () => {
+	const approvals = client();
+	const user = userEvent.setup();
+	render(<McpApprovalPrompt client={approvals} />);
+
+	await waitFor(() => expect(approvals.handlers.requested).toBeDefined());
+	act(() => approvals.handlers.requested?.(deleteRequest));
+
+	expect(
+		await screen.findByText("Run this statement on Billing?"),
+	).not.toBeNull();
+	expect(screen.getByText(deleteRequest.sql)).not.toBeNull();
+	expect(screen.getByText("Values: 42")).not.toBeNull();
+
+	await user.click(screen.getByRole("button", { name: "Run statement" }));
+	expect(approvals.resolveApproval).toHaveBeenCalledWith("approval-1", true);
+	await waitFor(() =>
+		expect(screen.queryByText("Run this statement on Billing?")).toBeNull(),
+	);
+});
+
+test("loads requests made before it mounted and dismisses expired ones", async () => {
+	const approvals = client([deleteRequest]);
+	render(<McpApprovalPrompt client={approvals} />);
+
+	expect(
+		await screen.findByText("Run this statement on Billing?"),
+	).not.toBeNull();
+	act(() =>
+		approvals.handlers.resolved?.({ id: "approval-1", approved: false }),
+	);
+	await waitFor(() =>
+		expect(screen.queryByText("Run this statement on Billing?")).toBeNull(),
+	);
+	expect(approvals.resolveApproval).not.toHaveBeenCalled();
+});
@@ -0,0 +1,161 @@
+import { useEffect, useState } from "react";
+import type { UnlistenFn } from "@tauri-apps/api/event";
+import {
+	AlertDialog,
+	AlertDialogAction,
+	AlertDialogCancel,
+	AlertDialogContent,
+	AlertDialogDescription,
+	AlertDialogFooter,
+	AlertDialogHeader,
+	AlertDialogTitle,
+} from "@/components/ui/alert-dialog";
+import { Spinner } from "@/components/ui/spinner";
+import type { McpApprovalRequest, McpApprovalResolution } from "@/lib/tauri";
+
+interface McpApprovalClient {
+	listPendingApprovals: () => Promise<McpApprovalRequest[]>;
+	resolveApproval: (id: string, approved: boolean) => Promise<void>;
+	onApprovalRequested: (
+		handler: (request: McpApprovalRequest) => void,
+	) => Promise<UnlistenFn>;
+	onApprovalResolved: (
+		handler: (resolution: McpApprovalResolution) => void,
+	) => Promise<UnlistenFn>;
+}
+
+interface McpApprovalPromptProps {
+	client: McpApprovalClient;
+}
+
+function formatValue(value: unknown) {
+	return typeof value === "string" ? value : JSON.stringify(value);
+}
+
+/**
+ * Asks the user to approve writes from MCP clients. The client's tool call
+ * blocks until the statement is approved or rejected here.
+ */
+export function McpApprovalPrompt({ client }: McpApprovalPromptProps) {
+	const [pending, setPending] = useState<McpApprovalRequest[]>([]);
+	const [busy, setBusy] = useState(false);
+
+	useEffect(() => {
+		let disposed = false;
+		const unlisteners: UnlistenFn[] = [];
+		const keep = (unlisten: UnlistenFn) => {
+			if (disposed) {
+				unlisten();
+			} else {
+				unlisteners.push(unlisten);
+			}
+		};
+
+		client
+			.onApprovalRequested((request) =>
+				setPending((current) =>
+					current.some((existing) => existing.id === request.id)
+						? current
+						: [...current, request],
+				),
+			)
+			.then(keep)
+			.catch((listenError) => {
+				console.error("Failed to listen for MCP approvals:", listenError);
+			});
+		client
+			.onApprovalResolved((resolution) =>
+				setPending((current) =>
+					current.filter((request) => request.id !== resolution.id),
+				),
+			)
+			.then(keep)
+			.catch((listenError) => {
+				console.error("Failed to listen for MCP approvals:", listenError);
+			});
+		// Requests made before the window was ready.
+		client
+			.listPendingApprovals()
+			.then((requests) => {
+				if (!disposed) setPending(requests);
+			})
+			.catch((loadError) => {
+				console.error("Failed to load MCP approvals:", loadError);
+			});
+
+		return () => {
+			disposed = true;
+			for (const unlisten of unlisteners) unlisten();
+		};
+	}, [client]);
+
+	const request = pending[0];
+
+	const handleResolve = async (approved: boolean) => {
+		if (!request) return;
+		setBusy(true);
+		try {
+			await client.resolveApproval(request.id, approved);
+		} catch (resolveError) {
+			// The request already expired or its MCP call went away.
+			console.error("Failed to resolve MCP approval:", resolveError);
+		} finally {
+			setPending((current) =>
+				current.filter((existing) => existing.id !== request.id),
+			);
+			setBusy(false);
+		}
+	};
+
+	return (
+		<AlertDialog open={request !== undefined}>
+			{request && (
+				<AlertDialogContent>
+					<AlertDialogHeader>
+						<AlertDialogTitle>
+							Run this statement on {request.connection_name}?
+						</AlertDialogTitle>
+						<AlertDialogDescription>
+							An MCP client called <code>{request.tool}</code> and is waiting
+							for your approval. Nothing runs until you approve it.
+						</AlertDialogDescription>
+					</AlertDialogHeader>
+					<pre className="max-h-64 overflow-auto whitespace-pre-wrap rounded-md border bg-muted/50 p-3 font-mono text-xs">
+						{request.sql}
+					</pre>
+					{request.values.length > 0 && (
+						<p className="break-all font-mono text-xs text-muted-foreground">
+							Values: {request.values.map(formatValue).join(", ")}
+						</p>
+					)}
+					{pending.length > 1 && (
+						<p className="text-xs text-muted-foreground">
+							{pending.length - 1} more waiting
+						</p>
+					)}
+					<AlertDialogFooter>
+						<AlertDialogCancel
+							disabled={busy}
+							onClick={(event) => {
+								event.preventDefault();
+								void handleResolve(false);
+							}}
+						>
+							Reject
+						</AlertDialogCancel>
+						<AlertDialogAction
+							disabled={busy}
+							onClick={(event) => {
+								event.preventDefault();
+								void handleResolve(true);
+							}}
+						>
+							{busy && <Spinner className="size-3.5" />}
+							Run statement
+						</AlertDialogAction>
+					</AlertDialogFooter>
+				</AlertDialogContent>
+			)}
+		</AlertDialog>
+	);
+}
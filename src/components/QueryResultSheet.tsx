@@ -11,6 +11,7 @@ import { Label } from "@/components/ui/label";
 import { Input } from "@/components/ui/input";
 import { Button } from "@/components/ui/button";
 import { ExpandableText } from "@/components/ExpandableText";
+import { cellText, isTaggedCell } from "@/lib/cellValues";
 import { Check, Copy, MagnifyingGlass } from "@phosphor-icons/react";
 import { toast } from "sonner";
 
@@ -39,8 +40,14 @@ export function QueryResultSheet({
 
 			if (value === null || value === undefined) {
 				displayValue = "null";
+			} else if (isTaggedCell(value) && value.$type !== "json") {
+				displayValue = cellText(value);
 			} else if (typeof value === "object") {
-				displayValue = JSON.stringify(value, null, 2);
+				displayValue = JSON.stringify(
+					isTaggedCell(value) ? value.value : value,
+					null,
+					2,
+				);
 				isJson = true;
 			} else if (typeof value === "boolean") {
 				displayValue = value ? "true" : "false";
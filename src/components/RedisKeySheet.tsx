@@ -15,9 +15,10 @@ import { Select, SelectContent, SelectItem, SelectTrigger, SelectValue } from "@
 import { Spinner } from "@/components/ui/spinner";
 import { FloppyDisk, Plus, Trash } from "@phosphor-icons/react";
 import { toast } from "sonner";
+import { JSON_KEY_TYPE, JSON_ROOT, jsonPathValue } from "@/lib/redisModules";
 import type { RedisKeyDetails } from "@/lib/tauri";
 
-export type RedisKeyType = "string" | "list" | "set" | "hash" | "zset";
+export type RedisKeyType = "string" | "list" | "set" | "hash" | "zset" | "json";
 
 interface RedisKeySheetProps {
 	open: boolean;
@@ -29,8 +30,16 @@ interface RedisKeySheetProps {
 		type: RedisKeyType;
 		value: unknown;
 		ttl?: number;
+		/** Where a `json` value is written in its document. */
+		path?: string;
 	}) => Promise<void>;
 	saving?: boolean;
+	/** Whether the server has RedisJSON, so JSON keys can be created. */
+	jsonEnabled?: boolean;
+	/** Read the JSON at a path of the edited document (`JSON.GET`). */
+	onLoadJsonPath?: (key: string, path: string) => Promise<unknown>;
+	/** Remove the JSON at a path of the edited document (`JSON.DEL`). */
+	onDeleteJsonPath?: (key: string, path: string) => Promise<void>;
 }
 
 export function RedisKeySheet({
@@ -40,6 +49,9 @@ export function RedisKeySheet({
 	keyDetails,
 	onSave,
 	saving = false,
+	jsonEnabled = false,
+	onLoadJsonPath,
+	onDeleteJsonPath,
 }: RedisKeySheetProps) {
 	const [key, setKey] = useState("");
 	const [keyType, setKeyType] = useState<RedisKeyType>("string");
@@ -63,6 +75,11 @@ export function RedisKeySheet({
 	const [newZsetMember, setNewZsetMember] = useState("");
 	const [newZsetScore, setNewZsetScore] = useState("");
 
+	// JSON document, edited at a path
+	const [jsonPath, setJsonPath] = useState(JSON_ROOT);
+	const [jsonText, setJsonText] = useState("");
+	const [loadingJsonPath, setLoadingJsonPath] = useState(false);
+
 	// Initialize form when opening or keyDetails changes
 	useEffect(() => {
 		if (open) {
@@ -70,7 +87,12 @@ export function RedisKeySheet({
 				// Synchronize the editable draft when the selected key changes.
 				// eslint-disable-next-line react-hooks/set-state-in-effect
 				setKey(keyDetails.key);
-				setKeyType(keyDetails.key_type as RedisKeyType);
+				setKeyType(
+					keyDetails.key_type === JSON_KEY_TYPE
+						? "json"
+						: (keyDetails.key_type as RedisKeyType),
+				);
+				setJsonPath(JSON_ROOT);
 				setTtlEnabled(keyDetails.ttl !== -1);
 				setTtl(keyDetails.ttl !== -1 ? String(keyDetails.ttl) : "");
 
@@ -102,6 +124,8 @@ export function RedisKeySheet({
 					} else {
 						setZsetMembers([]);
 					}
+				} else if (keyDetails.key_type === JSON_KEY_TYPE) {
+					setJsonText(JSON.stringify(value, null, 2));
 				} else {
 					console.warn("Unexpected Redis key type in edit mode:", keyDetails.key_type);
 					setStringValue("");
@@ -124,6 +148,8 @@ export function RedisKeySheet({
 				setZsetMembers([]);
 				setNewZsetMember("");
 				setNewZsetScore("");
+				setJsonPath(JSON_ROOT);
+				setJsonText("");
 			}
 		}
 	}, [open, mode, keyDetails]);
@@ -204,6 +230,33 @@ export function RedisKeySheet({
 		setZsetMembers(updatedZsetMembers);
 	};
 
+	const loadJsonPath = async (path: string) => {
+		if (!onLoadJsonPath) return;
+		setLoadingJsonPath(true);
+		try {
+			const reply = await onLoadJsonPath(key, path);
+			setJsonText(JSON.stringify(jsonPathValue(path, reply), null, 2));
+		} catch (error) {
+			console.error("Failed to read JSON path:", error);
+			toast.error(`Failed to read ${path}`);
+		} finally {
+			setLoadingJsonPath(false);
+		}
+	};
+
+	const handleDeleteJsonPath = async () => {
+		const path = jsonPath.trim();
+		if (!onDeleteJsonPath || !path || path === JSON_ROOT) return;
+		try {
+			await onDeleteJsonPath(key, path);
+			setJsonPath(JSON_ROOT);
+			await loadJsonPath(JSON_ROOT);
+		} catch (error) {
+			console.error("Failed to delete JSON path:", error);
+			toast.error(`Failed to delete ${path}`);
+		}
+	};
+
 	const handleSave = async () => {
 		if (!key.trim()) {
 			toast.error("Key is required");
@@ -257,6 +310,14 @@ export function RedisKeySheet({
 					}
 					value = zsetMembers.map((m) => [m.member, m.score] as [string, number]);
 					break;
+				case "json":
+					try {
+						value = JSON.parse(jsonText);
+					} catch {
+						toast.error("JSON values must be valid JSON");
+						return;
+					}
+					break;
 				default: {
 					console.error("Unsupported Redis key type encountered in handleSave:", keyType);
 					toast.error("Unsupported key type. Please refresh the page and try again.");
@@ -269,6 +330,7 @@ export function RedisKeySheet({
 				type: keyType,
 				value,
 				ttl: ttlValue,
+				path: keyType === "json" ? jsonPath.trim() || JSON_ROOT : undefined,
 			});
 		} catch (error) {
 			console.error("Failed to save Redis key:", error);
@@ -456,6 +518,60 @@ export function RedisKeySheet({
 					</div>
 				);
 
+			case "json":
+				return (
+					<div className="space-y-2">
+						<Label>Path</Label>
+						<div className="flex items-center gap-2">
+							<Input
+								value={jsonPath}
+								onChange={(e) => setJsonPath(e.target.value)}
+								onKeyDown={(e) => {
+									if (e.key === "Enter" && mode === "edit") {
+										e.preventDefault();
+										loadJsonPath(jsonPath.trim() || JSON_ROOT);
+									}
+								}}
+								placeholder="$.field"
+								className="flex-1 font-mono"
+								disabled={mode === "add"}
+							/>
+							{mode === "edit" && (
+								<>
+									<Button
+										variant="outline"
+										size="sm"
+										onClick={() => loadJsonPath(jsonPath.trim() || JSON_ROOT)}
+										disabled={loadingJsonPath}
+									>
+										{loadingJsonPath ? <Spinner /> : null}
+										Load
+									</Button>
+									<Button
+										variant="ghost"
+										size="icon-sm"
+										onClick={handleDeleteJsonPath}
+										disabled={jsonPath.trim() === JSON_ROOT || !jsonPath.trim()}
+										title="Delete the value at this path"
+									>
+										<Trash className="w-4 h-4" />
+									</Button>
+								</>
+							)}
+						</div>
+						<p className="text-xs text-muted-foreground">
+							Saving writes the value below at this path.
+						</p>
+						<Label>Value</Label>
+						<Textarea
+							value={jsonText}
+							onChange={(e) => setJsonText(e.target.value)}
+							placeholder='{"name": "value"}'
+							className="font-mono min-h-[160px]"
+						/>
+					</div>
+				);
+
 			default:
 				console.warn("Unexpected Redis key type:", keyType);
 				return (
@@ -511,6 +627,9 @@ export function RedisKeySheet({
 								<SelectItem value="set">Set</SelectItem>
 								<SelectItem value="hash">Hash</SelectItem>
 								<SelectItem value="zset">Sorted Set</SelectItem>
+								{(jsonEnabled || keyType === "json") && (
+									<SelectItem value="json">JSON</SelectItem>
+								)}
 							</SelectContent>
 						</Select>
 					</div>
@@ -29,7 +29,6 @@ import {
 	SheetTitle,
 } from "@/components/ui/sheet";
 import { Spinner } from "@/components/ui/spinner";
-import { supportsStructuredRowMutations } from "@/lib/databaseCapabilities";
 import type { TableColumn } from "@/types/tabTypes";
 
 interface RowEditSheetProps {
@@ -39,6 +38,7 @@ interface RowEditSheetProps {
 	row: Record<string, unknown> | null;
 	columns: TableColumn[];
 	dbType: DbType;
+	rowEditing: boolean;
 	onSave: (
 		updates: Array<{ column: string; value: unknown; isRawSql: boolean }>,
 	) => Promise<void>;
@@ -69,6 +69,7 @@ export function RowEditSheet({
 	row,
 	columns,
 	dbType,
+	rowEditing,
 	onSave,
 	onDelete,
 	saving = false,
@@ -87,7 +88,7 @@ export function RowEditSheet({
 
 	const hasPrimaryKey = primaryKeyColumns.length > 0;
 
-	const isReadOnly = !supportsStructuredRowMutations(dbType);
+	const isReadOnly = !rowEditing;
 
 	// Reset edited values when row changes
 	useEffect(() => {
@@ -10,7 +10,9 @@ mock.module("@codemirror/state", () => ({
 	Prec: { highest: (value: unknown) => value },
 }));
 mock.module("@codemirror/view", () => ({
+	Decoration: { mark: () => ({}) },
 	EditorView: {
+		decorations: { of: () => ({}) },
 		lineWrapping: {},
 		theme: () => ({}),
 		updateListener: { of: () => ({}) },
@@ -1,6 +1,6 @@
 import { type SQLConfig, sql } from "@codemirror/lang-sql";
 import { EditorState, Prec } from "@codemirror/state";
-import { EditorView, keymap } from "@codemirror/view";
+import { Decoration, EditorView, keymap } from "@codemirror/view";
 import { Sparkle, Warning, WarningCircle } from "@phosphor-icons/react";
 import CodeMirror from "@uiw/react-codemirror";
 import { useEffect, useMemo, useState } from "react";
@@ -15,6 +15,7 @@ import {
 	TooltipTrigger,
 } from "@/components/ui/tooltip";
 import type { QueryAiState } from "@/lib/aiDraftState";
+import { errorTokenRange } from "@/lib/sqlErrorPosition";
 
 const emptyAiState: QueryAiState = {
 	instruction: "",
@@ -49,6 +50,8 @@ interface SqlEditorProps {
 	ai?: SqlEditorAiProps;
 	onCursorActivity?: (line: number, char: number) => void;
 	cursorWarning?: string | null;
+	/** Offset in `value` of the last error the engine reported, underlined. */
+	errorOffset?: number | null;
 }
 
 export function SqlEditor({
@@ -60,6 +63,7 @@ export function SqlEditor({
 	ai,
 	onCursorActivity,
 	cursorWarning = null,
+	errorOffset = null,
 	disabled = false,
 }: SqlEditorProps) {
 	const [isDark, setIsDark] = useState(false);
@@ -126,6 +130,26 @@ export function SqlEditor({
 		[onCursorActivity],
 	);
 
+	const errorExtension = useMemo(() => {
+		if (errorOffset === null) return [];
+		const errorMark = Decoration.mark({ class: "cm-sql-error" });
+		return [
+			EditorView.decorations.of((view) => {
+				const range = errorTokenRange(view.state.doc.toString(), errorOffset);
+				return range
+					? Decoration.set([errorMark.range(range.from, range.to)])
+					: Decoration.none;
+			}),
+			EditorView.theme({
+				".cm-sql-error": {
+					textDecoration: "underline wavy #ef4444",
+					textDecorationSkipInk: "none",
+					backgroundColor: "rgba(239, 68, 68, 0.12)",
+				},
+			}),
+		];
+	}, [errorOffset]);
+
 	const sqlSchema = useMemo(() => {
 		const schema: SQLConfig["schema"] = {};
 		for (const table of tables) {
@@ -154,8 +178,16 @@ export function SqlEditor({
 			EditorState.readOnly.of(disabled),
 			EditorView.lineWrapping,
 			cursorExtension,
+			errorExtension,
+		],
+		[
+			runQueryKeymap,
+			sqlExtension,
+			fontTheme,
+			disabled,
+			cursorExtension,
+			errorExtension,
 		],
-		[runQueryKeymap, sqlExtension, fontTheme, disabled, cursorExtension],
 	);
 
 	const handleGenerate = async () => {
@@ -39,7 +39,10 @@ function createQueryTab(): QueryTab {
 		savedQueryId: null,
 		savedQueryName: null,
 		results: null,
+		resultColumns: [],
+		editTarget: null,
 		error: null,
+		errorOffset: null,
 		success: false,
 		executionTime: null,
 		affectedRows: null,
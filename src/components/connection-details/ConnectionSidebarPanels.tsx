@@ -1,5 +1,7 @@
+import { useState } from "react";
 import {
 	ArrowClockwise,
+	CaretUpDown,
 	Check,
 	Code,
 	DotsThreeVertical,
@@ -8,6 +10,7 @@ import {
 	TreeStructure,
 	WarningCircle,
 } from "@phosphor-icons/react";
+import { toast } from "sonner";
 import { Button } from "@/components/ui/button";
 import {
 	ContextMenu,
@@ -33,19 +36,86 @@ import {
 import { Spinner } from "@/components/ui/spinner";
 import { TabsContent } from "@/components/ui/tabs";
 import { getConnectionDatabaseDisplay } from "@/lib/connectionPresentation";
-import type { Connection, QueryHistory } from "@/lib/tauri";
+import { api, type Connection, type QueryHistory } from "@/lib/tauri";
 import type { SavedQuery } from "@/lib/tauri";
 
+/** Lists the databases on the connection's server and opens one of them. */
+function DatabasePicker({
+	connectionUuid,
+	database,
+	onOpenDatabase,
+}: {
+	connectionUuid: string;
+	database: string;
+	onOpenDatabase: (database: string) => void;
+}) {
+	const [databases, setDatabases] = useState<string[] | null>(null);
+	const [loading, setLoading] = useState(false);
+
+	const loadDatabases = async () => {
+		setLoading(true);
+		try {
+			setDatabases(await api.pool.listDatabases(connectionUuid));
+		} catch (error) {
+			console.error("Failed to list databases:", error);
+			toast.error("Failed to list databases");
+		} finally {
+			setLoading(false);
+		}
+	};
+
+	return (
+		<DropdownMenu
+			onOpenChange={(open) => {
+				if (open) void loadDatabases();
+			}}
+		>
+			<DropdownMenuTrigger
+				render={
+					<button
+						type="button"
+						className="flex max-w-full items-center gap-1 rounded hover:text-foreground"
+						title="Switch database"
+					/>
+				}
+			>
+				<span className="truncate">{database}</span>
+				<CaretUpDown className="w-3 h-3 shrink-0" />
+			</DropdownMenuTrigger>
+			<DropdownMenuContent align="start" className="max-h-80 overflow-y-auto">
+				{loading && databases === null ? (
+					<div className="flex items-center gap-2 px-2 py-1.5 text-xs text-muted-foreground">
+						<Spinner />
+						Loading databases
+					</div>
+				) : (
+					(databases ?? []).map((name) => (
+						<DropdownMenuItem key={name} onClick={() => onOpenDatabase(name)}>
+							<Check
+								className={name === database ? "w-4 h-4" : "w-4 h-4 invisible"}
+							/>
+							<span className="truncate">{name}</span>
+						</DropdownMenuItem>
+					))
+				)}
+			</DropdownMenuContent>
+		</DropdownMenu>
+	);
+}
+
 export function ConnectionSidebarHeader({
 	connection,
 	refreshing,
 	onOpenSchemaVisualizer,
 	onRefresh,
+	onOpenDatabase,
 }: {
 	connection: Connection;
 	refreshing: boolean;
 	onOpenSchemaVisualizer: () => void;
 	onRefresh: () => void;
+	/** Set when the server hosts other databases the connection can open. */
+	onOpenDatabase?: (database: string) => void;
 }) {
 	return (
 		<SidebarHeader
@@ -83,9 +153,17 @@ export function ConnectionSidebarHeader({
 				</div>
 			</div>
 			<div className="text-xs text-muted-foreground mt-1">
-				{connection.db_type === "duckdb"
-					? connection.file_path
-					: getConnectionDatabaseDisplay(connection)}
+				{connection.db_type === "duckdb" ? (
+					connection.file_path
+				) : onOpenDatabase ? (
+					<DatabasePicker
+						connectionUuid={connection.uuid}
+						database={connection.database}
+						onOpenDatabase={onOpenDatabase}
+					/>
+				) : (
+					getConnectionDatabaseDisplay(connection)
+				)}
 			</div>
 		</SidebarHeader>
 	);
@@ -14,6 +14,7 @@ import type { ColumnDef } from "@tanstack/react-table";
 import { toast } from "sonner";
 import { DataTable } from "@/components/DataTable";
 import { QueryResultSheet } from "@/components/QueryResultSheet";
+import { RowEditSheet } from "@/components/RowEditSheet";
 import { SqlEditor } from "@/components/SqlEditor";
 import { Button } from "@/components/ui/button";
 import {
@@ -32,6 +33,12 @@ import {
 import { Input } from "@/components/ui/input";
 import { Skeleton } from "@/components/ui/skeleton";
 import { Spinner } from "@/components/ui/spinner";
+import { cellText } from "@/lib/cellValues";
+import {
+	editTargetColumns,
+	editTargetRow,
+	resultRowObject,
+} from "@/lib/connection-details/queryTableState";
 import { getSqlFormatterLanguage } from "@/lib/databaseCapabilities";
 import type { SqlConnection } from "@/types/connection";
 import type { DatabaseTable } from "@/types/table";
@@ -40,6 +47,7 @@ import type { QueryWorkspaceController } from "@/hooks/connection-details/useQue
 interface QueryWorkspaceProps {
 	tab: QueryTab;
 	connection: SqlConnection;
+	rowEditing: boolean;
 	tables: DatabaseTable[];
 	tableColumns: Record<string, TableColumn[]>;
 	controller: QueryWorkspaceController;
@@ -57,6 +65,7 @@ function formatQuerySuccessDetail(affectedRows: number | null): string {
 export function QueryWorkspace({
 	tab,
 	connection,
+	rowEditing,
 	tables,
 	tableColumns,
 	controller,
@@ -67,25 +76,41 @@ export function QueryWorkspace({
 		row: Record<string, unknown>;
 		index: number;
 	} | null>(null);
-	const queryColumns = useMemo<ColumnDef<Record<string, unknown>>[]>(() => {
+	// Columns are addressed by position, so two columns with the same name
+	// (`SELECT a.id, b.id`) both show.
+	const queryColumns = useMemo<ColumnDef<unknown[]>[]>(() => {
 		if (!tab.results?.length) return [];
 
-		return Object.keys(tab.results[0]).map((key) => ({
-			accessorKey: key,
-			header: key,
+		return tab.resultColumns.map((column, index) => ({
+			id: String(index),
+			accessorFn: (row) => row[index],
+			header: column.name,
 			cell: ({ getValue }) => {
 				const value = getValue();
 				if (value === null) {
 					return <span className="text-muted-foreground italic">null</span>;
 				}
-				const rawValue =
-					typeof value === "object" ? JSON.stringify(value) : String(value);
+				const rawValue = cellText(value);
 				const displayValue =
 					rawValue.length > 200 ? `${rawValue.slice(0, 200)}…` : rawValue;
 				return <span title={rawValue}>{displayValue}</span>;
 			},
 		}));
-	}, [tab.results]);
+	}, [tab.results, tab.resultColumns]);
+	const querySort = useMemo(() => {
+		if (!tab.sort) return null;
+		const index = tab.resultColumns.findIndex(
+			(column) => column.name === tab.sort?.column,
+		);
+		return index < 0
+			? null
+			: { column: String(index), direction: tab.sort.direction };
+	}, [tab.sort, tab.resultColumns]);
+	const editTarget = tab.editTarget;
+	const editingRow =
+		controller.rowEdit.rowIndex === null
+			? undefined
+			: tab.results?.[controller.rowEdit.rowIndex];
 	const renderQueryError = (errorMessage: string) => {
 		const trimmedError = errorMessage.trimEnd();
 
@@ -247,6 +272,7 @@ export function QueryWorkspace({
 						}))}
 						ai={getEditorAiProps(tab)}
 						onCursorActivity={controller.handleCursorActivity}
+						errorOffset={tab.errorOffset}
 					/>
 				</CardContent>
 			</Card>
@@ -359,13 +385,29 @@ export function QueryWorkspace({
 										hidePagination
 										virtualize={tab.results.length > 100}
 										sortable={!!tab.resultBaseQuery}
-										sort={tab.sort}
+										sort={querySort}
 										onSortChange={
-											tab.resultBaseQuery ? controller.changeSort : undefined
+											tab.resultBaseQuery
+												? (sort) =>
+														controller.changeSort(
+															sort && {
+																column:
+																	tab.resultColumns[Number(sort.column)].name,
+																direction: sort.direction,
+															},
+														)
+												: undefined
 										}
 										onRowClick={(row) => {
 											const index = tab.results?.indexOf(row) ?? -1;
-											setSelectedQueryRow({ row, index });
+											if (editTarget) {
+												controller.rowEdit.open(index);
+												return;
+											}
+											setSelectedQueryRow({
+												row: resultRowObject(tab.resultColumns, row),
+												index,
+											});
 											setQueryResultSheetOpen(true);
 										}}
 									/>
@@ -405,6 +447,25 @@ export function QueryWorkspace({
 				row={selectedQueryRow?.row || null}
 				rowIndex={selectedQueryRow?.index}
 			/>
+			{editTarget && (
+				<RowEditSheet
+					open={editingRow !== undefined}
+					onOpenChange={(open) => {
+						if (!open) controller.rowEdit.close();
+					}}
+					tableName={`${editTarget.schema}.${editTarget.table}`}
+					row={
+						editingRow ? editTargetRow(tab.resultColumns, editingRow) : null
+					}
+					columns={editTargetColumns(tab.resultColumns, editTarget)}
+					dbType={connection.type}
+					rowEditing={rowEditing}
+					onSave={controller.rowEdit.save}
+					onDelete={controller.rowEdit.delete}
+					saving={controller.rowEdit.saving}
+					deleting={controller.rowEdit.deleting}
+				/>
+			)}
 		</div>
 	);
 }
@@ -0,0 +1,535 @@
+import { useCallback, useEffect, useRef, useState } from "react";
+import { listen } from "@tauri-apps/api/event";
+import { ArrowClockwise, Play, Stop } from "@phosphor-icons/react";
+import { toast } from "sonner";
+import {
+	AlertDialog,
+	AlertDialogAction,
+	AlertDialogCancel,
+	AlertDialogContent,
+	AlertDialogDescription,
+	AlertDialogFooter,
+	AlertDialogHeader,
+	AlertDialogTitle,
+} from "@/components/ui/alert-dialog";
+import { Button } from "@/components/ui/button";
+import { Card, CardContent, CardHeader, CardTitle } from "@/components/ui/card";
+import { Input } from "@/components/ui/input";
+import { Spinner } from "@/components/ui/spinner";
+import {
+	Table,
+	TableBody,
+	TableCell,
+	TableHead,
+	TableHeader,
+	TableRow,
+} from "@/components/ui/table";
+import { Tabs, TabsContent, TabsList, TabsTrigger } from "@/components/ui/tabs";
+import {
+	appendMonitorEvents,
+	clampMonitorSeconds,
+	DEFAULT_MONITOR_SECS,
+	formatMicroseconds,
+	formatRedisCommand,
+	MAX_MONITOR_SECS,
+} from "@/lib/redisDiagnostics";
+import {
+	api,
+	REDIS_LIVE_MESSAGES_EVENT,
+	REDIS_LIVE_STOPPED_EVENT,
+	type RedisClientInfo,
+	type RedisLatencySample,
+	type RedisLiveBatch,
+	type RedisLiveStopped,
+	type RedisMonitorEvent,
+} from "@/lib/tauri";
+
+type DiagnosticsView = "slowlog" | "latency" | "clients" | "monitor";
+
+function formatUnixTime(seconds: number): string {
+	return new Date(seconds * 1000).toLocaleString();
+}
+
+/** Loads one diagnostic when mounted and again on refresh. */
+function useDiagnostic<T>(load: () => Promise<T>, label: string) {
+	const [data, setData] = useState<T | null>(null);
+	const [loading, setLoading] = useState(false);
+
+	const refresh = useCallback(async () => {
+		setLoading(true);
+		try {
+			setData(await load());
+		} catch (error) {
+			console.error(`Failed to load ${label}:`, error);
+			toast.error(`Failed to load ${label}`);
+		} finally {
+			setLoading(false);
+		}
+	}, [load, label]);
+
+	useEffect(() => {
+		void refresh();
+	}, [refresh]);
+
+	return { data, loading, refresh };
+}
+
+function RefreshBar({
+	summary,
+	loading,
+	onRefresh,
+}: {
+	summary: string;
+	loading: boolean;
+	onRefresh: () => void;
+}) {
+	return (
+		<div className="flex items-center justify-between">
+			<span className="text-sm text-muted-foreground">{summary}</span>
+			<Button variant="outline" size="sm" onClick={onRefresh} disabled={loading}>
+				{loading ? <Spinner /> : <ArrowClockwise className="w-4 h-4" />}
+				Refresh
+			</Button>
+		</div>
+	);
+}
+
+function SlowlogView({ connectionUuid }: { connectionUuid: string }) {
+	const load = useCallback(
+		() => api.redis.slowlog(connectionUuid),
+		[connectionUuid],
+	);
+	const { data, loading, refresh } = useDiagnostic(load, "slow log");
+	const entries = data ?? [];
+
+	return (
+		<div className="space-y-3">
+			<RefreshBar
+				summary={`${entries.length} slow log entr${entries.length !== 1 ? "ies" : "y"}`}
+				loading={loading}
+				onRefresh={refresh}
+			/>
+			{entries.length > 0 && (
+				<div className="max-h-80 overflow-auto border rounded-md">
+					<Table>
+						<TableHeader>
+							<TableRow>
+								<TableHead>Time</TableHead>
+								<TableHead>Duration</TableHead>
+								<TableHead>Command</TableHead>
+								<TableHead>Client</TableHead>
+							</TableRow>
+						</TableHeader>
+						<TableBody>
+							{entries.map((entry) => (
+								<TableRow key={`${entry.node ?? ""}:${entry.id}`}>
+									<TableCell className="text-xs whitespace-nowrap">
+										{formatUnixTime(entry.timestamp)}
+									</TableCell>
+									<TableCell className="font-mono text-xs">
+										{formatMicroseconds(entry.duration_us)}
+									</TableCell>
+									<TableCell className="font-mono text-xs max-w-md truncate">
+										{formatRedisCommand(entry.command)}
+									</TableCell>
+									<TableCell className="font-mono text-xs">
+										{entry.client_name || entry.client_addr || ""}
+									</TableCell>
+								</TableRow>
+							))}
+						</TableBody>
+					</Table>
+				</div>
+			)}
+		</div>
+	);
+}
+
+function LatencyView({ connectionUuid }: { connectionUuid: string }) {
+	const load = useCallback(
+		() => api.redis.latencyLatest(connectionUuid),
+		[connectionUuid],
+	);
+	const { data, loading, refresh } = useDiagnostic(load, "latency events");
+	const [selectedEvent, setSelectedEvent] = useState<string | null>(null);
+	const [history, setHistory] = useState<RedisLatencySample[]>([]);
+	const events = data ?? [];
+
+	const handleSelectEvent = async (event: string) => {
+		setSelectedEvent(event);
+		setHistory([]);
+		try {
+			setHistory(await api.redis.latencyHistory(connectionUuid, event));
+		} catch (error) {
+			console.error("Failed to load latency history:", error);
+			toast.error("Failed to load latency history");
+		}
+	};
+
+	return (
+		<div className="space-y-3">
+			<RefreshBar
+				summary={
+					events.length === 0
+						? "No latency spikes recorded (is latency-monitor-threshold set?)"
+						: `${events.length} latency event${events.length !== 1 ? "s" : ""}`
+				}
+				loading={loading}
+				onRefresh={refresh}
+			/>
+			{events.length > 0 && (
+				<div className="max-h-80 overflow-auto border rounded-md">
+					<Table>
+						<TableHeader>
+							<TableRow>
+								<TableHead>Event</TableHead>
+								<TableHead>Latest</TableHead>
+								<TableHead>Max</TableHead>
+								<TableHead>Last spike</TableHead>
+							</TableRow>
+						</TableHeader>
+						<TableBody>
+							{events.map((event) => (
+								<TableRow
+									key={`${event.node ?? ""}:${event.event}`}
+									className="cursor-pointer"
+									data-state={
+										selectedEvent === event.event ? "selected" : undefined
+									}
+									onClick={() => handleSelectEvent(event.event)}
+								>
+									<TableCell className="font-mono text-xs">
+										{event.event}
+									</TableCell>
+									<TableCell className="font-mono text-xs">
+										{event.latest_ms}ms
+									</TableCell>
+									<TableCell className="font-mono text-xs">
+										{event.max_ms}ms
+									</TableCell>
+									<TableCell className="text-xs whitespace-nowrap">
+										{formatUnixTime(event.timestamp)}
+									</TableCell>
+								</TableRow>
+							))}
+						</TableBody>
+					</Table>
+				</div>
+			)}
+			{selectedEvent && history.length > 0 && (
+				<div className="space-y-1">
+					<div className="text-sm font-medium">
+						History of <span className="font-mono">{selectedEvent}</span>
+					</div>
+					<div className="max-h-48 overflow-auto border rounded-md p-2 font-mono text-xs space-y-0.5">
+						{history.map((sample) => (
+							<div key={`${sample.node ?? ""}:${sample.timestamp}`}>
+								{formatUnixTime(sample.timestamp)} • {sample.latency_ms}ms
+							</div>
+						))}
+					</div>
+				</div>
+			)}
+		</div>
+	);
+}
+
+function ClientsView({ connectionUuid }: { connectionUuid: string }) {
+	const load = useCallback(
+		() => api.redis.clientList(connectionUuid),
+		[connectionUuid],
+	);
+	const { data, loading, refresh } = useDiagnostic(load, "clients");
+	const [clientToKill, setClientToKill] = useState<RedisClientInfo | null>(
+		null,
+	);
+	const clients = data ?? [];
+
+	const handleKillClient = async () => {
+		const client = clientToKill;
+		setClientToKill(null);
+		if (!client) return;
+
+		try {
+			const killed = await api.redis.killClient(
+				connectionUuid,
+				client.node,
+				client.id,
+				client.addr,
+			);
+			if (killed) {
+				toast.success(`Client ${client.id} killed`);
+			} else {
+				toast.info(`Client ${client.id} had already disconnected`);
+			}
+			void refresh();
+		} catch (error) {
+			console.error("Failed to kill Redis client:", error);
+			toast.error("Failed to kill client");
+		}
+	};
+
+	return (
+		<div className="space-y-3">
+			<RefreshBar
+				summary={`${clients.length} connected client${clients.length !== 1 ? "s" : ""}`}
+				loading={loading}
+				onRefresh={refresh}
+			/>
+			{clients.length > 0 && (
+				<div className="max-h-80 overflow-auto border rounded-md">
+					<Table>
+						<TableHeader>
+							<TableRow>
+								<TableHead>ID</TableHead>
+								<TableHead>Address</TableHead>
+								<TableHead>Name</TableHead>
+								<TableHead>Age</TableHead>
+								<TableHead>Idle</TableHead>
+								<TableHead>DB</TableHead>
+								<TableHead>Last command</TableHead>
+								<TableHead />
+							</TableRow>
+						</TableHeader>
+						<TableBody>
+							{clients.map((client) => (
+								<TableRow key={`${client.node ?? ""}:${client.id}`}>
+									<TableCell className="font-mono text-xs">
+										{client.id}
+									</TableCell>
+									<TableCell className="font-mono text-xs">
+										{client.addr}
+									</TableCell>
+									<TableCell className="font-mono text-xs">
+										{client.name ?? ""}
+									</TableCell>
+									<TableCell className="text-xs">{client.age_secs}s</TableCell>
+									<TableCell className="text-xs">{client.idle_secs}s</TableCell>
+									<TableCell className="text-xs">{client.db}</TableCell>
+									<TableCell className="font-mono text-xs">
+										{client.cmd}
+									</TableCell>
+									<TableCell className="text-right">
+										<Button
+											variant="ghost"
+											size="sm"
+											className="h-7 px-2 text-destructive"
+											onClick={() => setClientToKill(client)}
+										>
+											Kill
+										</Button>
+									</TableCell>
+								</TableRow>
+							))}
+						</TableBody>
+					</Table>
+				</div>
+			)}
+
+			<AlertDialog
+				open={clientToKill !== null}
+				onOpenChange={(open) => {
+					if (!open) setClientToKill(null);
+				}}
+			>
+				<AlertDialogContent>
+					<AlertDialogHeader>
+						<AlertDialogTitle>Kill Redis Client?</AlertDialogTitle>
+						<AlertDialogDescription>
+							This closes the connection of client{" "}
+							<span className="font-mono bg-muted px-2 py-0.5 rounded">
+								{clientToKill?.id}
+							</span>{" "}
+							at{" "}
+							<span className="font-mono bg-muted px-2 py-0.5 rounded">
+								{clientToKill?.addr}
+							</span>
+							. The application behind it may reconnect or fail.
+						</AlertDialogDescription>
+					</AlertDialogHeader>
+					<AlertDialogFooter>
+						<AlertDialogCancel>Cancel</AlertDialogCancel>
+						<AlertDialogAction onClick={handleKillClient} variant="destructive">
+							Kill Client
+						</AlertDialogAction>
+					</AlertDialogFooter>
+				</AlertDialogContent>
+			</AlertDialog>
+		</div>
+	);
+}
+
+function MonitorView({ connectionUuid }: { connectionUuid: string }) {
+	const [durationSecs, setDurationSecs] = useState(DEFAULT_MONITOR_SECS);
+	const [events, setEvents] = useState<RedisMonitorEvent[]>([]);
+	const [dropped, setDropped] = useState(0);
+	const [starting, setStarting] = useState(false);
+	const [subscriptionId, setSubscriptionId] = useState<string | null>(null);
+	const subscriptionIdRef = useRef<string | null>(null);
+
+	// Collect the commands of this view's capture until it stops
+	useEffect(() => {
+		let isMounted = true;
+		const unlisteners: Array<() => void> = [];
+
+		const setupListeners = async () => {
+			const listeners = await Promise.all([
+				listen<RedisLiveBatch>(REDIS_LIVE_MESSAGES_EVENT, (event) => {
+					if (event.payload.subscription_id !== subscriptionIdRef.current) {
+						return;
+					}
+					setEvents((previous) =>
+						appendMonitorEvents(previous, event.payload.messages),
+					);
+					setDropped((previous) => previous + event.payload.dropped);
+				}),
+				listen<RedisLiveStopped>(REDIS_LIVE_STOPPED_EVENT, (event) => {
+					if (event.payload.subscription_id !== subscriptionIdRef.current) {
+						return;
+					}
+					subscriptionIdRef.current = null;
+					setSubscriptionId(null);
+					if (event.payload.error) toast.error(event.payload.error);
+				}),
+			]);
+
+			if (isMounted) {
+				unlisteners.push(...listeners);
+			} else {
+				for (const unlisten of listeners) unlisten();
+			}
+		};
+
+		setupListeners();
+
+		return () => {
+			isMounted = false;
+			for (const unlisten of unlisteners) unlisten();
+			// MONITOR slows the server down, so never leave it running unseen
+			if (subscriptionIdRef.current) {
+				void api.redis.unsubscribe(subscriptionIdRef.current);
+				subscriptionIdRef.current = null;
+			}
+		};
+	}, []);
+
+	const handleStart = async () => {
+		setStarting(true);
+		setEvents([]);
+		setDropped(0);
+		try {
+			const info = await api.redis.subscribe(connectionUuid, {
+				kind: "monitor",
+				duration_secs: clampMonitorSeconds(durationSecs),
+			});
+			subscriptionIdRef.current = info.id;
+			setSubscriptionId(info.id);
+		} catch (error) {
+			console.error("Failed to start MONITOR:", error);
+			toast.error(`Failed to start MONITOR: ${error}`);
+		} finally {
+			setStarting(false);
+		}
+	};
+
+	const handleStop = async () => {
+		const id = subscriptionIdRef.current;
+		subscriptionIdRef.current = null;
+		setSubscriptionId(null);
+		if (id) await api.redis.unsubscribe(id);
+	};
+
+	const running = subscriptionId !== null;
+
+	return (
+		<div className="space-y-3">
+			<div className="flex items-center gap-2">
+				<Input
+					type="number"
+					min={1}
+					max={MAX_MONITOR_SECS}
+					value={durationSecs}
+					onChange={(e) => setDurationSecs(Number(e.target.value))}
+					onBlur={() => setDurationSecs(clampMonitorSeconds(durationSecs))}
+					disabled={running || starting}
+					className="w-24"
+				/>
+				<span className="text-sm text-muted-foreground">seconds</span>
+				{running ? (
+					<Button variant="outline" onClick={handleStop}>
+						<Stop className="w-4 h-4" />
+						Stop
+					</Button>
+				) : (
+					<Button onClick={handleStart} disabled={starting}>
+						{starting ? <Spinner /> : <Play className="w-4 h-4" />}
+						Start MONITOR
+					</Button>
+				)}
+				<span className="text-sm text-muted-foreground ml-auto">
+					{events.length} command{events.length !== 1 ? "s" : ""}
+					{dropped > 0 && <span className="ml-2">• {dropped} dropped</span>}
+				</span>
+			</div>
+			<div className="text-xs text-muted-foreground">
+				MONITOR reports every command the server runs and slows a busy server
+				down. The capture stops on its own after the time above.
+			</div>
+			{events.length > 0 && (
+				<div className="max-h-80 overflow-auto border rounded-md p-2 font-mono text-xs space-y-0.5">
+					{events.map((event, index) => (
+						<div key={index} className="truncate">
+							<span className="text-muted-foreground">
+								{new Date(event.timestamp * 1000).toLocaleTimeString()} [
+								{event.db} {event.client}]
+							</span>{" "}
+							{formatRedisCommand(event.command)}
+						</div>
+					))}
+				</div>
+			)}
+		</div>
+	);
+}
+
+/** The slow log, latency spikes, connected clients and a `MONITOR` capture. */
+export function RedisDiagnosticsPanel({
+	connectionUuid,
+}: {
+	connectionUuid: string;
+}) {
+	const [view, setView] = useState<DiagnosticsView>("slowlog");
+
+	return (
+		<Card>
+			<CardHeader className="pb-3">
+				<CardTitle className="text-base">Diagnostics</CardTitle>
+			</CardHeader>
+			<CardContent>
+				<Tabs
+					value={view}
+					onValueChange={(value) => setView(value as DiagnosticsView)}
+				>
+					<TabsList>
+						<TabsTrigger value="slowlog">Slow Log</TabsTrigger>
+						<TabsTrigger value="latency">Latency</TabsTrigger>
+						<TabsTrigger value="clients">Clients</TabsTrigger>
+						<TabsTrigger value="monitor">Monitor</TabsTrigger>
+					</TabsList>
+					<TabsContent value="slowlog" className="mt-3">
+						<SlowlogView connectionUuid={connectionUuid} />
+					</TabsContent>
+					<TabsContent value="latency" className="mt-3">
+						<LatencyView connectionUuid={connectionUuid} />
+					</TabsContent>
+					<TabsContent value="clients" className="mt-3">
+						<ClientsView connectionUuid={connectionUuid} />
+					</TabsContent>
+					<TabsContent value="monitor" className="mt-3">
+						<MonitorView connectionUuid={connectionUuid} />
+					</TabsContent>
+				</Tabs>
+			</CardContent>
+		</Card>
+	);
+}
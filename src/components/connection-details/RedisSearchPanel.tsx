@@ -0,0 +1,178 @@
+import { useEffect, useState } from "react";
+import { MagnifyingGlass } from "@phosphor-icons/react";
+import { toast } from "sonner";
+import { Button } from "@/components/ui/button";
+import { Card, CardContent, CardHeader, CardTitle } from "@/components/ui/card";
+import { Input } from "@/components/ui/input";
+import {
+	Select,
+	SelectContent,
+	SelectGroup,
+	SelectItem,
+	SelectTrigger,
+	SelectValue,
+} from "@/components/ui/select";
+import { Spinner } from "@/components/ui/spinner";
+import {
+	Table,
+	TableBody,
+	TableCell,
+	TableHead,
+	TableHeader,
+	TableRow,
+} from "@/components/ui/table";
+import { cellText } from "@/lib/cellValues";
+import { searchCommand, searchIndexNames } from "@/lib/redisModules";
+import { api, type QueryResult } from "@/lib/tauri";
+
+const SEARCH_LIMIT = 100;
+
+/** Runs `FT.SEARCH` on the server's RediSearch indexes. */
+export function RedisSearchPanel({
+	connectionUuid,
+}: {
+	connectionUuid: string;
+}) {
+	const [indexes, setIndexes] = useState<string[]>([]);
+	const [index, setIndex] = useState<string | null>(null);
+	const [query, setQuery] = useState("*");
+	const [result, setResult] = useState<QueryResult | null>(null);
+	const [searching, setSearching] = useState(false);
+
+	useEffect(() => {
+		let cancelled = false;
+		api.pool
+			.listTables(connectionUuid)
+			.then((tables) => {
+				if (cancelled) return;
+				const names = searchIndexNames(tables);
+				setIndexes(names);
+				setIndex((current) => current ?? names[0] ?? null);
+			})
+			.catch((error) => {
+				console.error("Failed to list search indexes:", error);
+			});
+		return () => {
+			cancelled = true;
+		};
+	}, [connectionUuid]);
+
+	const handleSearch = async () => {
+		if (!index) return;
+		setSearching(true);
+		try {
+			const searchResult = await api.pool.executeQuery(
+				connectionUuid,
+				searchCommand(index, query, SEARCH_LIMIT),
+			);
+			setResult(searchResult);
+			if (searchResult.error) toast.error(searchResult.error);
+		} catch (error) {
+			console.error("Failed to search index:", error);
+			toast.error("Failed to search index");
+		} finally {
+			setSearching(false);
+		}
+	};
+
+	const rows = result && !result.error ? result.rows : [];
+	const columns = result && !result.error ? result.columns : [];
+	const items = indexes.map((name) => ({ value: name, label: name }));
+
+	return (
+		<Card>
+			<CardHeader className="pb-3">
+				<CardTitle className="text-base">Search Indexes</CardTitle>
+			</CardHeader>
+			<CardContent className="space-y-3">
+				{indexes.length === 0 ? (
+					<div className="text-sm text-muted-foreground">
+						No RediSearch indexes on this server
+					</div>
+				) : (
+					<div className="flex items-center gap-2">
+						<Select
+							name="redis-search-index"
+							items={items}
+							value={index}
+							onValueChange={(value) => value && setIndex(value)}
+						>
+							<SelectTrigger className="w-56 font-mono">
+								<SelectValue />
+							</SelectTrigger>
+							<SelectContent>
+								<SelectGroup>
+									{items.map((item) => (
+										<SelectItem key={item.value} value={item.value}>
+											{item.label}
+										</SelectItem>
+									))}
+								</SelectGroup>
+							</SelectContent>
+						</Select>
+						<Input
+							placeholder="Query (e.g., *, @name:ada, @age:[30 40])"
+							value={query}
+							onChange={(e) => setQuery(e.target.value)}
+							onKeyDown={(e) => {
+								if (e.key === "Enter" && !searching) {
+									handleSearch();
+								}
+							}}
+							disabled={searching}
+							className="flex-1 font-mono"
+						/>
+						<Button onClick={handleSearch} disabled={searching || !index}>
+							{searching ? (
+								<Spinner />
+							) : (
+								<MagnifyingGlass className="w-4 h-4" />
+							)}
+							Search
+						</Button>
+					</div>
+				)}
+
+				{result && !result.error && (
+					<>
+						<div className="text-sm text-muted-foreground">
+							{result.row_count} document{result.row_count !== 1 ? "s" : ""}
+							{result.time_taken_ms != null && (
+								<span className="ml-2">• {result.time_taken_ms}ms</span>
+							)}
+						</div>
+						{rows.length > 0 && (
+							<div className="max-h-80 overflow-auto border rounded-md">
+								<Table>
+									<TableHeader>
+										<TableRow>
+											{columns.map((column) => (
+												<TableHead key={column.name} className="font-mono">
+													{column.name}
+												</TableHead>
+											))}
+										</TableRow>
+									</TableHeader>
+									<TableBody>
+										{rows.map((row, rowIndex) => (
+											<TableRow key={rowIndex}>
+												{columns.map((column, index) => (
+													<TableCell
+														key={column.name}
+														className="font-mono text-xs max-w-xs truncate"
+													>
+														{row[index] == null ? "" : cellText(row[index])}
+													</TableCell>
+												))}
+											</TableRow>
+										))}
+									</TableBody>
+								</Table>
+							</div>
+						)}
+					</>
+				)}
+			</CardContent>
+		</Card>
+	);
+}
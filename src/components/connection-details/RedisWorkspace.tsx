@@ -4,7 +4,9 @@ import { listen } from "@tauri-apps/api/event";
 import { Check, Copy, Plus } from "@phosphor-icons/react";
 import { toast } from "sonner";
 import { ExpandableText } from "@/components/ExpandableText";
-import { RedisKeySheet } from "@/components/RedisKeySheet";
+import { RedisKeySheet, type RedisKeyType } from "@/components/RedisKeySheet";
+import { RedisDiagnosticsPanel } from "./RedisDiagnosticsPanel";
+import { RedisSearchPanel } from "./RedisSearchPanel";
 import { Button } from "@/components/ui/button";
 import { Card, CardContent, CardHeader, CardTitle } from "@/components/ui/card";
 import { Input } from "@/components/ui/input";
@@ -32,6 +34,7 @@ import {
 	type Connection,
 	type RedisKeyDetails,
 	type RedisKeyInfo,
+	type RedisModules,
 } from "@/lib/tauri";
 export function RedisWorkspace({ connection }: { connection: Connection }) {
 	// Redis-specific state (no tabs for Redis)
@@ -59,6 +62,7 @@ export function RedisWorkspace({ connection }: { connection: Connection }) {
 	const [redisScanCursor, setRedisScanCursor] = useState<number | null>(null);
 	const [redisScanComplete, setRedisScanComplete] = useState<boolean>(true);
 	const [redisScanBaseCount, setRedisScanBaseCount] = useState<number>(0);
+	const [redisModules, setRedisModules] = useState<RedisModules | null>(null);
 
 	// Ref for Redis keys list virtualization
 	const redisKeysListRef = useRef<HTMLDivElement>(null);
@@ -71,6 +75,22 @@ export function RedisWorkspace({ connection }: { connection: Connection }) {
 		overscan: 10,
 	});
 
+	// Detect RedisJSON and RediSearch, which add JSON keys and index search
+	useEffect(() => {
+		let cancelled = false;
+		api.redis
+			.getModules(connection.uuid)
+			.then((modules) => {
+				if (!cancelled) setRedisModules(modules);
+			})
+			.catch((error) => {
+				console.error("Failed to detect Redis modules:", error);
+			});
+		return () => {
+			cancelled = true;
+		};
+	}, [connection.uuid]);
+
 	// Listen for Redis scan progress events
 	useEffect(() => {
 		let isMounted = true;
@@ -245,9 +265,10 @@ export function RedisWorkspace({ connection }: { connection: Connection }) {
 
 	const handleRedisSaveKey = async (data: {
 		key: string;
-		type: "string" | "list" | "set" | "hash" | "zset";
+		type: RedisKeyType;
 		value: unknown;
 		ttl?: number;
+		path?: string;
 	}) => {
 		if (!connection) return;
 
@@ -294,6 +315,18 @@ export function RedisWorkspace({ connection }: { connection: Connection }) {
 						data.ttl,
 					);
 					break;
+				case "json":
+					await api.redis.jsonSet(
+						connection.uuid,
+						data.key,
+						data.value,
+						data.path,
+					);
+					// JSON.SET keeps the expiry, so apply the TTL the form shows.
+					if (data.ttl !== undefined || redisKeySheetMode === "edit") {
+						await api.redis.updateTTL(connection.uuid, data.key, data.ttl);
+					}
+					break;
 			}
 
 			toast.success(
@@ -355,6 +388,12 @@ export function RedisWorkspace({ connection }: { connection: Connection }) {
 				</CardContent>
 			</Card>
 
+			{redisModules?.search && (
+				<RedisSearchPanel connectionUuid={connection.uuid} />
+			)}
+
+			<RedisDiagnosticsPanel connectionUuid={connection.uuid} />
+
 			{/* Results */}
 			<Card className="flex-1 overflow-hidden flex flex-col">
 				<CardHeader className="pb-3">
@@ -676,6 +715,14 @@ export function RedisWorkspace({ connection }: { connection: Connection }) {
 				keyDetails={redisKeySheetMode === "edit" ? redisKeyDetails : null}
 				onSave={handleRedisSaveKey}
 				saving={savingRedisKey}
+				jsonEnabled={redisModules?.json ?? false}
+				onLoadJsonPath={(key, path) =>
+					api.redis.jsonGet(connection.uuid, key, path)
+				}
+				onDeleteJsonPath={async (key, path) => {
+					await api.redis.jsonDelete(connection.uuid, key, path);
+					toast.success(`Deleted ${path}`);
+				}}
 			/>
 		</div>
 	);
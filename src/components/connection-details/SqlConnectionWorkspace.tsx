@@ -107,6 +107,7 @@ export function SqlConnectionWorkspace({
 	const tables = lifecycle.schema.tables;
 	const tableColumns = lifecycle.schema.tableColumns;
 	const schemaOverview = lifecycle.schema.overview;
+	const rowEditing = lifecycle.connection.capabilities?.row_editing ?? false;
 
 	const {
 		generateDraft,
@@ -284,6 +285,7 @@ export function SqlConnectionWorkspace({
 					<TableDataWorkspace
 						tab={activeTab}
 						connection={connection}
+						rowEditing={rowEditing}
 						controller={tableDataController.workspace}
 						onOpenTableDataWithFilter={tabActions.handleOpenTableDataWithFilter}
 					/>
@@ -295,6 +297,7 @@ export function SqlConnectionWorkspace({
 					<QueryWorkspace
 						tab={activeTab}
 						connection={connection}
+						rowEditing={rowEditing}
 						tables={tables}
 						tableColumns={tableColumns}
 						controller={queryController.workspace}
@@ -318,6 +321,11 @@ export function SqlConnectionWorkspace({
 					refreshing={lifecycle.schema.refreshing || lifecycle.schema.loading}
 					onOpenSchemaVisualizer={tabActions.handleOpenSchemaVisualizer}
 					onRefresh={lifecycle.commands.refreshSchema}
+					onOpenDatabase={
+						lifecycle.connection.capabilities?.multiple_databases
+							? lifecycle.commands.openDatabase
+							: undefined
+					}
 				/>
 				<SidebarContent className="overflow-hidden p-2">
 					<Tabs
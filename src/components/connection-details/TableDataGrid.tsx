@@ -6,10 +6,9 @@ import { InlineEditableCell } from "@/components/InlineEditableCell";
 import { Button } from "@/components/ui/button";
 import { Skeleton } from "@/components/ui/skeleton";
 import { Spinner } from "@/components/ui/spinner";
-import { supportsStructuredRowMutations } from "@/lib/databaseCapabilities";
+import { cellText, isTaggedCell } from "@/lib/cellValues";
 import { getPrimaryKeyRowKey } from "@/lib/connection-details/queryTableState";
 import type { TableColumnLayout } from "@/lib/savedViews";
-import type { ConnectionType } from "@/types/connection";
 import type { SortConfig, TableDataTab } from "@/types/tabTypes";
 
 interface PendingInlineCellEdit {
@@ -59,7 +58,7 @@ export function PendingInlineChangesBar({
 
 export function TableDataGrid({
 	tab,
-	dbType,
+	rowEditing,
 	pendingInlineEdits,
 	highlightedRow,
 	onOpenTableDataWithFilter,
@@ -71,7 +70,7 @@ export function TableDataGrid({
 	onColumnLayoutChange,
 }: {
 	tab: TableDataTab;
-	dbType: ConnectionType;
+	rowEditing: boolean;
 	pendingInlineEdits: Record<string, PendingInlineCellEdit>;
 	highlightedRow: { tableName: string; rowKey: string } | null;
 	onOpenTableDataWithFilter: (
@@ -131,15 +130,11 @@ export function TableDataGrid({
 						value === null ? (
 							<span className="text-muted-foreground italic">null</span>
 						) : null;
-					const rawValue =
-						typeof value === "object" ? JSON.stringify(value) : String(value);
+					const rawValue = cellText(value);
 					const displayValue =
 						rawValue.length > 200 ? `${rawValue.slice(0, 200)}…` : rawValue;
 					const canEditInline =
-						!!column &&
-						!column.primary_key &&
-						hasPrimaryKey &&
-						supportsStructuredRowMutations(dbType);
+						!!column && !column.primary_key && hasPrimaryKey && rowEditing;
 
 					const content =
 						nullContent ??
@@ -154,10 +149,10 @@ export function TableDataGrid({
 										onOpenTableDataWithFilter(
 											`${schema}.${foreignKey.references_table}`,
 											foreignKey.references_column,
-											value,
+											isTaggedCell(value) ? rawValue : value,
 										);
 									}}
-									title={`View ${foreignKey.references_table} where ${foreignKey.references_column} = ${value}`}
+									title={`View ${foreignKey.references_table} where ${foreignKey.references_column} = ${rawValue}`}
 								>
 									<ArrowRight className="w-3.5 h-3.5 text-primary" />
 								</button>
@@ -189,7 +184,7 @@ export function TableDataGrid({
 		});
 	}, [
 		tab,
-		dbType,
+		rowEditing,
 		pendingInlineEdits,
 		onOpenTableDataWithFilter,
 		onInlineCellSave,
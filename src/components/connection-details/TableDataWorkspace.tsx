@@ -11,7 +11,6 @@ import {
 } from "@/components/ui/card";
 import { Spinner } from "@/components/ui/spinner";
 import type { TableDataWorkspaceController } from "@/hooks/connection-details/useTableDataController";
-import { supportsStructuredRowMutations } from "@/lib/databaseCapabilities";
 import {
 	captureSavedViewState,
 	hasUnappliedFilterDraft,
@@ -26,6 +25,7 @@ import { TableFilterBar } from "./TableFilterBar";
 interface TableDataWorkspaceProps {
 	tab: TableDataTab;
 	connection: SqlConnection;
+	rowEditing: boolean;
 	controller: TableDataWorkspaceController;
 	onOpenTableDataWithFilter: (
 		tableName: string,
@@ -37,6 +37,7 @@ interface TableDataWorkspaceProps {
 export function TableDataWorkspace({
 	tab,
 	connection,
+	rowEditing,
 	controller,
 	onOpenTableDataWithFilter,
 }: TableDataWorkspaceProps) {
@@ -82,7 +83,7 @@ export function TableDataWorkspace({
 								layout={tab.columnLayout}
 								onChange={controller.columnLayout.change}
 							/>
-							{supportsStructuredRowMutations(connection.db_type) && (
+							{rowEditing && (
 								<Button
 									variant="default"
 									size="sm"
@@ -127,7 +128,7 @@ export function TableDataWorkspace({
 				<CardContent className="max-h-[65vh] flex flex-col">
 					<TableDataGrid
 						tab={tab}
-						dbType={connection.db_type}
+						rowEditing={rowEditing}
 						pendingInlineEdits={controller.inlineEdits.byTab[tab.id] ?? {}}
 						highlightedRow={controller.highlightedRow}
 						onOpenTableDataWithFilter={onOpenTableDataWithFilter}
@@ -148,6 +149,7 @@ export function TableDataWorkspace({
 				row={controller.rowEdit.row}
 				columns={tab.columns}
 				dbType={connection.type}
+				rowEditing={rowEditing}
 				onSave={controller.rowEdit.save}
 				onDelete={controller.rowEdit.delete}
 				saving={controller.rowEdit.saving}
@@ -0,0 +1,117 @@
+import { Field, FieldDescription, FieldLabel } from "@/components/ui/field";
+import { Input } from "@/components/ui/input";
+import {
+	Select,
+	SelectContent,
+	SelectGroup,
+	SelectItem,
+	SelectTrigger,
+	SelectValue,
+} from "@/components/ui/select";
+import type {
+	RedisMode,
+	RedisTopologyFieldValues,
+} from "@/lib/connectionFormState";
+
+const modes: { value: RedisMode; label: string }[] = [
+	{ value: "standalone", label: "Standalone" },
+	{ value: "cluster", label: "Cluster" },
+	{ value: "sentinel", label: "Sentinel" },
+];
+
+interface RedisTopologyFieldsProps extends RedisTopologyFieldValues {
+	onChange: (values: Partial<RedisTopologyFieldValues>) => void;
+}
+
+export function RedisTopologyFields({
+	mode,
+	nodes,
+	sentinelMaster,
+	sentinelPassword,
+	onChange,
+}: RedisTopologyFieldsProps) {
+	return (
+		<>
+			<Field>
+				<FieldLabel htmlFor="connection-redis-mode">Mode</FieldLabel>
+				<Select
+					name="redis-mode"
+					items={modes}
+					value={mode}
+					onValueChange={(value) =>
+						value && onChange({ mode: value as RedisMode })
+					}
+				>
+					<SelectTrigger id="connection-redis-mode" className="w-full">
+						<SelectValue />
+					</SelectTrigger>
+					<SelectContent>
+						<SelectGroup>
+							{modes.map((option) => (
+								<SelectItem key={option.value} value={option.value}>
+									{option.label}
+								</SelectItem>
+							))}
+						</SelectGroup>
+					</SelectContent>
+				</Select>
+			</Field>
+
+			{mode !== "standalone" && (
+				<Field>
+					<FieldLabel htmlFor="connection-redis-nodes">
+						{mode === "cluster" ? "Other nodes" : "Other sentinels"}
+					</FieldLabel>
+					<Input
+						id="connection-redis-nodes"
+						value={nodes}
+						onChange={(event) => onChange({ nodes: event.target.value })}
+						placeholder={
+							mode === "cluster"
+								? "redis-2:6379, redis-3:6379"
+								: "sentinel-2:26379, sentinel-3:26379"
+						}
+					/>
+					<FieldDescription>
+						{mode === "cluster"
+							? "Tried after the host above when it is unreachable."
+							: "The host above is the first sentinel; these are asked when it is down."}
+					</FieldDescription>
+				</Field>
+			)}
+
+			{mode === "sentinel" && (
+				<div className="grid grid-cols-2 gap-4">
+					<Field>
+						<FieldLabel htmlFor="connection-sentinel-master">
+							Master name
+						</FieldLabel>
+						<Input
+							id="connection-sentinel-master"
+							required
+							value={sentinelMaster}
+							onChange={(event) =>
+								onChange({ sentinelMaster: event.target.value })
+							}
+							placeholder="mymaster"
+						/>
+					</Field>
+					<Field>
+						<FieldLabel htmlFor="connection-sentinel-password">
+							Sentinel password
+						</FieldLabel>
+						<Input
+							id="connection-sentinel-password"
+							type="password"
+							value={sentinelPassword}
+							onChange={(event) =>
+								onChange({ sentinelPassword: event.target.value })
+							}
+							placeholder="Optional"
+						/>
+					</Field>
+				</div>
+			)}
+		</>
+	);
+}
@@ -1,4 +1,5 @@
 import type { TableColumn } from "@/types/tabTypes";
+import { cellText, isTaggedCell } from "@/lib/cellValues";
 import { getSuggestedFunctions } from "@/lib/databaseCatalog";
 import { BooleanFieldInput } from "./BooleanFieldInput";
 import { JsonFieldInput } from "./JsonFieldInput";
@@ -57,7 +58,9 @@ export function FieldInput({
 
 	const commonProps = {
 		column,
-		value,
+		// Inputs edit the text of tagged cells; JSON inputs keep the document.
+		value:
+			isTaggedCell(value) && value.$type !== "json" ? cellText(value) : value,
 		isRawSql,
 		isNull,
 		suggestedFunctions,
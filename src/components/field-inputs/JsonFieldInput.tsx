@@ -1,6 +1,7 @@
 import { Textarea } from "@/components/ui/textarea";
 import { NullButton } from "./NullButton";
 import { ExpandableText } from "@/components/ExpandableText";
+import { isTaggedCell, jsonCell } from "@/lib/cellValues";
 import type { FieldInputProps } from "./types";
 
 export function JsonFieldInput({
@@ -10,12 +11,13 @@ export function JsonFieldInput({
 	onValueChange,
 	isReadonly = false,
 }: FieldInputProps) {
+	const json = isTaggedCell(value) ? value.value : value;
 	const stringValue =
-		typeof value === "object" && value !== null
-			? JSON.stringify(value, null, 2)
-			: value === null
+		typeof json === "object" && json !== null
+			? JSON.stringify(json, null, 2)
+			: json === null
 				? ""
-				: String(value);
+				: String(json);
 
 	if (isReadonly) {
 		return (
@@ -34,7 +36,7 @@ export function JsonFieldInput({
 				onChange={(e) => {
 					try {
 						const parsed = JSON.parse(e.target.value);
-						onValueChange(parsed, false);
+						onValueChange(jsonCell(parsed), false);
 					} catch {
 						onValueChange(e.target.value, false);
 					}
@@ -45,7 +47,7 @@ export function JsonFieldInput({
 			<NullButton
 				isNull={isNull}
 				nullable={column.nullable}
-				onToggle={() => onValueChange(isNull ? {} : null, false)}
+				onToggle={() => onValueChange(isNull ? jsonCell({}) : null, false)}
 			/>
 		</div>
 	);
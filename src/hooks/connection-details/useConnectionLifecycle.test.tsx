@@ -63,6 +63,7 @@ mock.module("../../lib/tauri", () => ({
 			disconnect: async (uuid: string) => {
 				disconnectCalls.push(uuid);
 			},
+			getCapabilities: async () => ({ row_editing: true }),
 			getSchemaOverview: async (uuid: string) => {
 				schemaCalls.push(uuid);
 				return {
@@ -119,6 +120,7 @@ test("connects, loads schema atomically, and disconnects on unmount", async () =
 
 	expect(result.current.connection).toMatchObject({
 		value: connection,
+		capabilities: { row_editing: true },
 		status: "connected",
 		hasEverConnected: true,
 	});
@@ -1,18 +1,27 @@
-import { useCallback, useEffect, useRef, useState } from "react";
+import { useCallback, useEffect, useMemo, useRef, useState } from "react";
 import { toast } from "sonner";
 import {
 	type ConnectionStatus,
 	type LoadingPhase,
 } from "../../lib/connection-details/connectionLifecycleState";
+import {
+	savedConnectionUuid,
+	sessionDatabase,
+} from "../../lib/databaseSessions";
 import {
 	prepareDuckDbRuntime,
 	type DuckDbHelperProgress,
 } from "../../lib/duckdbHelper";
-import { api, type Connection } from "../../lib/tauri";
+import {
+	api,
+	type Connection,
+	type DriverCapabilities,
+} from "../../lib/tauri";
 import type { DatabaseTable } from "../../types/table";
 import type { SchemaOverview, TableColumn } from "../../types/tabTypes";
 
 interface UseConnectionLifecycleOptions {
+	/** The connection UUID, or the pool key of a session on another database. */
 	uuid: string | undefined;
 	navigate: (path: string) => void;
 }
@@ -22,6 +31,9 @@ export function useConnectionLifecycle({
 	navigate,
 }: UseConnectionLifecycleOptions) {
 	const [connection, setConnection] = useState<Connection | null>(null);
+	const [capabilities, setCapabilities] = useState<DriverCapabilities | null>(
+		null,
+	);
 	const [loadingPhase, setLoadingPhase] =
 		useState<LoadingPhase>("fetching-config");
 	const [duckDbHelperProgress, setDuckDbHelperProgress] =
@@ -57,7 +69,9 @@ export function useConnectionLifecycle({
 			if (!uuid) return;
 			setLoadingPhase("fetching-config");
 			try {
-				const data = await api.connections.getByUuid(uuid);
+				const data = await api.connections.getByUuid(
+					savedConnectionUuid(uuid),
+				);
 				setConnection(data);
 				if (data.type === "duckdb") {
 					setLoadingPhase("preparing-duckdb");
@@ -87,7 +101,25 @@ export function useConnectionLifecycle({
 	useEffect(() => {
 		if (!uuid) return;
 		return () => {
-			api.pool.disconnect(uuid).catch(() => {});
+			// Disconnecting the connection closes its other sessions too
+			api.pool.disconnect(savedConnectionUuid(uuid)).catch(() => {});
+		};
+	}, [uuid]);
+
+	useEffect(() => {
+		if (!uuid) return;
+		let cancelled = false;
+		setCapabilities(null);
+		api.pool
+			.getCapabilities(uuid)
+			.then((next) => {
+				if (!cancelled) setCapabilities(next);
+			})
+			.catch((error) => {
+				console.error("Failed to fetch driver capabilities:", error);
+			});
+		return () => {
+			cancelled = true;
 		};
 	}, [uuid]);
 
@@ -207,6 +239,28 @@ export function useConnectionLifecycle({
 		}
 	}, [uuid, connection?.type, loadSchema, markConnected, markDisconnected]);
 
+	const openDatabase = useCallback(
+		async (database: string) => {
+			if (!connection) return;
+			try {
+				if (database === connection.database) {
+					navigate(`/connections/${connection.uuid}`);
+					return;
+				}
+				// Open the session before leaving, so a database that cannot be
+				// opened leaves the current one in place
+				await api.pool.openDatabase(connection.uuid, database);
+				navigate(
+					`/connections/${connection.uuid}?database=${encodeURIComponent(database)}`,
+				);
+			} catch (error) {
+				const message = error instanceof Error ? error.message : String(error);
+				toast.error(`Could not open ${database}`, { description: message });
+			}
+		},
+		[connection, navigate],
+	);
+
 	const recordConnectionStatus = useCallback(
 		(status: ConnectionStatus) => {
 			if (status === "connected") {
@@ -218,13 +272,22 @@ export function useConnectionLifecycle({
 		[markConnected],
 	);
 
+	// A session on another database shows that database and is pooled under
+	// its own key; everything else comes from the saved connection.
+	const sessionConnection = useMemo(() => {
+		const database = uuid ? sessionDatabase(uuid) : null;
+		if (!connection || !uuid || database === null) return connection;
+		return { ...connection, uuid, database };
+	}, [connection, uuid]);
+
 	return {
 		opening: {
 			phase: loadingPhase,
 			duckDbHelperProgress,
 		},
 		connection: {
-			value: connection,
+			value: sessionConnection,
+			capabilities,
 			status: connectionStatus,
 			error: connectionError,
 			hasEverConnected,
@@ -241,6 +304,7 @@ export function useConnectionLifecycle({
 			refreshSchema,
 			reconnect,
 			recordConnectionStatus,
+			openDatabase,
 		},
 	};
 }
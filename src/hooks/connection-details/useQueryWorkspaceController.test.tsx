@@ -9,14 +9,22 @@ import type { QueryTab, Tab } from "../../types/tabTypes";
 if (!globalThis.document) GlobalRegistrator.register();
 
 interface QueryResult {
-	data: Record<string, unknown>[];
+	columns: { name: string; type: string; filter_kind: string }[];
+	rows: unknown[][];
 	error: string | null;
+	error_details?: {
+		category: string;
+		message: string;
+		position?: { offset: number; line: number; column: number };
+	};
 	time_taken_ms: number;
 	rows_affected: number | null;
 	row_count: number | null;
 	truncated: boolean;
 }
 
+const valueColumn = { name: "value", type: "INT4", filter_kind: "integer" };
+
 function deferred<T>() {
 	let resolve: (value: T) => void = () => {};
 	const promise = new Promise<T>((complete) => {
@@ -79,7 +87,10 @@ function queryTab(id: string, query: string): QueryTab {
 		savedQueryId: null,
 		savedQueryName: null,
 		results: null,
+		resultColumns: [],
+		editTarget: null,
 		error: null,
+		errorOffset: null,
 		success: false,
 		executionTime: null,
 		affectedRows: null,
@@ -177,7 +188,8 @@ test("finishes a query on its originating tab after the user switches tabs", asy
 
 	await act(async () => {
 		executeQueryResult.resolve({
-			data: [{ value: 1 }],
+			columns: [valueColumn],
+			rows: [[1]],
 			error: null,
 			time_taken_ms: 4,
 			rows_affected: null,
@@ -189,7 +201,7 @@ test("finishes a query on its originating tab after the user switches tabs", asy
 
 	expect(result.current.tabs[0]).toMatchObject({
 		id: "query-1",
-		results: [{ value: 1 }],
+		results: [[1]],
 		executing: false,
 	});
 	expect(result.current.tabs[1]).toMatchObject({
@@ -217,7 +229,8 @@ test("keeps the newest query result when requests finish out of order", async ()
 
 	await act(async () => {
 		newerResult.resolve({
-			data: [{ value: "newer" }],
+			columns: [valueColumn],
+			rows: [["newer"]],
 			error: null,
 			time_taken_ms: 2,
 			rows_affected: null,
@@ -228,7 +241,8 @@ test("keeps the newest query result when requests finish out of order", async ()
 	});
 	await act(async () => {
 		olderResult.resolve({
-			data: [{ value: "older" }],
+			columns: [valueColumn],
+			rows: [["older"]],
 			error: null,
 			time_taken_ms: 8,
 			rows_affected: null,
@@ -240,7 +254,7 @@ test("keeps the newest query result when requests finish out of order", async ()
 
 	expect(result.current.tabs[0]).toMatchObject({
 		id: "query-1",
-		results: [{ value: "newer" }],
+		results: [["newer"]],
 		executionTime: 2,
 		executing: false,
 	});
@@ -265,7 +279,8 @@ test("stops run-all before another statement after a newer query starts", async
 
 	await act(async () => {
 		batchResult.resolve({
-			data: [{ value: "batch" }],
+			columns: [valueColumn],
+			rows: [["batch"]],
 			error: null,
 			time_taken_ms: 3,
 			rows_affected: null,
@@ -276,7 +291,8 @@ test("stops run-all before another statement after a newer query starts", async
 	});
 	await act(async () => {
 		newerResult.resolve({
-			data: [{ value: "newer" }],
+			columns: [valueColumn],
+			rows: [["newer"]],
 			error: null,
 			time_taken_ms: 1,
 			rows_affected: null,
@@ -288,11 +304,48 @@ test("stops run-all before another statement after a newer query starts", async
 
 	expect(executeQueryCalls).toBe(2);
 	expect(result.current.tabs[0]).toMatchObject({
-		results: [{ value: "newer" }],
+		results: [["newer"]],
 		executing: false,
 	});
 });
 
+test("points the editor at the error position until the query is edited", async () => {
+	const { result } = renderController("SELECT 1;\n\nSELECT * FORM users;");
+	act(() => result.current.controller.workspace.handleCursorActivity(2, 0));
+
+	let execution: Promise<void> | undefined;
+	act(() => {
+		execution = result.current.controller.commands.runQuery();
+	});
+	await act(async () => {
+		executeQueryResult.resolve({
+			columns: [],
+			rows: [],
+			error: 'syntax error at or near "FORM"',
+			error_details: {
+				category: "syntax",
+				message: 'syntax error at or near "FORM"',
+				position: { offset: 9, line: 1, column: 10 },
+			},
+			time_taken_ms: 1,
+			rows_affected: null,
+			row_count: null,
+			truncated: false,
+		});
+		await execution;
+	});
+
+	expect(result.current.tabs[0]).toMatchObject({
+		error: 'syntax error at or near "FORM"',
+		errorOffset: "SELECT 1;\n\nSELECT * ".length,
+	});
+
+	act(() =>
+		result.current.controller.workspace.changeQuery("SELECT * FROM users;"),
+	);
+	expect(result.current.tabs[0]).toMatchObject({ errorOffset: null });
+});
+
 test("preserves saved queries added while a create request is in flight", async () => {
 	const { result } = renderController();
 	const refreshedQuery: SavedQuery = {
@@ -1,14 +1,16 @@
 import { useCallback, useState } from "react";
 import { toast } from "sonner";
-import { api, type SavedQuery } from "../../lib/tauri";
+import { api, type QueryResult, type SavedQuery } from "../../lib/tauri";
 import {
 	buildWrappedQuery,
+	editTargetRow,
 	isWrappableQuery,
 	serializeRowsToCsv,
 	stripTrailingSemicolon,
 } from "../../lib/connection-details/queryTableState";
 import type { UpdateTab } from "../../lib/connection-details/tabState";
 import type { TabRequestController } from "../../lib/connection-details/tabRequestController";
+import { statementErrorOffset } from "../../lib/sqlErrorPosition";
 import {
 	getStatementAtCursor,
 	parseStatements as parseSqlStatements,
@@ -16,6 +18,7 @@ import {
 import type { QueryTab, SortConfig } from "../../types/tabTypes";
 import type { SqlConnection } from "../../types/connection";
 import type { HistoryRecordOptions } from "./useConnectionQueryRecords";
+import type { RowMutationValue } from "./useTableDataController";
 
 interface UseQueryWorkspaceControllerOptions {
 	connection: SqlConnection;
@@ -50,6 +53,9 @@ export function useQueryWorkspaceController({
 	const [cursorChar, setCursorChar] = useState(0);
 	const [queryToDelete, setQueryToDelete] = useState<SavedQuery | null>(null);
 	const [showQueryDeleteDialog, setShowQueryDeleteDialog] = useState(false);
+	const [editingRowIndex, setEditingRowIndex] = useState<number | null>(null);
+	const [savingRow, setSavingRow] = useState(false);
+	const [deletingRow, setDeletingRow] = useState(false);
 	const closeSaveDialog = useCallback(() => {
 		setShowSaveDialog(false);
 		setSaveQueryName("");
@@ -100,7 +106,9 @@ export function useQueryWorkspaceController({
 
 				request.commit(() =>
 					updateQueryTab(tab.id, {
-						results: result.data as Record<string, unknown>[],
+						results: result.rows,
+						resultColumns: result.columns,
+						editTarget: result.edit_target ?? null,
 						success: true,
 						error: null,
 						executionTime,
@@ -191,7 +199,7 @@ export function useQueryWorkspaceController({
 			cursorChar,
 		);
 		const queryToRun = statement?.text.trim() || "";
-		if (!queryToRun) {
+		if (!statement || !queryToRun) {
 			toast.error("No statement at cursor position");
 			return;
 		}
@@ -200,7 +208,10 @@ export function useQueryWorkspaceController({
 		updateQueryTab(activeTab.id, {
 			executing: true,
 			error: null,
+			errorOffset: null,
 			results: null,
+			resultColumns: [],
+			editTarget: null,
 			success: false,
 			executionTime: null,
 			affectedRows: null,
@@ -223,6 +234,11 @@ export function useQueryWorkspaceController({
 				request.commit(() =>
 					updateQueryTab(activeTab.id, {
 						error: result.error,
+						errorOffset: statementErrorOffset(
+							activeTab.query,
+							statement,
+							result.error_details?.position,
+						),
 						executionTime,
 						affectedRows: null,
 						executing: false,
@@ -238,7 +254,9 @@ export function useQueryWorkspaceController({
 
 			request.commit(() =>
 				updateQueryTab(activeTab.id, {
-					results: result.data as Record<string, unknown>[],
+					results: result.rows,
+					resultColumns: result.columns,
+					editTarget: result.edit_target ?? null,
 					success: true,
 					executionTime,
 					affectedRows: result.rows_affected ?? null,
@@ -289,7 +307,10 @@ export function useQueryWorkspaceController({
 		updateQueryTab(activeTab.id, {
 			executing: true,
 			error: null,
+			errorOffset: null,
 			results: null,
+			resultColumns: [],
+			editTarget: null,
 			success: false,
 			executionTime: null,
 			affectedRows: null,
@@ -300,8 +321,9 @@ export function useQueryWorkspaceController({
 		});
 
 		let totalTime = 0;
-		let lastResult: Record<string, unknown>[] = [];
+		let lastResult: QueryResult | null = null;
 		let lastError: string | null = null;
+		let lastErrorOffset: number | null = null;
 		let lastBaseQuery: string | null = null;
 		let lastAffectedRows: number | null = null;
 		let currentQuery: string | null = null;
@@ -322,6 +344,11 @@ export function useQueryWorkspaceController({
 
 				if (result.error) {
 					lastError = result.error;
+					lastErrorOffset = statementErrorOffset(
+						activeTab.query,
+						statement,
+						result.error_details?.position,
+					);
 					recordHistory(queryToRun, {
 						status: "error",
 						timeTakenMs: result.time_taken_ms ?? null,
@@ -331,7 +358,7 @@ export function useQueryWorkspaceController({
 					break;
 				}
 
-				lastResult = result.data as Record<string, unknown>[];
+				lastResult = result;
 				lastAffectedRows = result.rows_affected ?? null;
 				lastBaseQuery = isWrappableQuery(queryToRun)
 					? stripTrailingSemicolon(queryToRun)
@@ -352,12 +379,15 @@ export function useQueryWorkspaceController({
 					lastError
 						? {
 								error: lastError,
+								errorOffset: lastErrorOffset,
 								executionTime: totalTime,
 								affectedRows: null,
 								executing: false,
 							}
 						: {
-								results: lastResult,
+								results: lastResult?.rows ?? [],
+								resultColumns: lastResult?.columns ?? [],
+								editTarget: lastResult?.edit_target ?? null,
 								success: true,
 								executionTime: totalTime,
 								affectedRows: lastAffectedRows,
@@ -395,7 +425,8 @@ export function useQueryWorkspaceController({
 	const handleQueryChange = useCallback(
 		(query: string) => {
 			if (!activeTab) return;
-			updateQueryTab(activeTab.id, { query });
+			// An edit moves the text, so the last error no longer points anywhere.
+			updateQueryTab(activeTab.id, { query, errorOffset: null });
 		},
 		[activeTab, updateQueryTab],
 	);
@@ -504,7 +535,13 @@ export function useQueryWorkspaceController({
 		if (!filePath) return;
 
 		try {
-			await writeTextFile(filePath, serializeRowsToCsv(activeTab.results));
+			await writeTextFile(
+				filePath,
+				serializeRowsToCsv(
+					activeTab.resultColumns.map((column) => column.name),
+					activeTab.results,
+				),
+			);
 			toast.success("CSV saved successfully", {
 				action: {
 					label: "Open File Location",
@@ -518,6 +555,124 @@ export function useQueryWorkspaceController({
 		}
 	}, [activeTab]);
 
+	const closeRowEdit = useCallback(() => setEditingRowIndex(null), []);
+
+	// After an edit the result is read again when it can be; otherwise the
+	// edited row is patched in place.
+	const refreshEditedResult = useCallback(
+		(tab: QueryTab, patch: (rows: unknown[][]) => unknown[][]) => {
+			if (tab.resultBaseQuery) {
+				updateQueryTab(tab.id, { executing: true, error: null });
+				void runQueryResultViewQuery(tab, tab.filter, tab.sort);
+			} else {
+				updateQueryTab(tab.id, { results: patch(tab.results ?? []) });
+			}
+		},
+		[updateQueryTab, runQueryResultViewQuery],
+	);
+
+	const handleSaveResultRow = useCallback(
+		async (updates: RowMutationValue[]) => {
+			const tab = activeTab;
+			const target = tab?.editTarget;
+			const row =
+				editingRowIndex === null ? undefined : tab?.results?.[editingRowIndex];
+			if (!tab || !target || !row) return;
+
+			const request = requestController.watchQuery(tab.id);
+			const values = editTargetRow(tab.resultColumns, row);
+			setSavingRow(true);
+			try {
+				const result = await api.pool.updateTableRow(
+					connection.uuid,
+					target.schema,
+					target.table,
+					target.primary_key,
+					target.primary_key.map((column) => values[column]),
+					updates,
+				);
+				if (!request.isCurrent()) return;
+				if (result.error) {
+					toast.error("Failed to update row", { description: result.error });
+					return;
+				}
+				toast.success("Row updated successfully");
+				setEditingRowIndex(null);
+				refreshEditedResult(tab, (rows) =>
+					rows.map((current, index) =>
+						index === editingRowIndex
+							? current.map((value, position) => {
+									const column = tab.resultColumns[position]?.source?.column;
+									const update = updates.find(
+										(update) => update.column === column && !update.isRawSql,
+									);
+									return update ? update.value : value;
+								})
+							: current,
+					),
+				);
+			} catch (error) {
+				if (!request.isCurrent()) return;
+				toast.error("Failed to update row", {
+					description: error instanceof Error ? error.message : String(error),
+				});
+			} finally {
+				setSavingRow(false);
+			}
+		},
+		[
+			activeTab,
+			editingRowIndex,
+			connection.uuid,
+			requestController,
+			refreshEditedResult,
+		],
+	);
+
+	const handleDeleteResultRow = useCallback(async () => {
+		const tab = activeTab;
+		const target = tab?.editTarget;
+		const row =
+			editingRowIndex === null ? undefined : tab?.results?.[editingRowIndex];
+		if (!tab || !target || !row) return;
+
+		const request = requestController.watchQuery(tab.id);
+		const values = editTargetRow(tab.resultColumns, row);
+		setDeletingRow(true);
+		try {
+			const result = await api.pool.deleteTableRow(
+				connection.uuid,
+				target.schema,
+				target.table,
+				target.primary_key,
+				target.primary_key.map((column) => values[column]),
+			);
+			if (!request.isCurrent()) return;
+			if (result.error) {
+				toast.error("Failed to delete row", { description: result.error });
+				return;
+			}
+			toast.success("Row deleted successfully");
+			setEditingRowIndex(null);
+			refreshEditedResult(tab, (rows) =>
+				rows.filter((_, index) => index !== editingRowIndex),
+			);
+		} catch (error) {
+			if (!request.isCurrent()) return;
+			toast.error("Failed to delete row", {
+				description: error instanceof Error ? error.message : String(error),
+			});
+		} finally {
+			setDeletingRow(false);
+		}
+	}, [
+		activeTab,
+		editingRowIndex,
+		connection.uuid,
+		requestController,
+		refreshEditedResult,
+	]);
+
 	const handleSaveQueryFromPalette = useCallback(() => {
 		if (!activeTab || !activeTab.query.trim()) return;
 		if (activeTab.savedQueryName) setSaveQueryName(activeTab.savedQueryName);
@@ -541,6 +696,15 @@ export function useQueryWorkspaceController({
 			applyFilter: handleApplyQueryFilter,
 			clearFilter: handleClearQueryFilter,
 			changeSort: handleQuerySortChange,
+			rowEdit: {
+				rowIndex: editingRowIndex,
+				open: setEditingRowIndex,
+				close: closeRowEdit,
+				save: handleSaveResultRow,
+				delete: handleDeleteResultRow,
+				saving: savingRow,
+				deleting: deletingRow,
+			},
 		},
 		savedQueries: {
 			queryToDelete,
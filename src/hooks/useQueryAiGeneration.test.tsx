@@ -43,7 +43,10 @@ function createQuery(): QueryTab {
 		savedQueryId: null,
 		savedQueryName: null,
 		results: null,
+		resultColumns: [],
+		editTarget: null,
 		error: null,
+		errorOffset: null,
 		success: false,
 		executionTime: null,
 		affectedRows: null,
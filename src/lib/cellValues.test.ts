@@ -0,0 +1,49 @@
+import { expect, test } from "bun:test";
+import {
+	cellText,
+	editedTaggedCell,
+	isTaggedCell,
+	jsonCell,
+} from "./cellValues";
+
+test("shows tagged cells as the text they stand for", () => {
+	expect(cellText({ $type: "decimal", value: "12.50" })).toBe("12.50");
+	expect(cellText({ $type: "binary", value: "yv4=", length: 2 })).toBe(
+		"\\xcafe",
+	);
+	expect(
+		cellText({
+			$type: "array",
+			value: [1, { $type: "integer", value: "9007199254740993" }],
+		}),
+	).toBe('[1,"9007199254740993"]');
+	expect(cellText(null)).toBe("NULL");
+});
+
+test("keeps JSON documents with their own type field as JSON", () => {
+	const document = { type: "date", value: "2024-01-01" };
+	expect(isTaggedCell(document)).toBe(false);
+	expect(cellText(jsonCell(document))).toBe(JSON.stringify(document));
+	expect(editedTaggedCell(JSON.stringify(document), jsonCell({}))).toEqual(
+		jsonCell(document),
+	);
+});
+
+test("keeps the tag of an edited cell", () => {
+	expect(
+		editedTaggedCell(" 13.00 ", { $type: "decimal", value: "12.50" }),
+	).toEqual({ $type: "decimal", value: "13.00" });
+	expect(
+		editedTaggedCell("\\xCAFE", { $type: "binary", value: "", length: 0 }),
+	).toEqual({ $type: "binary", value: "yv4=", length: 2 });
+	expect(
+		editedTaggedCell("POINT(3 4)", {
+			$type: "geometry",
+			value: "POINT(1 2)",
+			srid: 4326,
+		}),
+	).toEqual({ $type: "geometry", value: "POINT(3 4)", srid: 4326 });
+	expect(() => editedTaggedCell("{", jsonCell({}))).toThrow(
+		"JSON values must be valid JSON",
+	);
+});
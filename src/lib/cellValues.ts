@@ -0,0 +1,117 @@
+/**
+ * Result cells that JSON can't carry exactly arrive tagged with their type,
+ * e.g. `{ $type: "decimal", value: "12.50" }`. JSON documents are tagged too,
+ * so any object with a `$type` key is a tag.
+ */
+export interface TaggedCell {
+	$type: string;
+	value: unknown;
+	/** Byte count of a `binary` cell. */
+	length?: number;
+	/** SRID of a `geometry` cell, when it has one. */
+	srid?: number;
+}
+
+export function isTaggedCell(value: unknown): value is TaggedCell {
+	return (
+		typeof value === "object" &&
+		value !== null &&
+		!Array.isArray(value) &&
+		typeof (value as { $type?: unknown }).$type === "string"
+	);
+}
+
+/** A JSON document to send back for a JSON column. */
+export function jsonCell(value: unknown): TaggedCell {
+	return { $type: "json", value };
+}
+
+function base64ToHex(value: string): string {
+	try {
+		return Array.from(atob(value), (character) =>
+			character.charCodeAt(0).toString(16).padStart(2, "0"),
+		).join("");
+	} catch {
+		return "";
+	}
+}
+
+function hexToBase64(text: string): { value: string; length: number } {
+	const hex = text.replace(/^\\x/i, "");
+	if (hex.length % 2 !== 0 || !/^[0-9a-f]*$/i.test(hex)) {
+		throw new Error("Binary values must be hex, e.g. \\xCAFE");
+	}
+	const bytes = hex.match(/../g) ?? [];
+	return {
+		value: btoa(
+			bytes
+				.map((byte) => String.fromCharCode(Number.parseInt(byte, 16)))
+				.join(""),
+		),
+		length: bytes.length,
+	};
+}
+
+/** A cell as plain JSON, tags replaced by their text. */
+function plainCell(value: unknown): unknown {
+	if (!isTaggedCell(value)) return value;
+	if (value.$type === "json") return value.value;
+	if (value.$type === "array" && Array.isArray(value.value)) {
+		return value.value.map(plainCell);
+	}
+	return cellText(value);
+}
+
+/** A cell as text, as it would be typed into a query. */
+export function cellText(value: unknown): string {
+	if (value === null || value === undefined) return "NULL";
+	if (isTaggedCell(value)) {
+		switch (value.$type) {
+			case "json":
+				return JSON.stringify(value.value);
+			case "array":
+				return JSON.stringify(plainCell(value));
+			case "binary":
+				return `\\x${base64ToHex(String(value.value))}`;
+			default:
+				return String(value.value);
+		}
+	}
+	if (typeof value === "object") return JSON.stringify(value);
+	return String(value);
+}
+
+function parseJson(text: string): unknown {
+	try {
+		return JSON.parse(text);
+	} catch {
+		throw new Error("JSON values must be valid JSON");
+	}
+}
+
+/** The cell to send back for `text` typed over a tagged cell, keeping its tag. */
+export function editedTaggedCell(
+	text: string,
+	original: TaggedCell,
+): TaggedCell {
+	const trimmed = text.trim();
+	switch (original.$type) {
+		case "json":
+			return jsonCell(parseJson(trimmed));
+		case "array": {
+			const value = parseJson(trimmed);
+			if (!Array.isArray(value)) {
+				throw new Error("Array values must be a JSON array");
+			}
+			return { $type: "array", value };
+		}
+		case "binary":
+			return { $type: "binary", ...hexToBase64(trimmed) };
+		case "geometry":
+			return original.srid === undefined
+				? { $type: "geometry", value: trimmed }
+				: { $type: "geometry", value: trimmed, srid: original.srid };
+		default:
+			return { $type: original.$type, value: trimmed };
+	}
+}
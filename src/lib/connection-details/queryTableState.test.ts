@@ -1,11 +1,15 @@
 import { describe, expect, test } from "bun:test";
+import type { ResultColumn } from "@/lib/tauri";
 import type { TableColumn } from "@/types/tabTypes";
 import {
 	areCellValuesEqual,
 	buildWrappedQuery,
+	editTargetColumns,
+	editTargetRow,
 	getPrimaryKeyRowKey,
 	isWrappableQuery,
 	quoteResultColumn,
+	resultRowObject,
 	serializeRowsToCsv,
 } from "./queryTableState";
 
@@ -69,21 +73,64 @@ describe("connection details query state", () => {
 describe("CSV serialization", () => {
 	test("serializes one header order and escapes CSV-sensitive values", () => {
 		expect(
-			serializeRowsToCsv([
-				{ id: 1, label: "alpha,beta", note: 'said "hello"', metadata: null },
-				{
-					id: 2,
-					label: "line\nbreak",
-					note: { active: true },
-					metadata: undefined,
-				},
-			]),
+			serializeRowsToCsv(
+				["id", "label", "note", "metadata"],
+				[
+					[1, "alpha,beta", 'said "hello"', null],
+					[2, "line\nbreak", { active: true }, undefined],
+				],
+			),
 		).toBe(
 			'id,label,note,metadata\n1,"alpha,beta","said ""hello""",\n2,"line\nbreak","{""active"":true}",',
 		);
 	});
 
 	test("returns an empty string for no rows", () => {
-		expect(serializeRowsToCsv([])).toBe("");
+		expect(serializeRowsToCsv(["id"], [])).toBe("");
+	});
+});
+
+describe("result rows", () => {
+	const source = (column: string) => ({
+		schema: "public",
+		table: "users",
+		column,
+	});
+	const columns: ResultColumn[] = [
+		{
+			name: "id",
+			type: "INT4",
+			filter_kind: "integer",
+			nullable: false,
+			source: source("id"),
+		},
+		{ name: "name", type: "TEXT", filter_kind: "text", source: source("name") },
+		{ name: "id", type: "INT4", filter_kind: "integer" },
+	];
+
+	test("keeps every value of columns that share a name", () => {
+		expect(resultRowObject(columns, [1, "Ada", 7])).toEqual({
+			id: 1,
+			name: "Ada",
+			"id (3)": 7,
+		});
+	});
+
+	test("maps an editable result onto its source table columns", () => {
+		const target = { schema: "public", table: "users", primary_key: ["id"] };
+		expect(editTargetRow(columns, [1, "Ada", 7])).toEqual({
+			id: 1,
+			name: "Ada",
+		});
+		expect(
+			editTargetColumns(columns, target).map((column) => [
+				column.name,
+				column.nullable,
+				column.primary_key,
+			]),
+		).toEqual([
+			["id", false, true],
+			["name", true, false],
+		]);
 	});
 });
@@ -1,3 +1,5 @@
+import { cellText } from "@/lib/cellValues";
+import type { ResultColumn, ResultEditTarget } from "@/lib/tauri";
 import type { SortConfig, TableColumn } from "@/types/tabTypes";
 
 export function stripTrailingSemicolon(query: string): string {
@@ -84,19 +86,20 @@ export function areCellValuesEqual(left: unknown, right: unknown): boolean {
 	return JSON.stringify(left) === JSON.stringify(right);
 }
 
-export function serializeRowsToCsv(rows: Record<string, unknown>[]): string {
+export function serializeRowsToCsv(
+	headers: string[],
+	rows: unknown[][],
+): string {
 	if (rows.length === 0) return "";
 
-	const headers = Object.keys(rows[0]);
 	return [
 		headers.join(","),
 		...rows.map((row) =>
 			headers
-				.map((header) => {
-					const value = row[header];
+				.map((_, index) => {
+					const value = row[index];
 					if (value === null || value === undefined) return "";
-					const stringValue =
-						typeof value === "object" ? JSON.stringify(value) : String(value);
+					const stringValue = cellText(value);
 					if (
 						stringValue.includes(",") ||
 						stringValue.includes('"') ||
@@ -110,3 +113,53 @@ export function serializeRowsToCsv(rows: Record<string, unknown>[]): string {
 		),
 	].join("\n");
 }
+
+/**
+ * A result row keyed by column name for display. A repeated name gets its
+ * position appended (`id (2)`) so no value is hidden.
+ */
+export function resultRowObject(
+	columns: ResultColumn[],
+	row: unknown[],
+): Record<string, unknown> {
+	const object: Record<string, unknown> = {};
+	columns.forEach((column, index) => {
+		const key =
+			column.name in object ? `${column.name} (${index + 1})` : column.name;
+		object[key] = row[index];
+	});
+	return object;
+}
+
+/** The columns of `target` a result reads, shaped for the row editor. */
+export function editTargetColumns(
+	columns: ResultColumn[],
+	target: ResultEditTarget,
+): TableColumn[] {
+	return columns.flatMap((column) =>
+		column.source
+			? [
+					{
+						name: column.source.column,
+						type: column.type,
+						filter_kind: column.filter_kind,
+						nullable: column.nullable ?? true,
+						default: null,
+						primary_key: target.primary_key.includes(column.source.column),
+					},
+				]
+			: [],
+	);
+}
+
+/** A result row keyed by the table columns its values are read from. */
+export function editTargetRow(
+	columns: ResultColumn[],
+	row: unknown[],
+): Record<string, unknown> {
+	const object: Record<string, unknown> = {};
+	columns.forEach((column, index) => {
+		if (column.source) object[column.source.column] = row[index];
+	});
+	return object;
+}
@@ -28,6 +28,10 @@ export class TabRequestController {
 		return this.request(this.registry.issue(this.queryChannel(tabId)));
 	}
 
+	watchQuery(tabId: string): CurrentRequest {
+		return this.request(this.registry.checkpoint(this.queryChannel(tabId)));
+	}
+
 	watchLifecycle(): CurrentRequest {
 		return this.request(this.registry.checkpoint("lifecycle"));
 	}
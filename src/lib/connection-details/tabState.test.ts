@@ -12,7 +12,10 @@ function queryTab(): QueryTab {
 		savedQueryId: null,
 		savedQueryName: null,
 		results: null,
+		resultColumns: [],
+		editTarget: null,
 		error: null,
+		errorOffset: null,
 		success: false,
 		executionTime: null,
 		affectedRows: null,
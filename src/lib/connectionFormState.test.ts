@@ -1,6 +1,11 @@
 import { expect, test } from "bun:test";
 import type { ConnectionFormData } from "@/types/connection";
-import { mergeD1ConnectionFields } from "./connectionFormState";
+import {
+	connectionOptions,
+	mergeD1ConnectionFields,
+	mergeRedisTopologyFields,
+	redisTopologyFields,
+} from "./connectionFormState";
 
 test("merges D1 field changes into the latest form state", () => {
 	const initial: ConnectionFormData = {
@@ -28,3 +33,60 @@ test("merges D1 field changes into the latest form state", () => {
 	expect(withToken.password).toBe("new-token");
 	expect(withToken.database).toBe("");
 });
+
+test("saves D1 ids as engine options and leaves other engines' alone", () => {
+	const d1: ConnectionFormData = {
+		type: "d1",
+		db_type: "d1",
+		name: "Production",
+		host: "api.cloudflare.com",
+		port: 443,
+		database: "database-id",
+		username: "account-id",
+		password: "token",
+		ssl: true,
+	};
+
+	expect(connectionOptions(d1)).toEqual({
+		account_id: "account-id",
+		database_id: "database-id",
+	});
+	expect(
+		connectionOptions({ ...d1, type: "postgres", db_type: "postgres" }),
+	).toBeUndefined();
+});
+
+test("saves only the Redis settings the chosen mode reads", () => {
+	const redis: ConnectionFormData = {
+		type: "redis",
+		db_type: "redis",
+		name: "Sessions",
+		host: "redis-1",
+		port: 6379,
+		database: "",
+		username: "",
+		password: "",
+		ssl: false,
+	};
+	expect(redisTopologyFields(redis).mode).toBe("standalone");
+	expect(connectionOptions(redis)).toEqual({});
+
+	const sentinel = mergeRedisTopologyFields(redis, {
+		mode: "sentinel",
+		nodes: " sentinel-2:26379 ",
+		sentinelMaster: "mymaster",
+	});
+	expect(connectionOptions(sentinel)).toEqual({
+		mode: "sentinel",
+		nodes: "sentinel-2:26379",
+		sentinel_master: "mymaster",
+		sentinel_password: "",
+	});
+
+	const cluster = mergeRedisTopologyFields(sentinel, { mode: "cluster" });
+	expect(redisTopologyFields(cluster).sentinelMaster).toBe("mymaster");
+	expect(connectionOptions(cluster)).toEqual({
+		mode: "cluster",
+		nodes: "sentinel-2:26379",
+	});
+});
@@ -1,4 +1,4 @@
-import type { ConnectionFormData } from "@/types/connection";
+import type { Connection, ConnectionFormData } from "@/types/connection";
 
 interface D1ConnectionFieldChanges {
 	accountId?: string;
@@ -17,3 +17,90 @@ export function mergeD1ConnectionFields(
 		database: changes.databaseId ?? current.database,
 	};
 }
+
+export type RedisMode = "standalone" | "cluster" | "sentinel";
+
+export interface RedisTopologyFieldValues {
+	mode: RedisMode;
+	/** Extra seed nodes as `host:port`, separated by commas or spaces. */
+	nodes: string;
+	sentinelMaster: string;
+	sentinelPassword: string;
+}
+
+function optionText(data: ConnectionFormData, name: string): string {
+	const value = data.options?.[name];
+	return typeof value === "string" ? value : "";
+}
+
+/** The Redis topology settings kept in the form's engine options. */
+export function redisTopologyFields(
+	data: ConnectionFormData,
+): RedisTopologyFieldValues {
+	const mode = optionText(data, "mode");
+	return {
+		mode: mode === "cluster" || mode === "sentinel" ? mode : "standalone",
+		nodes: optionText(data, "nodes"),
+		sentinelMaster: optionText(data, "sentinel_master"),
+		sentinelPassword: optionText(data, "sentinel_password"),
+	};
+}
+
+export function mergeRedisTopologyFields(
+	current: ConnectionFormData,
+	changes: Partial<RedisTopologyFieldValues>,
+): ConnectionFormData {
+	const fields = { ...redisTopologyFields(current), ...changes };
+	return {
+		...current,
+		// A cluster only has database 0.
+		database: fields.mode === "cluster" ? "" : current.database,
+		options: {
+			mode: fields.mode,
+			nodes: fields.nodes,
+			sentinel_master: fields.sentinelMaster,
+			sentinel_password: fields.sentinelPassword,
+		},
+	};
+}
+
+/**
+ * The engine options to save with the form. D1 keeps its account and
+ * database ids in options; the form edits them as username and database.
+ * Redis sends only the settings its mode reads.
+ */
+export function connectionOptions(
+	data: ConnectionFormData,
+): Record<string, unknown> | undefined {
+	if (data.type === "d1") {
+		return { account_id: data.username, database_id: data.database };
+	}
+	if (data.type !== "redis") return undefined;
+
+	const fields = redisTopologyFields(data);
+	switch (fields.mode) {
+		case "standalone":
+			return {};
+		case "cluster":
+			return { mode: "cluster", nodes: fields.nodes.trim() };
+		case "sentinel":
+			return {
+				mode: "sentinel",
+				nodes: fields.nodes.trim(),
+				sentinel_master: fields.sentinelMaster.trim(),
+				sentinel_password: fields.sentinelPassword,
+			};
+	}
+}
+
+/** A saved connection's D1 ids, read back into the form's fields. */
+export function d1FormFields(connection: Connection) {
+	const option = (name: string, fallback: string) => {
+		const value = connection.options?.[name];
+		return typeof value === "string" ? value : fallback;
+	};
+	return {
+		username: option("account_id", connection.username),
+		database: option("database_id", connection.database),
+	};
+}
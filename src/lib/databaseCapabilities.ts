@@ -8,12 +8,6 @@ export function isFileDatabase(dbType: ConnectionType): boolean {
 	return getDatabasePolicy(dbType).fileDatabase;
 }
 
-export function supportsStructuredRowMutations(
-	dbType: ConnectionType,
-): boolean {
-	return getDatabasePolicy(dbType).structuredRowMutations;
-}
-
 export function getSqlFormatterLanguage(
 	dbType: ConnectionType,
 ): SqlFormatterLanguage {
@@ -18,7 +18,6 @@ interface DatabasePolicy {
 	label: string;
 	defaultSchema: string;
 	fileDatabase: boolean;
-	structuredRowMutations: boolean;
 	formatterLanguage: SqlFormatterLanguage;
 	createTableTypes: string[];
 	literalKinds: Record<string, LiteralKind>;
@@ -0,0 +1,18 @@
+import { expect, test } from "bun:test";
+import {
+	databaseSessionKey,
+	savedConnectionUuid,
+	sessionDatabase,
+} from "./databaseSessions";
+
+test("splits a session key into its connection and database", () => {
+	const key = databaseSessionKey("connection-1", "tenant/a");
+	expect(key).toBe("connection-1/tenant/a");
+	expect(savedConnectionUuid(key)).toBe("connection-1");
+	expect(sessionDatabase(key)).toBe("tenant/a");
+});
+
+test("treats a plain UUID as the connection's own database", () => {
+	expect(savedConnectionUuid("connection-1")).toBe("connection-1");
+	expect(sessionDatabase("connection-1")).toBeNull();
+});
@@ -0,0 +1,23 @@
+/**
+ * A session on another database of a connection's server is pooled under
+ * `<uuid>/<database>`. The pool commands accept that key in place of the
+ * connection UUID; anything saved with the connection uses the UUID itself.
+ */
+const DATABASE_SESSION_SEPARATOR = "/";
+
+/** The pool key of a session on `database` of connection `uuid`'s server. */
+export function databaseSessionKey(uuid: string, database: string): string {
+	return `${uuid}${DATABASE_SESSION_SEPARATOR}${database}`;
+}
+
+/** The saved connection a pool key belongs to. */
+export function savedConnectionUuid(key: string): string {
+	const separator = key.indexOf(DATABASE_SESSION_SEPARATOR);
+	return separator === -1 ? key : key.slice(0, separator);
+}
+
+/** The database a session key opened, or null for the connection's own. */
+export function sessionDatabase(key: string): string | null {
+	const separator = key.indexOf(DATABASE_SESSION_SEPARATOR);
+	return separator === -1 ? null : key.slice(separator + 1);
+}
@@ -0,0 +1,53 @@
+import { expect, test } from "bun:test";
+import {
+	appendMonitorEvents,
+	clampMonitorSeconds,
+	formatMicroseconds,
+	formatRedisCommand,
+} from "./redisDiagnostics";
+import type { RedisLiveMessage, RedisMonitorEvent } from "./tauri";
+
+function monitorMessage(command: string[]): RedisLiveMessage {
+	return {
+		source: "127.0.0.1:50000",
+		pattern: null,
+		id: null,
+		payload: { timestamp: 1, db: 0, client: "127.0.0.1:50000", command },
+		received_at: "2026-01-01T00:00:00Z",
+	};
+}
+
+test("quotes only the arguments redis-cli would quote", () => {
+	expect(formatRedisCommand(["SET", "user:1", "Ada Lovelace"])).toBe(
+		'SET user:1 "Ada Lovelace"',
+	);
+	expect(formatRedisCommand(["GET", ""])).toBe('GET ""');
+});
+
+test("formats slow log durations in readable units", () => {
+	expect(formatMicroseconds(850)).toBe("850µs");
+	expect(formatMicroseconds(12_345)).toBe("12.3ms");
+	expect(formatMicroseconds(2_500_000)).toBe("2.50s");
+});
+
+test("keeps monitor captures within what the backend accepts", () => {
+	expect(clampMonitorSeconds(0)).toBe(1);
+	expect(clampMonitorSeconds(45.4)).toBe(45);
+	expect(clampMonitorSeconds(3_600)).toBe(300);
+	expect(clampMonitorSeconds(Number.NaN)).toBe(30);
+});
+
+test("keeps only the newest monitored commands", () => {
+	const events: RedisMonitorEvent[] = appendMonitorEvents(
+		[],
+		[monitorMessage(["GET", "a"]), monitorMessage(["GET", "b"])],
+	);
+	expect(
+		appendMonitorEvents(events, [monitorMessage(["GET", "c"])], 2).map(
+			(event) => event.command,
+		),
+	).toEqual([
+		["GET", "b"],
+		["GET", "c"],
+	]);
+});
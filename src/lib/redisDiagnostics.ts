@@ -0,0 +1,45 @@
+import { quoteRedisArgument } from "@/lib/redisModules";
+import type { RedisLiveMessage, RedisMonitorEvent } from "@/lib/tauri";
+
+/** How long a `MONITOR` capture runs, by default and at most. */
+export const DEFAULT_MONITOR_SECS = 30;
+export const MAX_MONITOR_SECS = 300;
+
+/** The most monitored commands kept on screen; older ones scroll away. */
+export const MONITOR_EVENT_LIMIT = 1_000;
+
+/** A command's arguments as redis-cli would print them. */
+export function formatRedisCommand(args: string[]): string {
+	return args
+		.map((arg) => (/^[^\s"'\\]+$/.test(arg) ? arg : quoteRedisArgument(arg)))
+		.join(" ");
+}
+
+/** A slow log duration in the largest unit that keeps it readable. */
+export function formatMicroseconds(us: number): string {
+	if (us < 1_000) return `${us}µs`;
+	if (us < 1_000_000) return `${(us / 1_000).toFixed(1)}ms`;
+	return `${(us / 1_000_000).toFixed(2)}s`;
+}
+
+/** A capture length the backend accepts, from whatever was typed. */
+export function clampMonitorSeconds(value: number): number {
+	if (!Number.isFinite(value)) return DEFAULT_MONITOR_SECS;
+	return Math.min(MAX_MONITOR_SECS, Math.max(1, Math.round(value)));
+}
+
+/**
+ * `events` followed by the commands of a batch of `MONITOR` messages, keeping
+ * only the newest `limit`.
+ */
+export function appendMonitorEvents(
+	events: RedisMonitorEvent[],
+	messages: RedisLiveMessage[],
+	limit = MONITOR_EVENT_LIMIT,
+): RedisMonitorEvent[] {
+	const next = [
+		...events,
+		...messages.map((message) => message.payload as RedisMonitorEvent),
+	];
+	return next.length > limit ? next.slice(next.length - limit) : next;
+}
@@ -0,0 +1,30 @@
+import { expect, test } from "bun:test";
+import {
+	jsonPathValue,
+	searchCommand,
+	searchIndexNames,
+} from "./redisModules";
+
+test("lists only the tables in the search schema as indexes", () => {
+	expect(
+		searchIndexNames([
+			{ schema: "redis", name: "keys", type: "keyspace" },
+			{ schema: "search", name: "idx:users", type: "search_index" },
+		]),
+	).toEqual(["idx:users"]);
+});
+
+test("quotes the index and query of a search", () => {
+	expect(searchCommand("idx:users", '@name:"ada"', 25)).toBe(
+		'FT.SEARCH "idx:users" "@name:\\"ada\\"" LIMIT 0 25',
+	);
+	expect(searchCommand("idx", "  ", 10)).toBe(
+		'FT.SEARCH "idx" "*" LIMIT 0 10',
+	);
+});
+
+test("unwraps a single JSONPath match", () => {
+	expect(jsonPathValue("$.name", ["Ada"])).toBe("Ada");
+	expect(jsonPathValue("$..id", [1, 2])).toEqual([1, 2]);
+	expect(jsonPathValue(".tags", ["a", "b"])).toEqual(["a", "b"]);
+});
@@ -0,0 +1,55 @@
+import type { TableInfo } from "@/lib/tauri";
+
+/** The type `TYPE` reports for RedisJSON documents. */
+export const JSON_KEY_TYPE = "ReJSON-RL";
+
+/** The sidebar schema the backend lists RediSearch indexes under. */
+export const SEARCH_SCHEMA = "search";
+
+/** The root path of a RedisJSON document. */
+export const JSON_ROOT = "$";
+
+/** The names of the RediSearch indexes among a connection's tables. */
+export function searchIndexNames(tables: TableInfo[]): string[] {
+	return tables
+		.filter((table) => table.schema === SEARCH_SCHEMA)
+		.map((table) => table.name);
+}
+
+/** One argument quoted the way the console's redis-cli parser reads it. */
+export function quoteRedisArgument(argument: string): string {
+	const escaped = argument
+		.replace(/\\/g, "\\\\")
+		.replace(/"/g, '\\"')
+		.replace(/\n/g, "\\n")
+		.replace(/\r/g, "\\r")
+		.replace(/\t/g, "\\t");
+	return `"${escaped}"`;
+}
+
+/** The `FT.SEARCH` command for `query` on `index`, first `limit` documents. */
+export function searchCommand(
+	index: string,
+	query: string,
+	limit: number,
+): string {
+	return [
+		"FT.SEARCH",
+		quoteRedisArgument(index),
+		quoteRedisArgument(query.trim() || "*"),
+		"LIMIT",
+		"0",
+		String(limit),
+	].join(" ");
+}
+
+/**
+ * The value at `path` from a `JSON.GET` reply. JSONPath (`$`) paths answer
+ * with every match; a single match is unwrapped so it can be edited.
+ */
+export function jsonPathValue(path: string, reply: unknown): unknown {
+	if (path.trim().startsWith("$") && Array.isArray(reply)) {
+		return reply.length === 1 ? reply[0] : reply;
+	}
+	return reply;
+}
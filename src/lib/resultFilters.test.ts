@@ -26,6 +26,27 @@ describe("result filters", () => {
 		});
 	});
 
+	test("filters on the text of tagged cells", () => {
+		expect(
+			createCellFilter("total", { $type: "decimal", value: "12.50" }, false),
+		).toEqual({
+			column: "total",
+			operator: "equals",
+			value: { kind: "decimal", value: "12.50" },
+		});
+		expect(
+			createCellFilter(
+				"created_on",
+				{ $type: "date", value: "2024-01-01" },
+				false,
+			),
+		).toEqual({
+			column: "created_on",
+			operator: "equals",
+			value: "2024-01-01",
+		});
+	});
+
 	test("uses null-aware operators for null cells", () => {
 		expect(createCellFilter("deleted_at", null, false)).toEqual({
 			column: "deleted_at",
@@ -1,3 +1,5 @@
+import { cellText, isTaggedCell } from "./cellValues";
+
 export interface ExactNumberFilterValue {
 	kind: "integer" | "decimal";
 	value: string;
@@ -191,7 +193,13 @@ export function isConditionComplete(condition: FilterCondition): boolean {
 function normalizeCellValue(value: unknown): FilterScalar {
 	if (value === null || typeof value === "string") return value;
 	if (typeof value === "number" || typeof value === "boolean") return value;
-	return JSON.stringify(value);
+	if (
+		isTaggedCell(value) &&
+		(value.$type === "integer" || value.$type === "decimal")
+	) {
+		return { kind: value.$type, value: String(value.value) };
+	}
+	return cellText(value);
 }
 
 export function createCellFilter(
@@ -0,0 +1,36 @@
+import { expect, test } from "bun:test";
+import { errorTokenRange, statementErrorOffset } from "./sqlErrorPosition";
+import { parseStatements } from "./sqlParser";
+
+test("places an engine error position in the editor text", () => {
+	const query = "SELECT 1;\n\n  SELEC name FROM users;";
+	const [, statement] = parseStatements(query);
+	const offset = statementErrorOffset(query, statement, {
+		offset: 0,
+		line: 1,
+		column: 1,
+	});
+	expect(offset).toBe(query.indexOf("SELEC "));
+	expect(statementErrorOffset(query, statement, undefined)).toBeNull();
+});
+
+test("counts characters, not UTF-16 units, in the statement", () => {
+	const query = "SELECT '😀' FORM t";
+	const [statement] = parseStatements(query);
+	const position = { offset: 11, line: 1, column: 12 };
+	expect(statementErrorOffset(query, statement, position)).toBe(
+		query.indexOf("FORM"),
+	);
+});
+
+test("underlines the token at an error offset", () => {
+	const text = 'SELECT * FROM "missing table" WHERE';
+	expect(errorTokenRange(text, 14)).toEqual({ from: 14, to: 29 });
+	expect(errorTokenRange(text, 0)).toEqual({ from: 0, to: 6 });
+	expect(errorTokenRange(text, 7)).toEqual({ from: 7, to: 8 });
+	expect(errorTokenRange(text, text.length)).toEqual({
+		from: text.length - 1,
+		to: text.length,
+	});
+	expect(errorTokenRange("", 0)).toBeNull();
+});
@@ -0,0 +1,35 @@
+import type { SqlStatement } from "@/lib/sqlParser";
+import type { ErrorPosition } from "@/lib/tauri";
+
+/**
+ * Where in the editor text an error reported for `statement` points.
+ * Engines count characters (code points) from the start of the statement
+ * that was sent; null when the statement can't be found at its offset.
+ */
+export function statementErrorOffset(
+	query: string,
+	statement: SqlStatement,
+	position: ErrorPosition | undefined,
+): number | null {
+	if (!position) return null;
+	const text = statement.text.trim();
+	if (!query.startsWith(text, statement.startOffset)) return null;
+	const prefix = Array.from(text).slice(0, position.offset).join("");
+	return statement.startOffset + prefix.length;
+}
+
+/**
+ * The span to underline for an error at `offset`: the word or quoted name
+ * starting there, a single character otherwise, or the last character when
+ * the error is at the end of the text.
+ */
+export function errorTokenRange(
+	text: string,
+	offset: number,
+): { from: number; to: number } | null {
+	if (text.length === 0) return null;
+	const from = Math.min(Math.max(offset, 0), text.length - 1);
+	if (offset >= text.length) return { from, to: text.length };
+	const token = /^(?:"[^"]*"?|`[^`]*`?|[\w$.]+)/.exec(text.slice(from));
+	return { from, to: from + (token ? token[0].length : 1) };
+}
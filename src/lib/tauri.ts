@@ -1,5 +1,7 @@
 import { invoke } from "@tauri-apps/api/core";
+import { listen, type UnlistenFn } from "@tauri-apps/api/event";
 import { isSqlFunction } from "@/lib/databaseCatalog";
+import { savedConnectionUuid } from "@/lib/databaseSessions";
 import type {
 	FilterColumnKind,
 	FilterExpression,
@@ -122,15 +124,79 @@ export interface TableDataResponse {
 	limit: number;
 }
 
+/** Where in a statement an error points; `offset` counts characters. */
+export interface ErrorPosition {
+	offset: number;
+	line: number;
+	column: number;
+}
+
+export interface DriverError {
+	category:
+		| "connection"
+		| "auth"
+		| "syntax"
+		| "permission"
+		| "constraint"
+		| "timeout"
+		| "cancelled"
+		| "other";
+	code?: string;
+	message: string;
+	position?: ErrorPosition;
+}
+
+/** The table column a result column is read from. */
+export interface ColumnSource {
+	schema: string;
+	table: string;
+	column: string;
+}
+
+export interface ResultColumn {
+	name: string;
+	/** The type as the engine reports it; empty when it reports none. */
+	type: string;
+	filter_kind: FilterColumnKind;
+	nullable?: boolean;
+	source?: ColumnSource;
+}
+
+/** The table a result's rows can be edited through, by its primary key. */
+export interface ResultEditTarget {
+	schema: string;
+	table: string;
+	primary_key: string[];
+}
+
 export interface QueryResult {
-	data: Record<string, unknown>[];
+	columns: ResultColumn[];
+	/** Rows as arrays aligned to `columns`. */
+	rows: unknown[][];
+	edit_target?: ResultEditTarget;
 	row_count: number;
 	truncated: boolean;
 	rows_affected?: number;
 	error?: string;
+	error_details?: DriverError;
 	time_taken_ms?: number;
 }
 
+/** What a connection's driver supports; see `DriverCapabilities` in Rust. */
+export interface DriverCapabilities {
+	transactions: boolean;
+	create_table: boolean;
+	alter_table: boolean;
+	functions: boolean;
+	read_only: "engine" | "allowlist" | "denylist" | "unsupported";
+	cancellation: boolean;
+	streaming: boolean;
+	multiple_databases: boolean;
+	key_value_browsing: boolean;
+	explain: boolean;
+	row_editing: boolean;
+}
+
 export interface TestConnectionResult {
 	success: boolean;
 	message: string;
@@ -237,6 +303,22 @@ export interface McpStatus {
 	token: string;
 }
 
+/** A write an MCP client is waiting for the user to approve. */
+export interface McpApprovalRequest {
+	id: string;
+	connection_uuid: string;
+	connection_name: string;
+	tool: string;
+	sql: string;
+	values: unknown[];
+	requested_at: string;
+}
+
+export interface McpApprovalResolution {
+	id: string;
+	approved: boolean;
+}
+
 // Redis types
 export interface RedisKeyInfo {
 	key: string;
@@ -263,6 +345,105 @@ export interface RedisKeyDetails {
 	length?: number;
 }
 
+export interface RedisModuleInfo {
+	name: string;
+	version: number | null;
+}
+
+export interface RedisModules {
+	json: boolean;
+	search: boolean;
+	modules: RedisModuleInfo[];
+}
+
+export interface RedisSlowlogEntry {
+	node: string | null;
+	id: number;
+	timestamp: number;
+	duration_us: number;
+	command: string[];
+	client_addr: string | null;
+	client_name: string | null;
+}
+
+export interface RedisLatencyEvent {
+	node: string | null;
+	event: string;
+	timestamp: number;
+	latest_ms: number;
+	max_ms: number;
+}
+
+export interface RedisLatencySample {
+	node: string | null;
+	timestamp: number;
+	latency_ms: number;
+}
+
+export interface RedisClientInfo {
+	node: string | null;
+	id: number;
+	addr: string;
+	name: string | null;
+	age_secs: number;
+	idle_secs: number;
+	db: number;
+	flags: string;
+	cmd: string;
+	fields: Record<string, unknown>;
+}
+
+/** What a live subscription listens to; see `LiveSource` in Rust. */
+export type RedisLiveSource =
+	| { kind: "channels"; channels?: string[]; patterns?: string[] }
+	| {
+			kind: "stream";
+			key: string;
+			start_id?: string | null;
+			group?: { group: string; consumer: string; ack?: boolean } | null;
+	  }
+	| { kind: "monitor"; duration_secs?: number };
+
+export interface RedisSubscriptionInfo {
+	id: string;
+	connection_uuid: string;
+	source: RedisLiveSource;
+	buffer_size: number;
+	started_at: string;
+}
+
+export interface RedisLiveMessage {
+	source: string;
+	pattern: string | null;
+	id: string | null;
+	payload: unknown;
+	received_at: string;
+}
+
+export interface RedisLiveBatch {
+	subscription_id: string;
+	connection_uuid: string;
+	messages: RedisLiveMessage[];
+	dropped: number;
+}
+
+export interface RedisLiveStopped {
+	subscription_id: string;
+	connection_uuid: string;
+	error: string | null;
+}
+
+/** The payload of a `MONITOR` live message. */
+export interface RedisMonitorEvent {
+	timestamp: number;
+	db: number;
+	client: string;
+	command: string[];
+}
+
+export const REDIS_LIVE_MESSAGES_EVENT = "redis-live-messages";
+export const REDIS_LIVE_STOPPED_EVENT = "redis-live-stopped";
+
 // Export/Import types
 export interface ExportedConnection {
 	type: string;
@@ -369,6 +550,7 @@ export const api = {
 			ssh_password?: string;
 			ssh_key_path?: string;
 			ssh_use_key?: boolean;
+			connect_timeout_ms?: number | null;
 		}) => invoke<TestConnectionResult>("test_connection", params),
 
 		listTables: (connection: Connection) =>
@@ -749,14 +931,96 @@ export const api = {
 				key,
 				ttl,
 			}),
+
+		getModules: (connectionUuid: string) =>
+			invoke<RedisModules>("redis_get_modules", { uuid: connectionUuid }),
+
+		jsonGet: (connectionUuid: string, key: string, path?: string) =>
+			invoke<unknown>("redis_json_get", {
+				uuid: connectionUuid,
+				key,
+				path,
+			}),
+
+		jsonSet: (
+			connectionUuid: string,
+			key: string,
+			value: unknown,
+			path?: string,
+		) =>
+			invoke<void>("redis_json_set", {
+				uuid: connectionUuid,
+				key,
+				path,
+				value,
+			}),
+
+		jsonDelete: (connectionUuid: string, key: string, path: string) =>
+			invoke<number>("redis_json_delete", {
+				uuid: connectionUuid,
+				key,
+				path,
+			}),
+
+		slowlog: (connectionUuid: string, count?: number) =>
+			invoke<RedisSlowlogEntry[]>("redis_slowlog", {
+				uuid: connectionUuid,
+				count,
+			}),
+
+		latencyLatest: (connectionUuid: string) =>
+			invoke<RedisLatencyEvent[]>("redis_latency_latest", {
+				uuid: connectionUuid,
+			}),
+
+		latencyHistory: (connectionUuid: string, event: string) =>
+			invoke<RedisLatencySample[]>("redis_latency_history", {
+				uuid: connectionUuid,
+				event,
+			}),
+
+		clientList: (connectionUuid: string) =>
+			invoke<RedisClientInfo[]>("redis_client_list", { uuid: connectionUuid }),
+
+		killClient: (
+			connectionUuid: string,
+			node: string | null,
+			clientId: number,
+			addr: string,
+		) =>
+			invoke<boolean>("redis_kill_client", {
+				uuid: connectionUuid,
+				node,
+				clientId,
+				addr,
+			}),
+
+		subscribe: (
+			connectionUuid: string,
+			source: RedisLiveSource,
+			bufferSize?: number,
+		) =>
+			invoke<RedisSubscriptionInfo>("redis_subscribe", {
+				uuid: connectionUuid,
+				source,
+				bufferSize,
+			}),
+
+		unsubscribe: (subscriptionId: string) =>
+			invoke<boolean>("redis_unsubscribe", { subscriptionId }),
 	},
 
 	queries: {
 		list: (connectionUuid: string) =>
-			invoke<SavedQuery[]>("get_saved_queries", { connectionUuid }),
+			invoke<SavedQuery[]>("get_saved_queries", {
+				connectionUuid: savedConnectionUuid(connectionUuid),
+			}),
 
 		create: (connectionUuid: string, data: SavedQueryFormData) =>
-			invoke<SavedQuery>("create_saved_query", { connectionUuid, data }),
+			invoke<SavedQuery>("create_saved_query", {
+				connectionUuid: savedConnectionUuid(connectionUuid),
+				data,
+			}),
 
 		update: (id: number, data: SavedQueryFormData) =>
 			invoke<SavedQuery>("update_saved_query", { id, data }),
@@ -764,7 +1028,9 @@ export const api = {
 		delete: (id: number) => invoke<boolean>("delete_saved_query", { id }),
 
 		history: (connectionUuid: string) =>
-			invoke<QueryHistory[]>("get_query_history", { connectionUuid }),
+			invoke<QueryHistory[]>("get_query_history", {
+				connectionUuid: savedConnectionUuid(connectionUuid),
+			}),
 
 		recordHistory: (args: {
 			connectionUuid: string;
@@ -774,18 +1040,30 @@ export const api = {
 			rowCount?: number | null;
 			rowsAffected?: number | null;
 			error?: string | null;
-		}) => invoke<void>("record_query_history", args),
+		}) =>
+			invoke<void>("record_query_history", {
+				...args,
+				connectionUuid: savedConnectionUuid(args.connectionUuid),
+			}),
 
 		clearHistory: (connectionUuid: string) =>
-			invoke<boolean>("clear_query_history", { connectionUuid }),
+			invoke<boolean>("clear_query_history", {
+				connectionUuid: savedConnectionUuid(connectionUuid),
+			}),
 	},
 
 	savedViews: {
 		list: (connectionUuid: string, tableName: string) =>
-			invoke<SavedView[]>("get_saved_views", { connectionUuid, tableName }),
+			invoke<SavedView[]>("get_saved_views", {
+				connectionUuid: savedConnectionUuid(connectionUuid),
+				tableName,
+			}),
 
 		create: (connectionUuid: string, data: SavedViewFormData) =>
-			invoke<SavedView>("create_saved_view", { connectionUuid, data }),
+			invoke<SavedView>("create_saved_view", {
+				connectionUuid: savedConnectionUuid(connectionUuid),
+				data,
+			}),
 
 		update: (id: number, data: SavedViewUpdateData) =>
 			invoke<SavedView>("update_saved_view", { id, data }),
@@ -812,6 +1090,26 @@ export const api = {
 			invoke<McpStatus>("mcp_set_enabled", { enabled }),
 
 		regenerateToken: () => invoke<McpStatus>("mcp_regenerate_token"),
+
+		listPendingApprovals: () =>
+			invoke<McpApprovalRequest[]>("mcp_list_pending_approvals"),
+
+		resolveApproval: (id: string, approved: boolean) =>
+			invoke<void>("mcp_resolve_approval", { id, approved }),
+
+		onApprovalRequested: (
+			handler: (request: McpApprovalRequest) => void,
+		): Promise<UnlistenFn> =>
+			listen<McpApprovalRequest>("mcp-approval-requested", (event) =>
+				handler(event.payload),
+			),
+
+		onApprovalResolved: (
+			handler: (resolution: McpApprovalResolution) => void,
+		): Promise<UnlistenFn> =>
+			listen<McpApprovalResolution>("mcp-approval-resolved", (event) =>
+				handler(event.payload),
+			),
 	},
 
 	pool: {
@@ -826,6 +1124,16 @@ export const api = {
 		healthCheck: (uuid: string) =>
 			invoke<TestConnectionResult>("pool_health_check", { uuid }),
 
+		getCapabilities: (uuid: string) =>
+			invoke<DriverCapabilities>("pool_get_capabilities", { uuid }),
+
+		listDatabases: (uuid: string) =>
+			invoke<string[]>("pool_list_databases", { uuid }),
+
+		/** Opens a session on another database; returns its pool key. */
+		openDatabase: (uuid: string, database: string) =>
+			invoke<string>("pool_open_database", { uuid, database }),
+
 		listTables: (uuid: string) =>
 			invoke<TableInfo[]>("pool_list_tables", { uuid }),
 
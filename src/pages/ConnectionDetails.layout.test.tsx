@@ -106,7 +106,10 @@ const tab: QueryTab = {
 	savedQueryId: null,
 	savedQueryName: null,
 	results: null,
+	resultColumns: [],
+	editTarget: null,
 	error: null,
+	errorOffset: null,
 	success: false,
 	executionTime: null,
 	affectedRows: null,
@@ -140,6 +143,7 @@ function renderQueryWorkspace() {
 		<QueryWorkspace
 			tab={tab}
 			connection={connection}
+			rowEditing={false}
 			tables={[]}
 			tableColumns={{}}
 			controller={controller}
@@ -1,4 +1,4 @@
-import { useNavigate, useParams } from "react-router-dom";
+import { useNavigate, useParams, useSearchParams } from "react-router-dom";
 import { RedisConnectionHeader } from "@/components/connection-details/ConnectionHeaders";
 import {
 	ConnectionOpeningScreen,
@@ -10,13 +10,19 @@ import { SqlConnectionWorkspace } from "@/components/connection-details/SqlConne
 import { useSettings } from "@/contexts/SettingsContext";
 import { useConnectionLifecycle } from "@/hooks/connection-details/useConnectionLifecycle";
 import { useNativeCloseListener } from "@/hooks/connection-details/useNativeCloseListener";
+import { databaseSessionKey } from "@/lib/databaseSessions";
 import { isSqlConnection } from "@/types/connection";
 
 export function ConnectionDetails() {
 	const { uuid } = useParams<{ uuid: string }>();
+	const [searchParams] = useSearchParams();
+	const database = searchParams.get("database");
 	const navigate = useNavigate();
 	const { openSettings } = useSettings();
-	const lifecycle = useConnectionLifecycle({ uuid, navigate });
+	const lifecycle = useConnectionLifecycle({
+		uuid: uuid && database ? databaseSessionKey(uuid, database) : uuid,
+		navigate,
+	});
 	const connection = lifecycle.connection.value;
 	const closeConnection = () => navigate("/");
 	const ready = lifecycle.opening.phase === "complete" && connection !== null;
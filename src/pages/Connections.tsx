@@ -220,6 +220,7 @@ export function Connections() {
 				ssh_use_key: connection.ssh_use_key
 					? Boolean(connection.ssh_use_key)
 					: undefined,
+				options: connection.options,
 			};
 
 			await api.connections.create(duplicatedData);
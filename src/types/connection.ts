@@ -28,6 +28,8 @@ export interface Connection {
 	ssh_password: string;
 	ssh_key_path: string;
 	ssh_use_key: number;
+	/** Engine-specific settings, e.g. D1's account and database ids. */
+	options?: Record<string, unknown>;
 	created_at: string;
 	updated_at: string;
 }
@@ -61,4 +63,6 @@ export type ConnectionFormData = {
 	ssh_password?: string;
 	ssh_key_path?: string;
 	ssh_use_key?: boolean;
+	/** Left out, an update keeps the stored options. */
+	options?: Record<string, unknown>;
 };
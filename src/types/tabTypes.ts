@@ -13,6 +13,8 @@ import type {
 	FunctionSummary,
 	RedisKeyDetails,
 	RedisKeyInfo,
+	ResultColumn,
+	ResultEditTarget,
 	SchemaOverview,
 	TableStructure,
 } from "@/lib/tauri";
@@ -76,8 +78,14 @@ export interface QueryTab extends BaseTab {
 	ai: QueryAiState;
 	savedQueryId: number | null;
 	savedQueryName: string | null;
-	results: Record<string, unknown>[] | null;
+	/** Result rows as arrays aligned to `resultColumns`. */
+	results: unknown[][] | null;
+	resultColumns: ResultColumn[];
+	/** Set when the result rows can be edited in their source table. */
+	editTarget: ResultEditTarget | null;
 	error: string | null;
+	/** Where in `query` the engine says `error` is, to underline it. */
+	errorOffset: number | null;
 	success: boolean;
 	executionTime: number | null;
 	affectedRows: number | null;
@@ -173,7 +181,10 @@ export function createQueryTab(
 		savedQueryId,
 		savedQueryName,
 		results: null,
+		resultColumns: [],
+		editTarget: null,
 		error: null,
+		errorOffset: null,
 		success: false,
 		executionTime: null,
 		affectedRows: null,